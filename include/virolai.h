@@ -0,0 +1,81 @@
+/* C bindings for virolai (`capi` feature). */
+
+#ifndef VIROLAI_H
+#define VIROLAI_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * A loaded problem: a block and the horizon to schedule it in.
+ */
+typedef struct VirolaiProblem VirolaiProblem;
+
+/**
+ * Message of the last failure on this thread, or `NULL` if none.
+ *
+ * The string is owned by the library and valid until the next failing call
+ * on the same thread; do not free it.
+ */
+const char *virolai_last_error(void);
+
+/**
+ * Loads a problem from a NUL-terminated UTF-8 JSON string.
+ *
+ * Returns `NULL` on malformed input. Free the problem with
+ * [`virolai_problem_free`].
+ *
+ * # Safety
+ *
+ * `json` must be `NULL` or point to a NUL-terminated string.
+ */
+struct VirolaiProblem *virolai_problem_from_json(const char *json);
+
+/**
+ * Number of tasks in `problem`, or 0 if it is `NULL`.
+ *
+ * # Safety
+ *
+ * `problem` must be `NULL` or returned by [`virolai_problem_from_json`] and
+ * not yet freed.
+ */
+size_t virolai_problem_task_count(const struct VirolaiProblem *problem);
+
+/**
+ * Schedules `problem` with the EST scheduler, prerequisites first, and
+ * returns the schedule as a JSON string.
+ *
+ * `endangered_threshold` is the scheduler's threshold; 1 is the default.
+ * Returns `NULL` if `problem` is `NULL` or its hard edges form a cycle.
+ * Free the string with [`virolai_string_free`].
+ *
+ * # Safety
+ *
+ * `problem` must be `NULL` or returned by [`virolai_problem_from_json`] and
+ * not yet freed.
+ */
+char *virolai_schedule(const struct VirolaiProblem *problem, uint32_t endangered_threshold);
+
+/**
+ * Frees a problem. `NULL` is ignored.
+ *
+ * # Safety
+ *
+ * `problem` must be `NULL` or returned by [`virolai_problem_from_json`] and
+ * not yet freed.
+ */
+void virolai_problem_free(struct VirolaiProblem *problem);
+
+/**
+ * Frees a string returned by the library. `NULL` is ignored.
+ *
+ * # Safety
+ *
+ * `s` must be `NULL` or returned by [`virolai_schedule`] and not yet
+ * freed.
+ */
+void virolai_string_free(char *s);
+
+#endif  /* VIROLAI_H */
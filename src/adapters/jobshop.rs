@@ -0,0 +1,528 @@
+//! Job-shop instances.
+//!
+//! A job-shop instance has `n` jobs and `m` machines; each job is a chain of
+//! operations, each running on one machine for a fixed time. The adapter
+//! maps it onto the crate's model:
+//!
+//! - every operation is an [`Operation`] task with ID `"j<job>.o<index>"`;
+//! - every job is a [`SchedulingBlock`] whose operations are chained with
+//!   [`DynConstraintKind::Consecutive`] edges;
+//! - every machine is a resource `"m<machine>"` of a [`ResourcePool`], and
+//!   each operation is compatible only with its own machine, so operations
+//!   on one machine never overlap.
+//!
+//! Jobs, operations and machines are numbered from 0. Instances are built
+//! by hand or read with [`JobShop::parse_taillard`] and
+//! [`JobShop::parse_standard`]. [`JobShop::schedule_greedy`] gives a quick
+//! feasible schedule; [`JobShop::violations`] checks any pool against the
+//! instance.
+
+use std::collections::HashMap;
+
+use qtty::{Quantity, Unit};
+use thiserror::Error;
+
+use crate::constraints::{DynConstraintKind, IntervalConstraint};
+use crate::schedule::ResourcePool;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum JobShopError {
+    #[error("Malformed job-shop instance: {0}")]
+    Malformed(String),
+
+    #[error("Job {job} uses machine {machine}, but the instance has {machines} machines")]
+    MachineOutOfRange {
+        job: usize,
+        machine: usize,
+        machines: usize,
+    },
+}
+
+/// One operation of a job.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operation<U: Unit> {
+    id: Id,
+    pub job: usize,
+    /// Position within the job.
+    pub index: usize,
+    pub machine: usize,
+    pub duration: Quantity<U>,
+}
+
+impl<U: Unit> Operation<U> {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl<U: Unit + Send + Sync> Task<U> for Operation<U> {
+    type SizeUnit = U;
+    type ConstraintLeaf = IntervalConstraint<U>;
+
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn size(&self) -> Quantity<U> {
+        self.duration
+    }
+}
+
+/// Something wrong with a pool as a solution of a [`JobShop`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobShopViolation {
+    /// The operation is not placed on its machine.
+    Missing(Id),
+    /// `after` starts before `before`, its predecessor in the job, ends.
+    Precedence { before: Id, after: Id },
+}
+
+/// A job-shop instance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JobShop<U: Unit> {
+    machines: usize,
+    /// Per job, `(machine, duration)` in processing order.
+    jobs: Vec<Vec<(usize, Quantity<U>)>>,
+}
+
+/// ID of operation `index` of job `job`.
+pub fn operation_id(job: usize, index: usize) -> Id {
+    format!("j{job}.o{index}")
+}
+
+/// Resource ID of machine `machine`.
+pub fn machine_id(machine: usize) -> Id {
+    format!("m{machine}")
+}
+
+impl<U: Unit> JobShop<U> {
+    /// An instance with `machines` machines and no jobs.
+    pub fn new(machines: usize) -> Self {
+        Self {
+            machines,
+            jobs: Vec::new(),
+        }
+    }
+
+    /// Appends a job given as `(machine, duration)` pairs in processing
+    /// order.
+    pub fn with_job(
+        mut self,
+        operations: impl IntoIterator<Item = (usize, Quantity<U>)>,
+    ) -> Result<Self, JobShopError> {
+        self.add_job(operations)?;
+        Ok(self)
+    }
+
+    /// Appends a job and returns its index.
+    pub fn add_job(
+        &mut self,
+        operations: impl IntoIterator<Item = (usize, Quantity<U>)>,
+    ) -> Result<usize, JobShopError> {
+        let job = self.jobs.len();
+        let operations: Vec<_> = operations.into_iter().collect();
+        if let Some(&(machine, _)) = operations.iter().find(|(m, _)| *m >= self.machines) {
+            return Err(JobShopError::MachineOutOfRange {
+                job,
+                machine,
+                machines: self.machines,
+            });
+        }
+        self.jobs.push(operations);
+        Ok(job)
+    }
+
+    /// Reads Taillard's format: a header line, a line starting with the job
+    /// and machine counts, then a `Times` and a `Machines` section with one
+    /// row per job. Machine numbers in the file start at 1.
+    ///
+    /// ```text
+    /// Nb of jobs, Nb of Machines, Time seed, Machine seed, Upper bound, Lower bound
+    ///           2           2   840612802   398197754          11          11
+    /// Times
+    ///  3 4
+    ///  5 2
+    /// Machines
+    ///  1 2
+    ///  2 1
+    /// ```
+    pub fn parse_taillard(text: &str) -> Result<Self, JobShopError> {
+        let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+        let sizes = lines
+            .by_ref()
+            .find(|l| l.starts_with(|c: char| c.is_ascii_digit()))
+            .ok_or_else(|| malformed("missing job and machine counts"))?;
+        let sizes = numbers(sizes)?;
+        let (n, m) = match sizes.as_slice() {
+            [n, m, ..] => (*n as usize, *m as usize),
+            _ => return Err(malformed("missing job and machine counts")),
+        };
+
+        let mut section = |name: &str| -> Result<Vec<Vec<f64>>, JobShopError> {
+            let header = lines
+                .next()
+                .ok_or_else(|| malformed(format!("missing {name} section")))?;
+            if !header.eq_ignore_ascii_case(name) {
+                return Err(malformed(format!("expected {name}, found {header:?}")));
+            }
+            (0..n)
+                .map(|row| {
+                    let line = lines
+                        .next()
+                        .ok_or_else(|| malformed(format!("{name} has fewer than {n} rows")))?;
+                    let values = numbers(line)?;
+                    if values.len() != m {
+                        return Err(malformed(format!(
+                            "{name} row {row} has {} values, expected {m}",
+                            values.len()
+                        )));
+                    }
+                    Ok(values)
+                })
+                .collect()
+        };
+        let times = section("Times")?;
+        let machines = section("Machines")?;
+
+        let mut shop = Self::new(m);
+        for (row, (times, machines)) in times.iter().zip(&machines).enumerate() {
+            let ops = machines
+                .iter()
+                .zip(times)
+                .map(|(&machine, &time)| {
+                    if machine < 1.0 {
+                        return Err(malformed(format!("job {row} uses machine {machine}")));
+                    }
+                    Ok((machine as usize - 1, Quantity::new(time)))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            shop.add_job(ops)?;
+        }
+        Ok(shop)
+    }
+
+    /// Reads the OR-Library format: the job and machine counts, then one
+    /// row per job of `machine duration` pairs. Machine numbers start at 0;
+    /// lines starting with `#` are comments.
+    ///
+    /// ```text
+    /// 2 2
+    /// 0 3 1 4
+    /// 1 5 0 2
+    /// ```
+    pub fn parse_standard(text: &str) -> Result<Self, JobShopError> {
+        let mut lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'));
+        let sizes = numbers(lines.next().ok_or_else(|| malformed("empty instance"))?)?;
+        let (n, m) = match sizes.as_slice() {
+            [n, m, ..] => (*n as usize, *m as usize),
+            _ => return Err(malformed("missing job and machine counts")),
+        };
+
+        let mut shop = Self::new(m);
+        for job in 0..n {
+            let line = lines
+                .next()
+                .ok_or_else(|| malformed(format!("expected {n} jobs, found {job}")))?;
+            let values = numbers(line)?;
+            if values.len() % 2 != 0 {
+                return Err(malformed(format!("job {job} has an odd number of values")));
+            }
+            shop.add_job(
+                values
+                    .chunks(2)
+                    .map(|pair| (pair[0] as usize, Quantity::new(pair[1]))),
+            )?;
+        }
+        Ok(shop)
+    }
+
+    pub fn job_count(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn machine_count(&self) -> usize {
+        self.machines
+    }
+
+    pub fn operation_count(&self) -> usize {
+        self.jobs.iter().map(Vec::len).sum()
+    }
+
+    /// Operations of `job`, in processing order.
+    pub fn operations(&self, job: usize) -> Vec<Operation<U>> {
+        self.jobs
+            .get(job)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, &(machine, duration))| Operation {
+                id: operation_id(job, index),
+                job,
+                index,
+                machine,
+                duration,
+            })
+            .collect()
+    }
+
+    /// Sum of all durations on the busiest machine or in the longest job —
+    /// a lower bound on the makespan.
+    pub fn lower_bound(&self) -> Quantity<U> {
+        let mut load = vec![0.0; self.machines];
+        let mut longest: f64 = 0.0;
+        for ops in &self.jobs {
+            let mut length = 0.0;
+            for &(machine, duration) in ops {
+                load[machine] += duration.value();
+                length += duration.value();
+            }
+            longest = longest.max(length);
+        }
+        Quantity::new(load.into_iter().fold(longest, f64::max))
+    }
+
+    /// A pool with one resource per machine, each operation compatible only
+    /// with its machine.
+    pub fn resource_pool(&self) -> ResourcePool<U> {
+        let mut pool = ResourcePool::new();
+        for machine in 0..self.machines {
+            pool.add_resource(machine_id(machine));
+        }
+        for job in 0..self.jobs.len() {
+            for op in self.operations(job) {
+                pool.set_compatibility(op.id, [machine_id(op.machine)]);
+            }
+        }
+        pool
+    }
+
+    /// Per-machine solution spaces where each operation may run anywhere in
+    /// `horizon` on its own machine.
+    pub fn resource_spaces(&self, horizon: Interval<U>) -> HashMap<Id, SolutionSpace<U>> {
+        let mut spaces: HashMap<Id, SolutionSpace<U>> = (0..self.machines)
+            .map(|m| (machine_id(m), SolutionSpace::new()))
+            .collect();
+        for job in 0..self.jobs.len() {
+            for op in self.operations(job) {
+                if let Some(space) = spaces.get_mut(&machine_id(op.machine)) {
+                    space.add_interval(op.id, horizon);
+                }
+            }
+        }
+        spaces
+    }
+}
+
+impl<U: Unit + Send + Sync> JobShop<U> {
+    /// One block per job, its operations chained by `Consecutive` edges.
+    pub fn to_blocks(&self) -> Vec<SchedulingBlock<Operation<U>, U, DynConstraintKind>> {
+        (0..self.jobs.len())
+            .map(|job| {
+                let mut block = SchedulingBlock::new();
+                let mut previous = None;
+                for op in self.operations(job) {
+                    let id = op.id.clone();
+                    block
+                        .add_task_with_id(op, Some(id.clone()))
+                        .expect("operation IDs are unique within a job");
+                    let node = block.node_of(&id).expect("just added");
+                    if let Some(prev) = previous {
+                        block
+                            .add_dependency(prev, node, DynConstraintKind::Consecutive)
+                            .expect("a chain has no cycles");
+                    }
+                    previous = Some(node);
+                }
+                block
+            })
+            .collect()
+    }
+
+    /// Dispatches operations one at a time, always the ready operation that
+    /// can start earliest (ties: shorter first, then lower job), starting
+    /// at `start`. Yields a feasible, usually not optimal, schedule.
+    pub fn schedule_greedy(&self, start: Quantity<U>) -> ResourcePool<U> {
+        let mut pool = self.resource_pool();
+        let mut next = vec![0; self.jobs.len()];
+        let mut job_ready = vec![start.value(); self.jobs.len()];
+        let mut machine_free = vec![start.value(); self.machines];
+
+        loop {
+            let best = (0..self.jobs.len())
+                .filter_map(|job| {
+                    let &(machine, duration) = self.jobs[job].get(next[job])?;
+                    let est = job_ready[job].max(machine_free[machine]);
+                    Some((est, duration.value(), job, machine))
+                })
+                .min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.total_cmp(&b.1)));
+            let Some((est, duration, job, machine)) = best else {
+                break;
+            };
+            let end = est + duration;
+            let interval = Interval::new(Quantity::new(est), Quantity::new(end));
+            pool.add(operation_id(job, next[job]), &machine_id(machine), interval)
+                .expect("machine is free from its cursor on");
+            job_ready[job] = end;
+            machine_free[machine] = end;
+            next[job] += 1;
+        }
+        pool
+    }
+
+    /// End of the last operation in `pool`, measured from `start`.
+    pub fn makespan(pool: &ResourcePool<U>, start: Quantity<U>) -> Quantity<U> {
+        pool.schedules()
+            .values()
+            .filter_map(|s| s.latest_end())
+            .fold(start, |acc, end| if end > acc { end } else { acc })
+            - start
+    }
+
+    /// Checks that every operation sits on its machine and starts after its
+    /// predecessor in the job ends. Machine overlaps cannot occur in a pool.
+    pub fn violations(&self, pool: &ResourcePool<U>) -> Vec<JobShopViolation> {
+        let mut violations = Vec::new();
+        for job in 0..self.jobs.len() {
+            let mut previous: Option<(Id, Interval<U>)> = None;
+            for op in self.operations(job) {
+                let placed = pool
+                    .schedule(&machine_id(op.machine))
+                    .and_then(|s| s.get_interval(op.id()));
+                let Some(interval) = placed else {
+                    violations.push(JobShopViolation::Missing(op.id));
+                    previous = None;
+                    continue;
+                };
+                if let Some((before, prev)) = previous.take() {
+                    if interval.start().value() < prev.end().value() {
+                        violations.push(JobShopViolation::Precedence {
+                            before,
+                            after: op.id.clone(),
+                        });
+                    }
+                }
+                previous = Some((op.id, interval));
+            }
+        }
+        violations
+    }
+}
+
+fn malformed(message: impl Into<String>) -> JobShopError {
+    JobShopError::Malformed(message.into())
+}
+
+/// Parses whitespace-separated numbers.
+fn numbers(line: &str) -> Result<Vec<f64>, JobShopError> {
+    line.split_whitespace()
+        .map(|t| {
+            t.parse::<f64>()
+                .map_err(|_| malformed(format!("not a number: {t:?}")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    const TAILLARD: &str = "\
+Nb of jobs, Nb of Machines, Time seed, Machine seed, Upper bound, Lower bound
+          3           2   840612802   398197754          11          11
+Times
+ 3 4
+ 5 2
+ 2 2
+Machines
+ 1 2
+ 2 1
+ 1 2
+";
+
+    fn shop() -> JobShop<Second> {
+        JobShop::parse_taillard(TAILLARD).unwrap()
+    }
+
+    #[test]
+    fn taillard_and_standard_agree() {
+        let standard = JobShop::parse_standard("# tiny\n3 2\n0 3 1 4\n1 5 0 2\n0 2 1 2\n");
+        assert_eq!(standard.unwrap(), shop());
+        let s = shop();
+        assert_eq!((s.job_count(), s.machine_count()), (3, 2));
+        assert_eq!(s.operation_count(), 6);
+        assert_eq!(s.lower_bound(), q(11.0));
+    }
+
+    #[test]
+    fn rejects_malformed_instances() {
+        assert!(matches!(
+            JobShop::<Second>::parse_standard("1 1\n3 4\n"),
+            Err(JobShopError::MachineOutOfRange { machine: 3, .. })
+        ));
+        assert!(JobShop::<Second>::parse_standard("2 1\n0 4\n").is_err());
+        let short = TAILLARD.replace(" 2 2\nMachines", "Machines");
+        assert!(JobShop::<Second>::parse_taillard(&short).is_err());
+    }
+
+    #[test]
+    fn blocks_chain_each_job() {
+        let blocks = shop().to_blocks();
+        assert_eq!(blocks.len(), 3);
+        let job1 = &blocks[1];
+        let (a, b) = (
+            job1.node_of("j1.o0").unwrap(),
+            job1.node_of("j1.o1").unwrap(),
+        );
+        let edge = job1.graph().find_edge(a, b).unwrap();
+        assert_eq!(job1.graph()[edge], DynConstraintKind::Consecutive);
+        let op = job1.task_by_id("j1.o0").unwrap();
+        assert_eq!((op.machine, op.duration), (1, q(5.0)));
+
+        let pool = shop().resource_pool();
+        assert_eq!(pool.compatible_resources("j1.o0"), vec!["m1"]);
+        let spaces = shop().resource_spaces(iv(0.0, 50.0));
+        assert!(spaces["m0"].get_intervals("j0.o0").is_some());
+        assert!(spaces["m1"].get_intervals("j0.o0").is_none());
+    }
+
+    #[test]
+    fn greedy_schedule_is_feasible() {
+        let s = shop();
+        let pool = s.schedule_greedy(q(0.0));
+        assert!(s.violations(&pool).is_empty());
+        assert_eq!(pool.task_count(), 6);
+        let makespan = JobShop::makespan(&pool, q(0.0));
+        assert!(makespan >= s.lower_bound());
+        // j2.o0 (2) beats j0.o0 (3) on m0 at t = 0; j1.o0 takes m1.
+        assert_eq!(
+            pool.schedule("m0").unwrap().get_interval("j2.o0"),
+            Some(iv(0.0, 2.0))
+        );
+        assert_eq!(
+            pool.schedule("m1").unwrap().get_interval("j1.o0"),
+            Some(iv(0.0, 5.0))
+        );
+    }
+
+    #[test]
+    fn violations_flag_order_and_gaps() {
+        let s = shop();
+        let mut pool = s.resource_pool();
+        pool.add("j0.o0", "m0", iv(5.0, 8.0)).unwrap();
+        pool.add("j0.o1", "m1", iv(6.0, 10.0)).unwrap();
+        let violations = s.violations(&pool);
+        assert!(violations.contains(&JobShopViolation::Precedence {
+            before: "j0.o0".into(),
+            after: "j0.o1".into()
+        }));
+        assert!(violations.contains(&JobShopViolation::Missing("j1.o0".into())));
+    }
+}
@@ -0,0 +1,9 @@
+//! Adapters from classic problem families to the crate's model.
+
+pub mod jobshop;
+pub mod timetabling;
+
+pub use jobshop::{JobShop, JobShopError, JobShopViolation, Operation};
+pub use timetabling::{
+    Event, Room, Timetable, TimetableError, TimetableSchedule, TimetableViolation,
+};
@@ -0,0 +1,538 @@
+//! Exam and course timetabling.
+//!
+//! A timetabling instance has events (exams, lectures) with a duration, an
+//! attendance and optionally the times they may run; rooms with a capacity;
+//! and student conflicts — pairs of events sharing students, which must not
+//! overlap in time even in different rooms. The adapter maps it onto the
+//! crate's model:
+//!
+//! - every event is an [`Event`] task whose constraint tree is the union of
+//!   its availability windows;
+//! - every room is a resource lane of a [`ResourcePool`], and an event is
+//!   compatible with the rooms that can seat its attendance;
+//! - student conflicts are kept as weighted pairs and enforced across lanes
+//!   by [`Timetable::schedule_greedy`] and checked by
+//!   [`Timetable::violations`].
+//!
+//! [`Timetable::parse_toronto`] reads the Toronto (Carter) exam benchmark.
+//! Those instances carry no rooms; add one large enough with
+//! [`Timetable::add_room`] to model the uncapacitated problem.
+
+use std::collections::{BTreeMap, HashMap};
+
+use qtty::{Quantity, Unit};
+use thiserror::Error;
+
+use crate::constraints::{Constraint, ConstraintExpr, DynConstraintKind, IntervalConstraint};
+use crate::schedule::ResourcePool;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::Interval;
+use crate::Id;
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum TimetableError {
+    #[error("Malformed timetabling instance: {0}")]
+    Malformed(String),
+
+    #[error("Event ID already exists: {0}")]
+    DuplicateEvent(Id),
+
+    #[error("Unknown event ID: {0}")]
+    UnknownEvent(Id),
+}
+
+/// One event to timetable.
+#[derive(Debug, Clone)]
+pub struct Event<U: Unit + Send + Sync> {
+    id: Id,
+    /// Number of attendees; rooms must seat them all.
+    pub attendance: usize,
+    pub duration: Quantity<U>,
+    availability: Option<ConstraintExpr<IntervalConstraint<U>>>,
+}
+
+impl<U: Unit + Send + Sync> Event<U> {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl<U: Unit + Send + Sync> Task<U> for Event<U> {
+    type SizeUnit = U;
+    type ConstraintLeaf = IntervalConstraint<U>;
+
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn size(&self) -> Quantity<U> {
+        self.duration
+    }
+
+    /// Availability windows; `None` when the event may run at any time.
+    fn constraints(&self) -> Option<&ConstraintExpr<IntervalConstraint<U>>> {
+        self.availability.as_ref()
+    }
+}
+
+/// A room and how many attendees it seats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Room {
+    pub id: Id,
+    pub capacity: usize,
+}
+
+/// Something wrong with a pool as a solution of a [`Timetable`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimetableViolation {
+    /// The event is not placed in any room.
+    Missing(Id),
+    /// Two events sharing students overlap in time.
+    StudentConflict {
+        first: Id,
+        second: Id,
+        students: usize,
+    },
+    /// The room seats fewer than the event's attendance.
+    Capacity { event: Id, room: Id },
+    /// The event runs outside its availability.
+    Unavailable(Id),
+}
+
+/// Result of [`Timetable::schedule_greedy`].
+#[derive(Debug, Clone)]
+pub struct TimetableSchedule<U: Unit> {
+    /// One lane per room.
+    pub pool: ResourcePool<U>,
+    /// Events no period could take, in placement order.
+    pub unplaced: Vec<Id>,
+}
+
+/// A timetabling instance.
+#[derive(Debug, Clone)]
+pub struct Timetable<U: Unit + Send + Sync> {
+    events: Vec<Event<U>>,
+    index: HashMap<Id, usize>,
+    rooms: Vec<Room>,
+    /// Shared students per pair of event indices, smaller index first.
+    conflicts: BTreeMap<(usize, usize), usize>,
+}
+
+impl<U: Unit + Send + Sync> Default for Timetable<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit + Send + Sync> Timetable<U> {
+    pub fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            index: HashMap::new(),
+            rooms: Vec::new(),
+            conflicts: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an event that may run at any time.
+    pub fn add_event(
+        &mut self,
+        id: impl Into<Id>,
+        attendance: usize,
+        duration: Quantity<U>,
+    ) -> Result<(), TimetableError> {
+        let id = id.into();
+        if self.index.contains_key(&id) {
+            return Err(TimetableError::DuplicateEvent(id));
+        }
+        self.index.insert(id.clone(), self.events.len());
+        self.events.push(Event {
+            id,
+            attendance,
+            duration,
+            availability: None,
+        });
+        Ok(())
+    }
+
+    /// Restricts `event` to `windows`.
+    pub fn set_availability(
+        &mut self,
+        event: &str,
+        windows: impl IntoIterator<Item = Interval<U>>,
+    ) -> Result<(), TimetableError> {
+        let i = self.event_index(event)?;
+        let leaves = windows
+            .into_iter()
+            .map(|w| ConstraintExpr::leaf(IntervalConstraint::new(w)))
+            .collect();
+        self.events[i].availability = Some(ConstraintExpr::union(leaves));
+        Ok(())
+    }
+
+    pub fn add_room(&mut self, id: impl Into<Id>, capacity: usize) {
+        self.rooms.push(Room {
+            id: id.into(),
+            capacity,
+        });
+    }
+
+    /// Records `students` students attending both `a` and `b`.
+    pub fn add_conflict(
+        &mut self,
+        a: &str,
+        b: &str,
+        students: usize,
+    ) -> Result<(), TimetableError> {
+        let (a, b) = (self.event_index(a)?, self.event_index(b)?);
+        if a != b && students > 0 {
+            *self.conflicts.entry((a.min(b), a.max(b))).or_insert(0) += students;
+        }
+        Ok(())
+    }
+
+    /// Records one student attending every event of `events`.
+    pub fn add_student(&mut self, events: &[&str]) -> Result<(), TimetableError> {
+        for (i, a) in events.iter().enumerate() {
+            for b in &events[i + 1..] {
+                self.add_conflict(a, b, 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a Toronto (Carter) instance: `crs` lists `event attendance`
+    /// per line, `stu` the events of one student per line. Every event
+    /// lasts `slot_length`.
+    ///
+    /// ```text
+    /// crs:  0001 2      stu:  0001 0002
+    ///       0002 1            0001
+    /// ```
+    pub fn parse_toronto(
+        crs: &str,
+        stu: &str,
+        slot_length: Quantity<U>,
+    ) -> Result<Self, TimetableError> {
+        let mut timetable = Self::new();
+        for line in crs.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let mut fields = line.split_whitespace();
+            let (Some(id), Some(attendance), None) = (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(malformed(format!(
+                    "expected \"event attendance\", got {line:?}"
+                )));
+            };
+            let attendance = attendance
+                .parse()
+                .map_err(|_| malformed(format!("not an attendance: {attendance:?}")))?;
+            timetable.add_event(id, attendance, slot_length)?;
+        }
+        for line in stu.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let events: Vec<&str> = line.split_whitespace().collect();
+            timetable.add_student(&events)?;
+        }
+        Ok(timetable)
+    }
+
+    pub fn events(&self) -> &[Event<U>] {
+        &self.events
+    }
+
+    pub fn event(&self, id: &str) -> Option<&Event<U>> {
+        self.index.get(id).map(|&i| &self.events[i])
+    }
+
+    pub fn rooms(&self) -> &[Room] {
+        &self.rooms
+    }
+
+    /// Conflicting pairs with their shared students, sorted by insertion
+    /// order of the events.
+    pub fn conflicts(&self) -> impl Iterator<Item = (&str, &str, usize)> + '_ {
+        self.conflicts
+            .iter()
+            .map(|(&(a, b), &n)| (self.events[a].id(), self.events[b].id(), n))
+    }
+
+    /// Number of events `event` conflicts with.
+    pub fn conflict_degree(&self, event: &str) -> usize {
+        self.index.get(event).map_or(0, |&i| {
+            self.conflicts
+                .keys()
+                .filter(|&&(a, b)| a == i || b == i)
+                .count()
+        })
+    }
+
+    /// Fraction of event pairs in conflict.
+    pub fn conflict_density(&self) -> f64 {
+        let n = self.events.len();
+        if n < 2 {
+            return 0.0;
+        }
+        self.conflicts.len() as f64 / (n * (n - 1) / 2) as f64
+    }
+
+    /// A pool with one lane per room; each event is compatible with the
+    /// rooms that seat its attendance.
+    pub fn resource_pool(&self) -> ResourcePool<U> {
+        let mut pool = ResourcePool::new();
+        for room in &self.rooms {
+            pool.add_resource(room.id.clone());
+        }
+        for event in &self.events {
+            let rooms = self
+                .rooms
+                .iter()
+                .filter(|r| r.capacity >= event.attendance)
+                .map(|r| r.id.clone());
+            pool.set_compatibility(event.id.clone(), rooms);
+        }
+        pool
+    }
+
+    fn event_index(&self, id: &str) -> Result<usize, TimetableError> {
+        self.index
+            .get(id)
+            .copied()
+            .ok_or_else(|| TimetableError::UnknownEvent(id.to_string()))
+    }
+
+    /// Neighbours of event `i` in the conflict graph, with shared students.
+    fn neighbours(&self, i: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.conflicts.iter().filter_map(move |(&(a, b), &n)| {
+            if a == i {
+                Some((b, n))
+            } else if b == i {
+                Some((a, n))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// One block holding every event; student conflicts are not edges (they
+    /// forbid overlap, not order).
+    pub fn to_block(&self) -> SchedulingBlock<Event<U>, U, DynConstraintKind> {
+        let mut block = SchedulingBlock::new();
+        for event in &self.events {
+            block
+                .add_task_with_id(event.clone(), Some(event.id.clone()))
+                .expect("event IDs are unique");
+        }
+        block
+    }
+
+    /// Places events into `periods`, most conflicted first (then largest
+    /// attendance, then insertion order).
+    ///
+    /// Each event starts at the beginning of the first period that fits its
+    /// duration and availability, has no overlapping conflicting event, and
+    /// has a free room seating its attendance; the smallest such room is
+    /// used. Events with no such period are reported as unplaced.
+    pub fn schedule_greedy(&self, periods: &[Interval<U>]) -> TimetableSchedule<U> {
+        let mut pool = self.resource_pool();
+        let mut placed: Vec<Option<Interval<U>>> = vec![None; self.events.len()];
+        let mut unplaced = Vec::new();
+
+        let mut rooms: Vec<&Room> = self.rooms.iter().collect();
+        rooms.sort_by_key(|r| (r.capacity, r.id.clone()));
+
+        let mut order: Vec<usize> = (0..self.events.len()).collect();
+        let degrees: Vec<usize> = order.iter().map(|&i| self.neighbours(i).count()).collect();
+        order.sort_by_key(|&i| {
+            (
+                std::cmp::Reverse(degrees[i]),
+                std::cmp::Reverse(self.events[i].attendance),
+                i,
+            )
+        });
+
+        for i in order {
+            let event = &self.events[i];
+            let slot = periods.iter().find_map(|period| {
+                let slot = Interval::new(period.start(), period.start() + event.duration);
+                if slot.end().value() > period.end().value() || !self.available(event, slot) {
+                    return None;
+                }
+                let clash = self
+                    .neighbours(i)
+                    .any(|(j, _)| placed[j].is_some_and(|p| p.overlaps(&slot)));
+                if clash {
+                    return None;
+                }
+                let room = rooms.iter().find(|r| {
+                    r.capacity >= event.attendance
+                        && pool
+                            .schedule(&r.id)
+                            .is_some_and(|s| s.is_free(slot).unwrap_or(false))
+                })?;
+                Some((slot, room.id.clone()))
+            });
+            match slot {
+                Some((slot, room)) => {
+                    pool.add(event.id.clone(), &room, slot)
+                        .expect("room is free and seats the event");
+                    placed[i] = Some(slot);
+                }
+                None => unplaced.push(event.id.clone()),
+            }
+        }
+        TimetableSchedule { pool, unplaced }
+    }
+
+    /// Checks `pool` against the instance: every event placed once, in a
+    /// room that seats it, within its availability, and no two conflicting
+    /// events overlapping.
+    pub fn violations(&self, pool: &ResourcePool<U>) -> Vec<TimetableViolation> {
+        let mut violations = Vec::new();
+        let mut placed: Vec<Option<Interval<U>>> = vec![None; self.events.len()];
+        for (i, event) in self.events.iter().enumerate() {
+            let Some((room, interval)) = pool.placements(&event.id).into_iter().next() else {
+                violations.push(TimetableViolation::Missing(event.id.clone()));
+                continue;
+            };
+            placed[i] = Some(interval);
+            let seats = self
+                .rooms
+                .iter()
+                .find(|r| r.id == room)
+                .map_or(0, |r| r.capacity);
+            if seats < event.attendance {
+                violations.push(TimetableViolation::Capacity {
+                    event: event.id.clone(),
+                    room: room.to_string(),
+                });
+            }
+            if !self.available(event, interval) {
+                violations.push(TimetableViolation::Unavailable(event.id.clone()));
+            }
+        }
+        for (&(a, b), &students) in &self.conflicts {
+            if let (Some(x), Some(y)) = (placed[a], placed[b]) {
+                if x.overlaps(&y) {
+                    violations.push(TimetableViolation::StudentConflict {
+                        first: self.events[a].id.clone(),
+                        second: self.events[b].id.clone(),
+                        students,
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// Whether `slot` lies inside one of `event`'s availability windows.
+    fn available(&self, event: &Event<U>, slot: Interval<U>) -> bool {
+        event.constraints().is_none_or(|tree| {
+            tree.compute_intervals(slot).iter().any(|w| {
+                w.start().value() <= slot.start().value() && slot.end().value() <= w.end().value()
+            })
+        })
+    }
+}
+
+fn malformed(message: impl Into<String>) -> TimetableError {
+    TimetableError::Malformed(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    const CRS: &str = "0001 30\n0002 20\n0003 10\n0004 5\n";
+    const STU: &str = "0001 0002\n0001 0002 0003\n0003 0004\n";
+
+    fn periods() -> Vec<Interval<Second>> {
+        vec![iv(0.0, 10.0), iv(10.0, 20.0), iv(20.0, 30.0)]
+    }
+
+    fn instance() -> Timetable<Second> {
+        let mut t = Timetable::parse_toronto(CRS, STU, q(10.0)).unwrap();
+        t.add_room("big", 40);
+        t.add_room("small", 15);
+        t
+    }
+
+    #[test]
+    fn toronto_builds_weighted_conflicts() {
+        let t = instance();
+        assert_eq!(t.events().len(), 4);
+        assert_eq!(t.event("0002").unwrap().attendance, 20);
+        let conflicts: Vec<_> = t.conflicts().collect();
+        assert_eq!(
+            conflicts,
+            vec![
+                ("0001", "0002", 2),
+                ("0001", "0003", 1),
+                ("0002", "0003", 1),
+                ("0003", "0004", 1),
+            ]
+        );
+        assert_eq!(t.conflict_degree("0003"), 3);
+        assert!((t.conflict_density() - 4.0 / 6.0).abs() < 1e-12);
+        assert!(Timetable::<Second>::parse_toronto("0001", "", q(1.0)).is_err());
+        assert_eq!(
+            Timetable::<Second>::parse_toronto(CRS, "0001 0009", q(1.0)).err(),
+            Some(TimetableError::UnknownEvent("0009".into()))
+        );
+    }
+
+    #[test]
+    fn pool_and_block_follow_rooms() {
+        let t = instance();
+        let pool = t.resource_pool();
+        assert_eq!(pool.compatible_resources("0001"), vec!["big"]);
+        assert_eq!(pool.compatible_resources("0004").len(), 2);
+        assert_eq!(t.to_block().task_count(), 4);
+    }
+
+    #[test]
+    fn greedy_separates_conflicting_events() {
+        let t = instance();
+        let result = t.schedule_greedy(&periods());
+        assert!(result.unplaced.is_empty());
+        assert!(t.violations(&result.pool).is_empty());
+        // 0001, 0002 and 0003 form a triangle: three distinct periods.
+        let start = |id: &str| result.pool.placements(id)[0].1.start().value();
+        let mut starts = vec![start("0001"), start("0002"), start("0003")];
+        starts.sort_by(f64::total_cmp);
+        assert_eq!(starts, vec![0.0, 10.0, 20.0]);
+        // 0004 does not conflict with 0001 and shares its period, in the small room.
+        assert_eq!(result.pool.resource_of("0004"), Some("small"));
+    }
+
+    #[test]
+    fn availability_and_capacity_limit_placement() {
+        let mut t = instance();
+        t.set_availability("0004", [iv(20.0, 30.0)]).unwrap();
+        t.add_event("huge", 100, q(10.0)).unwrap();
+        let result = t.schedule_greedy(&periods());
+        assert_eq!(result.unplaced, vec!["huge".to_string()]);
+        assert_eq!(result.pool.placements("0004")[0].1, iv(20.0, 30.0));
+        assert_eq!(
+            t.violations(&result.pool),
+            vec![TimetableViolation::Missing("huge".into())]
+        );
+    }
+
+    #[test]
+    fn violations_flag_conflicts() {
+        let t = instance();
+        let mut pool = t.resource_pool();
+        pool.add("0001", "big", iv(0.0, 10.0)).unwrap();
+        pool.add("0002", "big", iv(10.0, 20.0)).unwrap();
+        pool.add("0003", "small", iv(5.0, 15.0)).unwrap();
+        pool.add("0004", "small", iv(20.0, 30.0)).unwrap();
+        let violations = t.violations(&pool);
+        assert_eq!(violations.len(), 2);
+        assert!(violations.contains(&TimetableViolation::StudentConflict {
+            first: "0001".into(),
+            second: "0003".into(),
+            students: 1,
+        }));
+    }
+}
@@ -0,0 +1,268 @@
+//! Initial resource assignment by greedy graph colouring.
+//!
+//! Multi-resource schedulers decide both where and when each task runs.
+//! Starting them from a random or naive assignment wastes effort on pairs of
+//! tasks that can never share a resource. [`greedy_colouring`] computes an
+//! assignment up front:
+//!
+//! 1. Build the **conflict graph**: tasks `a` and `b` conflict on resource
+//!    `r` if both may use `r`, their windows on `r` overlap, and the union of
+//!    those windows is shorter than `size(a) + size(b)` — on `r` they cannot
+//!    both run.
+//! 2. Colour it greedily with resources as colours (Welsh–Powell order: most
+//!    conflicts first, then fewest compatible resources, then ID). Each task
+//!    takes the compatible resource with no conflicting neighbour already on
+//!    it, preferring the least-loaded one. When every compatible resource is
+//!    taken by a neighbour, the task goes where it clashes with the fewest
+//!    neighbours and the clash is counted.
+//!
+//! [`InitialAssignment::restrict`] then keeps each task's windows on its
+//! assigned resource only, so the main scheduler sees one resource per task.
+
+use std::collections::HashMap;
+
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{IntervalSet, SolutionSpace};
+use crate::Id;
+use qtty::Unit;
+
+/// Task → resource assignment produced by [`greedy_colouring`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitialAssignment {
+    /// Resource chosen for each assignable task.
+    pub assignment: HashMap<Id, Id>,
+    /// Tasks with no windows on any resource.
+    pub unassigned: Vec<Id>,
+    /// Conflicting pairs left on the same resource because no conflict-free
+    /// resource was available.
+    pub clashes: usize,
+}
+
+impl InitialAssignment {
+    /// Resource assigned to `task_id`.
+    pub fn resource_of(&self, task_id: &str) -> Option<&str> {
+        self.assignment.get(task_id).map(String::as_str)
+    }
+
+    /// Copies of `resource_spaces` in which each assigned task keeps windows
+    /// on its own resource only. Tasks not in the assignment are left as
+    /// they are.
+    pub fn restrict<U: Unit>(
+        &self,
+        resource_spaces: &HashMap<Id, SolutionSpace<U>>,
+    ) -> HashMap<Id, SolutionSpace<U>> {
+        resource_spaces
+            .iter()
+            .map(|(resource_id, space)| {
+                let mut restricted = space.clone();
+                for (task_id, assigned) in &self.assignment {
+                    if assigned != resource_id {
+                        restricted.remove(task_id.as_str());
+                    }
+                }
+                (resource_id.clone(), restricted)
+            })
+            .collect()
+    }
+}
+
+/// Assigns every task of `blocks` to one resource of `resource_spaces`.
+///
+/// A task may use resource `r` if `resource_spaces[r]` holds windows for it
+/// with room for its size. Deterministic for identical inputs.
+pub fn greedy_colouring<T, U, D, E>(
+    blocks: &[SchedulingBlock<T, U, D, E>],
+    resource_spaces: &HashMap<Id, SolutionSpace<U>>,
+) -> InitialAssignment
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    let mut resources: Vec<&Id> = resource_spaces.keys().collect();
+    resources.sort();
+
+    let tasks: Vec<(Id, f64)> = blocks
+        .iter()
+        .flat_map(|block| block.tasks())
+        .map(|(id, task)| (id.to_owned(), task.size_on_axis().value()))
+        .collect();
+
+    // Windows of each task on each compatible resource.
+    let windows: Vec<HashMap<&Id, &IntervalSet<U>>> = tasks
+        .iter()
+        .map(|(id, size)| {
+            resources
+                .iter()
+                .filter_map(|&r| {
+                    let set = resource_spaces[r].get_intervals(id.as_str())?;
+                    set.iter()
+                        .any(|w| w.duration().value() >= *size)
+                        .then_some((r, set))
+                })
+                .collect()
+        })
+        .collect();
+
+    // conflicts[i] = (neighbour, resource) pairs.
+    let mut conflicts: Vec<Vec<(usize, &Id)>> = vec![Vec::new(); tasks.len()];
+    for a in 0..tasks.len() {
+        for b in a + 1..tasks.len() {
+            for (&r, wa) in &windows[a] {
+                let Some(wb) = windows[b].get(r) else {
+                    continue;
+                };
+                if cannot_share(wa, wb, tasks[a].1 + tasks[b].1) {
+                    conflicts[a].push((b, r));
+                    conflicts[b].push((a, r));
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..tasks.len()).collect();
+    order.sort_by(|&a, &b| {
+        conflicts[b]
+            .len()
+            .cmp(&conflicts[a].len())
+            .then(windows[a].len().cmp(&windows[b].len()))
+            .then_with(|| tasks[a].0.cmp(&tasks[b].0))
+    });
+
+    let mut colour: Vec<Option<&Id>> = vec![None; tasks.len()];
+    let mut load: HashMap<&Id, f64> = HashMap::new();
+    let mut result = InitialAssignment {
+        assignment: HashMap::new(),
+        unassigned: Vec::new(),
+        clashes: 0,
+    };
+
+    for i in order {
+        let (id, size) = &tasks[i];
+        // (clashing neighbours, load, resource) — smallest wins.
+        let best = resources
+            .iter()
+            .filter(|r| windows[i].contains_key(**r))
+            .map(|&r| {
+                let clashes = conflicts[i]
+                    .iter()
+                    .filter(|&&(n, on)| on == r && colour[n] == Some(r))
+                    .count();
+                (clashes, load.get(r).copied().unwrap_or(0.0), r)
+            })
+            .min_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)).then(a.2.cmp(b.2)));
+
+        match best {
+            Some((clashes, _, r)) => {
+                colour[i] = Some(r);
+                *load.entry(r).or_default() += size;
+                result.clashes += clashes;
+                result.assignment.insert(id.clone(), r.clone());
+            }
+            None => result.unassigned.push(id.clone()),
+        }
+    }
+    result.unassigned.sort();
+    result
+}
+
+/// Whether two tasks with windows `a` and `b` on one resource cannot both
+/// run there: the windows overlap but their union is too short for both.
+fn cannot_share<U: Unit>(a: &IntervalSet<U>, b: &IntervalSet<U>, needed: f64) -> bool {
+    if a.intersection(b).is_empty() {
+        return false;
+    }
+    let union: f64 = a.union(b).iter().map(|w| w.duration().value()).sum();
+    union < needed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    type Block = SchedulingBlock<TestTask, Second>;
+
+    fn block(tasks: &[(&str, f64)]) -> Block {
+        let mut block = Block::new();
+        for &(id, size) in tasks {
+            block
+                .add_task_with_id(TestTask::new(id, size), Some(id.into()))
+                .unwrap();
+        }
+        block
+    }
+
+    type Windows<'a> = &'a [(&'a str, f64, f64)];
+
+    fn spaces(entries: &[(&str, Windows)]) -> HashMap<Id, SolutionSpace<Second>> {
+        entries
+            .iter()
+            .map(|(r, windows)| {
+                let mut ss = SolutionSpace::new();
+                for &(id, a, b) in windows.iter() {
+                    ss.add_interval(id, iv(a, b));
+                }
+                (r.to_string(), ss)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn conflicting_tasks_get_different_resources() {
+        // a and b both need 10 of the same 15-long window.
+        let blocks = [block(&[("a", 10.0), ("b", 10.0), ("c", 5.0)])];
+        let rs = spaces(&[
+            (
+                "r1",
+                &[("a", 0.0, 15.0), ("b", 0.0, 15.0), ("c", 0.0, 100.0)],
+            ),
+            ("r2", &[("a", 0.0, 15.0), ("b", 0.0, 15.0)]),
+        ]);
+        let result = greedy_colouring(&blocks, &rs);
+        assert_ne!(result.resource_of("a"), result.resource_of("b"));
+        assert_eq!(result.clashes, 0);
+        assert!(result.unassigned.is_empty());
+    }
+
+    #[test]
+    fn clash_counted_when_unavoidable() {
+        let blocks = [block(&[("a", 10.0), ("b", 10.0), ("z", 1.0)])];
+        let rs = spaces(&[("r1", &[("a", 0.0, 15.0), ("b", 5.0, 15.0)])]);
+        let result = greedy_colouring(&blocks, &rs);
+        assert_eq!(result.clashes, 1);
+        assert_eq!(result.unassigned, vec!["z".to_string()]);
+    }
+
+    #[test]
+    fn restrict_keeps_assigned_resource_only() {
+        let blocks = [block(&[("a", 10.0), ("b", 10.0)])];
+        let rs = spaces(&[
+            ("r1", &[("a", 0.0, 15.0), ("b", 0.0, 15.0)]),
+            ("r2", &[("a", 0.0, 15.0), ("b", 0.0, 15.0)]),
+        ]);
+        let result = greedy_colouring(&blocks, &rs);
+        let restricted = result.restrict(&rs);
+        for task in ["a", "b"] {
+            let on: Vec<_> = restricted
+                .iter()
+                .filter(|(_, ss)| ss.get_intervals(task).is_some())
+                .map(|(r, _)| r.as_str())
+                .collect();
+            assert_eq!(on, vec![result.resource_of(task).unwrap()]);
+        }
+    }
+
+    #[test]
+    fn load_spreads_unconflicted_tasks() {
+        let blocks = [block(&[("a", 10.0), ("b", 10.0)])];
+        let rs = spaces(&[
+            ("r1", &[("a", 0.0, 100.0), ("b", 0.0, 100.0)]),
+            ("r2", &[("a", 0.0, 100.0), ("b", 0.0, 100.0)]),
+        ]);
+        let result = greedy_colouring(&blocks, &rs);
+        assert_eq!(result.resource_of("a"), Some("r1"));
+        assert_eq!(result.resource_of("b"), Some("r2"));
+    }
+}
@@ -0,0 +1,154 @@
+//! Priority aging.
+//!
+//! In a long run the EST ranking can keep passing over the same
+//! medium-priority task: at every iteration something with an earlier start
+//! or a higher priority wins, and the task is left to whatever time remains
+//! at the end. [`PriorityAging`] raises a candidate's effective priority
+//! with the number of iterations it has lost to a task competing for the
+//! same start, following an [`AgingCurve`], so that it eventually wins the
+//! priority tie-break against fresher tasks.
+//!
+//! Like boosts, aging only affects the ranking; task definitions are left
+//! untouched.
+
+use crate::scheduling_block::Task;
+use qtty::Unit;
+
+use super::candidate::Candidate;
+
+/// How the priority bonus grows with the iterations a candidate has waited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AgingCurve {
+    /// `rate` priority points per iteration, rounded down.
+    Linear { rate: f64 },
+    /// `delta` points every `every` iterations.
+    Step { every: u32, delta: i32 },
+    /// `rate · waited²` points, rounded down: slow at first, then steep.
+    Quadratic { rate: f64 },
+}
+
+impl AgingCurve {
+    fn bonus(&self, waited: u32) -> i32 {
+        let points = match *self {
+            AgingCurve::Linear { rate } => rate * f64::from(waited),
+            AgingCurve::Step { every, delta } => {
+                f64::from(waited / every.max(1)) * f64::from(delta)
+            }
+            AgingCurve::Quadratic { rate } => rate * f64::from(waited) * f64::from(waited),
+        };
+        // `as` saturates, and maps NaN to zero.
+        points.floor() as i32
+    }
+}
+
+/// Priority bonus for candidates that wait without being placed.
+///
+/// A candidate's wait counts the iterations of the EST loop at which it
+/// could have started as early as the task that was picked, but ranked
+/// behind it; candidates whose windows have not opened yet do not age. The
+/// bonus is zero for the first `grace` iterations and then follows the curve
+/// over the iterations past the grace period, up to `cap` points.
+///
+/// # Example
+///
+/// ```
+/// use virolai::algorithms::est::PriorityAging;
+///
+/// let aging = PriorityAging::linear(0.5).with_grace(4).with_cap(3);
+/// assert_eq!(aging.bonus(4), 0);
+/// assert_eq!(aging.bonus(8), 2);
+/// assert_eq!(aging.bonus(100), 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriorityAging {
+    pub curve: AgingCurve,
+    /// Iterations a candidate waits before it starts aging.
+    pub grace: u32,
+    /// Largest bonus granted.
+    pub cap: i32,
+}
+
+impl PriorityAging {
+    /// Aging along `curve`, with no grace period and no cap.
+    pub fn new(curve: AgingCurve) -> Self {
+        Self {
+            curve,
+            grace: 0,
+            cap: i32::MAX,
+        }
+    }
+
+    /// `rate` points per iteration waited.
+    pub fn linear(rate: f64) -> Self {
+        Self::new(AgingCurve::Linear { rate })
+    }
+
+    /// `delta` points every `every` iterations waited.
+    pub fn stepped(every: u32, delta: i32) -> Self {
+        Self::new(AgingCurve::Step { every, delta })
+    }
+
+    /// Starts aging only after `iterations` iterations.
+    pub fn with_grace(mut self, iterations: u32) -> Self {
+        self.grace = iterations;
+        self
+    }
+
+    /// Limits the bonus to `cap` points.
+    pub fn with_cap(mut self, cap: i32) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Priority bonus of a candidate that has waited `waited` iterations.
+    pub fn bonus(&self, waited: u32) -> i32 {
+        self.curve
+            .bonus(waited.saturating_sub(self.grace))
+            .clamp(0, self.cap.max(0))
+    }
+}
+
+/// Recomputes each candidate's aging bonus from its wait.
+pub(crate) fn apply_aging<T, U>(candidates: &mut [Candidate<T, U>], aging: &PriorityAging)
+where
+    T: Task<U>,
+    U: Unit,
+{
+    for c in candidates.iter_mut() {
+        c.aged = aging.bonus(c.waited);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curves_grow_with_the_wait() {
+        let linear = PriorityAging::linear(0.5);
+        assert_eq!(linear.bonus(0), 0);
+        assert_eq!(linear.bonus(3), 1);
+        assert_eq!(linear.bonus(10), 5);
+
+        let stepped = PriorityAging::stepped(5, 2);
+        assert_eq!(stepped.bonus(4), 0);
+        assert_eq!(stepped.bonus(5), 2);
+        assert_eq!(stepped.bonus(12), 4);
+        // A zero step length counts every iteration.
+        assert_eq!(PriorityAging::stepped(0, 1).bonus(3), 3);
+
+        let quadratic = PriorityAging::new(AgingCurve::Quadratic { rate: 0.1 });
+        assert_eq!(quadratic.bonus(3), 0);
+        assert_eq!(quadratic.bonus(10), 10);
+    }
+
+    #[test]
+    fn grace_and_cap_bound_the_bonus() {
+        let aging = PriorityAging::linear(1.0).with_grace(3).with_cap(5);
+        assert_eq!(aging.bonus(3), 0);
+        assert_eq!(aging.bonus(5), 2);
+        assert_eq!(aging.bonus(u32::MAX), 5);
+        // Negative rates never demote.
+        assert_eq!(PriorityAging::linear(-1.0).bonus(10), 0);
+    }
+}
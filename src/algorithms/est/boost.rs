@@ -0,0 +1,197 @@
+//! Time-windowed priority boosts.
+//!
+//! A [`PriorityBoost`] raises (or lowers) the priority of a set of tasks while
+//! they would start inside a window — e.g. commissioning tasks get `+10`
+//! during the first week. Boosts only affect the ranking; task definitions
+//! are left untouched, so a campaign push is undone by dropping the rule.
+//!
+//! At every iteration, a candidate's effective priority is its own priority
+//! plus the `delta` of every rule that targets it and whose window contains
+//! the candidate's current EST. Rules stack.
+
+use std::collections::HashMap;
+
+use crate::schedule::Schedule;
+use crate::scheduling_block::Task;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::Unit;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::candidate::Candidate;
+
+/// Tasks a [`PriorityBoost`] applies to.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum BoostTarget {
+    /// Every task.
+    All,
+    /// Tasks with one of these IDs.
+    Tasks { ids: Vec<Id> },
+    /// Tasks whose ID starts with `prefix`.
+    Prefix { prefix: String },
+}
+
+impl BoostTarget {
+    /// Returns `true` if `task_id` is targeted.
+    pub fn matches(&self, task_id: &str) -> bool {
+        match self {
+            BoostTarget::All => true,
+            BoostTarget::Tasks { ids } => ids.iter().any(|id| id == task_id),
+            BoostTarget::Prefix { prefix } => task_id.starts_with(prefix.as_str()),
+        }
+    }
+}
+
+/// Adds `delta` to the priority of targeted tasks starting within `window`.
+///
+/// # Example
+///
+/// ```
+/// use virolai::algorithms::est::{BoostTarget, PriorityBoost};
+/// use virolai::solution_space::Interval;
+/// use qtty::{Day, Quantity};
+///
+/// let push = PriorityBoost::new("commissioning", Interval::<Day>::from_f64(0.0, 7.0), 10)
+///     .with_target(BoostTarget::Prefix { prefix: "comm-".into() });
+///
+/// assert_eq!(push.delta_for("comm-42", Quantity::new(2.0)), 10);
+/// assert_eq!(push.delta_for("comm-42", Quantity::new(8.0)), 0);
+/// assert_eq!(push.delta_for("survey-1", Quantity::new(2.0)), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct PriorityBoost<U: Unit> {
+    pub label: String,
+    pub window: Interval<U>,
+    pub delta: i32,
+    pub target: BoostTarget,
+}
+
+impl<U: Unit> PriorityBoost<U> {
+    /// Creates a rule applying to every task.
+    pub fn new(label: impl Into<String>, window: Interval<U>, delta: i32) -> Self {
+        Self {
+            label: label.into(),
+            window,
+            delta,
+            target: BoostTarget::All,
+        }
+    }
+
+    /// Restricts the rule to `target`.
+    pub fn with_target(mut self, target: BoostTarget) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Restricts the rule to the given task IDs.
+    pub fn with_tasks(self, ids: impl IntoIterator<Item = impl Into<Id>>) -> Self {
+        self.with_target(BoostTarget::Tasks {
+            ids: ids.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Priority change for `task_id` starting at `start`: `delta` if the rule
+    /// applies, zero otherwise.
+    pub fn delta_for(&self, task_id: &str, start: qtty::Quantity<U>) -> i32 {
+        let active = self.window.start().value() <= start.value()
+            && start.value() < self.window.end().value();
+        if active && self.target.matches(task_id) {
+            self.delta
+        } else {
+            0
+        }
+    }
+}
+
+/// Sum of the deltas of all `boosts` applying to `task_id` starting at `start`.
+pub(crate) fn total_delta<U: Unit>(
+    boosts: &[PriorityBoost<U>],
+    task_id: &str,
+    start: qtty::Quantity<U>,
+) -> i32 {
+    boosts
+        .iter()
+        .map(|b| b.delta_for(task_id, start))
+        .fold(0i32, i32::saturating_add)
+}
+
+/// Recomputes each candidate's boost from its current EST.
+pub(crate) fn apply_boosts<T, U>(candidates: &mut [Candidate<T, U>], boosts: &[PriorityBoost<U>])
+where
+    T: Task<U>,
+    U: Unit,
+{
+    for c in candidates.iter_mut() {
+        c.boost = c.est.map_or(0, |est| total_delta(boosts, &c.task_id, est));
+    }
+}
+
+/// Result of [`ESTScheduler::schedule_boosted`](super::ESTScheduler::schedule_boosted).
+#[derive(Debug, Clone)]
+pub struct BoostedSchedule<U: Unit> {
+    pub schedule: Schedule<U>,
+    /// The rules the run was configured with, kept for provenance.
+    pub boosts: Vec<PriorityBoost<U>>,
+    /// Boost each placed task held when it was picked (non-zero entries only).
+    pub applied: HashMap<Id, i32>,
+}
+
+impl<U: Unit> BoostedSchedule<U> {
+    /// Boost `task_id` was placed with, zero if none or not placed.
+    pub fn applied_to(&self, task_id: &str) -> i32 {
+        self.applied.get(task_id).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    #[test]
+    fn targets_and_window() {
+        let rule = PriorityBoost::new("push", iv(0.0, 10.0), 5).with_tasks(["a"]);
+        assert_eq!(rule.delta_for("a", q(0.0)), 5);
+        assert_eq!(rule.delta_for("a", q(10.0)), 0);
+        assert_eq!(rule.delta_for("b", q(5.0)), 0);
+
+        let prefix = BoostTarget::Prefix {
+            prefix: "comm".into(),
+        };
+        assert!(prefix.matches("comm-1"));
+        assert!(!prefix.matches("survey"));
+        assert!(BoostTarget::All.matches("anything"));
+    }
+
+    #[test]
+    fn rules_stack() {
+        let boosts: Vec<PriorityBoost<Second>> = vec![
+            PriorityBoost::new("week", iv(0.0, 10.0), 10),
+            PriorityBoost::new("demote", iv(5.0, 20.0), -3).with_tasks(["a"]),
+        ];
+        assert_eq!(total_delta(&boosts, "a", q(2.0)), 10);
+        assert_eq!(total_delta(&boosts, "a", q(7.0)), 7);
+        assert_eq!(total_delta(&boosts, "a", q(15.0)), -3);
+        assert_eq!(total_delta(&boosts, "b", q(15.0)), 0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let rule: PriorityBoost<Second> =
+            PriorityBoost::new("push", iv(0.0, 10.0), 5).with_target(BoostTarget::Prefix {
+                prefix: "comm".into(),
+            });
+        let json = serde_json::to_string(&rule).unwrap();
+        assert!(json.contains("\"type\":\"prefix\""));
+        let back: PriorityBoost<Second> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, rule);
+    }
+}
@@ -0,0 +1,206 @@
+//! Scheduling under a total cost budget.
+//!
+//! A [`CostLedger`] gates the winners of the [`engine`](super::engine) loop:
+//! before a winner is placed, its [`CostedTask::cost`] at the chosen
+//! interval is checked against what is left of the budget; a task that does
+//! not fit is dropped and the loop goes on with the next candidate. Tasks
+//! are not moved to a cheaper slot — rank with a
+//! [`CostConstraint`](crate::constraints::soft::static_::CostConstraint) for
+//! that.
+
+use std::collections::HashMap;
+
+use crate::schedule::Schedule;
+use crate::scheduling_block::CostedTask;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::Unit;
+
+use super::candidate::Candidate;
+use super::engine::PlacementGate;
+
+/// Result of [`ESTScheduler::schedule_budgeted`](super::ESTScheduler::schedule_budgeted).
+#[derive(Debug, Clone)]
+pub struct BudgetedSchedule<U: Unit> {
+    pub schedule: Schedule<U>,
+    /// Budget the schedule was built under.
+    pub budget: f64,
+    /// Cost of every placed task.
+    pub costs: HashMap<Id, f64>,
+    /// Tasks that were feasible but dropped because they would have
+    /// exceeded the budget, in the order they were rejected.
+    pub over_budget: Vec<Id>,
+}
+
+impl<U: Unit> BudgetedSchedule<U> {
+    /// Total cost of the placed tasks.
+    pub fn spent(&self) -> f64 {
+        self.costs.values().sum()
+    }
+
+    /// Budget left unspent.
+    pub fn remaining(&self) -> f64 {
+        self.budget - self.spent()
+    }
+}
+
+/// Costs of the placements of a run, held against a budget.
+#[derive(Debug, Clone)]
+pub(crate) struct CostLedger {
+    budget: f64,
+    spent: f64,
+    costs: HashMap<Id, f64>,
+    over_budget: Vec<Id>,
+}
+
+impl CostLedger {
+    pub(crate) fn new(budget: f64) -> Self {
+        Self {
+            budget,
+            spent: 0.0,
+            costs: HashMap::new(),
+            over_budget: Vec::new(),
+        }
+    }
+
+    /// The result of the run that built `schedule` under this ledger.
+    pub(crate) fn into_result<U: Unit>(self, schedule: Schedule<U>) -> BudgetedSchedule<U> {
+        BudgetedSchedule {
+            schedule,
+            budget: self.budget,
+            costs: self.costs,
+            over_budget: self.over_budget,
+        }
+    }
+}
+
+impl<T: CostedTask<U>, U: Unit> PlacementGate<T, U> for CostLedger {
+    fn admit(&mut self, candidate: &Candidate<T, U>, interval: Interval<U>) -> bool {
+        let cost = candidate.task().cost(interval);
+        if self.spent + cost > self.budget {
+            self.over_budget.push(candidate.task_id.clone());
+            return false;
+        }
+        true
+    }
+
+    fn placed(&mut self, candidate: &Candidate<T, U>, interval: Interval<U>) {
+        let cost = candidate.task().cost(interval);
+        self.spent += cost;
+        self.costs.insert(candidate.task_id.clone(), cost);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::est::engine::{schedule_segment_traced, SegmentHooks};
+    use crate::scheduling_block::{CostProfile, Task};
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::{Quantity, Second};
+
+    fn schedule_segment_budgeted(
+        candidates: Vec<Candidate<Priced, Second>>,
+        solution_space: &SolutionSpace<Second>,
+        horizon: Interval<Second>,
+        endangered_threshold: u32,
+        budget: f64,
+    ) -> BudgetedSchedule<Second> {
+        let mut ledger = CostLedger::new(budget);
+        let mut schedule = Schedule::new();
+        schedule_segment_traced(
+            &mut schedule,
+            candidates,
+            solution_space,
+            horizon,
+            endangered_threshold,
+            SegmentHooks {
+                gate: Some(&mut ledger),
+                ..SegmentHooks::default()
+            },
+        );
+        ledger.into_result(schedule)
+    }
+
+    #[derive(Debug, Clone)]
+    struct Priced {
+        task: TestTask,
+        price: CostProfile<Second>,
+    }
+
+    impl Task<Second> for Priced {
+        type SizeUnit = Second;
+        type ConstraintLeaf = <TestTask as Task<Second>>::ConstraintLeaf;
+
+        fn name(&self) -> &str {
+            self.task.name()
+        }
+
+        fn size(&self) -> Quantity<Second> {
+            self.task.size()
+        }
+
+        fn priority(&self) -> i32 {
+            self.task.priority()
+        }
+    }
+
+    impl CostedTask<Second> for Priced {
+        fn cost(&self, placement: Interval<Second>) -> f64 {
+            self.price.cost(placement)
+        }
+    }
+
+    fn candidate(id: &str, priority: i32, rate: f64) -> Candidate<Priced, Second> {
+        let task = TestTask::new(id, 10.0).with_priority(priority);
+        Candidate::new(
+            Priced {
+                task,
+                price: CostProfile::new(rate),
+            },
+            id,
+        )
+    }
+
+    fn space(ids: &[&str]) -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for id in ids {
+            ss.set_intervals(*id, vec![iv(0.0, 100.0)]);
+        }
+        ss
+    }
+
+    #[test]
+    fn expensive_task_is_dropped() {
+        let result = schedule_segment_budgeted(
+            vec![
+                candidate("a", 10, 1.0),
+                candidate("b", 5, 5.0),
+                candidate("c", 0, 2.0),
+            ],
+            &space(&["a", "b", "c"]),
+            iv(0.0, 100.0),
+            1,
+            40.0,
+        );
+        // a costs 10, b would bring the total to 60, c to 30.
+        assert_eq!(result.over_budget, vec!["b".to_string()]);
+        assert_eq!(result.schedule.get_interval("c"), Some(iv(10.0, 20.0)));
+        assert_eq!(result.spent(), 30.0);
+        assert_eq!(result.remaining(), 10.0);
+    }
+
+    #[test]
+    fn ample_budget_places_everything() {
+        let result = schedule_segment_budgeted(
+            vec![candidate("a", 0, 1.0), candidate("b", 0, 1.0)],
+            &space(&["a", "b"]),
+            iv(0.0, 100.0),
+            1,
+            f64::INFINITY,
+        );
+        assert_eq!(result.schedule.len(), 2);
+        assert!(result.over_budget.is_empty());
+    }
+}
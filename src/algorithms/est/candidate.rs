@@ -1,5 +1,6 @@
 //! Task candidate with computed scheduling metrics.
 
+use crate::rng::derive_seed;
 use crate::scheduling_block::Task;
 use crate::solution_space::Interval;
 use crate::Id;
@@ -17,6 +18,17 @@ where
     pub(crate) est: Option<Quantity<A>>,
     pub(crate) deadline: Option<Quantity<A>>,
     pub(crate) flexibility: Quantity<A>,
+    /// Priority boost active at the current EST (see [`super::boost`]).
+    pub(crate) boost: i32,
+    /// Iterations lost to a task that started no earlier than this one could.
+    pub(crate) waited: u32,
+    /// Priority bonus earned by waiting (see [`super::aging`]).
+    pub(crate) aged: i32,
+    /// Seeded tie-break key ranked before the task ID; `0` without jitter.
+    pub(crate) tie: u64,
+    /// Lane of the [`Frontier`](super::frontier::Frontier) the EST was
+    /// found on, where the candidate is placed if it wins.
+    pub(crate) lane: usize,
 }
 
 impl<T, A> Candidate<T, A>
@@ -32,9 +44,27 @@ where
             est: None,
             deadline: None,
             flexibility: Quantity::new(0.0),
+            boost: 0,
+            waited: 0,
+            aged: 0,
+            tie: 0,
+            lane: 0,
         }
     }
 
+    /// Sets the tie-break key from `seed` and the task ID.
+    ///
+    /// The key depends only on the two, so a task keeps its key across runs
+    /// and block orders, and a different seed reshuffles every tie.
+    pub(crate) fn with_tie_jitter(mut self, seed: u64) -> Self {
+        // FNV-1a over the ID, then mixed with the seed.
+        let id_hash = self.task_id.bytes().fold(0xCBF2_9CE4_8422_2325u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01B3)
+        });
+        self.tie = derive_seed(seed, id_hash);
+        self
+    }
+
     /// Returns true if the task cannot be scheduled (no EST found).
     pub fn is_impossible(&self) -> bool {
         self.est.is_none()
@@ -83,6 +113,15 @@ where
     pub fn flexibility(&self) -> Quantity<A> {
         self.flexibility
     }
+
+    /// Task priority plus the active boost and aging bonus; this is what
+    /// the ranking uses.
+    pub fn priority(&self) -> i32 {
+        self.task
+            .priority()
+            .saturating_add(self.boost)
+            .saturating_add(self.aged)
+    }
 }
 
 #[cfg(test)]
@@ -101,6 +140,18 @@ mod tests {
         assert_eq!(c.flexibility().value(), 0.0);
     }
 
+    #[test]
+    fn tie_jitter_depends_on_seed_and_id() {
+        let c = |id: &str, seed| {
+            Candidate::<TestTask, Second>::new(TestTask::new("t", 1.0), id)
+                .with_tie_jitter(seed)
+                .tie
+        };
+        assert_eq!(c("a", 7), c("a", 7));
+        assert_ne!(c("a", 7), c("b", 7));
+        assert_ne!(c("a", 7), c("a", 8));
+    }
+
     #[test]
     fn is_impossible_when_no_est() {
         let c = Candidate::<TestTask, Second>::new(TestTask::new("t", 10.0), "t");
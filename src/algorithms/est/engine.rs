@@ -1,35 +1,78 @@
 //! Core scheduling engine with candidate update and scheduling loop.
 
+use std::collections::HashMap;
+
+use crate::constraints::soft::Objective;
+use crate::constraints::{
+    ConsumableBudget, DynamicConstraint, DynamicConstraintIndex, PowerEnvelope, SchedulingContext,
+};
 use crate::schedule::Schedule;
 use crate::scheduling_block::Task;
-use crate::solution_space::{Interval, SolutionSpace};
+use crate::solution_space::{Interval, IntervalSet, SolutionSpace, TimeGrid};
+use crate::Id;
 use qtty::Unit;
 
+use super::aging::{apply_aging, PriorityAging};
+use super::boost::{apply_boosts, PriorityBoost};
 use super::candidate::Candidate;
-use super::metrics::{compute_deadline, compute_est, compute_flexibility};
+use super::frontier::{Delay, Frontier};
+use super::layered::lowest_layer_first;
+use super::limit::LimitRun;
+use super::lookahead::Lookahead;
+use super::metrics::compute_metrics;
+use super::objective::break_ties;
+use super::observer::SchedulerObserver;
+use super::ranking::ranked;
+use super::selection::{select_with, SelectionHeuristic};
 
 /// Updates candidate metrics and sorts them.
+#[allow(dead_code)]
 pub fn update_candidates<T, U>(
     candidates: &mut [Candidate<T, U>],
     solution_space: &SolutionSpace<U>,
     horizon: Interval<U>,
     endangered_threshold: u32,
+    boosts: &[PriorityBoost<U>],
 ) where
     T: Task<U>,
-    U: Unit,
+    U: Unit + Send + Sync,
 {
     // Update metrics for all candidates
-    for candidate in candidates.iter_mut() {
-        candidate.flexibility =
-            compute_flexibility(&candidate.task, &candidate.task_id, solution_space, horizon);
-        candidate.est = compute_est(&candidate.task, &candidate.task_id, solution_space, horizon);
-        candidate.deadline =
-            compute_deadline(&candidate.task, &candidate.task_id, solution_space, horizon);
+    refresh_metrics(candidates, solution_space, horizon);
+    rank_candidates(candidates, endangered_threshold, boosts, None);
+}
+
+/// Applies `boosts` and the aging bonus of `aging` to freshly refreshed
+/// candidates, then sorts them by [`rank_key`].
+fn rank_candidates<T, U>(
+    candidates: &mut [Candidate<T, U>],
+    endangered_threshold: u32,
+    boosts: &[PriorityBoost<U>],
+    aging: Option<&PriorityAging>,
+) where
+    T: Task<U>,
+    U: Unit,
+{
+    apply_boosts(candidates, boosts);
+    if let Some(aging) = aging {
+        apply_aging(candidates, aging);
     }
 
-    // Sort candidates
-    // Sort candidates using a total, deterministic key to avoid panics from
-    // comparator inconsistencies when floating-point values (NaN) are present.
+    candidates.sort_by_key(|c| rank_key(c, endangered_threshold));
+}
+
+/// Sort key of the candidate ranking (ascending = picked first).
+pub(crate) type RankKey = (u8, u8, i128, i32, i128, u64, String);
+
+/// Builds the candidate sort key.
+///
+/// Uses a total, deterministic key to avoid panics from comparator
+/// inconsistencies when floating-point values (NaN) are present.
+pub(crate) fn rank_key<T, U>(c: &Candidate<T, U>, endangered_threshold: u32) -> RankKey
+where
+    T: Task<U>,
+    U: Unit,
+{
     fn f64_to_ordered_i128(x: f64) -> i128 {
         let u = x.to_bits() as i128;
         // Map IEEE-754 bit pattern to lexicographically ordered integer
@@ -40,30 +83,107 @@ pub fn update_candidates<T, U>(
         }
     }
 
-    candidates.sort_by_key(|c| {
-        // impossible last
-        let impossible_flag: u8 = if c.is_impossible() { 1 } else { 0 };
-        // kind: endangered (0), flexible (1), other (2)
-        let kind: u8 = if c.is_endangered(endangered_threshold) {
-            0
-        } else if c.is_flexible(endangered_threshold) {
-            1
-        } else {
-            2
-        };
-        // EST key (total order). Missing EST → large value to push later.
-        let est_key: i128 = c
-            .est()
-            .map(|q| f64_to_ordered_i128(q.value()))
-            .unwrap_or(i128::MAX / 4);
-        // priority: higher first → negate to sort ascending
-        let prio_key: i32 = -c.task().priority();
-        // flexibility key (total order)
-        let flex_key: i128 = f64_to_ordered_i128(c.flexibility().value());
-        // final tie-breaker: task id
-        let tid = c.task_id().to_string();
-        (impossible_flag, kind, est_key, prio_key, flex_key, tid)
-    });
+    // impossible last
+    let impossible_flag: u8 = if c.is_impossible() { 1 } else { 0 };
+    // kind: endangered (0), flexible (1), other (2)
+    let kind: u8 = if c.is_endangered(endangered_threshold) {
+        0
+    } else if c.is_flexible(endangered_threshold) {
+        1
+    } else {
+        2
+    };
+    // EST key (total order). Missing EST → large value to push later.
+    let est_key: i128 = c
+        .est()
+        .map(|q| f64_to_ordered_i128(q.value()))
+        .unwrap_or(i128::MAX / 4);
+    // priority (boost included): higher first → negate to sort ascending
+    let prio_key: i32 = c.priority().saturating_neg();
+    // flexibility key (total order)
+    let flex_key: i128 = f64_to_ordered_i128(c.flexibility().value());
+    // final tie-breakers: seeded jitter (0 when disabled), then task id
+    let tid = c.task_id().to_string();
+    (
+        impossible_flag,
+        kind,
+        est_key,
+        prio_key,
+        flex_key,
+        c.tie,
+        tid,
+    )
+}
+
+/// Recomputes EST, deadline and flexibility for a slice of candidates and
+/// returns the number of windows examined.
+fn refresh_serial<T, U>(
+    candidates: &mut [Candidate<T, U>],
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+) -> usize
+where
+    T: Task<U>,
+    U: Unit,
+{
+    let mut examined = 0;
+    for candidate in candidates.iter_mut() {
+        let metrics = compute_metrics(&candidate.task, &candidate.task_id, solution_space, horizon);
+        candidate.flexibility = metrics.flexibility;
+        candidate.est = metrics.est;
+        candidate.deadline = metrics.deadline;
+        examined += metrics.windows;
+    }
+    examined
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn refresh_metrics<T, U>(
+    candidates: &mut [Candidate<T, U>],
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+) -> usize
+where
+    T: Task<U>,
+    U: Unit + Send + Sync,
+{
+    refresh_serial(candidates, solution_space, horizon)
+}
+
+/// Below this many candidates, handing chunks to the pool costs more than it saves.
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_CANDIDATES: usize = 256;
+
+/// Parallel metric refresh (`parallel` feature).
+///
+/// Candidates are split into contiguous chunks, one per thread of rayon's
+/// global pool, so the workers are reused across iterations instead of
+/// spawned for each refresh. Every candidate's metrics depend only on its
+/// own task and the read-only solution space, and each chunk writes back in
+/// place, so the result is identical to the serial path regardless of
+/// thread scheduling; the subsequent sort is a total order.
+#[cfg(feature = "parallel")]
+pub(crate) fn refresh_metrics<T, U>(
+    candidates: &mut [Candidate<T, U>],
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+) -> usize
+where
+    T: Task<U>,
+    U: Unit + Send + Sync,
+{
+    use rayon::prelude::*;
+
+    let workers = rayon::current_num_threads();
+    if workers <= 1 || candidates.len() < PARALLEL_MIN_CANDIDATES {
+        return refresh_serial(candidates, solution_space, horizon);
+    }
+
+    let chunk_size = candidates.len().div_ceil(workers);
+    candidates
+        .par_chunks_mut(chunk_size)
+        .map(|chunk| refresh_serial(chunk, solution_space, horizon))
+        .sum()
 }
 
 /// Checks if scheduling is done (no more schedulable tasks or cursor past horizon).
@@ -111,47 +231,317 @@ where
 /// Candidate metrics are recomputed on `[cursor, horizon.end]` at each iteration.
 /// This keeps EST/deadline/flexibility aligned with the already scheduled prefix,
 /// so candidates are not dropped due to stale EST values that overlap.
+#[allow(dead_code)]
 pub fn schedule_segment<T, U>(
     schedule: &mut Schedule<U>,
-    mut candidates: Vec<Candidate<T, U>>,
+    candidates: Vec<Candidate<T, U>>,
     solution_space: &SolutionSpace<U>,
     horizon: Interval<U>,
     endangered_threshold: u32,
 ) where
     T: Task<U>,
-    U: Unit,
+    U: Unit + Send + Sync,
 {
-    // Initialize cursor at horizon start
-    let mut cursor = horizon.start();
+    schedule_segment_traced(
+        schedule,
+        candidates,
+        solution_space,
+        horizon,
+        endangered_threshold,
+        SegmentHooks::default(),
+    );
+}
+
+/// Incoming dynamic edges of the candidates, evaluated by the loop against
+/// the schedule built so far.
+pub(crate) trait DynamicEdges<U: Unit> {
+    /// Windows the incoming edges of `task_id` admit within `range`, or
+    /// `None` if it has none.
+    fn admitted(
+        &mut self,
+        task_id: &str,
+        range: Interval<U>,
+        ctx: &SchedulingContext<U>,
+    ) -> Option<IntervalSet<U>>;
+
+    /// Hands a set returned by [`admitted`](Self::admitted) back for reuse.
+    fn recycle(&mut self, set: IntervalSet<U>);
+
+    /// Edges computed so far; results reused from a cache are not counted.
+    fn evaluated(&self) -> usize;
+}
+
+impl<D: DynamicConstraint<U>, U: Unit> DynamicEdges<U> for DynamicConstraintIndex<'_, D, U> {
+    fn admitted(
+        &mut self,
+        task_id: &str,
+        range: Interval<U>,
+        ctx: &SchedulingContext<U>,
+    ) -> Option<IntervalSet<U>> {
+        self.evaluate_memoized(task_id, range, ctx)
+    }
+
+    fn recycle(&mut self, set: IntervalSet<U>) {
+        DynamicConstraintIndex::recycle(self, set);
+    }
+
+    fn evaluated(&self) -> usize {
+        self.edges_evaluated()
+    }
+}
+
+/// Checks each winner before it is placed.
+pub(crate) trait PlacementGate<T: Task<U>, U: Unit> {
+    /// `false` to drop `candidate` instead of placing it over `interval`.
+    fn admit(&mut self, candidate: &Candidate<T, U>, interval: Interval<U>) -> bool;
+
+    /// `candidate` was placed over `interval`.
+    fn placed(&mut self, candidate: &Candidate<T, U>, interval: Interval<U>);
+}
+
+/// Optional extensions of the plain loop.
+pub(crate) struct SegmentHooks<'a, T: Task<U>, U: Unit> {
+    /// Time-windowed priority boosts.
+    pub boosts: &'a [PriorityBoost<U>],
+    /// Receives every step of the loop.
+    pub observer: Option<&'a mut dyn SchedulerObserver<U>>,
+    /// Grid the cursor is snapped up to after each placement; the solution
+    /// space is expected to be [quantized](SolutionSpace::quantized) against
+    /// it.
+    pub grid: Option<&'a TimeGrid<U>>,
+    /// Priority bonus for candidates left waiting.
+    pub aging: Option<&'a PriorityAging>,
+    /// Replaces the default ranking when picking the next candidate.
+    pub heuristic: Option<&'a dyn SelectionHeuristic<T, U>>,
+    /// Scores the top candidates by the damage their placement does.
+    pub lookahead: Option<&'a Lookahead>,
+    /// Incoming dynamic edges; each candidate's windows are narrowed to
+    /// what its edges admit before the metrics are refreshed.
+    pub edges: Option<&'a mut dyn DynamicEdges<U>>,
+    /// Budgets placements draw on, read by `edges`; a candidate the budget
+    /// can no longer afford loses its windows.
+    pub consumables: Option<&'a ConsumableBudget>,
+    /// Power envelope over the whole schedule, checked for every candidate
+    /// along with `edges`.
+    pub power: Option<&'a PowerEnvelope>,
+    /// Sequence-dependent delay: each candidate is evaluated this long after
+    /// the end and gap of the task placed before it.
+    pub delay: Option<&'a Delay<'a, T, U>>,
+    /// Topological layer of each task; among the feasible candidates, the
+    /// lowest layer goes first (see [`super::layered`]).
+    pub layers: Option<&'a HashMap<Id, usize>>,
+    /// Breaks ties on class and EST by score (see [`super::objective`]).
+    pub objective: Option<&'a Objective<U>>,
+    /// Vetoes winners, which are then dropped.
+    pub gate: Option<&'a mut dyn PlacementGate<T, U>>,
+    /// Execution limit the loop runs under (see [`super::limit`]).
+    pub limit: Option<&'a mut LimitRun>,
+}
+
+impl<T: Task<U>, U: Unit> Default for SegmentHooks<'_, T, U> {
+    fn default() -> Self {
+        Self {
+            boosts: &[],
+            observer: None,
+            grid: None,
+            aging: None,
+            heuristic: None,
+            lookahead: None,
+            edges: None,
+            consumables: None,
+            power: None,
+            delay: None,
+            layers: None,
+            objective: None,
+            gate: None,
+            limit: None,
+        }
+    }
+}
+
+/// What a run of the loop leaves behind besides its placements.
+pub(crate) struct SegmentOutcome<T: Task<U>, U: Unit> {
+    /// Boost each placed task was picked with (non-zero ones only).
+    pub applied: HashMap<Id, i32>,
+    /// Winners that could not be placed, in the order they were dropped.
+    pub dropped: Vec<Candidate<T, U>>,
+    /// Candidates left when the loop stopped, in rank order.
+    pub remaining: Vec<Candidate<T, U>>,
+}
+
+/// [`schedule_segment`] with the extensions in `hooks`.
+pub(crate) fn schedule_segment_traced<T, U>(
+    schedule: &mut Schedule<U>,
+    candidates: Vec<Candidate<T, U>>,
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+    endangered_threshold: u32,
+    hooks: SegmentHooks<'_, T, U>,
+) -> SegmentOutcome<T, U>
+where
+    T: Task<U>,
+    U: Unit + Send + Sync,
+{
+    let frontier = Frontier::single(schedule, solution_space, horizon, hooks.grid, hooks.delay);
+    run_segment(frontier, candidates, endangered_threshold, hooks)
+}
+
+/// The scheduling loop, placing `candidates` on the lanes of `frontier`.
+///
+/// `hooks.grid` and `hooks.delay` are expected to be those `frontier` was
+/// built with.
+#[cfg_attr(
+    feature = "trace",
+    tracing::instrument(
+        name = "est_segment",
+        level = "debug",
+        skip_all,
+        fields(candidates = candidates.len(), horizon = %frontier.horizon())
+    )
+)]
+pub(crate) fn run_segment<T, U>(
+    mut frontier: Frontier<'_, T, U>,
+    mut candidates: Vec<Candidate<T, U>>,
+    endangered_threshold: u32,
+    hooks: SegmentHooks<'_, T, U>,
+) -> SegmentOutcome<T, U>
+where
+    T: Task<U>,
+    U: Unit + Send + Sync,
+{
+    let SegmentHooks {
+        boosts,
+        mut observer,
+        grid: _,
+        aging,
+        heuristic,
+        lookahead,
+        mut edges,
+        consumables,
+        power,
+        delay: _,
+        layers,
+        objective,
+        mut gate,
+        mut limit,
+    } = hooks;
+    let mut applied = HashMap::new();
+    let mut dropped = Vec::new();
+    let mut iteration = 0;
 
     while !candidates.is_empty() {
-        let remaining_horizon = Interval::new(cursor, horizon.end());
+        let dynamic_edges = match edges.as_deref_mut() {
+            Some(edges) => frontier.narrow(edges, &candidates, consumables, power),
+            None => 0,
+        };
 
         // Recompute all remaining candidates against the current frontier.
-        update_candidates(
-            &mut candidates,
-            solution_space,
-            remaining_horizon,
-            endangered_threshold,
-        );
+        let windows_examined = frontier.refresh(&mut candidates);
+        rank_candidates(&mut candidates, endangered_threshold, boosts, aging);
+        if let Some(heuristic) = heuristic {
+            select_with(&mut candidates, heuristic);
+        }
+        if let Some(layers) = layers {
+            lowest_layer_first(&mut candidates, layers);
+        }
+        if let Some(objective) = objective {
+            break_ties(
+                &mut candidates,
+                endangered_threshold,
+                objective,
+                &frontier.context(),
+            );
+        }
 
-        if is_done(&candidates, cursor, horizon) {
+        let cursor = frontier.cursor();
+        if is_done(&candidates, cursor, frontier.horizon()) {
             break;
         }
+        if let Some(limit) = limit.as_deref_mut() {
+            if !limit.admits(&candidates[0], endangered_threshold) {
+                break;
+            }
+        }
 
-        let candidate = candidates.remove(0);
+        #[cfg(feature = "trace")]
+        tracing::debug!(
+            iteration,
+            cursor = cursor.value(),
+            candidates = candidates.len(),
+            windows_examined,
+            dynamic_edges,
+            "est iteration"
+        );
+        #[cfg(not(feature = "trace"))]
+        let _ = (windows_examined, dynamic_edges);
+        if let Some(observer) = observer.as_deref_mut() {
+            observer.on_iteration_start(iteration, cursor, candidates.len());
+            for (rank, c) in candidates.iter().enumerate() {
+                let scored = ranked(&candidates[0], c, rank, endangered_threshold);
+                observer.on_candidate_scored(iteration, rank, &scored);
+            }
+        }
+
+        let pick = lookahead.map_or(0, |lookahead| {
+            lookahead.pick(&candidates, &frontier, endangered_threshold)
+        });
+        let candidate = candidates.remove(pick);
+        // Candidates that could have started as early as the winner waited.
+        let contested = candidate.est().map_or(f64::NEG_INFINITY, |t| t.value());
+        for waiting in candidates.iter_mut() {
+            if waiting.est().is_some_and(|t| t.value() <= contested) {
+                waiting.waited = waiting.waited.saturating_add(1);
+            }
+        }
 
         // Schedule the task
-        if let Some(interval) = candidate.get_interval() {
-            if schedule.add(candidate.task_id(), interval).is_ok() {
-                // Advance cursor to the end of the scheduled task plus any
-                // required gap. Because intervals are half-open [start, end),
-                // the next task may begin exactly at `interval.end()` without
-                // overlapping — no epsilon offset is needed.
-                cursor = interval.end() + candidate.task().gap_after();
+        let admitted = match (candidate.get_interval(), gate.as_deref_mut()) {
+            (Some(interval), Some(gate)) => gate.admit(&candidate, interval),
+            _ => true,
+        };
+        match admitted.then(|| frontier.place(&candidate)).flatten() {
+            Some(interval) => {
+                #[cfg(feature = "trace")]
+                tracing::trace!(
+                    task = candidate.task_id(),
+                    start = interval.start().value(),
+                    end = interval.end().value(),
+                    "placed"
+                );
+                if let Some(observer) = observer.as_deref_mut() {
+                    observer.on_task_placed(iteration, candidate.task_id(), interval);
+                }
+                if let Some(gate) = gate.as_deref_mut() {
+                    gate.placed(&candidate, interval);
+                }
+                if candidate.boost != 0 {
+                    applied.insert(candidate.task_id.clone(), candidate.boost);
+                }
+                frontier.retire(candidate);
             }
+            None => {
+                #[cfg(feature = "trace")]
+                tracing::trace!(task = candidate.task_id(), "dropped");
+                if let Some(observer) = observer.as_deref_mut() {
+                    observer.on_task_impossible(candidate.task_id());
+                }
+                dropped.push(candidate);
+            }
+        }
+        iteration += 1;
+    }
+
+    if let Some(observer) = observer {
+        for c in &candidates {
+            observer.on_task_impossible(c.task_id());
         }
     }
+
+    SegmentOutcome {
+        applied,
+        dropped,
+        remaining: candidates,
+    }
 }
 
 #[cfg(test)]
@@ -180,7 +570,7 @@ mod tests {
         let ss = make_space_for(&[("a", vec![iv(0.0, 100.0)]), ("b", vec![iv(50.0, 100.0)])]);
         let horizon = iv(0.0, 100.0);
 
-        update_candidates(&mut candidates, &ss, horizon, 5);
+        update_candidates(&mut candidates, &ss, horizon, 5, &[]);
 
         // After update, candidates should have EST set and be sorted
         assert!(candidates[0].est().is_some());
@@ -201,11 +591,93 @@ mod tests {
             ("high", vec![iv(0.0, 100.0)]),
         ]);
 
-        update_candidates(&mut candidates, &ss, iv(0.0, 100.0), 5);
+        update_candidates(&mut candidates, &ss, iv(0.0, 100.0), 5, &[]);
         // Both have same EST/priority/flexibility, sorted by ID
         assert!(candidates[0].task_id() < candidates[1].task_id());
     }
 
+    #[test]
+    fn update_candidates_large_batch_matches_serial() {
+        // Large enough to take the threaded path when `parallel` is enabled.
+        let n = 600;
+        let ids: Vec<String> = (0..n).map(|i| format!("t{i:04}")).collect();
+        let mut ss = SolutionSpace::new();
+        for (i, id) in ids.iter().enumerate() {
+            let start = (i % 37) as f64;
+            ss.set_intervals(id.clone(), vec![iv(start, start + 50.0), iv(200.0, 260.0)]);
+        }
+        let horizon = iv(0.0, 300.0);
+
+        let mut batched: Vec<_> = ids.iter().map(|id| make_candidate(id, 10.0)).collect();
+        update_candidates(&mut batched, &ss, horizon, 5, &[]);
+
+        let mut serial: Vec<_> = ids.iter().map(|id| make_candidate(id, 10.0)).collect();
+        refresh_serial(&mut serial, &ss, horizon);
+
+        for c in &batched {
+            let s = serial.iter().find(|s| s.task_id() == c.task_id()).unwrap();
+            assert_eq!(c.est(), s.est());
+            assert_eq!(c.flexibility(), s.flexibility());
+        }
+        // Sorted output is deterministic: ties on every metric fall back to id.
+        assert!(batched
+            .windows(2)
+            .filter(|w| w[0].est() == w[1].est() && w[0].flexibility() == w[1].flexibility())
+            .all(|w| w[0].task_id() < w[1].task_id()));
+    }
+
+    // ── tracing ───────────────────────────────────────────────────────
+
+    #[cfg(feature = "trace")]
+    #[test]
+    fn schedule_segment_emits_iteration_events() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use tracing::span::{Attributes, Id as SpanId, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        #[derive(Default)]
+        struct Counter {
+            spans: AtomicUsize,
+            iterations: AtomicUsize,
+        }
+
+        struct Counting(Arc<Counter>);
+
+        impl Subscriber for Counting {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, attrs: &Attributes<'_>) -> SpanId {
+                if attrs.metadata().name() == "est_segment" {
+                    self.0.spans.fetch_add(1, Ordering::Relaxed);
+                }
+                SpanId::from_u64(1)
+            }
+            fn record(&self, _: &SpanId, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &SpanId, _: &SpanId) {}
+            fn event(&self, event: &Event<'_>) {
+                let fields = event.metadata().fields();
+                if fields.field("windows_examined").is_some()
+                    && fields.field("dynamic_edges").is_some()
+                {
+                    self.0.iterations.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            fn enter(&self, _: &SpanId) {}
+            fn exit(&self, _: &SpanId) {}
+        }
+
+        let counter = Arc::new(Counter::default());
+        let candidates = vec![make_candidate("a", 10.0), make_candidate("b", 10.0)];
+        let ss = make_space_for(&[("a", vec![iv(0.0, 100.0)]), ("b", vec![iv(0.0, 100.0)])]);
+        tracing::subscriber::with_default(Counting(counter.clone()), || {
+            schedule_segment(&mut Schedule::new(), candidates, &ss, iv(0.0, 100.0), 5);
+        });
+        assert_eq!(counter.spans.load(Ordering::Relaxed), 1);
+        assert_eq!(counter.iterations.load(Ordering::Relaxed), 2);
+    }
+
     // ── is_done ───────────────────────────────────────────────────────
 
     #[test]
@@ -290,6 +762,44 @@ mod tests {
         assert!(schedule.contains_task("b"));
     }
 
+    #[test]
+    fn schedule_segment_evaluates_edges_against_the_schedule() {
+        use crate::constraints::DynConstraintKind;
+
+        let dependence = DynConstraintKind::Dependence;
+        let mut index: DynamicConstraintIndex<'_, DynConstraintKind, Second> =
+            DynamicConstraintIndex::from_edges([("a".to_string(), "c".to_string(), &dependence)]);
+        let mut schedule = Schedule::new();
+        let candidates = vec![
+            make_candidate("a", 10.0),
+            Candidate::new(TestTask::new("b", 10.0).with_priority(5), "b"),
+            Candidate::new(TestTask::new("c", 10.0).with_priority(9), "c"),
+        ];
+        let ss = make_space_for(&[
+            ("a", vec![iv(0.0, 100.0)]),
+            ("b", vec![iv(0.0, 100.0)]),
+            ("c", vec![iv(0.0, 100.0)]),
+        ]);
+
+        schedule_segment_traced(
+            &mut schedule,
+            candidates,
+            &ss,
+            iv(0.0, 100.0),
+            1,
+            SegmentHooks {
+                edges: Some(&mut index),
+                ..SegmentHooks::default()
+            },
+        );
+
+        // "c" outranks the others but waits for "a".
+        let order: Vec<_> = schedule.iter().map(|(id, _)| id).collect();
+        assert_eq!(order, ["b", "a", "c"]);
+        // Placing "b" leaves the cached result for "c" valid.
+        assert_eq!(index.edges_evaluated(), 2);
+    }
+
     #[test]
     fn schedule_segment_impossible_task_skipped() {
         let mut schedule = Schedule::new();
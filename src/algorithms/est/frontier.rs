@@ -0,0 +1,392 @@
+//! Timelines the EST loop places candidates on.
+//!
+//! A single-resource run fills one [`Schedule`]; a pool run fills every
+//! resource of a [`ResourcePool`], each behind its own cursor. The loop sees
+//! both as a [`Frontier`] with one lane per timeline, holding the
+//! candidates' windows there and the cursor they are evaluated from. A
+//! candidate's metrics are taken over the lanes it may use:
+//!
+//! - EST is the earliest start on any of them, and that lane is where the
+//!   candidate would be placed (ties go to the lowest resource ID);
+//! - flexibility is summed across them;
+//! - the deadline is the latest one across them.
+//!
+//! With a single lane these are the lane's own metrics.
+
+use std::collections::HashMap;
+
+use crate::constraints::{ConsumableBudget, DynamicConstraint, PowerEnvelope, SchedulingContext};
+use crate::schedule::{ResourcePool, Schedule};
+use crate::scheduling_block::Task;
+use crate::solution_space::{Interval, SolutionSpace, TimeGrid};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+use super::candidate::Candidate;
+use super::engine::{refresh_metrics, DynamicEdges};
+use super::metrics::{compute_metrics, Metrics};
+
+/// Time between the task placed last on a lane and the next one, on top of
+/// the former's gap.
+pub(crate) type Delay<'a, T, U> = dyn Fn(&T, &T) -> Quantity<U> + 'a;
+
+/// Where placements go.
+enum Target<'a, U: Unit> {
+    Schedule {
+        schedule: &'a mut Schedule<U>,
+        space: &'a SolutionSpace<U>,
+    },
+    /// Placements live in the pool's schedules; `blank` and `blank_space`
+    /// only fill the single-schedule fields of the context.
+    Pool {
+        pool: &'a mut ResourcePool<U>,
+        blank: Schedule<U>,
+        blank_space: SolutionSpace<U>,
+    },
+}
+
+impl<U: Unit> Target<'_, U> {
+    fn context(&self) -> SchedulingContext<'_, U> {
+        match self {
+            Self::Schedule { schedule, space } => SchedulingContext::new(schedule, space),
+            Self::Pool {
+                pool,
+                blank,
+                blank_space,
+            } => SchedulingContext::new(blank, blank_space).with_resources(pool.schedules()),
+        }
+    }
+
+    /// `true` if `task_id` may be placed on the resource of a lane.
+    fn admits(&self, task_id: &str, resource: Option<&str>) -> bool {
+        match (self, resource) {
+            (Self::Pool { pool, .. }, Some(resource)) => pool.is_compatible(task_id, resource),
+            _ => true,
+        }
+    }
+}
+
+/// One timeline: the schedule, or one resource of the pool.
+struct Lane<'a, T, U: Unit> {
+    /// Resource of the lane in a pool run.
+    resource: Option<Id>,
+    /// Static windows of the candidates.
+    space: &'a SolutionSpace<U>,
+    /// `space` narrowed by the dynamic edges, once they are evaluated.
+    narrowed: Option<SolutionSpace<U>>,
+    /// Earliest start of the next placement.
+    cursor: Quantity<U>,
+    /// Task placed last, kept while a delay applies.
+    previous: Option<T>,
+}
+
+impl<T, U: Unit> Lane<'_, T, U> {
+    fn windows(&self) -> &SolutionSpace<U> {
+        self.narrowed.as_ref().unwrap_or(self.space)
+    }
+}
+
+/// The lanes of a run and where their placements go.
+pub(crate) struct Frontier<'a, T, U: Unit> {
+    target: Target<'a, U>,
+    lanes: Vec<Lane<'a, T, U>>,
+    horizon: Interval<U>,
+    grid: Option<&'a TimeGrid<U>>,
+    delay: Option<&'a Delay<'a, T, U>>,
+}
+
+impl<'a, T: Task<U>, U: Unit> Frontier<'a, T, U> {
+    /// One lane filling `schedule` from the start of `horizon`.
+    ///
+    /// Cursors are snapped up to `grid`, and each candidate is evaluated
+    /// `delay` after the task placed before it.
+    pub(crate) fn single(
+        schedule: &'a mut Schedule<U>,
+        space: &'a SolutionSpace<U>,
+        horizon: Interval<U>,
+        grid: Option<&'a TimeGrid<U>>,
+        delay: Option<&'a Delay<'a, T, U>>,
+    ) -> Self {
+        let lane = Lane {
+            resource: None,
+            space,
+            narrowed: None,
+            cursor: snap(grid, horizon.start()),
+            previous: None,
+        };
+        Self {
+            target: Target::Schedule { schedule, space },
+            lanes: vec![lane],
+            horizon,
+            grid,
+            delay,
+        }
+    }
+
+    /// One lane per resource of `pool` with windows in `spaces`, in
+    /// resource ID order.
+    ///
+    /// Each lane starts at the horizon start or after the last entry already
+    /// on its resource, whichever is later.
+    pub(crate) fn pool(
+        pool: &'a mut ResourcePool<U>,
+        spaces: &'a HashMap<Id, SolutionSpace<U>>,
+        horizon: Interval<U>,
+        grid: Option<&'a TimeGrid<U>>,
+        delay: Option<&'a Delay<'a, T, U>>,
+    ) -> Self {
+        let lanes = pool
+            .resource_ids()
+            .into_iter()
+            .filter_map(|resource| {
+                let space = spaces.get(resource)?;
+                let start = pool
+                    .schedule(resource)
+                    .and_then(|s| s.latest_end())
+                    .filter(|end| end.value() > horizon.start().value())
+                    .unwrap_or(horizon.start());
+                Some(Lane {
+                    resource: Some(resource.to_owned()),
+                    space,
+                    narrowed: None,
+                    cursor: snap(grid, start),
+                    previous: None,
+                })
+            })
+            .collect();
+        Self {
+            target: Target::Pool {
+                pool,
+                blank: Schedule::new(),
+                blank_space: SolutionSpace::new(),
+            },
+            lanes,
+            horizon,
+            grid,
+            delay,
+        }
+    }
+
+    pub(crate) fn horizon(&self) -> Interval<U> {
+        self.horizon
+    }
+
+    /// Earliest cursor of the lanes; the horizon end if there are none.
+    pub(crate) fn cursor(&self) -> Quantity<U> {
+        self.lanes
+            .iter()
+            .map(|lane| lane.cursor)
+            .min_by(|a, b| a.value().total_cmp(&b.value()))
+            .unwrap_or(self.horizon.end())
+    }
+
+    /// Context over the placements so far.
+    pub(crate) fn context(&self) -> SchedulingContext<'_, U> {
+        self.target.context()
+    }
+
+    /// Sets the windows of every candidate with incoming edges, or under a
+    /// power envelope, to its static windows on each lane intersected with
+    /// what the edges and the envelope admit against the placements so far,
+    /// and returns the number of edges computed.
+    ///
+    /// Edges are evaluated over the whole horizon rather than the remaining
+    /// part of it, so that results cached by the index stay valid while the
+    /// cursors move.
+    pub(crate) fn narrow(
+        &mut self,
+        edges: &mut dyn DynamicEdges<U>,
+        candidates: &[Candidate<T, U>],
+        consumables: Option<&ConsumableBudget>,
+        power: Option<&PowerEnvelope>,
+    ) -> usize {
+        let horizon = self.horizon;
+        for lane in &mut self.lanes {
+            if lane.narrowed.is_none() {
+                let mut narrowed = SolutionSpace::with_capacity(candidates.len());
+                for c in candidates {
+                    if let Some(windows) = lane.space.get_intervals(c.task_id()) {
+                        narrowed.set_intervals(c.task_id(), windows.as_slice().to_vec());
+                    }
+                }
+                lane.narrowed = Some(narrowed);
+            }
+        }
+
+        let mut ctx = self.target.context();
+        if let Some(budget) = consumables {
+            ctx = ctx.with_consumables(budget);
+        }
+        let before = edges.evaluated();
+        for c in candidates {
+            let ctx = ctx
+                .for_target(c.task_id())
+                .with_target_size(c.task().size_on_axis());
+            let admitted = edges.admitted(c.task_id(), horizon, &ctx);
+            let powered = power.map(|envelope| envelope.compute_intervals(horizon, "", &ctx));
+            let admitted = match (admitted, powered) {
+                (Some(admitted), Some(powered)) => {
+                    let both = admitted.intersection(&powered);
+                    edges.recycle(admitted);
+                    both
+                }
+                (Some(admitted), None) | (None, Some(admitted)) => admitted,
+                (None, None) => continue,
+            };
+            for lane in &mut self.lanes {
+                let windows = lane
+                    .space
+                    .get_intervals(c.task_id())
+                    .map(|set| set.intersection(&admitted))
+                    .unwrap_or_default();
+                if let Some(narrowed) = lane.narrowed.as_mut() {
+                    narrowed.set_intervals(c.task_id(), windows.into_inner());
+                }
+            }
+            edges.recycle(admitted);
+        }
+        edges.evaluated() - before
+    }
+
+    /// Recomputes the metrics of `candidates` against what the lanes have
+    /// left, and returns the number of windows examined.
+    pub(crate) fn refresh(&self, candidates: &mut [Candidate<T, U>]) -> usize
+    where
+        U: Send + Sync,
+    {
+        if let (Target::Schedule { .. }, [lane], None) =
+            (&self.target, self.lanes.as_slice(), self.delay)
+        {
+            let remaining = Interval::new(lane.cursor, self.horizon.end());
+            return refresh_metrics(candidates, lane.windows(), remaining);
+        }
+        let mut examined = 0;
+        for c in candidates.iter_mut() {
+            let (metrics, lane) = self.evaluate(c, None);
+            c.est = metrics.est;
+            c.deadline = metrics.deadline;
+            c.flexibility = metrics.flexibility;
+            c.lane = lane;
+            examined += metrics.windows;
+        }
+        examined
+    }
+
+    /// Metrics of `candidate` if `placed` were placed first.
+    pub(crate) fn metrics_after(
+        &self,
+        placed: &Candidate<T, U>,
+        candidate: &Candidate<T, U>,
+    ) -> Metrics<U> {
+        self.evaluate(candidate, Some(placed)).0
+    }
+
+    /// Metrics of `candidate` over the lanes it may use, and the lane of its
+    /// EST; with `after`, as if that candidate had been placed first.
+    fn evaluate(
+        &self,
+        candidate: &Candidate<T, U>,
+        after: Option<&Candidate<T, U>>,
+    ) -> (Metrics<U>, usize) {
+        let end = self.horizon.end();
+        let mut metrics = Metrics {
+            est: None,
+            deadline: None,
+            flexibility: Quantity::new(0.0),
+            windows: 0,
+        };
+        let mut flexibility = 0.0;
+        let mut best = 0;
+        for (i, lane) in self.lanes.iter().enumerate() {
+            if !self
+                .target
+                .admits(candidate.task_id(), lane.resource.as_deref())
+            {
+                continue;
+            }
+            let moved = after
+                .filter(|p| p.lane == i)
+                .and_then(|p| Some((p, p.get_interval()?)));
+            let (cursor, previous) = match moved {
+                Some((p, interval)) => (
+                    snap(self.grid, interval.end() + p.task().gap_after()),
+                    Some(p.task()),
+                ),
+                None => (lane.cursor, lane.previous.as_ref()),
+            };
+            let earliest = match (self.delay, previous) {
+                (Some(delay), Some(previous)) => cursor + delay(previous, candidate.task()),
+                _ => cursor,
+            };
+            if earliest.value() >= end.value() {
+                continue;
+            }
+
+            let on_lane = compute_metrics(
+                candidate.task(),
+                candidate.task_id(),
+                lane.windows(),
+                Interval::new(earliest, end),
+            );
+            metrics.windows += on_lane.windows;
+            flexibility += on_lane.flexibility.value();
+            if let Some(est) = on_lane.est {
+                if metrics.est.is_none_or(|best| est.value() < best.value()) {
+                    metrics.est = Some(est);
+                    best = i;
+                }
+            }
+            if let Some(deadline) = on_lane.deadline {
+                if metrics
+                    .deadline
+                    .is_none_or(|latest| deadline.value() > latest.value())
+                {
+                    metrics.deadline = Some(deadline);
+                }
+            }
+        }
+        metrics.flexibility = Quantity::new(flexibility);
+        (metrics, best)
+    }
+
+    /// Places `candidate` at its EST on its lane, and advances the lane's
+    /// cursor past it and its gap.
+    ///
+    /// Returns `None`, leaving everything as is, if the candidate has no EST
+    /// or the placement is refused.
+    pub(crate) fn place(&mut self, candidate: &Candidate<T, U>) -> Option<Interval<U>> {
+        let interval = candidate.get_interval()?;
+        let lane = self.lanes.get_mut(candidate.lane)?;
+        let added = match (&mut self.target, lane.resource.as_deref()) {
+            (Target::Schedule { schedule, .. }, _) => {
+                schedule.add(candidate.task_id(), interval).is_ok()
+            }
+            (Target::Pool { pool, .. }, Some(resource)) => {
+                pool.add(candidate.task_id(), resource, interval).is_ok()
+            }
+            (Target::Pool { .. }, None) => false,
+        };
+        if !added {
+            return None;
+        }
+        // Because intervals are half-open [start, end), the next task may
+        // begin exactly at `interval.end()` without overlapping — no
+        // epsilon offset is needed.
+        lane.cursor = snap(self.grid, interval.end() + candidate.task().gap_after());
+        Some(interval)
+    }
+
+    /// Keeps the task of `candidate`, just placed, as the one the delay of
+    /// the next candidate on its lane is measured from.
+    pub(crate) fn retire(&mut self, candidate: Candidate<T, U>) {
+        if self.delay.is_some() {
+            if let Some(lane) = self.lanes.get_mut(candidate.lane) {
+                lane.previous = Some(candidate.task);
+            }
+        }
+    }
+}
+
+fn snap<U: Unit>(grid: Option<&TimeGrid<U>>, t: Quantity<U>) -> Quantity<U> {
+    grid.map_or(t, |g| g.snap_up(t))
+}
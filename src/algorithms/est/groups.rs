@@ -0,0 +1,212 @@
+//! Group declarations of the scheduled blocks, enforced by the loop.
+//!
+//! Edges express a relation between two tasks, and the loop evaluates an
+//! edge only for its target. That is enough for precedence, but not for
+//! declarations over a whole group, which must hold whichever member is
+//! placed first. An [`AlternativeGroup`], for instance, connects its members
+//! with `Exclusive` edges along the topological order; if the later member
+//! outranks the earlier one, nothing stops the earlier member from being
+//! placed as well. An [`ExclusionGroup`] has the same gap with its
+//! `Disjoint` edges.
+//!
+//! A [`ContiguousGroup`] declared on a block has no edges at all: whether a
+//! task may go somewhere depends on where every member already is, and it
+//! concerns foreign tasks as much as members.
+//!
+//! [`BlockEdges`] evaluates such groups next to the edge index: a member of
+//! an alternative group has no window once another member is placed, a
+//! member of an exclusion group avoids every other member placed so far, on
+//! every resource, and every task is kept where it does not break a
+//! contiguous group.
+
+use crate::constraints::{
+    ContiguousGroup, DynamicConstraint, DynamicConstraintIndex, SchedulingContext,
+};
+use crate::scheduling_block::{AlternativeGroup, ExclusionGroup, SchedulingBlock, Task};
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::Unit;
+
+use super::engine::DynamicEdges;
+
+/// Dynamic edges and group declarations of the blocks of one run.
+pub(crate) struct BlockEdges<'a, D, U: Unit> {
+    index: DynamicConstraintIndex<'a, D, U>,
+    alternatives: Vec<&'a AlternativeGroup>,
+    exclusions: Vec<&'a ExclusionGroup>,
+    contiguous: Vec<&'a ContiguousGroup>,
+}
+
+impl<'a, D, U: Unit> BlockEdges<'a, D, U> {
+    /// Evaluates `index` together with the groups declared on `blocks`.
+    pub(crate) fn new<T, E>(
+        index: DynamicConstraintIndex<'a, D, U>,
+        blocks: &'a [SchedulingBlock<T, U, D, E>],
+    ) -> Self
+    where
+        T: Task<U>,
+        E: petgraph::EdgeType,
+    {
+        Self {
+            index,
+            alternatives: blocks.iter().flat_map(|b| b.alternatives()).collect(),
+            exclusions: blocks.iter().flat_map(|b| b.exclusions()).collect(),
+            contiguous: blocks.iter().flat_map(|b| b.contiguous_groups()).collect(),
+        }
+    }
+
+    /// `true` if there is nothing to evaluate.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.index.target_count() == 0
+            && self.alternatives.is_empty()
+            && self.exclusions.is_empty()
+            && self.contiguous.is_empty()
+    }
+
+    /// `true` if another member of an alternative group of `task_id` is
+    /// already placed.
+    fn alternative_chosen(&self, task_id: &str, ctx: &SchedulingContext<U>) -> bool {
+        self.alternatives
+            .iter()
+            .filter(|group| group.contains(task_id))
+            .flat_map(|group| group.members())
+            .any(|m| m != task_id && ctx.is_placed(m))
+    }
+
+    /// Parts of `range` where `task_id` overlaps no other member of its
+    /// exclusion groups, on any resource; `None` if it belongs to none.
+    fn exclusion_free(
+        &self,
+        task_id: &str,
+        range: Interval<U>,
+        ctx: &SchedulingContext<U>,
+    ) -> Option<IntervalSet<U>> {
+        let mut groups = self
+            .exclusions
+            .iter()
+            .filter(|group| group.contains(task_id))
+            .peekable();
+        groups.peek()?;
+        // Milestones occupy no time, so they never overlap.
+        if range.is_empty() {
+            return Some(IntervalSet::from(range));
+        }
+        let schedules: Vec<_> = match ctx.resources {
+            Some(resources) => resources.values().collect(),
+            None => vec![ctx.schedule],
+        };
+        let busy: Vec<_> = groups
+            .flat_map(|group| group.members())
+            .filter(|m| *m != task_id)
+            .flat_map(|m| schedules.iter().filter_map(|s| s.get_interval(m.as_str())))
+            .filter(|placement| !placement.is_empty())
+            .collect();
+        Some(IntervalSet::from(busy).complement(range))
+    }
+}
+
+impl<D: DynamicConstraint<U>, U: Unit> DynamicEdges<U> for BlockEdges<'_, D, U> {
+    fn admitted(
+        &mut self,
+        task_id: &str,
+        range: Interval<U>,
+        ctx: &SchedulingContext<U>,
+    ) -> Option<IntervalSet<U>> {
+        if self.alternative_chosen(task_id, ctx) {
+            return Some(IntervalSet::new());
+        }
+        let free = self.exclusion_free(task_id, range, ctx);
+        let kept = self
+            .contiguous
+            .iter()
+            .map(|group| group.available(range, task_id, ctx.schedule));
+        let index = &mut self.index;
+        let mut admitted = index.admitted(task_id, range, ctx);
+        for kept in free.into_iter().chain(kept) {
+            admitted = Some(match admitted {
+                Some(set) => {
+                    let both = set.intersection(&kept);
+                    index.recycle(set);
+                    both
+                }
+                None => kept,
+            });
+        }
+        admitted
+    }
+
+    fn recycle(&mut self, set: IntervalSet<U>) {
+        self.index.recycle(set);
+    }
+
+    fn evaluated(&self) -> usize {
+        self.index.edges_evaluated()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::DynConstraintKind;
+    use crate::schedule::Schedule;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+    use std::collections::HashMap;
+
+    #[test]
+    fn alternatives_exclude_each_other_in_both_directions() {
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        for id in ["lamp", "sky", "other"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        block
+            .add_alternatives(AlternativeGroup::new("calibrator", ["lamp", "sky"]))
+            .unwrap();
+        let blocks = [block];
+        let mut edges = BlockEdges::new(DynamicConstraintIndex::from_blocks(&blocks), &blocks);
+        assert!(!edges.is_empty());
+
+        let ss = SolutionSpace::new();
+        for (placed, excluded) in [("lamp", "sky"), ("sky", "lamp")] {
+            let mut schedule = Schedule::new();
+            schedule.add(placed, iv(0.0, 10.0)).unwrap();
+            let ctx = SchedulingContext::new(&schedule, &ss);
+            let admitted = edges.admitted(excluded, iv(0.0, 100.0), &ctx);
+            assert_eq!(admitted, Some(IntervalSet::new()));
+            assert_eq!(edges.admitted("other", iv(0.0, 100.0), &ctx), None);
+        }
+    }
+
+    #[test]
+    fn exclusions_keep_members_apart_in_both_directions() {
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        for id in ["a", "b"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        block
+            .add_exclusion(ExclusionGroup::new("crew", ["a", "b"]))
+            .unwrap();
+        let blocks = [block];
+        let mut edges = BlockEdges::new(DynamicConstraintIndex::from_blocks(&blocks), &blocks);
+
+        let ss = SolutionSpace::new();
+        for (placed, other) in [("a", "b"), ("b", "a")] {
+            let mut busy = Schedule::new();
+            busy.add(placed, iv(20.0, 30.0)).unwrap();
+            let resources = HashMap::from([
+                ("r1".to_string(), busy),
+                ("r2".to_string(), Schedule::new()),
+            ]);
+            let blank = Schedule::new();
+            let ctx = SchedulingContext::new(&blank, &ss).with_resources(&resources);
+            let admitted = edges.admitted(other, iv(0.0, 100.0), &ctx).unwrap();
+            assert_eq!(admitted.as_slice(), &[iv(0.0, 20.0), iv(30.0, 100.0)]);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Ranking that respects the topological order of hard edges.
+//!
+//! The plain [`engine`](super::engine) ranks every candidate on its own
+//! metrics, so the downstream end of a long `Dependence`/`Consecutive` chain
+//! may win before its prerequisites and push them past the horizon. With
+//! [`SegmentHooks::layers`](super::engine::SegmentHooks::layers) set, each
+//! candidate carries its topological layer and, among the feasible
+//! candidates, only those of the lowest remaining layer compete; within a
+//! layer the usual ranking applies.
+//!
+//! [`LayeredScheduler`] exposes the loop as a [`SchedulingAlgorithm`], so
+//! front ends can pick it by name from a [`Registry`](crate::registry::Registry).
+
+use std::collections::HashMap;
+
+use crate::algorithms::SchedulingAlgorithm;
+use crate::constraints::DynConstraintKind;
+use crate::schedule::Schedule;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+use qtty::Unit;
+
+use super::candidate::Candidate;
+use super::ESTScheduler;
+
+/// [`ESTScheduler::schedule_layered`] as a [`SchedulingAlgorithm`].
+///
+/// Blocks whose dependency graph has a cycle are refused:
+/// [`schedule`](SchedulingAlgorithm::schedule) leaves every task
+/// unscheduled and [`try_schedule`](SchedulingAlgorithm::try_schedule)
+/// reports the cycle.
+#[derive(Debug, Clone, Default)]
+pub struct LayeredScheduler {
+    inner: ESTScheduler,
+}
+
+impl LayeredScheduler {
+    /// Runs `scheduler`'s loop prerequisites first.
+    pub fn new(scheduler: ESTScheduler) -> Self {
+        Self { inner: scheduler }
+    }
+}
+
+impl<T, U, E> SchedulingAlgorithm<T, U, DynConstraintKind, E> for LayeredScheduler
+where
+    T: Task<U> + Clone,
+    U: Unit + Send + Sync,
+    E: petgraph::EdgeType,
+{
+    fn schedule(
+        &self,
+        blocks: &[SchedulingBlock<T, U, DynConstraintKind, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> Schedule<U> {
+        self.inner
+            .schedule_layered(blocks, solution_space, horizon)
+            .unwrap_or_default()
+    }
+}
+
+/// Stable-sorts ranked `candidates` by layer, feasible ones first, so the
+/// ranking holds within each layer. Tasks missing from `layers` are in
+/// layer 0.
+pub(crate) fn lowest_layer_first<T, U>(
+    candidates: &mut [Candidate<T, U>],
+    layers: &HashMap<Id, usize>,
+) where
+    T: Task<U>,
+    U: Unit,
+{
+    let layer = |c: &Candidate<T, U>| layers.get(c.task_id()).copied().unwrap_or(0);
+    candidates.sort_by_key(|c| (c.is_impossible(), layer(c)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::est::engine::{schedule_segment_traced, SegmentHooks};
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    fn candidate(id: &str, priority: i32) -> Candidate<TestTask, Second> {
+        Candidate::new(TestTask::new(id, 10.0).with_priority(priority), id)
+    }
+
+    fn schedule_segment_layered(
+        schedule: &mut Schedule<Second>,
+        candidates: Vec<Candidate<TestTask, Second>>,
+        solution_space: &SolutionSpace<Second>,
+        horizon: Interval<Second>,
+        endangered_threshold: u32,
+        layers: &HashMap<Id, usize>,
+    ) {
+        schedule_segment_traced(
+            schedule,
+            candidates,
+            solution_space,
+            horizon,
+            endangered_threshold,
+            SegmentHooks {
+                layers: Some(layers),
+                ..SegmentHooks::default()
+            },
+        );
+    }
+
+    fn space(ids: &[&str]) -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for id in ids {
+            ss.set_intervals(*id, vec![iv(0.0, 100.0)]);
+        }
+        ss
+    }
+
+    #[test]
+    fn prerequisites_go_first() {
+        let layers = HashMap::from([("up".to_string(), 0), ("down".to_string(), 1)]);
+        let mut schedule = Schedule::new();
+        schedule_segment_layered(
+            &mut schedule,
+            vec![candidate("down", 10), candidate("up", 0)],
+            &space(&["down", "up"]),
+            iv(0.0, 100.0),
+            1,
+            &layers,
+        );
+        let order: Vec<_> = schedule.iter().map(|(id, _)| id).collect();
+        assert_eq!(order, vec!["up", "down"]);
+    }
+
+    #[test]
+    fn impossible_low_layer_does_not_block() {
+        let layers = HashMap::from([("up".to_string(), 0), ("down".to_string(), 1)]);
+        let mut schedule = Schedule::new();
+        schedule_segment_layered(
+            &mut schedule,
+            vec![candidate("down", 0), candidate("up", 0)],
+            &space(&["down"]),
+            iv(0.0, 100.0),
+            1,
+            &layers,
+        );
+        assert_eq!(schedule.get_interval("down"), Some(iv(0.0, 10.0)));
+        assert!(!schedule.contains_task("up"));
+    }
+
+    #[test]
+    fn cyclic_blocks_are_refused() {
+        use crate::scheduling_block::SchedulingError;
+
+        let mut block = SchedulingBlock::<TestTask, Second, DynConstraintKind>::new();
+        for id in ["a", "b"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        block.add_edge_unchecked("a", "b", DynConstraintKind::Dependence);
+        block.add_edge_unchecked("b", "a", DynConstraintKind::Dependence);
+        let blocks = [block];
+        let (ss, horizon) = (space(&["a", "b"]), iv(0.0, 100.0));
+        let is_cycle =
+            |e: &SchedulingError| matches!(e, SchedulingError::Cycle(edges) if edges.len() == 2);
+
+        let est = ESTScheduler::default();
+        assert!(is_cycle(
+            &est.schedule_layered(&blocks, &ss, horizon).unwrap_err()
+        ));
+        assert!(is_cycle(
+            &est.try_schedule(&blocks, &ss, horizon).unwrap_err()
+        ));
+        assert!(est.schedule(&blocks, &ss, horizon).is_empty());
+        assert_eq!(
+            est.schedule_result(&blocks, &ss, horizon)
+                .unscheduled()
+                .count(),
+            2
+        );
+
+        let layered = LayeredScheduler::default();
+        assert!(is_cycle(
+            &layered.try_schedule(&blocks, &ss, horizon).unwrap_err()
+        ));
+        assert!(layered.schedule(&blocks, &ss, horizon).is_empty());
+    }
+}
@@ -0,0 +1,387 @@
+//! Scheduling under an execution limit, with a degradation ladder.
+//!
+//! A run has two phases: the plain EST loop, then an **improvement** pass
+//! that places leftover tasks into gaps the cursor has moved past. When the
+//! [`ExecutionLimit`] runs out, the run degrades the same way whatever the
+//! phase, so the result always has the same shape:
+//!
+//! 1. **During the loop** — only endangered candidates are placed from then
+//!    on: the loop continues while the top-ranked candidate is endangered,
+//!    then stops. The remaining candidates are omitted and the improvement
+//!    pass is skipped.
+//! 2. **During the improvement pass** — the pass stops; the leftover tasks
+//!    not tried yet are omitted.
+//!
+//! A limit can also carry a cancel flag, set from another thread. A
+//! cancelled run does not degrade: it stops at its next placement attempt,
+//! in either phase, and every task not placed yet is omitted.
+//!
+//! [`LimitedSchedule::is_partial`] flags a degraded result, and
+//! [`omitted`](LimitedSchedule::omitted) lists the tasks the limit cost.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::clock::Instant;
+use crate::schedule::Schedule;
+use crate::scheduling_block::Task;
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+use qtty::Unit;
+
+use super::candidate::Candidate;
+use super::engine::SegmentOutcome;
+use super::free_space;
+
+/// Wall-clock and iteration budget of a run.
+///
+/// Both bounds are optional; the run is limited by whichever runs out first.
+/// An iteration is one placement attempt, in either phase.
+///
+/// Two limits are equal if their bounds are and they share the same cancel
+/// flag, if any.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionLimit {
+    time: Option<Duration>,
+    iterations: Option<usize>,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+impl ExecutionLimit {
+    /// A limit that never runs out.
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Runs out once `time` has elapsed since the run started.
+    pub fn with_time(mut self, time: Duration) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    /// Runs out after `iterations` placement attempts.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = Some(iterations);
+        self
+    }
+
+    /// Stops the run at its next placement attempt once `flag` is set.
+    pub fn with_cancel(mut self, flag: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(flag);
+        self
+    }
+
+    /// `true` once the cancel flag has been set.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    }
+
+    fn exhausted(&self, started: Instant, iterations: usize) -> bool {
+        self.iterations.is_some_and(|max| iterations >= max)
+            || self.time.is_some_and(|max| started.elapsed() >= max)
+    }
+}
+
+impl PartialEq for ExecutionLimit {
+    fn eq(&self, other: &Self) -> bool {
+        self.time == other.time
+            && self.iterations == other.iterations
+            && match (&self.cancel, &other.cancel) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            }
+    }
+}
+
+impl Eq for ExecutionLimit {}
+
+/// Phase of a run in which the limit ran out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitPhase {
+    /// The plain EST loop.
+    Loop,
+    /// The gap-filling improvement pass.
+    Improvement,
+}
+
+/// Result of [`ESTScheduler::schedule_limited`](super::ESTScheduler::schedule_limited).
+#[derive(Debug, Clone)]
+pub struct LimitedSchedule<U: Unit> {
+    pub schedule: Schedule<U>,
+    /// Phase in which the limit ran out, or `None` if the run completed.
+    pub exhausted_in: Option<LimitPhase>,
+    /// Tasks left out because the limit ran out, in rank order.
+    pub omitted: Vec<Id>,
+    /// `true` if the improvement pass never ran.
+    pub improvement_skipped: bool,
+    /// `true` if the run stopped because the cancel flag was set.
+    pub cancelled: bool,
+    /// Placement attempts made, in both phases.
+    pub iterations: usize,
+}
+
+impl<U: Unit> LimitedSchedule<U> {
+    /// `true` if the limit ran out before the run completed.
+    pub fn is_partial(&self) -> bool {
+        self.exhausted_in.is_some()
+    }
+}
+
+/// An [`ExecutionLimit`] being spent by a run.
+#[derive(Debug)]
+pub(crate) struct LimitRun {
+    limit: ExecutionLimit,
+    started: Instant,
+    iterations: usize,
+    degraded: bool,
+    cancelled: bool,
+}
+
+impl LimitRun {
+    /// Starts spending `limit`.
+    pub(crate) fn new(limit: ExecutionLimit) -> Self {
+        Self {
+            limit,
+            started: Instant::now(),
+            iterations: 0,
+            degraded: false,
+            cancelled: false,
+        }
+    }
+
+    /// Counts a placement attempt of the loop, whose top-ranked candidate is
+    /// `leader`, or returns `false` if the loop must stop instead.
+    pub(crate) fn admits<T, U>(
+        &mut self,
+        leader: &Candidate<T, U>,
+        endangered_threshold: u32,
+    ) -> bool
+    where
+        T: Task<U>,
+        U: Unit,
+    {
+        if self.limit.is_cancelled() {
+            (self.degraded, self.cancelled) = (true, true);
+            return false;
+        }
+        if !self.degraded && self.limit.exhausted(self.started, self.iterations) {
+            self.degraded = true;
+        }
+        if self.degraded && !leader.is_endangered(endangered_threshold) {
+            return false;
+        }
+        self.iterations += 1;
+        true
+    }
+
+    /// Runs the improvement pass on what the loop that built `schedule`
+    /// left in `outcome`, unless the limit ran out during the loop.
+    pub(crate) fn finish<T, U>(
+        mut self,
+        mut schedule: Schedule<U>,
+        outcome: SegmentOutcome<T, U>,
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> LimitedSchedule<U>
+    where
+        T: Task<U>,
+        U: Unit,
+    {
+        if self.degraded {
+            return LimitedSchedule {
+                schedule,
+                exhausted_in: Some(LimitPhase::Loop),
+                omitted: outcome
+                    .remaining
+                    .iter()
+                    .map(|c| c.task_id().to_owned())
+                    .collect(),
+                improvement_skipped: true,
+                cancelled: self.cancelled,
+                iterations: self.iterations,
+            };
+        }
+
+        // Improvement: place leftovers into the gaps, highest priority first.
+        let mut leftover = outcome.dropped;
+        leftover.extend(outcome.remaining);
+        leftover.sort_by(|a, b| {
+            b.priority()
+                .cmp(&a.priority())
+                .then_with(|| a.task_id().cmp(b.task_id()))
+        });
+        let mut exhausted_in = None;
+        let mut omitted = Vec::new();
+        for candidate in leftover {
+            if exhausted_in.is_none() && self.limit.is_cancelled() {
+                self.cancelled = true;
+            }
+            if self.cancelled
+                || exhausted_in.is_some()
+                || self.limit.exhausted(self.started, self.iterations)
+            {
+                exhausted_in = Some(LimitPhase::Improvement);
+                omitted.push(candidate.task_id().to_owned());
+                continue;
+            }
+            self.iterations += 1;
+            let space = free_space(
+                &schedule,
+                solution_space,
+                std::slice::from_ref(&candidate),
+                horizon,
+            );
+            let size = candidate.task().size_on_axis();
+            if let Some(start) = space.find_earliest_fit_for(candidate.task_id(), size) {
+                let _ = schedule.add(candidate.task_id(), Interval::new(start, start + size));
+            }
+        }
+
+        LimitedSchedule {
+            schedule,
+            exhausted_in,
+            omitted,
+            improvement_skipped: false,
+            cancelled: self.cancelled,
+            iterations: self.iterations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::est::engine::{schedule_segment_traced, SegmentHooks};
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    fn schedule_limited(
+        candidates: Vec<Candidate<TestTask, Second>>,
+        solution_space: &SolutionSpace<Second>,
+        horizon: Interval<Second>,
+        endangered_threshold: u32,
+        limit: ExecutionLimit,
+    ) -> LimitedSchedule<Second> {
+        let mut run = LimitRun::new(limit);
+        let mut schedule = Schedule::new();
+        let outcome = schedule_segment_traced(
+            &mut schedule,
+            candidates,
+            solution_space,
+            horizon,
+            endangered_threshold,
+            SegmentHooks {
+                limit: Some(&mut run),
+                ..SegmentHooks::default()
+            },
+        );
+        run.finish(schedule, outcome, solution_space, horizon)
+    }
+
+    fn candidate(id: &str, size: f64) -> Candidate<TestTask, Second> {
+        Candidate::new(TestTask::new(id, size), id)
+    }
+
+    fn space(entries: &[(&str, f64, f64)]) -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for &(id, a, b) in entries {
+            ss.add_interval(id, iv(a, b));
+        }
+        ss
+    }
+
+    /// "tight" has a single start; once it is placed the cursor has passed
+    /// the room "early" needs, so "early" stays out.
+    fn fixture() -> (Vec<Candidate<TestTask, Second>>, SolutionSpace<Second>) {
+        let candidates = vec![
+            candidate("tight", 10.0),
+            candidate("free1", 10.0),
+            candidate("free2", 10.0),
+            candidate("early", 5.0),
+        ];
+        let ss = space(&[
+            ("tight", 0.0, 10.0),
+            ("free1", 0.0, 100.0),
+            ("free2", 0.0, 100.0),
+            ("early", 0.0, 12.0),
+        ]);
+        (candidates, ss)
+    }
+
+    #[test]
+    fn unlimited_run_completes_with_improvement() {
+        let (candidates, ss) = fixture();
+        let result = schedule_limited(
+            candidates,
+            &ss,
+            iv(0.0, 100.0),
+            1,
+            ExecutionLimit::unlimited(),
+        );
+        assert!(!result.is_partial());
+        assert!(!result.improvement_skipped);
+        assert!(result.omitted.is_empty());
+        assert_eq!(result.schedule.len(), 3);
+    }
+
+    #[test]
+    fn loop_exhaustion_places_endangered_only() {
+        let (candidates, ss) = fixture();
+        let result = schedule_limited(
+            candidates,
+            &ss,
+            iv(0.0, 100.0),
+            2,
+            ExecutionLimit::unlimited().with_iterations(0),
+        );
+        assert_eq!(result.exhausted_in, Some(LimitPhase::Loop));
+        assert!(result.improvement_skipped);
+        // "tight" has a single start and is endangered; the rest is omitted.
+        assert!(result.schedule.contains_task("tight"));
+        assert_eq!(result.schedule.len(), 1);
+        assert_eq!(result.omitted.len(), 3);
+    }
+
+    #[test]
+    fn improvement_exhaustion_omits_untried_leftovers() {
+        let candidates = vec![
+            candidate("a", 10.0),
+            candidate("b", 10.0),
+            candidate("c", 10.0),
+        ];
+        // Each window fits one task; the loop places one, cursor then passes
+        // the others, which only the improvement pass can reach.
+        let ss = space(&[("a", 0.0, 10.0), ("b", 0.0, 10.0), ("c", 0.0, 10.0)]);
+        let limit = ExecutionLimit::unlimited().with_iterations(2);
+        let result = schedule_limited(candidates, &ss, iv(0.0, 100.0), 1, limit);
+        assert_eq!(result.exhausted_in, Some(LimitPhase::Improvement));
+        assert!(!result.improvement_skipped);
+        assert_eq!(result.iterations, 2);
+        assert_eq!(result.omitted, vec!["c".to_string()]);
+    }
+
+    #[test]
+    fn cancelled_runs_stop_without_degrading() {
+        let flag = Arc::new(AtomicBool::new(true));
+        let limit = ExecutionLimit::unlimited().with_cancel(Arc::clone(&flag));
+        assert_ne!(limit, ExecutionLimit::unlimited());
+        assert_eq!(limit, limit.clone());
+
+        let (candidates, ss) = fixture();
+        let result = schedule_limited(candidates, &ss, iv(0.0, 100.0), 2, limit.clone());
+        assert!(result.cancelled);
+        assert_eq!(result.exhausted_in, Some(LimitPhase::Loop));
+        // Unlike an exhausted limit, not even the endangered "tight" is placed.
+        assert!(result.schedule.is_empty());
+        assert_eq!(result.omitted.len(), 4);
+
+        flag.store(false, Ordering::Relaxed);
+        let (candidates, ss) = fixture();
+        let result = schedule_limited(candidates, &ss, iv(0.0, 100.0), 2, limit);
+        assert!(!result.cancelled);
+        assert!(!result.is_partial());
+    }
+}
@@ -0,0 +1,179 @@
+//! Lookahead scoring of the top candidates.
+//!
+//! The greedy loop places the best-ranked candidate without asking what the
+//! placement does to the others. Moving the cursor past a long task can push
+//! two flexible tasks below the endangered threshold, or out of their
+//! windows altogether, to place one. A [`Lookahead`] simulates placing each
+//! of the top `k` candidates, re-evaluates every other candidate on what
+//! the lanes would have left, and counts the damage:
+//!
+//! - each flexible candidate that would become endangered counts one;
+//! - each feasible candidate that would lose its last feasible start counts
+//!   [`impossible_weight`](Lookahead::impossible_weight).
+//!
+//! The least damaging of the `k` is placed; ties go to the better-ranked
+//! one, so a placement that hurts nobody always keeps the greedy choice.
+//! Scoring costs `k` metric refreshes of the pool per iteration.
+//!
+//! Experimental: only reachable with the `unstable` feature.
+
+// The loop always carries the hook; only its constructors are gated.
+#![cfg_attr(not(feature = "unstable"), allow(dead_code))]
+
+use crate::scheduling_block::Task;
+use qtty::Unit;
+
+use super::candidate::Candidate;
+use super::frontier::Frontier;
+
+/// How many candidates to simulate, and how to weigh the damage.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "unstable")] {
+/// use virolai::algorithms::est::Lookahead;
+///
+/// let lookahead = Lookahead::new(3).with_impossible_weight(5.0);
+/// assert_eq!(lookahead.top_k, 3);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lookahead {
+    /// Number of best-ranked candidates simulated at each iteration.
+    pub top_k: usize,
+    /// Damage of a candidate that would be left without a feasible start,
+    /// relative to one that would become endangered.
+    pub impossible_weight: f64,
+}
+
+impl Lookahead {
+    /// Simulates the `top_k` best-ranked candidates; a lost task weighs as
+    /// much as two endangered ones.
+    pub fn new(top_k: usize) -> Self {
+        Self {
+            top_k,
+            impossible_weight: 2.0,
+        }
+    }
+
+    /// Sets the damage of a candidate left without a feasible start.
+    pub fn with_impossible_weight(mut self, weight: f64) -> Self {
+        self.impossible_weight = weight;
+        self
+    }
+
+    /// Index of the candidate to place among the first `top_k` of
+    /// `candidates`, which must be in rank order and refreshed against
+    /// `frontier`.
+    pub(crate) fn pick<T, U>(
+        &self,
+        candidates: &[Candidate<T, U>],
+        frontier: &Frontier<'_, T, U>,
+        endangered_threshold: u32,
+    ) -> usize
+    where
+        T: Task<U>,
+        U: Unit,
+    {
+        let mut best = (0, f64::INFINITY);
+        for (i, c) in candidates.iter().enumerate().take(self.top_k) {
+            if c.is_impossible() {
+                break;
+            }
+            let damage = self.damage(candidates, i, frontier, endangered_threshold);
+            if damage < best.1 {
+                best = (i, damage);
+            }
+            if damage == 0.0 {
+                break;
+            }
+        }
+        best.0
+    }
+
+    /// Damage to the others if `candidates[placed]` is placed first.
+    fn damage<T, U>(
+        &self,
+        candidates: &[Candidate<T, U>],
+        placed: usize,
+        frontier: &Frontier<'_, T, U>,
+        endangered_threshold: u32,
+    ) -> f64
+    where
+        T: Task<U>,
+        U: Unit,
+    {
+        candidates
+            .iter()
+            .enumerate()
+            .filter(|&(j, c)| j != placed && !c.is_impossible())
+            .map(|(_, c)| {
+                let after = frontier.metrics_after(&candidates[placed], c);
+                if after.est.is_none() {
+                    self.impossible_weight
+                } else if c.is_flexible(endangered_threshold)
+                    && after.flexibility.value() < f64::from(endangered_threshold)
+                {
+                    1.0
+                } else {
+                    0.0
+                }
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::Schedule;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    fn candidate(id: &str, size: f64, est: f64, flex: f64) -> Candidate<TestTask, Second> {
+        let mut c = Candidate::new(TestTask::new(id, size), id);
+        c.est = Some(q(est));
+        c.flexibility = q(flex);
+        c
+    }
+
+    #[test]
+    fn picks_the_least_damaging_of_the_top_k() {
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("long", vec![iv(0.0, 100.0)]);
+        ss.set_intervals("short", vec![iv(0.0, 100.0)]);
+        ss.set_intervals("x", vec![iv(0.0, 35.0)]);
+        ss.set_intervals("y", vec![iv(0.0, 40.0)]);
+        let candidates = vec![
+            candidate("long", 30.0, 0.0, 3.3),
+            candidate("short", 5.0, 0.0, 20.0),
+            candidate("x", 10.0, 0.0, 3.5),
+            candidate("y", 10.0, 0.0, 4.0),
+        ];
+        let mut schedule = Schedule::new();
+        let frontier = Frontier::single(&mut schedule, &ss, iv(0.0, 100.0), None, None);
+
+        // Placing `long` first loses `x` and endangers `y`.
+        let greedy = Lookahead::new(1).pick(&candidates, &frontier, 2);
+        assert_eq!(greedy, 0);
+        let lookahead = Lookahead::new(2);
+        assert_eq!(lookahead.damage(&candidates, 0, &frontier, 2), 3.0);
+        assert_eq!(lookahead.pick(&candidates, &frontier, 2), 1);
+    }
+
+    #[test]
+    fn harmless_leader_is_kept() {
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("a", vec![iv(0.0, 100.0)]);
+        ss.set_intervals("b", vec![iv(0.0, 100.0)]);
+        let candidates = vec![
+            candidate("a", 10.0, 0.0, 10.0),
+            candidate("b", 10.0, 0.0, 10.0),
+        ];
+        let mut schedule = Schedule::new();
+        let frontier = Frontier::single(&mut schedule, &ss, iv(0.0, 100.0), None, None);
+        assert_eq!(Lookahead::new(5).pick(&candidates, &frontier, 5), 0);
+    }
+}
@@ -30,6 +30,7 @@ use qtty::{Quantity, Unit};
 /// The earliest possible start time, or None if the task cannot fit.
 ///
 /// Note: Uses `task.size_on_axis()` to get the duration in axis units.
+#[allow(dead_code)]
 pub fn compute_est<T, A>(
     task: &T,
     task_id: &str,
@@ -43,23 +44,11 @@ where
     let intervals = solution_space.get_intervals(task_id)?;
     let task_size = task.size_on_axis();
 
-    for interval in intervals {
-        // Skip windows that end before horizon begins
-        if interval.end().value() <= horizon.start().value() {
-            continue;
-        }
-
-        // Skip windows that start after horizon ends (intervals are sorted)
-        if interval.start().value() >= horizon.end().value() {
-            break;
-        }
-
-        // Compute intersection: window ∩ horizon
-        if let Some(intersection) = interval.intersection(&horizon) {
-            // Check if task fits within the effective window
-            if intersection.duration().value() >= task_size.value() {
-                return Some(intersection.start());
-            }
+    // Windows overlapping the horizon, clipped to it: window ∩ horizon
+    for intersection in intervals.clipped(horizon) {
+        // Check if task fits within the effective window
+        if intersection.duration().value() >= task_size.value() {
+            return Some(intersection.start());
         }
     }
 
@@ -83,6 +72,7 @@ where
 /// The latest possible start time, or None if the task cannot fit.
 ///
 /// Note: Uses `task.size_on_axis()` to get the duration in axis units.
+#[allow(dead_code)]
 pub fn compute_deadline<T, A>(
     task: &T,
     task_id: &str,
@@ -96,24 +86,11 @@ where
     let intervals = solution_space.get_intervals(task_id)?;
     let task_size = task.size_on_axis();
 
-    // Search backwards from the end
-    for interval in intervals.iter().rev() {
-        // Skip windows that begin after horizon ends
-        if interval.start().value() >= horizon.end().value() {
-            continue;
-        }
-
-        // Skip windows that end before horizon begins
-        if interval.end().value() <= horizon.start().value() {
-            break;
-        }
-
-        // Compute intersection: window ∩ horizon
-        if let Some(intersection) = interval.intersection(&horizon) {
-            // Check if task fits within the effective window
-            if intersection.duration().value() >= task_size.value() {
-                return Some(intersection.end() - task_size);
-            }
+    // Search backwards through the windows clipped to the horizon
+    for intersection in intervals.clipped(horizon).rev() {
+        // Check if task fits within the effective window
+        if intersection.duration().value() >= task_size.value() {
+            return Some(intersection.end() - task_size);
         }
     }
 
@@ -128,6 +105,7 @@ where
 /// The flexibility metric indicates how many times the task could theoretically fit
 /// within its available windows. A flexibility < 1.0 means the task is impossible.
 /// A flexibility < endangered_threshold means the task is endangered.
+/// Zero-size tasks (milestones) count one per window instead.
 ///
 /// # Arguments
 ///
@@ -141,6 +119,7 @@ where
 /// The flexibility value (dimensionless ratio).
 ///
 /// Note: Uses `task.size_on_axis()` to get the duration in axis units.
+#[allow(dead_code)]
 pub fn compute_flexibility<T, A>(
     task: &T,
     task_id: &str,
@@ -160,30 +139,77 @@ where
     let task_size = task.size_on_axis();
     let mut flexibility = 0.0;
 
-    for interval in intervals {
-        // Skip windows that end before horizon begins
-        if interval.end().value() <= horizon.start().value() {
-            continue;
-        }
+    // Windows overlapping the horizon, clipped to it: window ∩ horizon
+    for intersection in intervals.clipped(horizon) {
+        let intersection_duration = intersection.duration().value();
+        let task_duration = task_size.value();
 
-        // Skip windows that start after horizon ends (intervals are sorted)
-        if interval.start().value() >= horizon.end().value() {
-            break;
+        if task_duration == 0.0 {
+            // A milestone fits every window exactly once; dividing by its
+            // zero size would make it look infinitely flexible.
+            flexibility += 1.0;
+        } else if task_duration <= intersection_duration {
+            // Check if task fits within the effective window
+            flexibility += intersection_duration / task_duration;
         }
+    }
 
-        // Compute intersection: window ∩ horizon
-        if let Some(intersection) = interval.intersection(&horizon) {
-            let intersection_duration = intersection.duration().value();
-            let task_duration = task_size.value();
+    Quantity::new(flexibility)
+}
 
-            // Check if task fits within the effective window
-            if task_duration <= intersection_duration {
-                flexibility += intersection_duration / task_duration;
-            }
+/// EST, deadline and flexibility of one task, read in a single pass.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Metrics<A: Unit> {
+    pub est: Option<Quantity<A>>,
+    pub deadline: Option<Quantity<A>>,
+    pub flexibility: Quantity<A>,
+    /// Windows overlapping the horizon that were read.
+    pub windows: usize,
+}
+
+/// Computes what [`compute_est`], [`compute_deadline`] and
+/// [`compute_flexibility`] return, with one lookup and one pass over the
+/// windows.
+pub(crate) fn compute_metrics<T, A>(
+    task: &T,
+    task_id: &str,
+    solution_space: &SolutionSpace<A>,
+    horizon: Interval<A>,
+) -> Metrics<A>
+where
+    T: Task<A>,
+    A: Unit,
+{
+    let mut metrics = Metrics {
+        est: None,
+        deadline: None,
+        flexibility: Quantity::new(0.0),
+        windows: 0,
+    };
+    let Some(intervals) = solution_space.get_intervals(task_id) else {
+        return metrics;
+    };
+    let task_size = task.size_on_axis();
+    let mut flexibility = 0.0;
+
+    for intersection in intervals.clipped(horizon) {
+        metrics.windows += 1;
+        let intersection_duration = intersection.duration().value();
+        let task_duration = task_size.value();
+
+        if task_duration == 0.0 {
+            flexibility += 1.0;
+        } else if task_duration <= intersection_duration {
+            flexibility += intersection_duration / task_duration;
+        }
+        if intersection_duration >= task_duration {
+            metrics.est.get_or_insert(intersection.start());
+            metrics.deadline = Some(intersection.end() - task_size);
         }
     }
 
-    Quantity::new(flexibility)
+    metrics.flexibility = Quantity::new(flexibility);
+    metrics
 }
 
 #[cfg(test)]
@@ -330,4 +356,40 @@ mod tests {
         // Intersection = [50, 80], duration = 30, 30/10 = 3.0
         assert!((flex.value() - 3.0).abs() < 1e-9);
     }
+
+    #[test]
+    fn single_pass_matches_the_separate_metrics() {
+        let ss = make_space(
+            "t",
+            vec![iv(0.0, 8.0), iv(20.0, 45.0), iv(60.0, 75.0), iv(90.0, 95.0)],
+        );
+        for size in [0.0, 5.0, 10.0, 30.0] {
+            let task = TestTask::new("t", size);
+            for horizon in [iv(0.0, 100.0), iv(30.0, 70.0), iv(96.0, 100.0)] {
+                let metrics = compute_metrics(&task, "t", &ss, horizon);
+                assert_eq!(metrics.est, compute_est(&task, "t", &ss, horizon));
+                assert_eq!(metrics.deadline, compute_deadline(&task, "t", &ss, horizon));
+                assert_eq!(
+                    metrics.flexibility,
+                    compute_flexibility(&task, "t", &ss, horizon)
+                );
+            }
+        }
+        let task = TestTask::new("t", 10.0);
+        assert_eq!(compute_metrics(&task, "t", &ss, iv(30.0, 70.0)).windows, 2);
+        assert_eq!(compute_metrics(&task, "x", &ss, iv(0.0, 100.0)).windows, 0);
+    }
+
+    #[test]
+    fn milestone_flexibility_counts_windows() {
+        let task = TestTask::new("m", 0.0);
+        let ss = make_space("m", vec![iv(0.0, 50.0), iv(60.0, 100.0)]);
+        let flex = compute_flexibility(&task, "m", &ss, iv(0.0, 100.0));
+        assert_eq!(flex.value(), 2.0);
+        assert_eq!(compute_est(&task, "m", &ss, iv(0.0, 100.0)), Some(q(0.0)));
+        assert_eq!(
+            compute_deadline(&task, "m", &ss, iv(0.0, 100.0)),
+            Some(q(100.0))
+        );
+    }
 }
@@ -35,36 +35,289 @@
 //!   3. Re-compute candidate metrics on the remaining horizon `[cursor, end]`
 //! - Continue until no schedulable tasks remain or cursor exceeds horizon
 //!
+//! Before the metrics are re-computed, each candidate's windows are narrowed
+//! to what its incoming dynamic edges admit against the schedule so far.
+//! Edges are evaluated through
+//! [`DynamicConstraintIndex::evaluate_memoized`] over the whole horizon, so
+//! an edge that only reads its source's placement is recomputed once the
+//! source is placed, not at every iteration. Group declarations are
+//! checked at the same point: a member of an
+//! [`AlternativeGroup`](crate::scheduling_block::AlternativeGroup) loses its
+//! windows once another member is placed, whichever of them ranks first,
+//! and no task is placed where it would break a
+//! [`ContiguousGroup`](crate::constraints::ContiguousGroup) declared with
+//! [`SchedulingBlock::add_contiguous`].
+//!
 //! ## 4. Task Gaps
 //!
 //! The algorithm supports inter-task gaps via the Task trait:
 //! - `gap_after()`: Required gap after this task completes (added to cursor)
 //! - `compute_gap_after(previous)`: Gap between two specific tasks (used in ordering)
 //!
+//! ## 5. Ranking Snapshots
+//!
+//! [`ESTScheduler::schedule_ranked`] runs the same loop and additionally
+//! records, per iteration, the top-N ranked candidates with their metrics and
+//! the criterion on which the winner beat each runner-up.
+//!
+//! ## 6. Parallel Evaluation
+//!
+//! With the `parallel` feature, candidate metrics are recomputed on rayon's
+//! global thread pool when the candidate list is large. The candidate order
+//! after each update is identical to the serial build. The EST entry points
+//! require `U: Send + Sync` in every build, so enabling the feature never
+//! changes which units they accept.
+//!
+//! ## 7. Multiple Resources
+//!
+//! [`ESTScheduler::schedule_pool`] (and the
+//! [`MultiResourceAlgorithm`](crate::algorithms::MultiResourceAlgorithm) impl)
+//! keeps one cursor per resource of a [`ResourcePool`] and picks, for the
+//! winning candidate, both the resource and the start time: its EST is the
+//! earliest over all compatible resources. Every resource is a lane of the
+//! same loop the single-resource variants run. Dynamic edges see every
+//! resource through
+//! [`SchedulingContext::resources`](crate::constraints::SchedulingContext::resources),
+//! so constraints that span resources, such as a
+//! [`MaxParallelism`](crate::constraints::MaxParallelism) over a shared
+//! family, hold across the pool.
+//!
+//! ## 8. Priority Boosts
+//!
+//! [`ESTScheduler::schedule_boosted`] applies time-windowed
+//! [`PriorityBoost`] rules: a candidate whose EST falls in a rule's window
+//! ranks with its priority raised by the rule's delta. The rules and the
+//! boosts actually used are returned with the schedule.
+//!
+//! ## 9. Transition Times
+//!
+//! [`ESTScheduler::schedule_with_transitions`] handles tasks with a
+//! [`SpatialTask`] position: after each placement, every candidate's earliest
+//! start is pushed back by the [`TransitionModel`] time from the previously
+//! placed task's position to its own, on top of `gap_after()`.
+//! [`ESTScheduler::schedule_with_setups`] does the same for tasks with a
+//! [`SetupTask`] class, delaying each by the [`SetupMatrix`] changeover from
+//! the previous task's class.
+//!
+//! ## 10. Cost Budgets
+//!
+//! [`ESTScheduler::schedule_budgeted`] handles [`CostedTask`]s: before the
+//! winning candidate is placed, its cost at the chosen interval is checked
+//! against the remaining budget, and a task that would overspend is dropped.
+//!
+//! ## 11. Topological Order
+//!
+//! [`ESTScheduler::schedule_layered`] ranks candidates by the topological
+//! layer of the `Dependence`/`Consecutive` edges first, so the members of a
+//! long chain are placed prerequisites first instead of starving behind
+//! downstream tasks with better metrics. [`LayeredScheduler`] wraps it as a
+//! [`SchedulingAlgorithm`](crate::algorithms::SchedulingAlgorithm).
+//!
+//! ## 12. Preemption
+//!
+//! With the `unstable` feature, `ESTScheduler::schedule_preemptive` lets a
+//! task left out by the plain
+//! loop evict strictly lower-priority tasks from its last window; the
+//! evicted tasks go back to the candidate pool and are rescheduled into the
+//! time left free.
+//!
+//! ## 13. Observers
+//!
+//! [`ESTScheduler::schedule_observed`] runs the plain loop and reports each
+//! iteration, every ranked candidate, and each placement or drop to a
+//! [`SchedulerObserver`], for progress displays and logging.
+//!
+//! ## 14. Tie-breaking
+//!
+//! Candidates whose metrics all tie are ordered by task ID by default.
+//! [`ESTScheduler::with_tie_break`] with [`TieBreak::Random`] ranks them
+//! first by a key derived from a seed and the task ID, so no ID is favoured
+//! systematically while the run stays reproducible from the seed; varying
+//! the seed yields diverse schedules for ensemble evaluation. The scheduler
+//! is a [`SeededAlgorithm`](crate::algorithms::SeededAlgorithm) that runs
+//! with `TieBreak::Random(seed)`, so a
+//! [`RestartsDriver`](crate::algorithms::RestartsDriver) can search over
+//! seeds.
+//!
+//! ## 15. Tracing
+//!
+//! With the `trace` feature, every segment runs in an `est_segment`
+//! `tracing` span and each iteration emits a `debug` event with the
+//! cursor, the candidate count, the number of solution-space windows
+//! examined while refreshing the metrics and the number of dynamic edges
+//! evaluated; placements and drops are `trace` events. Solution-space
+//! population and dynamic-edge evaluation are instrumented the same way.
+//!
+//! ## 16. Execution Limits
+//!
+//! [`ESTScheduler::schedule_limited`] runs under an [`ExecutionLimit`] and
+//! follows the plain loop with an improvement pass that fills gaps the
+//! cursor moved past. When the limit runs out the run degrades the same way
+//! in either phase: only endangered tasks are placed, the improvement pass is
+//! skipped, and the result lists the tasks omitted. A cancel flag on the
+//! limit stops the run outright, for callers that abandon it midway.
+//!
+//! ## 17. Objectives
+//!
+//! [`ESTScheduler::schedule_objective`] breaks ties between candidates of
+//! the same class and earliest start by the score of an [`Objective`] built
+//! from weighted soft constraints, highest first.
+//!
+//! ## 18. Time Grids
+//!
+//! [`ESTScheduler::schedule_on_grid`] only starts tasks on the points of a
+//! [`TimeGrid`], for instruments that accept commands at a fixed cadence.
+//! Windows are shrunk to the grid starts they admit and the cursor is
+//! snapped up to the grid after each placement.
+//!
+//! ## 19. Uncertain Durations
+//!
+//! [`ESTScheduler::schedule_uncertain`] plans [`UncertainTask`]s at a
+//! duration quantile, or at their nominal size followed by a protective
+//! buffer, as chosen by a [`BufferPolicy`]. Each placement is reported with
+//! the probability that the task overruns into the next one.
+//!
+//! ## 20. Priority Aging
+//!
+//! [`ESTScheduler::with_aging`] raises the priority of candidates that keep
+//! losing the ranking to tasks competing for the same start, by a bonus
+//! that grows along a [`PriorityAging`] curve with the number of iterations
+//! lost, so that medium-priority tasks are not starved in long runs. Like
+//! the other settings of the scheduler, it applies to the plain loop and to
+//! every variant of it.
+//!
+//! ## 21. Selection Heuristics
+//!
+//! [`ESTScheduler::schedule_selected`] runs the plain loop but picks each
+//! next task with a [`SelectionHeuristic`] instead of the default ranking:
+//! [`MostEndangeredFirst`], [`EarliestDeadlineFirst`],
+//! [`HighestPriorityFirst`], or any comparison over the candidates' earliest
+//! start, deadline, flexibility and priority.
+//!
+//! ## 22. Lookahead
+//!
+//! With the `unstable` feature, `ESTScheduler::with_lookahead` simulates
+//! placing each of the top few
+//! ranked candidates and counts how many others would become endangered or
+//! lose their last feasible start on the horizon left after it. The least
+//! damaging placement wins, ties going to the better-ranked candidate, so
+//! the loop stops blocking two tasks to place one. It applies to every
+//! variant of the loop.
+//!
+//! ## 23. Consumables
+//!
+//! [`ESTScheduler::with_consumables`] attaches a [`ConsumableBudget`] that
+//! every placement spends. At each iteration the budget is checked along
+//! with the dynamic edges, and a candidate the remaining budget cannot
+//! afford loses its windows and is dropped. It applies to every variant of
+//! the loop.
+//!
+//! ## 24. Power Envelopes
+//!
+//! [`ESTScheduler::with_power`] keeps the combined draw of running tasks
+//! under a [`PowerEnvelope`]. Each iteration restricts every candidate to
+//! the parts of the horizon where its draw fits on top of the placements so
+//! far and under the limit in force, so a task too hungry for an eclipse
+//! step waits for it to end, or is dropped. It applies to every variant of
+//! the loop.
+//!
 //! # Module Structure
 //!
 //! - [`candidate`] - Task candidate with computed metrics
 //! - [`metrics`] - Metric computation functions (EST, deadline, flexibility)
 //! - [`ordering`] - Candidate comparison and priority logic
 //! - [`engine`] - Core scheduling loop and candidate updates
+//! - [`groups`] - Group declarations enforced by the loop
+//! - [`ranking`] - Per-iteration ranking snapshots
+//! - `aging` - Priority bonus for candidates left waiting
+//! - `boost` - Time-windowed priority boosts
+//! - `budget` - Cost budget checked before each placement
+//! - `frontier` - Timelines the loop places candidates on
+//! - `layered` - Topological order of hard edges among the candidates
+//! - `limit` - Scheduling under an execution limit, with a degradation ladder
+//! - `lookahead` - Damage scoring of the top candidates before placement
+//! - `transition` - Sequence-dependent transition delays
+//! - `multi` - Multi-resource scheduling loop
+//! - `objective` - Tie-breaking by an objective
+//! - `observer` - Event hooks into the scheduling loop
+//! - `preempt` - Urgent tasks evicting lower-priority ones after the loop
+//! - `selection` - Pluggable heuristics for picking the next candidate
+//! - `uncertain` - Planned lengths for tasks with uncertain durations
 
+mod aging;
+mod boost;
+mod budget;
 mod candidate;
 mod engine;
+mod frontier;
+mod groups;
+mod layered;
+mod limit;
+mod lookahead;
 mod metrics;
+mod multi;
+mod objective;
+mod observer;
 mod ordering;
+#[cfg(feature = "unstable")]
+mod preempt;
+mod ranking;
+mod selection;
+mod transition;
+mod uncertain;
+
+use crate::clock::Instant;
+use std::collections::HashMap;
 
-use crate::schedule::Schedule;
-use crate::scheduling_block::{SchedulingBlock, Task};
-use crate::solution_space::Interval;
+use crate::constraints::soft::Objective;
+use crate::constraints::{
+    ConsumableBudget, DynConstraintKind, DynamicConstraint, DynamicConstraintIndex, PowerEnvelope,
+    Relaxable,
+};
+use crate::schedule::{ResourcePool, Schedule};
+use crate::scheduling_block::{
+    CostedTask, SchedulingBlock, SchedulingError, SetupMatrix, SetupTask, SpatialTask, Task,
+    TransitionModel, UncertainTask,
+};
 use crate::solution_space::SolutionSpace;
+use crate::solution_space::{Interval, IntervalSet, TimeGrid};
+use crate::Id;
 use qtty::Unit;
 
-use candidate::Candidate;
-use engine::schedule_segment;
+use budget::CostLedger;
+use engine::{schedule_segment_traced, DynamicEdges, SegmentHooks};
+use groups::BlockEdges;
+use limit::LimitRun;
+use observer::IterationCounter;
+use ranking::RankingTrace;
+
+pub use aging::{AgingCurve, PriorityAging};
+pub use boost::{BoostTarget, BoostedSchedule, PriorityBoost};
+pub use budget::BudgetedSchedule;
+pub use candidate::Candidate;
+pub use layered::LayeredScheduler;
+pub use limit::{ExecutionLimit, LimitPhase, LimitedSchedule};
+#[cfg(feature = "unstable")]
+pub use lookahead::Lookahead;
+pub use observer::SchedulerObserver;
+pub use ordering::TieBreak;
+#[cfg(feature = "unstable")]
+pub use preempt::{Eviction, PreemptiveSchedule};
+pub use ranking::{CandidateKind, RankReason, RankedCandidate, RankedSchedule, RankingSnapshot};
+pub use selection::{
+    EarliestDeadlineFirst, HighestPriorityFirst, MostEndangeredFirst, SelectionHeuristic,
+};
+pub use uncertain::{BufferPolicy, UncertainSchedule};
 
 /// Early Starting Time scheduler.
+#[derive(Debug, Clone)]
 pub struct ESTScheduler {
     endangered_threshold: u32,
+    tie_break: TieBreak,
+    aging: Option<PriorityAging>,
+    lookahead: Option<lookahead::Lookahead>,
+    consumables: Option<ConsumableBudget>,
+    power: Option<PowerEnvelope>,
 }
 
 impl ESTScheduler {
@@ -76,7 +329,871 @@ impl ESTScheduler {
     pub fn new(endangered_threshold: u32) -> Self {
         Self {
             endangered_threshold,
+            tie_break: TieBreak::Deterministic,
+            aging: None,
+            lookahead: None,
+            consumables: None,
+            power: None,
+        }
+    }
+
+    /// Sets how candidates whose metrics all tie are ordered.
+    ///
+    /// With [`TieBreak::Deterministic`] (the default), lexicographically
+    /// earlier IDs win every tie, so they systematically get the best
+    /// windows. [`TieBreak::Random`] spreads the advantage while staying
+    /// reproducible from its seed.
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Raises the priority of candidates that wait without being placed.
+    ///
+    /// Each iteration at which another task is picked although the candidate
+    /// could have started as early adds one to its wait, and the candidate
+    /// ranks with its priority plus `aging`'s bonus for that wait. Without
+    /// aging (the default) a candidate's priority never changes between
+    /// iterations.
+    pub fn with_aging(mut self, aging: PriorityAging) -> Self {
+        self.aging = Some(aging);
+        self
+    }
+
+    /// Picks each next task among the top candidates by the damage its
+    /// placement does to the others (see [`Lookahead`]).
+    ///
+    /// Without lookahead (the default) the best-ranked candidate is always
+    /// placed.
+    #[cfg(feature = "unstable")]
+    pub fn with_lookahead(mut self, lookahead: Lookahead) -> Self {
+        self.lookahead = Some(lookahead);
+        self
+    }
+
+    /// Spends `budget` on every placement.
+    ///
+    /// A candidate whose requirements exceed what the tasks placed so far
+    /// leave is dropped, with or without incoming edges. Without a budget
+    /// (the default) placements spend nothing.
+    pub fn with_consumables(mut self, budget: ConsumableBudget) -> Self {
+        self.consumables = Some(budget);
+        self
+    }
+
+    /// Keeps the combined draw of the placed tasks under `envelope`.
+    ///
+    /// Every candidate is restricted to where its draw fits on top of the
+    /// tasks placed so far, with or without incoming edges. Without an
+    /// envelope (the default) tasks draw nothing.
+    pub fn with_power(mut self, envelope: PowerEnvelope) -> Self {
+        self.power = Some(envelope);
+        self
+    }
+
+    /// Loop extensions every variant of the plain loop shares, evaluating
+    /// the incoming dynamic edges and group declarations in `edges`.
+    fn hooks<'a, T, U, D>(&'a self, edges: &'a mut BlockEdges<'_, D, U>) -> SegmentHooks<'a, T, U>
+    where
+        T: Task<U>,
+        U: Unit,
+        D: DynamicConstraint<U>,
+    {
+        let edges: Option<&mut dyn DynamicEdges<U>> =
+            if !edges.is_empty() || self.consumables.is_some() || self.power.is_some() {
+                Some(edges)
+            } else {
+                None
+            };
+        SegmentHooks {
+            aging: self.aging.as_ref(),
+            lookahead: self.lookahead.as_ref(),
+            edges,
+            consumables: self.consumables.as_ref(),
+            power: self.power.as_ref(),
+            ..SegmentHooks::default()
+        }
+    }
+}
+
+impl ESTScheduler {
+    /// Schedules tasks, retrying infeasible ones at increasing relaxation levels.
+    ///
+    /// A first pass schedules every task with its constraints at the nominal
+    /// level (0). Each subsequent pass takes the tasks that are still
+    /// unscheduled, re-evaluates their constraints one level higher, restricts
+    /// the result to time not already occupied, and runs the EST loop again on
+    /// the same schedule. Passes stop when every task is placed or the highest
+    /// level declared by any task has been tried.
+    ///
+    /// Unlike [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule),
+    /// the solution space is computed internally from the tasks' constraints
+    /// via [`SolutionSpace::populate_at_level`].
+    pub fn schedule_relaxed<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        horizon: Interval<U>,
+    ) -> RelaxedSchedule<U>
+    where
+        T: Task<U> + Clone,
+        T::ConstraintLeaf: Relaxable<U>,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let max_level = blocks
+            .iter()
+            .flat_map(|block| block.tasks())
+            .filter_map(|(_, task)| task.constraints().map(|c| c.max_level()))
+            .max()
+            .unwrap_or(0);
+
+        let mut schedule = Schedule::new();
+        let mut levels = HashMap::new();
+        let mut pending = self.collect_candidates(blocks);
+        let mut edges = BlockEdges::new(DynamicConstraintIndex::from_blocks(blocks), blocks);
+
+        for level in 0..=max_level {
+            if pending.is_empty() {
+                break;
+            }
+
+            let space = SolutionSpace::populate_at_level(blocks, horizon, level);
+            let masked = free_space(&schedule, &space, &pending, horizon);
+
+            schedule_segment_traced(
+                &mut schedule,
+                pending.clone(),
+                &masked,
+                horizon,
+                self.endangered_threshold,
+                self.hooks(&mut edges),
+            );
+
+            pending.retain(|c| {
+                if schedule.contains_task(c.task_id()) {
+                    levels.insert(c.task_id().to_owned(), level);
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        RelaxedSchedule { schedule, levels }
+    }
+}
+
+impl ESTScheduler {
+    /// Schedules like [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule)
+    /// and records the `top_n` highest-ranked candidates at every iteration.
+    ///
+    /// The schedule is identical to the one `schedule` returns.
+    pub fn schedule_ranked<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        top_n: usize,
+    ) -> RankedSchedule<U>
+    where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        let mut trace = RankingTrace::new(top_n);
+        schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                observer: Some(&mut trace),
+                ..self.hooks(&mut edges)
+            },
+        );
+        RankedSchedule {
+            schedule,
+            snapshots: trace.snapshots,
+        }
+    }
+
+    /// Schedules like [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule)
+    /// and reports every step of the loop to `observer`.
+    ///
+    /// The schedule is identical to the one `schedule` returns.
+    pub fn schedule_observed<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        observer: &mut dyn SchedulerObserver<U>,
+    ) -> Schedule<U>
+    where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                observer: Some(observer),
+                ..self.hooks(&mut edges)
+            },
+        );
+        schedule
+    }
+
+    /// Schedules tasks with time-windowed priority boosts.
+    ///
+    /// Same loop as [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule),
+    /// except that each candidate ranks with its priority plus the deltas of
+    /// the `boosts` active at its current EST. With no rules the schedule is
+    /// identical to the one `schedule` returns.
+    pub fn schedule_boosted<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        boosts: &[PriorityBoost<U>],
+    ) -> BoostedSchedule<U>
+    where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        let applied = schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                boosts,
+                ..self.hooks(&mut edges)
+            },
+        )
+        .applied;
+        BoostedSchedule {
+            schedule,
+            boosts: boosts.to_vec(),
+            applied,
+        }
+    }
+
+    /// Schedules like [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule),
+    /// picking each next task by `heuristic` instead of the default ranking.
+    ///
+    /// Candidates the heuristic ties keep their default order, and
+    /// candidates with no feasible start are never picked.
+    pub fn schedule_selected<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        heuristic: &dyn SelectionHeuristic<T, U>,
+    ) -> Schedule<U>
+    where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                heuristic: Some(heuristic),
+                ..self.hooks(&mut edges)
+            },
+        );
+        schedule
+    }
+
+    /// Schedules tasks whose start depends on the previously placed task's
+    /// position.
+    ///
+    /// Each candidate is evaluated from the previous task's end, plus its
+    /// `gap_after()`, plus `model`'s transition time between the two
+    /// positions. With a model that always returns zero the schedule is
+    /// identical to the one `schedule` returns.
+    pub fn schedule_with_transitions<T, U, D, E, C, M>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        model: &M,
+    ) -> Schedule<U>
+    where
+        T: Task<U> + SpatialTask<C> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+        C: std::fmt::Debug,
+        M: TransitionModel<C, U> + ?Sized,
+    {
+        let delay = transition::transition_delay(model);
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                delay: Some(&delay),
+                ..self.hooks(&mut edges)
+            },
+        );
+        schedule
+    }
+
+    /// Schedules tasks with sequence-dependent setup times.
+    ///
+    /// Each candidate is evaluated from the previous task's end, plus its
+    /// `gap_after()`, plus the `matrix` setup from the previous task's class
+    /// to the candidate's. Setups are not charged before the first task.
+    pub fn schedule_with_setups<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        matrix: &SetupMatrix<U>,
+    ) -> Schedule<U>
+    where
+        T: Task<U> + SetupTask + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let delay = transition::setup_delay(matrix);
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                delay: Some(&delay),
+                ..self.hooks(&mut edges)
+            },
+        );
+        schedule
+    }
+
+    /// Schedules tasks while their total cost stays within `budget`.
+    ///
+    /// Same loop as [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule),
+    /// except that a winning candidate whose cost at its chosen interval
+    /// would push the total over `budget` is dropped instead of placed. With
+    /// an infinite budget the schedule is identical to the one `schedule`
+    /// returns.
+    pub fn schedule_budgeted<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        budget: f64,
+    ) -> BudgetedSchedule<U>
+    where
+        T: CostedTask<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut ledger = CostLedger::new(budget);
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                gate: Some(&mut ledger),
+                ..self.hooks(&mut edges)
+            },
+        );
+        ledger.into_result(schedule)
+    }
+
+    /// Schedules tasks under an execution `limit`.
+    ///
+    /// Runs the loop of [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule),
+    /// then an improvement pass that places leftover tasks, highest priority
+    /// first, into gaps the cursor moved past. If the limit runs out during
+    /// the loop, only endangered candidates are placed from then on and the
+    /// improvement pass is skipped; if it runs out during the improvement
+    /// pass, the pass stops. Either way the tasks left out are listed in
+    /// [`LimitedSchedule::omitted`].
+    pub fn schedule_limited<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        limit: ExecutionLimit,
+    ) -> LimitedSchedule<U>
+    where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut run = LimitRun::new(limit);
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        let outcome = schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                limit: Some(&mut run),
+                ..self.hooks(&mut edges)
+            },
+        );
+        run.finish(schedule, outcome, solution_space, horizon)
+    }
+
+    /// Schedules tasks, breaking ranking ties by `objective`.
+    ///
+    /// Same loop as [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule),
+    /// except that candidates sharing the leader's class (endangered or
+    /// flexible) and earliest start are reordered by the objective score of
+    /// their EST placement against the schedule so far, highest first.
+    /// Equal scores keep the usual order, so an objective that scores every
+    /// placement alike yields the schedule `schedule` returns.
+    pub fn schedule_objective<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        objective: &Objective<U>,
+    ) -> Schedule<U>
+    where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                objective: Some(objective),
+                ..self.hooks(&mut edges)
+            },
+        );
+        schedule
+    }
+
+    /// Schedules tasks so that every start lies on `grid`.
+    ///
+    /// Same loop as [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule),
+    /// run on the solution space [quantized](SolutionSpace::quantized) to the
+    /// grid within `horizon`, with the cursor snapped up to the next grid
+    /// point after each placement. EST, deadline and flexibility are all read
+    /// off the quantized windows, so they round consistently.
+    pub fn schedule_on_grid<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        grid: &TimeGrid<U>,
+    ) -> Schedule<U>
+    where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            &solution_space.quantized(blocks, grid, horizon),
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                grid: Some(grid),
+                ..self.hooks(&mut edges)
+            },
+        );
+        schedule
+    }
+
+    /// Schedules tasks whose actual durations are uncertain.
+    ///
+    /// Every task is planned at the length `policy` reads off its
+    /// [`duration_distribution`](UncertainTask::duration_distribution):
+    /// the quantile itself under [`BufferPolicy::Quantile`], or its nominal
+    /// size followed by idle time up to the quantile under
+    /// [`BufferPolicy::Buffer`]. Windows are not widened, so a task planned
+    /// longer than its nominal size needs a window that fits the longer
+    /// length. Each placement is reported with its overrun risk: the
+    /// probability that the task, started on time, is still running when
+    /// the next placement (or the horizon end) comes.
+    pub fn schedule_uncertain<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        policy: BufferPolicy,
+    ) -> UncertainSchedule<U>
+    where
+        T: UncertainTask<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut edges = run_edges(blocks, solution_space);
+        uncertain::schedule_segment_uncertain(
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            policy,
+            self.hooks(&mut edges),
+        )
+    }
+
+    /// Schedules tasks prerequisites first.
+    ///
+    /// Same loop as [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule),
+    /// except that only the feasible candidates of the lowest
+    /// [topological layer](SchedulingBlock::topological_layers_by) of
+    /// `Dependence` and `Consecutive` edges compete at each iteration. Other
+    /// edge kinds do not affect the order.
+    ///
+    /// # Errors
+    ///
+    /// [`SchedulingError::Cycle`] naming the first cycle found in a block.
+    pub fn schedule_layered<T, U, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, DynConstraintKind, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> Result<Schedule<U>, SchedulingError>
+    where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        E: petgraph::EdgeType,
+    {
+        crate::algorithms::ensure_acyclic(blocks)?;
+        let mut layers = HashMap::new();
+        for block in blocks {
+            let hard = block.topological_layers_by(|d| {
+                matches!(
+                    d,
+                    DynConstraintKind::Dependence | DynConstraintKind::Consecutive
+                )
+            })?;
+            for (depth, nodes) in hard.into_iter().enumerate() {
+                for node in nodes {
+                    if let Some(id) = block.id_of(node) {
+                        layers.insert(id.to_owned(), depth);
+                    }
+                }
+            }
+        }
+
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        schedule_segment_traced(
+            &mut schedule,
+            self.collect_candidates(blocks),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            SegmentHooks {
+                layers: Some(&layers),
+                ..self.hooks(&mut edges)
+            },
+        );
+        Ok(schedule)
+    }
+
+    /// Schedules tasks, letting an unplaced task evict strictly
+    /// lower-priority ones from its last window.
+    ///
+    /// Runs the loop of [`schedule`](crate::algorithms::SchedulingAlgorithm::schedule),
+    /// then repeatedly gives each unplaced task, highest priority first, the
+    /// cheapest slot of its last window whose occupants all have a lower
+    /// priority, evicts them, and reschedules the evicted tasks into the
+    /// time left free. When no task is left out the schedule is identical to
+    /// the one `schedule` returns.
+    #[cfg(feature = "unstable")]
+    pub fn schedule_preemptive<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> PreemptiveSchedule<U>
+    where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        let candidates = self.collect_candidates(blocks);
+        schedule_segment_traced(
+            &mut schedule,
+            candidates.clone(),
+            solution_space,
+            horizon,
+            self.endangered_threshold,
+            self.hooks(&mut edges),
+        );
+        let evictions = preempt::preempt(
+            &mut schedule,
+            &candidates,
+            solution_space,
+            horizon,
+            &mut |schedule, pending, space| {
+                schedule_segment_traced(
+                    schedule,
+                    pending,
+                    space,
+                    horizon,
+                    self.endangered_threshold,
+                    self.hooks(&mut edges),
+                );
+            },
+        );
+        PreemptiveSchedule {
+            schedule,
+            evictions,
+        }
+    }
+
+    /// Schedules tasks across the resources of `pool`, choosing a resource
+    /// and a start time for each.
+    ///
+    /// A task may use resource `r` if `pool` deems it compatible and
+    /// `resource_spaces[r]` holds windows for it. Entries already in `pool`
+    /// are kept: their tasks are not scheduled again, and each resource's
+    /// cursor starts after its last entry.
+    pub fn schedule_pool<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        resource_spaces: &HashMap<Id, SolutionSpace<U>>,
+        pool: &mut ResourcePool<U>,
+        horizon: Interval<U>,
+    ) where
+        T: Task<U> + Clone,
+        U: Unit + Send + Sync,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let candidates = self
+            .collect_candidates(blocks)
+            .into_iter()
+            .filter(|c| !pool.contains_task(c.task_id()))
+            .collect();
+        let mut edges = pool_edges(blocks, resource_spaces);
+        multi::schedule_pool(
+            pool,
+            candidates,
+            resource_spaces,
+            horizon,
+            self.endangered_threshold,
+            self.hooks(&mut edges),
+        );
+    }
+}
+
+impl<T, U, D, E> crate::algorithms::MultiResourceAlgorithm<T, U, D, E> for ESTScheduler
+where
+    T: Task<U> + Clone,
+    U: Unit + Send + Sync,
+    D: DynamicConstraint<U>,
+    E: petgraph::EdgeType,
+{
+    /// Runs [`schedule_pool`](ESTScheduler::schedule_pool) on a pool with one
+    /// empty schedule per entry of `resource_spaces`. Each task is placed on
+    /// at most one resource.
+    fn schedule_multi(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        resource_spaces: &HashMap<Id, SolutionSpace<U>>,
+        horizon: Interval<U>,
+    ) -> HashMap<Id, Schedule<U>> {
+        let mut pool = ResourcePool::new();
+        for resource_id in resource_spaces.keys() {
+            pool.add_resource(resource_id.clone());
         }
+        self.schedule_pool(blocks, resource_spaces, &mut pool, horizon);
+        pool.into_schedules()
+    }
+}
+
+impl ESTScheduler {
+    /// Wraps every task of every block in a fresh candidate, with its
+    /// tie-break key under [`TieBreak::Random`].
+    fn collect_candidates<T, U, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+    ) -> Vec<Candidate<T, U>>
+    where
+        T: Task<U> + Clone,
+        U: Unit,
+        E: petgraph::EdgeType,
+    {
+        blocks
+            .iter()
+            .flat_map(|block| {
+                block.tasks().map(|(id, task)| {
+                    let candidate = Candidate::new(task.clone(), id);
+                    match self.tie_break {
+                        TieBreak::Random(seed) => candidate.with_tie_jitter(seed),
+                        TieBreak::Deterministic => candidate,
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+/// Restricts the windows of `pending` in `space` to time `schedule` leaves
+/// free, dropping windows too short for the task.
+fn free_space<T, U>(
+    schedule: &Schedule<U>,
+    space: &SolutionSpace<U>,
+    pending: &[Candidate<T, U>],
+    horizon: Interval<U>,
+) -> SolutionSpace<U>
+where
+    T: Task<U>,
+    U: Unit,
+{
+    let free = IntervalSet::from(schedule.intervals().collect::<Vec<_>>()).complement(horizon);
+    let mut masked = SolutionSpace::with_capacity(pending.len());
+    for candidate in pending {
+        let size = candidate.task().size_on_axis().value();
+        let intervals = space
+            .get_intervals(candidate.task_id())
+            .map(|set| set.intersection(&free).into_inner())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|i| i.duration().value() >= size)
+            .collect();
+        masked.set_intervals(candidate.task_id(), intervals);
+    }
+    masked
+}
+
+/// Indexes the dynamic edges of `blocks` whose source the run may place,
+/// along with the groups declared on them.
+///
+/// Tasks without an entry in `solution_space` are outside the run, like the
+/// frozen tasks of a [`replan`](crate::algorithms::replan()): the space is
+/// expected to reflect their edges already, so those edges are left out.
+fn run_edges<'a, T, U, D, E>(
+    blocks: &'a [SchedulingBlock<T, U, D, E>],
+    solution_space: &SolutionSpace<U>,
+) -> BlockEdges<'a, D, U>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    edges_from(blocks, |source| {
+        solution_space.get_intervals(source).is_some()
+    })
+}
+
+/// [`run_edges`] for a pool run, where a task is in the run if any of
+/// `resource_spaces` has an entry for it.
+fn pool_edges<'a, T, U, D, E>(
+    blocks: &'a [SchedulingBlock<T, U, D, E>],
+    resource_spaces: &HashMap<Id, SolutionSpace<U>>,
+) -> BlockEdges<'a, D, U>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    edges_from(blocks, |source| {
+        resource_spaces
+            .values()
+            .any(|space| space.get_intervals(source).is_some())
+    })
+}
+
+fn edges_from<'a, T, U, D, E>(
+    blocks: &'a [SchedulingBlock<T, U, D, E>],
+    in_run: impl Fn(&str) -> bool + Copy,
+) -> BlockEdges<'a, D, U>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+    let index = DynamicConstraintIndex::from_edges(blocks.iter().flat_map(|block| {
+        block.graph().edge_references().filter_map(move |edge| {
+            let source = block.id_of(edge.source())?;
+            let target = block.id_of(edge.target())?;
+            in_run(source).then(|| (source.to_owned(), target.to_owned(), edge.weight()))
+        })
+    }));
+    BlockEdges::new(index, blocks)
+}
+
+/// Result of [`ESTScheduler::schedule_relaxed`].
+#[derive(Debug, Clone)]
+pub struct RelaxedSchedule<U: Unit> {
+    /// The schedule produced across all relaxation passes.
+    pub schedule: Schedule<U>,
+    /// Relaxation level at which each scheduled task was placed (0 = nominal).
+    pub levels: HashMap<Id, usize>,
+}
+
+impl<U: Unit> RelaxedSchedule<U> {
+    /// Returns the level used for `task_id`, or `None` if it was not scheduled.
+    pub fn level_of(&self, task_id: &str) -> Option<usize> {
+        self.levels.get(task_id).copied()
+    }
+
+    /// Returns the IDs of tasks placed above the nominal level.
+    pub fn relaxed_tasks(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.levels
+            .iter()
+            .filter(|(_, &level)| level > 0)
+            .map(|(id, &level)| (id.as_str(), level))
     }
 }
 
@@ -87,10 +1204,14 @@ impl Default for ESTScheduler {
     }
 }
 
+/// Refuses blocks whose dependency graph has a cycle: every task is left
+/// unscheduled, and [`try_schedule`](crate::algorithms::SchedulingAlgorithm::try_schedule)
+/// reports the cycle.
 impl<T, U, D, E> crate::algorithms::SchedulingAlgorithm<T, U, D, E> for ESTScheduler
 where
     T: Task<U> + Clone,
-    U: Unit,
+    U: Unit + Send + Sync,
+    D: DynamicConstraint<U>,
     E: petgraph::EdgeType,
 {
     fn schedule(
@@ -100,28 +1221,83 @@ where
         horizon: Interval<U>,
     ) -> Schedule<U> {
         let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        if crate::algorithms::ensure_acyclic(blocks).is_err() {
+            return schedule;
+        }
 
         // Collect all tasks from all blocks
-        let candidates: Vec<Candidate<T, U>> = blocks
-            .iter()
-            .flat_map(|block| {
-                block
-                    .tasks()
-                    .map(|(id, task)| Candidate::new(task.clone(), id))
-            })
-            .collect();
+        let candidates = self.collect_candidates(blocks);
 
         // Schedule
-        schedule_segment(
+        schedule_segment_traced(
             &mut schedule,
             candidates,
             solution_space,
             horizon,
             self.endangered_threshold,
+            self.hooks(&mut edges),
         );
 
         schedule
     }
+
+    /// Counts one iteration per placement attempt of the loop.
+    fn schedule_result(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> crate::algorithms::SchedulerResult<U> {
+        let started = Instant::now();
+        let mut schedule = Schedule::new();
+        let mut edges = run_edges(blocks, solution_space);
+        let mut counter = IterationCounter::default();
+        if crate::algorithms::ensure_acyclic(blocks).is_ok() {
+            schedule_segment_traced(
+                &mut schedule,
+                self.collect_candidates(blocks),
+                solution_space,
+                horizon,
+                self.endangered_threshold,
+                SegmentHooks {
+                    observer: Some(&mut counter),
+                    ..self.hooks(&mut edges)
+                },
+            );
+        }
+        crate::algorithms::SchedulerResult::new(
+            schedule,
+            blocks,
+            solution_space,
+            horizon,
+            Some(counter.0),
+            started.elapsed(),
+        )
+    }
+}
+
+/// Runs with [`TieBreak::Random`] under `seed`, whatever the configured
+/// tie-break.
+impl<T, U, D, E> crate::algorithms::SeededAlgorithm<T, U, D, E> for ESTScheduler
+where
+    T: Task<U> + Clone,
+    U: Unit + Send + Sync,
+    D: DynamicConstraint<U>,
+    E: petgraph::EdgeType,
+{
+    fn schedule_seeded(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        seed: u64,
+    ) -> Schedule<U> {
+        use crate::algorithms::SchedulingAlgorithm;
+        self.clone()
+            .with_tie_break(TieBreak::Random(seed))
+            .schedule(blocks, solution_space, horizon)
+    }
 }
 
 #[cfg(test)]
@@ -411,4 +1587,907 @@ mod tests {
         let next_endangered = find_next_endangered_index(&candidates, threshold);
         assert_eq!(next_endangered, 3, "No endangered should return len()");
     }
+
+    // ── schedule_ranked ───────────────────────────────────────────────
+
+    #[test]
+    fn schedule_ranked_matches_schedule_and_explains_choices() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        for (id, prio) in [("lo", 1), ("hi", 9), ("late", 5)] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0).with_priority(prio), Some(id.into()))
+                .unwrap();
+        }
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("lo", vec![iv(0.0, 100.0)]);
+        ss.set_intervals("hi", vec![iv(0.0, 100.0)]);
+        ss.set_intervals("late", vec![iv(50.0, 100.0)]);
+        let blocks = [block];
+        let horizon = iv(0.0, 100.0);
+
+        let scheduler = ESTScheduler::new(1);
+        let plain = scheduler.schedule(&blocks, &ss, horizon);
+        let ranked = scheduler.schedule_ranked(&blocks, &ss, horizon, 3);
+        assert!(plain.diff(&ranked.schedule).is_empty());
+
+        assert_eq!(ranked.snapshots.len(), 3);
+        let first = &ranked.snapshots[0];
+        assert_eq!(first.winner().task_id, "hi");
+        assert_eq!(first.placed, Some(iv(0.0, 10.0)));
+        let reasons: Vec<_> = first
+            .runners_up()
+            .iter()
+            .map(|r| (r.task_id.as_str(), r.beaten_because.unwrap()))
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                ("lo", RankReason::HigherPriority),
+                ("late", RankReason::EarlierStart)
+            ]
+        );
+        assert_eq!(ranked.snapshots[2].ranked.len(), 1);
+    }
+
+    // ── dynamic edges ─────────────────────────────────────────────────
+
+    #[test]
+    fn dynamic_edges_hold_back_their_targets() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::constraints::soft::ObjectiveBuilder;
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        let mut ss = SolutionSpace::new();
+        for (id, priority) in [("setup", 0), ("observe", 9)] {
+            block
+                .add_task_with_id(
+                    TestTask::new(id, 10.0).with_priority(priority),
+                    Some(id.into()),
+                )
+                .unwrap();
+            ss.set_intervals(id, vec![iv(0.0, 100.0)]);
+        }
+        let (setup, observe) = (
+            block.node_of("setup").unwrap(),
+            block.node_of("observe").unwrap(),
+        );
+        block
+            .add_dependency(setup, observe, DynConstraintKind::Consecutive)
+            .unwrap();
+        let blocks = [block];
+
+        // "observe" outranks "setup" but may only start once it has ended.
+        let schedule = ESTScheduler::new(1).schedule(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(schedule.get_interval("setup"), Some(iv(0.0, 10.0)));
+        assert_eq!(schedule.get_interval("observe"), Some(iv(10.0, 20.0)));
+
+        // The variants run the same loop, edges included.
+        let scheduler = ESTScheduler::new(1);
+        let horizon = iv(0.0, 100.0);
+        let variants = [
+            scheduler
+                .schedule_limited(&blocks, &ss, horizon, ExecutionLimit::unlimited())
+                .schedule,
+            scheduler.schedule_objective(&blocks, &ss, horizon, &ObjectiveBuilder::new().build()),
+            scheduler.schedule_layered(&blocks, &ss, horizon).unwrap(),
+        ];
+        for variant in variants {
+            assert_eq!(variant.get_interval("observe"), Some(iv(10.0, 20.0)));
+        }
+    }
+
+    #[test]
+    fn only_one_alternative_is_placed() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::scheduling_block::{AlternativeGroup, AlternativeStatus};
+        use crate::test_utils::{iv, TestTask};
+
+        // Either member may outrank the other; the loser is never placed.
+        for (lamp, sky) in [(9, 0), (0, 9)] {
+            let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+                SchedulingBlock::new();
+            let mut ss = SolutionSpace::new();
+            for (id, priority) in [("lamp", lamp), ("sky", sky)] {
+                block
+                    .add_task_with_id(
+                        TestTask::new(id, 10.0).with_priority(priority),
+                        Some(id.into()),
+                    )
+                    .unwrap();
+                ss.set_intervals(id, vec![iv(0.0, 100.0)]);
+            }
+            let group = AlternativeGroup::new("calibrator", ["lamp", "sky"]);
+            block.add_alternatives(group.clone()).unwrap();
+            let blocks = [block];
+
+            let schedule = ESTScheduler::new(1).schedule(&blocks, &ss, iv(0.0, 100.0));
+            let winner = if lamp > sky { "lamp" } else { "sky" };
+            assert_eq!(
+                group.check(&schedule),
+                AlternativeStatus::Chosen(winner.into())
+            );
+            assert_eq!(schedule.len(), 1);
+        }
+    }
+
+    #[test]
+    fn contiguous_groups_keep_foreign_tasks_out() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::constraints::ContiguousGroup;
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        let mut ss = SolutionSpace::new();
+        for (id, priority, window) in [
+            ("m1", 9, iv(0.0, 100.0)),
+            ("survey", 5, iv(0.0, 100.0)),
+            ("m2", 0, iv(0.0, 100.0)),
+            ("guide", 0, iv(60.0, 100.0)),
+        ] {
+            block
+                .add_task_with_id(
+                    TestTask::new(id, 10.0).with_priority(priority),
+                    Some(id.into()),
+                )
+                .unwrap();
+            ss.set_intervals(id, vec![window]);
+        }
+        let mosaic = ContiguousGroup::new("mosaic").with_members(["m1", "m2"]);
+        let plain = ESTScheduler::new(1).schedule(&[block.clone()], &ss, iv(0.0, 100.0));
+        assert_eq!(mosaic.intruders(&plain), vec!["survey".to_string()]);
+
+        // Once "survey" follows "m1", the block can no longer grow past it.
+        block.add_contiguous(mosaic.clone()).unwrap();
+        let schedule = ESTScheduler::new(1).schedule(&[block], &ss, iv(0.0, 100.0));
+        assert!(mosaic.is_satisfied(&schedule));
+        assert_eq!(schedule.get_interval("m1"), Some(iv(0.0, 10.0)));
+        assert_eq!(schedule.get_interval("survey"), Some(iv(10.0, 20.0)));
+        assert_eq!(schedule.get_interval("m2"), None);
+        assert_eq!(schedule.get_interval("guide"), Some(iv(60.0, 70.0)));
+    }
+
+    #[test]
+    fn consumables_drop_what_the_budget_cannot_afford() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        let mut ss = SolutionSpace::new();
+        for (id, priority) in [("burn-1", 9), ("burn-2", 5), ("trim", 0)] {
+            block
+                .add_task_with_id(
+                    TestTask::new(id, 10.0).with_priority(priority),
+                    Some(id.into()),
+                )
+                .unwrap();
+            ss.set_intervals(id, vec![iv(0.0, 100.0)]);
+        }
+        let blocks = [block];
+        let fuel = ConsumableBudget::new()
+            .with_total("fuel", 10.0)
+            .with_requirement("burn-1", "fuel", 6.0)
+            .with_requirement("burn-2", "fuel", 6.0)
+            .with_requirement("trim", "fuel", 3.0);
+
+        // "burn-1" leaves 4 units: too few for "burn-2", enough for "trim".
+        let schedule = ESTScheduler::new(1)
+            .with_consumables(fuel.clone())
+            .schedule(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(schedule.get_interval("burn-1"), Some(iv(0.0, 10.0)));
+        assert_eq!(schedule.get_interval("burn-2"), None);
+        assert_eq!(schedule.get_interval("trim"), Some(iv(10.0, 20.0)));
+        assert!(fuel.is_satisfied([&schedule]));
+
+        let unlimited = ESTScheduler::new(1).schedule(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(unlimited.len(), 3);
+    }
+
+    #[test]
+    fn power_envelope_holds_back_hungry_tasks() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        let mut ss = SolutionSpace::new();
+        for (id, size, priority) in [("heater", 50.0, 9), ("camera", 10.0, 5), ("radio", 10.0, 0)] {
+            block
+                .add_task_with_id(
+                    TestTask::new(id, size).with_priority(priority),
+                    Some(id.into()),
+                )
+                .unwrap();
+            ss.set_intervals(id, vec![iv(0.0, 100.0)]);
+        }
+        let blocks = [block];
+        // 100 W until eclipse at t = 50, then 40 W until t = 80.
+        let bus = PowerEnvelope::new(100.0)
+            .with_step(50.0, 40.0)
+            .with_step(80.0, 100.0)
+            .with_draws([("heater", 20.0), ("camera", 60.0), ("radio", 30.0)]);
+
+        // The heater takes the sunlit half; the camera waits out the eclipse.
+        let schedule =
+            ESTScheduler::new(1)
+                .with_power(bus.clone())
+                .schedule(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(schedule.get_interval("heater"), Some(iv(0.0, 50.0)));
+        assert_eq!(schedule.get_interval("radio"), Some(iv(50.0, 60.0)));
+        assert_eq!(schedule.get_interval("camera"), Some(iv(80.0, 90.0)));
+
+        let unpowered = ESTScheduler::new(1).schedule(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(unpowered.get_interval("camera"), Some(iv(50.0, 60.0)));
+
+        // Without the second step the camera never fits.
+        let dark = PowerEnvelope::new(100.0)
+            .with_step(50.0, 40.0)
+            .with_draw("camera", 60.0);
+        let schedule = ESTScheduler::new(1)
+            .with_power(dark)
+            .schedule(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(schedule.get_interval("camera"), None);
+        assert_eq!(schedule.len(), 2);
+    }
+
+    // ── schedule_pool ─────────────────────────────────────────────────
+
+    fn pool_spaces(ids: &[&str]) -> HashMap<Id, SolutionSpace<Second>> {
+        use crate::test_utils::iv;
+
+        let mut space = SolutionSpace::new();
+        for id in ids {
+            space.set_intervals(*id, vec![iv(0.0, 100.0)]);
+        }
+        HashMap::from([("r1".to_string(), space.clone()), ("r2".to_string(), space)])
+    }
+
+    #[test]
+    fn pool_edges_see_every_resource() {
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        for (id, priority) in [("setup", 0), ("observe", 9)] {
+            block
+                .add_task_with_id(
+                    TestTask::new(id, 10.0).with_priority(priority),
+                    Some(id.into()),
+                )
+                .unwrap();
+        }
+        let (setup, observe) = (
+            block.node_of("setup").unwrap(),
+            block.node_of("observe").unwrap(),
+        );
+        block
+            .add_dependency(setup, observe, DynConstraintKind::Consecutive)
+            .unwrap();
+
+        // r2 is free at 0, but "observe" waits for "setup" on r1.
+        let mut pool = ResourcePool::new().with_resource("r1").with_resource("r2");
+        ESTScheduler::new(1).schedule_pool(
+            &[block],
+            &pool_spaces(&["setup", "observe"]),
+            &mut pool,
+            iv(0.0, 100.0),
+        );
+        assert_eq!(pool.placements("setup"), vec![("r1", iv(0.0, 10.0))]);
+        assert_eq!(pool.placements("observe"), vec![("r1", iv(10.0, 20.0))]);
+    }
+
+    #[test]
+    fn pool_caps_a_family_across_resources() {
+        use crate::algorithms::MultiResourceAlgorithm;
+        use crate::constraints::MaxParallelism;
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second, MaxParallelism> = SchedulingBlock::new();
+        for id in ["dl-a", "dl-b"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let (a, b) = (
+            block.node_of("dl-a").unwrap(),
+            block.node_of("dl-b").unwrap(),
+        );
+        let downlink = MaxParallelism::new("downlink", 1).with_members(["dl-a", "dl-b"]);
+        block.add_dependency(a, b, downlink.clone()).unwrap();
+
+        // Two antennas, one downlink: the second pass waits for the first.
+        let schedules = ESTScheduler::new(1).schedule_multi(
+            &[block],
+            &pool_spaces(&["dl-a", "dl-b"]),
+            iv(0.0, 100.0),
+        );
+        assert!(downlink.is_satisfied(&schedules));
+        assert_eq!(schedules["r1"].get_interval("dl-a"), Some(iv(0.0, 10.0)));
+        assert_eq!(schedules["r1"].get_interval("dl-b"), Some(iv(10.0, 20.0)));
+    }
+
+    #[test]
+    fn pool_stops_a_group_at_its_upper_bound() {
+        use crate::constraints::CoalitionConstraint;
+        use crate::test_utils::{iv, TestTask};
+
+        let ids = ["anchor", "p1", "p2", "p3"];
+        let mut block: SchedulingBlock<TestTask, Second, CoalitionConstraint> =
+            SchedulingBlock::new();
+        for id in ids {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let pick = CoalitionConstraint::group("pointings", ["p1", "p2", "p3"]).with_at_most(2);
+        let anchor = block.node_of("anchor").unwrap();
+        for member in ["p1", "p2", "p3"] {
+            let member = block.node_of(member).unwrap();
+            block.add_dependency(anchor, member, pick.clone()).unwrap();
+        }
+
+        // Members on either resource count towards the bound.
+        let mut pool = ResourcePool::new().with_resource("r1").with_resource("r2");
+        ESTScheduler::new(1).schedule_pool(&[block], &pool_spaces(&ids), &mut pool, iv(0.0, 100.0));
+        assert_eq!(pool.resource_of("p1"), Some("r2"));
+        assert_eq!(pool.resource_of("p2"), Some("r1"));
+        assert!(!pool.contains_task("p3"));
+        assert!(pick.check_cardinality(pool.schedules().values()).is_ok());
+    }
+
+    #[test]
+    fn pool_keeps_exclusion_groups_apart_across_resources() {
+        use crate::scheduling_block::ExclusionGroup;
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        for (id, priority) in [("a", 0), ("b", 9)] {
+            block
+                .add_task_with_id(
+                    TestTask::new(id, 10.0).with_priority(priority),
+                    Some(id.into()),
+                )
+                .unwrap();
+        }
+        let crew = ExclusionGroup::new("crew", ["a", "b"]);
+        block.add_exclusion(crew.clone()).unwrap();
+
+        // "b" is placed first although its edge points the other way; "a"
+        // still avoids it on the idle resource.
+        let mut pool = ResourcePool::new().with_resource("r1").with_resource("r2");
+        ESTScheduler::new(1).schedule_pool(
+            &[block],
+            &pool_spaces(&["a", "b"]),
+            &mut pool,
+            iv(0.0, 100.0),
+        );
+        assert_eq!(pool.placements("b"), vec![("r1", iv(0.0, 10.0))]);
+        assert_eq!(pool.placements("a"), vec![("r1", iv(10.0, 20.0))]);
+        assert!(crew.is_satisfied(pool.schedules().values()));
+    }
+
+    // ── with_aging ────────────────────────────────────────────────────
+
+    #[test]
+    fn aging_lets_a_passed_over_task_through() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::test_utils::{iv, TestTask};
+
+        // A stream of urgent tasks opening one after the other keeps
+        // winning the start `mid` could take.
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        let mut ss = SolutionSpace::new();
+        block
+            .add_task_with_id(
+                TestTask::new("mid", 10.0).with_priority(3),
+                Some("mid".into()),
+            )
+            .unwrap();
+        ss.set_intervals("mid", vec![iv(0.0, 100.0)]);
+        for i in 0..6 {
+            let id = format!("h{i}");
+            block
+                .add_task_with_id(TestTask::new(&id, 10.0).with_priority(5), Some(id.clone()))
+                .unwrap();
+            ss.set_intervals(id, vec![iv(10.0 * i as f64, 100.0)]);
+        }
+        let blocks = [block];
+        let horizon = iv(0.0, 100.0);
+        let start_of_mid = |scheduler: ESTScheduler| {
+            scheduler
+                .schedule(&blocks, &ss, horizon)
+                .get_interval("mid")
+                .unwrap()
+                .start()
+                .value()
+        };
+
+        assert_eq!(start_of_mid(ESTScheduler::new(1)), 60.0);
+        let aging = PriorityAging::linear(1.0);
+        // Bonus 2 ties the urgent tasks, which still win on ID; bonus 3 wins.
+        assert_eq!(start_of_mid(ESTScheduler::new(1).with_aging(aging)), 30.0);
+        let late = aging.with_grace(2);
+        assert_eq!(start_of_mid(ESTScheduler::new(1).with_aging(late)), 50.0);
+    }
+
+    // ── schedule_selected ─────────────────────────────────────────────
+
+    #[test]
+    fn selection_heuristics_change_the_pick() {
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        let mut ss = SolutionSpace::new();
+        for (id, prio, window) in [
+            ("a", 1, iv(0.0, 100.0)),
+            ("b", 9, iv(5.0, 100.0)),
+            ("c", 5, iv(0.0, 40.0)),
+        ] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0).with_priority(prio), Some(id.into()))
+                .unwrap();
+            ss.set_intervals(id, vec![window]);
+        }
+        let blocks = [block];
+        let horizon = iv(0.0, 100.0);
+        let order = |heuristic: &dyn SelectionHeuristic<TestTask, Second>| {
+            let schedule = ESTScheduler::new(1).schedule_selected(&blocks, &ss, horizon, heuristic);
+            schedule.iter().map(|(id, _)| id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(&HighestPriorityFirst), ["b", "c", "a"]);
+        assert_eq!(order(&EarliestDeadlineFirst), ["c", "b", "a"]);
+        assert_eq!(order(&MostEndangeredFirst), ["c", "b", "a"]);
+        // Ties fall back to the default ranking, which prefers `c` at 0.
+        let indifferent = |_: &Candidate<TestTask, Second>, _: &Candidate<TestTask, Second>| {
+            std::cmp::Ordering::Equal
+        };
+        assert_eq!(order(&indifferent), ["c", "b", "a"]);
+    }
+
+    // ── with_lookahead ────────────────────────────────────────────────
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn lookahead_avoids_blocking_two_tasks_for_one() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        let mut ss = SolutionSpace::new();
+        for (id, size, window) in [
+            ("long", 30.0, iv(0.0, 100.0)),
+            ("x", 10.0, iv(0.0, 35.0)),
+            ("y", 10.0, iv(0.0, 40.0)),
+        ] {
+            block
+                .add_task_with_id(TestTask::new(id, size), Some(id.into()))
+                .unwrap();
+            ss.set_intervals(id, vec![window]);
+        }
+        let blocks = [block];
+        let horizon = iv(0.0, 100.0);
+
+        // `long` is the least flexible, and placing it first shuts `x` out.
+        let greedy = ESTScheduler::new(2).schedule(&blocks, &ss, horizon);
+        assert!(!greedy.contains_task("x"));
+
+        let scheduler = ESTScheduler::new(2).with_lookahead(Lookahead::new(2));
+        let schedule = scheduler.schedule(&blocks, &ss, horizon);
+        let order: Vec<_> = schedule.iter().map(|(id, _)| id).collect();
+        assert_eq!(order, ["x", "y", "long"]);
+    }
+
+    // ── schedule_boosted ──────────────────────────────────────────────
+
+    #[test]
+    fn boost_applies_only_inside_its_window() {
+        use crate::test_utils::{iv, q, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        for (id, prio) in [("comm-1", 1), ("comm-2", 1), ("survey", 5)] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0).with_priority(prio), Some(id.into()))
+                .unwrap();
+        }
+        let mut ss = SolutionSpace::new();
+        for id in ["comm-1", "comm-2", "survey"] {
+            ss.set_intervals(id, vec![iv(0.0, 100.0)]);
+        }
+        let blocks = [block];
+        let push = PriorityBoost::new("commissioning", iv(0.0, 10.0), 10).with_target(
+            BoostTarget::Prefix {
+                prefix: "comm-".into(),
+            },
+        );
+
+        let boosted = ESTScheduler::new(1).schedule_boosted(
+            &blocks,
+            &ss,
+            iv(0.0, 100.0),
+            std::slice::from_ref(&push),
+        );
+        let order: Vec<_> = boosted.schedule.iter().map(|(id, _)| id).collect();
+        // comm-1 wins at 0 thanks to the boost; at 10 the push is over.
+        assert_eq!(order, vec!["comm-1", "survey", "comm-2"]);
+        assert_eq!(boosted.applied_to("comm-1"), 10);
+        assert_eq!(boosted.applied_to("comm-2"), 0);
+        assert_eq!(boosted.boosts, vec![push]);
+
+        let plain = ESTScheduler::new(1).schedule_boosted(&blocks, &ss, iv(0.0, 100.0), &[]);
+        assert_eq!(
+            plain.schedule.task_at(q(0.0)),
+            Ok(Some("survey".to_string()))
+        );
+        assert!(plain.applied.is_empty());
+    }
+
+    // ── schedule_observed ─────────────────────────────────────────────
+
+    #[test]
+    fn schedule_observed_reports_every_step() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::test_utils::{iv, TestTask};
+
+        #[derive(Default)]
+        struct Log(Vec<String>);
+
+        impl SchedulerObserver<Second> for Log {
+            fn on_iteration_start(
+                &mut self,
+                iteration: usize,
+                cursor: qtty::Quantity<Second>,
+                remaining: usize,
+            ) {
+                self.0.push(format!(
+                    "start {iteration} @{} ({remaining})",
+                    cursor.value()
+                ));
+            }
+
+            fn on_candidate_scored(
+                &mut self,
+                _iteration: usize,
+                rank: usize,
+                candidate: &RankedCandidate<Second>,
+            ) {
+                self.0.push(format!("rank {rank} {}", candidate.task_id));
+            }
+
+            fn on_task_placed(
+                &mut self,
+                _iteration: usize,
+                task_id: &str,
+                interval: Interval<Second>,
+            ) {
+                self.0.push(format!("placed {task_id} {interval}"));
+            }
+
+            fn on_task_impossible(&mut self, task_id: &str) {
+                self.0.push(format!("impossible {task_id}"));
+            }
+        }
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        for (id, prio, size) in [("lo", 1, 10.0), ("hi", 9, 10.0), ("big", 5, 500.0)] {
+            block
+                .add_task_with_id(TestTask::new(id, size).with_priority(prio), Some(id.into()))
+                .unwrap();
+        }
+        let mut ss = SolutionSpace::new();
+        for id in ["lo", "hi", "big"] {
+            ss.set_intervals(id, vec![iv(0.0, 100.0)]);
+        }
+        let blocks = [block];
+        let scheduler = ESTScheduler::new(1);
+
+        let mut log = Log::default();
+        let observed = scheduler.schedule_observed(&blocks, &ss, iv(0.0, 100.0), &mut log);
+        assert!(scheduler
+            .schedule(&blocks, &ss, iv(0.0, 100.0))
+            .diff(&observed)
+            .is_empty());
+        assert_eq!(
+            log.0,
+            vec![
+                "start 0 @0 (3)".to_string(),
+                "rank 0 hi".into(),
+                "rank 1 lo".into(),
+                "rank 2 big".into(),
+                format!("placed hi {}", iv(0.0, 10.0)),
+                "start 1 @10 (2)".into(),
+                "rank 0 lo".into(),
+                "rank 1 big".into(),
+                format!("placed lo {}", iv(10.0, 20.0)),
+                "impossible big".into(),
+            ]
+        );
+    }
+
+    // ── Tie-breaking ──────────────────────────────────────────────────
+
+    #[test]
+    fn random_tie_break_is_reproducible_and_diverse() {
+        use crate::algorithms::SchedulingAlgorithm;
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        let mut ss = SolutionSpace::new();
+        for i in 0..8 {
+            let id = format!("t{i}");
+            ss.set_intervals(id.clone(), vec![iv(0.0, 100.0)]);
+            block
+                .add_task_with_id(TestTask::new(&id, 10.0), Some(id))
+                .unwrap();
+        }
+        let blocks = [block];
+        let horizon = iv(0.0, 100.0);
+        let first = |s: &Schedule<Second>| s.iter().next().unwrap().0;
+
+        let plain = ESTScheduler::new(1).schedule(&blocks, &ss, horizon);
+        assert_eq!(first(&plain), "t0");
+        let explicit = ESTScheduler::new(1)
+            .with_tie_break(TieBreak::Deterministic)
+            .schedule(&blocks, &ss, horizon);
+        assert!(plain.diff(&explicit).is_empty());
+
+        let jittered = ESTScheduler::new(1).with_tie_break(TieBreak::Random(42));
+        let a = jittered.schedule(&blocks, &ss, horizon);
+        let b = jittered.schedule(&blocks, &ss, horizon);
+        assert!(a.diff(&b).is_empty());
+        assert_eq!(a.len(), 8);
+
+        let winners: std::collections::HashSet<_> = (0..16)
+            .map(|seed| {
+                first(
+                    &ESTScheduler::new(1)
+                        .with_tie_break(TieBreak::Random(seed))
+                        .schedule(&blocks, &ss, horizon),
+                )
+            })
+            .collect();
+        assert!(winners.len() > 1);
+
+        use crate::algorithms::{RestartsDriver, SeededAlgorithm};
+        let seeded = ESTScheduler::new(1).schedule_seeded(&blocks, &ss, horizon, 42);
+        assert!(seeded.diff(&a).is_empty());
+        let outcome = RestartsDriver::new(ESTScheduler::new(1))
+            .with_max_restarts(4)
+            .run(&blocks, &ss, horizon, |s| {
+                -s.get_interval("t7").unwrap().start().value()
+            });
+        let best = ESTScheduler::new(1).schedule_seeded(&blocks, &ss, horizon, outcome.best_seed);
+        assert!(best.diff(&outcome.best).is_empty());
+    }
+
+    // ── Milestones ────────────────────────────────────────────────────
+
+    #[test]
+    fn milestone_placed_at_instant_without_blocking() {
+        use crate::test_utils::{iv, TestTask};
+
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        for (id, size) in [("a", 10.0), ("done", 0.0), ("b", 10.0)] {
+            block
+                .add_task_with_id(TestTask::new(id, size), Some(id.into()))
+                .unwrap();
+        }
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("a", vec![iv(0.0, 10.0)]);
+        ss.set_intervals("done", vec![iv(10.0, 30.0)]);
+        ss.set_intervals("b", vec![iv(0.0, 100.0)]);
+        let blocks = [block];
+
+        let ranked = ESTScheduler::new(1).schedule_ranked(&blocks, &ss, iv(0.0, 100.0), 3);
+        let placed: Vec<_> = ranked.schedule.iter().collect();
+        assert_eq!(
+            placed,
+            vec![
+                ("a".to_string(), iv(0.0, 10.0)),
+                ("done".to_string(), iv(10.0, 10.0)),
+                ("b".to_string(), iv(10.0, 20.0)),
+            ]
+        );
+        assert!(ranked
+            .snapshots
+            .iter()
+            .flat_map(|s| &s.ranked)
+            .all(|c| c.flexibility.value().is_finite()));
+        assert!(blocks[0].task_by_id("done").unwrap().is_milestone());
+    }
+
+    // ── schedule_relaxed ──────────────────────────────────────────────
+
+    mod relaxed {
+        use super::*;
+        use crate::constraints::{ConstraintExpr, IntervalConstraint, RelaxationLadder};
+        use crate::test_utils::iv;
+        use qtty::{Quantity, Second};
+
+        type Ladder = RelaxationLadder<IntervalConstraint<Second>>;
+
+        #[derive(Debug, Clone)]
+        struct LadderTask {
+            name: String,
+            constraints: ConstraintExpr<Ladder>,
+        }
+
+        impl LadderTask {
+            fn new(name: &str, levels: &[(f64, f64)]) -> Self {
+                let mut ladder =
+                    RelaxationLadder::new(IntervalConstraint::new(iv(levels[0].0, levels[0].1)));
+                for &(a, b) in &levels[1..] {
+                    ladder = ladder.then(IntervalConstraint::new(iv(a, b)));
+                }
+                Self {
+                    name: name.to_string(),
+                    constraints: ConstraintExpr::leaf(ladder),
+                }
+            }
+        }
+
+        impl Task<Second> for LadderTask {
+            type SizeUnit = Second;
+            type ConstraintLeaf = Ladder;
+
+            fn name(&self) -> &str {
+                &self.name
+            }
+
+            fn size(&self) -> Quantity<Second> {
+                Quantity::new(10.0)
+            }
+
+            fn constraints(&self) -> Option<&ConstraintExpr<Ladder>> {
+                Some(&self.constraints)
+            }
+        }
+
+        fn run(tasks: Vec<LadderTask>) -> RelaxedSchedule<Second> {
+            let mut block: SchedulingBlock<LadderTask, Second> = SchedulingBlock::new();
+            for t in tasks {
+                let name = t.name.clone();
+                block.add_task_with_id(t, Some(name)).unwrap();
+            }
+            ESTScheduler::new(1).schedule_relaxed(&[block], iv(0.0, 100.0))
+        }
+
+        #[test]
+        fn conflicting_task_retried_at_relaxed_level() {
+            let result = run(vec![
+                LadderTask::new("a", &[(0.0, 10.0)]),
+                LadderTask::new("b", &[(0.0, 10.0), (0.0, 30.0)]),
+            ]);
+
+            assert_eq!(result.level_of("a"), Some(0));
+            assert_eq!(result.level_of("b"), Some(1));
+            assert_eq!(result.schedule.get_interval("b"), Some(iv(10.0, 20.0)));
+            assert_eq!(result.relaxed_tasks().collect::<Vec<_>>(), vec![("b", 1)]);
+        }
+
+        #[test]
+        fn emergency_level_used_only_when_needed() {
+            let result = run(vec![
+                LadderTask::new("a", &[(0.0, 10.0)]),
+                LadderTask::new("b", &[(0.0, 10.0), (5.0, 15.0), (0.0, 40.0)]),
+            ]);
+            assert_eq!(result.level_of("b"), Some(2));
+        }
+
+        #[test]
+        fn task_infeasible_at_every_level_is_not_scheduled() {
+            let result = run(vec![LadderTask::new("x", &[(0.0, 5.0), (0.0, 8.0)])]);
+            assert!(result.level_of("x").is_none());
+            assert!(result.schedule.is_empty());
+        }
+
+        #[test]
+        fn nominal_pass_matches_plain_schedule() {
+            use crate::algorithms::SchedulingAlgorithm;
+
+            let mut block: SchedulingBlock<LadderTask, Second> = SchedulingBlock::new();
+            block
+                .add_task_with_id(LadderTask::new("a", &[(30.0, 60.0)]), Some("a".into()))
+                .unwrap();
+            let blocks = [block];
+            let ss = SolutionSpace::populate(&blocks, iv(0.0, 100.0));
+            let plain = ESTScheduler::new(1).schedule(&blocks, &ss, iv(0.0, 100.0));
+            let relaxed = ESTScheduler::new(1).schedule_relaxed(&blocks, iv(0.0, 100.0));
+            assert_eq!(plain.get_interval("a"), relaxed.schedule.get_interval("a"));
+        }
+    }
+
+    mod on_grid {
+        use super::*;
+        use crate::test_utils::{iv, q};
+
+        #[test]
+        fn starts_snap_to_grid() {
+            let mut block: SchedulingBlock<crate::test_utils::TestTask, Second> =
+                SchedulingBlock::new();
+            for (id, size) in [("a", 7.0), ("b", 7.0), ("c", 25.0)] {
+                block
+                    .add_task_with_id(crate::test_utils::TestTask::new(id, size), Some(id.into()))
+                    .unwrap();
+            }
+            let mut ss = SolutionSpace::new();
+            ss.set_intervals("a", vec![iv(3.0, 100.0)]);
+            ss.set_intervals("b", vec![iv(3.0, 100.0)]);
+            // Fits between 33 and 58, but no start on the grid does.
+            ss.set_intervals("c", vec![iv(33.0, 58.0)]);
+
+            let grid = TimeGrid::new(q(0.0), q(10.0));
+            let schedule =
+                ESTScheduler::new(1).schedule_on_grid(&[block], &ss, iv(0.0, 100.0), &grid);
+            assert_eq!(schedule.get_interval("a"), Some(iv(10.0, 17.0)));
+            assert_eq!(schedule.get_interval("b"), Some(iv(20.0, 27.0)));
+            assert!(!schedule.contains_task("c"));
+            assert!(schedule.iter().all(|(_, i)| grid.is_aligned(i.start())));
+        }
+    }
+
+    mod uncertain {
+        use super::*;
+        use crate::scheduling_block::DurationDistribution;
+        use crate::test_utils::{iv, q, TestTask};
+
+        /// Observed to take 10, 10, 10 or 30 s.
+        #[derive(Debug, Clone)]
+        struct Straggler(TestTask);
+
+        impl Task<Second> for Straggler {
+            type SizeUnit = Second;
+            type ConstraintLeaf = <TestTask as Task<Second>>::ConstraintLeaf;
+
+            fn name(&self) -> &str {
+                self.0.name()
+            }
+
+            fn size(&self) -> qtty::Quantity<Second> {
+                self.0.size()
+            }
+        }
+
+        impl UncertainTask<Second> for Straggler {
+            fn duration_distribution(&self) -> DurationDistribution<Second> {
+                DurationDistribution::empirical([q(10.0), q(10.0), q(30.0), q(10.0)])
+            }
+        }
+
+        #[test]
+        fn buffers_absorb_stragglers() {
+            let mut block: SchedulingBlock<Straggler, Second> = SchedulingBlock::new();
+            let mut ss = SolutionSpace::new();
+            for id in ["a", "b"] {
+                block
+                    .add_task_with_id(Straggler(TestTask::new(id, 10.0)), Some(id.into()))
+                    .unwrap();
+                ss.set_intervals(id, vec![iv(0.0, 100.0)]);
+            }
+            let scheduler = ESTScheduler::new(1);
+
+            let tight = scheduler.schedule_uncertain(
+                std::slice::from_ref(&block),
+                &ss,
+                iv(0.0, 100.0),
+                BufferPolicy::Buffer(0.5),
+            );
+            assert_eq!(tight.schedule.get_interval("b"), Some(iv(10.0, 20.0)));
+            assert_eq!(tight.risk("a"), Some(0.25));
+
+            let safe = scheduler.schedule_uncertain(
+                &[block],
+                &ss,
+                iv(0.0, 100.0),
+                BufferPolicy::Buffer(1.0),
+            );
+            assert_eq!(safe.schedule.get_interval("b"), Some(iv(30.0, 40.0)));
+            assert_eq!(safe.buffers["a"], q(20.0));
+            assert_eq!(safe.max_risk(), 0.0);
+        }
+    }
 }
@@ -0,0 +1,128 @@
+//! Multi-resource scheduling loop.
+//!
+//! The single-resource [`engine`](super::engine) loop run on a
+//! [`Frontier`] with one lane per resource, each keeping its own cursor.
+//! At every iteration a candidate's metrics are taken over all resources it
+//! may use (see [`super::frontier`]), and the winner is placed on the
+//! resource of its EST, whose cursor then advances past the task and its
+//! gap. Every hook of the single-resource loop applies here too.
+
+use std::collections::HashMap;
+
+use crate::schedule::ResourcePool;
+use crate::scheduling_block::Task;
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+use qtty::Unit;
+
+use super::candidate::Candidate;
+use super::engine::{run_segment, SegmentHooks, SegmentOutcome};
+use super::frontier::Frontier;
+
+/// Schedules `candidates` onto the resources of `pool` with the extensions
+/// in `hooks`.
+///
+/// A task may use resource `r` if the pool deems it compatible and
+/// `resource_spaces[r]` has windows for it. Each resource's cursor starts at
+/// the horizon start or after its last existing entry, whichever is later.
+pub(crate) fn schedule_pool<T, U>(
+    pool: &mut ResourcePool<U>,
+    candidates: Vec<Candidate<T, U>>,
+    resource_spaces: &HashMap<Id, SolutionSpace<U>>,
+    horizon: Interval<U>,
+    endangered_threshold: u32,
+    hooks: SegmentHooks<'_, T, U>,
+) -> SegmentOutcome<T, U>
+where
+    T: Task<U>,
+    U: Unit + Send + Sync,
+{
+    let frontier = Frontier::pool(pool, resource_spaces, horizon, hooks.grid, hooks.delay);
+    run_segment(frontier, candidates, endangered_threshold, hooks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    fn spaces(entries: &[(&str, &str, Interval<Second>)]) -> HashMap<Id, SolutionSpace<Second>> {
+        let mut out: HashMap<Id, SolutionSpace<Second>> = HashMap::new();
+        for &(resource, task, window) in entries {
+            out.entry(resource.to_string())
+                .or_default()
+                .set_intervals(task, vec![window]);
+        }
+        out
+    }
+
+    fn candidates(tasks: &[(&str, f64)]) -> Vec<Candidate<TestTask, Second>> {
+        tasks
+            .iter()
+            .map(|&(id, size)| Candidate::new(TestTask::new(id, size), id))
+            .collect()
+    }
+
+    #[test]
+    fn tasks_spread_over_resources() {
+        let ss = spaces(&[
+            ("r1", "a", iv(0.0, 100.0)),
+            ("r1", "b", iv(0.0, 100.0)),
+            ("r2", "a", iv(0.0, 100.0)),
+            ("r2", "b", iv(0.0, 100.0)),
+        ]);
+        let mut pool = ResourcePool::new().with_resource("r1").with_resource("r2");
+        schedule_pool(
+            &mut pool,
+            candidates(&[("a", 10.0), ("b", 10.0)]),
+            &ss,
+            iv(0.0, 100.0),
+            1,
+            SegmentHooks::default(),
+        );
+        assert_eq!(pool.placements("a"), vec![("r1", iv(0.0, 10.0))]);
+        assert_eq!(pool.placements("b"), vec![("r2", iv(0.0, 10.0))]);
+    }
+
+    #[test]
+    fn compatibility_and_existing_entries_respected() {
+        let ss = spaces(&[
+            ("r1", "a", iv(0.0, 100.0)),
+            ("r2", "a", iv(0.0, 100.0)),
+            ("r1", "b", iv(0.0, 100.0)),
+        ]);
+        let mut pool = ResourcePool::new()
+            .with_resource("r1")
+            .with_resource("r2")
+            .with_compatibility("a", ["r2"]);
+        pool.add("fixed", "r2", iv(0.0, 30.0)).unwrap();
+
+        schedule_pool(
+            &mut pool,
+            candidates(&[("a", 10.0), ("b", 10.0)]),
+            &ss,
+            iv(0.0, 100.0),
+            1,
+            SegmentHooks::default(),
+        );
+        // `a` may only use r2, which is busy until 30; `b` only has r1 windows.
+        assert_eq!(pool.placements("a"), vec![("r2", iv(30.0, 40.0))]);
+        assert_eq!(pool.placements("b"), vec![("r1", iv(0.0, 10.0))]);
+    }
+
+    #[test]
+    fn earliest_resource_wins() {
+        let ss = spaces(&[("r1", "a", iv(50.0, 100.0)), ("r2", "a", iv(20.0, 100.0))]);
+        let mut pool = ResourcePool::new().with_resource("r1").with_resource("r2");
+        schedule_pool(
+            &mut pool,
+            candidates(&[("a", 10.0)]),
+            &ss,
+            iv(0.0, 100.0),
+            1,
+            SegmentHooks::default(),
+        );
+        assert_eq!(pool.resource_of("a"), Some("r2"));
+    }
+}
@@ -0,0 +1,148 @@
+//! Ranking that breaks EST ties by an objective.
+//!
+//! Same ranking as the [`engine`](super::engine), except that with
+//! [`SegmentHooks::objective`](super::engine::SegmentHooks::objective) set,
+//! the candidates sharing the leader's class and earliest start are
+//! reordered by the [`Objective`] score of their placement against the
+//! schedule so far, highest first. The objective never overrides an earlier
+//! start or a more urgent class, so endangered tasks keep their protection.
+
+use std::cmp::Ordering;
+
+use crate::constraints::soft::Objective;
+use crate::constraints::SchedulingContext;
+use crate::scheduling_block::Task;
+use qtty::Unit;
+
+use super::candidate::Candidate;
+use super::engine::rank_key;
+
+/// Reorders the ranked `candidates` tied with the leader on class and EST
+/// by the score of their placement against `ctx`, highest first.
+pub(crate) fn break_ties<T, U>(
+    candidates: &mut Vec<Candidate<T, U>>,
+    endangered_threshold: u32,
+    objective: &Objective<U>,
+    ctx: &SchedulingContext<U>,
+) where
+    T: Task<U>,
+    U: Unit,
+{
+    let class = |c: &Candidate<T, U>| {
+        let (impossible, kind, est, ..) = rank_key(c, endangered_threshold);
+        (impossible, kind, est)
+    };
+    let Some(leader) = candidates.first().map(class) else {
+        return;
+    };
+    let tied = candidates.iter().take_while(|c| class(c) == leader).count();
+    if tied > 1 {
+        let score = |c: &Candidate<T, U>| {
+            let ctx = ctx
+                .for_target(c.task_id())
+                .with_target_size(c.task().size_on_axis());
+            c.get_interval()
+                .map_or(0.0, |interval| objective.score(interval, &ctx))
+        };
+        let mut scored: Vec<_> = candidates.drain(..tied).map(|c| (score(&c), c)).collect();
+        // Stable: equal scores keep the usual ranking.
+        scored.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        candidates.splice(0..0, scored.into_iter().map(|(_, c)| c));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::est::engine::{schedule_segment_traced, SegmentHooks};
+    use crate::constraints::soft::dynamic::ModeGrouping;
+    use crate::constraints::soft::static_::CostConstraint;
+    use crate::constraints::soft::ObjectiveBuilder;
+    use crate::schedule::Schedule;
+    use crate::scheduling_block::CostProfile;
+    use crate::solution_space::{Interval, SolutionSpace};
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    fn schedule_segment_objective(
+        schedule: &mut Schedule<Second>,
+        candidates: Vec<Candidate<TestTask, Second>>,
+        solution_space: &SolutionSpace<Second>,
+        horizon: Interval<Second>,
+        endangered_threshold: u32,
+        objective: &Objective<Second>,
+    ) {
+        schedule_segment_traced(
+            schedule,
+            candidates,
+            solution_space,
+            horizon,
+            endangered_threshold,
+            SegmentHooks {
+                objective: Some(objective),
+                ..SegmentHooks::default()
+            },
+        );
+    }
+
+    fn candidate(id: &str) -> Candidate<TestTask, Second> {
+        Candidate::new(TestTask::new(id, 10.0), id)
+    }
+
+    fn space(ids: &[&str]) -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for id in ids {
+            ss.set_intervals(*id, vec![iv(0.0, 100.0)]);
+        }
+        ss
+    }
+
+    #[test]
+    fn objective_groups_modes_among_ties() {
+        let ids = ["a", "b", "c", "d"];
+        let objective = ObjectiveBuilder::new()
+            .with_dynamic(
+                ModeGrouping::new()
+                    .with_mode("a", "r")
+                    .with_mode("b", "g")
+                    .with_mode("c", "r")
+                    .with_mode("d", "g"),
+                1.0,
+            )
+            .build();
+        let mut schedule = Schedule::new();
+        schedule_segment_objective(
+            &mut schedule,
+            ids.iter().map(|id| candidate(id)).collect(),
+            &space(&ids),
+            iv(0.0, 100.0),
+            1,
+            &objective,
+        );
+        let order: Vec<_> = schedule.iter().map(|(id, _)| id).collect();
+        // The plain ranking alternates modes in ID order.
+        assert_eq!(order, vec!["a", "c", "b", "d"]);
+    }
+
+    #[test]
+    fn objective_does_not_override_earlier_starts() {
+        // Peak rate until 50: "early" at its EST scores 0, "late" scores 1.
+        let peak = CostProfile::new(1.0).with_rate(iv(0.0, 50.0), 4.0);
+        let objective = ObjectiveBuilder::new()
+            .with_static(CostConstraint::new(peak), 1.0)
+            .build();
+        let mut ss = space(&["early"]);
+        ss.set_intervals("late", vec![iv(50.0, 100.0)]);
+        let mut schedule = Schedule::new();
+        schedule_segment_objective(
+            &mut schedule,
+            vec![candidate("late"), candidate("early")],
+            &ss,
+            iv(0.0, 100.0),
+            1,
+            &objective,
+        );
+        assert_eq!(schedule.get_interval("early"), Some(iv(0.0, 10.0)));
+        assert_eq!(schedule.get_interval("late"), Some(iv(50.0, 60.0)));
+    }
+}
@@ -0,0 +1,56 @@
+//! Event hooks into the EST loop.
+//!
+//! GUIs, progress bars and loggers want to follow a run as it happens
+//! rather than inspect the schedule afterwards. A [`SchedulerObserver`]
+//! passed to [`ESTScheduler::schedule_observed`](super::ESTScheduler::schedule_observed)
+//! is called back at each step of the loop; every method has an empty
+//! default, so an observer only implements the events it cares about.
+
+use crate::solution_space::Interval;
+use qtty::{Quantity, Unit};
+
+use super::ranking::RankedCandidate;
+
+/// Callbacks invoked by the EST loop.
+///
+/// Per iteration the order is: [`on_iteration_start`](Self::on_iteration_start),
+/// one [`on_candidate_scored`](Self::on_candidate_scored) per remaining
+/// candidate in rank order, then [`on_task_placed`](Self::on_task_placed) or
+/// [`on_task_impossible`](Self::on_task_impossible) for the winner. When the
+/// loop stops, every candidate still left is reported impossible.
+pub trait SchedulerObserver<U: Unit> {
+    /// A new iteration starts at `cursor` with `remaining` candidates.
+    fn on_iteration_start(&mut self, iteration: usize, cursor: Quantity<U>, remaining: usize) {
+        let _ = (iteration, cursor, remaining);
+    }
+
+    /// A candidate was ranked at position `rank` (zero for the winner).
+    fn on_candidate_scored(
+        &mut self,
+        iteration: usize,
+        rank: usize,
+        candidate: &RankedCandidate<U>,
+    ) {
+        let _ = (iteration, rank, candidate);
+    }
+
+    /// The winner of `iteration` was placed over `interval`.
+    fn on_task_placed(&mut self, iteration: usize, task_id: &str, interval: Interval<U>) {
+        let _ = (iteration, task_id, interval);
+    }
+
+    /// `task_id` was dropped without a placement.
+    fn on_task_impossible(&mut self, task_id: &str) {
+        let _ = task_id;
+    }
+}
+
+/// Counts the iterations of a run.
+#[derive(Debug, Default)]
+pub(crate) struct IterationCounter(pub usize);
+
+impl<U: Unit> SchedulerObserver<U> for IterationCounter {
+    fn on_iteration_start(&mut self, _iteration: usize, _cursor: Quantity<U>, _remaining: usize) {
+        self.0 += 1;
+    }
+}
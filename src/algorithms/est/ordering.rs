@@ -6,6 +6,19 @@ use std::cmp::Ordering;
 
 use super::candidate::Candidate;
 
+/// How the EST ranking orders candidates whose metrics all tie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// By task ID; lexicographically earlier IDs win.
+    #[default]
+    Deterministic,
+    /// By a key drawn from the seed and the task ID, then by task ID.
+    ///
+    /// A task's key depends only on the two, so the same seed reproduces the
+    /// same schedule and different seeds give diverse ones.
+    Random(u64),
+}
+
 /// Compares candidates by task ID for deterministic tie-breaking.
 #[allow(dead_code)]
 pub fn compare_by_id<T, U>(a: &Candidate<T, U>, b: &Candidate<T, U>) -> Ordering
@@ -16,6 +29,19 @@ where
     a.task_id().cmp(b.task_id())
 }
 
+/// Compares candidates by their [`TieBreak`] key, then by task ID.
+///
+/// Under [`TieBreak::Deterministic`] every key is zero and this is
+/// [`compare_by_id`].
+#[allow(dead_code)]
+pub fn compare_by_tie<T, U>(a: &Candidate<T, U>, b: &Candidate<T, U>) -> Ordering
+where
+    T: Task<U>,
+    U: Unit,
+{
+    a.tie.cmp(&b.tie).then_with(|| compare_by_id(a, b))
+}
+
 /// Compares candidates of the same kind (both endangered or both flexible).
 #[allow(dead_code)]
 pub fn compare_same_kind<T, U>(a: &Candidate<T, U>, b: &Candidate<T, U>) -> Ordering
@@ -42,8 +68,8 @@ where
         return f64::total_cmp(&a.flexibility().value(), &b.flexibility().value());
     }
 
-    // Tie-breaker: task ID
-    compare_by_id(a, b)
+    // Tie-breaker: jitter key, then task ID
+    compare_by_tie(a, b)
 }
 
 /// Compares endangered vs flexible candidates.
@@ -98,7 +124,7 @@ where
     match (a.is_impossible(), b.is_impossible()) {
         (true, false) => return Ordering::Greater,
         (false, true) => return Ordering::Less,
-        (true, true) => return compare_by_id(a, b),
+        (true, true) => return compare_by_tie(a, b),
         (false, false) => {}
     }
 
@@ -156,6 +182,18 @@ mod tests {
         assert_eq!(compare_by_id(&a, &a), Ordering::Equal);
     }
 
+    #[test]
+    fn compare_by_tie_ranks_the_key_before_the_id() {
+        let mut a = make_candidate("alpha", 10.0, 0, Some(0.0), None, 5.0, 0.0);
+        let mut b = make_candidate("beta", 10.0, 0, Some(0.0), None, 5.0, 0.0);
+        assert_eq!(compare_by_tie(&a, &b), Ordering::Less);
+        a.tie = 2;
+        b.tie = 1;
+        assert_eq!(compare_by_tie(&a, &b), Ordering::Greater);
+        // The ID comparator ignores the key.
+        assert_eq!(compare_by_id(&a, &b), Ordering::Less);
+    }
+
     // ── compare_same_kind ─────────────────────────────────────────────
 
     #[test]
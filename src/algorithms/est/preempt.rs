@@ -0,0 +1,304 @@
+//! Priority-preemptive conflict resolution.
+//!
+//! The plain [`engine`](super::engine) never revisits a placement, so an
+//! urgent task whose windows were taken by less important work simply stays
+//! out. After the plain loop, [`preempt`] goes through the unplaced tasks,
+//! highest priority first, and looks at each one's **last** window that can
+//! hold it. A slot there is admissible if every task it overlaps has a
+//! strictly lower priority; among admissible slots the one evicting the
+//! least total priority wins (then fewer tasks, then the earliest start).
+//! The occupants are removed, the task is placed, and the evicted tasks
+//! return to the candidate pool, which is scheduled again, by the same loop
+//! and hooks as the first pass, into the time left free.
+//!
+//! Rounds repeat until one places nothing. Each placement replaces tasks by
+//! a strictly more important one, so the loop terminates.
+
+use std::collections::HashMap;
+
+use crate::schedule::Schedule;
+use crate::scheduling_block::Task;
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+use super::candidate::Candidate;
+use super::free_space;
+
+/// A task removed to make room for a more important one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eviction<U: Unit> {
+    /// The evicted task.
+    pub task_id: Id,
+    /// Where it was placed before the eviction.
+    pub interval: Interval<U>,
+    /// The task that took its place.
+    pub by: Id,
+}
+
+/// Result of [`ESTScheduler::schedule_preemptive`](super::ESTScheduler::schedule_preemptive).
+#[derive(Debug, Clone)]
+pub struct PreemptiveSchedule<U: Unit> {
+    pub schedule: Schedule<U>,
+    /// Evictions in the order they happened.
+    pub evictions: Vec<Eviction<U>>,
+}
+
+impl<U: Unit> PreemptiveSchedule<U> {
+    /// Evicted tasks that did not find a new place.
+    pub fn displaced(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self
+            .evictions
+            .iter()
+            .map(|e| e.task_id.as_str())
+            .filter(|id| !self.schedule.contains_task(*id))
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
+
+/// Scheduling loop run on what a preemption round leaves: places the
+/// candidates into the schedule within the windows of the solution space.
+pub(crate) type Reschedule<'a, T, U> =
+    dyn FnMut(&mut Schedule<U>, Vec<Candidate<T, U>>, &SolutionSpace<U>) + 'a;
+
+/// Runs preemption rounds on `schedule` for the tasks of `candidates` it
+/// does not hold, and returns the evictions. The evicted tasks go back
+/// through `reschedule` after each round.
+pub(crate) fn preempt<T, U>(
+    schedule: &mut Schedule<U>,
+    candidates: &[Candidate<T, U>],
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+    reschedule: &mut Reschedule<'_, T, U>,
+) -> Vec<Eviction<U>>
+where
+    T: Task<U> + Clone,
+    U: Unit,
+{
+    let priorities: HashMap<&str, i32> = candidates
+        .iter()
+        .map(|c| (c.task_id(), c.task().priority()))
+        .collect();
+    let mut evictions = Vec::new();
+
+    loop {
+        let mut pending: Vec<&Candidate<T, U>> = candidates
+            .iter()
+            .filter(|c| !schedule.contains_task(c.task_id()))
+            .collect();
+        pending.sort_by(|a, b| {
+            b.task()
+                .priority()
+                .cmp(&a.task().priority())
+                .then_with(|| a.task_id().cmp(b.task_id()))
+        });
+
+        let mut placed = false;
+        for candidate in pending {
+            let Some((interval, victims)) =
+                best_slot(schedule, candidate, solution_space, horizon, &priorities)
+            else {
+                continue;
+            };
+            for (task_id, old) in victims {
+                schedule.remove(task_id.as_str());
+                evictions.push(Eviction {
+                    task_id,
+                    interval: old,
+                    by: candidate.task_id().to_owned(),
+                });
+            }
+            if schedule.add(candidate.task_id(), interval).is_ok() {
+                placed = true;
+            }
+        }
+        if !placed {
+            break;
+        }
+
+        let pending: Vec<Candidate<T, U>> = candidates
+            .iter()
+            .filter(|c| !schedule.contains_task(c.task_id()))
+            .cloned()
+            .collect();
+        let space = free_space(schedule, solution_space, &pending, horizon);
+        reschedule(schedule, pending, &space);
+    }
+    evictions
+}
+
+/// A slot and the entries placing a task there would evict.
+type Slot<U> = (Interval<U>, Vec<(Id, Interval<U>)>);
+
+/// The cheapest admissible slot of `candidate`'s last window, with the
+/// entries it would evict.
+fn best_slot<T, U>(
+    schedule: &Schedule<U>,
+    candidate: &Candidate<T, U>,
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+    priorities: &HashMap<&str, i32>,
+) -> Option<Slot<U>>
+where
+    T: Task<U>,
+    U: Unit,
+{
+    let size = candidate.task().size_on_axis().value();
+    let priority = candidate.task().priority();
+    let window = solution_space
+        .get_intervals(candidate.task_id())?
+        .iter()
+        .rev()
+        .find_map(|w| {
+            let start = w.start().value().max(horizon.start().value());
+            let end = w.end().value().min(horizon.end().value());
+            (end - start >= size).then_some((start, end))
+        })?;
+
+    // Slot starts: the window edges and the edges of every entry inside it.
+    let (lo, hi) = (window.0, window.1 - size);
+    let mut starts = vec![lo, hi];
+    for (_, entry) in schedule
+        .conflicts(Interval::new(
+            Quantity::new(window.0),
+            Quantity::new(window.1),
+        ))
+        .ok()?
+    {
+        starts.push(entry.end().value());
+        starts.push(entry.start().value() - size);
+    }
+    starts.retain(|t| (lo..=hi).contains(t));
+    starts.sort_by(f64::total_cmp);
+    starts.dedup();
+
+    let mut best: Option<((i64, usize, f64), Slot<U>)> = None;
+    for start in starts {
+        let slot = Interval::new(Quantity::new(start), Quantity::new(start + size));
+        let Ok(victims) = schedule.conflicts_vec(slot) else {
+            continue;
+        };
+        let victim_priorities: Option<Vec<i32>> = victims
+            .iter()
+            .map(|(id, _)| priorities.get(id.as_str()).copied())
+            .collect();
+        let Some(victim_priorities) = victim_priorities else {
+            continue; // Entries from outside the blocks are never evicted.
+        };
+        if victim_priorities.iter().any(|&p| p >= priority) {
+            continue;
+        }
+        let cost: i64 = victim_priorities.iter().map(|&p| i64::from(p)).sum();
+        let key = (cost, victims.len(), start);
+        let better = best.as_ref().is_none_or(|(k, _)| {
+            (key.0, key.1)
+                .cmp(&(k.0, k.1))
+                .then(key.2.total_cmp(&k.2))
+                .is_lt()
+        });
+        if better {
+            best = Some((key, (slot, victims)));
+        }
+    }
+    best.map(|(_, slot)| slot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::est::engine::schedule_segment;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    fn run(
+        schedule: &mut Schedule<Second>,
+        candidates: &[Candidate<TestTask, Second>],
+        ss: &SolutionSpace<Second>,
+    ) -> Vec<Eviction<Second>> {
+        let horizon = iv(0.0, 100.0);
+        preempt(
+            schedule,
+            candidates,
+            ss,
+            horizon,
+            &mut |schedule, pending, space| schedule_segment(schedule, pending, space, horizon, 1),
+        )
+    }
+
+    fn candidate(id: &str, size: f64, priority: i32) -> Candidate<TestTask, Second> {
+        Candidate::new(TestTask::new(id, size).with_priority(priority), id)
+    }
+
+    fn space(entries: &[(&str, f64, f64)]) -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for &(id, a, b) in entries {
+            ss.add_interval(id, iv(a, b));
+        }
+        ss
+    }
+
+    #[test]
+    fn urgent_task_evicts_lower_priority() {
+        let candidates = vec![candidate("low", 10.0, 1), candidate("urgent", 10.0, 9)];
+        let ss = space(&[("low", 0.0, 100.0), ("urgent", 20.0, 30.0)]);
+        let mut schedule = Schedule::new();
+        schedule.add("low", iv(15.0, 25.0)).unwrap();
+
+        let evictions = run(&mut schedule, &candidates, &ss);
+        assert_eq!(
+            evictions,
+            vec![Eviction {
+                task_id: "low".into(),
+                interval: iv(15.0, 25.0),
+                by: "urgent".into(),
+            }]
+        );
+        assert_eq!(schedule.get_interval("urgent"), Some(iv(20.0, 30.0)));
+        // The evicted task is rescheduled into the remaining free time.
+        assert!(schedule.contains_task("low"));
+    }
+
+    #[test]
+    fn equal_priority_is_not_evicted() {
+        let candidates = vec![candidate("a", 10.0, 5), candidate("b", 10.0, 5)];
+        let ss = space(&[("a", 0.0, 10.0), ("b", 0.0, 10.0)]);
+        let mut schedule = Schedule::new();
+        schedule.add("a", iv(0.0, 10.0)).unwrap();
+        assert!(run(&mut schedule, &candidates, &ss).is_empty());
+        assert!(!schedule.contains_task("b"));
+    }
+
+    #[test]
+    fn cheapest_slot_wins() {
+        let candidates = vec![
+            candidate("p1", 10.0, 1),
+            candidate("p3", 10.0, 3),
+            candidate("urgent", 10.0, 9),
+        ];
+        let ss = space(&[("p1", 10.0, 20.0), ("p3", 0.0, 10.0), ("urgent", 0.0, 20.0)]);
+        let mut schedule = Schedule::new();
+        schedule.add("p3", iv(0.0, 10.0)).unwrap();
+        schedule.add("p1", iv(10.0, 20.0)).unwrap();
+
+        let evictions = run(&mut schedule, &candidates, &ss);
+        assert_eq!(evictions.len(), 1);
+        assert_eq!(evictions[0].task_id, "p1");
+        assert_eq!(schedule.get_interval("urgent"), Some(iv(10.0, 20.0)));
+    }
+
+    #[test]
+    fn displaced_lists_tasks_left_out() {
+        let result = PreemptiveSchedule {
+            schedule: Schedule::<Second>::new(),
+            evictions: vec![Eviction {
+                task_id: "x".into(),
+                interval: iv(0.0, 1.0),
+                by: "y".into(),
+            }],
+        };
+        assert_eq!(result.displaced(), vec!["x"]);
+    }
+}
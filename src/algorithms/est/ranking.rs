@@ -0,0 +1,305 @@
+//! Per-iteration ranking snapshots.
+//!
+//! At every iteration of the EST loop the remaining candidates are sorted and
+//! the first one is placed. A [`RankingSnapshot`] records the top of that
+//! ranking — the winner and the runners-up, with the metrics they were sorted
+//! by and the criterion that put the winner ahead of each of them — so that a
+//! reviewer can see the alternatives the scheduler passed over.
+
+use std::fmt;
+
+use crate::schedule::Schedule;
+use crate::scheduling_block::Task;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+use super::candidate::Candidate;
+use super::observer::SchedulerObserver;
+
+/// Classification of a candidate at ranking time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandidateKind {
+    /// Flexibility below the endangered threshold.
+    Endangered,
+    /// Flexibility at or above the endangered threshold.
+    Flexible,
+    /// No feasible start left in the remaining horizon.
+    Impossible,
+}
+
+/// First ranking criterion on which the winner beat a runner-up.
+///
+/// Criteria are listed in the order the EST loop applies them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankReason {
+    /// The runner-up had no feasible start.
+    Feasibility,
+    /// The winner was endangered and the runner-up was not.
+    Kind,
+    /// The winner could start earlier.
+    EarlierStart,
+    /// Same start; the winner has higher priority.
+    HigherPriority,
+    /// Same start and priority; the winner has fewer alternatives.
+    LessFlexible,
+    /// Every metric tied; the winner drew the lower seeded jitter key.
+    Jitter,
+    /// Every metric tied; the winner's ID sorts first.
+    TaskId,
+}
+
+impl fmt::Display for RankReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            RankReason::Feasibility => "runner-up has no feasible start",
+            RankReason::Kind => "winner is endangered",
+            RankReason::EarlierStart => "earlier start",
+            RankReason::HigherPriority => "higher priority",
+            RankReason::LessFlexible => "less flexible",
+            RankReason::Jitter => "tie broken by seeded jitter",
+            RankReason::TaskId => "tie broken by ID",
+        };
+        f.write_str(text)
+    }
+}
+
+/// One candidate as ranked in a snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedCandidate<U: Unit> {
+    pub task_id: Id,
+    pub kind: CandidateKind,
+    pub est: Option<Quantity<U>>,
+    pub deadline: Option<Quantity<U>>,
+    pub flexibility: Quantity<U>,
+    pub priority: i32,
+    /// Why the winner ranked ahead of this candidate (`None` for the winner).
+    pub beaten_because: Option<RankReason>,
+}
+
+/// Top of the candidate ranking at one iteration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankingSnapshot<U: Unit> {
+    /// Zero-based iteration index.
+    pub iteration: usize,
+    /// Start of the remaining horizon when the ranking was made.
+    pub cursor: Quantity<U>,
+    /// Winner first, then runners-up in rank order.
+    pub ranked: Vec<RankedCandidate<U>>,
+    /// Interval given to the winner, or `None` if it could not be placed.
+    pub placed: Option<Interval<U>>,
+}
+
+impl<U: Unit> RankingSnapshot<U> {
+    /// The candidate that was picked at this iteration.
+    pub fn winner(&self) -> &RankedCandidate<U> {
+        &self.ranked[0]
+    }
+
+    /// The candidates that were passed over, in rank order.
+    pub fn runners_up(&self) -> &[RankedCandidate<U>] {
+        &self.ranked[1..]
+    }
+}
+
+impl<U: Unit> fmt::Display for RankingSnapshot<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let winner = self.winner();
+        match self.placed {
+            Some(iv) => writeln!(
+                f,
+                "#{} @ {:.3}: {} -> {iv}",
+                self.iteration,
+                self.cursor.value(),
+                winner.task_id
+            )?,
+            None => writeln!(
+                f,
+                "#{} @ {:.3}: {} (not placed)",
+                self.iteration,
+                self.cursor.value(),
+                winner.task_id
+            )?,
+        }
+        for runner in self.runners_up() {
+            let reason = runner.beaten_because.expect("runners-up carry a reason");
+            writeln!(f, "  vs {}: {reason}", runner.task_id)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of [`ESTScheduler::schedule_ranked`](super::ESTScheduler::schedule_ranked).
+#[derive(Debug, Clone)]
+pub struct RankedSchedule<U: Unit> {
+    pub schedule: Schedule<U>,
+    /// One snapshot per iteration, in order.
+    pub snapshots: Vec<RankingSnapshot<U>>,
+}
+
+/// Collects snapshots while the EST loop runs.
+pub(crate) struct RankingTrace<U: Unit> {
+    top_n: usize,
+    pub(crate) snapshots: Vec<RankingSnapshot<U>>,
+}
+
+impl<U: Unit> RankingTrace<U> {
+    pub(crate) fn new(top_n: usize) -> Self {
+        Self {
+            top_n: top_n.max(1),
+            snapshots: Vec::new(),
+        }
+    }
+}
+
+impl<U: Unit> SchedulerObserver<U> for RankingTrace<U> {
+    fn on_iteration_start(&mut self, _iteration: usize, cursor: Quantity<U>, _remaining: usize) {
+        self.snapshots.push(RankingSnapshot {
+            iteration: self.snapshots.len(),
+            cursor,
+            ranked: Vec::new(),
+            placed: None,
+        });
+    }
+
+    fn on_candidate_scored(
+        &mut self,
+        _iteration: usize,
+        rank: usize,
+        candidate: &RankedCandidate<U>,
+    ) {
+        if rank < self.top_n {
+            if let Some(last) = self.snapshots.last_mut() {
+                last.ranked.push(candidate.clone());
+            }
+        }
+    }
+
+    fn on_task_placed(&mut self, _iteration: usize, _task_id: &str, interval: Interval<U>) {
+        if let Some(last) = self.snapshots.last_mut() {
+            last.placed = Some(interval);
+        }
+    }
+}
+
+/// `c` as ranked at position `rank` behind `winner`.
+pub(crate) fn ranked<T: Task<U>, U: Unit>(
+    winner: &Candidate<T, U>,
+    c: &Candidate<T, U>,
+    rank: usize,
+    endangered_threshold: u32,
+) -> RankedCandidate<U> {
+    RankedCandidate {
+        task_id: c.task_id().to_owned(),
+        kind: kind_of(c, endangered_threshold),
+        est: c.est(),
+        deadline: c.deadline(),
+        flexibility: c.flexibility(),
+        priority: c.priority(),
+        beaten_because: (rank > 0).then(|| reason(winner, c, endangered_threshold)),
+    }
+}
+
+fn kind_of<T: Task<U>, U: Unit>(c: &Candidate<T, U>, threshold: u32) -> CandidateKind {
+    if c.is_impossible() {
+        CandidateKind::Impossible
+    } else if c.is_endangered(threshold) {
+        CandidateKind::Endangered
+    } else {
+        CandidateKind::Flexible
+    }
+}
+
+/// Mirrors the sort key in [`update_candidates`](super::engine::update_candidates).
+fn reason<T: Task<U>, U: Unit>(
+    winner: &Candidate<T, U>,
+    other: &Candidate<T, U>,
+    threshold: u32,
+) -> RankReason {
+    if other.is_impossible() && !winner.is_impossible() {
+        return RankReason::Feasibility;
+    }
+    if kind_of(winner, threshold) != kind_of(other, threshold) {
+        return RankReason::Kind;
+    }
+    if winner.est().map(|q| q.value()) != other.est().map(|q| q.value()) {
+        return RankReason::EarlierStart;
+    }
+    if winner.priority() != other.priority() {
+        return RankReason::HigherPriority;
+    }
+    if winner.flexibility().value() != other.flexibility().value() {
+        return RankReason::LessFlexible;
+    }
+    if winner.tie != other.tie {
+        return RankReason::Jitter;
+    }
+    RankReason::TaskId
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{q, TestTask};
+    use qtty::Second;
+
+    fn candidate(
+        id: &str,
+        est: Option<f64>,
+        flex: f64,
+        priority: i32,
+    ) -> Candidate<TestTask, Second> {
+        let mut c = Candidate::new(TestTask::new(id, 10.0).with_priority(priority), id);
+        c.est = est.map(q);
+        c.flexibility = q(flex);
+        c
+    }
+
+    #[test]
+    fn reasons_follow_sort_criteria() {
+        let winner = candidate("a", Some(0.0), 10.0, 1);
+        let cases = [
+            (candidate("b", None, 0.0, 1), RankReason::Feasibility),
+            (candidate("b", Some(0.0), 2.0, 1), RankReason::Kind),
+            (candidate("b", Some(5.0), 10.0, 1), RankReason::EarlierStart),
+            (
+                candidate("b", Some(0.0), 10.0, 0),
+                RankReason::HigherPriority,
+            ),
+            (candidate("b", Some(0.0), 12.0, 1), RankReason::LessFlexible),
+            (candidate("b", Some(0.0), 10.0, 1), RankReason::TaskId),
+            (
+                candidate("b", Some(0.0), 10.0, 1).with_tie_jitter(3),
+                RankReason::Jitter,
+            ),
+        ];
+        for (other, expected) in cases {
+            assert_eq!(reason(&winner, &other, 5), expected);
+        }
+    }
+
+    #[test]
+    fn record_keeps_top_n() {
+        let candidates = [
+            candidate("a", Some(0.0), 10.0, 1),
+            candidate("b", Some(5.0), 10.0, 1),
+            candidate("c", Some(9.0), 10.0, 1),
+        ];
+        let mut trace = RankingTrace::new(2);
+        trace.on_iteration_start(0, q(0.0), candidates.len());
+        for (rank, c) in candidates.iter().enumerate() {
+            trace.on_candidate_scored(0, rank, &ranked(&candidates[0], c, rank, 5));
+        }
+        trace.on_task_placed(0, "a", crate::test_utils::iv(0.0, 10.0));
+
+        let snap = &trace.snapshots[0];
+        assert_eq!(snap.ranked.len(), 2);
+        assert_eq!(snap.winner().task_id, "a");
+        assert!(snap.winner().beaten_because.is_none());
+        assert_eq!(
+            snap.runners_up()[0].beaten_because,
+            Some(RankReason::EarlierStart)
+        );
+        assert!(snap.to_string().contains("vs b: earlier start"));
+    }
+}
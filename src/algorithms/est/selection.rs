@@ -0,0 +1,169 @@
+//! Pluggable candidate selection.
+//!
+//! At every iteration the EST loop ranks the remaining candidates and places
+//! the first one. The default ranking is fixed (see the
+//! [module docs](super)); a [`SelectionHeuristic`] replaces it with any
+//! other order over the candidates' metrics — earliest start, latest start
+//! (deadline), flexibility and priority — while the rest of the loop
+//! (metric refresh, placement, cursor advance) stays the same.
+//!
+//! Three heuristics ship with the crate:
+//!
+//! - [`MostEndangeredFirst`] — least flexibility first;
+//! - [`EarliestDeadlineFirst`] — earliest latest-start first;
+//! - [`HighestPriorityFirst`] — highest priority first.
+//!
+//! Any `Fn(&Candidate, &Candidate) -> Ordering` is a heuristic too.
+
+use std::cmp::Ordering;
+
+use crate::scheduling_block::Task;
+use qtty::Unit;
+
+use super::candidate::Candidate;
+
+/// Orders candidates for placement; the least candidate is placed next.
+///
+/// Candidates without a feasible start are always ranked last, and
+/// candidates the heuristic considers equal keep the order of the default
+/// EST ranking, so `compare` only has to express the preference itself.
+pub trait SelectionHeuristic<T: Task<U>, U: Unit> {
+    /// Returns [`Ordering::Less`] if `a` should be placed before `b`.
+    ///
+    /// Must be a total order over feasible candidates.
+    fn compare(&self, a: &Candidate<T, U>, b: &Candidate<T, U>) -> Ordering;
+}
+
+impl<T, U, F> SelectionHeuristic<T, U> for F
+where
+    T: Task<U>,
+    U: Unit,
+    F: Fn(&Candidate<T, U>, &Candidate<T, U>) -> Ordering,
+{
+    fn compare(&self, a: &Candidate<T, U>, b: &Candidate<T, U>) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// Least flexible first, then earliest start.
+///
+/// Unlike the default ranking, flexible candidates that could start earlier
+/// do not go ahead of an endangered one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MostEndangeredFirst;
+
+impl<T: Task<U>, U: Unit> SelectionHeuristic<T, U> for MostEndangeredFirst {
+    fn compare(&self, a: &Candidate<T, U>, b: &Candidate<T, U>) -> Ordering {
+        a.flexibility()
+            .value()
+            .total_cmp(&b.flexibility().value())
+            .then_with(|| by_start(a, b))
+    }
+}
+
+/// Earliest latest-start first, then earliest start.
+///
+/// The deadline is the latest start at which the task still fits its
+/// windows; candidates without one go after those with one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EarliestDeadlineFirst;
+
+impl<T: Task<U>, U: Unit> SelectionHeuristic<T, U> for EarliestDeadlineFirst {
+    fn compare(&self, a: &Candidate<T, U>, b: &Candidate<T, U>) -> Ordering {
+        let deadline = |c: &Candidate<T, U>| c.deadline().map_or(f64::INFINITY, |d| d.value());
+        deadline(a)
+            .total_cmp(&deadline(b))
+            .then_with(|| by_start(a, b))
+    }
+}
+
+/// Highest priority first (boosts and aging included), then earliest start.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HighestPriorityFirst;
+
+impl<T: Task<U>, U: Unit> SelectionHeuristic<T, U> for HighestPriorityFirst {
+    fn compare(&self, a: &Candidate<T, U>, b: &Candidate<T, U>) -> Ordering {
+        b.priority().cmp(&a.priority()).then_with(|| by_start(a, b))
+    }
+}
+
+fn by_start<T: Task<U>, U: Unit>(a: &Candidate<T, U>, b: &Candidate<T, U>) -> Ordering {
+    let start = |c: &Candidate<T, U>| c.est().map_or(f64::INFINITY, |t| t.value());
+    start(a).total_cmp(&start(b))
+}
+
+/// Re-sorts `candidates`, already in default rank order, by `heuristic`.
+///
+/// The sort is stable, so ties keep the default order.
+pub(crate) fn select_with<T, U>(
+    candidates: &mut [Candidate<T, U>],
+    heuristic: &dyn SelectionHeuristic<T, U>,
+) where
+    T: Task<U>,
+    U: Unit,
+{
+    candidates.sort_by(|a, b| {
+        a.is_impossible()
+            .cmp(&b.is_impossible())
+            .then_with(|| heuristic.compare(a, b))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{q, TestTask};
+    use qtty::Second;
+
+    fn candidate(
+        id: &str,
+        est: f64,
+        deadline: f64,
+        flex: f64,
+        prio: i32,
+    ) -> Candidate<TestTask, Second> {
+        let mut c = Candidate::new(TestTask::new(id, 1.0).with_priority(prio), id);
+        c.est = Some(q(est));
+        c.deadline = Some(q(deadline));
+        c.flexibility = q(flex);
+        c
+    }
+
+    fn order(heuristic: &dyn SelectionHeuristic<TestTask, Second>) -> Vec<String> {
+        let mut candidates = vec![
+            candidate("early", 0.0, 40.0, 50.0, 1),
+            candidate("tight", 20.0, 25.0, 5.0, 2),
+            candidate("vip", 10.0, 60.0, 30.0, 9),
+            Candidate::new(TestTask::new("never", 1.0).with_priority(99), "never"),
+        ];
+        select_with(&mut candidates, heuristic);
+        candidates.into_iter().map(|c| c.task_id).collect()
+    }
+
+    #[test]
+    fn shipped_heuristics() {
+        assert_eq!(
+            order(&MostEndangeredFirst),
+            ["tight", "vip", "early", "never"]
+        );
+        assert_eq!(
+            order(&EarliestDeadlineFirst),
+            ["tight", "early", "vip", "never"]
+        );
+        assert_eq!(
+            order(&HighestPriorityFirst),
+            ["vip", "tight", "early", "never"]
+        );
+    }
+
+    #[test]
+    fn closures_are_heuristics() {
+        let latest_first = |a: &Candidate<TestTask, Second>, b: &Candidate<TestTask, Second>| {
+            b.est()
+                .unwrap()
+                .value()
+                .total_cmp(&a.est().unwrap().value())
+        };
+        assert_eq!(order(&latest_first), ["tight", "vip", "early", "never"]);
+    }
+}
@@ -0,0 +1,210 @@
+//! Sequence-dependent transition times.
+//!
+//! Same loop as the [`engine`](super::engine), but the horizon each
+//! candidate is evaluated on depends on the task placed before it: the
+//! candidate may start no earlier than
+//!
+//! ```text
+//! previous.end + previous.gap_after() + delay(previous, candidate)
+//! ```
+//!
+//! where `delay` is a slew between positions ([`TransitionModel`]) or a
+//! changeover between task classes ([`SetupMatrix`]). A target close to the
+//! previous one, or of the same class, thus gets an earlier EST. Before the
+//! first placement there is no previous task and no delay.
+//!
+//! The delays built here are passed to the loop as
+//! [`SegmentHooks::delay`](super::engine::SegmentHooks::delay); on a
+//! resource pool each resource keeps its own previous task.
+
+use std::fmt::Debug;
+
+use crate::scheduling_block::{SetupMatrix, SetupTask, SpatialTask, Task, TransitionModel};
+use qtty::{Quantity, Unit};
+
+/// Delay of the slew from the previously placed task's position.
+pub(crate) fn transition_delay<T, U, C, M>(model: &M) -> impl Fn(&T, &T) -> Quantity<U> + '_
+where
+    T: Task<U> + SpatialTask<C>,
+    U: Unit,
+    C: Debug,
+    M: TransitionModel<C, U> + ?Sized,
+{
+    move |prev: &T, next: &T| model.transition_time(prev.position(), next.position())
+}
+
+/// Delay of the setup from the previously placed task's class.
+pub(crate) fn setup_delay<T, U>(matrix: &SetupMatrix<U>) -> impl Fn(&T, &T) -> Quantity<U> + '_
+where
+    T: Task<U> + SetupTask,
+    U: Unit,
+{
+    move |prev: &T, next: &T| matrix.setup(prev.setup_class(), next.setup_class())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::est::candidate::Candidate;
+    use crate::algorithms::est::engine::{schedule_segment_traced, SegmentHooks};
+    use crate::schedule::Schedule;
+    use crate::solution_space::{Interval, SolutionSpace};
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    fn schedule_segment_delayed(
+        schedule: &mut Schedule<Second>,
+        candidates: Vec<Candidate<Target, Second>>,
+        solution_space: &SolutionSpace<Second>,
+        horizon: Interval<Second>,
+        delay: &dyn Fn(&Target, &Target) -> Quantity<Second>,
+    ) {
+        schedule_segment_traced(
+            schedule,
+            candidates,
+            solution_space,
+            horizon,
+            1,
+            SegmentHooks {
+                delay: Some(delay),
+                ..SegmentHooks::default()
+            },
+        );
+    }
+
+    #[derive(Debug, Clone)]
+    struct Target {
+        task: TestTask,
+        position: f64,
+        class: &'static str,
+    }
+
+    impl Task<Second> for Target {
+        type SizeUnit = Second;
+        type ConstraintLeaf = <TestTask as Task<Second>>::ConstraintLeaf;
+
+        fn name(&self) -> &str {
+            self.task.name()
+        }
+
+        fn size(&self) -> Quantity<Second> {
+            self.task.size()
+        }
+
+        fn priority(&self) -> i32 {
+            self.task.priority()
+        }
+    }
+
+    impl SpatialTask<f64> for Target {
+        fn position(&self) -> &f64 {
+            &self.position
+        }
+    }
+
+    impl SetupTask for Target {
+        fn setup_class(&self) -> &str {
+            self.class
+        }
+    }
+
+    fn candidate(id: &str, position: f64, priority: i32) -> Candidate<Target, Second> {
+        classed(id, "", position, priority)
+    }
+
+    fn classed(
+        id: &str,
+        class: &'static str,
+        position: f64,
+        priority: i32,
+    ) -> Candidate<Target, Second> {
+        let task = TestTask::new(id, 10.0).with_priority(priority);
+        Candidate::new(
+            Target {
+                task,
+                position,
+                class,
+            },
+            id,
+        )
+    }
+
+    fn slew(a: &f64, b: &f64) -> Quantity<Second> {
+        q((a - b).abs())
+    }
+
+    fn space(ids: &[&str]) -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for id in ids {
+            ss.set_intervals(*id, vec![iv(0.0, 200.0)]);
+        }
+        ss
+    }
+
+    #[test]
+    fn nearby_target_goes_next() {
+        let mut schedule = Schedule::new();
+        schedule_segment_delayed(
+            &mut schedule,
+            vec![
+                candidate("start", 0.0, 10),
+                candidate("far", 50.0, 0),
+                candidate("near", 5.0, 0),
+            ],
+            &space(&["start", "far", "near"]),
+            iv(0.0, 200.0),
+            &transition_delay(&slew),
+        );
+        let placed: Vec<_> = schedule.iter().collect();
+        assert_eq!(
+            placed,
+            vec![
+                ("start".to_string(), iv(0.0, 10.0)),
+                ("near".to_string(), iv(15.0, 25.0)),
+                ("far".to_string(), iv(70.0, 80.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn transition_past_horizon_is_impossible() {
+        let mut schedule = Schedule::new();
+        schedule_segment_delayed(
+            &mut schedule,
+            vec![candidate("start", 0.0, 10), candidate("far", 500.0, 0)],
+            &space(&["start", "far"]),
+            iv(0.0, 200.0),
+            &transition_delay(&slew),
+        );
+        assert!(schedule.contains_task("start"));
+        assert!(!schedule.contains_task("far"));
+    }
+
+    #[test]
+    fn same_class_avoids_changeover() {
+        let matrix = SetupMatrix::new(q(20.0)).with_setup("paint", "drill", q(5.0));
+        let mut schedule = Schedule::new();
+        schedule_segment_delayed(
+            &mut schedule,
+            vec![
+                classed("first", "paint", 0.0, 10),
+                classed("drill", "drill", 0.0, 5),
+                classed("sand", "sand", 0.0, 5),
+                classed("paint-2", "paint", 0.0, 0),
+            ],
+            &space(&["first", "drill", "sand", "paint-2"]),
+            iv(0.0, 200.0),
+            &setup_delay(&matrix),
+        );
+        let placed: Vec<_> = schedule.iter().collect();
+        assert_eq!(
+            placed,
+            vec![
+                ("first".to_string(), iv(0.0, 10.0)),
+                ("paint-2".to_string(), iv(10.0, 20.0)),
+                ("drill".to_string(), iv(25.0, 35.0)),
+                ("sand".to_string(), iv(55.0, 65.0)),
+            ]
+        );
+    }
+}
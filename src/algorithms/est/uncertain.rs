@@ -0,0 +1,276 @@
+//! Scheduling loop for tasks with uncertain durations.
+//!
+//! Each [`UncertainTask`] is planned at a length read off its
+//! [`DurationDistribution`] according to a [`BufferPolicy`], then the
+//! ordinary [`engine`](super::engine) loop, hooks included, runs on those
+//! lengths. Afterwards
+//! every placement is scored with the probability that the task's actual
+//! duration runs past the time left before the next placement starts.
+
+use std::collections::HashMap;
+
+use crate::constraints::ConstraintExpr;
+use crate::schedule::Schedule;
+use crate::scheduling_block::{DurationDistribution, Task, UncertainTask};
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+use super::candidate::Candidate;
+use super::engine::{schedule_segment_traced, SegmentHooks};
+
+/// How [`ESTScheduler::schedule_uncertain`](super::ESTScheduler::schedule_uncertain)
+/// protects placements against overruns.
+///
+/// The probability is clamped to `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BufferPolicy {
+    /// Reserve the duration quantile at this probability for each task.
+    Quantile(f64),
+    /// Reserve the task's nominal size, then keep the cursor idle until the
+    /// duration quantile at this probability.
+    Buffer(f64),
+}
+
+impl BufferPolicy {
+    fn probability(self) -> f64 {
+        match self {
+            Self::Quantile(p) | Self::Buffer(p) => p,
+        }
+    }
+
+    /// Planned length and trailing buffer for a task of `nominal` size.
+    fn plan<U: Unit>(
+        self,
+        nominal: Quantity<U>,
+        duration: &DurationDistribution<U>,
+    ) -> (Quantity<U>, Quantity<U>) {
+        let quantile = duration.quantile(self.probability());
+        match self {
+            Self::Quantile(_) => (quantile, Quantity::new(0.0)),
+            Self::Buffer(_) => (
+                nominal,
+                Quantity::new((quantile.value() - nominal.value()).max(0.0)),
+            ),
+        }
+    }
+}
+
+/// Result of [`ESTScheduler::schedule_uncertain`](super::ESTScheduler::schedule_uncertain).
+#[derive(Debug, Clone)]
+pub struct UncertainSchedule<U: Unit> {
+    pub schedule: Schedule<U>,
+    /// Policy the schedule was planned with.
+    pub policy: BufferPolicy,
+    /// Idle time kept after every placed task; zero under
+    /// [`BufferPolicy::Quantile`].
+    pub buffers: HashMap<Id, Quantity<U>>,
+    /// Probability that every placed task, started on time, is still running
+    /// when the next placement starts (or when the horizon ends, for the
+    /// last one).
+    pub overrun_risk: HashMap<Id, f64>,
+}
+
+impl<U: Unit> UncertainSchedule<U> {
+    /// Overrun risk of the placement of `id`, if it was placed.
+    pub fn risk(&self, id: &str) -> Option<f64> {
+        self.overrun_risk.get(id).copied()
+    }
+
+    /// Highest overrun risk of any placement; `0.0` for an empty schedule.
+    pub fn max_risk(&self) -> f64 {
+        self.overrun_risk.values().copied().fold(0.0, f64::max)
+    }
+
+    /// Expected number of placements that overrun.
+    pub fn expected_overruns(&self) -> f64 {
+        self.overrun_risk.values().sum()
+    }
+}
+
+/// A task seen by the engine at its planned length, with its buffer added
+/// to the gap after it.
+#[derive(Debug)]
+pub(crate) struct Planned<T> {
+    task: T,
+    size: f64,
+    buffer: f64,
+}
+
+impl<T, U> Task<U> for Planned<T>
+where
+    T: Task<U>,
+    U: Unit,
+{
+    type SizeUnit = U;
+    type ConstraintLeaf = T::ConstraintLeaf;
+
+    fn name(&self) -> &str {
+        self.task.name()
+    }
+
+    fn size(&self) -> Quantity<U> {
+        Quantity::new(self.size)
+    }
+
+    fn priority(&self) -> i32 {
+        self.task.priority()
+    }
+
+    fn constraints(&self) -> Option<&ConstraintExpr<Self::ConstraintLeaf>> {
+        self.task.constraints()
+    }
+
+    fn gap_after(&self) -> Quantity<U> {
+        self.task.gap_after() + Quantity::new(self.buffer)
+    }
+
+    fn compute_gap_after(&self, previous_task: &Self) -> Quantity<U> {
+        self.task.compute_gap_after(&previous_task.task)
+    }
+}
+
+/// Schedules `candidates` at the lengths `policy` plans for them and scores
+/// each placement's overrun risk, running the loop with `hooks`.
+pub(crate) fn schedule_segment_uncertain<T, U>(
+    candidates: Vec<Candidate<T, U>>,
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+    endangered_threshold: u32,
+    policy: BufferPolicy,
+    hooks: SegmentHooks<'_, Planned<T>, U>,
+) -> UncertainSchedule<U>
+where
+    T: UncertainTask<U>,
+    U: Unit + Send + Sync,
+{
+    let mut durations = HashMap::with_capacity(candidates.len());
+    let mut buffers = HashMap::with_capacity(candidates.len());
+    let planned = candidates
+        .into_iter()
+        .map(|candidate| {
+            let duration = candidate.task().duration_distribution();
+            let (size, buffer) = policy.plan(candidate.task().size_on_axis(), &duration);
+            durations.insert(candidate.task_id.clone(), duration);
+            buffers.insert(candidate.task_id.clone(), buffer);
+            let mut planned = Candidate::new(
+                Planned {
+                    task: candidate.task,
+                    size: size.value(),
+                    buffer: buffer.value(),
+                },
+                candidate.task_id,
+            );
+            planned.tie = candidate.tie;
+            planned
+        })
+        .collect();
+
+    let mut schedule = Schedule::new();
+    schedule_segment_traced(
+        &mut schedule,
+        planned,
+        solution_space,
+        horizon,
+        endangered_threshold,
+        hooks,
+    );
+
+    let placed: Vec<_> = schedule.iter().collect();
+    let mut overrun_risk = HashMap::with_capacity(placed.len());
+    for (i, (id, interval)) in placed.iter().enumerate() {
+        let next_start = placed
+            .get(i + 1)
+            .map_or(horizon.end(), |(_, next)| next.start());
+        let risk = durations[id].exceedance(next_start - interval.start());
+        overrun_risk.insert(id.clone(), risk);
+    }
+    buffers.retain(|id, _| overrun_risk.contains_key(id));
+
+    UncertainSchedule {
+        schedule,
+        policy,
+        buffers,
+        overrun_risk,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    /// A 10 s task that takes 20 s one time in twenty.
+    #[derive(Debug, Clone)]
+    struct Risky(TestTask);
+
+    impl Task<Second> for Risky {
+        type SizeUnit = Second;
+        type ConstraintLeaf = <TestTask as Task<Second>>::ConstraintLeaf;
+
+        fn name(&self) -> &str {
+            self.0.name()
+        }
+
+        fn size(&self) -> Quantity<Second> {
+            self.0.size()
+        }
+    }
+
+    impl UncertainTask<Second> for Risky {
+        fn duration_distribution(&self) -> DurationDistribution<Second> {
+            DurationDistribution::from_p95(q(10.0), q(20.0))
+        }
+    }
+
+    fn run(policy: BufferPolicy) -> UncertainSchedule<Second> {
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("a", vec![iv(0.0, 100.0)]);
+        ss.set_intervals("b", vec![iv(0.0, 100.0)]);
+        let candidates = ["a", "b"]
+            .into_iter()
+            .map(|id| Candidate::new(Risky(TestTask::new(id, 10.0)), id))
+            .collect();
+        schedule_segment_uncertain(
+            candidates,
+            &ss,
+            iv(0.0, 100.0),
+            1,
+            policy,
+            SegmentHooks::default(),
+        )
+    }
+
+    #[test]
+    fn quantile_policy_reserves_the_quantile() {
+        let result = run(BufferPolicy::Quantile(0.95));
+        let a = result.schedule.get_interval("a").unwrap();
+        assert!((a.duration().value() - 20.0).abs() < 1e-6);
+        assert!((result.risk("a").unwrap() - 0.05).abs() < 1e-6);
+        assert_eq!(result.buffers["a"], q(0.0));
+    }
+
+    #[test]
+    fn buffer_policy_keeps_nominal_placements_apart() {
+        let result = run(BufferPolicy::Buffer(0.95));
+        assert_eq!(result.schedule.get_interval("a"), Some(iv(0.0, 10.0)));
+        let b = result.schedule.get_interval("b").unwrap();
+        assert!((b.start().value() - 20.0).abs() < 1e-6);
+        assert!((result.buffers["a"].value() - 10.0).abs() < 1e-6);
+        assert!((result.risk("a").unwrap() - 0.05).abs() < 1e-6);
+        // "b" has the rest of the horizon.
+        assert!(result.risk("b").unwrap() < 1e-6);
+        assert!((result.max_risk() - 0.05).abs() < 1e-6);
+    }
+
+    #[test]
+    fn nominal_planning_reports_even_odds() {
+        // Back to back at the median: each overrun is a coin flip, except
+        // the last placement's.
+        let result = run(BufferPolicy::Quantile(0.5));
+        assert_eq!(result.schedule.get_interval("b"), Some(iv(10.0, 20.0)));
+        assert!((result.risk("a").unwrap() - 0.5).abs() < 1e-6);
+        assert!((result.expected_overruns() - 0.5).abs() < 1e-6);
+    }
+}
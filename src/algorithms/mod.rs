@@ -1,13 +1,29 @@
+pub mod colouring;
 pub mod est;
+pub mod reassign;
+pub mod replan;
+pub mod restarts;
+pub mod result;
 pub mod rl;
+pub mod rolling;
+pub mod split;
 
-pub use est::ESTScheduler;
+pub use colouring::{greedy_colouring, InitialAssignment};
+pub use est::{ESTScheduler, TieBreak};
+pub use reassign::{ReassignmentOutcome, ReassignmentPass};
+pub use replan::{replan, try_replan, Replan};
+pub use restarts::{RestartOutcome, RestartsDriver, SeededAlgorithm};
+pub use result::{SchedulerResult, TaskOutcome};
 pub use rl::scheduler::RLScheduler;
+pub use rolling::{RollingHorizonScheduler, RollingOutcome, WindowStats};
+pub use split::{place_split, split_unscheduled};
 
+use crate::clock::Instant;
 use std::collections::HashMap;
 
+use crate::constraints::DynamicConstraint;
 use crate::schedule::Schedule;
-use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::scheduling_block::{SchedulingBlock, SchedulingError, Task};
 use crate::solution_space::{Interval, SolutionSpace};
 use crate::Id;
 
@@ -42,6 +58,74 @@ where
         solution_space: &SolutionSpace<U>,
         horizon: Interval<U>,
     ) -> Schedule<U>;
+
+    /// Like [`schedule`](Self::schedule), but refuses to start when a block's
+    /// dependency graph contains a cycle.
+    ///
+    /// # Errors
+    ///
+    /// [`SchedulingError::Cycle`] naming the first cycle found.
+    fn try_schedule(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> Result<Schedule<U>, SchedulingError> {
+        ensure_acyclic(blocks)?;
+        Ok(self.schedule(blocks, solution_space, horizon))
+    }
+
+    /// Like [`schedule`](Self::schedule), but also reports why each task
+    /// was left out, the iteration count and the time taken.
+    ///
+    /// The default implementation times `schedule` and leaves
+    /// [`iterations`](SchedulerResult::iterations) empty; algorithms that
+    /// count their iterations override it.
+    fn schedule_result(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> SchedulerResult<U>
+    where
+        D: DynamicConstraint<U>,
+    {
+        let started = Instant::now();
+        let schedule = self.schedule(blocks, solution_space, horizon);
+        let elapsed = started.elapsed();
+        SchedulerResult::new(schedule, blocks, solution_space, horizon, None, elapsed)
+    }
+}
+
+/// Forwards to the boxed algorithm, so schedulers picked at run time — for
+/// example from a registry — can be wrapped by generic drivers.
+impl<A, T, U, D, E> SchedulingAlgorithm<T, U, D, E> for Box<A>
+where
+    A: SchedulingAlgorithm<T, U, D, E> + ?Sized,
+    T: Task<U>,
+    U: qtty::Unit,
+    E: petgraph::EdgeType,
+{
+    fn schedule(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> Schedule<U> {
+        (**self).schedule(blocks, solution_space, horizon)
+    }
+
+    fn schedule_result(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> SchedulerResult<U>
+    where
+        D: DynamicConstraint<U>,
+    {
+        (**self).schedule_result(blocks, solution_space, horizon)
+    }
 }
 
 /// Algorithm for scheduling tasks across multiple resources.
@@ -79,6 +163,34 @@ where
         resource_spaces: &HashMap<Id, SolutionSpace<U>>,
         horizon: Interval<U>,
     ) -> HashMap<Id, Schedule<U>>;
+
+    /// Like [`schedule_multi`](Self::schedule_multi), but refuses to start
+    /// when a block's dependency graph contains a cycle.
+    ///
+    /// # Errors
+    ///
+    /// [`SchedulingError::Cycle`] naming the first cycle found.
+    fn try_schedule_multi(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        resource_spaces: &HashMap<Id, SolutionSpace<U>>,
+        horizon: Interval<U>,
+    ) -> Result<HashMap<Id, Schedule<U>>, SchedulingError> {
+        ensure_acyclic(blocks)?;
+        Ok(self.schedule_multi(blocks, resource_spaces, horizon))
+    }
+}
+
+/// Checks every block with [`SchedulingBlock::ensure_acyclic`].
+pub(crate) fn ensure_acyclic<T, U, D, E>(
+    blocks: &[SchedulingBlock<T, U, D, E>],
+) -> Result<(), SchedulingError>
+where
+    T: Task<U>,
+    U: qtty::Unit,
+    E: petgraph::EdgeType,
+{
+    blocks.iter().try_for_each(SchedulingBlock::ensure_acyclic)
 }
 
 /// Adapter that runs a single-resource [`SchedulingAlgorithm`] independently per resource.
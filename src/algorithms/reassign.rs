@@ -0,0 +1,427 @@
+//! Late-binding resource assignment.
+//!
+//! Multi-resource scheduling decides *when* each task runs and, tentatively,
+//! *where*. [`ReassignmentPass`] revisits only the second decision: with every
+//! interval held fixed, it moves entries between resources to balance load
+//! and/or reduce transition costs between consecutive tasks, as long as the
+//! target resource is compatible, available (per its [`ResourceCalendar`]) and
+//! free over the entry's interval.
+//!
+//! The pass is a deterministic local search: entries are visited in start
+//! order, and each is moved to the resource giving the lowest total cost if
+//! that strictly improves on its current placement. When a single entry on the
+//! target overlaps, the two are swapped instead (provided the other one fits
+//! back). Sweeps repeat until no entry moves or the sweep limit is hit.
+//!
+//! # Cost
+//!
+//! ```text
+//! cost = load_weight       · Σ_r busy(r)²
+//!      + transition_weight · Σ_r Σ_consecutive transition_cost(prev, next)
+//! ```
+//!
+//! Minimising the sum of squared busy times spreads work evenly; set
+//! `load_weight` to zero to optimise transitions alone.
+
+use std::collections::HashMap;
+
+use crate::resource::ResourceCalendar;
+use crate::schedule::Schedule;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::Unit;
+
+/// Result of [`ReassignmentPass::run`].
+#[derive(Debug, Clone)]
+pub struct ReassignmentOutcome<U: Unit> {
+    /// Per-resource schedules after reassignment (same intervals as the input).
+    pub schedules: HashMap<Id, Schedule<U>>,
+    /// Number of entries moved to a different resource (a swap counts twice).
+    pub moves: usize,
+    /// Cost of the input assignment.
+    pub initial_cost: f64,
+    /// Cost of the returned assignment (never above `initial_cost`).
+    pub final_cost: f64,
+}
+
+/// A strictly improving move found while scanning targets.
+struct Candidate<U: Unit> {
+    delta: f64,
+    target: Id,
+    source: Schedule<U>,
+    dest: Schedule<U>,
+    swapped: Option<Id>,
+}
+
+/// Re-optimises the resource of each scheduled entry, keeping times fixed.
+#[derive(Debug, Clone)]
+pub struct ReassignmentPass<U: Unit> {
+    calendar: Option<ResourceCalendar<U>>,
+    compatibility: HashMap<Id, Vec<Id>>,
+    load_weight: f64,
+    transition_weight: f64,
+    max_sweeps: usize,
+}
+
+impl<U: Unit> Default for ReassignmentPass<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit> ReassignmentPass<U> {
+    /// Creates a pass with unit weights, no calendar, no compatibility
+    /// restrictions and at most 10 sweeps.
+    pub fn new() -> Self {
+        Self {
+            calendar: None,
+            compatibility: HashMap::new(),
+            load_weight: 1.0,
+            transition_weight: 1.0,
+            max_sweeps: 10,
+        }
+    }
+
+    /// Only moves an entry onto a resource whose calendar fully covers its
+    /// interval. Calendar resources with no schedule are considered too.
+    pub fn with_calendar(mut self, calendar: ResourceCalendar<U>) -> Self {
+        self.calendar = Some(calendar);
+        self
+    }
+
+    /// Restricts `task_id` to the given resources. Tasks without an entry may
+    /// use any resource.
+    pub fn with_compatibility(
+        mut self,
+        task_id: impl Into<Id>,
+        resources: impl IntoIterator<Item = impl Into<Id>>,
+    ) -> Self {
+        self.compatibility.insert(
+            task_id.into(),
+            resources.into_iter().map(Into::into).collect(),
+        );
+        self
+    }
+
+    /// Sets the weight of the load-balance term.
+    pub fn with_load_weight(mut self, weight: f64) -> Self {
+        self.load_weight = weight;
+        self
+    }
+
+    /// Sets the weight of the transition-cost term.
+    pub fn with_transition_weight(mut self, weight: f64) -> Self {
+        self.transition_weight = weight;
+        self
+    }
+
+    /// Sets the maximum number of sweeps over all entries.
+    pub fn with_max_sweeps(mut self, max_sweeps: usize) -> Self {
+        self.max_sweeps = max_sweeps;
+        self
+    }
+
+    /// Returns `true` if `task_id` may run on `resource_id`.
+    pub fn is_compatible(&self, task_id: &str, resource_id: &str) -> bool {
+        self.compatibility
+            .get(task_id)
+            .is_none_or(|allowed| allowed.iter().any(|r| r == resource_id))
+    }
+
+    fn is_available(&self, resource_id: &str, interval: Interval<U>) -> bool {
+        let Some(calendar) = &self.calendar else {
+            return true;
+        };
+        calendar.availability(resource_id).is_some_and(|windows| {
+            windows.query_overlapping(interval).iter().any(|w| {
+                w.start().value() <= interval.start().value()
+                    && interval.end().value() <= w.end().value()
+            })
+        })
+    }
+
+    /// Runs the pass on `schedules` (resource ID → schedule).
+    ///
+    /// `transition_cost(prev, next)` is charged for every pair of consecutive
+    /// entries on the same resource; pass `|_, _| 0.0` to balance load only.
+    ///
+    /// A task ID found in more than one schedule is left where it is.
+    pub fn run<F>(
+        &self,
+        schedules: &HashMap<Id, Schedule<U>>,
+        transition_cost: F,
+    ) -> ReassignmentOutcome<U>
+    where
+        F: Fn(&str, &str) -> f64,
+    {
+        let mut schedules = schedules.clone();
+        if let Some(calendar) = &self.calendar {
+            for resource_id in calendar.resource_ids() {
+                schedules.entry(resource_id.to_owned()).or_default();
+            }
+        }
+
+        let mut resources: Vec<Id> = schedules.keys().cloned().collect();
+        resources.sort();
+
+        let resource_cost = |schedule: &Schedule<U>| -> f64 {
+            let busy = schedule.total_duration().value();
+            let ids: Vec<Id> = schedule.ids().collect();
+            let transitions: f64 = ids.windows(2).map(|w| transition_cost(&w[0], &w[1])).sum();
+            self.load_weight * busy * busy + self.transition_weight * transitions
+        };
+
+        let mut costs: HashMap<Id, f64> = schedules
+            .iter()
+            .map(|(r, s)| (r.clone(), resource_cost(s)))
+            .collect();
+        let initial_cost: f64 = costs.values().sum();
+
+        let mut entries: Vec<(Id, Interval<U>)> = Vec::new();
+        let mut assigned: HashMap<Id, Id> = HashMap::new();
+        let mut duplicated: Vec<Id> = Vec::new();
+        for (resource_id, schedule) in &schedules {
+            for (id, interval) in schedule.iter() {
+                if assigned.insert(id.clone(), resource_id.clone()).is_some() {
+                    duplicated.push(id);
+                } else {
+                    entries.push((id, interval));
+                }
+            }
+        }
+        entries.retain(|(id, _)| !duplicated.contains(id));
+        entries.sort_by(|a, b| {
+            a.1.start()
+                .value()
+                .total_cmp(&b.1.start().value())
+                .then_with(|| a.0.cmp(&b.0))
+        });
+
+        let mut moves = 0;
+        for _ in 0..self.max_sweeps {
+            let mut moved = false;
+            for (id, interval) in &entries {
+                let current = assigned[id].clone();
+                let mut source = schedules[&current].clone();
+                source.remove(id);
+
+                let mut best: Option<Candidate<U>> = None;
+                for target in resources.iter().filter(|r| **r != current) {
+                    if !self.is_compatible(id, target) || !self.is_available(target, *interval) {
+                        continue;
+                    }
+                    let mut dest = schedules[target].clone();
+                    let mut src = source.clone();
+                    let blockers = dest.conflicts_vec(*interval).unwrap_or_default();
+                    let swapped = match blockers.as_slice() {
+                        [] => None,
+                        // Swap with the single entry in the way, if it fits back.
+                        [(other, other_iv)] => {
+                            if duplicated.contains(other)
+                                || !self.is_compatible(other, &current)
+                                || !self.is_available(&current, *other_iv)
+                            {
+                                continue;
+                            }
+                            dest.remove(other);
+                            if src.add(other.clone(), *other_iv).is_err() {
+                                continue;
+                            }
+                            Some(other.clone())
+                        }
+                        _ => continue,
+                    };
+                    if dest.add(id.clone(), *interval).is_err() {
+                        continue;
+                    }
+                    let delta = resource_cost(&src) + resource_cost(&dest)
+                        - costs[&current]
+                        - costs[target];
+                    if delta < -1e-9 && best.as_ref().is_none_or(|b| delta < b.delta) {
+                        best = Some(Candidate {
+                            delta,
+                            target: target.clone(),
+                            source: src,
+                            dest,
+                            swapped,
+                        });
+                    }
+                }
+
+                if let Some(Candidate {
+                    target,
+                    source: src,
+                    dest,
+                    swapped,
+                    ..
+                }) = best
+                {
+                    costs.insert(current.clone(), resource_cost(&src));
+                    costs.insert(target.clone(), resource_cost(&dest));
+                    schedules.insert(current.clone(), src);
+                    schedules.insert(target.clone(), dest);
+                    assigned.insert(id.clone(), target.clone());
+                    moves += 1;
+                    if let Some(other) = swapped {
+                        assigned.insert(other, current);
+                        moves += 1;
+                    }
+                    moved = true;
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+
+        ReassignmentOutcome {
+            schedules,
+            moves,
+            initial_cost,
+            final_cost: costs.values().sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution_space::IntervalSet;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn schedule(entries: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in entries {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    fn all_on_a() -> HashMap<Id, Schedule<Second>> {
+        HashMap::from([
+            (
+                "a".to_string(),
+                schedule(&[("t1", 0.0, 10.0), ("t2", 10.0, 20.0), ("t3", 20.0, 30.0)]),
+            ),
+            ("b".to_string(), Schedule::new()),
+        ])
+    }
+
+    fn assert_times_preserved(
+        before: &HashMap<Id, Schedule<Second>>,
+        after: &HashMap<Id, Schedule<Second>>,
+    ) {
+        let collect = |m: &HashMap<Id, Schedule<Second>>| {
+            let mut v: Vec<_> = m
+                .values()
+                .flat_map(|s| s.iter())
+                .map(|(id, iv)| (id, iv.start().value(), iv.end().value()))
+                .collect();
+            v.sort_by(|a, b| a.0.cmp(&b.0));
+            v
+        };
+        assert_eq!(collect(before), collect(after));
+    }
+
+    #[test]
+    fn balances_load_across_resources() {
+        let input = all_on_a();
+        let out = ReassignmentPass::new().run(&input, |_, _| 0.0);
+
+        assert_times_preserved(&input, &out.schedules);
+        let mut sizes = [out.schedules["a"].len(), out.schedules["b"].len()];
+        sizes.sort();
+        assert_eq!(sizes, [1, 2]);
+        assert!(out.final_cost < out.initial_cost);
+        assert!(out.moves >= 1);
+    }
+
+    #[test]
+    fn respects_compatibility() {
+        let input = all_on_a();
+        let pass = ReassignmentPass::new()
+            .with_compatibility("t1", ["a"])
+            .with_compatibility("t2", ["a"])
+            .with_compatibility("t3", ["a"]);
+        let out = pass.run(&input, |_, _| 0.0);
+        assert_eq!(out.moves, 0);
+        assert_eq!(out.schedules["a"].len(), 3);
+    }
+
+    #[test]
+    fn respects_calendar() {
+        let input = all_on_a();
+        let mut calendar = ResourceCalendar::new(iv(0.0, 100.0));
+        calendar.set_availability("a", IntervalSet::from(iv(0.0, 100.0)));
+        // `b` is only open while t3 runs; `c` only exists in the calendar.
+        calendar.set_availability("b", IntervalSet::from(iv(15.0, 30.0)));
+        calendar.set_availability("c", IntervalSet::from(iv(0.0, 12.0)));
+
+        let out = ReassignmentPass::new()
+            .with_calendar(calendar)
+            .run(&input, |_, _| 0.0);
+
+        assert!(out.schedules["b"].contains_task("t3"));
+        assert!(out.schedules["c"].contains_task("t1"));
+        assert!(out.schedules["a"].contains_task("t2"));
+    }
+
+    #[test]
+    fn minimises_transitions() {
+        // Alternating setups on each resource; grouping by setup removes all
+        // transitions.
+        let setup = |id: &str| id.as_bytes()[0];
+        let input = HashMap::from([
+            (
+                "a".to_string(),
+                schedule(&[("x1", 0.0, 10.0), ("y2", 10.0, 20.0)]),
+            ),
+            (
+                "b".to_string(),
+                schedule(&[("y1", 0.0, 10.0), ("x2", 10.0, 20.0)]),
+            ),
+        ]);
+        let cost = |p: &str, n: &str| if setup(p) == setup(n) { 0.0 } else { 1.0 };
+
+        let out = ReassignmentPass::new()
+            .with_load_weight(0.0)
+            .run(&input, cost);
+
+        assert_eq!(out.initial_cost, 2.0);
+        assert_eq!(out.final_cost, 0.0);
+        for s in out.schedules.values() {
+            let ids: Vec<_> = s.ids().collect();
+            assert!(ids.windows(2).all(|w| setup(&w[0]) == setup(&w[1])));
+        }
+    }
+
+    #[test]
+    fn duplicated_tasks_stay_put() {
+        let input = HashMap::from([
+            (
+                "a".to_string(),
+                schedule(&[("t1", 0.0, 10.0), ("t2", 10.0, 20.0), ("t3", 20.0, 30.0)]),
+            ),
+            ("b".to_string(), schedule(&[("t1", 0.0, 10.0)])),
+        ]);
+        let out = ReassignmentPass::new().run(&input, |_, _| 0.0);
+        assert!(out.schedules["a"].contains_task("t1"));
+        assert!(out.schedules["b"].contains_task("t1"));
+        assert!(out.schedules["b"].contains_task("t2"));
+    }
+
+    #[test]
+    fn never_overlaps_on_target() {
+        let input = HashMap::from([
+            (
+                "a".to_string(),
+                schedule(&[("t1", 0.0, 10.0), ("t2", 10.0, 20.0)]),
+            ),
+            ("b".to_string(), schedule(&[("t3", 0.0, 20.0)])),
+        ]);
+        let out = ReassignmentPass::new().run(&input, |_, _| 0.0);
+        assert_eq!(out.moves, 0);
+        assert_times_preserved(&input, &out.schedules);
+    }
+}
@@ -0,0 +1,340 @@
+//! Freeze-and-replan.
+//!
+//! Operational systems schedule once and then keep rescheduling as time
+//! passes: weather closes a window, a new target arrives, a task overruns.
+//! [`replan`] takes the current schedule and the present instant `now` and
+//!
+//! 1. **freezes** every entry that has started before `now` — finished
+//!    tasks and the one running at `now` cannot be moved;
+//! 2. **drops** every entry starting at or after `now`;
+//! 3. reruns the algorithm over `[now, horizon.end)` for every task that is
+//!    not frozen, with windows restricted to time the frozen entries leave
+//!    free and to what the dynamic edges from frozen tasks allow — a task
+//!    `Consecutive` to a frozen one still starts after it ends.
+//!
+//! Edges whose reference task is not frozen are left to the algorithm, as
+//! in a first run.
+
+use crate::constraints::{DynamicConstraint, DynamicConstraintIndex, SchedulingContext};
+use crate::schedule::Schedule;
+use crate::scheduling_block::{SchedulingBlock, SchedulingError, Task};
+use crate::solution_space::{Interval, IntervalSet, SolutionSpace};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+use super::{ensure_acyclic, SchedulingAlgorithm};
+
+/// Result of [`replan`].
+#[derive(Debug, Clone)]
+pub struct Replan<U: Unit> {
+    /// Frozen entries plus the new plan.
+    pub schedule: Schedule<U>,
+    /// Entries kept from the previous schedule, in start order.
+    pub frozen: Vec<Id>,
+    /// Tasks of the blocks left out of the new schedule, in block order.
+    pub unplaced: Vec<Id>,
+}
+
+/// Freezes the part of `schedule` that has started by `now` and reschedules
+/// everything else over the rest of `horizon` with `algorithm`.
+///
+/// # Example
+///
+/// ```
+/// use virolai::algorithms::{replan, ESTScheduler};
+/// use virolai::constraints::{DynConstraintKind, IntervalConstraint};
+/// use virolai::schedule::Schedule;
+/// use virolai::scheduling_block::{SchedulingBlock, Task};
+/// use virolai::solution_space::{Interval, SolutionSpace};
+/// use qtty::{Quantity, Second};
+///
+/// #[derive(Debug, Clone)]
+/// struct Job(&'static str);
+///
+/// impl Task<Second> for Job {
+///     type SizeUnit = Second;
+///     type ConstraintLeaf = IntervalConstraint<Second>;
+///     fn name(&self) -> &str { self.0 }
+///     fn size(&self) -> Quantity<Second> { Quantity::new(10.0) }
+/// }
+///
+/// let mut block = SchedulingBlock::<Job, Second, DynConstraintKind>::new();
+/// for id in ["a", "b"] {
+///     block.add_task_with_id(Job(id), Some(id.into())).unwrap();
+/// }
+/// let mut ss = SolutionSpace::new();
+/// ss.add_interval("a", Interval::from_f64(0.0, 100.0));
+/// ss.add_interval("b", Interval::from_f64(0.0, 100.0));
+///
+/// let mut current = Schedule::new();
+/// current.add("a", Interval::from_f64(0.0, 10.0)).unwrap();
+/// current.add("b", Interval::from_f64(10.0, 20.0)).unwrap();
+///
+/// // At t = 5, "a" is running and stays; "b" is planned again from t = 10.
+/// let result = replan(
+///     &ESTScheduler::default(),
+///     &current,
+///     Quantity::new(5.0),
+///     &[block],
+///     &ss,
+///     Interval::from_f64(0.0, 100.0),
+/// );
+/// assert_eq!(result.frozen, vec!["a".to_string()]);
+/// assert_eq!(result.schedule.get_interval("b"), Some(Interval::from_f64(10.0, 20.0)));
+/// ```
+pub fn replan<A, T, U, D, E>(
+    algorithm: &A,
+    schedule: &Schedule<U>,
+    now: Quantity<U>,
+    blocks: &[SchedulingBlock<T, U, D, E>],
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+) -> Replan<U>
+where
+    A: SchedulingAlgorithm<T, U, D, E> + ?Sized,
+    T: Task<U>,
+    U: Unit,
+    D: DynamicConstraint<U>,
+    E: petgraph::EdgeType,
+{
+    let mut frozen = Schedule::new();
+    let mut frozen_ids = Vec::new();
+    for (id, interval) in schedule.iter() {
+        if interval.start().value() < now.value() {
+            frozen
+                .add(id.clone(), interval)
+                .expect("entries of a schedule do not conflict");
+            frozen_ids.push(id);
+        }
+    }
+
+    let start = if now.value() > horizon.start().value() {
+        now
+    } else {
+        horizon.start()
+    };
+    let mut result = frozen.clone();
+    if start.value() < horizon.end().value() {
+        let remaining = Interval::new(start, horizon.end());
+        let space = replan_space(&frozen, blocks, solution_space, remaining);
+        let plan = algorithm.schedule(blocks, &space, remaining);
+        for (id, interval) in plan.iter() {
+            if !frozen.contains_task(id.as_str()) {
+                let _ = result.add(id, interval);
+            }
+        }
+    }
+
+    let unplaced = blocks
+        .iter()
+        .flat_map(|block| block.tasks().map(|(id, _)| id))
+        .filter(|id| !result.contains_task(*id))
+        .map(str::to_owned)
+        .collect();
+    Replan {
+        schedule: result,
+        frozen: frozen_ids,
+        unplaced,
+    }
+}
+
+/// Like [`replan`], but refuses to start when a block's dependency graph
+/// contains a cycle.
+///
+/// # Errors
+///
+/// [`SchedulingError::Cycle`] naming the first cycle found.
+pub fn try_replan<A, T, U, D, E>(
+    algorithm: &A,
+    schedule: &Schedule<U>,
+    now: Quantity<U>,
+    blocks: &[SchedulingBlock<T, U, D, E>],
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+) -> Result<Replan<U>, SchedulingError>
+where
+    A: SchedulingAlgorithm<T, U, D, E> + ?Sized,
+    T: Task<U>,
+    U: Unit,
+    D: DynamicConstraint<U>,
+    E: petgraph::EdgeType,
+{
+    ensure_acyclic(blocks)?;
+    Ok(replan(
+        algorithm,
+        schedule,
+        now,
+        blocks,
+        solution_space,
+        horizon,
+    ))
+}
+
+/// Windows of every task that is not frozen: its static windows within
+/// `remaining`, minus frozen time, intersected with the dynamic edges whose
+/// reference task is frozen.
+pub(crate) fn replan_space<T, U, D, E>(
+    frozen: &Schedule<U>,
+    blocks: &[SchedulingBlock<T, U, D, E>],
+    solution_space: &SolutionSpace<U>,
+    remaining: Interval<U>,
+) -> SolutionSpace<U>
+where
+    T: Task<U>,
+    U: Unit,
+    D: DynamicConstraint<U>,
+    E: petgraph::EdgeType,
+{
+    let free = IntervalSet::from(frozen.intervals().collect::<Vec<_>>()).complement(remaining);
+    let index = DynamicConstraintIndex::from_blocks(blocks);
+    let mut space = SolutionSpace::new();
+
+    for (id, task) in blocks.iter().flat_map(|block| block.tasks()) {
+        if frozen.contains_task(id) {
+            continue;
+        }
+        let mut windows = solution_space
+            .get_intervals(id)
+            .map(|set| set.intersection(&free))
+            .unwrap_or_default();
+        let ctx = SchedulingContext::new(frozen, solution_space)
+            .with_target_size(task.size_on_axis())
+            .with_target_id(id);
+        for (source, constraint) in index.get_edges(id).unwrap_or_default() {
+            if frozen.contains_task(source.as_str()) {
+                let allowed = constraint.compute_intervals(remaining, source, &ctx);
+                windows = windows.intersection(&allowed);
+            }
+        }
+        space.set_intervals(id, windows.into_inner());
+    }
+    space
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::ESTScheduler;
+    use crate::constraints::DynConstraintKind;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    fn block(ids: &[&str]) -> SchedulingBlock<TestTask, Second, DynConstraintKind> {
+        let mut block = SchedulingBlock::new();
+        for id in ids {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some((*id).into()))
+                .unwrap();
+        }
+        block
+    }
+
+    fn space(ids: &[&str]) -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for id in ids {
+            ss.add_interval(*id, iv(0.0, 100.0));
+        }
+        ss
+    }
+
+    fn schedule(entries: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in entries {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    #[test]
+    fn freezes_started_entries_and_replans_the_rest() {
+        let blocks = [block(&["a", "b", "c"])];
+        let current = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 20.0), ("c", 60.0, 70.0)]);
+        let result = replan(
+            &ESTScheduler::default(),
+            &current,
+            q(15.0),
+            &blocks,
+            &space(&["a", "b", "c"]),
+            iv(0.0, 100.0),
+        );
+        assert_eq!(result.frozen, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(result.schedule.get_interval("b"), Some(iv(10.0, 20.0)));
+        // "c" moves up to the first free time after "now" and the running "b".
+        assert_eq!(result.schedule.get_interval("c"), Some(iv(20.0, 30.0)));
+        assert!(result.unplaced.is_empty());
+    }
+
+    #[test]
+    fn dynamic_edges_from_frozen_tasks_still_apply() {
+        let mut b = block(&["a", "b", "c"]);
+        let (na, nc) = (b.node_of("a").unwrap(), b.node_of("c").unwrap());
+        b.add_dependency(na, nc, DynConstraintKind::start_to_start(50.0))
+            .unwrap();
+        let current = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 20.0)]);
+        let result = replan(
+            &ESTScheduler::default(),
+            &current,
+            q(5.0),
+            &[b],
+            &space(&["a", "b", "c"]),
+            iv(0.0, 100.0),
+        );
+        assert_eq!(result.frozen, vec!["a".to_string()]);
+        assert_eq!(result.schedule.get_interval("b"), Some(iv(10.0, 20.0)));
+        // StartToStart(lag = 50) from the frozen "a" holds "c" until t = 50.
+        assert_eq!(result.schedule.get_interval("c"), Some(iv(50.0, 60.0)));
+    }
+
+    #[test]
+    fn nothing_is_planned_past_the_horizon() {
+        let blocks = [block(&["a", "b"])];
+        let current = schedule(&[("a", 0.0, 10.0), ("b", 90.0, 100.0)]);
+        let result = replan(
+            &ESTScheduler::default(),
+            &current,
+            q(200.0),
+            &blocks,
+            &space(&["a", "b"]),
+            iv(0.0, 100.0),
+        );
+        assert_eq!(result.schedule.len(), 2);
+        assert!(result.unplaced.is_empty());
+
+        let result = replan(
+            &ESTScheduler::default(),
+            &current,
+            q(95.0),
+            &[block(&["a", "b", "late"])],
+            &space(&["a", "b", "late"]),
+            iv(0.0, 100.0),
+        );
+        assert_eq!(result.unplaced, vec!["late".to_string()]);
+    }
+
+    #[test]
+    fn try_replan_refuses_cyclic_blocks() {
+        let mut b = block(&["a", "b"]);
+        b.add_edge_unchecked("a", "b", DynConstraintKind::Dependence);
+        b.add_edge_unchecked("b", "a", DynConstraintKind::Dependence);
+        let result = try_replan(
+            &ESTScheduler::default(),
+            &schedule(&[("a", 0.0, 10.0)]),
+            q(5.0),
+            &[b],
+            &space(&["a", "b"]),
+            iv(0.0, 100.0),
+        );
+        assert!(matches!(result, Err(SchedulingError::Cycle(_))));
+
+        let result = try_replan(
+            &ESTScheduler::default(),
+            &schedule(&[("a", 0.0, 10.0)]),
+            q(5.0),
+            &[block(&["a", "b"])],
+            &space(&["a", "b"]),
+            iv(0.0, 100.0),
+        )
+        .unwrap();
+        assert_eq!(result.schedule.get_interval("b"), Some(iv(10.0, 20.0)));
+    }
+}
@@ -0,0 +1,273 @@
+//! Random-restart meta-driver for stochastic schedulers.
+//!
+//! A stochastic scheduler produces a different schedule for each seed. Running
+//! it many times with cheap, independent seeds and keeping the best result is
+//! often better than one long run. [`RestartsDriver`] automates that loop:
+//!
+//! 1. Derive the next seed from the base seed and the restart index
+//! 2. Run the wrapped [`SeededAlgorithm`] with that seed
+//! 3. Score the schedule with the caller's objective (higher is better)
+//! 4. Keep it if it beats the incumbent
+//! 5. Stop once the quality target is reached or the budget (restart count
+//!    and/or wall-clock time) is spent
+//!
+//! The outcome reports the seed that produced the best schedule, so the run
+//! can be reproduced exactly with [`SeededAlgorithm::schedule_seeded`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use virolai::algorithms::restarts::RestartsDriver;
+//!
+//! let driver = RestartsDriver::new(my_stochastic_scheduler)
+//!     .with_base_seed(7)
+//!     .with_max_restarts(50)
+//!     .with_target(0.95 * horizon.duration().value());
+//!
+//! let outcome = driver.run(&blocks, &solution_space, horizon, |s| s.total_duration().value());
+//! println!("best seed = {}", outcome.best_seed);
+//! ```
+
+use std::time::Duration;
+
+use crate::clock::Instant;
+use crate::rng::derive_seed;
+use crate::schedule::Schedule;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{Interval, SolutionSpace};
+
+/// A scheduling algorithm whose output is fully determined by a seed.
+///
+/// Implementations must return the same schedule for the same inputs and
+/// seed; randomness is only allowed to flow from `seed`.
+pub trait SeededAlgorithm<T, U, D, E>
+where
+    T: Task<U>,
+    U: qtty::Unit,
+    E: petgraph::EdgeType,
+{
+    /// Schedules tasks using `seed` to drive any randomized decisions.
+    fn schedule_seeded(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        seed: u64,
+    ) -> Schedule<U>;
+}
+
+/// Re-runs a [`SeededAlgorithm`] with fresh seeds and keeps the best schedule.
+#[derive(Debug, Clone)]
+pub struct RestartsDriver<A> {
+    inner: A,
+    base_seed: u64,
+    max_restarts: usize,
+    time_budget: Option<Duration>,
+    target: Option<f64>,
+}
+
+/// Result of a [`RestartsDriver::run`].
+#[derive(Debug, Clone)]
+pub struct RestartOutcome<U: qtty::Unit> {
+    /// Highest-scoring schedule found.
+    pub best: Schedule<U>,
+    /// Seed that produced [`best`](Self::best).
+    pub best_seed: u64,
+    /// Score of [`best`](Self::best).
+    pub best_score: f64,
+    /// Number of runs performed (≥ 1).
+    pub runs: usize,
+    /// `true` if the run stopped because the quality target was reached.
+    pub target_reached: bool,
+}
+
+impl<A> RestartsDriver<A> {
+    /// Wraps `algorithm` with a default budget of 10 restarts and base seed 0.
+    pub fn new(algorithm: A) -> Self {
+        Self {
+            inner: algorithm,
+            base_seed: 0,
+            max_restarts: 10,
+            time_budget: None,
+            target: None,
+        }
+    }
+
+    /// Sets the base seed from which per-restart seeds are derived.
+    pub fn with_base_seed(mut self, seed: u64) -> Self {
+        self.base_seed = seed;
+        self
+    }
+
+    /// Sets the maximum number of runs. Values below 1 are clamped to 1.
+    pub fn with_max_restarts(mut self, max_restarts: usize) -> Self {
+        self.max_restarts = max_restarts.max(1);
+        self
+    }
+
+    /// Stops starting new runs once `budget` of wall-clock time has elapsed.
+    ///
+    /// At least one run is always performed.
+    pub fn with_time_budget(mut self, budget: Duration) -> Self {
+        self.time_budget = Some(budget);
+        self
+    }
+
+    /// Stops as soon as a schedule scores at least `target`.
+    pub fn with_target(mut self, target: f64) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Returns the seed used for restart `index`.
+    pub fn seed_for(&self, index: usize) -> u64 {
+        derive_seed(self.base_seed, index as u64)
+    }
+
+    /// Returns a reference to the wrapped algorithm.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Runs the restart loop and returns the best schedule found.
+    ///
+    /// `score` maps a schedule to a quality value; higher is better. Ties keep
+    /// the earlier run, so results are reproducible for a given base seed
+    /// when no time budget is set. NaN scores never become the incumbent
+    /// unless every run scores NaN.
+    pub fn run<T, U, D, E, F>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        score: F,
+    ) -> RestartOutcome<U>
+    where
+        A: SeededAlgorithm<T, U, D, E>,
+        T: Task<U>,
+        U: qtty::Unit,
+        E: petgraph::EdgeType,
+        F: Fn(&Schedule<U>) -> f64,
+    {
+        let started = Instant::now();
+        let mut best: Option<(Schedule<U>, u64, f64)> = None;
+        let mut runs = 0;
+        let mut target_reached = false;
+
+        for index in 0..self.max_restarts {
+            if runs > 0 && self.time_budget.is_some_and(|b| started.elapsed() >= b) {
+                break;
+            }
+
+            let seed = self.seed_for(index);
+            let schedule = self
+                .inner
+                .schedule_seeded(blocks, solution_space, horizon, seed);
+            let value = score(&schedule);
+            runs += 1;
+
+            let improves = match &best {
+                None => true,
+                Some((_, _, incumbent)) => value > *incumbent || incumbent.is_nan(),
+            };
+            if improves {
+                best = Some((schedule, seed, value));
+            }
+
+            if self.target.is_some_and(|t| value >= t) {
+                target_reached = true;
+                break;
+            }
+        }
+
+        let (best, best_seed, best_score) = best.expect("at least one run is always performed");
+        RestartOutcome {
+            best,
+            best_seed,
+            best_score,
+            runs,
+            target_reached,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    /// Places a single task at a seed-dependent offset; score = start time.
+    #[derive(Debug)]
+    struct OffsetByMod(u64);
+
+    impl SeededAlgorithm<TestTask, Second, (), petgraph::Directed> for OffsetByMod {
+        fn schedule_seeded(
+            &self,
+            _blocks: &[SchedulingBlock<TestTask, Second>],
+            _solution_space: &SolutionSpace<Second>,
+            _horizon: Interval<Second>,
+            seed: u64,
+        ) -> Schedule<Second> {
+            let start = (seed % self.0) as f64;
+            let mut s = Schedule::new();
+            s.add("t", iv(start, start + 1.0)).unwrap();
+            s
+        }
+    }
+
+    fn start_of(s: &Schedule<Second>) -> f64 {
+        s.earliest_start().unwrap().value()
+    }
+
+    fn run_with(driver: &RestartsDriver<OffsetByMod>) -> RestartOutcome<Second> {
+        let blocks: Vec<SchedulingBlock<TestTask, Second>> = vec![];
+        driver.run(&blocks, &SolutionSpace::new(), iv(0.0, 100.0), start_of)
+    }
+
+    #[test]
+    fn keeps_best_and_reports_seed() {
+        let driver = RestartsDriver::new(OffsetByMod(1000))
+            .with_base_seed(3)
+            .with_max_restarts(20);
+        let outcome = run_with(&driver);
+
+        assert_eq!(outcome.runs, 20);
+        assert!(!outcome.target_reached);
+        let best_expected = (0..20)
+            .map(|i| (driver.seed_for(i) % 1000) as f64)
+            .fold(f64::MIN, f64::max);
+        assert_eq!(outcome.best_score, best_expected);
+        assert_eq!((outcome.best_seed % 1000) as f64, best_expected);
+        assert_eq!(start_of(&outcome.best), best_expected);
+    }
+
+    #[test]
+    fn stops_at_target() {
+        // Every run scores ≥ 0, so the first run meets the target.
+        let driver = RestartsDriver::new(OffsetByMod(1000))
+            .with_max_restarts(50)
+            .with_target(0.0);
+        let outcome = run_with(&driver);
+        assert_eq!(outcome.runs, 1);
+        assert!(outcome.target_reached);
+        assert_eq!(outcome.best_seed, driver.seed_for(0));
+    }
+
+    #[test]
+    fn reproducible_for_same_base_seed() {
+        let a = run_with(&RestartsDriver::new(OffsetByMod(97)).with_base_seed(11));
+        let b = run_with(&RestartsDriver::new(OffsetByMod(97)).with_base_seed(11));
+        assert_eq!(a.best_seed, b.best_seed);
+        assert_eq!(a.best_score, b.best_score);
+    }
+
+    #[test]
+    fn zero_time_budget_still_runs_once() {
+        let driver = RestartsDriver::new(OffsetByMod(10))
+            .with_max_restarts(0)
+            .with_time_budget(Duration::ZERO);
+        let outcome = run_with(&driver);
+        assert_eq!(outcome.runs, 1);
+    }
+}
@@ -0,0 +1,212 @@
+//! Structured scheduler output.
+//!
+//! A bare [`Schedule`] says where the placed tasks went but not why the
+//! others are missing. [`SchedulingAlgorithm::schedule_result`](super::SchedulingAlgorithm::schedule_result)
+//! wraps it in a [`SchedulerResult`] that adds, for every task of the blocks,
+//! a [`TaskOutcome`], plus the iteration count and the wall-clock time of the
+//! run.
+//!
+//! Outcomes of unplaced tasks are classified against the final schedule,
+//! most specific first:
+//!
+//! 1. [`NoStaticWindow`](TaskOutcome::NoStaticWindow) — no solution-space
+//!    window inside the horizon is long enough for the task;
+//! 2. [`KilledByDynamicEdge`](TaskOutcome::KilledByDynamicEdge) — an incoming
+//!    dynamic edge, evaluated against the final schedule, leaves no such
+//!    window;
+//! 3. [`HorizonExhausted`](TaskOutcome::HorizonExhausted) — windows remain,
+//!    but the run ended without placing the task.
+
+use std::time::Duration;
+
+use crate::constraints::{DynamicConstraint, DynamicConstraintIndex, SchedulingContext};
+use crate::schedule::Schedule;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{Interval, IntervalSet, SolutionSpace};
+use crate::Id;
+use qtty::Unit;
+
+/// What happened to one task in a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskOutcome {
+    /// The task is in the schedule.
+    Placed,
+    /// No static window inside the horizon fits the task.
+    NoStaticWindow,
+    /// The incoming dynamic edge from this source task leaves no window
+    /// that fits the task.
+    KilledByDynamicEdge(Id),
+    /// Windows that fit the task remain, but it was not placed.
+    HorizonExhausted,
+}
+
+/// Result of [`SchedulingAlgorithm::schedule_result`](super::SchedulingAlgorithm::schedule_result).
+#[derive(Debug, Clone)]
+pub struct SchedulerResult<U: Unit> {
+    pub schedule: Schedule<U>,
+    /// Outcome of every task of the blocks, in block order.
+    pub outcomes: Vec<(Id, TaskOutcome)>,
+    /// Iterations of the algorithm's main loop, if it counts them.
+    pub iterations: Option<usize>,
+    /// Wall-clock time of the run.
+    pub elapsed: Duration,
+}
+
+impl<U: Unit> SchedulerResult<U> {
+    /// Classifies every task of `blocks` against `schedule`.
+    pub fn new<T, D, E>(
+        schedule: Schedule<U>,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        iterations: Option<usize>,
+        elapsed: Duration,
+    ) -> Self
+    where
+        T: Task<U>,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let index = DynamicConstraintIndex::from_blocks(blocks);
+        let outcomes = blocks
+            .iter()
+            .flat_map(|block| block.tasks())
+            .map(|(id, task)| {
+                let outcome = classify(id, task, &schedule, solution_space, horizon, &index);
+                (id.to_owned(), outcome)
+            })
+            .collect();
+        Self {
+            schedule,
+            outcomes,
+            iterations,
+            elapsed,
+        }
+    }
+
+    /// Outcome of `task_id`, if it belongs to the blocks.
+    pub fn outcome(&self, task_id: &str) -> Option<&TaskOutcome> {
+        self.outcomes
+            .iter()
+            .find(|(id, _)| id == task_id)
+            .map(|(_, outcome)| outcome)
+    }
+
+    /// Tasks left out of the schedule, with their outcome, in block order.
+    pub fn unscheduled(&self) -> impl Iterator<Item = (&str, &TaskOutcome)> + '_ {
+        self.outcomes
+            .iter()
+            .filter(|(_, outcome)| *outcome != TaskOutcome::Placed)
+            .map(|(id, outcome)| (id.as_str(), outcome))
+    }
+
+    /// `true` if every task was placed.
+    pub fn is_complete(&self) -> bool {
+        self.unscheduled().next().is_none()
+    }
+}
+
+fn classify<T, U, D>(
+    task_id: &str,
+    task: &T,
+    schedule: &Schedule<U>,
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+    index: &DynamicConstraintIndex<'_, D, U>,
+) -> TaskOutcome
+where
+    T: Task<U>,
+    U: Unit,
+    D: DynamicConstraint<U>,
+{
+    if schedule.contains_task(task_id) {
+        return TaskOutcome::Placed;
+    }
+    let size = task.size_on_axis();
+    let fits = |set: &IntervalSet<U>| set.iter().any(|i| i.duration().value() >= size.value());
+
+    let windows = solution_space
+        .get_intervals(task_id)
+        .map(|set| set.intersection(&IntervalSet::from(horizon)))
+        .unwrap_or_default();
+    if !fits(&windows) {
+        return TaskOutcome::NoStaticWindow;
+    }
+
+    let ctx = SchedulingContext::new(schedule, solution_space).with_target_size(size);
+    let ctx = ctx.for_target(task_id);
+    for (source, edge) in index.get_edges(task_id).unwrap_or_default() {
+        let allowed = edge.compute_intervals(horizon, source, &ctx);
+        if !fits(&windows.intersection(&allowed)) {
+            return TaskOutcome::KilledByDynamicEdge(source.clone());
+        }
+    }
+    TaskOutcome::HorizonExhausted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{ESTScheduler, SchedulingAlgorithm};
+    use crate::constraints::DynConstraintKind;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    /// "a" fills [0, 10); "b" only fits there too; "c" has no window; "d"
+    /// must not run while "a" is placed.
+    fn fixture() -> (
+        Vec<SchedulingBlock<TestTask, Second, DynConstraintKind>>,
+        SolutionSpace<Second>,
+    ) {
+        let mut block = SchedulingBlock::new();
+        for id in ["a", "b", "c", "d"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let a = block.node_of("a").unwrap();
+        let d = block.node_of("d").unwrap();
+        block
+            .add_dependency(a, d, DynConstraintKind::Exclusive)
+            .unwrap();
+
+        let mut ss = SolutionSpace::new();
+        ss.add_interval("a", iv(0.0, 10.0));
+        ss.add_interval("b", iv(0.0, 10.0));
+        ss.add_interval("c", iv(50.0, 55.0));
+        ss.add_interval("d", iv(0.0, 100.0));
+        (vec![block], ss)
+    }
+
+    #[test]
+    fn outcomes_classify_unplaced_tasks() {
+        let (blocks, ss) = fixture();
+        let mut schedule = Schedule::new();
+        schedule.add("a", iv(0.0, 10.0)).unwrap();
+        let result =
+            SchedulerResult::new(schedule, &blocks, &ss, iv(0.0, 100.0), None, Duration::ZERO);
+
+        assert_eq!(result.outcome("a"), Some(&TaskOutcome::Placed));
+        assert_eq!(result.outcome("b"), Some(&TaskOutcome::HorizonExhausted));
+        assert_eq!(result.outcome("c"), Some(&TaskOutcome::NoStaticWindow));
+        assert_eq!(
+            result.outcome("d"),
+            Some(&TaskOutcome::KilledByDynamicEdge("a".into()))
+        );
+        assert_eq!(result.unscheduled().count(), 3);
+        assert!(!result.is_complete());
+    }
+
+    #[test]
+    fn est_reports_its_iterations() {
+        let (blocks, ss) = fixture();
+        let est = ESTScheduler::default();
+        let result = est.schedule_result(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(
+            result.schedule.len(),
+            est.schedule(&blocks, &ss, iv(0.0, 100.0)).len()
+        );
+        assert_eq!(result.iterations, Some(result.schedule.len()));
+        assert_eq!(result.outcomes.len(), 4);
+    }
+}
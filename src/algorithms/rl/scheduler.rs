@@ -296,7 +296,7 @@ mod tests {
         // Verify no overlaps
         let mut intervals: Vec<_> = [&id1, &id2, &id3]
             .iter()
-            .filter_map(|id| schedule.get_interval(id))
+            .filter_map(|id| schedule.get_interval(id.as_str()))
             .collect();
         intervals.sort_by(|a, b| a.start().value().partial_cmp(&b.start().value()).unwrap());
 
@@ -0,0 +1,292 @@
+//! Rolling-horizon scheduling.
+//!
+//! Long campaigns are too big to solve as one monolithic horizon, and a
+//! plan far into the future is rarely kept anyway. [`RollingHorizonScheduler`]
+//! solves them piece by piece:
+//!
+//! 1. schedule the window `[cursor, cursor + W)` with the wrapped algorithm;
+//! 2. **commit** the entries that start before `cursor + Δ` and drop the rest;
+//! 3. advance `cursor` by `Δ` and carry every uncommitted task forward.
+//!
+//! Each window sees the committed entries as frozen, exactly as in
+//! [`replan`](super::replan()): their time is taken and dynamic edges from
+//! committed tasks still restrict the tasks that follow. The last window,
+//! which reaches the end of the horizon, commits everything it plans.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use virolai::algorithms::{ESTScheduler, RollingHorizonScheduler};
+//!
+//! let rolling = RollingHorizonScheduler::new(
+//!     ESTScheduler::default(),
+//!     Quantity::new(7.0 * 86_400.0), // plan a week ahead
+//!     Quantity::new(86_400.0),       // commit one day at a time
+//! );
+//! let outcome = rolling.run(&blocks, &solution_space, horizon);
+//! for stats in &outcome.windows {
+//!     println!("{:?}: {} committed, {} carried", stats.window, stats.committed, stats.carried);
+//! }
+//! ```
+
+use crate::constraints::DynamicConstraint;
+use crate::schedule::Schedule;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+use super::replan::replan_space;
+use super::SchedulingAlgorithm;
+
+/// Schedules a long horizon one window at a time with a wrapped algorithm.
+#[derive(Debug, Clone)]
+pub struct RollingHorizonScheduler<A, U: Unit> {
+    inner: A,
+    window: Quantity<U>,
+    step: Quantity<U>,
+}
+
+/// Statistics of one window of a [`RollingHorizonScheduler::run`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowStats<U: Unit> {
+    /// The window handed to the wrapped algorithm.
+    pub window: Interval<U>,
+    /// The part of the window whose entries were committed.
+    pub commit: Interval<U>,
+    /// Entries the wrapped algorithm planned in the window.
+    pub planned: usize,
+    /// Entries committed from this window.
+    pub committed: usize,
+    /// Tasks still uncommitted after this window.
+    pub carried: usize,
+    /// Fraction of [`commit`](Self::commit) covered by committed entries.
+    pub utilization: f64,
+}
+
+/// Result of a [`RollingHorizonScheduler::run`].
+#[derive(Debug, Clone)]
+pub struct RollingOutcome<U: Unit> {
+    /// Every committed entry.
+    pub schedule: Schedule<U>,
+    /// Per-window statistics, in window order.
+    pub windows: Vec<WindowStats<U>>,
+    /// Tasks of the blocks never committed, in block order.
+    pub unplaced: Vec<Id>,
+}
+
+impl<A, U: Unit> RollingHorizonScheduler<A, U> {
+    /// Wraps `algorithm` with windows of length `window`, advancing by
+    /// `step`. A step longer than the window is clamped to the window.
+    ///
+    /// # Panics
+    ///
+    /// If `step` is not positive.
+    pub fn new(algorithm: A, window: Quantity<U>, step: Quantity<U>) -> Self {
+        assert!(step.value() > 0.0, "rolling step must be positive");
+        let step = if step.value() > window.value() {
+            window
+        } else {
+            step
+        };
+        Self {
+            inner: algorithm,
+            window,
+            step,
+        }
+    }
+
+    /// Length of each planning window.
+    pub fn window(&self) -> Quantity<U> {
+        self.window
+    }
+
+    /// Length of the committed part of each window.
+    pub fn step(&self) -> Quantity<U> {
+        self.step
+    }
+
+    /// Returns a reference to the wrapped algorithm.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Rolls the wrapped algorithm over `horizon`.
+    pub fn run<T, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> RollingOutcome<U>
+    where
+        A: SchedulingAlgorithm<T, U, D, E>,
+        T: Task<U>,
+        D: DynamicConstraint<U>,
+        E: petgraph::EdgeType,
+    {
+        let total: usize = blocks.iter().map(|block| block.tasks().count()).sum();
+        let end = horizon.end().value();
+        let mut committed = Schedule::new();
+        let mut windows = Vec::new();
+        let mut cursor = horizon.start().value();
+
+        while cursor < end {
+            let window_end = (cursor + self.window.value()).min(end);
+            let commit_end = if window_end >= end {
+                end
+            } else {
+                cursor + self.step.value()
+            };
+            let window = Interval::new(Quantity::new(cursor), Quantity::new(window_end));
+            let commit = Interval::new(Quantity::new(cursor), Quantity::new(commit_end));
+
+            let space = replan_space(&committed, blocks, solution_space, window);
+            let plan = self.inner.schedule(blocks, &space, window);
+            let mut count = 0;
+            let mut busy = 0.0;
+            for (id, interval) in plan.iter() {
+                if interval.start().value() < commit_end
+                    && !committed.contains_task(id.as_str())
+                    && committed.add(id, interval).is_ok()
+                {
+                    count += 1;
+                    busy += interval.end().value().min(commit_end) - interval.start().value();
+                }
+            }
+            windows.push(WindowStats {
+                window,
+                commit,
+                planned: plan.len(),
+                committed: count,
+                carried: total - committed.len(),
+                utilization: busy / (commit_end - cursor),
+            });
+            cursor = commit_end;
+        }
+
+        let unplaced = blocks
+            .iter()
+            .flat_map(|block| block.tasks().map(|(id, _)| id))
+            .filter(|id| !committed.contains_task(*id))
+            .map(str::to_owned)
+            .collect();
+        RollingOutcome {
+            schedule: committed,
+            windows,
+            unplaced,
+        }
+    }
+}
+
+impl<A, T, U, D, E> SchedulingAlgorithm<T, U, D, E> for RollingHorizonScheduler<A, U>
+where
+    A: SchedulingAlgorithm<T, U, D, E>,
+    T: Task<U>,
+    U: Unit,
+    D: DynamicConstraint<U>,
+    E: petgraph::EdgeType,
+{
+    fn schedule(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+    ) -> Schedule<U> {
+        self.run(blocks, solution_space, horizon).schedule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::ESTScheduler;
+    use crate::constraints::DynConstraintKind;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    fn block(tasks: &[(&str, f64)]) -> SchedulingBlock<TestTask, Second, DynConstraintKind> {
+        let mut block = SchedulingBlock::new();
+        for &(id, size) in tasks {
+            block
+                .add_task_with_id(TestTask::new(id, size), Some(id.into()))
+                .unwrap();
+        }
+        block
+    }
+
+    fn space(entries: &[(&str, f64, f64)]) -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for &(id, a, b) in entries {
+            ss.add_interval(id, iv(a, b));
+        }
+        ss
+    }
+
+    #[test]
+    fn commits_step_by_step_and_carries_the_rest() {
+        let blocks = [block(&[("a", 10.0), ("b", 10.0), ("c", 10.0)])];
+        let ss = space(&[("a", 0.0, 100.0), ("b", 0.0, 100.0), ("c", 50.0, 100.0)]);
+        let rolling = RollingHorizonScheduler::new(ESTScheduler::default(), q(40.0), q(20.0));
+        let outcome = rolling.run(&blocks, &ss, iv(0.0, 100.0));
+
+        assert!(outcome.unplaced.is_empty());
+        assert_eq!(outcome.schedule.get_interval("c"), Some(iv(50.0, 60.0)));
+        assert_eq!(outcome.windows.len(), 4);
+
+        let first = &outcome.windows[0];
+        assert_eq!(first.window, iv(0.0, 40.0));
+        assert_eq!(first.commit, iv(0.0, 20.0));
+        assert_eq!(first.committed, 2);
+        assert_eq!(first.carried, 1);
+        assert!((first.utilization - 1.0).abs() < 1e-9);
+
+        // The last window reaches the horizon end and commits all it plans.
+        let last = outcome.windows.last().unwrap();
+        assert_eq!(last.window, iv(60.0, 100.0));
+        assert_eq!(last.commit, iv(60.0, 100.0));
+    }
+
+    #[test]
+    fn entries_starting_after_the_step_are_replanned() {
+        let blocks = [block(&[("a", 30.0), ("b", 10.0)])];
+        let ss = space(&[("a", 0.0, 100.0), ("b", 0.0, 100.0)]);
+        let rolling = RollingHorizonScheduler::new(ESTScheduler::default(), q(50.0), q(10.0));
+        let outcome = rolling.run(&blocks, &ss, iv(0.0, 100.0));
+
+        assert_eq!(outcome.windows[0].planned, 2);
+        assert_eq!(outcome.windows[0].committed, 1);
+        // The committed entry runs past the step and blocks the next window.
+        let a = outcome.schedule.get_interval("a").unwrap();
+        let b = outcome.schedule.get_interval("b").unwrap();
+        assert!(b.start().value() >= a.end().value() || b.end().value() <= a.start().value());
+        assert_eq!(outcome.schedule.len(), 2);
+    }
+
+    #[test]
+    fn dynamic_edges_from_committed_tasks_apply() {
+        let mut b = block(&[("a", 10.0), ("c", 10.0)]);
+        let (na, nc) = (b.node_of("a").unwrap(), b.node_of("c").unwrap());
+        b.add_dependency(na, nc, DynConstraintKind::start_to_start(50.0))
+            .unwrap();
+        let ss = space(&[("a", 0.0, 10.0), ("c", 0.0, 100.0)]);
+        let rolling = RollingHorizonScheduler::new(ESTScheduler::default(), q(20.0), q(10.0));
+        let outcome = rolling.run(&[b], &ss, iv(0.0, 100.0));
+
+        // "c" is planned at t = 10 in the first window but not committed;
+        // once "a" is committed, StartToStart(lag = 50) holds it until t = 50.
+        assert_eq!(outcome.windows[0].committed, 1);
+        assert_eq!(outcome.schedule.get_interval("a"), Some(iv(0.0, 10.0)));
+        assert_eq!(outcome.schedule.get_interval("c"), Some(iv(50.0, 60.0)));
+    }
+
+    #[test]
+    fn tasks_that_never_fit_are_unplaced() {
+        let blocks = [block(&[("a", 10.0), ("big", 500.0)])];
+        let ss = space(&[("a", 0.0, 100.0), ("big", 0.0, 1000.0)]);
+        let rolling = RollingHorizonScheduler::new(ESTScheduler::default(), q(30.0), q(50.0));
+        assert_eq!(rolling.step(), q(30.0));
+        let outcome = rolling.run(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(outcome.unplaced, vec!["big".to_string()]);
+        assert_eq!(outcome.windows.last().unwrap().carried, 1);
+    }
+}
@@ -0,0 +1,263 @@
+//! Preemptive placement of [`SplittableTask`]s.
+//!
+//! When no free window can hold a task in one piece, a splittable task may
+//! still be placed as several pieces, each in a different free gap:
+//!
+//! 1. Free time is the task's windows minus every occupied entry of the
+//!    schedule, clipped to the horizon
+//! 2. If one free gap is long enough, the task is placed there whole, under
+//!    its own ID
+//! 3. Otherwise gaps are filled earliest first, each piece at least
+//!    [`min_chunk`](SplittableTask::min_chunk) long, with at most
+//!    [`max_splits`](SplittableTask::max_splits) cuts
+//!
+//! A schedule holds one interval per ID, so pieces are stored under
+//! [`chunk_id`]s (`"<task>#0"`, `"<task>#1"`, …). Dynamic constraints are not
+//! evaluated for split tasks.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use virolai::algorithms::split::split_unscheduled;
+//!
+//! let mut schedule = scheduler.schedule(&blocks, &solution_space, horizon);
+//! let split = split_unscheduled(&mut schedule, &blocks, &solution_space, horizon);
+//! println!("placed {} more tasks in pieces", split.len());
+//! ```
+
+use crate::schedule::Schedule;
+use crate::scheduling_block::{SchedulingBlock, SplittableTask};
+use crate::solution_space::{Interval, IntervalSet, SolutionSpace};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+/// ID under which piece `index` of `task_id` is stored in a schedule.
+pub fn chunk_id(task_id: &str, index: usize) -> Id {
+    format!("{task_id}#{index}")
+}
+
+/// Cuts `size` into pieces laid over the sorted, disjoint `free` gaps.
+///
+/// Gaps are filled earliest first. Every piece is at least `min_chunk` long
+/// and at most `max_splits + 1` pieces are used; a piece is shortened when
+/// taking the whole gap would leave a remainder below `min_chunk`. Returns
+/// `None` if `size` cannot be covered under these rules.
+pub fn plan_chunks<U: Unit>(
+    free: &[Interval<U>],
+    size: Quantity<U>,
+    min_chunk: Quantity<U>,
+    max_splits: usize,
+) -> Option<Vec<Interval<U>>> {
+    let min_chunk = min_chunk.value();
+    let mut remaining = size.value();
+    let mut chunks = Vec::new();
+
+    for gap in free {
+        if remaining <= 0.0 {
+            break;
+        }
+        let last_piece = chunks.len() == max_splits;
+        let available = gap.duration().value();
+        let mut take = available.min(remaining);
+        let left = remaining - take;
+        if last_piece && left > 0.0 {
+            continue;
+        }
+        if left > 0.0 && left < min_chunk {
+            take = remaining - min_chunk;
+        }
+        if take < min_chunk.min(remaining) || take <= 0.0 {
+            continue;
+        }
+        let start = gap.start();
+        chunks.push(Interval::new(start, start + Quantity::new(take)));
+        remaining -= take;
+    }
+
+    (remaining <= 0.0).then_some(chunks)
+}
+
+/// Places `task` in `schedule`, splitting it only if no free gap fits it whole.
+///
+/// `windows` are the task's static windows. Returns the intervals added — one
+/// stored under `task_id`, or several stored under [`chunk_id`]s — or `None`
+/// (leaving `schedule` untouched) if the task cannot be placed.
+pub fn place_split<T, U>(
+    schedule: &mut Schedule<U>,
+    task_id: &str,
+    task: &T,
+    windows: &[Interval<U>],
+    horizon: Interval<U>,
+) -> Option<Vec<Interval<U>>>
+where
+    T: SplittableTask<U>,
+    U: Unit,
+{
+    let size = task.size_on_axis();
+    let free = free_time(schedule, windows, horizon);
+
+    if let Some(gap) = free
+        .iter()
+        .find(|gap| gap.duration().value() >= size.value())
+    {
+        let interval = Interval::new(gap.start(), gap.start() + size);
+        schedule.add(task_id, interval).ok()?;
+        return Some(vec![interval]);
+    }
+
+    let chunks = plan_chunks(&free, size, task.min_chunk(), task.max_splits())?;
+    for (index, &chunk) in chunks.iter().enumerate() {
+        // Chunks lie in free time, so this only fails on an ID clash.
+        if schedule.add(chunk_id(task_id, index), chunk).is_err() {
+            for undo in 0..index {
+                schedule.remove(chunk_id(task_id, undo).as_str());
+            }
+            return None;
+        }
+    }
+    Some(chunks)
+}
+
+/// Places, in pieces if needed, every task of `blocks` missing from `schedule`.
+///
+/// Tasks are tried by descending priority, then by ID; a task counts as
+/// present if its own ID or its first [`chunk_id`] is scheduled. Returns the
+/// IDs of the tasks placed, in placement order.
+pub fn split_unscheduled<T, U, D, E>(
+    schedule: &mut Schedule<U>,
+    blocks: &[SchedulingBlock<T, U, D, E>],
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+) -> Vec<Id>
+where
+    T: SplittableTask<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    let mut pending: Vec<(&str, &T)> = blocks
+        .iter()
+        .flat_map(|block| block.tasks())
+        .filter(|(id, _)| {
+            !schedule.contains_task(*id) && !schedule.contains_task(chunk_id(id, 0).as_str())
+        })
+        .collect();
+    pending.sort_by(|a, b| b.1.priority().cmp(&a.1.priority()).then(a.0.cmp(b.0)));
+
+    let mut placed = Vec::new();
+    for (id, task) in pending {
+        let windows = solution_space
+            .get_intervals(id)
+            .map(|set| set.as_slice())
+            .unwrap_or_default();
+        if place_split(schedule, id, task, windows, horizon).is_some() {
+            placed.push(id.to_owned());
+        }
+    }
+    placed
+}
+
+/// Parts of `windows` within `horizon` not occupied by `schedule`.
+fn free_time<U: Unit>(
+    schedule: &Schedule<U>,
+    windows: &[Interval<U>],
+    horizon: Interval<U>,
+) -> IntervalSet<U> {
+    let busy: IntervalSet<U> = schedule.intervals().filter(|iv| !iv.is_empty()).collect();
+    IntervalSet::from(windows.to_vec()).intersection(&busy.complement(horizon))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    /// Test policy: pieces of at least 10 s, at most two cuts.
+    impl SplittableTask<Second> for TestTask {
+        fn min_chunk(&self) -> Quantity<Second> {
+            q(10.0)
+        }
+
+        fn max_splits(&self) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn plan_fills_gaps_earliest_first() {
+        let free = [iv(0.0, 20.0), iv(30.0, 40.0), iv(50.0, 100.0)];
+        let chunks = plan_chunks(&free, q(45.0), q(10.0), 2).unwrap();
+        assert_eq!(chunks, vec![iv(0.0, 20.0), iv(30.0, 40.0), iv(50.0, 65.0)]);
+    }
+
+    #[test]
+    fn plan_respects_min_chunk_and_max_splits() {
+        // The 5 s gap is too short for a piece.
+        let free = [iv(0.0, 5.0), iv(10.0, 30.0), iv(40.0, 60.0)];
+        let chunks = plan_chunks(&free, q(30.0), q(10.0), 1).unwrap();
+        assert_eq!(chunks, vec![iv(10.0, 30.0), iv(40.0, 50.0)]);
+
+        // Taking all 20 s would leave a 5 s remainder: shorten the first piece.
+        let chunks = plan_chunks(&free, q(25.0), q(10.0), 1).unwrap();
+        assert_eq!(chunks, vec![iv(10.0, 25.0), iv(40.0, 50.0)]);
+
+        // No cuts allowed and no gap fits whole.
+        assert!(plan_chunks(&free, q(25.0), q(10.0), 0).is_none());
+        // Not enough free time at all.
+        assert!(plan_chunks(&free, q(50.0), q(10.0), 5).is_none());
+    }
+
+    #[test]
+    fn place_split_prefers_a_single_interval() {
+        let mut schedule = Schedule::new();
+        let task = TestTask::new("long", 20.0);
+        let placed = place_split(
+            &mut schedule,
+            "long",
+            &task,
+            &[iv(0.0, 15.0), iv(30.0, 60.0)],
+            iv(0.0, 100.0),
+        );
+        assert_eq!(placed, Some(vec![iv(30.0, 50.0)]));
+        assert_eq!(schedule.get_interval("long"), Some(iv(30.0, 50.0)));
+    }
+
+    #[test]
+    fn place_split_spans_a_gap() {
+        // A meridian gap at 40..50 splits the only window.
+        let mut schedule = Schedule::new();
+        schedule.add("other", iv(40.0, 50.0)).unwrap();
+        let task = TestTask::new("long", 50.0);
+        let placed = place_split(
+            &mut schedule,
+            "long",
+            &task,
+            &[iv(0.0, 80.0)],
+            iv(0.0, 100.0),
+        );
+        assert_eq!(placed, Some(vec![iv(0.0, 40.0), iv(50.0, 60.0)]));
+        assert_eq!(schedule.get_interval("long#0"), Some(iv(0.0, 40.0)));
+        assert_eq!(schedule.get_interval("long#1"), Some(iv(50.0, 60.0)));
+        assert!(!schedule.contains_task("long"));
+    }
+
+    #[test]
+    fn split_unscheduled_skips_present_tasks() {
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        for (id, size) in [("a", 10.0), ("b", 30.0)] {
+            block
+                .add_task_with_id(TestTask::new(id, size), Some(id.into()))
+                .unwrap();
+        }
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("a", vec![iv(0.0, 10.0)]);
+        ss.set_intervals("b", vec![iv(0.0, 20.0), iv(30.0, 50.0)]);
+
+        let mut schedule = Schedule::new();
+        schedule.add("a", iv(0.0, 10.0)).unwrap();
+        let placed = split_unscheduled(&mut schedule, &[block], &ss, iv(0.0, 100.0));
+        assert_eq!(placed, vec!["b".to_string()]);
+        assert_eq!(schedule.get_interval("b#0"), Some(iv(10.0, 20.0)));
+        assert_eq!(schedule.get_interval("b#1"), Some(iv(30.0, 50.0)));
+    }
+}
@@ -0,0 +1,259 @@
+//! Pre-run detection of tasks that cannot all fit.
+//!
+//! Before scheduling, each task is reduced to its envelope: the span from
+//! the start of its first window to the end of its last, keeping only
+//! windows within the horizon that are long enough for it. Any group of
+//! tasks whose envelopes all lie inside one span `[s, e)` has to fit its
+//! combined size into `e − s` on the single resource; when it does not, at
+//! least one member will be left out whatever the scheduler does.
+//!
+//! The check is necessary, not sufficient: groups it reports are certain
+//! conflicts, but dynamic edges, gaps and the shape of the windows inside
+//! an envelope can make a clear report infeasible all the same.
+
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+/// Demand above the available length that counts as an overload, absorbing
+/// rounding error.
+const DEMAND_EPSILON: f64 = 1e-9;
+
+/// A group of tasks confined to a span too short for all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict<U: Unit> {
+    /// Members of the group, sorted by ID.
+    pub tasks: Vec<Id>,
+    /// Span every member's envelope lies in.
+    pub window: Interval<U>,
+    /// Combined size of the members.
+    pub demand: Quantity<U>,
+}
+
+impl<U: Unit> Conflict<U> {
+    /// Size that does not fit into the span.
+    pub fn excess(&self) -> Quantity<U> {
+        self.demand - self.window.duration()
+    }
+}
+
+/// Result of [`conflicts`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictReport<U: Unit> {
+    /// Tasks without a window in the horizon long enough for them, sorted by
+    /// ID.
+    pub unschedulable: Vec<Id>,
+    /// Minimal overloaded groups: no reported group contains another. Sorted
+    /// by window start, then by members.
+    pub overloads: Vec<Conflict<U>>,
+}
+
+impl<U: Unit> ConflictReport<U> {
+    /// `true` if no conflict was found.
+    pub fn is_clear(&self) -> bool {
+        self.unschedulable.is_empty() && self.overloads.is_empty()
+    }
+}
+
+/// Finds the tasks of `block` that cannot all be placed within `horizon`
+/// given their windows in `solution_space`.
+///
+/// Tasks missing from `solution_space` count as unschedulable; milestones
+/// never overload a span. Runs in `O(n³)` for `n` tasks.
+///
+/// # Example
+///
+/// ```
+/// use qtty::{Quantity, Second};
+/// use virolai::analysis::conflicts;
+/// use virolai::scheduling_block::SchedulingBlock;
+/// use virolai::solution_space::{Interval, SolutionSpace};
+/// # use virolai::constraints::IntervalConstraint;
+/// # use virolai::scheduling_block::Task;
+/// # #[derive(Debug)]
+/// # struct Job(f64);
+/// # impl Task<Second> for Job {
+/// #     type SizeUnit = Second;
+/// #     type ConstraintLeaf = IntervalConstraint<Second>;
+/// #     fn name(&self) -> &str { "job" }
+/// #     fn size(&self) -> Quantity<Second> { Quantity::new(self.0) }
+/// # }
+///
+/// let mut block = SchedulingBlock::<Job, Second>::new();
+/// let mut ss = SolutionSpace::new();
+/// for id in ["a", "b"] {
+///     block.add_task_with_id(Job(30.0), Some(id.into())).unwrap();
+///     ss.add_interval(id, Interval::from_f64(0.0, 50.0));
+/// }
+///
+/// let report = conflicts(&block, &ss, Interval::from_f64(0.0, 100.0));
+/// assert_eq!(report.overloads[0].tasks, vec!["a", "b"]);
+/// assert_eq!(report.overloads[0].excess().value(), 10.0);
+/// ```
+pub fn conflicts<T, U, D, E>(
+    block: &SchedulingBlock<T, U, D, E>,
+    solution_space: &SolutionSpace<U>,
+    horizon: Interval<U>,
+) -> ConflictReport<U>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    let mut unschedulable = Vec::new();
+    let mut envelopes = Vec::new();
+    for (id, task) in block.tasks() {
+        let size = task.size_on_axis();
+        let windows: Vec<_> = solution_space
+            .get_intervals(id)
+            .into_iter()
+            .flat_map(|set| set.iter())
+            .filter_map(|w| w.intersection(&horizon))
+            .filter(|w| w.duration() >= size)
+            .collect();
+        let (Some(first), Some(last)) = (
+            windows
+                .iter()
+                .min_by(|a, b| a.start().value().total_cmp(&b.start().value())),
+            windows
+                .iter()
+                .max_by(|a, b| a.end().value().total_cmp(&b.end().value())),
+        ) else {
+            unschedulable.push(id.to_owned());
+            continue;
+        };
+        if size.value() > 0.0 {
+            envelopes.push((id, size, Interval::new(first.start(), last.end())));
+        }
+    }
+    unschedulable.sort();
+
+    let mut starts: Vec<_> = envelopes.iter().map(|(_, _, env)| env.start()).collect();
+    let mut ends: Vec<_> = envelopes.iter().map(|(_, _, env)| env.end()).collect();
+    for points in [&mut starts, &mut ends] {
+        points.sort_by(|a, b| a.value().total_cmp(&b.value()));
+        points.dedup();
+    }
+
+    let mut found = Vec::new();
+    for &s in &starts {
+        for &e in ends.iter().filter(|&&e| e > s) {
+            let window = Interval::new(s, e);
+            let mut tasks = Vec::new();
+            let mut demand = 0.0;
+            for (id, size, envelope) in &envelopes {
+                if envelope.start() >= s && envelope.end() <= e {
+                    tasks.push(id.to_string());
+                    demand += size.value();
+                }
+            }
+            if demand > window.duration().value() + DEMAND_EPSILON {
+                tasks.sort();
+                found.push(Conflict {
+                    tasks,
+                    window,
+                    demand: Quantity::new(demand),
+                });
+            }
+        }
+    }
+
+    // Smallest groups first, worst span first among equals, so the first
+    // kept group of each set is its tightest and supersets are skipped.
+    found.sort_by(|a, b| {
+        a.tasks
+            .len()
+            .cmp(&b.tasks.len())
+            .then_with(|| b.excess().value().total_cmp(&a.excess().value()))
+    });
+    let mut overloads: Vec<Conflict<U>> = Vec::new();
+    for conflict in found {
+        let covered = overloads
+            .iter()
+            .any(|kept| kept.tasks.iter().all(|id| conflict.tasks.contains(id)));
+        if !covered {
+            overloads.push(conflict);
+        }
+    }
+    overloads.sort_by(|a, b| {
+        a.window
+            .start()
+            .value()
+            .total_cmp(&b.window.start().value())
+            .then_with(|| a.tasks.cmp(&b.tasks))
+    });
+
+    ConflictReport {
+        unschedulable,
+        overloads,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    /// ID, size and windows of one task.
+    type Spec<'a> = (&'a str, f64, &'a [(f64, f64)]);
+
+    fn problem(tasks: &[Spec<'_>]) -> (SchedulingBlock<TestTask, Second>, SolutionSpace<Second>) {
+        let mut block = SchedulingBlock::new();
+        let mut ss = SolutionSpace::new();
+        for &(id, size, windows) in tasks {
+            block
+                .add_task_with_id(TestTask::new(id, size), Some(id.into()))
+                .unwrap();
+            ss.set_intervals(id, windows.iter().map(|&(s, e)| iv(s, e)).collect());
+        }
+        (block, ss)
+    }
+
+    #[test]
+    fn feasible_problems_are_clear() {
+        let (block, ss) = problem(&[
+            ("a", 10.0, &[(0.0, 20.0)]),
+            ("b", 10.0, &[(0.0, 20.0)]),
+            ("c", 0.0, &[(5.0, 5.0)]),
+        ]);
+        assert!(conflicts(&block, &ss, iv(0.0, 100.0)).is_clear());
+    }
+
+    #[test]
+    fn only_minimal_groups_are_reported() {
+        let (block, ss) = problem(&[
+            ("a", 10.0, &[(0.0, 15.0)]),
+            ("b", 10.0, &[(0.0, 15.0)]),
+            ("c", 10.0, &[(0.0, 25.0)]),
+            ("d", 20.0, &[(50.0, 60.0), (70.0, 75.0)]),
+        ]);
+        let report = conflicts(&block, &ss, iv(0.0, 100.0));
+        // {a, b, c} overloads [0, 25) too, but contains {a, b}.
+        assert_eq!(
+            report.overloads,
+            vec![Conflict {
+                tasks: vec!["a".into(), "b".into()],
+                window: iv(0.0, 15.0),
+                demand: q(20.0),
+            }]
+        );
+        // "d" has no window long enough.
+        assert_eq!(report.unschedulable, vec!["d"]);
+    }
+
+    #[test]
+    fn envelopes_are_clipped_to_the_horizon() {
+        let (block, ss) = problem(&[
+            ("a", 10.0, &[(0.0, 100.0)]),
+            ("b", 10.0, &[(0.0, 100.0)]),
+            ("ghost", 1.0, &[]),
+        ]);
+        let report = conflicts(&block, &ss, iv(85.0, 100.0));
+        assert_eq!(report.overloads.len(), 1);
+        assert_eq!(report.overloads[0].window, iv(85.0, 100.0));
+        assert_eq!(report.overloads[0].excess(), q(5.0));
+        assert_eq!(report.unschedulable, vec!["ghost"]);
+    }
+}
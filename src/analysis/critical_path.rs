@@ -0,0 +1,307 @@
+//! Critical path and slack of a finished schedule.
+//!
+//! The schedule is read as a precedence network with an arc for every
+//! `Dependence` or `Consecutive` edge between two placed tasks, in time
+//! order, and one between neighbours on the timeline, lagged by the earlier
+//! task's [`gap_after`](Task::gap_after). The timeline arcs model the
+//! single resource: once the idle time in front of the next task is used
+//! up, a slip pushes it back too, as in the right-shift repair of
+//! [`monte_carlo`](super::monte_carlo).
+
+use std::collections::HashMap;
+
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::constraints::DynConstraintKind;
+use crate::schedule::Schedule;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+/// Slack below which a task counts as critical, absorbing rounding error.
+const SLACK_EPSILON: f64 = 1e-9;
+
+/// How far one task may slip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaskSlack<U: Unit> {
+    /// Delay the task absorbs without moving the schedule's completion.
+    pub total: Quantity<U>,
+    /// Delay the task absorbs without moving any successor.
+    pub free: Quantity<U>,
+}
+
+impl<U: Unit> TaskSlack<U> {
+    /// `true` if any slip delays completion.
+    pub fn is_critical(&self) -> bool {
+        self.total.value() <= SLACK_EPSILON
+    }
+}
+
+/// Result of [`critical_path`].
+#[derive(Debug, Clone)]
+pub struct CriticalPathReport<U: Unit> {
+    /// End of the last placement.
+    pub completion: Quantity<U>,
+    /// Longest chain of placed tasks linked by `Dependence`/`Consecutive`
+    /// edges, by total placed duration, first task first.
+    pub longest_chain: Vec<Id>,
+    /// Total placed duration of `longest_chain`.
+    pub chain_length: Quantity<U>,
+    /// Slack of every placed task.
+    pub slack: HashMap<Id, TaskSlack<U>>,
+    /// Tasks whose slip delays completion, in start order.
+    pub critical: Vec<Id>,
+}
+
+impl<U: Unit> CriticalPathReport<U> {
+    /// Slack of `id`, if it was placed.
+    pub fn slack_of(&self, id: &str) -> Option<TaskSlack<U>> {
+        self.slack.get(id).copied()
+    }
+
+    /// `true` if `id` was placed and any slip of it delays completion.
+    pub fn is_critical(&self, id: &str) -> bool {
+        self.slack_of(id).is_some_and(|s| s.is_critical())
+    }
+}
+
+/// Critical path and per-task slack of `schedule` under the edges of
+/// `block`.
+///
+/// Edges whose reference is placed after its target do not order the two
+/// in time and are ignored. Tasks of `schedule` missing from `block` take
+/// part through their timeline arcs only, with no gap. Returns `None` for
+/// an empty schedule.
+///
+/// # Example
+///
+/// ```
+/// use qtty::{Quantity, Second};
+/// use virolai::analysis::critical_path;
+/// use virolai::constraints::DynConstraintKind;
+/// use virolai::schedule::Schedule;
+/// use virolai::scheduling_block::SchedulingBlock;
+/// use virolai::solution_space::Interval;
+/// # use virolai::constraints::IntervalConstraint;
+/// # use virolai::scheduling_block::Task;
+/// # #[derive(Debug)]
+/// # struct Job(f64);
+/// # impl Task<Second> for Job {
+/// #     type SizeUnit = Second;
+/// #     type ConstraintLeaf = IntervalConstraint<Second>;
+/// #     fn name(&self) -> &str { "job" }
+/// #     fn size(&self) -> Quantity<Second> { Quantity::new(self.0) }
+/// # }
+///
+/// let mut block = SchedulingBlock::<Job, Second, DynConstraintKind>::new();
+/// let a = block.add_task_with_id(Job(10.0), Some("a".into())).unwrap();
+/// let b = block.add_task_with_id(Job(10.0), Some("b".into())).unwrap();
+/// block
+///     .add_dependency(
+///         block.node_of(&a).unwrap(),
+///         block.node_of(&b).unwrap(),
+///         DynConstraintKind::Consecutive,
+///     )
+///     .unwrap();
+///
+/// // Five seconds of idle time between "a" and "b".
+/// let mut schedule = Schedule::new();
+/// schedule.add("a", Interval::from_f64(0.0, 10.0)).unwrap();
+/// schedule.add("b", Interval::from_f64(15.0, 25.0)).unwrap();
+///
+/// let report = critical_path(&schedule, &block).unwrap();
+/// assert_eq!(report.longest_chain, vec!["a", "b"]);
+/// assert_eq!(report.slack_of("a").unwrap().total.value(), 5.0);
+/// assert_eq!(report.critical, vec!["b"]);
+/// ```
+pub fn critical_path<T, U, E>(
+    schedule: &Schedule<U>,
+    block: &SchedulingBlock<T, U, DynConstraintKind, E>,
+) -> Option<CriticalPathReport<U>>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    let completion = schedule.latest_end()?;
+    let placed: Vec<(Id, Interval<U>)> = schedule.iter().collect();
+    let position: HashMap<&str, usize> = placed
+        .iter()
+        .enumerate()
+        .map(|(i, (id, _))| (id.as_str(), i))
+        .collect();
+    let start = |i: usize| placed[i].1.start().value();
+    let end = |i: usize| placed[i].1.end().value();
+    let duration = |i: usize| placed[i].1.duration().value();
+
+    // Edge arcs, oriented forward in time.
+    let mut edge_preds: Vec<Vec<usize>> = vec![Vec::new(); placed.len()];
+    let mut successors: Vec<Vec<(usize, f64)>> = vec![Vec::new(); placed.len()];
+    for edge in block.graph().edge_references() {
+        if !matches!(
+            edge.weight(),
+            DynConstraintKind::Dependence | DynConstraintKind::Consecutive
+        ) {
+            continue;
+        }
+        let endpoint = |node| block.id_of(node).and_then(|id| position.get(id).copied());
+        let (Some(mut from), Some(mut to)) = (endpoint(edge.source()), endpoint(edge.target()))
+        else {
+            continue;
+        };
+        if !E::is_directed() && to < from {
+            std::mem::swap(&mut from, &mut to);
+        }
+        if from < to && end(from) <= start(to) + SLACK_EPSILON {
+            edge_preds[to].push(from);
+            successors[from].push((to, 0.0));
+        }
+    }
+    // Timeline arcs.
+    for i in 1..placed.len() {
+        let gap = block
+            .task_by_id(&placed[i - 1].0)
+            .map_or(0.0, |task| task.gap_after().value());
+        successors[i - 1].push((i, gap));
+    }
+
+    // Longest edge chain, by placed duration.
+    let mut length = vec![0.0f64; placed.len()];
+    let mut chain_pred = vec![None; placed.len()];
+    for j in 0..placed.len() {
+        let best = edge_preds[j]
+            .iter()
+            .copied()
+            .max_by(|&a, &b| length[a].total_cmp(&length[b]).then(b.cmp(&a)));
+        length[j] = duration(j) + best.map_or(0.0, |i| length[i]);
+        chain_pred[j] = best;
+    }
+    let last = (0..placed.len())
+        .max_by(|&a, &b| length[a].total_cmp(&length[b]).then(b.cmp(&a)))
+        .expect("schedule is not empty");
+    let mut longest_chain = Vec::new();
+    let mut current = Some(last);
+    while let Some(i) = current {
+        longest_chain.push(placed[i].0.clone());
+        current = chain_pred[i];
+    }
+    longest_chain.reverse();
+
+    // Latest finishes, backwards in time.
+    let mut latest_finish = vec![completion.value(); placed.len()];
+    for i in (0..placed.len()).rev() {
+        for &(j, lag) in &successors[i] {
+            let latest_start = latest_finish[j] - duration(j);
+            latest_finish[i] = latest_finish[i].min(latest_start - lag);
+        }
+    }
+
+    let mut slack = HashMap::with_capacity(placed.len());
+    let mut critical = Vec::new();
+    for (i, (id, _)) in placed.iter().enumerate() {
+        let free = successors[i]
+            .iter()
+            .map(|&(j, lag)| start(j) - end(i) - lag)
+            .fold(completion.value() - end(i), f64::min);
+        let task_slack = TaskSlack {
+            total: Quantity::new((latest_finish[i] - end(i)).max(0.0)),
+            free: Quantity::new(free.max(0.0)),
+        };
+        if task_slack.is_critical() {
+            critical.push(id.clone());
+        }
+        slack.insert(id.clone(), task_slack);
+    }
+
+    Some(CriticalPathReport {
+        completion,
+        longest_chain,
+        chain_length: Quantity::new(length[last]),
+        slack,
+        critical,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    type Block = SchedulingBlock<TestTask, Second, DynConstraintKind>;
+
+    fn block(tasks: &[(&str, f64)], edges: &[(&str, &str, DynConstraintKind)]) -> Block {
+        let mut block = Block::new();
+        for &(id, size) in tasks {
+            block
+                .add_task_with_id(TestTask::new(id, size), Some(id.into()))
+                .unwrap();
+        }
+        for (from, to, kind) in edges {
+            let (from, to) = (block.node_of(from).unwrap(), block.node_of(to).unwrap());
+            block.add_dependency(from, to, *kind).unwrap();
+        }
+        block
+    }
+
+    fn schedule(placements: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut schedule = Schedule::new();
+        for &(id, start, end) in placements {
+            schedule.add(id, iv(start, end)).unwrap();
+        }
+        schedule
+    }
+
+    #[test]
+    fn slack_follows_idle_time_and_gaps() {
+        let block = block(
+            &[("a", 10.0), ("b", 10.0), ("c", 5.0)],
+            &[("a", "c", DynConstraintKind::Consecutive)],
+        );
+        // a, 5 idle, b, 10 idle, c.
+        let schedule = schedule(&[("a", 0.0, 10.0), ("b", 15.0, 25.0), ("c", 35.0, 40.0)]);
+        let report = critical_path(&schedule, &block).unwrap();
+
+        assert_eq!(report.completion, q(40.0));
+        assert_eq!(report.longest_chain, vec!["a", "c"]);
+        assert_eq!(report.chain_length, q(15.0));
+        // "a" can use the idle time before "b" and before "c".
+        assert_eq!(
+            report.slack_of("a"),
+            Some(TaskSlack {
+                total: q(15.0),
+                free: q(5.0)
+            })
+        );
+        assert_eq!(report.slack_of("b").unwrap().total, q(10.0));
+        assert_eq!(report.critical, vec!["c"]);
+        assert!(!report.is_critical("ghost"));
+    }
+
+    #[test]
+    fn back_to_back_tasks_are_all_critical() {
+        let block = block(&[("a", 10.0), ("b", 10.0)], &[]);
+        let schedule = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 20.0)]);
+        let report = critical_path(&schedule, &block).unwrap();
+        assert_eq!(report.critical, vec!["a", "b"]);
+        // Without edges, the longest chain is the longest single task; ties
+        // go to the earliest.
+        assert_eq!(report.longest_chain, vec!["a"]);
+    }
+
+    #[test]
+    fn edges_against_time_and_other_kinds_are_ignored() {
+        let block = block(
+            &[("a", 10.0), ("b", 10.0), ("c", 10.0)],
+            &[
+                ("b", "a", DynConstraintKind::Dependence),
+                ("a", "c", DynConstraintKind::Exclusive),
+            ],
+        );
+        let schedule = schedule(&[("a", 0.0, 10.0), ("b", 20.0, 30.0), ("c", 40.0, 50.0)]);
+        let report = critical_path(&schedule, &block).unwrap();
+        assert_eq!(report.longest_chain, vec!["a"]);
+        assert!(critical_path(&Schedule::new(), &block).is_none());
+    }
+}
@@ -0,0 +1,18 @@
+//! Analysis of scheduling problems and finished schedules.
+//!
+//! A schedule is built from nominal durations; execution rarely follows it
+//! exactly. Most tools here estimate how a plan holds up once tasks start
+//! late or run long, before operators commit to it; [`conflicts`] looks at
+//! the problem itself, before any scheduler runs.
+//!
+//! - [`conflicts`] - Pre-run detection of tasks that cannot all fit
+//! - [`critical_path`] - Critical path and per-task slack
+//! - [`monte_carlo`] - Sampled execution with right-shift repair
+
+mod conflicts;
+mod critical_path;
+mod monte_carlo;
+
+pub use conflicts::{conflicts, Conflict, ConflictReport};
+pub use critical_path::{critical_path, CriticalPathReport, TaskSlack};
+pub use monte_carlo::{monte_carlo, CompletionStats, DurationModel, MonteCarloReport, TaskRisk};
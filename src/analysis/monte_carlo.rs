@@ -0,0 +1,367 @@
+//! Monte Carlo execution of a schedule.
+//!
+//! Each trial replays the schedule in start order with durations and start
+//! delays drawn from a [`DurationModel`]. Execution is repaired by
+//! right-shifting only: a task never starts before its planned start, nor
+//! before its predecessor on the timeline has finished and cleared its
+//! [`gap_after`](Task::gap_after). The order of the plan is kept.
+
+use std::collections::HashMap;
+
+use crate::rng::{derive_seed, SplitMix64};
+use crate::schedule::Schedule;
+use crate::scheduling_block::{DurationDistribution, SchedulingBlock, Task, UncertainTask};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+/// How actual durations and start delays deviate from the plan.
+///
+/// Tasks without an explicit distribution take a normal duration centred on
+/// their nominal size, with a standard deviation of
+/// [`relative_spread`](Self::with_relative_spread) times that size (zero by
+/// default, i.e. nominal durations).
+///
+/// # Example
+///
+/// ```
+/// use qtty::{Quantity, Second};
+/// use virolai::analysis::{monte_carlo, DurationModel};
+/// use virolai::schedule::Schedule;
+/// use virolai::scheduling_block::{DurationDistribution, SchedulingBlock};
+/// use virolai::solution_space::Interval;
+/// # use virolai::constraints::IntervalConstraint;
+/// # use virolai::scheduling_block::Task;
+/// # #[derive(Debug)]
+/// # struct Job(f64);
+/// # impl Task<Second> for Job {
+/// #     type SizeUnit = Second;
+/// #     type ConstraintLeaf = IntervalConstraint<Second>;
+/// #     fn name(&self) -> &str { "job" }
+/// #     fn size(&self) -> Quantity<Second> { Quantity::new(self.0) }
+/// # }
+///
+/// let mut block = SchedulingBlock::<Job, Second>::new();
+/// block.add_task_with_id(Job(10.0), Some("a".into())).unwrap();
+/// block.add_task_with_id(Job(10.0), Some("b".into())).unwrap();
+/// let mut schedule = Schedule::new();
+/// schedule.add("a", Interval::from_f64(0.0, 10.0)).unwrap();
+/// schedule.add("b", Interval::from_f64(10.0, 20.0)).unwrap();
+///
+/// // "a" takes 20 s half the time, pushing "b" back.
+/// let model = DurationModel::new(7).with_duration(
+///     "a",
+///     DurationDistribution::empirical([Quantity::new(10.0), Quantity::new(20.0)]),
+/// );
+/// let report = monte_carlo(&schedule, &block, &model, 1000);
+/// let b = &report.tasks["b"];
+/// assert!((b.late_probability - 0.5).abs() < 0.1);
+/// assert_eq!(b.max_lateness.value(), 10.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DurationModel<U: Unit> {
+    seed: u64,
+    relative_spread: f64,
+    durations: HashMap<Id, DurationDistribution<U>>,
+    start_delay: Option<DurationDistribution<U>>,
+}
+
+impl<U: Unit> DurationModel<U> {
+    /// Creates a model with nominal durations and no delays, sampled from a
+    /// stream seeded by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            relative_spread: 0.0,
+            durations: HashMap::new(),
+            start_delay: None,
+        }
+    }
+
+    /// Takes every task's distribution from its [`UncertainTask`] impl.
+    pub fn from_block<T, D, E>(block: &SchedulingBlock<T, U, D, E>, seed: u64) -> Self
+    where
+        T: UncertainTask<U>,
+        E: petgraph::EdgeType,
+    {
+        block.tasks().fold(Self::new(seed), |model, (id, task)| {
+            model.with_duration(id, task.duration_distribution())
+        })
+    }
+
+    /// Draws durations of tasks without an explicit distribution with a
+    /// standard deviation of `spread` times their nominal size.
+    ///
+    /// # Panics
+    ///
+    /// If `spread` is negative or not finite.
+    pub fn with_relative_spread(mut self, spread: f64) -> Self {
+        assert!(
+            spread.is_finite() && spread >= 0.0,
+            "relative spread must be finite and non-negative, got {spread}"
+        );
+        self.relative_spread = spread;
+        self
+    }
+
+    /// Draws the duration of `id` from `distribution`.
+    pub fn with_duration(
+        mut self,
+        id: impl Into<Id>,
+        distribution: DurationDistribution<U>,
+    ) -> Self {
+        self.durations.insert(id.into(), distribution);
+        self
+    }
+
+    /// Delays every task's release past its planned start by a draw from
+    /// `delay`.
+    pub fn with_start_delay(mut self, delay: DurationDistribution<U>) -> Self {
+        self.start_delay = Some(delay);
+        self
+    }
+
+    /// Seed of the sampling stream.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn sample_duration(&self, id: &str, nominal: Quantity<U>, rng: &mut SplitMix64) -> Quantity<U> {
+        match self.durations.get(id) {
+            Some(distribution) => distribution.sample(rng),
+            None if self.relative_spread == 0.0 => nominal,
+            None => DurationDistribution::normal(
+                nominal,
+                Quantity::new(nominal.value().abs() * self.relative_spread),
+            )
+            .sample(rng),
+        }
+    }
+
+    fn sample_delay(&self, rng: &mut SplitMix64) -> Quantity<U> {
+        self.start_delay
+            .as_ref()
+            .map_or(Quantity::new(0.0), |delay| delay.sample(rng))
+    }
+}
+
+/// Sampled lateness of one task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskRisk<U: Unit> {
+    /// Fraction of trials in which the task finished after its planned end.
+    pub late_probability: f64,
+    /// Mean of `max(0, actual end − planned end)`.
+    pub mean_lateness: Quantity<U>,
+    /// Largest lateness in any trial.
+    pub max_lateness: Quantity<U>,
+}
+
+/// Distribution of the time the last task finishes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionStats<U: Unit> {
+    /// Latest planned end.
+    pub planned: Quantity<U>,
+    pub mean: Quantity<U>,
+    pub std_dev: Quantity<U>,
+    /// Median completion.
+    pub p50: Quantity<U>,
+    /// 95th-percentile completion.
+    pub p95: Quantity<U>,
+    pub max: Quantity<U>,
+    /// Fraction of trials in which every task finished by `planned`.
+    pub on_time_probability: f64,
+}
+
+/// Result of [`monte_carlo`].
+#[derive(Debug, Clone)]
+pub struct MonteCarloReport<U: Unit> {
+    /// Number of trials run.
+    pub trials: usize,
+    /// Lateness of every scheduled task.
+    pub tasks: HashMap<Id, TaskRisk<U>>,
+    /// Completion time of the whole schedule; `None` if it is empty.
+    pub completion: Option<CompletionStats<U>>,
+}
+
+impl<U: Unit> MonteCarloReport<U> {
+    /// Scheduled tasks, most likely to be late first; ties by ID.
+    pub fn most_fragile(&self) -> Vec<(&str, &TaskRisk<U>)> {
+        let mut tasks: Vec<_> = self
+            .tasks
+            .iter()
+            .map(|(id, risk)| (id.as_str(), risk))
+            .collect();
+        tasks.sort_by(|(a_id, a), (b_id, b)| {
+            b.late_probability
+                .total_cmp(&a.late_probability)
+                .then_with(|| a_id.cmp(b_id))
+        });
+        tasks
+    }
+}
+
+/// Replays `schedule` `trials` times with durations and delays drawn from
+/// `model`, and reports how late tasks and the whole plan finish.
+///
+/// Nominal sizes and gaps come from `block`; scheduled tasks missing from it
+/// keep their placed length as nominal size and no gap. The same `model`
+/// seed yields the same report.
+///
+/// # Panics
+///
+/// If `trials` is zero.
+pub fn monte_carlo<T, U, D, E>(
+    schedule: &Schedule<U>,
+    block: &SchedulingBlock<T, U, D, E>,
+    model: &DurationModel<U>,
+    trials: usize,
+) -> MonteCarloReport<U>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    assert!(trials > 0, "monte carlo needs at least one trial");
+
+    let placed: Vec<_> = schedule
+        .iter()
+        .map(|(id, planned)| {
+            let task = block.task_by_id(&id);
+            let nominal = task.map_or(planned.duration(), |t| t.size_on_axis());
+            let gap = task.map_or(Quantity::new(0.0), |t| t.gap_after());
+            (id, planned, nominal, gap)
+        })
+        .collect();
+
+    let mut late = vec![0usize; placed.len()];
+    let mut total_lateness = vec![0.0; placed.len()];
+    let mut max_lateness = vec![0.0f64; placed.len()];
+    let mut completions = Vec::with_capacity(trials);
+
+    for trial in 0..trials {
+        let mut rng = SplitMix64::new(derive_seed(model.seed, trial as u64));
+        let mut ready: Option<Quantity<U>> = None;
+        let mut completion = f64::NEG_INFINITY;
+        for (i, (id, planned, nominal, gap)) in placed.iter().enumerate() {
+            let released = planned.start() + model.sample_delay(&mut rng);
+            let start = ready.map_or(released, |r| if r > released { r } else { released });
+            let end = start + model.sample_duration(id, *nominal, &mut rng);
+            ready = Some(end + *gap);
+            completion = completion.max(end.value());
+
+            let lateness = (end - planned.end()).value();
+            if lateness > 0.0 {
+                late[i] += 1;
+                total_lateness[i] += lateness;
+                max_lateness[i] = max_lateness[i].max(lateness);
+            }
+        }
+        completions.push(completion);
+    }
+
+    let n = trials as f64;
+    let tasks = placed
+        .iter()
+        .enumerate()
+        .map(|(i, (id, ..))| {
+            let risk = TaskRisk {
+                late_probability: late[i] as f64 / n,
+                mean_lateness: Quantity::new(total_lateness[i] / n),
+                max_lateness: Quantity::new(max_lateness[i]),
+            };
+            (id.clone(), risk)
+        })
+        .collect();
+
+    let completion = schedule.latest_end().map(|planned| {
+        completions.sort_by(f64::total_cmp);
+        let mean = completions.iter().sum::<f64>() / n;
+        let variance = completions.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / n;
+        let nearest_rank = |p: f64| {
+            let rank = (p * n).ceil() as usize;
+            Quantity::new(completions[rank.clamp(1, trials) - 1])
+        };
+        CompletionStats {
+            planned,
+            mean: Quantity::new(mean),
+            std_dev: Quantity::new(variance.sqrt()),
+            p50: nearest_rank(0.5),
+            p95: nearest_rank(0.95),
+            max: Quantity::new(completions[trials - 1]),
+            on_time_probability: completions
+                .iter()
+                .filter(|&&c| c <= planned.value())
+                .count() as f64
+                / n,
+        }
+    });
+
+    MonteCarloReport {
+        trials,
+        tasks,
+        completion,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    fn plan(slack: f64) -> (Schedule<Second>, SchedulingBlock<TestTask, Second>) {
+        let mut block = SchedulingBlock::new();
+        let mut schedule = Schedule::new();
+        let mut start = 0.0;
+        for id in ["a", "b", "c"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+            schedule.add(id, iv(start, start + 10.0)).unwrap();
+            start += 10.0 + slack;
+        }
+        (schedule, block)
+    }
+
+    #[test]
+    fn nominal_model_is_never_late() {
+        let (schedule, block) = plan(0.0);
+        let report = monte_carlo(&schedule, &block, &DurationModel::new(1), 20);
+        assert!(report.tasks.values().all(|r| r.late_probability == 0.0));
+        let completion = report.completion.unwrap();
+        assert_eq!(completion.p95, q(30.0));
+        assert_eq!(completion.on_time_probability, 1.0);
+    }
+
+    #[test]
+    fn overruns_propagate_until_slack_absorbs_them() {
+        let overrun = DurationDistribution::fixed(q(15.0));
+        let (tight, block) = plan(0.0);
+        let model = DurationModel::new(1).with_duration("a", overrun.clone());
+        let report = monte_carlo(&tight, &block, &model, 5);
+        for id in ["a", "b", "c"] {
+            assert_eq!(report.tasks[id].late_probability, 1.0, "{id}");
+            assert_eq!(report.tasks[id].max_lateness, q(5.0));
+        }
+        assert_eq!(report.completion.unwrap().max, q(35.0));
+
+        let (loose, block) = plan(5.0);
+        let report = monte_carlo(&loose, &block, &model, 5);
+        assert_eq!(report.tasks["b"].late_probability, 0.0);
+        assert_eq!(report.most_fragile()[0].0, "a");
+    }
+
+    #[test]
+    fn start_delays_and_spread_are_reproducible() {
+        let (schedule, block) = plan(2.0);
+        let model = DurationModel::new(42)
+            .with_relative_spread(0.2)
+            .with_start_delay(DurationDistribution::empirical([q(0.0), q(0.0), q(3.0)]));
+        let first = monte_carlo(&schedule, &block, &model, 200);
+        let second = monte_carlo(&schedule, &block, &model, 200);
+        assert_eq!(first.tasks, second.tasks);
+        assert_eq!(first.completion, second.completion);
+        let completion = first.completion.unwrap();
+        assert!(completion.on_time_probability < 1.0);
+        assert!(completion.p95 > completion.planned);
+    }
+}
@@ -0,0 +1,358 @@
+//! Command-line front end (`cli` feature).
+//!
+//! ```text
+//! virolai PROBLEM.json [--algorithm NAME] [--horizon START,END] [--seed N]
+//!                      [--format json|csv] [--output PATH]
+//! ```
+//!
+//! `PROBLEM.json` is a [`ProblemDocument`] of [`TaskSpec`]s and
+//! [`DynConstraintKind`] edges; `--horizon` supplies or overrides its
+//! `"horizon"`. `--algorithm` names a scheduler of the built-in
+//! [`Registry`]; `--seed N` passes it `seed=N`. JSON output holds the schedule, its statistics and the
+//! reason each left-out task was not placed. CSV output is the schedule
+//! alone, with the statistics printed to stderr. The format follows the
+//! output extension unless `--format` is given.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use qtty::Second;
+use serde_json::{json, Value};
+
+use virolai::algorithms::{SchedulerResult, TaskOutcome};
+use virolai::constraints::DynConstraintKind;
+use virolai::registry::Registry;
+use virolai::schedule::io::csv;
+use virolai::schedule::ScheduleStats;
+use virolai::scheduling_block::import::{ProblemDocument, TaskSpec};
+use virolai::scheduling_block::SchedulingBlock;
+use virolai::solution_space::SolutionSpace;
+
+const USAGE: &str = "\
+Usage: virolai PROBLEM.json [OPTIONS]
+
+Options:
+  --algorithm NAME     registered scheduler (default: est-layered,
+                       prerequisites first)
+  --horizon START,END  scheduling horizon, overriding the problem's
+  --seed N             break ties randomly from seed N
+  --format FORMAT      json or csv (default: from --output, else json)
+  --output PATH        write here instead of stdout
+  -h, --help           print this help";
+
+type Problem = ProblemDocument<Second, TaskSpec<Second>, DynConstraintKind>;
+
+const DEFAULT_ALGORITHM: &str = "est-layered";
+
+fn registry() -> Registry<TaskSpec<Second>, Second> {
+    Registry::with_builtins()
+}
+
+/// [`USAGE`] followed by the registered scheduler names.
+fn usage() -> String {
+    format!(
+        "{USAGE}\n\nAlgorithms: {}",
+        registry().scheduler_names().join(", ")
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    problem: PathBuf,
+    algorithm: String,
+    horizon: Option<(f64, f64)>,
+    seed: Option<u64>,
+    format: Format,
+    output: Option<PathBuf>,
+}
+
+/// Parses the arguments after the program name; `Ok(None)` asks for help.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut problem = None;
+    let mut algorithm = DEFAULT_ALGORITHM.to_string();
+    let mut horizon = None;
+    let mut seed = None;
+    let mut format = None;
+    let mut output: Option<PathBuf> = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.into())),
+            _ => (arg, None),
+        };
+        let mut value = |name: &str| {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("{name} needs a value"))
+        };
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--algorithm" => {
+                algorithm = value("--algorithm")?;
+                if !registry().scheduler_names().contains(&algorithm.as_str()) {
+                    return Err(format!("unknown algorithm '{algorithm}'"));
+                }
+            }
+            "--horizon" => {
+                let text = value("--horizon")?;
+                let bounds = text
+                    .split_once(',')
+                    .and_then(|(s, e)| Some((s.trim().parse().ok()?, e.trim().parse().ok()?)))
+                    .filter(|&(s, e): &(f64, f64)| s <= e)
+                    .ok_or_else(|| format!("invalid horizon '{text}', expected START,END"))?;
+                horizon = Some(bounds);
+            }
+            "--seed" => {
+                let text = value("--seed")?;
+                seed = Some(text.parse().map_err(|_| format!("invalid seed '{text}'"))?);
+            }
+            "--format" => {
+                format = Some(match value("--format")?.as_str() {
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    other => return Err(format!("unknown format '{other}'")),
+                })
+            }
+            "--output" => output = Some(value("--output")?.into()),
+            flag if flag.starts_with('-') => return Err(format!("unknown option '{flag}'")),
+            _ if problem.is_none() => problem = Some(PathBuf::from(flag)),
+            _ => return Err(format!("unexpected argument '{flag}'")),
+        }
+    }
+
+    let format = format.unwrap_or_else(|| match output.as_ref().and_then(|p| p.extension()) {
+        Some(ext) if ext.eq_ignore_ascii_case("csv") => Format::Csv,
+        _ => Format::Json,
+    });
+    Ok(Some(Options {
+        problem: problem.ok_or("missing problem file")?,
+        algorithm,
+        horizon,
+        seed,
+        format,
+        output,
+    }))
+}
+
+/// Output of a run: the rendered schedule and a statistics summary.
+#[derive(Debug)]
+struct Report {
+    output: String,
+    summary: String,
+}
+
+fn run(options: &Options, problem: &str) -> Result<Report, String> {
+    let mut document: Value =
+        serde_json::from_str(problem).map_err(|e| format!("malformed problem: {e}"))?;
+    if let (Some((start, end)), Some(fields)) = (options.horizon, document.as_object_mut()) {
+        fields.insert("horizon".into(), json!({ "start": start, "end": end }));
+    }
+    let problem = Problem::from_json(&document.to_string()).map_err(|e| e.to_string())?;
+    let horizon = problem.horizon;
+    let blocks: [SchedulingBlock<_, Second, _>; 1] =
+        [problem.into_block().map_err(|e| e.to_string())?];
+    let solution_space =
+        SolutionSpace::try_populate(&blocks, horizon).map_err(|e| e.to_string())?;
+
+    let params = options.seed.map(|seed| format!("seed={seed}"));
+    let scheduler = registry()
+        .scheduler(&options.algorithm, params.as_deref().unwrap_or_default())
+        .map_err(|e| e.to_string())?;
+    let started = Instant::now();
+    let schedule = scheduler
+        .try_schedule(&blocks, &solution_space, horizon)
+        .map_err(|e| e.to_string())?;
+    let result = SchedulerResult::new(
+        schedule,
+        &blocks,
+        &solution_space,
+        horizon,
+        None,
+        started.elapsed(),
+    );
+    let stats = ScheduleStats::compute(&result.schedule, &blocks, horizon);
+
+    let output = match options.format {
+        Format::Csv => csv::to_string(&result.schedule),
+        Format::Json => {
+            let unscheduled: Vec<Value> = result
+                .unscheduled()
+                .map(|(id, outcome)| json!({ "task": id, "reason": reason(outcome) }))
+                .collect();
+            let report = json!({
+                "schedule": result.schedule,
+                "stats": {
+                    "scheduled": stats.scheduled_count,
+                    "requested": stats.requested_count,
+                    "utilization": stats.utilization,
+                    "busy_time": stats.busy_time.value(),
+                    "idle_time": stats.idle_time.value(),
+                    "makespan": stats.makespan.map(|m| m.value()),
+                    "priority_weighted_completion": stats.priority_weighted_completion,
+                    "elapsed_ms": result.elapsed.as_secs_f64() * 1000.0,
+                },
+                "unscheduled": unscheduled,
+            });
+            let mut text = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+            text.push('\n');
+            text
+        }
+    };
+    Ok(Report {
+        output,
+        summary: stats.to_string(),
+    })
+}
+
+fn reason(outcome: &TaskOutcome) -> String {
+    match outcome {
+        TaskOutcome::Placed => "placed".into(),
+        TaskOutcome::NoStaticWindow => "no_static_window".into(),
+        TaskOutcome::KilledByDynamicEdge(source) => format!("killed_by_dynamic_edge:{source}"),
+        TaskOutcome::HorizonExhausted => "horizon_exhausted".into(),
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", usage());
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("error: {message}\n\n{}", usage());
+            return ExitCode::from(2);
+        }
+    };
+    let report = std::fs::read_to_string(&options.problem)
+        .map_err(|e| format!("cannot read {}: {e}", options.problem.display()))
+        .and_then(|problem| run(&options, &problem));
+    let written = report.and_then(|report| {
+        if options.format == Format::Csv {
+            eprintln!("{}", report.summary);
+        }
+        match &options.output {
+            Some(path) => std::fs::write(path, report.output)
+                .map_err(|e| format!("cannot write {}: {e}", path.display())),
+            None => {
+                print!("{}", report.output);
+                Ok(())
+            }
+        }
+    });
+    match written {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Result<Option<Options>, String> {
+        parse_args(line.split_whitespace().map(String::from))
+    }
+
+    const PROBLEM: &str = r#"{
+        "version": 1,
+        "tasks": [
+            { "id": "obs", "name": "science", "size": 30.0, "priority": 2 },
+            { "id": "cal", "name": "calib", "size": 10.0 },
+            { "id": "late", "name": "late", "size": 5.0,
+              "constraints": { "start": 500.0, "end": 600.0 } }
+        ],
+        "edges": [ { "from": "cal", "to": "obs", "kind": "dependence" } ]
+    }"#;
+
+    #[test]
+    fn parses_flags_in_both_spellings() {
+        let options = args("p.json --algorithm est --horizon=0,100 --seed 7 --output out.csv")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            options,
+            Options {
+                problem: "p.json".into(),
+                algorithm: "est".into(),
+                horizon: Some((0.0, 100.0)),
+                seed: Some(7),
+                format: Format::Csv,
+                output: Some("out.csv".into()),
+            }
+        );
+        assert_eq!(args("--help").unwrap(), None);
+        let defaults = args("p.json").unwrap().unwrap();
+        assert_eq!(
+            (defaults.algorithm.as_str(), defaults.format),
+            ("est-layered", Format::Json)
+        );
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(args("").unwrap_err().contains("missing problem"));
+        assert!(args("p.json --algorithm magic")
+            .unwrap_err()
+            .contains("magic"));
+        assert!(args("p.json --horizon 10,0")
+            .unwrap_err()
+            .contains("START,END"));
+        assert!(args("p.json --seed").unwrap_err().contains("needs a value"));
+        assert!(args("p.json q.json").unwrap_err().contains("q.json"));
+    }
+
+    #[test]
+    fn writes_schedule_stats_and_reasons_as_json() {
+        let options = args("p.json --horizon 0,100").unwrap().unwrap();
+        let report = run(&options, PROBLEM).unwrap();
+        let out: Value = serde_json::from_str(&report.output).unwrap();
+        assert_eq!(out["schedule"][0]["task"], "cal");
+        assert_eq!(
+            out["schedule"][1]["interval"],
+            json!({ "start": 10.0, "end": 40.0 })
+        );
+        assert_eq!(out["stats"]["scheduled"], 2);
+        assert_eq!(out["stats"]["requested"], 3);
+        assert_eq!(
+            out["unscheduled"],
+            json!([{ "task": "late", "reason": "no_static_window" }])
+        );
+    }
+
+    #[test]
+    fn writes_csv_and_requires_a_horizon() {
+        let options = args("p.json --horizon 0,100 --format csv")
+            .unwrap()
+            .unwrap();
+        let report = run(&options, PROBLEM).unwrap();
+        assert_eq!(report.output, "task_id,start,end\ncal,0,10\nobs,10,40\n");
+        assert!(report.summary.contains("scheduled:    2/3"));
+
+        let options = args("p.json").unwrap().unwrap();
+        assert!(run(&options, PROBLEM).unwrap_err().contains("horizon"));
+    }
+
+    #[test]
+    fn algorithms_come_from_the_registry() {
+        assert!(usage().contains("est, est-layered"));
+        let options = args("p.json --algorithm est --seed 3 --horizon 0,100")
+            .unwrap()
+            .unwrap();
+        let out: Value = serde_json::from_str(&run(&options, PROBLEM).unwrap().output).unwrap();
+        assert_eq!(out["stats"]["scheduled"], 2);
+    }
+}
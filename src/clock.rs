@@ -0,0 +1,33 @@
+//! Wall clock for run timings and time budgets.
+//!
+//! `std::time::Instant::now` panics on `wasm32-unknown-unknown`, which has no
+//! clock without JavaScript bindings. There, [`Instant`] reads zero elapsed
+//! time: reported timings are zero and time budgets never run out, while
+//! iteration budgets still apply.
+
+use std::time::Duration;
+
+/// Drop-in for [`std::time::Instant`], limited to what the schedulers use.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Instant {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    inner: std::time::Instant,
+}
+
+impl Instant {
+    /// The current instant.
+    pub(crate) fn now() -> Self {
+        Self {
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            inner: std::time::Instant::now(),
+        }
+    }
+
+    /// Time since `self`; always zero without a clock.
+    pub(crate) fn elapsed(&self) -> Duration {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        return self.inner.elapsed();
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        return Duration::ZERO;
+    }
+}
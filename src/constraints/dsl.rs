@@ -0,0 +1,429 @@
+//! Textual constraint expressions (`dsl` feature).
+//!
+//! Lets constraint trees live in configuration files instead of Rust code:
+//!
+//! ```text
+//! interval(0, 100) & !interval(40, 60) | periodic(start=0, period=86400, width=3600)
+//! ```
+//!
+//! # Grammar
+//!
+//! ```text
+//! expr    := and ('|' and)*
+//! and     := unary ('&' unary)*
+//! unary   := '!' unary | '(' expr ')' | call
+//! call    := name '(' arg (',' arg)* ')'
+//! arg     := number | name '=' number
+//! ```
+//!
+//! `&` binds tighter than `|`, so the example above reads
+//! `(interval & !interval) | periodic`. Chains of the same operator become
+//! a single [`ConstraintExpr::intersection`] or [`ConstraintExpr::union`]
+//! node. Numbers are in axis units and accept signs, fractions and
+//! exponents.
+//!
+//! | Constraint | Parameters, in positional order |
+//! |------------|---------------------------------|
+//! | `interval` | `start`, `end`                  |
+//! | `periodic` | `start`, `period`, `width`      |
+//!
+//! Positional arguments come first; every parameter is given exactly once.
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use super::{Constraint, ConstraintExpr, IntervalConstraint, PeriodicConstraint};
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::{Quantity, Unit};
+
+/// Leaf constraints the DSL can produce.
+#[derive(Debug, Clone, Copy)]
+pub enum DslLeaf<U: Unit + Send + Sync> {
+    /// `interval(start, end)`.
+    Interval(IntervalConstraint<U>),
+    /// `periodic(start, period, width)`.
+    Periodic(PeriodicConstraint<U>),
+}
+
+impl<U: Unit + Send + Sync> Constraint<U> for DslLeaf<U> {
+    fn compute_intervals(&self, range: Interval<U>) -> IntervalSet<U> {
+        match self {
+            Self::Interval(c) => c.compute_intervals(range),
+            Self::Periodic(c) => c.compute_intervals(range),
+        }
+    }
+
+    fn stringify(&self) -> String {
+        match self {
+            Self::Interval(c) => c.stringify(),
+            Self::Periodic(c) => c.stringify(),
+        }
+    }
+}
+
+/// Why a constraint expression could not be parsed.
+///
+/// Positions are byte offsets into the input.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum DslError {
+    #[error("Expected {expected} at {position}, found {found}")]
+    Unexpected {
+        position: usize,
+        expected: &'static str,
+        found: String,
+    },
+
+    #[error("Unknown constraint `{name}` at {position}")]
+    UnknownConstraint { position: usize, name: String },
+
+    #[error("Invalid arguments to `{name}` at {position}: {reason}")]
+    InvalidArguments {
+        position: usize,
+        name: String,
+        reason: String,
+    },
+}
+
+/// Parses `input` into a constraint tree.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::dsl::parse_constraint;
+/// use virolai::constraints::Constraint;
+/// use virolai::solution_space::Interval;
+/// use qtty::Second;
+///
+/// let expr = parse_constraint::<Second>("interval(0, 100) & !interval(40, 60)").unwrap();
+/// assert_eq!(
+///     expr.compute_intervals(Interval::from_f64(0.0, 200.0)).as_slice(),
+///     &[Interval::from_f64(0.0, 40.0), Interval::from_f64(60.0, 100.0)]
+/// );
+/// ```
+///
+/// # Errors
+///
+/// [`DslError`] pointing at the first offending token.
+pub fn parse_constraint<U: Unit + Send + Sync>(
+    input: &str,
+) -> Result<ConstraintExpr<DslLeaf<U>>, DslError> {
+    let mut parser = Parser { input, pos: 0 };
+    let expr = parser.expr()?;
+    parser.skip_whitespace();
+    if parser.pos < input.len() {
+        return Err(parser.unexpected("`&`, `|` or end of input"));
+    }
+    Ok(expr)
+}
+
+impl<U: Unit + Send + Sync> FromStr for ConstraintExpr<DslLeaf<U>> {
+    type Err = DslError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_constraint(s)
+    }
+}
+
+/// Recursive-descent parser over the raw input.
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn expr<U: Unit + Send + Sync>(&mut self) -> Result<ConstraintExpr<DslLeaf<U>>, DslError> {
+        let mut children = vec![self.and()?];
+        while self.eat('|') {
+            children.push(self.and()?);
+        }
+        Ok(if children.len() == 1 {
+            children.remove(0)
+        } else {
+            ConstraintExpr::union(children)
+        })
+    }
+
+    fn and<U: Unit + Send + Sync>(&mut self) -> Result<ConstraintExpr<DslLeaf<U>>, DslError> {
+        let mut children = vec![self.unary()?];
+        while self.eat('&') {
+            children.push(self.unary()?);
+        }
+        Ok(if children.len() == 1 {
+            children.remove(0)
+        } else {
+            ConstraintExpr::intersection(children)
+        })
+    }
+
+    fn unary<U: Unit + Send + Sync>(&mut self) -> Result<ConstraintExpr<DslLeaf<U>>, DslError> {
+        if self.eat('!') {
+            return Ok(ConstraintExpr::negate(self.unary()?));
+        }
+        if self.eat('(') {
+            let expr = self.expr()?;
+            self.expect(')')?;
+            return Ok(expr);
+        }
+        self.call()
+    }
+
+    fn call<U: Unit + Send + Sync>(&mut self) -> Result<ConstraintExpr<DslLeaf<U>>, DslError> {
+        self.skip_whitespace();
+        let position = self.pos;
+        let name = self
+            .ident()
+            .ok_or_else(|| self.unexpected("a constraint, `!` or `(`"))?;
+        let params: &[&str] = match name {
+            "interval" => &["start", "end"],
+            "periodic" => &["start", "period", "width"],
+            _ => {
+                return Err(DslError::UnknownConstraint {
+                    position,
+                    name: name.to_owned(),
+                })
+            }
+        };
+        let values = self.arguments(name, position, params)?;
+        let invalid = |reason: String| DslError::InvalidArguments {
+            position,
+            name: name.to_owned(),
+            reason,
+        };
+        if let Some(value) = values.iter().find(|v| !v.is_finite()) {
+            return Err(invalid(format!("{value} is not finite")));
+        }
+
+        let leaf = match name {
+            "interval" => {
+                let (start, end) = (values[0], values[1]);
+                if start > end {
+                    return Err(invalid(format!("start {start} is after end {end}")));
+                }
+                DslLeaf::Interval(IntervalConstraint::new(Interval::from_f64(start, end)))
+            }
+            _ => {
+                let (start, period, width) = (values[0], values[1], values[2]);
+                if period <= 0.0 || width <= 0.0 {
+                    return Err(invalid("period and width must be positive".to_owned()));
+                }
+                DslLeaf::Periodic(PeriodicConstraint::new(
+                    Quantity::new(start),
+                    Quantity::new(period),
+                    Quantity::new(width),
+                ))
+            }
+        };
+        Ok(ConstraintExpr::leaf(leaf))
+    }
+
+    /// Parses `(arg, …)` and binds the arguments to `params`, in order.
+    fn arguments(
+        &mut self,
+        name: &str,
+        position: usize,
+        params: &[&str],
+    ) -> Result<Vec<f64>, DslError> {
+        let invalid = |reason: String| DslError::InvalidArguments {
+            position,
+            name: name.to_owned(),
+            reason,
+        };
+        let mut values: Vec<Option<f64>> = vec![None; params.len()];
+        let mut positional = 0;
+        let mut named = false;
+
+        self.expect('(')?;
+        loop {
+            self.skip_whitespace();
+            let checkpoint = self.pos;
+            let key = self.ident().filter(|_| self.eat('='));
+            if key.is_none() {
+                self.pos = checkpoint;
+            }
+            let value = self.number()?;
+            let slot = match key {
+                Some(key) => {
+                    named = true;
+                    params
+                        .iter()
+                        .position(|p| *p == key)
+                        .ok_or_else(|| invalid(format!("unknown parameter `{key}`")))?
+                }
+                None if named => {
+                    return Err(invalid("positional argument after a named one".to_owned()))
+                }
+                None => {
+                    positional += 1;
+                    positional - 1
+                }
+            };
+            match values.get_mut(slot) {
+                Some(Some(_)) => {
+                    return Err(invalid(format!("`{}` given twice", params[slot])));
+                }
+                Some(entry) => *entry = Some(value),
+                None => {
+                    return Err(invalid(format!(
+                        "expected {} arguments, got more",
+                        params.len()
+                    )))
+                }
+            }
+            if !self.eat(',') {
+                break;
+            }
+        }
+        self.expect(')')?;
+
+        values
+            .iter()
+            .zip(params)
+            .map(|(value, param)| value.ok_or_else(|| invalid(format!("missing `{param}`"))))
+            .collect()
+    }
+
+    fn ident(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        let len = rest
+            .char_indices()
+            .find(|&(i, c)| !(c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())))
+            .map_or(rest.len(), |(i, _)| i);
+        (len > 0).then(|| {
+            self.pos += len;
+            &rest[..len]
+        })
+    }
+
+    fn number(&mut self) -> Result<f64, DslError> {
+        self.skip_whitespace();
+        let rest = &self.input[self.pos..];
+        let bytes = rest.as_bytes();
+        let mut len = 0;
+        let mut previous = None;
+        while let Some(&b) = bytes.get(len) {
+            let sign_allowed = len == 0 || matches!(previous, Some(b'e' | b'E'));
+            let accepted = b.is_ascii_digit()
+                || b == b'.'
+                || (b == b'e' || b == b'E') && len > 0
+                || (b == b'-' || b == b'+') && sign_allowed;
+            if !accepted {
+                break;
+            }
+            previous = Some(b);
+            len += 1;
+        }
+        let value = rest[..len]
+            .parse()
+            .map_err(|_| self.unexpected("a number"))?;
+        self.pos += len;
+        Ok(value)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        let matched = self.input[self.pos..].starts_with(c);
+        if matched {
+            self.pos += c.len_utf8();
+        }
+        matched
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), DslError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.unexpected(match c {
+                '(' => "`(`",
+                _ => "`)`",
+            }))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn unexpected(&self, expected: &'static str) -> DslError {
+        let found = self.input[self.pos..]
+            .chars()
+            .next()
+            .map_or_else(|| "end of input".to_owned(), |c| format!("`{c}`"));
+        DslError::Unexpected {
+            position: self.pos,
+            expected,
+            found,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn parse(input: &str) -> Result<ConstraintExpr<DslLeaf<Second>>, DslError> {
+        parse_constraint(input)
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let expr =
+            parse("interval(0,100) & !interval(40,60) | periodic(start=0, period=1000, width=10)")
+                .unwrap();
+        assert!(expr.is_union());
+        assert_eq!(expr.children().unwrap().len(), 2);
+        assert_eq!(
+            expr.compute_intervals(iv(0.0, 2000.0)).as_slice(),
+            &[iv(0.0, 40.0), iv(60.0, 100.0), iv(1000.0, 1010.0)]
+        );
+    }
+
+    #[test]
+    fn parentheses_and_chains() {
+        let expr = parse("!(interval(0, 10) | interval(20, 30) | interval(40, 50))").unwrap();
+        assert!(expr.is_not());
+        assert_eq!(expr.leaf_count(), 3);
+        let expr: ConstraintExpr<DslLeaf<Second>> =
+            "periodic(-5, 1e2, width = 2.5)".parse().unwrap();
+        assert_eq!(
+            expr.compute_intervals(iv(0.0, 100.0)).as_slice(),
+            &[iv(95.0, 97.5)]
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        assert_eq!(
+            parse("interval(0, 10) &").unwrap_err(),
+            DslError::Unexpected {
+                position: 17,
+                expected: "a constraint, `!` or `(`",
+                found: "end of input".into(),
+            }
+        );
+        assert!(matches!(
+            parse("window(0, 1)"),
+            Err(DslError::UnknownConstraint { position: 0, .. })
+        ));
+        assert!(matches!(
+            parse("interval(10, 0)"),
+            Err(DslError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            parse("periodic(start=0, period=10)"),
+            Err(DslError::InvalidArguments { reason, .. }) if reason == "missing `width`"
+        ));
+        assert!(matches!(
+            parse("interval(end=1, 0)"),
+            Err(DslError::InvalidArguments { .. })
+        ));
+        assert!(matches!(
+            parse("interval(0, 1) interval(2, 3)"),
+            Err(DslError::Unexpected { position: 15, .. })
+        ));
+    }
+}
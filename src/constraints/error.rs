@@ -1,13 +1,21 @@
 use thiserror::Error;
 
 /// Errors that can occur during constraint tree operations.
-#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[derive(Debug, Error, Clone, PartialEq)]
 pub enum ConstraintError {
     #[error("Cannot add child to a leaf node")]
     CannotAddChildToLeaf,
 
     #[error("Cannot add child to a NOT node")]
     CannotAddChildToNot,
+
+    /// An interval bound was NaN or infinite, or the start was after the end.
+    #[error("Invalid interval bounds [{start}, {end}): bounds must be finite with start <= end")]
+    InvalidBound { start: f64, end: f64 },
+
+    /// A constraint parameter, such as a lag, was NaN.
+    #[error("Constraint parameter cannot be NaN")]
+    NanParameter,
 }
 
 #[cfg(test)]
@@ -26,6 +34,18 @@ mod tests {
         assert_eq!(e.to_string(), "Cannot add child to a NOT node");
     }
 
+    #[test]
+    fn invalid_bound_display() {
+        let e = ConstraintError::InvalidBound {
+            start: f64::NAN,
+            end: 5.0,
+        };
+        assert_eq!(
+            e.to_string(),
+            "Invalid interval bounds [NaN, 5): bounds must be finite with start <= end"
+        );
+    }
+
     #[test]
     fn error_equality() {
         assert_eq!(
@@ -0,0 +1,149 @@
+//! Dynamic constraints from closures.
+//!
+//! [`FnDynamicConstraint`] wraps a closure of the range, the reference task
+//! and the [`SchedulingContext`], for experiments that would otherwise need
+//! a new [`DynamicConstraint`] type. The closure is type-erased, so edges
+//! built from different closures share one edge type in a
+//! [`SchedulingBlock`](crate::scheduling_block::SchedulingBlock).
+
+use std::fmt;
+use std::sync::Arc;
+
+use super::constraint::{DynamicConstraint, SchedulingContext};
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::Unit;
+
+type EdgeFn<U> = dyn Fn(Interval<U>, &str, &SchedulingContext<U>) -> IntervalSet<U> + Send + Sync;
+
+/// A [`DynamicConstraint`] computed by a closure.
+///
+/// The closure receives the query range, the edge's reference task and the
+/// context, and must honour the [`DynamicConstraint`] contract.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::hard::dynamic::FnDynamicConstraint;
+/// use virolai::constraints::{DynamicConstraint, SchedulingContext};
+/// use virolai::schedule::Schedule;
+/// use virolai::solution_space::{Interval, IntervalSet, SolutionSpace};
+/// use qtty::Second;
+///
+/// // Start at least 5 after the reference ends.
+/// let cooldown = FnDynamicConstraint::new(|range: Interval<Second>, reference: &str, ctx: &SchedulingContext<Second>| {
+///     match ctx.placement(reference) {
+///         Some(done) => range
+///             .intersection(&Interval::new(done.end() + qtty::Quantity::new(5.0), range.end()))
+///             .map_or_else(IntervalSet::new, IntervalSet::from),
+///         None => IntervalSet::new(),
+///     }
+/// });
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add("warmup", Interval::from_f64(0.0, 10.0)).unwrap();
+/// let ss = SolutionSpace::new();
+/// let ctx = SchedulingContext::new(&schedule, &ss);
+/// let windows = cooldown.compute_intervals(Interval::from_f64(0.0, 100.0), "warmup", &ctx);
+/// assert_eq!(windows.as_slice(), &[Interval::from_f64(15.0, 100.0)]);
+/// ```
+pub struct FnDynamicConstraint<U: Unit> {
+    f: Arc<EdgeFn<U>>,
+    name: String,
+}
+
+impl<U: Unit> FnDynamicConstraint<U> {
+    /// Wraps `f`, named `"FnDynamicConstraint"`.
+    pub fn new(
+        f: impl Fn(Interval<U>, &str, &SchedulingContext<U>) -> IntervalSet<U> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            f: Arc::new(f),
+            name: "FnDynamicConstraint".to_string(),
+        }
+    }
+
+    /// Sets the name returned by [`stringify`](DynamicConstraint::stringify).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+impl<U: Unit> Clone for FnDynamicConstraint<U> {
+    fn clone(&self) -> Self {
+        Self {
+            f: Arc::clone(&self.f),
+            name: self.name.clone(),
+        }
+    }
+}
+
+impl<U: Unit> fmt::Debug for FnDynamicConstraint<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnDynamicConstraint")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<U: Unit> DynamicConstraint<U> for FnDynamicConstraint<U> {
+    fn compute_intervals(
+        &self,
+        range: Interval<U>,
+        ref_task_id: &str,
+        ctx: &SchedulingContext<U>,
+    ) -> IntervalSet<U> {
+        (self.f)(range, ref_task_id, ctx)
+    }
+
+    fn stringify(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::DynamicConstraintIndex;
+    use crate::schedule::Schedule;
+    use crate::scheduling_block::SchedulingBlock;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    /// Allowed only while the reference is unplaced.
+    fn before_reference() -> FnDynamicConstraint<Second> {
+        FnDynamicConstraint::new(|range, reference, ctx| {
+            if ctx.is_placed(reference) {
+                IntervalSet::new()
+            } else {
+                IntervalSet::from(range)
+            }
+        })
+        .with_name("before reference")
+    }
+
+    #[test]
+    fn closures_work_as_graph_edges() {
+        let mut block: SchedulingBlock<TestTask, Second, FnDynamicConstraint<Second>> =
+            SchedulingBlock::new();
+        for id in ["a", "b"] {
+            block
+                .add_task_with_id(TestTask::new(id, 1.0), Some(id.into()))
+                .unwrap();
+        }
+        let (a, b) = (block.node_of("a").unwrap(), block.node_of("b").unwrap());
+        block.add_dependency(a, b, before_reference()).unwrap();
+        let blocks = [block];
+        let index = DynamicConstraintIndex::from_blocks(&blocks);
+
+        let ss = SolutionSpace::new();
+        let mut schedule = Schedule::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        assert_eq!(index.evaluate("b", iv(0.0, 10.0), &ctx).unwrap().len(), 1);
+        schedule.add("a", iv(0.0, 1.0)).unwrap();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        assert!(index.evaluate("b", iv(0.0, 10.0), &ctx).unwrap().is_empty());
+        assert_eq!(before_reference().stringify(), "before reference");
+    }
+}
@@ -22,10 +22,39 @@
 //!
 //! The `DynamicConstraint` implementation is a secondary integration path for
 //! algorithms that use the edge-based dynamic constraint evaluation.
+//!
+//! # Group cardinality
+//!
+//! A coalition can also name a **group of tasks** and bound how many of them
+//! end up scheduled: "at least 2 of these 5 pointings", "at most one of the
+//! alternatives". Built with [`CoalitionConstraint::group`]:
+//!
+//! - the upper bound is enforced during the loop: once `at_most` other
+//!   members are placed, the [`DynamicConstraint`] implementation returns no
+//!   window for the remaining ones. Attach it on an edge into every member;
+//!   the edge's reference task is not used.
+//! - the lower bound cannot be forced by a greedy loop; it is checked on the
+//!   finished plan by [`check_cardinality`](CoalitionConstraint::check_cardinality),
+//!   and [`shortfall`](CoalitionConstraint::shortfall) tells a loop how many
+//!   members are still missing.
+//!
+//! Members are counted across every resource schedule of
+//! [`SchedulingContext::resources`], falling back to
+//! [`SchedulingContext::schedule`]; a member placed on several resources
+//! counts once.
+//! [`ESTScheduler::schedule_pool`](crate::algorithms::est::ESTScheduler::schedule_pool)
+//! evaluates the edges against every resource of the pool, so the upper
+//! bound holds across the pool as it is filled.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+use super::constraint::{DynamicConstraint, SchedulingContext};
+use crate::schedule::Schedule;
+use crate::solution_space::{Interval, IntervalSet};
+use crate::Id;
+use qtty::Unit;
+
 /// Requires a minimum number of resources per type to be assigned to a task.
 ///
 /// # Example
@@ -51,6 +80,51 @@ use std::fmt;
 pub struct CoalitionConstraint {
     /// Maps resource type → minimum count required.
     requirements: HashMap<String, u32>,
+    /// Name of the task group bounded by `at_least` / `at_most`.
+    group: Option<String>,
+    members: HashSet<Id>,
+    at_least: u32,
+    at_most: Option<u32>,
+}
+
+/// A group cardinality bound broken by a plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardinalityViolation {
+    /// Fewer members scheduled than `at_least`.
+    TooFew {
+        group: String,
+        scheduled: u32,
+        at_least: u32,
+    },
+    /// More members scheduled than `at_most`.
+    TooMany {
+        group: String,
+        scheduled: u32,
+        at_most: u32,
+    },
+}
+
+impl fmt::Display for CardinalityViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFew {
+                group,
+                scheduled,
+                at_least,
+            } => write!(
+                f,
+                "Group {group} has {scheduled} scheduled, needs at least {at_least}"
+            ),
+            Self::TooMany {
+                group,
+                scheduled,
+                at_most,
+            } => write!(
+                f,
+                "Group {group} has {scheduled} scheduled, allows at most {at_most}"
+            ),
+        }
+    }
 }
 
 impl CoalitionConstraint {
@@ -61,14 +135,144 @@ impl CoalitionConstraint {
                 .into_iter()
                 .map(|(k, v)| (k.into(), v))
                 .collect(),
+            group: None,
+            members: HashSet::new(),
+            at_least: 0,
+            at_most: None,
         }
     }
 
     /// Creates a simple coalition requiring `count` resources of a single type.
     pub fn single_type(resource_type: impl Into<String>, count: u32) -> Self {
-        let mut requirements = HashMap::new();
-        requirements.insert(resource_type.into(), count);
-        Self { requirements }
+        Self::new([(resource_type.into(), count)])
+    }
+
+    /// Creates a constraint over a named group of tasks, with no
+    /// cardinality bounds yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use virolai::constraints::CoalitionConstraint;
+    /// use virolai::schedule::Schedule;
+    /// use virolai::solution_space::Interval;
+    /// use qtty::Second;
+    ///
+    /// let pick = CoalitionConstraint::group("pointings", ["p1", "p2", "p3"])
+    ///     .with_at_least(2)
+    ///     .with_at_most(2);
+    ///
+    /// let mut plan = Schedule::<Second>::new();
+    /// plan.add("p1", Interval::from_f64(0.0, 10.0)).unwrap();
+    /// assert_eq!(pick.shortfall(std::iter::once(&plan)), 1);
+    /// assert!(pick.check_cardinality(std::iter::once(&plan)).is_err());
+    ///
+    /// plan.add("p3", Interval::from_f64(10.0, 20.0)).unwrap();
+    /// assert!(pick.check_cardinality(std::iter::once(&plan)).is_ok());
+    /// ```
+    pub fn group(
+        name: impl Into<String>,
+        members: impl IntoIterator<Item = impl Into<Id>>,
+    ) -> Self {
+        Self {
+            group: Some(name.into()),
+            members: members.into_iter().map(Into::into).collect(),
+            ..Self::new(std::iter::empty::<(String, u32)>())
+        }
+    }
+
+    /// Adds a task to the group.
+    pub fn with_member(mut self, task_id: impl Into<Id>) -> Self {
+        self.members.insert(task_id.into());
+        self
+    }
+
+    /// Requires at least `n` group members to be scheduled.
+    pub fn with_at_least(mut self, n: u32) -> Self {
+        self.at_least = n;
+        self
+    }
+
+    /// Allows at most `n` group members to be scheduled.
+    pub fn with_at_most(mut self, n: u32) -> Self {
+        self.at_most = Some(n);
+        self
+    }
+
+    /// Name of the task group, if this constraint has one.
+    pub fn group_name(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    /// Returns `true` if `task_id` belongs to the group.
+    pub fn is_member(&self, task_id: &str) -> bool {
+        self.members.contains(task_id)
+    }
+
+    pub fn at_least(&self) -> u32 {
+        self.at_least
+    }
+
+    pub fn at_most(&self) -> Option<u32> {
+        self.at_most
+    }
+
+    /// Number of distinct group members placed in `schedules`.
+    pub fn scheduled_count<'a, U: Unit + 'a>(
+        &self,
+        schedules: impl IntoIterator<Item = &'a Schedule<U>>,
+    ) -> u32 {
+        self.placed(schedules, None)
+    }
+
+    /// How many more members must be scheduled to reach `at_least`.
+    pub fn shortfall<'a, U: Unit + 'a>(
+        &self,
+        schedules: impl IntoIterator<Item = &'a Schedule<U>>,
+    ) -> u32 {
+        self.at_least
+            .saturating_sub(self.scheduled_count(schedules))
+    }
+
+    /// Checks both cardinality bounds on a finished plan.
+    pub fn check_cardinality<'a, U: Unit + 'a>(
+        &self,
+        schedules: impl IntoIterator<Item = &'a Schedule<U>>,
+    ) -> Result<(), CardinalityViolation> {
+        let scheduled = self.scheduled_count(schedules);
+        let group = || self.group.clone().unwrap_or_default();
+        if scheduled < self.at_least {
+            return Err(CardinalityViolation::TooFew {
+                group: group(),
+                scheduled,
+                at_least: self.at_least,
+            });
+        }
+        match self.at_most {
+            Some(at_most) if scheduled > at_most => Err(CardinalityViolation::TooMany {
+                group: group(),
+                scheduled,
+                at_most,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Distinct members placed in `schedules`, not counting `except`.
+    fn placed<'a, U: Unit + 'a>(
+        &self,
+        schedules: impl IntoIterator<Item = &'a Schedule<U>>,
+        except: Option<&str>,
+    ) -> u32 {
+        let mut seen = HashSet::new();
+        for schedule in schedules {
+            for id in &self.members {
+                if Some(id.as_str()) != except && schedule.contains_task(id) {
+                    seen.insert(id.as_str());
+                }
+            }
+        }
+        seen.len() as u32
     }
 
     /// Returns the requirement map (resource type → minimum count).
@@ -116,6 +320,38 @@ impl CoalitionConstraint {
     }
 }
 
+impl<U: Unit> DynamicConstraint<U> for CoalitionConstraint {
+    /// Enforces `at_most`: a member is admitted over the whole `range` while
+    /// fewer than `at_most` other members are placed, and nowhere after.
+    /// Non-members and groups without an upper bound are unaffected.
+    fn compute_intervals(
+        &self,
+        range: Interval<U>,
+        _ref_task_id: &str,
+        ctx: &SchedulingContext<U>,
+    ) -> IntervalSet<U> {
+        let Some(at_most) = self.at_most else {
+            return IntervalSet::from(range);
+        };
+        if ctx.target_id.is_some_and(|id| !self.is_member(id)) {
+            return IntervalSet::from(range);
+        }
+        let others = match ctx.resources {
+            Some(resources) => self.placed(resources.values(), ctx.target_id),
+            None => self.placed(std::iter::once(ctx.schedule), ctx.target_id),
+        };
+        if others < at_most {
+            IntervalSet::from(range)
+        } else {
+            IntervalSet::new()
+        }
+    }
+
+    fn stringify(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl fmt::Display for CoalitionConstraint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Coalition(")?;
@@ -127,6 +363,20 @@ impl fmt::Display for CoalitionConstraint {
             write!(f, "{}×{}", count, rtype)?;
             first = false;
         }
+        if let Some(group) = &self.group {
+            if !first {
+                write!(f, ", ")?;
+            }
+            let upper = self
+                .at_most
+                .map_or_else(|| "*".to_string(), |n| n.to_string());
+            write!(
+                f,
+                "{group}: {}..={upper} of {}",
+                self.at_least,
+                self.members.len()
+            )?;
+        }
         write!(f, ")")
     }
 }
@@ -185,6 +435,66 @@ mod tests {
         assert!(c.is_satisfied(&assigned));
     }
 
+    // ── Group cardinality ─────────────────────────────────────────────
+
+    use crate::solution_space::SolutionSpace;
+    use qtty::Second;
+
+    fn iv(a: f64, b: f64) -> Interval<Second> {
+        Interval::from_f64(a, b)
+    }
+
+    #[test]
+    fn at_most_blocks_further_members() {
+        let c = CoalitionConstraint::group("alt", ["a", "b", "c"]).with_at_most(1);
+        let mut schedule = Schedule::new();
+        let ss = SolutionSpace::new();
+
+        let ctx = SchedulingContext::new(&schedule, &ss).with_target_id("b");
+        assert_eq!(c.compute_intervals(iv(0.0, 50.0), "a", &ctx).len(), 1);
+
+        schedule.add("a", iv(0.0, 10.0)).unwrap();
+        let ctx = SchedulingContext::new(&schedule, &ss).with_target_id("b");
+        assert!(c.compute_intervals(iv(0.0, 50.0), "a", &ctx).is_empty());
+
+        // The placed member itself still validates, and outsiders are free.
+        let own = SchedulingContext::new(&schedule, &ss).with_target_id("a");
+        assert_eq!(c.compute_intervals(iv(0.0, 10.0), "b", &own).len(), 1);
+        let outsider = SchedulingContext::new(&schedule, &ss).with_target_id("z");
+        assert_eq!(c.compute_intervals(iv(0.0, 10.0), "a", &outsider).len(), 1);
+    }
+
+    #[test]
+    fn members_count_once_across_resources() {
+        let c = CoalitionConstraint::group("g", ["a", "b"]).with_at_most(1);
+        let mut r1 = Schedule::new();
+        r1.add("a", iv(0.0, 10.0)).unwrap();
+        let mut r2 = Schedule::new();
+        r2.add("a", iv(0.0, 10.0)).unwrap();
+        assert_eq!(c.scheduled_count([&r1, &r2]), 1);
+        assert!(c.check_cardinality([&r1, &r2]).is_ok());
+
+        r2.add("b", iv(20.0, 30.0)).unwrap();
+        assert_eq!(
+            c.check_cardinality([&r1, &r2]),
+            Err(CardinalityViolation::TooMany {
+                group: "g".into(),
+                scheduled: 2,
+                at_most: 1
+            })
+        );
+    }
+
+    #[test]
+    fn at_least_reports_shortfall() {
+        let c = CoalitionConstraint::group("g", ["a", "b", "c"]).with_at_least(2);
+        let empty = Schedule::<Second>::new();
+        assert_eq!(c.shortfall([&empty]), 2);
+        let err = c.check_cardinality([&empty]).unwrap_err();
+        assert!(err.to_string().contains("needs at least 2"));
+        assert_eq!(c.to_string(), "Coalition(g: 2..=* of 3)");
+    }
+
     #[test]
     fn display_format() {
         let c = CoalitionConstraint::single_type("LST", 2);
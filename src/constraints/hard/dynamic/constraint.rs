@@ -8,9 +8,12 @@
 //! See also: [`Constraint`](crate::constraints::Constraint) for the static
 //! counterpart whose windows are fixed before the scheduling loop.
 
+use super::consumable::ConsumableBudget;
 use crate::schedule::Schedule;
 use crate::solution_space::{Interval, IntervalSet, SolutionSpace};
-use qtty::Unit;
+use crate::Id;
+use qtty::{Quantity, Unit};
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 /// Runtime state available to dynamic constraints during evaluation.
@@ -26,6 +29,21 @@ pub struct SchedulingContext<'a, U: Unit> {
     pub schedule: &'a Schedule<U>,
     /// Static solution space (pre-computed from static constraints).
     pub solution_space: &'a SolutionSpace<U>,
+    /// Per-resource schedules, for constraints that span resources
+    /// (`None` when scheduling a single resource).
+    pub resources: Option<&'a HashMap<Id, Schedule<U>>>,
+    /// Duration of the task being evaluated, when known. Constraints on the
+    /// target's end use it to widen their window; without it they fall back
+    /// to a conservative bound on the start.
+    pub target_size: Option<Quantity<U>>,
+    /// ID of the task being evaluated, when known. Set by
+    /// [`DynamicConstraintIndex`](super::DynamicConstraintIndex) and
+    /// [`validate`](crate::schedule::validate()) so constraints over a group
+    /// can tell the target apart from the other members.
+    pub target_id: Option<&'a str>,
+    /// Non-renewable budgets spent by placements, if any. Tasks the budget
+    /// cannot afford are infeasible; see [`target_within_budget`](Self::target_within_budget).
+    pub consumables: Option<&'a ConsumableBudget>,
 }
 
 impl<'a, U: Unit> SchedulingContext<'a, U> {
@@ -34,6 +52,91 @@ impl<'a, U: Unit> SchedulingContext<'a, U> {
         Self {
             schedule,
             solution_space,
+            resources: None,
+            target_size: None,
+            target_id: None,
+            consumables: None,
+        }
+    }
+
+    /// Attaches the schedules of every resource, keyed by resource ID.
+    pub fn with_resources(mut self, resources: &'a HashMap<Id, Schedule<U>>) -> Self {
+        self.resources = Some(resources);
+        self
+    }
+
+    /// Sets the duration of the task being evaluated.
+    pub fn with_target_size(mut self, size: Quantity<U>) -> Self {
+        self.target_size = Some(size);
+        self
+    }
+
+    /// Sets the ID of the task being evaluated.
+    pub fn with_target_id(mut self, task_id: &'a str) -> Self {
+        self.target_id = Some(task_id);
+        self
+    }
+
+    /// Attaches the consumable budgets placements draw on.
+    pub fn with_consumables(mut self, budget: &'a ConsumableBudget) -> Self {
+        self.consumables = Some(budget);
+        self
+    }
+
+    /// Returns `false` if the target task needs more of a consumable than
+    /// the placed tasks leave.
+    ///
+    /// Spending is read from [`resources`](Self::resources) when set, and
+    /// from [`schedule`](Self::schedule) otherwise. Always `true` without
+    /// a budget or a target ID.
+    pub fn target_within_budget(&self) -> bool {
+        match (self.consumables, self.target_id) {
+            (Some(budget), Some(task_id)) => match self.resources {
+                Some(resources) => budget.affords(task_id, resources.values()),
+                None => budget.affords(task_id, [self.schedule]),
+            },
+            _ => true,
+        }
+    }
+
+    /// Schedule `task_id` is placed on: its resource when
+    /// [`resources`](Self::resources) is set, [`schedule`](Self::schedule)
+    /// otherwise. `None` if it is not placed.
+    pub fn schedule_of(&self, task_id: &str) -> Option<&'a Schedule<U>> {
+        match self.resources {
+            Some(resources) => resources.values().find(|s| s.contains_task(task_id)),
+            None => Some(self.schedule).filter(|s| s.contains_task(task_id)),
+        }
+    }
+
+    /// Interval `task_id` is placed over, on whichever schedule holds it.
+    pub fn placement(&self, task_id: &str) -> Option<Interval<U>> {
+        self.schedule_of(task_id)?.get_interval(task_id)
+    }
+
+    /// `true` if `task_id` is placed on any schedule of the context.
+    pub fn is_placed(&self, task_id: &str) -> bool {
+        self.schedule_of(task_id).is_some()
+    }
+
+    /// Stamp that changes whenever `task_id` is added or removed anywhere in
+    /// the context; see [`Schedule::changed_at`].
+    pub fn changed_at(&self, task_id: &str) -> u64 {
+        match self.resources {
+            Some(resources) => resources.values().map(|s| s.changed_at(task_id)).sum(),
+            None => self.schedule.changed_at(task_id),
+        }
+    }
+
+    /// Copy of this context evaluating `task_id`.
+    pub fn for_target<'b>(&'b self, task_id: &'b str) -> SchedulingContext<'b, U> {
+        SchedulingContext {
+            schedule: self.schedule,
+            solution_space: self.solution_space,
+            resources: self.resources,
+            target_size: self.target_size,
+            target_id: Some(task_id),
+            consumables: self.consumables,
         }
     }
 }
@@ -65,12 +168,43 @@ pub trait DynamicConstraint<U: Unit>: Send + Sync + Debug {
     /// Returns a human-readable description of this constraint.
     fn stringify(&self) -> String;
 
+    /// `true` if the result depends only on `range`, the placement of
+    /// `ref_task_id` and `ctx.target_size`.
+    ///
+    /// Lets [`DynamicConstraintIndex::evaluate_memoized`](super::DynamicConstraintIndex::evaluate_memoized)
+    /// reuse a result until the reference task is added or removed.
+    /// Defaults to `false`, which is always safe.
+    fn is_reference_local(&self) -> bool {
+        false
+    }
+
     /// Prints this constraint to stdout.
     fn print(&self) {
         println!("{}", self.stringify());
     }
 }
 
+/// The default edge type of [`SchedulingBlock`](crate::scheduling_block::SchedulingBlock):
+/// an edge that only orders the graph and admits the whole range.
+impl<U: Unit> DynamicConstraint<U> for () {
+    fn compute_intervals(
+        &self,
+        range: Interval<U>,
+        _ref_task_id: &str,
+        _ctx: &SchedulingContext<U>,
+    ) -> IntervalSet<U> {
+        IntervalSet::from(range)
+    }
+
+    fn stringify(&self) -> String {
+        "Unconstrained".to_string()
+    }
+
+    fn is_reference_local(&self) -> bool {
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,4 +218,28 @@ mod tests {
         assert!(ctx.schedule.is_empty());
         assert!(ctx.solution_space.is_empty());
     }
+
+    #[test]
+    fn placements_are_found_on_any_resource() {
+        let blank = Schedule::<Second>::new();
+        let ss = SolutionSpace::<Second>::new();
+        let mut r2 = Schedule::new();
+        r2.add("a", Interval::from_f64(5.0, 10.0)).unwrap();
+        let resources =
+            HashMap::from([("r1".to_string(), Schedule::new()), ("r2".to_string(), r2)]);
+
+        let ctx = SchedulingContext::new(&blank, &ss).with_resources(&resources);
+        assert!(ctx.is_placed("a"));
+        assert_eq!(ctx.placement("a"), Some(Interval::from_f64(5.0, 10.0)));
+        assert!(std::ptr::eq(
+            ctx.schedule_of("a").unwrap(),
+            &resources["r2"]
+        ));
+        assert_eq!(ctx.changed_at("a"), resources["r2"].changed_at("a"));
+        assert!(!ctx.is_placed("b"));
+
+        let single = SchedulingContext::new(&resources["r2"], &ss);
+        assert_eq!(single.placement("a"), Some(Interval::from_f64(5.0, 10.0)));
+        assert!(single.schedule_of("b").is_none());
+    }
 }
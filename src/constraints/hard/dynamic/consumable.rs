@@ -0,0 +1,254 @@
+//! Consumable budgets — non-renewable resources used up by placements.
+//!
+//! Fuel, cryogen or a total exposure allowance are not freed when a task
+//! ends: every placement spends part of a fixed budget, and a task whose
+//! requirement exceeds what is left cannot run at all, whenever it would
+//! start. That is unlike the renewable capacity of
+//! [`ResourceConstraint`](crate::constraints::ResourceConstraint) or
+//! [`MaxParallelism`](super::MaxParallelism), which only limit overlap.
+//!
+//! # Evaluation
+//!
+//! A [`ConsumableBudget`] holds each consumable's total and each task's
+//! requirements. The amount left is derived from the placements, so
+//! [`SchedulingContext`](super::SchedulingContext) only borrows the budget (see
+//! [`with_consumables`](super::SchedulingContext::with_consumables)):
+//!
+//! - [`remaining`](ConsumableBudget::remaining) is the total minus the
+//!   requirements of every placed task;
+//! - [`affords`](ConsumableBudget::affords) checks one task against it;
+//! - [`is_satisfied`](ConsumableBudget::is_satisfied) checks a finished plan.
+//!
+//! [`DynamicConstraintIndex`](super::DynamicConstraintIndex) reads the
+//! budget from the context and returns no window for a task the budget
+//! cannot afford, whether or not it has incoming edges.
+//!
+//! A task placed on several resources at once (a coalition) spends its
+//! requirement once. Consumables without a declared total are unlimited.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::schedule::Schedule;
+use crate::Id;
+use qtty::Unit;
+
+/// Totals of non-renewable resources and what each task spends.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::ConsumableBudget;
+/// use virolai::schedule::Schedule;
+/// use virolai::solution_space::Interval;
+/// use qtty::Second;
+///
+/// let fuel = ConsumableBudget::new()
+///     .with_total("fuel", 10.0)
+///     .with_requirement("burn-1", "fuel", 6.0)
+///     .with_requirement("burn-2", "fuel", 6.0);
+///
+/// let mut schedule = Schedule::<Second>::new();
+/// assert!(fuel.affords("burn-2", [&schedule]));
+///
+/// schedule.add("burn-1", Interval::from_f64(0.0, 10.0)).unwrap();
+/// assert_eq!(fuel.remaining("fuel", [&schedule]), 4.0);
+/// assert!(!fuel.affords("burn-2", [&schedule]));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConsumableBudget {
+    totals: HashMap<String, f64>,
+    requirements: HashMap<Id, HashMap<String, f64>>,
+}
+
+impl ConsumableBudget {
+    /// Creates a budget with no consumables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares `total` units of `consumable`.
+    ///
+    /// # Panics
+    ///
+    /// If `total` is negative or not finite.
+    pub fn with_total(mut self, consumable: impl Into<String>, total: f64) -> Self {
+        assert!(
+            total >= 0.0 && total.is_finite(),
+            "consumable totals must be non-negative and finite"
+        );
+        self.totals.insert(consumable.into(), total);
+        self
+    }
+
+    /// Records that placing `task_id` spends `amount` units of `consumable`.
+    ///
+    /// # Panics
+    ///
+    /// If `amount` is negative or not finite.
+    pub fn with_requirement(
+        mut self,
+        task_id: impl Into<Id>,
+        consumable: impl Into<String>,
+        amount: f64,
+    ) -> Self {
+        assert!(
+            amount >= 0.0 && amount.is_finite(),
+            "consumable requirements must be non-negative and finite"
+        );
+        self.requirements
+            .entry(task_id.into())
+            .or_default()
+            .insert(consumable.into(), amount);
+        self
+    }
+
+    /// Declared total of `consumable`, if limited.
+    pub fn total(&self, consumable: &str) -> Option<f64> {
+        self.totals.get(consumable).copied()
+    }
+
+    /// Units of `consumable` that `task_id` spends.
+    pub fn requirement(&self, task_id: &str, consumable: &str) -> f64 {
+        self.requirements
+            .get(task_id)
+            .and_then(|needs| needs.get(consumable))
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Units of `consumable` spent by the tasks placed in `schedules`.
+    pub fn spent<'s, U: Unit + 's>(
+        &self,
+        consumable: &str,
+        schedules: impl IntoIterator<Item = &'s Schedule<U>>,
+    ) -> f64 {
+        placed(schedules)
+            .iter()
+            .map(|id| self.requirement(id, consumable))
+            .sum()
+    }
+
+    /// Units of `consumable` left after the placements in `schedules`.
+    ///
+    /// Infinite for a consumable without a declared total. Negative when
+    /// the placements overspend.
+    pub fn remaining<'s, U: Unit + 's>(
+        &self,
+        consumable: &str,
+        schedules: impl IntoIterator<Item = &'s Schedule<U>>,
+    ) -> f64 {
+        match self.total(consumable) {
+            Some(total) => total - self.spent(consumable, schedules),
+            None => f64::INFINITY,
+        }
+    }
+
+    /// Returns `true` if every requirement of `task_id` fits in what the
+    /// other placed tasks leave.
+    ///
+    /// A placement of `task_id` itself in `schedules` is not counted, so
+    /// the check also holds for a task that is already placed.
+    pub fn affords<'s, U: Unit + 's>(
+        &self,
+        task_id: &str,
+        schedules: impl IntoIterator<Item = &'s Schedule<U>>,
+    ) -> bool {
+        let Some(needs) = self.requirements.get(task_id) else {
+            return true;
+        };
+        let mut others = placed(schedules);
+        others.remove(task_id);
+        needs.iter().all(|(consumable, &need)| {
+            let Some(total) = self.total(consumable) else {
+                return true;
+            };
+            let spent: f64 = others
+                .iter()
+                .map(|id| self.requirement(id, consumable))
+                .sum();
+            need <= total - spent
+        })
+    }
+
+    /// Returns `true` if no consumable is overspent.
+    pub fn is_satisfied<'s, U: Unit + 's>(
+        &self,
+        schedules: impl IntoIterator<Item = &'s Schedule<U>>,
+    ) -> bool {
+        let placed = placed(schedules);
+        self.totals.iter().all(|(consumable, &total)| {
+            let spent: f64 = placed
+                .iter()
+                .map(|id| self.requirement(id, consumable))
+                .sum();
+            spent <= total
+        })
+    }
+}
+
+/// IDs of the tasks placed in any of `schedules`, each once.
+fn placed<'s, U: Unit + 's>(schedules: impl IntoIterator<Item = &'s Schedule<U>>) -> HashSet<Id> {
+    schedules
+        .into_iter()
+        .flat_map(|schedule| schedule.iter().map(|(id, _)| id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn budget() -> ConsumableBudget {
+        ConsumableBudget::new()
+            .with_total("fuel", 10.0)
+            .with_total("cryogen", 3.0)
+            .with_requirement("a", "fuel", 4.0)
+            .with_requirement("b", "fuel", 4.0)
+            .with_requirement("b", "cryogen", 1.0)
+            .with_requirement("c", "fuel", 3.0)
+            .with_requirement("c", "exposure", 1e9)
+    }
+
+    fn schedule(ids: &[&str]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for (i, id) in ids.iter().enumerate() {
+            let start = 10.0 * i as f64;
+            s.add(*id, iv(start, start + 5.0)).unwrap();
+        }
+        s
+    }
+
+    #[test]
+    fn placements_spend_the_budget() {
+        let b = budget();
+        let s = schedule(&["a", "b"]);
+        assert_eq!(b.spent("fuel", [&s]), 8.0);
+        assert_eq!(b.remaining("fuel", [&s]), 2.0);
+        assert_eq!(b.remaining("cryogen", [&s]), 2.0);
+        // Undeclared consumables are unlimited.
+        assert_eq!(b.remaining("exposure", [&s]), f64::INFINITY);
+
+        assert!(!b.affords("c", [&s]));
+        assert!(b.affords("c", [&schedule(&["a"])]));
+        // Tasks without requirements are always affordable.
+        assert!(b.affords("x", [&s]));
+    }
+
+    #[test]
+    fn own_and_duplicate_placements_count_once() {
+        let b = budget();
+        let s = schedule(&["a", "b"]);
+        assert!(b.affords("b", [&s]));
+        assert!(b.is_satisfied([&s]));
+
+        // A coalition placement on two resources spends once.
+        let other = schedule(&["b"]);
+        assert_eq!(b.spent("fuel", [&s, &other]), 8.0);
+
+        let over = schedule(&["a", "b", "c"]);
+        assert!(!b.is_satisfied([&over]));
+        assert!(!b.affords("c", [&over]));
+    }
+}
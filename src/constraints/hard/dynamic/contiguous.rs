@@ -0,0 +1,293 @@
+//! Contiguous group constraint — keeps a named set of tasks in one block.
+//!
+//! This is a **hard + dynamic** constraint over a *group* of tasks that must
+//! run back to back: the tiles of a mosaic, the parts of a multi-part scan.
+//! Members that are scheduled must form one contiguous block on the
+//! timeline, with no foreign task between the first and the last of them.
+//! Idle time inside the block is allowed; members left unscheduled are not
+//! required. No set of pairwise edges expresses this, because whether a
+//! member may go somewhere depends on where all the others already are.
+//!
+//! # Evaluation
+//!
+//! The constraint reads a single timeline, [`SchedulingContext::schedule`]:
+//!
+//! - [`span`](ContiguousGroup::span) is the hull of the placed members;
+//! - [`available`](ContiguousGroup::available) returns where a task may go
+//!   without breaking the block — for a member, between the foreign tasks
+//!   on either side of the span; for any other task, outside the span;
+//! - [`intruders`](ContiguousGroup::intruders) and
+//!   [`is_satisfied`](ContiguousGroup::is_satisfied) check a finished plan.
+//!
+//! The [`DynamicConstraint`] implementation takes the candidate from
+//! [`SchedulingContext::target_id`], treating an unnamed target as a member.
+//! Attach it on an edge into every member, and into any other task that
+//! could be placed inside the block; the edge's reference task is not used.
+//! Alternatively, declare it on the block with
+//! [`SchedulingBlock::add_contiguous`](crate::scheduling_block::SchedulingBlock::add_contiguous):
+//! the EST scheduler then evaluates it for every task, with no edges.
+//! Milestones occupy no time and never break a block.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use super::constraint::{DynamicConstraint, SchedulingContext};
+use crate::schedule::Schedule;
+use crate::solution_space::{Interval, IntervalSet};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+/// Scheduled members of a group form one block with no foreign task inside.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::ContiguousGroup;
+/// use virolai::schedule::Schedule;
+/// use virolai::solution_space::Interval;
+/// use qtty::Second;
+///
+/// let mosaic = ContiguousGroup::new("mosaic").with_members(["m1", "m2", "m3"]);
+///
+/// let mut plan = Schedule::<Second>::new();
+/// plan.add("calib", Interval::from_f64(0.0, 10.0)).unwrap();
+/// plan.add("m1", Interval::from_f64(20.0, 30.0)).unwrap();
+/// plan.add("survey", Interval::from_f64(50.0, 60.0)).unwrap();
+///
+/// // The next tile must fit between `calib` and `survey`.
+/// let windows = mosaic.available(Interval::from_f64(0.0, 100.0), "m2", &plan);
+/// assert_eq!(windows.as_slice(), &[Interval::from_f64(10.0, 50.0)]);
+///
+/// plan.add("m2", Interval::from_f64(70.0, 80.0)).unwrap();
+/// assert_eq!(mosaic.intruders(&plan), vec!["survey".to_string()]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContiguousGroup {
+    name: String,
+    members: HashSet<Id>,
+}
+
+impl ContiguousGroup {
+    /// Creates a group called `name` with no members yet.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            members: HashSet::new(),
+        }
+    }
+
+    /// Adds a task to the group.
+    pub fn with_member(mut self, task_id: impl Into<Id>) -> Self {
+        self.members.insert(task_id.into());
+        self
+    }
+
+    /// Adds several tasks to the group.
+    pub fn with_members(mut self, task_ids: impl IntoIterator<Item = impl Into<Id>>) -> Self {
+        self.members.extend(task_ids.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `true` if `task_id` belongs to the group.
+    pub fn contains(&self, task_id: &str) -> bool {
+        self.members.contains(task_id)
+    }
+
+    /// Members of the group, in no particular order.
+    pub fn members(&self) -> impl Iterator<Item = &str> + '_ {
+        self.members.iter().map(String::as_str)
+    }
+
+    pub(crate) fn remove_member(&mut self, task_id: &str) {
+        self.members.remove(task_id);
+    }
+
+    /// From the start of the first placed member to the end of the last.
+    ///
+    /// `None` while no member occupies time.
+    pub fn span<U: Unit>(&self, schedule: &Schedule<U>) -> Option<Interval<U>> {
+        self.span_without(schedule, None)
+    }
+
+    /// Foreign tasks placed inside the [`span`](Self::span), in time order.
+    pub fn intruders<U: Unit>(&self, schedule: &Schedule<U>) -> Vec<Id> {
+        let Some(span) = self.span(schedule) else {
+            return Vec::new();
+        };
+        schedule
+            .iter()
+            .filter(|(id, placed)| {
+                !placed.is_empty() && !self.contains(id) && placed.overlaps(&span)
+            })
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Returns `true` if no foreign task sits inside the block.
+    pub fn is_satisfied<U: Unit>(&self, schedule: &Schedule<U>) -> bool {
+        self.intruders(schedule).is_empty()
+    }
+
+    /// Parts of `range` where `task_id` may be placed without breaking the
+    /// block.
+    ///
+    /// A placement of `task_id` itself in `schedule` is ignored.
+    pub fn available<U: Unit>(
+        &self,
+        range: Interval<U>,
+        task_id: &str,
+        schedule: &Schedule<U>,
+    ) -> IntervalSet<U> {
+        self.available_for(range, Some(task_id), schedule)
+    }
+
+    fn available_for<U: Unit>(
+        &self,
+        range: Interval<U>,
+        task_id: Option<&str>,
+        schedule: &Schedule<U>,
+    ) -> IntervalSet<U> {
+        let Some(span) = self.span_without(schedule, task_id) else {
+            return IntervalSet::from(range);
+        };
+        if task_id.is_some_and(|id| !self.contains(id)) {
+            return IntervalSet::from(span).complement(range);
+        }
+
+        // Between the nearest foreign tasks on either side of the block.
+        let (mut lo, mut hi) = (range.start(), range.end());
+        for (id, placed) in schedule.iter() {
+            if placed.is_empty() || self.contains(&id) || Some(id.as_str()) == task_id {
+                continue;
+            }
+            if placed.end() <= span.start() {
+                lo = lo.max(placed.end());
+            } else if placed.start() >= span.end() {
+                hi = hi.min(placed.start());
+            }
+        }
+        if lo < hi {
+            IntervalSet::from(Interval::new(lo, hi))
+        } else {
+            IntervalSet::new()
+        }
+    }
+
+    fn span_without<U: Unit>(
+        &self,
+        schedule: &Schedule<U>,
+        skip: Option<&str>,
+    ) -> Option<Interval<U>> {
+        schedule
+            .iter()
+            .filter(|(id, placed)| {
+                !placed.is_empty() && self.contains(id) && Some(id.as_str()) != skip
+            })
+            .map(|(_, placed)| (placed.start().value(), placed.end().value()))
+            .reduce(|(a, b), (c, d)| (a.min(c), b.max(d)))
+            .map(|(start, end)| Interval::new(Quantity::new(start), Quantity::new(end)))
+    }
+}
+
+impl fmt::Display for ContiguousGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Contiguous({})", self.name)
+    }
+}
+
+impl<U: Unit> DynamicConstraint<U> for ContiguousGroup {
+    fn compute_intervals(
+        &self,
+        range: Interval<U>,
+        _ref_task_id: &str,
+        ctx: &SchedulingContext<U>,
+    ) -> IntervalSet<U> {
+        self.available_for(range, ctx.target_id, ctx.schedule)
+    }
+
+    fn stringify(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn plan(tasks: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in tasks {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    fn mosaic() -> ContiguousGroup {
+        ContiguousGroup::new("mosaic").with_members(["m1", "m2", "m3"])
+    }
+
+    #[test]
+    fn members_stay_between_foreign_neighbours() {
+        let s = plan(&[
+            ("x", 0.0, 10.0),
+            ("m1", 20.0, 30.0),
+            ("m3", 35.0, 40.0),
+            ("y", 60.0, 70.0),
+        ]);
+        assert_eq!(mosaic().span(&s), Some(iv(20.0, 40.0)));
+        assert_eq!(
+            mosaic().available(iv(0.0, 100.0), "m2", &s).as_slice(),
+            &[iv(10.0, 60.0)]
+        );
+        // Foreign tasks keep out of the block.
+        assert_eq!(
+            mosaic().available(iv(0.0, 100.0), "z", &s).as_slice(),
+            &[iv(0.0, 20.0), iv(40.0, 100.0)]
+        );
+        assert!(mosaic().is_satisfied(&s));
+    }
+
+    #[test]
+    fn empty_group_leaves_the_range_open() {
+        let s = plan(&[("x", 0.0, 10.0), ("m1", 10.0, 10.0)]);
+        assert_eq!(mosaic().span(&s), None);
+        assert_eq!(
+            mosaic().available(iv(0.0, 100.0), "m2", &s).as_slice(),
+            &[iv(0.0, 100.0)]
+        );
+    }
+
+    #[test]
+    fn intruders_are_reported_in_order() {
+        let s = plan(&[
+            ("m1", 0.0, 10.0),
+            ("x", 10.0, 20.0),
+            ("y", 25.0, 30.0),
+            ("m2", 30.0, 40.0),
+            ("tick", 35.0, 35.0),
+        ]);
+        assert_eq!(mosaic().intruders(&s), vec!["x".to_string(), "y".into()]);
+        assert!(!mosaic().is_satisfied(&s));
+    }
+
+    #[test]
+    fn dynamic_evaluation_ignores_the_target_placement() {
+        let s = plan(&[("m1", 0.0, 10.0), ("x", 10.0, 20.0), ("m2", 40.0, 50.0)]);
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&s, &ss);
+
+        // Re-checking `m2` sees only `m1`, so it must stay before `x`.
+        let windows = mosaic().compute_intervals(iv(0.0, 100.0), "any", &ctx.for_target("m2"));
+        assert_eq!(windows.as_slice(), &[iv(0.0, 10.0)]);
+        let windows = mosaic().compute_intervals(iv(0.0, 100.0), "any", &ctx.for_target("x"));
+        assert_eq!(windows.as_slice(), &[iv(50.0, 100.0)]);
+        assert_eq!(mosaic().to_string(), "Contiguous(mosaic)");
+    }
+}
@@ -22,14 +22,64 @@
 //! [`compute_effective_intervals()`](DynamicConstraintIndex::compute_effective_intervals)
 //! to obtain the combined valid intervals for a task after all dynamic
 //! constraints are applied.
+//!
+//! # Incremental maintenance
+//!
+//! Re-evaluating every incoming edge of every candidate after each placement
+//! is wasteful: placing task `A` can only change the feasible intervals of
+//! tasks that have an edge *from* `A`. After seeding the index with the static
+//! solution space via [`with_incremental()`](DynamicConstraintIndex::with_incremental),
+//! each call to [`apply_placement()`](DynamicConstraintIndex::apply_placement)
+//! records the placement and recomputes only the effective interval sets of
+//! `A`'s successors. The cached result is read back with
+//! [`effective_intervals()`](DynamicConstraintIndex::effective_intervals).
+//!
+//! # Memoization
+//!
+//! Constraints that report [`is_reference_local`](DynamicConstraint::is_reference_local)
+//! only change when their source task is added to or removed from the
+//! schedule. [`evaluate_memoized()`](DynamicConstraintIndex::evaluate_memoized)
+//! caches each such edge's result together with the source's
+//! [`changed_at`](Schedule::changed_at) stamp and reuses it while the stamp,
+//! range and target size are unchanged, so an iteration only recomputes the
+//! edges whose source moved.
+//!
+//! # Buffer reuse
+//!
+//! Per-edge results live only until they are intersected. The memoized and
+//! incremental paths draw their working sets from an internal
+//! [`IntervalPool`] and return them to it once combined, so steady-state
+//! iterations reuse buffers instead of allocating. Sets returned by
+//! [`evaluate_memoized()`](DynamicConstraintIndex::evaluate_memoized) can be
+//! handed back with [`recycle()`](DynamicConstraintIndex::recycle) once the
+//! caller is done with them.
+//!
+//! # Consumables
+//!
+//! When the context carries a [`ConsumableBudget`](super::ConsumableBudget),
+//! the evaluation methods first check that the target task is affordable
+//! and report no feasible window otherwise. The incremental cache is built
+//! from its own context and does not see budgets.
+//!
+//! # Key type
+//!
+//! The index is generic over the task key `I` ([`Id`] by default). Indexes
+//! keyed by other types are built with
+//! [`from_edges()`](DynamicConstraintIndex::from_edges) and support the
+//! structural queries; evaluation goes through [`DynamicConstraint`], whose
+//! reference task is a string ID, and is therefore available for [`Id`] keys.
 
 use super::constraint::{DynamicConstraint, SchedulingContext};
+use crate::schedule::errors::ScheduleError;
+use crate::schedule::Schedule;
 use crate::scheduling_block::{SchedulingBlock, Task};
-use crate::solution_space::{Interval, IntervalSet};
-use crate::Id;
+use crate::solution_space::{Interval, IntervalPool, IntervalSet, PoolStats, SolutionSpace};
+use crate::{Id, TaskKey};
 use petgraph::visit::{EdgeRef, IntoEdgeReferences};
-use qtty::Unit;
+use qtty::{Second, Unit};
+use std::borrow::Borrow;
 use std::collections::HashMap;
+use std::hash::Hash;
 
 /// Pre-built index mapping target task IDs to their incoming dynamic constraints.
 ///
@@ -41,12 +91,44 @@ use std::collections::HashMap;
 ///
 /// Borrows edge data from the blocks, so the index lives as long as the blocks.
 #[derive(Debug)]
-pub struct DynamicConstraintIndex<'a, D> {
+pub struct DynamicConstraintIndex<'a, D, U: Unit = Second, I: TaskKey = Id> {
     /// `target_task_id → Vec<(source_task_id, &constraint)>`
-    edges: HashMap<Id, Vec<(Id, &'a D)>>,
+    edges: HashMap<I, Vec<(I, &'a D)>>,
+    /// `source_task_id → Vec<target_task_id>` (reverse of `edges`).
+    successors: HashMap<I, Vec<I>>,
+    /// Placements recorded through [`apply_placement`](Self::apply_placement).
+    placements: Schedule<U, I>,
+    /// Cached state for incremental maintenance, if enabled.
+    incremental: Option<Incremental<U, I>>,
+    /// `target_task_id → per-edge result` for [`evaluate_memoized`](Self::evaluate_memoized),
+    /// parallel to `edges`.
+    memo: HashMap<I, Vec<Option<Memo<U>>>>,
+    /// Recycled buffers for transient per-edge and effective sets.
+    pool: IntervalPool<U>,
+    /// Edges computed by [`evaluate_memoized`](Self::evaluate_memoized).
+    evaluated: usize,
+}
+
+/// One edge result cached by [`DynamicConstraintIndex::evaluate_memoized`].
+#[derive(Debug)]
+struct Memo<U: Unit> {
+    /// [`Schedule::changed_at`] of the source when computed.
+    stamp: u64,
+    range: Interval<U>,
+    target_size: Option<f64>,
+    result: IntervalSet<U>,
+}
+
+/// Static inputs and cached effective intervals for incremental maintenance.
+#[derive(Debug)]
+struct Incremental<U: Unit, I: TaskKey = Id> {
+    static_space: SolutionSpace<U, I>,
+    range: Interval<U>,
+    /// `target_task_id → static ∩ dynamic` as of the last relevant placement.
+    effective: HashMap<I, IntervalSet<U>>,
 }
 
-impl<'a, D> DynamicConstraintIndex<'a, D> {
+impl<'a, D, U: Unit> DynamicConstraintIndex<'a, D, U> {
     /// Builds an index from one or more scheduling blocks.
     ///
     /// Walks every edge in every block and records `(source_id, &edge_data)`
@@ -55,33 +137,48 @@ impl<'a, D> DynamicConstraintIndex<'a, D> {
     /// # Complexity
     ///
     /// O(total edges across all blocks).
-    pub fn from_blocks<T, U, E>(blocks: &'a [SchedulingBlock<T, U, D, E>]) -> Self
+    pub fn from_blocks<T, E>(blocks: &'a [SchedulingBlock<T, U, D, E>]) -> Self
     where
         T: Task<U>,
-        U: Unit,
         E: petgraph::EdgeType,
     {
-        let mut edges: HashMap<Id, Vec<(Id, &'a D)>> = HashMap::new();
-
-        for block in blocks {
-            let graph = block.graph();
-            for edge_ref in graph.edge_references() {
-                let source_node = edge_ref.source();
-                let target_node = edge_ref.target();
-
+        let edges = blocks.iter().flat_map(|block| {
+            block.graph().edge_references().filter_map(move |edge_ref| {
                 // Resolve node indices → task IDs via the block's ID map.
-                if let (Some(source_id), Some(target_id)) =
-                    (block.id_of(source_node), block.id_of(target_node))
-                {
-                    edges
-                        .entry(target_id.to_owned())
-                        .or_default()
-                        .push((source_id.to_owned(), edge_ref.weight()));
-                }
-            }
-        }
+                let source_id = block.id_of(edge_ref.source())?;
+                let target_id = block.id_of(edge_ref.target())?;
+                Some((
+                    source_id.to_owned(),
+                    target_id.to_owned(),
+                    edge_ref.weight(),
+                ))
+            })
+        });
+        Self::from_edges(edges)
+    }
+}
 
-        Self { edges }
+impl<'a, D, U: Unit, I: TaskKey> DynamicConstraintIndex<'a, D, U, I> {
+    /// Builds an index from `(source_id, target_id, &constraint)` triples.
+    ///
+    /// # Complexity
+    ///
+    /// O(number of edges).
+    pub fn from_edges(edges: impl IntoIterator<Item = (I, I, &'a D)>) -> Self {
+        let mut index = Self::default();
+        for (source_id, target_id, constraint) in edges {
+            index
+                .edges
+                .entry(target_id.clone())
+                .or_default()
+                .push((source_id.clone(), constraint));
+            index
+                .successors
+                .entry(source_id)
+                .or_default()
+                .push(target_id);
+        }
+        index
     }
 
     /// Returns the number of target tasks that have dynamic constraints.
@@ -90,28 +187,55 @@ impl<'a, D> DynamicConstraintIndex<'a, D> {
     }
 
     /// Returns `true` if `task_id` has any incoming dynamic constraint edges.
-    pub fn has_constraints(&self, task_id: &str) -> bool {
+    pub fn has_constraints<Q>(&self, task_id: &Q) -> bool
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.edges.contains_key(task_id)
     }
 
     /// Returns the incoming constraint edges for a target task, if any.
-    pub fn get_edges(&self, task_id: &str) -> Option<&[(Id, &'a D)]> {
+    pub fn get_edges<Q>(&self, task_id: &Q) -> Option<&[(I, &'a D)]>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.edges.get(task_id).map(|v| v.as_slice())
     }
+
+    /// Returns the IDs of tasks with an incoming edge from `task_id`.
+    pub fn successors<Q>(&self, task_id: &Q) -> &[I]
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.successors
+            .get(task_id)
+            .map(|v| v.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the placements recorded via [`apply_placement`](Self::apply_placement).
+    pub fn placements(&self) -> &Schedule<U, I> {
+        &self.placements
+    }
 }
 
-impl<'a, D> DynamicConstraintIndex<'a, D> {
+impl<'a, D, U: Unit> DynamicConstraintIndex<'a, D, U> {
     /// Evaluates all incoming dynamic constraints for `task_id` and returns
     /// their **intersection** (AND-composition).
     ///
     /// Returns `None` if `task_id` has no dynamic constraints — this lets the
     /// caller skip unnecessary intersection with the static solution space.
+    /// A task that the context's [`ConsumableBudget`](super::ConsumableBudget)
+    /// cannot afford gets an empty set, with or without edges.
     ///
     /// # Complexity
     ///
     /// O(k · C) where k = number of incoming edges and C = cost of a single
     /// constraint evaluation (typically O(1) for built-in kinds).
-    pub fn evaluate<U>(
+    pub fn evaluate(
         &self,
         task_id: &str,
         range: Interval<U>,
@@ -119,20 +243,204 @@ impl<'a, D> DynamicConstraintIndex<'a, D> {
     ) -> Option<IntervalSet<U>>
     where
         D: DynamicConstraint<U>,
-        U: Unit,
     {
+        let ctx = &ctx.for_target(task_id);
+        if !ctx.target_within_budget() {
+            return Some(IntervalSet::new());
+        }
         let incoming = self.edges.get(task_id)?;
         if incoming.is_empty() {
             return None;
         }
 
-        let result = incoming
+        #[cfg(feature = "trace")]
+        tracing::trace!(
+            task = task_id,
+            edges = incoming.len(),
+            "dynamic edges evaluated"
+        );
+        let results: Vec<_> = incoming
             .iter()
             .map(|(source_id, constraint)| constraint.compute_intervals(range, source_id, ctx))
-            .reduce(|acc, v| crate::constraints::operations::compute_intersection(&acc, &v))
-            .unwrap_or_default();
+            .collect();
+
+        Some(crate::constraints::operations::compute_intersection_many(
+            &results,
+        ))
+    }
+
+    /// Same as [`evaluate`](Self::evaluate), reusing cached edge results.
+    ///
+    /// Edges whose constraint is [reference-local](DynamicConstraint::is_reference_local)
+    /// are recomputed only when their source was added or removed since the
+    /// last call, or when `range` or `ctx.target_size` differ; other edges
+    /// are evaluated every time.
+    ///
+    /// Stamps come from [`SchedulingContext::changed_at`], so the cache
+    /// assumes successive calls see one evolving schedule, or one evolving
+    /// set of resources. Call [`clear_memo`](Self::clear_memo) before
+    /// switching to an unrelated one.
+    pub fn evaluate_memoized(
+        &mut self,
+        task_id: &str,
+        range: Interval<U>,
+        ctx: &SchedulingContext<U>,
+    ) -> Option<IntervalSet<U>>
+    where
+        D: DynamicConstraint<U>,
+    {
+        let ctx = &ctx.for_target(task_id);
+        if !ctx.target_within_budget() {
+            return Some(IntervalSet::new());
+        }
+        let incoming = self.edges.get(task_id)?;
+        if incoming.is_empty() {
+            return None;
+        }
+
+        let target_size = ctx.target_size.map(|q| q.value());
+        let memo = match self.memo.get_mut(task_id) {
+            Some(memo) => memo,
+            None => self
+                .memo
+                .entry(task_id.to_owned())
+                .or_insert_with(|| (0..incoming.len()).map(|_| None).collect()),
+        };
+        let mut results = Vec::with_capacity(incoming.len());
+        for ((source_id, constraint), slot) in incoming.iter().zip(memo.iter_mut()) {
+            let v = if constraint.is_reference_local() {
+                let stamp = ctx.changed_at(source_id);
+                let fresh = slot.as_ref().is_some_and(|m| {
+                    m.stamp == stamp && m.range == range && m.target_size == target_size
+                });
+                if !fresh {
+                    #[cfg(feature = "trace")]
+                    tracing::trace!(task = task_id, source = %source_id, "dynamic edge evaluated");
+                    self.evaluated += 1;
+                    *slot = Some(Memo {
+                        stamp,
+                        range,
+                        target_size,
+                        result: constraint.compute_intervals(range, source_id, ctx),
+                    });
+                }
+                slot.as_ref()
+                    .map(|m| self.pool.copy_of(&m.result))
+                    .unwrap_or_default()
+            } else {
+                self.evaluated += 1;
+                constraint.compute_intervals(range, source_id, ctx)
+            };
+            results.push(v);
+        }
+        let combined = crate::constraints::operations::compute_intersection_many_pooled(
+            &results,
+            &mut self.pool,
+        );
+        for set in results {
+            self.pool.recycle(set);
+        }
+        Some(combined)
+    }
 
-        Some(result)
+    /// Drops every result cached by [`evaluate_memoized`](Self::evaluate_memoized).
+    pub fn clear_memo(&mut self) {
+        self.memo.clear();
+    }
+
+    /// Hands a set obtained from this index back to its buffer pool.
+    pub fn recycle(&mut self, set: IntervalSet<U>) {
+        self.pool.recycle(set);
+    }
+
+    /// Edges [`evaluate_memoized`](Self::evaluate_memoized) has computed so
+    /// far; results reused from the cache are not counted.
+    pub fn edges_evaluated(&self) -> usize {
+        self.evaluated
+    }
+
+    /// Buffer reuse counters for the memoized and incremental paths.
+    pub fn pool_stats(&self) -> PoolStats {
+        self.pool.stats()
+    }
+
+    /// Evaluates the incoming constraints of `task_id` restricted to `window`.
+    ///
+    /// Equivalent to [`evaluate`](Self::evaluate) over the full range followed
+    /// by an intersection with `window`, provided the edge constraints are
+    /// *local* (`f(w) = f(range) ∩ w`, which holds for every
+    /// [`DynConstraintKind`](super::DynConstraintKind)). Evaluation stops
+    /// early once the running intersection becomes empty.
+    pub fn evaluate_window(
+        &self,
+        task_id: &str,
+        window: Interval<U>,
+        ctx: &SchedulingContext<U>,
+    ) -> Option<IntervalSet<U>>
+    where
+        D: DynamicConstraint<U>,
+    {
+        let ctx = &ctx.for_target(task_id);
+        if !ctx.target_within_budget() {
+            return Some(IntervalSet::new());
+        }
+        let incoming = self.edges.get(task_id)?;
+        if incoming.is_empty() {
+            return None;
+        }
+
+        #[cfg(feature = "trace")]
+        tracing::trace!(
+            task = task_id,
+            edges = incoming.len(),
+            "dynamic edges evaluated"
+        );
+        let mut acc = IntervalSet::from(window);
+        for (source_id, constraint) in incoming {
+            let v = constraint.compute_intervals(window, source_id, ctx);
+            acc = crate::constraints::operations::compute_intersection(&acc, &v);
+            if acc.is_empty() {
+                break;
+            }
+        }
+        Some(acc)
+    }
+
+    /// Returns `true` if placing `task_id` at `placement` satisfies all of its
+    /// incoming dynamic constraints.
+    ///
+    /// Each edge is evaluated only over `placement` and must leave it fully
+    /// feasible; the first failing edge short-circuits. Tasks without incoming
+    /// edges are admitted unless the context's consumable budget cannot
+    /// afford them. Assumes local edge constraints, as
+    /// [`evaluate_window`](Self::evaluate_window) does.
+    ///
+    /// # Complexity
+    ///
+    /// O(k · C) worst case, with no intersection of intermediate results.
+    pub fn admits(&self, task_id: &str, placement: Interval<U>, ctx: &SchedulingContext<U>) -> bool
+    where
+        D: DynamicConstraint<U>,
+    {
+        let ctx = &ctx.for_target(task_id);
+        if !ctx.target_within_budget() {
+            return false;
+        }
+        let Some(incoming) = self.edges.get(task_id) else {
+            return true;
+        };
+        #[cfg(feature = "trace")]
+        tracing::trace!(
+            task = task_id,
+            edges = incoming.len(),
+            "dynamic edges evaluated"
+        );
+        incoming.iter().all(|(source_id, constraint)| {
+            constraint
+                .compute_intervals(placement, source_id, ctx)
+                .iter()
+                .any(|iv| iv.start() <= placement.start() && iv.end() >= placement.end())
+        })
     }
 
     /// Computes the **effective** intervals for a task by intersecting the
@@ -147,7 +455,7 @@ impl<'a, D> DynamicConstraintIndex<'a, D> {
     /// * `static_intervals` — pre-computed intervals from static constraints
     /// * `range` — the current scheduling horizon / query range
     /// * `ctx` — current scheduling context (partial schedule + solution space)
-    pub fn compute_effective_intervals<U>(
+    pub fn compute_effective_intervals(
         &self,
         task_id: &str,
         static_intervals: &IntervalSet<U>,
@@ -156,7 +464,6 @@ impl<'a, D> DynamicConstraintIndex<'a, D> {
     ) -> IntervalSet<U>
     where
         D: DynamicConstraint<U>,
-        U: Unit,
     {
         match self.evaluate(task_id, range, ctx) {
             Some(dynamic_intervals) => crate::constraints::operations::compute_intersection(
@@ -166,12 +473,151 @@ impl<'a, D> DynamicConstraintIndex<'a, D> {
             None => static_intervals.clone(),
         }
     }
+
+    /// Enables incremental maintenance against a static solution space.
+    ///
+    /// The static intervals are copied into the index and the effective
+    /// intervals of every constrained task are computed once against the
+    /// placements recorded so far (none, for a fresh index). Subsequent
+    /// [`apply_placement`](Self::apply_placement) calls keep them current.
+    pub fn with_incremental(mut self, solution_space: &SolutionSpace<U>, range: Interval<U>) -> Self
+    where
+        D: DynamicConstraint<U>,
+    {
+        let mut state = Incremental {
+            static_space: solution_space.clone(),
+            range,
+            effective: HashMap::new(),
+        };
+        let targets: Vec<Id> = self.edges.keys().cloned().collect();
+        let mut pool = std::mem::take(&mut self.pool);
+        for target in targets {
+            let effective = self.recompute(&target, &state, &mut pool);
+            state.effective.insert(target, effective);
+        }
+        self.pool = pool;
+        self.incremental = Some(state);
+        self
+    }
+
+    /// Records that `task_id` was placed at `interval` and refreshes the
+    /// effective intervals of its successors.
+    ///
+    /// Only tasks with an edge from `task_id` are re-evaluated; every other
+    /// cached set is left untouched. Without [`with_incremental`](Self::with_incremental)
+    /// the placement is recorded but no intervals are cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`ScheduleError`] from recording the placement (duplicate
+    /// task, overlap with an earlier placement, NaN bounds). The cache is not
+    /// modified in that case.
+    ///
+    /// # Complexity
+    ///
+    /// O(Σ k_s · C) over the successors `s`, where k_s is the in-degree of `s`.
+    pub fn apply_placement(
+        &mut self,
+        task_id: impl Into<Id>,
+        interval: Interval<U>,
+    ) -> Result<(), ScheduleError>
+    where
+        D: DynamicConstraint<U>,
+    {
+        let task_id = task_id.into();
+        self.placements.add(task_id.clone(), interval)?;
+
+        let Some(mut state) = self.incremental.take() else {
+            return Ok(());
+        };
+        let mut pool = std::mem::take(&mut self.pool);
+        for target in self.successors(&task_id).to_vec() {
+            let effective = self.recompute(&target, &state, &mut pool);
+            if let Some(stale) = state.effective.insert(target, effective) {
+                pool.recycle(stale);
+            }
+        }
+        self.pool = pool;
+        self.incremental = Some(state);
+        Ok(())
+    }
+
+    /// Returns the current effective intervals for `task_id`.
+    ///
+    /// Constrained tasks return their cached static ∩ dynamic set; tasks
+    /// without incoming edges return their static intervals. Returns `None`
+    /// if incremental maintenance is disabled or the task is unknown to the
+    /// static solution space and has no edges.
+    pub fn effective_intervals(&self, task_id: &str) -> Option<&IntervalSet<U>> {
+        let state = self.incremental.as_ref()?;
+        state
+            .effective
+            .get(task_id)
+            .or_else(|| state.static_space.get_intervals(task_id))
+    }
+
+    /// Evaluates `target` against the recorded placements.
+    ///
+    /// Same result as [`compute_effective_intervals`](Self::compute_effective_intervals),
+    /// but intersects the static set with every edge result in one pass and
+    /// cycles all buffers through `pool`.
+    fn recompute(
+        &self,
+        target: &str,
+        state: &Incremental<U>,
+        pool: &mut IntervalPool<U>,
+    ) -> IntervalSet<U>
+    where
+        D: DynamicConstraint<U>,
+    {
+        let fallback;
+        let static_intervals = match state.static_space.get_intervals(target) {
+            Some(set) => set,
+            None => {
+                fallback = IntervalSet::from(state.range);
+                &fallback
+            }
+        };
+        let Some(incoming) = self.edges.get(target).filter(|e| !e.is_empty()) else {
+            return pool.copy_of(static_intervals);
+        };
+
+        #[cfg(feature = "trace")]
+        tracing::trace!(
+            task = target,
+            edges = incoming.len(),
+            "dynamic edges evaluated"
+        );
+        let ctx = SchedulingContext::new(&self.placements, &state.static_space);
+        let ctx = &ctx.for_target(target);
+        let results: Vec<_> = incoming
+            .iter()
+            .map(|(source_id, constraint)| {
+                constraint.compute_intervals(state.range, source_id, ctx)
+            })
+            .collect();
+        let lists: Vec<&[Interval<U>]> = std::iter::once(static_intervals.as_slice())
+            .chain(results.iter().map(IntervalSet::as_slice))
+            .collect();
+        let effective =
+            crate::constraints::operations::compute_intersection_many_pooled(&lists, pool);
+        for set in results {
+            pool.recycle(set);
+        }
+        effective
+    }
 }
 
-impl<'a, D> Default for DynamicConstraintIndex<'a, D> {
+impl<'a, D, U: Unit, I: TaskKey> Default for DynamicConstraintIndex<'a, D, U, I> {
     fn default() -> Self {
         Self {
             edges: HashMap::new(),
+            successors: HashMap::new(),
+            placements: Schedule::default(),
+            incremental: None,
+            memo: HashMap::new(),
+            pool: IntervalPool::new(),
+            evaluated: 0,
         }
     }
 }
@@ -405,4 +851,300 @@ mod tests {
         assert_eq!(result[0], iv(10.0, 50.0));
         assert_eq!(result[1], iv(60.0, 90.0));
     }
+
+    // ── apply_placement ───────────────────────────────────────────────
+
+    /// A → B (Consecutive), A → C (Dependence), D unconstrained.
+    fn chain_blocks() -> Vec<SchedulingBlock<TestTask, Second, DynConstraintKind>> {
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        for id in ["A", "B", "C", "D"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let a = block.node_of("A").unwrap();
+        let b = block.node_of("B").unwrap();
+        let c = block.node_of("C").unwrap();
+        block
+            .add_dependency(a, b, DynConstraintKind::Consecutive)
+            .unwrap();
+        block
+            .add_dependency(a, c, DynConstraintKind::Dependence)
+            .unwrap();
+        vec![block]
+    }
+
+    fn chain_space() -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for id in ["A", "B", "C", "D"] {
+            ss.set_intervals(id.to_string(), vec![iv(0.0, 40.0), iv(60.0, 100.0)]);
+        }
+        ss
+    }
+
+    #[test]
+    fn incremental_seed_blocks_targets_until_source_placed() {
+        let blocks = chain_blocks();
+        let ss = chain_space();
+        let index =
+            DynamicConstraintIndex::from_blocks(&blocks).with_incremental(&ss, iv(0.0, 100.0));
+
+        assert!(index.effective_intervals("B").unwrap().is_empty());
+        assert!(index.effective_intervals("C").unwrap().is_empty());
+        assert_eq!(index.effective_intervals("A").unwrap().len(), 2);
+        assert_eq!(index.effective_intervals("D").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn apply_placement_updates_successors() {
+        let blocks = chain_blocks();
+        let ss = chain_space();
+        let mut index =
+            DynamicConstraintIndex::from_blocks(&blocks).with_incremental(&ss, iv(0.0, 100.0));
+
+        index.apply_placement("A", iv(20.0, 30.0)).unwrap();
+
+        assert_eq!(
+            index.effective_intervals("B").unwrap(),
+            &vec![iv(30.0, 40.0), iv(60.0, 100.0)]
+        );
+        assert_eq!(
+            index.effective_intervals("C").unwrap(),
+            &vec![iv(0.0, 40.0), iv(60.0, 100.0)]
+        );
+        assert!(index.placements().contains_task("A"));
+    }
+
+    #[test]
+    fn apply_placement_matches_full_recomputation() {
+        let blocks = chain_blocks();
+        let ss = chain_space();
+        let mut index =
+            DynamicConstraintIndex::from_blocks(&blocks).with_incremental(&ss, iv(0.0, 100.0));
+        index.apply_placement("A", iv(65.0, 75.0)).unwrap();
+        index.apply_placement("D", iv(0.0, 10.0)).unwrap();
+
+        let mut schedule = Schedule::new();
+        schedule.add("A", iv(65.0, 75.0)).unwrap();
+        schedule.add("D", iv(0.0, 10.0)).unwrap();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        for id in ["A", "B", "C", "D"] {
+            let full = index.compute_effective_intervals(
+                id,
+                ss.get_intervals(id).unwrap(),
+                iv(0.0, 100.0),
+                &ctx,
+            );
+            assert_eq!(index.effective_intervals(id).unwrap(), &full, "task {id}");
+        }
+    }
+
+    #[test]
+    fn apply_placement_rejects_overlap_and_keeps_cache() {
+        let blocks = chain_blocks();
+        let ss = chain_space();
+        let mut index =
+            DynamicConstraintIndex::from_blocks(&blocks).with_incremental(&ss, iv(0.0, 100.0));
+        index.apply_placement("D", iv(20.0, 30.0)).unwrap();
+
+        assert!(index.apply_placement("A", iv(25.0, 35.0)).is_err());
+        assert!(index.effective_intervals("B").unwrap().is_empty());
+    }
+
+    #[test]
+    fn apply_placement_without_incremental_only_records() {
+        let blocks = chain_blocks();
+        let mut index = DynamicConstraintIndex::from_blocks(&blocks);
+        index.apply_placement("A", iv(0.0, 10.0)).unwrap();
+
+        assert_eq!(index.placements().len(), 1);
+        assert!(index.effective_intervals("B").is_none());
+        assert_eq!(index.successors("A").len(), 2);
+        assert!(index.successors("B").is_empty());
+    }
+
+    // ── custom keys ───────────────────────────────────────────────────
+
+    #[test]
+    fn from_edges_with_integer_keys() {
+        let dep = DynConstraintKind::Dependence;
+        let index: DynamicConstraintIndex<'_, DynConstraintKind, Second, u32> =
+            DynamicConstraintIndex::from_edges([(1, 2, &dep), (1, 3, &dep), (2, 3, &dep)]);
+
+        assert_eq!(index.target_count(), 2);
+        assert!(index.has_constraints(&3));
+        assert!(!index.has_constraints(&1));
+        assert_eq!(index.get_edges(&3).unwrap().len(), 2);
+        assert_eq!(index.successors(&1), &[2, 3]);
+    }
+
+    // ── window-scoped evaluation ──────────────────────────────────────
+
+    #[test]
+    fn evaluate_window_matches_full_evaluation_restricted() {
+        let blocks = chain_blocks();
+        let index = DynamicConstraintIndex::from_blocks(&blocks);
+        let mut schedule = Schedule::new();
+        schedule.add("A", iv(20.0, 30.0)).unwrap();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+
+        let window = iv(25.0, 50.0);
+        let scoped = index.evaluate_window("B", window, &ctx).unwrap();
+        let full = index.evaluate("B", iv(0.0, 100.0), &ctx).unwrap();
+        assert_eq!(scoped, full.intersection(&IntervalSet::from(window)));
+        assert_eq!(scoped, vec![iv(30.0, 50.0)]);
+        assert!(index.evaluate_window("D", window, &ctx).is_none());
+    }
+
+    #[test]
+    fn admits_checks_placement_against_edges() {
+        let blocks = chain_blocks();
+        let index = DynamicConstraintIndex::from_blocks(&blocks);
+        let mut schedule = Schedule::new();
+        schedule.add("A", iv(20.0, 30.0)).unwrap();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+
+        assert!(index.admits("B", iv(30.0, 40.0), &ctx));
+        assert!(!index.admits("B", iv(25.0, 35.0), &ctx));
+        assert!(index.admits("C", iv(0.0, 10.0), &ctx));
+        assert!(index.admits("D", iv(0.0, 10.0), &ctx));
+    }
+
+    #[test]
+    fn admits_rejects_when_source_absent() {
+        let blocks = chain_blocks();
+        let index = DynamicConstraintIndex::from_blocks(&blocks);
+        let schedule = Schedule::new();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+
+        assert!(!index.admits("B", iv(50.0, 60.0), &ctx));
+        assert!(index
+            .evaluate_window("B", iv(50.0, 60.0), &ctx)
+            .unwrap()
+            .is_empty());
+    }
+
+    // ── memoized evaluation ───────────────────────────────────────────
+
+    /// `Consecutive` that counts its evaluations.
+    #[derive(Debug, Default)]
+    struct Counting(std::sync::atomic::AtomicUsize);
+
+    impl DynamicConstraint<Second> for Counting {
+        fn compute_intervals(
+            &self,
+            range: Interval<Second>,
+            ref_task_id: &str,
+            ctx: &SchedulingContext<Second>,
+        ) -> IntervalSet<Second> {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            DynConstraintKind::Consecutive.compute_intervals(range, ref_task_id, ctx)
+        }
+
+        fn stringify(&self) -> String {
+            "Counting".into()
+        }
+
+        fn is_reference_local(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn evaluate_memoized_recomputes_only_changed_sources() {
+        let edge = Counting::default();
+        let mut index: DynamicConstraintIndex<'_, Counting, Second> =
+            DynamicConstraintIndex::from_edges([("A".to_string(), "B".to_string(), &edge)]);
+        let ss = SolutionSpace::new();
+        let mut schedule = Schedule::new();
+        let evaluations = || edge.0.load(std::sync::atomic::Ordering::Relaxed);
+
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        assert!(index
+            .evaluate_memoized("B", iv(0.0, 100.0), &ctx)
+            .unwrap()
+            .is_empty());
+        assert_eq!(evaluations(), 1);
+
+        // Unrelated placements keep the cached result.
+        schedule.add("X", iv(50.0, 60.0)).unwrap();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        index.evaluate_memoized("B", iv(0.0, 100.0), &ctx);
+        assert_eq!(evaluations(), 1);
+
+        schedule.add("A", iv(0.0, 10.0)).unwrap();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        let result = index.evaluate_memoized("B", iv(0.0, 100.0), &ctx).unwrap();
+        assert_eq!(result, index.evaluate("B", iv(0.0, 100.0), &ctx).unwrap());
+        assert_eq!(result[0], iv(10.0, 100.0));
+        assert_eq!(evaluations(), 3);
+
+        // A different range is a miss.
+        index.evaluate_memoized("B", iv(0.0, 50.0), &ctx);
+        assert_eq!(evaluations(), 4);
+
+        index.clear_memo();
+        index.evaluate_memoized("B", iv(0.0, 50.0), &ctx);
+        assert_eq!(evaluations(), 5);
+        // The plain `evaluate` above bypasses both the cache and the count.
+        assert_eq!(index.edges_evaluated(), 4);
+        assert!(index.evaluate_memoized("A", iv(0.0, 50.0), &ctx).is_none());
+    }
+
+    #[test]
+    fn unaffordable_targets_have_no_window() {
+        let blocks = chain_blocks();
+        let index = DynamicConstraintIndex::from_blocks(&blocks);
+        let budget = crate::constraints::ConsumableBudget::new()
+            .with_total("fuel", 5.0)
+            .with_requirement("A", "fuel", 3.0)
+            .with_requirement("B", "fuel", 3.0)
+            .with_requirement("D", "fuel", 3.0);
+
+        let mut schedule = Schedule::new();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss).with_consumables(&budget);
+        assert!(index.evaluate("D", iv(0.0, 100.0), &ctx).is_none());
+        assert!(index.admits("D", iv(0.0, 10.0), &ctx));
+
+        schedule.add("A", iv(0.0, 10.0)).unwrap();
+        let ctx = SchedulingContext::new(&schedule, &ss).with_consumables(&budget);
+        // B has an edge from A, D has none; both are now out of budget.
+        assert!(index
+            .evaluate("B", iv(0.0, 100.0), &ctx)
+            .unwrap()
+            .is_empty());
+        assert!(index
+            .evaluate("D", iv(0.0, 100.0), &ctx)
+            .unwrap()
+            .is_empty());
+        assert!(index
+            .evaluate_window("B", iv(10.0, 20.0), &ctx)
+            .unwrap()
+            .is_empty());
+        assert!(!index.admits("D", iv(10.0, 20.0), &ctx));
+    }
+
+    #[test]
+    fn evaluate_memoized_reuses_recycled_buffers() {
+        let blocks = chain_blocks();
+        let ss = chain_space();
+        let mut index = DynamicConstraintIndex::from_blocks(&blocks);
+        let mut schedule = Schedule::new();
+        schedule.add("A", iv(20.0, 30.0)).unwrap();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+
+        for _ in 0..10 {
+            let result = index.evaluate_memoized("B", iv(0.0, 100.0), &ctx).unwrap();
+            assert_eq!(result, vec![iv(30.0, 100.0)]);
+            index.recycle(result);
+        }
+        let stats = index.pool_stats();
+        assert!(stats.allocated <= 2, "{stats:?}");
+        assert!(stats.reused >= 18, "{stats:?}");
+    }
 }
@@ -5,15 +5,53 @@
 //!
 //! # Variants
 //!
-//! | Kind          | Meaning                                                      |
-//! |---------------|--------------------------------------------------------------|
-//! | `Dependence`  | Target task schedulable **only if** reference task is placed |
-//! | `Consecutive` | Target task schedulable **only after** reference task ends   |
-//! | `Exclusive`   | Target task schedulable **only if** reference task is absent |
+//! | Kind             | Meaning                                                      |
+//! |------------------|--------------------------------------------------------------|
+//! | `Dependence`     | Target task schedulable **only if** reference task is placed |
+//! | `Consecutive`    | Target task schedulable **only after** reference task ends   |
+//! | `Exclusive`      | Target task schedulable **only if** reference task is absent |
+//! | `Simultaneous`   | Target task schedulable **only during** reference task       |
+//! | `StartToStart`   | Target starts **no earlier than** reference start + lag      |
+//! | `FinishToFinish` | Target ends **no earlier than** reference end + lag          |
+//! | `StartToFinish`  | Target ends **no earlier than** reference start + lag        |
+//! | `MaxWait`        | Target starts **within** `max_wait` of reference end         |
+//! | `Chained`        | Target **directly follows** the reference, within `max_gap`  |
+//! | `Disjoint`       | Target **never overlaps** the reference                      |
+//!
+//! `Consecutive` is the classical finish-to-start relation without lag; the
+//! other three complete the precedence relations of project networks. Lags are
+//! in axis units and may be negative (leads). Relations on the target's end
+//! use [`SchedulingContext::target_size`] to derive the earliest start; when
+//! the size is unknown they require the target to start after the bound,
+//! which never admits a violating placement.
+//!
+//! `MaxWait` is `Consecutive` with a deadline: "use it or lose it" for
+//! targets that consume something the reference produces, such as a sample
+//! that degrades. Once the reference has ended for longer than `max_wait`
+//! the target can no longer be scheduled at all. Its window ends at
+//! `a_end + max_wait + d`; with the size unknown, only a milestone fits.
+//!
+//! `Chained` tightens `MaxWait` for targets that must directly follow their
+//! reference, such as calibration frames after their science exposure: no
+//! other task may be placed between the two, so the target must also end
+//! before the next task that starts after the reference.
+//!
+//! Parameters are [`Offset`]s: plain axis values that are never NaN, so
+//! kinds can be compared, hashed and used as map keys.
+//!
+//! When [`SchedulingContext::resources`] is set, the reference is looked up
+//! on whichever resource holds it, and `Chained` keeps other tasks out of
+//! the gap on that resource only. `Disjoint` avoids the reference on every
+//! resource, so two tasks sharing a detector or a crew stay apart even when
+//! they are scheduled on different machines.
 
 use super::constraint::{DynamicConstraint, SchedulingContext};
+use crate::constraints::ConstraintError;
+use crate::schedule::Schedule;
 use crate::solution_space::{Interval, IntervalSet};
-use qtty::Unit;
+use qtty::{Quantity, Unit};
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -37,9 +75,13 @@ use serde::{Deserialize, Serialize};
 ///
 /// // "task B can only be scheduled if task A is NOT scheduled"
 /// block.add_dependency(node_a, node_b, DynConstraintKind::Exclusive);
+///
+/// // "task B must run while task A runs, for at least 5 units"
+/// block.add_dependency(node_a, node_b, DynConstraintKind::simultaneous(5.0));
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum DynConstraintKind {
     /// Target is schedulable **only if** the reference task has been placed.
@@ -63,6 +105,263 @@ pub enum DynConstraintKind {
     /// - Reference task absent → full `range` is valid
     /// - Reference task scheduled → empty (target is excluded)
     Exclusive,
+
+    /// Target **overlaps** the reference task by at least `min_overlap`.
+    ///
+    /// Used to coordinate tasks on different resources, such as two
+    /// instruments observing the same event. Without a minimum the overlap
+    /// must be as long as possible: a target shorter than the reference runs
+    /// inside it, a longer one covers it.
+    ///
+    /// - Reference task scheduled at `[a_start, a_end)`, target size `d`,
+    ///   required overlap `r` (`min_overlap`, or `min(d, a_end - a_start)`
+    ///   without one) → valid window is
+    ///   `[a_start + r - d, a_end - r + d) ∩ range`, empty if `r` exceeds
+    ///   `d` or the reference's duration
+    /// - Target size unknown → `[a_start, a_end) ∩ range`, if at least
+    ///   `min_overlap` long
+    /// - Reference task absent → empty
+    Simultaneous {
+        #[cfg_attr(feature = "serde", serde(default))]
+        min_overlap: Offset,
+    },
+
+    /// Target starts **no earlier than** `lag` after the reference starts.
+    ///
+    /// - Reference task scheduled at `[a_start, a_end)` →
+    ///   valid window is `[max(range.start, a_start + lag), range.end)`
+    /// - Reference task absent → empty
+    StartToStart {
+        #[cfg_attr(feature = "serde", serde(default))]
+        lag: Offset,
+    },
+
+    /// Target ends **no earlier than** `lag` after the reference ends.
+    ///
+    /// - Reference task scheduled at `[a_start, a_end)`, target size `d` →
+    ///   valid window is `[max(range.start, a_end + lag - d), range.end)`
+    /// - Reference task absent → empty
+    FinishToFinish {
+        #[cfg_attr(feature = "serde", serde(default))]
+        lag: Offset,
+    },
+
+    /// Target ends **no earlier than** `lag` after the reference starts.
+    ///
+    /// - Reference task scheduled at `[a_start, a_end)`, target size `d` →
+    ///   valid window is `[max(range.start, a_start + lag - d), range.end)`
+    /// - Reference task absent → empty
+    StartToFinish {
+        #[cfg_attr(feature = "serde", serde(default))]
+        lag: Offset,
+    },
+
+    /// Target starts **after** the reference ends and **no later than**
+    /// `max_wait` after that.
+    ///
+    /// - Reference task scheduled at `[a_start, a_end)`, target size `d` →
+    ///   valid window is `[max(range.start, a_end), min(range.end, a_end + max_wait + d))`
+    /// - Reference task absent → empty
+    MaxWait {
+        #[cfg_attr(feature = "serde", serde(default))]
+        max_wait: Offset,
+    },
+
+    /// Target **directly follows** the reference: it starts after the
+    /// reference ends, at most `max_gap` later, and no other task is placed
+    /// in between.
+    ///
+    /// - Reference task scheduled at `[a_start, a_end)`, target size `d`,
+    ///   next task placed after the reference starting at `n` →
+    ///   valid window is `[max(range.start, a_end), min(range.end, a_end + max_gap + d, n))`
+    /// - Reference task absent → empty
+    Chained {
+        #[cfg_attr(feature = "serde", serde(default))]
+        max_gap: Offset,
+    },
+
+    /// Target **never overlaps** the reference task, on any resource.
+    ///
+    /// Generated by
+    /// [`SchedulingBlock::add_exclusion`](crate::scheduling_block::SchedulingBlock::add_exclusion)
+    /// between members of a mutual-exclusion group.
+    ///
+    /// - Reference task placed at `[a_start, a_end)` → `range` minus that
+    ///   interval, for each of its placements
+    /// - Reference task absent → full `range` is valid
+    Disjoint,
+}
+
+impl DynConstraintKind {
+    /// [`Simultaneous`](Self::Simultaneous) with a minimum overlap;
+    /// negative or NaN values mean no minimum.
+    pub fn simultaneous(min_overlap: f64) -> Self {
+        Self::Simultaneous {
+            min_overlap: Offset::non_negative(min_overlap),
+        }
+    }
+
+    /// [`StartToStart`](Self::StartToStart) with `lag`; NaN means no lag.
+    pub fn start_to_start(lag: f64) -> Self {
+        Self::StartToStart {
+            lag: Offset::or_zero(lag),
+        }
+    }
+
+    /// [`FinishToFinish`](Self::FinishToFinish) with `lag`; NaN means no lag.
+    pub fn finish_to_finish(lag: f64) -> Self {
+        Self::FinishToFinish {
+            lag: Offset::or_zero(lag),
+        }
+    }
+
+    /// [`StartToFinish`](Self::StartToFinish) with `lag`; NaN means no lag.
+    pub fn start_to_finish(lag: f64) -> Self {
+        Self::StartToFinish {
+            lag: Offset::or_zero(lag),
+        }
+    }
+
+    /// [`MaxWait`](Self::MaxWait) allowing up to `max_wait`; negative or NaN
+    /// values mean the target must start as the reference ends.
+    pub fn max_wait(max_wait: f64) -> Self {
+        Self::MaxWait {
+            max_wait: Offset::non_negative(max_wait),
+        }
+    }
+
+    /// [`Chained`](Self::Chained) allowing a gap of up to `max_gap`;
+    /// negative or NaN values mean the target must start as the reference
+    /// ends.
+    pub fn chained(max_gap: f64) -> Self {
+        Self::Chained {
+            max_gap: Offset::non_negative(max_gap),
+        }
+    }
+}
+
+/// A parameter of a [`DynConstraintKind`] in axis units — a lag, a wait, a
+/// gap or an overlap. Never NaN, so kinds are [`Eq`] and [`Hash`].
+///
+/// Serialized as a plain number; NaN is rejected on deserialization.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::hard::dynamic::kinds::Offset;
+///
+/// assert_eq!(Offset::new(2.5).unwrap().value(), 2.5);
+/// assert!(Offset::new(f64::NAN).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f64", into = "f64"))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Offset(f64);
+
+impl Offset {
+    /// Zero offset.
+    pub const ZERO: Self = Self(0.0);
+
+    /// Wraps `value`.
+    ///
+    /// # Errors
+    ///
+    /// [`ConstraintError::NanParameter`] if `value` is NaN.
+    pub fn new(value: f64) -> Result<Self, ConstraintError> {
+        if value.is_nan() {
+            Err(ConstraintError::NanParameter)
+        } else {
+            Ok(Self(value))
+        }
+    }
+
+    /// The offset in axis units.
+    pub const fn value(self) -> f64 {
+        self.0
+    }
+
+    /// `value`, with NaN read as zero.
+    fn or_zero(value: f64) -> Self {
+        Self::new(value).unwrap_or(Self::ZERO)
+    }
+
+    /// `value` clamped to zero from below, with NaN read as zero.
+    fn non_negative(value: f64) -> Self {
+        if value > 0.0 {
+            Self(value)
+        } else {
+            Self::ZERO
+        }
+    }
+
+    fn quantity<U: Unit>(self) -> Quantity<U> {
+        Quantity::new(self.0)
+    }
+}
+
+impl TryFrom<f64> for Offset {
+    type Error = ConstraintError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<Offset> for f64 {
+    fn from(offset: Offset) -> f64 {
+        offset.0
+    }
+}
+
+// Never NaN, so equality is reflexive.
+impl Eq for Offset {}
+
+impl Hash for Offset {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Normalise -0.0 so equal values hash alike.
+        (self.0 + 0.0).to_bits().hash(state);
+    }
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Start of the first task other than the target that occupies time in
+/// `[after, before)` of `schedule`.
+fn next_start_after<U: Unit>(
+    ctx: &SchedulingContext<U>,
+    schedule: &Schedule<U>,
+    after: Quantity<U>,
+    before: Quantity<U>,
+) -> Option<Quantity<U>> {
+    if after >= before {
+        return None;
+    }
+    schedule
+        .conflicts_vec(Interval::new(after, before))
+        .ok()?
+        .into_iter()
+        .filter(|(id, iv)| {
+            !iv.is_empty() && iv.start() >= after && Some(id.as_str()) != ctx.target_id
+        })
+        .map(|(_, iv)| iv.start())
+        .next()
+}
+
+/// `range` from `bound` onwards; a milestone range is admitted as-is once
+/// `bound` has passed.
+fn from_bound<U: Unit>(range: Interval<U>, bound: Quantity<U>) -> IntervalSet<U> {
+    let start = range.start().max(bound);
+    let admitted = start < range.end() || (range.is_empty() && bound <= range.start());
+    if admitted {
+        IntervalSet::from(Interval::new(start, range.end()))
+    } else {
+        IntervalSet::new()
+    }
 }
 
 impl<U: Unit> DynamicConstraint<U> for DynConstraintKind {
@@ -74,29 +373,121 @@ impl<U: Unit> DynamicConstraint<U> for DynConstraintKind {
     ) -> IntervalSet<U> {
         match self {
             Self::Dependence => {
-                if ctx.schedule.contains_task(ref_task_id) {
+                if ctx.is_placed(ref_task_id) {
                     IntervalSet::from(range)
                 } else {
                     IntervalSet::new()
                 }
             }
 
+            // An empty range is a milestone placement: admitted as-is once
+            // the reference has ended.
             Self::Consecutive => ctx
-                .schedule
-                .get_interval(ref_task_id)
-                .and_then(|ref_interval| {
-                    let start = range.start().max(ref_interval.end());
-                    (start < range.end()).then(|| Interval::new(start, range.end()))
-                })
-                .map_or_else(IntervalSet::new, IntervalSet::from),
+                .placement(ref_task_id)
+                .map_or_else(IntervalSet::new, |r| from_bound(range, r.end())),
 
             Self::Exclusive => {
-                if !ctx.schedule.contains_task(ref_task_id) {
+                if !ctx.is_placed(ref_task_id) {
                     IntervalSet::from(range)
                 } else {
                     IntervalSet::new()
                 }
             }
+
+            Self::Simultaneous { min_overlap } => ctx
+                .placement(ref_task_id)
+                .and_then(|r| {
+                    if range.is_empty() {
+                        // A milestone is admitted at any instant the
+                        // reference is running.
+                        let t = range.start();
+                        return (r.start() <= t && t < r.end()).then_some(range);
+                    }
+                    let min_overlap = min_overlap.quantity();
+                    let Some(size) = ctx.target_size else {
+                        return r
+                            .intersection(&range)
+                            .filter(|w| w.duration() >= min_overlap);
+                    };
+                    let required = if min_overlap > Quantity::new(0.0) {
+                        min_overlap
+                    } else {
+                        size.min(r.duration())
+                    };
+                    if required > size || required > r.duration() {
+                        return None;
+                    }
+                    let start = range.start().max(r.start() + required - size);
+                    let end = range.end().min(r.end() - required + size);
+                    (start < end).then(|| Interval::new(start, end))
+                })
+                .map_or_else(IntervalSet::new, IntervalSet::from),
+
+            Self::StartToStart { lag } => ctx
+                .placement(ref_task_id)
+                .map_or_else(IntervalSet::new, |r| {
+                    from_bound(range, r.start() + lag.quantity())
+                }),
+
+            Self::FinishToFinish { lag } | Self::StartToFinish { lag } => ctx
+                .placement(ref_task_id)
+                .map_or_else(IntervalSet::new, |r| {
+                    let anchor = match self {
+                        Self::FinishToFinish { .. } => r.end(),
+                        _ => r.start(),
+                    };
+                    let size = ctx.target_size.unwrap_or(Quantity::new(0.0));
+                    from_bound(range, anchor + lag.quantity() - size)
+                }),
+
+            Self::MaxWait { max_wait } => ctx
+                .placement(ref_task_id)
+                .and_then(|r| {
+                    let latest_start = r.end() + max_wait.quantity();
+                    if range.is_empty() {
+                        let t = range.start();
+                        return (r.end() <= t && t <= latest_start).then_some(range);
+                    }
+                    let size = ctx.target_size.unwrap_or(Quantity::new(0.0));
+                    let start = range.start().max(r.end());
+                    let end = range.end().min(latest_start + size);
+                    (start < end).then(|| Interval::new(start, end))
+                })
+                .map_or_else(IntervalSet::new, IntervalSet::from),
+
+            Self::Chained { max_gap } => ctx
+                .placement(ref_task_id)
+                .and_then(|r| {
+                    let latest_start = r.end() + max_gap.quantity();
+                    let size = ctx.target_size.unwrap_or(Quantity::new(0.0));
+                    let on = ctx.schedule_of(ref_task_id).unwrap_or(ctx.schedule);
+                    let next = next_start_after(ctx, on, r.end(), latest_start + size);
+                    if range.is_empty() {
+                        let t = range.start();
+                        let before_next = next.is_none_or(|n| t <= n);
+                        return (r.end() <= t && t <= latest_start && before_next).then_some(range);
+                    }
+                    let start = range.start().max(r.end());
+                    let mut end = range.end().min(latest_start + size);
+                    if let Some(n) = next {
+                        end = end.min(n);
+                    }
+                    (start < end).then(|| Interval::new(start, end))
+                })
+                .map_or_else(IntervalSet::new, IntervalSet::from),
+
+            // Milestones occupy no time, so they never overlap.
+            Self::Disjoint if range.is_empty() => IntervalSet::from(range),
+            Self::Disjoint => {
+                let busy: Vec<_> = match ctx.resources {
+                    Some(resources) => resources
+                        .values()
+                        .filter_map(|s| s.get_interval(ref_task_id))
+                        .collect(),
+                    None => ctx.schedule.get_interval(ref_task_id).into_iter().collect(),
+                };
+                IntervalSet::from(busy).complement(range)
+            }
         }
     }
 
@@ -105,8 +496,16 @@ impl<U: Unit> DynamicConstraint<U> for DynConstraintKind {
             Self::Dependence => "Dependence".to_string(),
             Self::Consecutive => "Consecutive".to_string(),
             Self::Exclusive => "Exclusive".to_string(),
+            Self::Disjoint => "Disjoint".to_string(),
+            _ => self.to_string(),
         }
     }
+
+    /// Every kind except `Disjoint`, which also reads the reference's
+    /// placements on other resources.
+    fn is_reference_local(&self) -> bool {
+        !matches!(self, Self::Disjoint)
+    }
 }
 
 impl std::fmt::Display for DynConstraintKind {
@@ -115,14 +514,31 @@ impl std::fmt::Display for DynConstraintKind {
             Self::Dependence => write!(f, "Dependence"),
             Self::Consecutive => write!(f, "Consecutive"),
             Self::Exclusive => write!(f, "Exclusive"),
+            Self::Simultaneous { min_overlap } if min_overlap.value() > 0.0 => {
+                write!(f, "Simultaneous(min_overlap={min_overlap})")
+            }
+            Self::Simultaneous { .. } => write!(f, "Simultaneous"),
+            Self::StartToStart { lag } => write_lagged(f, "StartToStart", lag.value()),
+            Self::FinishToFinish { lag } => write_lagged(f, "FinishToFinish", lag.value()),
+            Self::StartToFinish { lag } => write_lagged(f, "StartToFinish", lag.value()),
+            Self::MaxWait { max_wait } => write!(f, "MaxWait({max_wait})"),
+            Self::Chained { max_gap } => write!(f, "Chained({max_gap})"),
+            Self::Disjoint => write!(f, "Disjoint"),
         }
     }
 }
 
+fn write_lagged(f: &mut std::fmt::Formatter<'_>, name: &str, lag: f64) -> std::fmt::Result {
+    if lag == 0.0 {
+        write!(f, "{name}")
+    } else {
+        write!(f, "{name}(lag={lag})")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::schedule::Schedule;
     use crate::solution_space::{Interval, SolutionSpace};
     use qtty::Second;
 
@@ -201,6 +617,23 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn consecutive_milestone_at_ref_end() {
+        let mut schedule = Schedule::new();
+        schedule.add("task-a", iv(10.0, 30.0)).unwrap();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+
+        let at_end =
+            DynConstraintKind::Consecutive.compute_intervals(iv(30.0, 30.0), "task-a", &ctx);
+        assert_eq!(at_end.len(), 1);
+        assert_eq!(at_end[0], iv(30.0, 30.0));
+
+        let inside =
+            DynConstraintKind::Consecutive.compute_intervals(iv(20.0, 20.0), "task-a", &ctx);
+        assert!(inside.is_empty());
+    }
+
     #[test]
     fn consecutive_ref_absent_returns_empty() {
         let (schedule, ss) = empty_ctx();
@@ -234,6 +667,350 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    // ── Simultaneous ──────────────────────────────────────────────────
+
+    #[test]
+    fn simultaneous_window_is_ref_placement() {
+        let mut schedule = Schedule::new();
+        schedule.add("task-a", iv(10.0, 30.0)).unwrap();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+
+        let kind = DynConstraintKind::simultaneous(0.0);
+        let result = kind.compute_intervals(iv(0.0, 100.0), "task-a", &ctx);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0], iv(10.0, 30.0));
+
+        let clipped = kind.compute_intervals(iv(25.0, 100.0), "task-a", &ctx);
+        assert_eq!(clipped[0], iv(25.0, 30.0));
+        assert!(kind
+            .compute_intervals(iv(30.0, 100.0), "task-a", &ctx)
+            .is_empty());
+    }
+
+    #[test]
+    fn simultaneous_enforces_min_overlap() {
+        let mut schedule = Schedule::new();
+        schedule.add("task-a", iv(10.0, 30.0)).unwrap();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+
+        let kind = DynConstraintKind::simultaneous(8.0);
+        let wide = kind.compute_intervals(iv(20.0, 100.0), "task-a", &ctx);
+        assert_eq!(wide[0], iv(20.0, 30.0));
+        let narrow = kind.compute_intervals(iv(25.0, 100.0), "task-a", &ctx);
+        assert!(narrow.is_empty());
+    }
+
+    #[test]
+    fn simultaneous_uses_the_target_size() {
+        let (schedule, ss) = placed_ref();
+        let ctx = |size: f64| {
+            SchedulingContext::new(&schedule, &ss).with_target_size(Quantity::new(size))
+        };
+        let overlap = |kind: DynConstraintKind, size: f64| {
+            kind.compute_intervals(iv(0.0, 100.0), "task-a", &ctx(size))
+        };
+
+        // Without a minimum a short target runs inside the reference ...
+        assert_eq!(
+            overlap(DynConstraintKind::simultaneous(0.0), 8.0)[0],
+            iv(10.0, 30.0)
+        );
+        // ... and a long one covers it.
+        assert_eq!(
+            overlap(DynConstraintKind::simultaneous(0.0), 40.0)[0],
+            iv(0.0, 50.0)
+        );
+        // Starting at 25, a 40-long target overlaps the reference by 5.
+        assert_eq!(
+            overlap(DynConstraintKind::simultaneous(5.0), 40.0)[0],
+            iv(0.0, 65.0)
+        );
+        // More overlap than the reference, or the target, lasts.
+        assert!(overlap(DynConstraintKind::simultaneous(25.0), 40.0).is_empty());
+        assert!(overlap(DynConstraintKind::simultaneous(10.0), 8.0).is_empty());
+    }
+
+    #[test]
+    fn simultaneous_milestone_and_absent_ref() {
+        let mut schedule = Schedule::new();
+        schedule.add("task-a", iv(10.0, 30.0)).unwrap();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        let kind = DynConstraintKind::simultaneous(0.0);
+
+        assert_eq!(
+            kind.compute_intervals(iv(10.0, 10.0), "task-a", &ctx)[0],
+            iv(10.0, 10.0)
+        );
+        assert!(kind
+            .compute_intervals(iv(30.0, 30.0), "task-a", &ctx)
+            .is_empty());
+        assert!(kind
+            .compute_intervals(iv(0.0, 100.0), "task-b", &ctx)
+            .is_empty());
+    }
+
+    #[test]
+    fn simultaneous_constructor_normalises() {
+        assert_eq!(
+            DynConstraintKind::simultaneous(f64::NAN),
+            DynConstraintKind::Simultaneous {
+                min_overlap: Offset::ZERO
+            }
+        );
+        assert_eq!(
+            DynConstraintKind::simultaneous(-3.0),
+            DynConstraintKind::simultaneous(0.0)
+        );
+    }
+
+    #[test]
+    fn offsets_reject_nan_and_hash_signed_zeros_alike() {
+        use std::collections::hash_map::DefaultHasher;
+
+        assert_eq!(Offset::new(f64::NAN), Err(ConstraintError::NanParameter));
+        assert_eq!(Offset::try_from(-1.5).unwrap().value(), -1.5);
+        let hash = |kind: DynConstraintKind| {
+            let mut h = DefaultHasher::new();
+            kind.hash(&mut h);
+            h.finish()
+        };
+        let (pos, neg) = (
+            DynConstraintKind::start_to_start(0.0),
+            DynConstraintKind::start_to_start(-0.0),
+        );
+        assert_eq!(pos, neg);
+        assert_eq!(hash(pos), hash(neg));
+    }
+
+    // ── Start/finish relations ────────────────────────────────────────
+
+    fn placed_ref() -> (Schedule<Second>, SolutionSpace<Second>) {
+        let mut schedule = Schedule::new();
+        schedule.add("task-a", iv(10.0, 30.0)).unwrap();
+        (schedule, SolutionSpace::new())
+    }
+
+    #[test]
+    fn start_to_start_applies_lag() {
+        let (schedule, ss) = placed_ref();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+
+        let result = DynConstraintKind::start_to_start(5.0).compute_intervals(
+            iv(0.0, 100.0),
+            "task-a",
+            &ctx,
+        );
+        assert_eq!(result[0], iv(15.0, 100.0));
+
+        let lead = DynConstraintKind::start_to_start(-4.0).compute_intervals(
+            iv(0.0, 100.0),
+            "task-a",
+            &ctx,
+        );
+        assert_eq!(lead[0], iv(6.0, 100.0));
+    }
+
+    #[test]
+    fn finish_to_finish_uses_target_size() {
+        let (schedule, ss) = placed_ref();
+        let kind = DynConstraintKind::finish_to_finish(10.0);
+
+        // End ≥ 40: a 15-long target may start at 25.
+        let sized = SchedulingContext::new(&schedule, &ss).with_target_size(Quantity::new(15.0));
+        assert_eq!(
+            kind.compute_intervals(iv(0.0, 100.0), "task-a", &sized)[0],
+            iv(25.0, 100.0)
+        );
+
+        // Unknown size: conservatively start after the bound.
+        let unsized_ctx = SchedulingContext::new(&schedule, &ss);
+        assert_eq!(
+            kind.compute_intervals(iv(0.0, 100.0), "task-a", &unsized_ctx)[0],
+            iv(40.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn start_to_finish_anchors_on_ref_start() {
+        let (schedule, ss) = placed_ref();
+        let ctx = SchedulingContext::new(&schedule, &ss).with_target_size(Quantity::new(5.0));
+
+        let result = DynConstraintKind::start_to_finish(0.0).compute_intervals(
+            iv(0.0, 100.0),
+            "task-a",
+            &ctx,
+        );
+        assert_eq!(result[0], iv(5.0, 100.0));
+    }
+
+    #[test]
+    fn relations_require_ref() {
+        let (schedule, ss) = empty_ctx();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        for kind in [
+            DynConstraintKind::start_to_start(0.0),
+            DynConstraintKind::finish_to_finish(0.0),
+            DynConstraintKind::start_to_finish(0.0),
+        ] {
+            assert!(kind
+                .compute_intervals(iv(0.0, 100.0), "task-a", &ctx)
+                .is_empty());
+        }
+    }
+
+    // ── MaxWait ───────────────────────────────────────────────────────
+
+    #[test]
+    fn max_wait_bounds_start_after_ref_end() {
+        let (schedule, ss) = placed_ref();
+        let kind = DynConstraintKind::max_wait(20.0);
+
+        // Start in [30, 50]: a 10-long target must end by 60.
+        let sized = SchedulingContext::new(&schedule, &ss).with_target_size(Quantity::new(10.0));
+        assert_eq!(
+            kind.compute_intervals(iv(0.0, 100.0), "task-a", &sized)[0],
+            iv(30.0, 60.0)
+        );
+        assert!(kind
+            .compute_intervals(iv(70.0, 100.0), "task-a", &sized)
+            .is_empty());
+        assert_eq!(
+            kind.compute_intervals(iv(50.0, 50.0), "task-a", &sized)[0],
+            iv(50.0, 50.0)
+        );
+        assert!(kind
+            .compute_intervals(iv(51.0, 51.0), "task-a", &sized)
+            .is_empty());
+    }
+
+    #[test]
+    fn max_wait_requires_ref_and_normalises() {
+        let (schedule, ss) = empty_ctx();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        assert!(DynConstraintKind::max_wait(10.0)
+            .compute_intervals(iv(0.0, 100.0), "task-a", &ctx)
+            .is_empty());
+        assert_eq!(
+            DynConstraintKind::max_wait(f64::NAN),
+            DynConstraintKind::MaxWait {
+                max_wait: Offset::ZERO
+            }
+        );
+        assert_eq!(
+            DynConstraintKind::max_wait(3600.0).to_string(),
+            "MaxWait(3600)"
+        );
+    }
+
+    // ── Chained ───────────────────────────────────────────────────────
+
+    #[test]
+    fn chained_keeps_the_target_close_to_the_reference() {
+        let (schedule, ss) = placed_ref();
+        let sized = SchedulingContext::new(&schedule, &ss).with_target_size(Quantity::new(5.0));
+        let kind = DynConstraintKind::chained(2.0);
+        assert_eq!(
+            kind.compute_intervals(iv(0.0, 100.0), "task-a", &sized)[0],
+            iv(30.0, 37.0)
+        );
+        // Plain `Consecutive` lets the target drift arbitrarily far.
+        assert_eq!(
+            DynConstraintKind::Consecutive.compute_intervals(iv(0.0, 100.0), "task-a", &sized)[0],
+            iv(30.0, 100.0)
+        );
+        assert_eq!(
+            DynConstraintKind::chained(-1.0),
+            DynConstraintKind::Chained {
+                max_gap: Offset::ZERO
+            }
+        );
+        assert_eq!(kind.to_string(), "Chained(2)");
+    }
+
+    #[test]
+    fn chained_admits_no_task_in_between() {
+        let (mut schedule, ss) = placed_ref();
+        schedule.add("other", iv(33.0, 40.0)).unwrap();
+        let kind = DynConstraintKind::chained(10.0);
+
+        // `max_wait` alone would allow [30, 45); `other` cuts it at 33,
+        // too short for a 5-long target.
+        let sized = SchedulingContext::new(&schedule, &ss).with_target_size(Quantity::new(5.0));
+        assert_eq!(
+            kind.compute_intervals(iv(0.0, 100.0), "task-a", &sized)[0],
+            iv(30.0, 33.0)
+        );
+        let short = SchedulingContext::new(&schedule, &ss).with_target_size(Quantity::new(3.0));
+        assert_eq!(
+            DynConstraintKind::max_wait(10.0).compute_intervals(iv(0.0, 100.0), "task-a", &sized)
+                [0],
+            iv(30.0, 45.0)
+        );
+        // The target's own placement is not in the way.
+        let own = SchedulingContext::new(&schedule, &ss)
+            .with_target_size(Quantity::new(3.0))
+            .with_target_id("other");
+        assert_eq!(
+            kind.compute_intervals(iv(0.0, 100.0), "task-a", &own)[0],
+            iv(30.0, 43.0)
+        );
+        assert!(kind
+            .compute_intervals(iv(34.0, 34.0), "task-a", &short)
+            .is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn chained_round_trips_through_serde() {
+        let kind: DynConstraintKind =
+            serde_json::from_str(r#"{"chained": {"max_gap": 60.0}}"#).unwrap();
+        assert_eq!(kind, DynConstraintKind::chained(60.0));
+        let json = serde_json::to_string(&kind).unwrap();
+        assert_eq!(
+            serde_json::from_str::<DynConstraintKind>(&json).unwrap(),
+            kind
+        );
+    }
+
+    // ── Disjoint ──────────────────────────────────────────────────────
+
+    #[test]
+    fn disjoint_avoids_the_reference_on_every_resource() {
+        let (schedule, ss) = placed_ref();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        let kind = DynConstraintKind::Disjoint;
+        assert_eq!(
+            kind.compute_intervals(iv(0.0, 100.0), "task-a", &ctx)
+                .as_slice(),
+            &[iv(0.0, 10.0), iv(30.0, 100.0)]
+        );
+        assert_eq!(
+            kind.compute_intervals(iv(0.0, 100.0), "absent", &ctx)
+                .as_slice(),
+            &[iv(0.0, 100.0)]
+        );
+        assert_eq!(
+            kind.compute_intervals(iv(20.0, 20.0), "task-a", &ctx)[0],
+            iv(20.0, 20.0)
+        );
+
+        let mut other = Schedule::new();
+        other.add("task-a", iv(50.0, 60.0)).unwrap();
+        let resources = std::collections::HashMap::from([
+            ("r1".to_string(), schedule.clone()),
+            ("r2".to_string(), other),
+        ]);
+        let multi = SchedulingContext::new(&schedule, &ss).with_resources(&resources);
+        assert_eq!(
+            kind.compute_intervals(iv(0.0, 100.0), "task-a", &multi)
+                .as_slice(),
+            &[iv(0.0, 10.0), iv(30.0, 50.0), iv(60.0, 100.0)]
+        );
+    }
+
     // ── Display / stringify ───────────────────────────────────────────
 
     #[test]
@@ -251,6 +1028,22 @@ mod tests {
             DynamicConstraint::<Second>::stringify(&DynConstraintKind::Exclusive),
             "Exclusive"
         );
+        assert_eq!(
+            DynamicConstraint::<Second>::stringify(&DynConstraintKind::simultaneous(0.0)),
+            "Simultaneous"
+        );
+        assert_eq!(
+            DynamicConstraint::<Second>::stringify(&DynConstraintKind::simultaneous(5.0)),
+            "Simultaneous(min_overlap=5)"
+        );
+        assert_eq!(
+            DynamicConstraint::<Second>::stringify(&DynConstraintKind::start_to_start(0.0)),
+            "StartToStart"
+        );
+        assert_eq!(
+            DynamicConstraint::<Second>::stringify(&DynConstraintKind::finish_to_finish(-2.5)),
+            "FinishToFinish(lag=-2.5)"
+        );
     }
 
     #[test]
@@ -260,6 +1053,13 @@ mod tests {
             DynConstraintKind::Dependence,
             DynConstraintKind::Consecutive,
             DynConstraintKind::Exclusive,
+            DynConstraintKind::simultaneous(5.0),
+            DynConstraintKind::start_to_start(1.0),
+            DynConstraintKind::finish_to_finish(2.0),
+            DynConstraintKind::start_to_finish(3.0),
+            DynConstraintKind::max_wait(4.0),
+            DynConstraintKind::chained(5.0),
+            DynConstraintKind::Disjoint,
         ] {
             assert_eq!(
                 format!("{kind}"),
@@ -16,21 +16,51 @@
 //!
 //! # Built-in kinds
 //!
-//! | Kind          | Meaning                                               |
-//! |---------------|-------------------------------------------------------|
-//! | `Dependence`  | Target schedulable only if reference is placed         |
-//! | `Consecutive` | Target schedulable only after reference finishes       |
-//! | `Exclusive`   | Target schedulable only if reference is **not** placed |
+//! | Kind             | Meaning                                                |
+//! |------------------|--------------------------------------------------------|
+//! | `Dependence`     | Target schedulable only if reference is placed         |
+//! | `Consecutive`    | Target schedulable only after reference finishes       |
+//! | `Exclusive`      | Target schedulable only if reference is **not** placed |
+//! | `Simultaneous`   | Target overlaps the reference task                     |
+//! | `StartToStart`   | Target starts no earlier than reference start + lag    |
+//! | `FinishToFinish` | Target ends no earlier than reference end + lag        |
+//! | `StartToFinish`  | Target ends no earlier than reference start + lag      |
+//! | `MaxWait`        | Target starts within a bound after reference ends      |
+//! | `Chained`        | Target directly follows the reference, within a gap    |
+//! | `Disjoint`       | Target never overlaps reference, on any resource       |
 //!
 //! Custom kinds can be added by implementing [`DynamicConstraint`] for a
-//! new type.
+//! new type, or, for one-off rules, by wrapping a closure in a
+//! [`FnDynamicConstraint`].
+//!
+//! [`MaxParallelism`] spans resources: it reads every resource's schedule
+//! through [`SchedulingContext::resources`].
+//! [`ResourceConstraint`](crate::constraints::ResourceConstraint) does the
+//! same for a pool with a time-varying capacity profile, and
+//! [`PowerEnvelope`] for the combined power draw of running tasks.
+//! [`ContiguousGroup`] keeps the scheduled members of a named set in one
+//! block with no foreign task between them.
+//!
+//! Non-renewable resources — fuel, cryogen, an exposure allowance — are not
+//! edges: a [`ConsumableBudget`] attached to the [`SchedulingContext`] makes
+//! every task it cannot afford infeasible.
 
+pub mod closure;
 pub mod coalition;
 pub mod constraint;
+pub mod consumable;
+pub mod contiguous;
 pub mod evaluate;
 pub mod kinds;
+pub mod parallelism;
+pub mod power;
 
-pub use coalition::CoalitionConstraint;
+pub use closure::FnDynamicConstraint;
+pub use coalition::{CardinalityViolation, CoalitionConstraint};
 pub use constraint::{DynamicConstraint, SchedulingContext};
+pub use consumable::ConsumableBudget;
+pub use contiguous::ContiguousGroup;
 pub use evaluate::DynamicConstraintIndex;
-pub use kinds::DynConstraintKind;
+pub use kinds::{DynConstraintKind, Offset};
+pub use parallelism::MaxParallelism;
+pub use power::PowerEnvelope;
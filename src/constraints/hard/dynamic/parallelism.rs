@@ -0,0 +1,310 @@
+//! Max-parallelism constraint — caps how many tasks of a family run at once.
+//!
+//! This is a **hard + dynamic** constraint over a *family* of tasks that share
+//! a bottleneck spanning resources (a downlink channel, an operator's
+//! attention): at most `limit` family members may be executing concurrently,
+//! counted across every resource schedule.
+//!
+//! # Evaluation
+//!
+//! Like [`CoalitionConstraint`](super::CoalitionConstraint), the constraint is
+//! checked directly against the multi-resource state — a map from resource ID
+//! to that resource's [`Schedule`]:
+//!
+//! - [`available`](MaxParallelism::available) returns where one more member
+//!   may run;
+//! - [`is_satisfied`](MaxParallelism::is_satisfied) checks a finished plan.
+//!
+//! The [`DynamicConstraint`] implementation reads the schedules from
+//! [`SchedulingContext::resources`], falling back to the single
+//! [`SchedulingContext::schedule`]. The edge's reference task is not used, so
+//! the constraint can hang off any edge into the constrained task.
+//! [`ESTScheduler::schedule_pool`](crate::algorithms::est::ESTScheduler::schedule_pool)
+//! evaluates its edges against every resource of the pool, so the cap holds
+//! while the pool is filled, not only when the plan is checked.
+//!
+//! A task placed on several resources at once (a coalition) counts once.
+//! Milestones occupy no time and never count.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::constraint::{DynamicConstraint, SchedulingContext};
+use crate::schedule::Schedule;
+use crate::solution_space::{Interval, IntervalSet};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+/// At most `limit` tasks from a family may execute concurrently.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use virolai::constraints::MaxParallelism;
+/// use virolai::schedule::Schedule;
+/// use virolai::solution_space::Interval;
+/// use qtty::Second;
+///
+/// let downlink = MaxParallelism::new("downlink", 1).with_members(["dl-a", "dl-b"]);
+///
+/// let mut ant1 = Schedule::<Second>::new();
+/// ant1.add("dl-a", Interval::from_f64(0.0, 10.0)).unwrap();
+/// let mut ant2 = Schedule::<Second>::new();
+/// ant2.add("dl-b", Interval::from_f64(5.0, 15.0)).unwrap();
+/// let resources = HashMap::from([("ant1".to_string(), ant1), ("ant2".to_string(), ant2)]);
+///
+/// assert!(!downlink.is_satisfied(&resources));
+/// assert_eq!(downlink.peak(&resources), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MaxParallelism {
+    family: String,
+    limit: u32,
+    members: HashSet<Id>,
+}
+
+impl MaxParallelism {
+    /// Creates a constraint for `family` with no members yet.
+    pub fn new(family: impl Into<String>, limit: u32) -> Self {
+        Self {
+            family: family.into(),
+            limit,
+            members: HashSet::new(),
+        }
+    }
+
+    /// Adds a task to the family.
+    pub fn with_member(mut self, task_id: impl Into<Id>) -> Self {
+        self.members.insert(task_id.into());
+        self
+    }
+
+    /// Adds several tasks to the family.
+    pub fn with_members(mut self, task_ids: impl IntoIterator<Item = impl Into<Id>>) -> Self {
+        self.members.extend(task_ids.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn family(&self) -> &str {
+        &self.family
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    /// Returns `true` if `task_id` belongs to the family.
+    pub fn contains(&self, task_id: &str) -> bool {
+        self.members.contains(task_id)
+    }
+
+    /// Number of family members running over time.
+    ///
+    /// Returns maximal pieces with a constant, non-zero count, in time order.
+    pub fn concurrency<U: Unit>(
+        &self,
+        resources: &HashMap<Id, Schedule<U>>,
+    ) -> Vec<(Interval<U>, u32)> {
+        self.profile(resources.values())
+    }
+
+    /// Highest number of family members running at the same time.
+    pub fn peak<U: Unit>(&self, resources: &HashMap<Id, Schedule<U>>) -> u32 {
+        self.concurrency(resources)
+            .iter()
+            .map(|&(_, n)| n)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if the limit is never exceeded.
+    pub fn is_satisfied<U: Unit>(&self, resources: &HashMap<Id, Schedule<U>>) -> bool {
+        self.peak(resources) <= self.limit
+    }
+
+    /// Parts of `range` where another family member may run, i.e. where fewer
+    /// than `limit` members are already running.
+    pub fn available<U: Unit>(
+        &self,
+        range: Interval<U>,
+        resources: &HashMap<Id, Schedule<U>>,
+    ) -> IntervalSet<U> {
+        self.available_in(range, resources.values())
+    }
+
+    fn available_in<'a, U: Unit>(
+        &self,
+        range: Interval<U>,
+        schedules: impl Iterator<Item = &'a Schedule<U>>,
+    ) -> IntervalSet<U> {
+        if self.limit == 0 {
+            return IntervalSet::new();
+        }
+        let saturated: Vec<_> = self
+            .profile(schedules)
+            .into_iter()
+            .filter(|&(_, n)| n >= self.limit)
+            .map(|(iv, _)| iv)
+            .collect();
+        IntervalSet::from(saturated).complement(range)
+    }
+
+    /// Sweeps the members' placements into a piecewise-constant count.
+    fn profile<'a, U: Unit>(
+        &self,
+        schedules: impl Iterator<Item = &'a Schedule<U>>,
+    ) -> Vec<(Interval<U>, u32)> {
+        // Union each member's placements so coalitions count once.
+        let mut per_task: HashMap<Id, Vec<Interval<U>>> = HashMap::new();
+        for schedule in schedules {
+            for (id, interval) in schedule.iter() {
+                if !interval.is_empty() && self.members.contains(&id) {
+                    per_task.entry(id).or_default().push(interval);
+                }
+            }
+        }
+
+        let mut events: Vec<(f64, i32)> = per_task
+            .into_values()
+            .flat_map(|ivs| IntervalSet::from(ivs).into_inner())
+            .flat_map(|iv| [(iv.start().value(), 1), (iv.end().value(), -1)])
+            .collect();
+        // Ends before starts at the same instant: intervals are half-open.
+        events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        let mut pieces: Vec<(Interval<U>, u32)> = Vec::new();
+        let mut running = 0i32;
+        for (i, &(at, delta)) in events.iter().enumerate() {
+            running += delta;
+            let Some(&(next, _)) = events.get(i + 1) else {
+                break;
+            };
+            if running == 0 || next == at {
+                continue;
+            }
+            let piece = Interval::new(Quantity::new(at), Quantity::new(next));
+            let count = running as u32;
+            match pieces.last_mut() {
+                Some((last, n)) if *n == count && last.end().value() == at => {
+                    *last = Interval::new(last.start(), piece.end());
+                }
+                _ => pieces.push((piece, count)),
+            }
+        }
+        pieces
+    }
+}
+
+impl fmt::Display for MaxParallelism {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MaxParallelism({} ≤ {})", self.family, self.limit)
+    }
+}
+
+impl<U: Unit> DynamicConstraint<U> for MaxParallelism {
+    fn compute_intervals(
+        &self,
+        range: Interval<U>,
+        _ref_task_id: &str,
+        ctx: &SchedulingContext<U>,
+    ) -> IntervalSet<U> {
+        match ctx.resources {
+            Some(resources) => self.available_in(range, resources.values()),
+            None => self.available_in(range, std::iter::once(ctx.schedule)),
+        }
+    }
+
+    fn stringify(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    type Placements<'a> = &'a [(&'a str, f64, f64)];
+
+    fn resources(plan: &[(&str, Placements)]) -> HashMap<Id, Schedule<Second>> {
+        plan.iter()
+            .map(|(resource, tasks)| {
+                let mut s = Schedule::new();
+                for &(id, a, b) in tasks.iter() {
+                    s.add(id, iv(a, b)).unwrap();
+                }
+                (resource.to_string(), s)
+            })
+            .collect()
+    }
+
+    fn family(limit: u32) -> MaxParallelism {
+        MaxParallelism::new("downlink", limit).with_members(["a", "b", "c"])
+    }
+
+    #[test]
+    fn concurrency_counts_across_resources() {
+        let r = resources(&[
+            ("r1", &[("a", 0.0, 10.0), ("x", 10.0, 20.0)]),
+            ("r2", &[("b", 5.0, 15.0)]),
+            ("r3", &[("c", 5.0, 8.0)]),
+        ]);
+        let profile = family(2).concurrency(&r);
+        assert_eq!(
+            profile,
+            vec![
+                (iv(0.0, 5.0), 1),
+                (iv(5.0, 8.0), 3),
+                (iv(8.0, 10.0), 2),
+                (iv(10.0, 15.0), 1),
+            ]
+        );
+        assert_eq!(family(2).peak(&r), 3);
+        assert!(!family(2).is_satisfied(&r));
+        assert!(family(3).is_satisfied(&r));
+    }
+
+    #[test]
+    fn abutting_members_do_not_overlap() {
+        let r = resources(&[("r1", &[("a", 0.0, 10.0)]), ("r2", &[("b", 10.0, 20.0)])]);
+        assert_eq!(family(1).peak(&r), 1);
+        assert_eq!(family(1).concurrency(&r), vec![(iv(0.0, 20.0), 1)]);
+    }
+
+    #[test]
+    fn coalition_member_counts_once() {
+        let r = resources(&[("r1", &[("a", 0.0, 10.0)]), ("r2", &[("a", 0.0, 10.0)])]);
+        assert_eq!(family(1).peak(&r), 1);
+    }
+
+    #[test]
+    fn available_excludes_saturated_time() {
+        let r = resources(&[("r1", &[("a", 0.0, 10.0)]), ("r2", &[("b", 5.0, 15.0)])]);
+        let free = family(2).available(iv(0.0, 30.0), &r);
+        assert_eq!(free.into_inner(), vec![iv(0.0, 5.0), iv(10.0, 30.0)]);
+        assert!(family(0).available(iv(0.0, 30.0), &r).is_empty());
+    }
+
+    #[test]
+    fn dynamic_evaluation_uses_context_resources() {
+        let r = resources(&[("r1", &[("a", 0.0, 10.0)]), ("r2", &[("b", 20.0, 30.0)])]);
+        let local = Schedule::new();
+        let ss = SolutionSpace::new();
+        let c = family(1);
+
+        let ctx = SchedulingContext::new(&local, &ss).with_resources(&r);
+        let free = c.compute_intervals(iv(0.0, 40.0), "unused", &ctx);
+        assert_eq!(free.into_inner(), vec![iv(10.0, 20.0), iv(30.0, 40.0)]);
+
+        let ctx = SchedulingContext::new(&r["r1"], &ss);
+        let free = c.compute_intervals(iv(0.0, 40.0), "unused", &ctx);
+        assert_eq!(free.into_inner(), vec![iv(10.0, 40.0)]);
+        assert_eq!(
+            DynamicConstraint::<Second>::stringify(&c),
+            "MaxParallelism(downlink ≤ 1)"
+        );
+    }
+}
@@ -0,0 +1,416 @@
+//! Power envelope constraint — caps the combined draw of running tasks.
+//!
+//! This is a **hard + dynamic** constraint over the whole schedule: each task
+//! may declare a power draw, and at no instant may the draws of the tasks
+//! running then exceed the envelope. The envelope can step over time (less
+//! power in eclipse, more in sunlight).
+//!
+//! # Evaluation
+//!
+//! Like [`MaxParallelism`](super::MaxParallelism), the constraint sums
+//! placements across every resource schedule:
+//!
+//! - [`draw_profile`](PowerEnvelope::draw_profile) returns the piecewise
+//!   draw of the placed tasks;
+//! - [`available`](PowerEnvelope::available) returns where one more task
+//!   drawing a given power fits under the envelope;
+//! - [`is_satisfied`](PowerEnvelope::is_satisfied) checks a finished plan.
+//!
+//! The [`DynamicConstraint`] implementation reads the schedules from
+//! [`SchedulingContext::resources`], falling back to the single
+//! [`SchedulingContext::schedule`], and takes the candidate's draw from
+//! [`SchedulingContext::target_id`]. The edge's reference task is not used,
+//! so the constraint can hang off any edge into the constrained task.
+//! [`ESTScheduler::with_power`](crate::algorithms::est::ESTScheduler::with_power)
+//! evaluates it for every task instead, with no edge at all.
+//!
+//! A task placed on several resources at once (a coalition) draws once.
+//! Milestones occupy no time and never draw.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::constraint::{DynamicConstraint, SchedulingContext};
+use crate::schedule::Schedule;
+use crate::solution_space::{Interval, IntervalSet};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+/// The combined draw of running tasks stays within a stepped envelope.
+///
+/// Envelope steps are positions on the scheduling axis, as plain numbers;
+/// draws and limits share whatever power unit the caller picks.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use virolai::constraints::PowerEnvelope;
+/// use virolai::schedule::Schedule;
+/// use virolai::solution_space::Interval;
+/// use qtty::Second;
+///
+/// // 100 W in sunlight, 40 W in eclipse from t = 50.
+/// let bus = PowerEnvelope::new(100.0)
+///     .with_step(50.0, 40.0)
+///     .with_draw("camera", 60.0)
+///     .with_draw("radio", 30.0);
+///
+/// let mut plan = Schedule::<Second>::new();
+/// plan.add("camera", Interval::from_f64(0.0, 20.0)).unwrap();
+/// let resources = HashMap::from([("bus".to_string(), plan)]);
+///
+/// // A 50 W load fits once the camera is off, until eclipse.
+/// let windows = bus.available(Interval::from_f64(0.0, 100.0), 50.0, &resources);
+/// assert_eq!(
+///     windows.as_slice(),
+///     &[Interval::from_f64(20.0, 50.0)]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowerEnvelope {
+    initial: f64,
+    /// `(at, limit)` pairs, sorted by `at`.
+    steps: Vec<(f64, f64)>,
+    draws: HashMap<Id, f64>,
+}
+
+impl PowerEnvelope {
+    /// Creates an envelope allowing `limit` at all times, with no drawing
+    /// tasks yet.
+    ///
+    /// # Panics
+    ///
+    /// If `limit` is NaN.
+    pub fn new(limit: f64) -> Self {
+        assert!(!limit.is_nan(), "power limits must not be NaN");
+        Self {
+            initial: limit,
+            steps: Vec::new(),
+            draws: HashMap::new(),
+        }
+    }
+
+    /// Changes the limit to `limit` from `at` onwards, replacing any step
+    /// already at `at`.
+    ///
+    /// # Panics
+    ///
+    /// If `at` is not finite or `limit` is NaN.
+    pub fn with_step(mut self, at: f64, limit: f64) -> Self {
+        assert!(at.is_finite(), "envelope steps must be finite");
+        assert!(!limit.is_nan(), "power limits must not be NaN");
+        match self.steps.binary_search_by(|(t, _)| t.total_cmp(&at)) {
+            Ok(i) => self.steps[i].1 = limit,
+            Err(i) => self.steps.insert(i, (at, limit)),
+        }
+        self
+    }
+
+    /// Declares that `task_id` draws `draw` while it runs.
+    ///
+    /// # Panics
+    ///
+    /// If `draw` is negative or not finite.
+    pub fn with_draw(mut self, task_id: impl Into<Id>, draw: f64) -> Self {
+        assert!(
+            draw >= 0.0 && draw.is_finite(),
+            "power draws must be non-negative and finite"
+        );
+        self.draws.insert(task_id.into(), draw);
+        self
+    }
+
+    /// Declares the draws of several tasks.
+    pub fn with_draws(mut self, draws: impl IntoIterator<Item = (impl Into<Id>, f64)>) -> Self {
+        for (task_id, draw) in draws {
+            self = self.with_draw(task_id, draw);
+        }
+        self
+    }
+
+    /// Power `task_id` draws while running; zero if undeclared.
+    pub fn draw(&self, task_id: &str) -> f64 {
+        self.draws.get(task_id).copied().unwrap_or(0.0)
+    }
+
+    /// Limit in force at `t`.
+    pub fn limit_at(&self, t: f64) -> f64 {
+        match self.steps.partition_point(|&(at, _)| at <= t) {
+            0 => self.initial,
+            i => self.steps[i - 1].1,
+        }
+    }
+
+    /// Combined draw of the placed tasks over time.
+    ///
+    /// Returns maximal pieces with a constant, non-zero draw, in time order.
+    pub fn draw_profile<U: Unit>(
+        &self,
+        resources: &HashMap<Id, Schedule<U>>,
+    ) -> Vec<(Interval<U>, f64)> {
+        self.profile(resources.values(), None)
+    }
+
+    /// Highest combined draw at any instant.
+    pub fn peak<U: Unit>(&self, resources: &HashMap<Id, Schedule<U>>) -> f64 {
+        self.draw_profile(resources)
+            .iter()
+            .map(|&(_, w)| w)
+            .fold(0.0, f64::max)
+    }
+
+    /// Returns `true` if the combined draw never exceeds the envelope.
+    pub fn is_satisfied<U: Unit>(&self, resources: &HashMap<Id, Schedule<U>>) -> bool {
+        self.draw_profile(resources)
+            .into_iter()
+            .all(|(piece, load)| self.fits(piece, load))
+    }
+
+    /// Parts of `range` where a task drawing `draw` fits under the envelope
+    /// next to the placed tasks.
+    pub fn available<U: Unit>(
+        &self,
+        range: Interval<U>,
+        draw: f64,
+        resources: &HashMap<Id, Schedule<U>>,
+    ) -> IntervalSet<U> {
+        IntervalSet::from(self.fitting_pieces(range, draw, &self.profile(resources.values(), None)))
+    }
+
+    /// Returns `true` if `draw` stays within the limit throughout `piece`.
+    fn fits<U: Unit>(&self, piece: Interval<U>, draw: f64) -> bool {
+        let (start, end) = (piece.start().value(), piece.end().value());
+        draw <= self.limit_at(start)
+            && self
+                .steps
+                .iter()
+                .filter(|&&(at, _)| start < at && at < end)
+                .all(|&(_, limit)| draw <= limit)
+    }
+
+    /// Pieces of `range` where `draw` more fits on top of `load`.
+    fn fitting_pieces<U: Unit>(
+        &self,
+        range: Interval<U>,
+        draw: f64,
+        load: &[(Interval<U>, f64)],
+    ) -> Vec<Interval<U>> {
+        let (lo, hi) = (range.start().value(), range.end().value());
+        let inside = |t: &f64| lo < *t && *t < hi;
+        let mut cuts = vec![lo, hi];
+        cuts.extend(self.steps.iter().map(|&(at, _)| at).filter(inside));
+        cuts.extend(
+            load.iter()
+                .flat_map(|(piece, _)| [piece.start().value(), piece.end().value()])
+                .filter(inside),
+        );
+        cuts.sort_by(f64::total_cmp);
+        cuts.dedup();
+
+        let mut pieces = load.iter().peekable();
+        let mut windows = Vec::new();
+        for cut in cuts.windows(2) {
+            let (a, b) = (cut[0], cut[1]);
+            while pieces
+                .next_if(|(piece, _)| piece.end().value() <= a)
+                .is_some()
+            {}
+            let used = pieces
+                .peek()
+                .filter(|(piece, _)| piece.start().value() <= a)
+                .map_or(0.0, |&&(_, w)| w);
+            if used + draw <= self.limit_at(a) {
+                windows.push(Interval::new(Quantity::new(a), Quantity::new(b)));
+            }
+        }
+        windows
+    }
+
+    /// Sweeps the drawing tasks' placements into a piecewise-constant load,
+    /// leaving out `skip`.
+    fn profile<'a, U: Unit>(
+        &self,
+        schedules: impl Iterator<Item = &'a Schedule<U>>,
+        skip: Option<&str>,
+    ) -> Vec<(Interval<U>, f64)> {
+        // Union each task's placements so coalitions draw once.
+        let mut per_task: HashMap<Id, Vec<Interval<U>>> = HashMap::new();
+        for schedule in schedules {
+            for (id, interval) in schedule.iter() {
+                if !interval.is_empty() && skip != Some(id.as_str()) && self.draw(&id) > 0.0 {
+                    per_task.entry(id).or_default().push(interval);
+                }
+            }
+        }
+
+        let mut events: Vec<(f64, i32, f64)> = per_task
+            .into_iter()
+            .flat_map(|(id, ivs)| {
+                let draw = self.draw(&id);
+                IntervalSet::from(ivs)
+                    .into_inner()
+                    .into_iter()
+                    .flat_map(move |iv| {
+                        [(iv.start().value(), 1, draw), (iv.end().value(), -1, -draw)]
+                    })
+            })
+            .collect();
+        // Ends before starts at the same instant: intervals are half-open.
+        events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        let mut pieces: Vec<(Interval<U>, f64)> = Vec::new();
+        let (mut running, mut load) = (0, 0.0);
+        for (i, &(at, delta, watts)) in events.iter().enumerate() {
+            running += delta;
+            // Reset on idle so rounding cannot leave a phantom load behind.
+            load = if running == 0 { 0.0 } else { load + watts };
+            let Some(&(next, _, _)) = events.get(i + 1) else {
+                break;
+            };
+            if running == 0 || next == at {
+                continue;
+            }
+            let piece = Interval::new(Quantity::new(at), Quantity::new(next));
+            match pieces.last_mut() {
+                Some((last, w)) if *w == load && last.end().value() == at => {
+                    *last = Interval::new(last.start(), piece.end());
+                }
+                _ => pieces.push((piece, load)),
+            }
+        }
+        pieces
+    }
+}
+
+impl fmt::Display for PowerEnvelope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PowerEnvelope({}", self.initial)?;
+        for (at, limit) in &self.steps {
+            write!(f, ", {}@{}", limit, at)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<U: Unit> DynamicConstraint<U> for PowerEnvelope {
+    fn compute_intervals(
+        &self,
+        range: Interval<U>,
+        _ref_task_id: &str,
+        ctx: &SchedulingContext<U>,
+    ) -> IntervalSet<U> {
+        let draw = ctx.target_id.map_or(0.0, |id| self.draw(id));
+        let load = match ctx.resources {
+            Some(resources) => self.profile(resources.values(), ctx.target_id),
+            None => self.profile(std::iter::once(ctx.schedule), ctx.target_id),
+        };
+        IntervalSet::from(self.fitting_pieces(range, draw, &load))
+    }
+
+    fn stringify(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    type Placements<'a> = &'a [(&'a str, f64, f64)];
+
+    fn resources(plan: &[(&str, Placements)]) -> HashMap<Id, Schedule<Second>> {
+        plan.iter()
+            .map(|(resource, tasks)| {
+                let mut s = Schedule::new();
+                for &(id, a, b) in tasks.iter() {
+                    s.add(id, iv(a, b)).unwrap();
+                }
+                (resource.to_string(), s)
+            })
+            .collect()
+    }
+
+    fn bus() -> PowerEnvelope {
+        PowerEnvelope::new(100.0).with_step(50.0, 40.0).with_draws([
+            ("a", 30.0),
+            ("b", 45.5),
+            ("c", 0.1),
+            ("d", 0.2),
+        ])
+    }
+
+    #[test]
+    fn draw_profile_sums_across_resources() {
+        let r = resources(&[
+            ("r1", &[("a", 0.0, 10.0), ("x", 10.0, 20.0)]),
+            ("r2", &[("b", 5.0, 15.0)]),
+            ("r3", &[("a", 0.0, 10.0)]),
+        ]);
+        assert_eq!(
+            bus().draw_profile(&r),
+            vec![
+                (iv(0.0, 5.0), 30.0),
+                (iv(5.0, 10.0), 75.5),
+                (iv(10.0, 15.0), 45.5)
+            ]
+        );
+        assert_eq!(bus().peak(&r), 75.5);
+        assert!(bus().is_satisfied(&r));
+    }
+
+    #[test]
+    fn idle_gaps_carry_no_rounding_residue() {
+        let r = resources(&[
+            ("r1", &[("c", 0.0, 10.0), ("a", 20.0, 30.0)]),
+            ("r2", &[("d", 5.0, 10.0)]),
+        ]);
+        let profile = bus().draw_profile(&r);
+        assert_eq!(profile.len(), 3);
+        assert_eq!(profile[2], (iv(20.0, 30.0), 30.0));
+    }
+
+    #[test]
+    fn available_follows_the_stepped_envelope() {
+        let r = resources(&[("r1", &[("a", 40.0, 60.0)])]);
+        // 30 W already drawn over [40, 60); the limit drops to 40 W at 50.
+        assert_eq!(
+            bus().available(iv(0.0, 100.0), 45.5, &r).as_slice(),
+            &[iv(0.0, 50.0)]
+        );
+        assert_eq!(
+            bus().available(iv(0.0, 100.0), 70.5, &r).as_slice(),
+            &[iv(0.0, 40.0)]
+        );
+        assert_eq!(
+            bus().available(iv(0.0, 100.0), 10.0, &r).as_slice(),
+            &[iv(0.0, 100.0)]
+        );
+        assert!(!bus().is_satisfied(&resources(&[("r1", &[("b", 45.0, 55.0)])])));
+    }
+
+    #[test]
+    fn dynamic_evaluation_uses_the_target_draw() {
+        let schedule = resources(&[("r1", &[("a", 0.0, 10.0), ("b", 20.0, 30.0)])])
+            .remove("r1")
+            .unwrap();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+
+        // `b` is evaluated without its own placement: only `a` is loaded.
+        let as_b = ctx.for_target("b");
+        let windows = bus().compute_intervals(iv(0.0, 50.0), "ignored", &as_b);
+        assert_eq!(windows.as_slice(), &[iv(0.0, 50.0)]);
+
+        let r = HashMap::from([("r1".to_string(), schedule.clone())]);
+        let multi = SchedulingContext::new(&schedule, &ss)
+            .with_resources(&r)
+            .with_target_id("b");
+        let windows = bus().compute_intervals(iv(0.0, 60.0), "ignored", &multi);
+        assert_eq!(windows.as_slice(), &[iv(0.0, 50.0)]);
+        assert_eq!(bus().to_string(), "PowerEnvelope(100, 40@50)");
+    }
+}
@@ -10,11 +10,15 @@ pub mod static_;
 
 // Re-export the static API at the `hard` level for convenience.
 pub use static_::Constraint;
+pub use static_::FnConstraint;
 pub use static_::IntervalConstraint;
-pub use static_::ResourceConstraint;
+pub use static_::PeriodicConstraint;
+pub use static_::{CapacityProfile, ResourceConstraint};
+pub use static_::{Relaxable, RelaxationLadder};
 
 // Re-export key dynamic types for ergonomic access.
 pub use dynamic::{
-    CoalitionConstraint, DynConstraintKind, DynamicConstraint, DynamicConstraintIndex,
-    SchedulingContext,
+    CardinalityViolation, CoalitionConstraint, ConsumableBudget, ContiguousGroup,
+    DynConstraintKind, DynamicConstraint, DynamicConstraintIndex, FnDynamicConstraint,
+    MaxParallelism, PowerEnvelope, SchedulingContext,
 };
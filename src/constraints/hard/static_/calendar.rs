@@ -0,0 +1,294 @@
+//! Calendar availability in a time zone (`calendar` feature).
+//!
+//! Ground-station passes and staffing rotas are naturally written as weekly
+//! patterns in local time — "weekdays 09:00–17:30 Europe/Madrid" — with a
+//! list of holidays on top. [`CalendarConstraint`] expands such a pattern
+//! into windows on the relative axis through an [`Epoch`], following the
+//! zone's daylight-saving transitions.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday};
+use chrono_tz::Tz;
+
+use super::constraint::Constraint;
+use crate::epoch::{AbsoluteTime, Epoch};
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::time::Time;
+use qtty::{Quantity, Unit};
+
+/// One weekly availability slot, in local time.
+///
+/// A slot whose end is not after its start runs past midnight into the
+/// next day; `00:00–00:00` covers the whole day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeeklySlot {
+    pub weekday: Weekday,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+/// Weekly local-time availability minus holidays, as a static constraint.
+///
+/// Local times that fall in a daylight-saving gap are moved to the end of
+/// the gap; ambiguous ones resolve to the earlier instant for slot starts
+/// and the later one for slot ends, so repeated hours stay available.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{NaiveDate, NaiveTime, Weekday};
+/// use virolai::constraints::hard::static_::CalendarConstraint;
+/// use virolai::constraints::Constraint;
+/// use virolai::epoch::{AbsoluteTime, Epoch};
+/// use virolai::solution_space::Interval;
+/// use qtty::Hour;
+///
+/// // Relative zero is Monday 2024-01-01 00:00 UTC, in hours.
+/// let epoch = Epoch::<Hour>::new(AbsoluteTime::from_utc(2024, 1, 1, 0, 0, 0.0));
+/// let office = CalendarConstraint::new(chrono_tz::Europe::Madrid, epoch)
+///     .with_weekdays(
+///         &[Weekday::Mon, Weekday::Tue],
+///         NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+///         NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+///     )
+///     .with_exception(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap());
+///
+/// // Madrid is UTC+1 in winter; Tuesday is a holiday.
+/// let windows = office.compute_intervals(Interval::from_f64(0.0, 168.0));
+/// assert_eq!(windows.as_slice(), &[Interval::from_f64(8.0, 16.0)]);
+/// ```
+#[derive(Clone)]
+pub struct CalendarConstraint<U: Unit<Dim = Time>> {
+    tz: Tz,
+    epoch: Epoch<U>,
+    slots: Vec<WeeklySlot>,
+    exceptions: BTreeSet<NaiveDate>,
+}
+
+impl<U: Unit<Dim = Time>> CalendarConstraint<U> {
+    /// Creates a calendar in `tz`, mapped onto the axis by `epoch`, with no
+    /// availability.
+    pub fn new(tz: Tz, epoch: Epoch<U>) -> Self {
+        Self {
+            tz,
+            epoch,
+            slots: Vec::new(),
+            exceptions: BTreeSet::new(),
+        }
+    }
+
+    /// Adds a slot from `start` to `end` local time every `weekday`.
+    pub fn with_slot(mut self, weekday: Weekday, start: NaiveTime, end: NaiveTime) -> Self {
+        self.slots.push(WeeklySlot {
+            weekday,
+            start,
+            end,
+        });
+        self
+    }
+
+    /// Adds the same slot on each of `weekdays`.
+    pub fn with_weekdays(mut self, weekdays: &[Weekday], start: NaiveTime, end: NaiveTime) -> Self {
+        for &weekday in weekdays {
+            self = self.with_slot(weekday, start, end);
+        }
+        self
+    }
+
+    /// Removes the whole local day `date`.
+    pub fn with_exception(mut self, date: NaiveDate) -> Self {
+        self.exceptions.insert(date);
+        self
+    }
+
+    /// Removes every local day in `dates`.
+    pub fn with_exceptions(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.exceptions.extend(dates);
+        self
+    }
+
+    /// Time zone the slots and exceptions are read in.
+    pub fn tz(&self) -> Tz {
+        self.tz
+    }
+
+    /// Weekly slots, in the order they were added.
+    pub fn slots(&self) -> &[WeeklySlot] {
+        &self.slots
+    }
+
+    /// Holiday dates, ascending.
+    pub fn exceptions(&self) -> impl Iterator<Item = &NaiveDate> + '_ {
+        self.exceptions.iter()
+    }
+
+    /// Local date of the axis value `t`.
+    fn local_date(&self, t: Quantity<U>) -> NaiveDate {
+        let seconds = self.epoch.to_absolute(t).unix_seconds().floor() as i64;
+        self.tz
+            .timestamp_opt(seconds, 0)
+            .earliest()
+            .map_or(NaiveDate::MIN, |dt| dt.date_naive())
+    }
+
+    /// Axis value of the local time `local`; see the type docs for gaps and
+    /// ambiguous times.
+    fn to_axis(&self, local: NaiveDateTime, earliest: bool) -> Quantity<U> {
+        let mut local = local;
+        // Gaps are at most a few hours; step forward until past one.
+        let resolved = loop {
+            let result = self.tz.from_local_datetime(&local);
+            let instant = if earliest {
+                result.earliest()
+            } else {
+                result.latest()
+            };
+            match instant {
+                Some(instant) => break instant,
+                None => local += Duration::minutes(15),
+            }
+        };
+        self.epoch
+            .to_relative(AbsoluteTime::from_unix_seconds(resolved.timestamp() as f64))
+    }
+
+    /// Local day `date` on the axis.
+    fn day(&self, date: NaiveDate) -> Interval<U> {
+        let next = date.succ_opt().unwrap_or(date);
+        Interval::new(
+            self.to_axis(date.and_time(NaiveTime::MIN), true),
+            self.to_axis(next.and_time(NaiveTime::MIN), true),
+        )
+    }
+}
+
+impl<U: Unit<Dim = Time> + Send + Sync> Constraint<U> for CalendarConstraint<U> {
+    fn compute_intervals(&self, range: Interval<U>) -> IntervalSet<U> {
+        if range.is_empty() || self.slots.is_empty() {
+            return IntervalSet::new();
+        }
+        // Start a day early for slots running past midnight into the range.
+        let first = self
+            .local_date(range.start())
+            .pred_opt()
+            .unwrap_or(NaiveDate::MIN);
+        let last = self.local_date(range.end());
+
+        let mut windows = Vec::new();
+        for date in first.iter_days().take_while(|d| *d <= last) {
+            for slot in self.slots.iter().filter(|s| s.weekday == date.weekday()) {
+                let end_date = if slot.end > slot.start {
+                    date
+                } else {
+                    date.succ_opt().unwrap_or(date)
+                };
+                let window = Interval::new(
+                    self.to_axis(date.and_time(slot.start), true),
+                    self.to_axis(end_date.and_time(slot.end), false),
+                );
+                windows.extend(window.intersection(&range));
+            }
+        }
+        let available = IntervalSet::from(windows);
+
+        let holidays: Vec<_> = self
+            .exceptions
+            .range(first..=last)
+            .filter_map(|&date| self.day(date).intersection(&range))
+            .collect();
+        if holidays.is_empty() {
+            available
+        } else {
+            available.intersection(&IntervalSet::from(holidays).complement(range))
+        }
+    }
+
+    fn stringify(&self) -> String {
+        format!(
+            "Calendar({}, {} slots, {} exceptions)",
+            self.tz,
+            self.slots.len(),
+            self.exceptions.len()
+        )
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Debug for CalendarConstraint<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CalendarConstraint")
+            .field("tz", &self.tz)
+            .field("origin", &self.epoch.origin())
+            .field("slots", &self.slots)
+            .field("exceptions", &self.exceptions)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qtty::Hour;
+
+    fn hm(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn iv(start: f64, end: f64) -> Interval<Hour> {
+        Interval::from_f64(start, end)
+    }
+
+    /// Relative zero at 2024-03-25 00:00 UTC, a Monday; hours.
+    fn epoch() -> Epoch<Hour> {
+        Epoch::new(AbsoluteTime::from_utc(2024, 3, 25, 0, 0, 0.0))
+    }
+
+    #[test]
+    fn overnight_slots_cross_midnight() {
+        let night = CalendarConstraint::new(chrono_tz::UTC, epoch()).with_slot(
+            Weekday::Sun,
+            hm(22, 0),
+            hm(2, 0),
+        );
+        // Sunday 2024-03-24 22:00 to Monday 02:00, clipped to the range.
+        let windows = night.compute_intervals(iv(0.0, 168.0));
+        assert_eq!(windows.as_slice(), &[iv(0.0, 2.0), iv(166.0, 168.0)]);
+    }
+
+    #[test]
+    fn daylight_saving_shifts_utc_windows() {
+        // Europe/Berlin switches to CEST on Sunday 2024-03-31.
+        let office = CalendarConstraint::new(chrono_tz::Europe::Berlin, epoch()).with_weekdays(
+            &[Weekday::Fri, Weekday::Mon],
+            hm(9, 0),
+            hm(17, 0),
+        );
+        let windows = office.compute_intervals(iv(96.0, 200.0));
+        // Friday 09:00 CET = 08:00 UTC; Monday 09:00 CEST = 07:00 UTC.
+        assert_eq!(windows.as_slice(), &[iv(104.0, 112.0), iv(175.0, 183.0)]);
+    }
+
+    #[test]
+    fn gap_times_move_past_the_transition() {
+        // 02:30 does not exist in Berlin on 2024-03-31; the slot starts at
+        // 03:00 CEST, 01:00 UTC.
+        let early = CalendarConstraint::new(chrono_tz::Europe::Berlin, epoch()).with_slot(
+            Weekday::Sun,
+            hm(2, 30),
+            hm(4, 0),
+        );
+        let windows = early.compute_intervals(iv(144.0, 168.0));
+        assert_eq!(windows.as_slice(), &[iv(145.0, 146.0)]);
+    }
+
+    #[test]
+    fn exceptions_remove_whole_local_days() {
+        let all_day = CalendarConstraint::new(chrono_tz::UTC, epoch())
+            .with_weekdays(&[Weekday::Mon, Weekday::Tue], hm(0, 0), hm(0, 0))
+            .with_exception(NaiveDate::from_ymd_opt(2024, 3, 26).unwrap());
+        let windows = all_day.compute_intervals(iv(0.0, 48.0));
+        assert_eq!(windows.as_slice(), &[iv(0.0, 24.0)]);
+        assert_eq!(all_day.stringify(), "Calendar(UTC, 2 slots, 1 exceptions)");
+    }
+}
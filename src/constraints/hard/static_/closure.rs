@@ -0,0 +1,115 @@
+//! Static constraints from closures.
+//!
+//! A one-off window rule — "not during the maintenance slot", "only on even
+//! hours" — should not need a new type. [`FnConstraint`] wraps a closure
+//! from a range to its feasible intervals. The closure is type-erased, so
+//! constraints built from different closures share one type and mix freely
+//! as leaves of a [`ConstraintExpr`](crate::constraints::ConstraintExpr).
+
+use std::fmt;
+use std::sync::Arc;
+
+use super::constraint::Constraint;
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::Unit;
+
+type WindowFn<U> = dyn Fn(Interval<U>) -> IntervalSet<U> + Send + Sync;
+
+/// A [`Constraint`] computed by a closure.
+///
+/// The closure must honour the [`Constraint`] contract: sorted,
+/// non-overlapping intervals within the range.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::hard::static_::FnConstraint;
+/// use virolai::constraints::{Constraint, ConstraintExpr};
+/// use virolai::solution_space::{Interval, IntervalSet};
+/// use qtty::Second;
+///
+/// let maintenance = Interval::<Second>::from_f64(40.0, 60.0);
+/// let not_maintenance = FnConstraint::new(move |range| {
+///     IntervalSet::from(maintenance).complement(range)
+/// })
+/// .with_name("not maintenance");
+/// let morning = FnConstraint::new(|range: Interval<Second>| {
+///     range
+///         .intersection(&Interval::from_f64(0.0, 50.0))
+///         .map_or_else(IntervalSet::new, IntervalSet::from)
+/// });
+///
+/// let expr = ConstraintExpr::intersection(vec![
+///     ConstraintExpr::leaf(not_maintenance),
+///     ConstraintExpr::leaf(morning),
+/// ]);
+/// let windows = expr.compute_intervals(Interval::from_f64(0.0, 100.0));
+/// assert_eq!(windows.as_slice(), &[Interval::from_f64(0.0, 40.0)]);
+/// ```
+pub struct FnConstraint<U: Unit> {
+    f: Arc<WindowFn<U>>,
+    name: String,
+}
+
+impl<U: Unit> FnConstraint<U> {
+    /// Wraps `f`, named `"FnConstraint"`.
+    pub fn new(f: impl Fn(Interval<U>) -> IntervalSet<U> + Send + Sync + 'static) -> Self {
+        Self {
+            f: Arc::new(f),
+            name: "FnConstraint".to_string(),
+        }
+    }
+
+    /// Sets the name returned by [`stringify`](Constraint::stringify).
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+}
+
+impl<U: Unit> Clone for FnConstraint<U> {
+    fn clone(&self) -> Self {
+        Self {
+            f: Arc::clone(&self.f),
+            name: self.name.clone(),
+        }
+    }
+}
+
+impl<U: Unit> fmt::Debug for FnConstraint<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FnConstraint")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<U: Unit> Constraint<U> for FnConstraint<U> {
+    fn compute_intervals(&self, range: Interval<U>) -> IntervalSet<U> {
+        (self.f)(range)
+    }
+
+    fn stringify(&self) -> String {
+        self.name.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    #[test]
+    fn delegates_to_the_closure() {
+        let c = FnConstraint::<Second>::new(IntervalSet::from);
+        assert_eq!(
+            c.compute_intervals(iv(0.0, 10.0)).as_slice(),
+            &[iv(0.0, 10.0)]
+        );
+        assert_eq!(c.stringify(), "FnConstraint");
+        let named = c.clone().with_name("all");
+        assert_eq!(named.stringify(), "all");
+        assert_eq!(format!("{named:?}"), "FnConstraint { name: \"all\", .. }");
+    }
+}
@@ -10,6 +10,9 @@ use crate::solution_space::IntervalSet;
 use qtty::Unit;
 use std::fmt::Debug;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Computes intervals where a scheduling condition is satisfied.
 ///
 /// Constraints compose via combinators ([`ConstraintExpr`](crate::constraints::ConstraintExpr))
@@ -35,7 +38,14 @@ pub trait Constraint<U: Unit>: Send + Sync + Debug {
 }
 
 /// A fixed-window constraint that allows scheduling only within `[allowed_start, allowed_end]`.
+///
+/// With the `serde` feature it serializes as its interval,
+/// `{"start": .., "end": ..}`.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+#[cfg_attr(feature = "schema", schemars(bound = ""))]
 pub struct IntervalConstraint<U: Unit + Send + Sync>(Interval<U>);
 
 impl<U: Unit + Send + Sync> IntervalConstraint<U> {
@@ -3,11 +3,24 @@
 //! Feasibility windows fully determined before the scheduling loop.
 //! Produces a binary accept/reject (hard) decision from fixed (static) data.
 //!
-//! The [`Constraint`] trait and the built-in [`IntervalConstraint`] live here.
+//! The [`Constraint`] trait, the built-in [`IntervalConstraint`] and
+//! [`PeriodicConstraint`], and the closure adapter [`FnConstraint`] live
+//! here. With the `calendar` feature, `CalendarConstraint` adds weekly
+//! local-time availability.
 
+#[cfg(feature = "calendar")]
+pub mod calendar;
+pub mod closure;
 pub mod constraint;
+pub mod periodic;
+pub mod relaxation;
 pub mod resource;
 
+#[cfg(feature = "calendar")]
+pub use calendar::{CalendarConstraint, WeeklySlot};
+pub use closure::FnConstraint;
 pub use constraint::Constraint;
 pub use constraint::IntervalConstraint;
-pub use resource::ResourceConstraint;
+pub use periodic::PeriodicConstraint;
+pub use relaxation::{Relaxable, RelaxationLadder};
+pub use resource::{CapacityProfile, ResourceConstraint};
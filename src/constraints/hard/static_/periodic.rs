@@ -0,0 +1,154 @@
+//! Repeating windows at a fixed period.
+
+use super::constraint::Constraint;
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::{Quantity, Unit};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Windows `[start + k·period, start + k·period + width)` for `k = 0, 1, …`.
+///
+/// Nightly slots, orbit passes and shift rotations repeat with a fixed
+/// period on the axis. Windows wider than the period merge.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::{Constraint, PeriodicConstraint};
+/// use virolai::solution_space::Interval;
+/// use qtty::{Quantity, Second};
+///
+/// // One hour a day, from 01:00.
+/// let nightly = PeriodicConstraint::<Second>::new(
+///     Quantity::new(3600.0),
+///     Quantity::new(86400.0),
+///     Quantity::new(3600.0),
+/// );
+/// let windows = nightly.compute_intervals(Interval::from_f64(0.0, 2.0 * 86400.0));
+/// assert_eq!(
+///     windows.as_slice(),
+///     &[Interval::from_f64(3600.0, 7200.0), Interval::from_f64(90000.0, 93600.0)]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+#[cfg_attr(feature = "schema", schemars(bound = ""))]
+pub struct PeriodicConstraint<U: Unit> {
+    #[cfg_attr(feature = "schema", schemars(with = "f64"))]
+    start: Quantity<U>,
+    #[cfg_attr(feature = "schema", schemars(with = "f64"))]
+    period: Quantity<U>,
+    #[cfg_attr(feature = "schema", schemars(with = "f64"))]
+    width: Quantity<U>,
+}
+
+impl<U: Unit> PeriodicConstraint<U> {
+    /// Creates windows of `width` every `period`, the first at `start`.
+    ///
+    /// # Panics
+    ///
+    /// If `start` is not finite, or `period` or `width` is not finite and
+    /// positive.
+    pub fn new(start: Quantity<U>, period: Quantity<U>, width: Quantity<U>) -> Self {
+        assert!(
+            start.value().is_finite(),
+            "periodic start must be finite, got {}",
+            start.value()
+        );
+        for (name, value) in [("period", period), ("width", width)] {
+            assert!(
+                value.value().is_finite() && value.value() > 0.0,
+                "periodic {name} must be finite and positive, got {}",
+                value.value()
+            );
+        }
+        Self {
+            start,
+            period,
+            width,
+        }
+    }
+
+    /// Start of the first window.
+    pub fn start(&self) -> Quantity<U> {
+        self.start
+    }
+
+    /// Distance between window starts.
+    pub fn period(&self) -> Quantity<U> {
+        self.period
+    }
+
+    /// Length of each window.
+    pub fn width(&self) -> Quantity<U> {
+        self.width
+    }
+}
+
+impl<U: Unit + Send + Sync> Constraint<U> for PeriodicConstraint<U> {
+    fn compute_intervals(&self, range: Interval<U>) -> IntervalSet<U> {
+        let (start, period, width) = (self.start.value(), self.period.value(), self.width.value());
+        // First window that can still reach the range.
+        let first = ((range.start().value() - start - width) / period)
+            .floor()
+            .max(0.0);
+        let mut windows = Vec::new();
+        let mut k = first;
+        loop {
+            let at = start + k * period;
+            if at >= range.end().value() {
+                break;
+            }
+            let window = Interval::new(Quantity::new(at), Quantity::new(at + width));
+            windows.extend(window.intersection(&range).filter(|w| !w.is_empty()));
+            k += 1.0;
+        }
+        IntervalSet::from(windows)
+    }
+
+    fn stringify(&self) -> String {
+        format!(
+            "Periodic(start={}, period={}, width={})",
+            self.start.value(),
+            self.period.value(),
+            self.width.value()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    #[test]
+    fn windows_are_clipped_to_the_range() {
+        let c = PeriodicConstraint::<Second>::new(q(10.0), q(100.0), q(20.0));
+        assert_eq!(
+            c.compute_intervals(iv(15.0, 215.0)).as_slice(),
+            &[iv(15.0, 30.0), iv(110.0, 130.0), iv(210.0, 215.0)]
+        );
+        // Nothing before the first window.
+        assert!(c.compute_intervals(iv(-500.0, 10.0)).is_empty());
+    }
+
+    #[test]
+    fn wide_windows_merge() {
+        let c = PeriodicConstraint::<Second>::new(q(0.0), q(10.0), q(15.0));
+        assert_eq!(
+            c.compute_intervals(iv(0.0, 30.0)).as_slice(),
+            &[iv(0.0, 30.0)]
+        );
+        assert_eq!(c.stringify(), "Periodic(start=0, period=10, width=15)");
+    }
+
+    #[test]
+    #[should_panic(expected = "periodic period")]
+    fn zero_period_is_rejected() {
+        let _ = PeriodicConstraint::<Second>::new(q(0.0), q(0.0), q(1.0));
+    }
+}
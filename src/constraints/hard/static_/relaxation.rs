@@ -0,0 +1,221 @@
+//! Ordered relaxation levels for static constraints.
+//!
+//! Operational limits often come in tiers: a *nominal* limit used by default,
+//! a *relaxed* limit that is acceptable when needed, and an *emergency* limit
+//! approved only as a last resort. A [`RelaxationLadder`] declares those tiers
+//! for one constraint, most restrictive first. Used as the leaf type of a
+//! [`ConstraintExpr`], the whole tree can then be evaluated at any level via
+//! [`Relaxable::compute_intervals_at`]:
+//!
+//! ```text
+//!   level 0 (nominal)   ──▶ every ladder uses its first entry
+//!   level 1 (relaxed)   ──▶ every ladder uses its second entry (or its last)
+//!   level 2 (emergency) ──▶ …
+//! ```
+//!
+//! Schedulers use this to retry infeasible tasks at increasing levels; see
+//! [`ESTScheduler::schedule_relaxed`](crate::algorithms::ESTScheduler::schedule_relaxed).
+//!
+//! # Negation
+//!
+//! A ladder under a `Not` node inverts its effect: widening the inner window
+//! narrows the overall result. Ladders are intended to widen feasibility, so
+//! place them outside negations.
+
+use super::constraint::Constraint;
+use crate::constraints::operations::{
+    compute_complement, compute_intersection_many, compute_union,
+};
+use crate::constraints::ConstraintExpr;
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::Unit;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A constraint that can be evaluated at an explicit relaxation level.
+pub trait Relaxable<U: Unit>: Constraint<U> {
+    /// Computes intervals at relaxation `level` (0 = nominal).
+    ///
+    /// Levels beyond [`max_level`](Self::max_level) evaluate as the last level.
+    fn compute_intervals_at(&self, range: Interval<U>, level: usize) -> IntervalSet<U>;
+
+    /// Highest distinct relaxation level (0 if the constraint cannot relax).
+    fn max_level(&self) -> usize;
+}
+
+/// Ordered relaxation levels for a single constraint, most restrictive first.
+///
+/// Evaluating it as a plain [`Constraint`] uses the nominal level.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::hard::static_::{Relaxable, RelaxationLadder};
+/// use virolai::constraints::IntervalConstraint;
+/// use virolai::solution_space::Interval;
+/// use qtty::Second;
+///
+/// let ladder = RelaxationLadder::new(IntervalConstraint::new(Interval::<Second>::from_f64(20.0, 40.0)))
+///     .then(IntervalConstraint::new(Interval::from_f64(10.0, 50.0)))
+///     .then(IntervalConstraint::new(Interval::from_f64(0.0, 60.0)));
+///
+/// let range = Interval::from_f64(0.0, 100.0);
+/// assert_eq!(ladder.max_level(), 2);
+/// assert_eq!(ladder.compute_intervals_at(range, 1)[0], Interval::from_f64(10.0, 50.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RelaxationLadder<C> {
+    levels: Vec<C>,
+}
+
+impl<C> RelaxationLadder<C> {
+    /// Creates a ladder with only the nominal level.
+    pub fn new(nominal: C) -> Self {
+        Self {
+            levels: vec![nominal],
+        }
+    }
+
+    /// Appends the next, less restrictive level.
+    pub fn then(mut self, relaxed: C) -> Self {
+        self.levels.push(relaxed);
+        self
+    }
+
+    /// Returns the number of declared levels (≥ 1).
+    pub fn level_count(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns the constraint for `level`, clamped to the last declared level.
+    pub fn at_level(&self, level: usize) -> &C {
+        &self.levels[level.min(self.levels.len() - 1)]
+    }
+
+    /// Returns the nominal (level 0) constraint.
+    pub fn nominal(&self) -> &C {
+        &self.levels[0]
+    }
+}
+
+impl<U: Unit, C: Constraint<U>> Constraint<U> for RelaxationLadder<C> {
+    fn compute_intervals(&self, range: Interval<U>) -> IntervalSet<U> {
+        self.nominal().compute_intervals(range)
+    }
+
+    fn stringify(&self) -> String {
+        let levels: Vec<String> = self.levels.iter().map(|c| c.stringify()).collect();
+        format!("Ladder[{}]", levels.join(" → "))
+    }
+}
+
+impl<U: Unit, C: Constraint<U>> Relaxable<U> for RelaxationLadder<C> {
+    fn compute_intervals_at(&self, range: Interval<U>, level: usize) -> IntervalSet<U> {
+        self.at_level(level).compute_intervals(range)
+    }
+
+    fn max_level(&self) -> usize {
+        self.levels.len() - 1
+    }
+}
+
+impl<U: Unit, C: Relaxable<U>> Relaxable<U> for ConstraintExpr<C> {
+    fn compute_intervals_at(&self, range: Interval<U>, level: usize) -> IntervalSet<U> {
+        match self {
+            ConstraintExpr::Leaf(c) => c.compute_intervals_at(range, level),
+            ConstraintExpr::Not { child, .. } => {
+                compute_complement(child.compute_intervals_at(range, level).into_inner(), range)
+            }
+            ConstraintExpr::Intersection { children, .. } => compute_intersection_many(
+                &children
+                    .iter()
+                    .map(|c| c.compute_intervals_at(range, level))
+                    .collect::<Vec<_>>(),
+            ),
+            ConstraintExpr::Union { children, .. } => children
+                .iter()
+                .map(|c| c.compute_intervals_at(range, level))
+                .fold(IntervalSet::new(), |acc, v| compute_union(&acc, &v)),
+        }
+    }
+
+    fn max_level(&self) -> usize {
+        match self {
+            ConstraintExpr::Leaf(c) => c.max_level(),
+            ConstraintExpr::Not { child, .. } => child.max_level(),
+            ConstraintExpr::Intersection { children, .. }
+            | ConstraintExpr::Union { children, .. } => {
+                children.iter().map(|c| c.max_level()).max().unwrap_or(0)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::IntervalConstraint;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn ladder() -> RelaxationLadder<IntervalConstraint<Second>> {
+        RelaxationLadder::new(IntervalConstraint::new(iv(20.0, 40.0)))
+            .then(IntervalConstraint::new(iv(10.0, 50.0)))
+    }
+
+    #[test]
+    fn plain_evaluation_uses_nominal() {
+        let r = ladder().compute_intervals(iv(0.0, 100.0));
+        assert_eq!(r, vec![iv(20.0, 40.0)]);
+    }
+
+    #[test]
+    fn levels_clamp_to_last() {
+        let l = ladder();
+        assert_eq!(l.level_count(), 2);
+        assert_eq!(l.max_level(), 1);
+        assert_eq!(
+            l.compute_intervals_at(iv(0.0, 100.0), 1),
+            vec![iv(10.0, 50.0)]
+        );
+        assert_eq!(
+            l.compute_intervals_at(iv(0.0, 100.0), 7),
+            vec![iv(10.0, 50.0)]
+        );
+    }
+
+    #[test]
+    fn tree_relaxes_every_ladder() {
+        let tree = ConstraintExpr::intersection(vec![
+            ConstraintExpr::leaf(ladder()),
+            ConstraintExpr::leaf(
+                RelaxationLadder::new(IntervalConstraint::new(iv(30.0, 60.0)))
+                    .then(IntervalConstraint::new(iv(25.0, 60.0)))
+                    .then(IntervalConstraint::new(iv(0.0, 60.0))),
+            ),
+        ]);
+        assert_eq!(tree.max_level(), 2);
+        assert_eq!(
+            tree.compute_intervals_at(iv(0.0, 100.0), 0),
+            vec![iv(30.0, 40.0)]
+        );
+        assert_eq!(
+            tree.compute_intervals_at(iv(0.0, 100.0), 1),
+            vec![iv(25.0, 50.0)]
+        );
+        assert_eq!(
+            tree.compute_intervals_at(iv(0.0, 100.0), 2),
+            vec![iv(10.0, 50.0)]
+        );
+    }
+
+    #[test]
+    fn stringify_lists_levels() {
+        assert_eq!(
+            ladder().stringify(),
+            "Ladder[[20.000, 40.000] → [10.000, 50.000]]"
+        );
+    }
+}
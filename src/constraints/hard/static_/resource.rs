@@ -1,19 +1,113 @@
 //! Resource constraint — restricts a task to eligible resources by ID and/or type.
 //!
 //! This is a **hard + static** constraint: it determines at pre-scheduling time
-//! which resources can host a given task. Because resource eligibility is not
-//! time-dependent, `compute_intervals` returns the full scheduling range —
-//! the actual filtering is performed by the prescheduler, which inspects these
-//! constraints to decide which (resource, task) pairs to populate.
+//! which resources can host a given task. Resource eligibility is not
+//! time-dependent — the actual filtering is performed by the prescheduler,
+//! which inspects these constraints to decide which (resource, task) pairs to
+//! populate.
+//!
+//! # Capacity
+//!
+//! A resource pool can also have a [`CapacityProfile`]: a step function of
+//! how many units are available over time (two antennas at night, one during
+//! the day). With a profile attached, `compute_intervals` returns the windows
+//! where the task's requested [`amount`](ResourceConstraint::amount) fits at
+//! all, and the [`DynamicConstraint`] implementation narrows them further to
+//! where it fits next to the tasks already drawing on the pool.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+use crate::constraints::hard::dynamic::{DynamicConstraint, SchedulingContext};
+use crate::schedule::Schedule;
 use crate::solution_space::{Interval, IntervalSet};
-use qtty::Unit;
+use crate::Id;
+use qtty::{Quantity, Unit};
 
 use super::constraint::Constraint;
 
+/// Units of a resource available over time, as a step function.
+///
+/// The profile starts at an initial capacity and changes at each step, in
+/// axis values, holding until the next one. Like the lags of
+/// [`DynConstraintKind`](crate::constraints::DynConstraintKind), step
+/// positions are plain numbers read on whatever axis the constraint is
+/// evaluated.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::CapacityProfile;
+///
+/// // Two antennas at night, one between 06:00 and 18:00 (axis in hours).
+/// let antennas = CapacityProfile::constant(2)
+///     .with_step(6.0, 1)
+///     .with_step(18.0, 2);
+/// assert_eq!(antennas.capacity_at(3.0), 2);
+/// assert_eq!(antennas.capacity_at(6.0), 1);
+/// assert_eq!(antennas.capacity_at(20.0), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapacityProfile {
+    initial: u32,
+    /// `(at, capacity)` pairs, sorted by `at`.
+    steps: Vec<(f64, u32)>,
+}
+
+impl CapacityProfile {
+    /// Creates a profile holding `capacity` units at all times.
+    pub fn constant(capacity: u32) -> Self {
+        Self {
+            initial: capacity,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Changes the capacity to `capacity` from `at` onwards, replacing any
+    /// step already at `at`.
+    ///
+    /// # Panics
+    ///
+    /// If `at` is not finite.
+    pub fn with_step(mut self, at: f64, capacity: u32) -> Self {
+        assert!(at.is_finite(), "capacity steps must be finite");
+        match self.steps.binary_search_by(|(t, _)| t.total_cmp(&at)) {
+            Ok(i) => self.steps[i].1 = capacity,
+            Err(i) => self.steps.insert(i, (at, capacity)),
+        }
+        self
+    }
+
+    /// Units available at `t`.
+    pub fn capacity_at(&self, t: f64) -> u32 {
+        let after = self.steps.partition_point(|&(at, _)| at <= t);
+        match after {
+            0 => self.initial,
+            i => self.steps[i - 1].1,
+        }
+    }
+
+    /// Fewest units available anywhere in `interval`.
+    pub fn min_capacity<U: Unit>(&self, interval: Interval<U>) -> u32 {
+        let (start, end) = (interval.start().value(), interval.end().value());
+        self.steps
+            .iter()
+            .filter(|&&(at, _)| start < at && at < end)
+            .map(|&(_, capacity)| capacity)
+            .fold(self.capacity_at(start), u32::min)
+    }
+}
+
+impl fmt::Display for CapacityProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.initial)?;
+        for (at, capacity) in &self.steps {
+            write!(f, ", {}@{}", capacity, at)?;
+        }
+        Ok(())
+    }
+}
+
 /// Constrains a task to run only on resources whose ID or type matches.
 ///
 /// - `allowed_ids`: if `Some`, the task may only be scheduled on resources whose
@@ -24,6 +118,12 @@ use super::constraint::Constraint;
 /// When both are `Some`, a resource is eligible if it matches **either** (union).
 /// When both are `None`, the constraint is vacuous (all resources are eligible).
 ///
+/// With [`with_capacity`](Self::with_capacity), the task also draws
+/// [`amount`](Self::amount) units from a pool whose size follows a
+/// [`CapacityProfile`]. Tasks registered with
+/// [`with_consumer`](Self::with_consumer) use the same pool; their
+/// placements in the partial schedule make up its usage profile.
+///
 /// # Example
 ///
 /// ```
@@ -39,12 +139,38 @@ use super::constraint::Constraint;
 /// assert!(by_type.matches("LST3", "LST"));
 /// assert!(!by_type.matches("MAGIC1", "MAGIC"));
 /// ```
+///
+/// Capacity over time:
+///
+/// ```
+/// use virolai::constraints::CapacityProfile;
+/// use virolai::constraints::{Constraint, ResourceConstraint};
+/// use virolai::solution_space::Interval;
+/// use qtty::Hour;
+///
+/// // Two antennas at night, one during the day; this pass needs both.
+/// let antennas = CapacityProfile::constant(2).with_step(6.0, 1).with_step(18.0, 2);
+/// let pass = ResourceConstraint::from_types(["antenna"])
+///     .with_capacity(antennas)
+///     .with_amount(2);
+/// let windows = pass.compute_intervals(Interval::<Hour>::from_f64(0.0, 24.0));
+/// assert_eq!(
+///     windows.as_slice(),
+///     &[Interval::from_f64(0.0, 6.0), Interval::from_f64(18.0, 24.0)]
+/// );
+/// ```
 #[derive(Debug, Clone)]
 pub struct ResourceConstraint {
     /// Allowed resource identifiers (e.g., `"LST1"`, `"MAGIC2"`).
     allowed_ids: Option<HashSet<String>>,
     /// Allowed resource type/category labels (e.g., `"LST"`, `"MAGIC"`).
     allowed_types: Option<HashSet<String>>,
+    /// Size of the shared pool over time, if limited.
+    capacity: Option<CapacityProfile>,
+    /// Units this task draws from the pool.
+    amount: u32,
+    /// Other tasks drawing on the pool, with the units each holds.
+    consumers: HashMap<Id, u32>,
 }
 
 impl ResourceConstraint {
@@ -56,6 +182,7 @@ impl ResourceConstraint {
         Self {
             allowed_ids,
             allowed_types,
+            ..Self::default()
         }
     }
 
@@ -64,6 +191,7 @@ impl ResourceConstraint {
         Self {
             allowed_ids: Some(ids.into_iter().map(Into::into).collect()),
             allowed_types: None,
+            ..Self::default()
         }
     }
 
@@ -72,6 +200,7 @@ impl ResourceConstraint {
         Self {
             allowed_ids: None,
             allowed_types: Some(types.into_iter().map(Into::into).collect()),
+            ..Self::default()
         }
     }
 
@@ -83,9 +212,38 @@ impl ResourceConstraint {
         Self {
             allowed_ids: Some(ids.into_iter().map(Into::into).collect()),
             allowed_types: Some(types.into_iter().map(Into::into).collect()),
+            ..Self::default()
         }
     }
 
+    /// Limits the shared pool to `profile`.
+    pub fn with_capacity(mut self, profile: CapacityProfile) -> Self {
+        self.capacity = Some(profile);
+        self
+    }
+
+    /// Sets the units this task draws from the pool (default 1).
+    pub fn with_amount(mut self, amount: u32) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    /// Registers a task that holds `amount` units of the pool while it runs.
+    pub fn with_consumer(mut self, task_id: impl Into<Id>, amount: u32) -> Self {
+        self.consumers.insert(task_id.into(), amount);
+        self
+    }
+
+    /// Registers several pool consumers.
+    pub fn with_consumers(
+        mut self,
+        consumers: impl IntoIterator<Item = (impl Into<Id>, u32)>,
+    ) -> Self {
+        self.consumers
+            .extend(consumers.into_iter().map(|(id, n)| (id.into(), n)));
+        self
+    }
+
     /// Returns the allowed resource IDs, if specified.
     pub fn allowed_ids(&self) -> Option<&HashSet<String>> {
         self.allowed_ids.as_ref()
@@ -96,6 +254,16 @@ impl ResourceConstraint {
         self.allowed_types.as_ref()
     }
 
+    /// Returns the pool's capacity profile, if limited.
+    pub fn capacity(&self) -> Option<&CapacityProfile> {
+        self.capacity.as_ref()
+    }
+
+    /// Units this task draws from the pool.
+    pub fn amount(&self) -> u32 {
+        self.amount
+    }
+
     /// Returns `true` if the given resource is eligible for this task.
     ///
     /// A resource matches if:
@@ -117,28 +285,194 @@ impl ResourceConstraint {
             _ => id_match || type_match,
         }
     }
+
+    /// Units held by the registered consumers over time, across every
+    /// resource schedule.
+    ///
+    /// Returns maximal pieces with a constant, non-zero usage, in time order.
+    /// A consumer placed on several resources at once holds its units once.
+    pub fn usage<U: Unit>(&self, resources: &HashMap<Id, Schedule<U>>) -> Vec<(Interval<U>, u32)> {
+        self.usage_in(resources.values(), None)
+    }
+
+    /// Returns `true` if usage never exceeds the capacity profile.
+    pub fn is_satisfied<U: Unit>(&self, resources: &HashMap<Id, Schedule<U>>) -> bool {
+        let Some(profile) = &self.capacity else {
+            return true;
+        };
+        self.usage(resources)
+            .into_iter()
+            .all(|(piece, used)| used <= profile.min_capacity(piece))
+    }
+
+    /// Parts of `range` where this task's amount fits next to the current
+    /// usage.
+    pub fn available<U: Unit>(
+        &self,
+        range: Interval<U>,
+        resources: &HashMap<Id, Schedule<U>>,
+    ) -> IntervalSet<U> {
+        self.fitting(range, &self.usage(resources))
+    }
+
+    /// Parts of `range` where `amount` more units fit under the profile,
+    /// given the current `usage` pieces.
+    fn fitting<U: Unit>(&self, range: Interval<U>, usage: &[(Interval<U>, u32)]) -> IntervalSet<U> {
+        let Some(profile) = &self.capacity else {
+            return IntervalSet::from(range);
+        };
+        let (lo, hi) = (range.start().value(), range.end().value());
+        let inside = |t: &f64| lo < *t && *t < hi;
+        let mut cuts = vec![lo, hi];
+        cuts.extend(profile.steps.iter().map(|&(at, _)| at).filter(inside));
+        cuts.extend(
+            usage
+                .iter()
+                .flat_map(|(piece, _)| [piece.start().value(), piece.end().value()])
+                .filter(inside),
+        );
+        cuts.sort_by(f64::total_cmp);
+        cuts.dedup();
+
+        let mut pieces = usage.iter().peekable();
+        let mut windows = Vec::new();
+        for cut in cuts.windows(2) {
+            let (a, b) = (cut[0], cut[1]);
+            while pieces
+                .next_if(|(piece, _)| piece.end().value() <= a)
+                .is_some()
+            {}
+            let used = pieces
+                .peek()
+                .filter(|(piece, _)| piece.start().value() <= a)
+                .map_or(0, |&&(_, n)| n);
+            if profile.capacity_at(a).saturating_sub(used) >= self.amount {
+                windows.push(Interval::new(Quantity::new(a), Quantity::new(b)));
+            }
+        }
+        IntervalSet::from(windows)
+    }
+
+    /// Sweeps the consumers' placements into a piecewise-constant usage,
+    /// leaving out `skip`.
+    fn usage_in<'a, U: Unit>(
+        &self,
+        schedules: impl Iterator<Item = &'a Schedule<U>>,
+        skip: Option<&str>,
+    ) -> Vec<(Interval<U>, u32)> {
+        // Union each consumer's placements so coalitions count once.
+        let mut per_task: HashMap<Id, Vec<Interval<U>>> = HashMap::new();
+        for schedule in schedules {
+            for (id, interval) in schedule.iter() {
+                if !interval.is_empty()
+                    && skip != Some(id.as_str())
+                    && self.consumers.contains_key(&id)
+                {
+                    per_task.entry(id).or_default().push(interval);
+                }
+            }
+        }
+
+        let mut events: Vec<(f64, i64)> = per_task
+            .into_iter()
+            .flat_map(|(id, ivs)| {
+                let units = i64::from(self.consumers[&id]);
+                IntervalSet::from(ivs)
+                    .into_inner()
+                    .into_iter()
+                    .flat_map(move |iv| [(iv.start().value(), units), (iv.end().value(), -units)])
+            })
+            .collect();
+        // Ends before starts at the same instant: intervals are half-open.
+        events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        let mut pieces: Vec<(Interval<U>, u32)> = Vec::new();
+        let mut running = 0i64;
+        for (i, &(at, delta)) in events.iter().enumerate() {
+            running += delta;
+            let Some(&(next, _)) = events.get(i + 1) else {
+                break;
+            };
+            if running == 0 || next == at {
+                continue;
+            }
+            let piece = Interval::new(Quantity::new(at), Quantity::new(next));
+            let used = running as u32;
+            match pieces.last_mut() {
+                Some((last, n)) if *n == used && last.end().value() == at => {
+                    *last = Interval::new(last.start(), piece.end());
+                }
+                _ => pieces.push((piece, used)),
+            }
+        }
+        pieces
+    }
+}
+
+impl Default for ResourceConstraint {
+    /// A vacuous constraint: every resource is eligible and the pool is
+    /// unlimited.
+    fn default() -> Self {
+        Self {
+            allowed_ids: None,
+            allowed_types: None,
+            capacity: None,
+            amount: 1,
+            consumers: HashMap::new(),
+        }
+    }
 }
 
 impl fmt::Display for ResourceConstraint {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match (&self.allowed_ids, &self.allowed_types) {
-            (Some(ids), Some(types)) => {
-                write!(f, "Resource(ids={:?}, types={:?})", ids, types)
-            }
-            (Some(ids), None) => write!(f, "Resource(ids={:?})", ids),
-            (None, Some(types)) => write!(f, "Resource(types={:?})", types),
-            (None, None) => write!(f, "Resource(any)"),
+            (Some(ids), Some(types)) => write!(f, "Resource(ids={:?}, types={:?}", ids, types)?,
+            (Some(ids), None) => write!(f, "Resource(ids={:?}", ids)?,
+            (None, Some(types)) => write!(f, "Resource(types={:?}", types)?,
+            (None, None) => write!(f, "Resource(any")?,
         }
+        if let Some(profile) = &self.capacity {
+            write!(f, ", amount={} of [{}]", self.amount, profile)?;
+        }
+        write!(f, ")")
     }
 }
 
-/// `ResourceConstraint` is not time-dependent — it always returns the full range.
+/// Eligibility is not time-dependent: without a capacity profile this
+/// returns the full range.
 ///
 /// The actual resource filtering is done by the prescheduler, which reads
 /// `allowed_ids`/`allowed_types` to decide which (resource, task) pairs to evaluate.
+/// With a profile, the range is narrowed to where the profile alone can
+/// supply [`amount`](ResourceConstraint::amount) units.
 impl<U: Unit> Constraint<U> for ResourceConstraint {
     fn compute_intervals(&self, range: Interval<U>) -> IntervalSet<U> {
-        IntervalSet::from(range)
+        self.fitting(range, &[])
+    }
+
+    fn stringify(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// Enforces the capacity profile against the consumers already placed.
+///
+/// Reads the schedules from [`SchedulingContext::resources`], falling back
+/// to the single [`SchedulingContext::schedule`]. The target task's own
+/// placement is left out of the usage, and the edge's reference task is not
+/// used, so the constraint can hang off any edge into the target.
+impl<U: Unit> DynamicConstraint<U> for ResourceConstraint {
+    fn compute_intervals(
+        &self,
+        range: Interval<U>,
+        _ref_task_id: &str,
+        ctx: &SchedulingContext<U>,
+    ) -> IntervalSet<U> {
+        let usage = match ctx.resources {
+            Some(resources) => self.usage_in(resources.values(), ctx.target_id),
+            None => self.usage_in(std::iter::once(ctx.schedule), ctx.target_id),
+        };
+        self.fitting(range, &usage)
     }
 
     fn stringify(&self) -> String {
@@ -149,6 +483,7 @@ impl<U: Unit> Constraint<U> for ResourceConstraint {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::iv;
     use qtty::Second;
 
     #[test]
@@ -185,8 +520,87 @@ mod tests {
     fn compute_intervals_returns_full_range() {
         let c = ResourceConstraint::from_ids(["LST1"]);
         let range = Interval::<Second>::from_f64(0.0, 100.0);
-        let result = c.compute_intervals(range);
+        let result = Constraint::compute_intervals(&c, range);
         assert_eq!(result.len(), 1);
         assert_eq!(result[0], range);
     }
+
+    fn antennas() -> CapacityProfile {
+        CapacityProfile::constant(2)
+            .with_step(6.0, 1)
+            .with_step(18.0, 2)
+    }
+
+    #[test]
+    fn capacity_profile_is_a_step_function() {
+        let p = antennas().with_step(6.0, 0);
+        assert_eq!(p.capacity_at(-100.0), 2);
+        assert_eq!(p.capacity_at(6.0), 0);
+        assert_eq!(p.capacity_at(17.9), 0);
+        assert_eq!(p.capacity_at(18.0), 2);
+        assert_eq!(p.min_capacity(iv(0.0, 6.0)), 2);
+        assert_eq!(p.min_capacity(iv(0.0, 6.5)), 0);
+        assert_eq!(p.to_string(), "2, 0@6, 2@18");
+    }
+
+    #[test]
+    fn static_windows_follow_the_profile() {
+        let single = ResourceConstraint::from_types(["antenna"]).with_capacity(antennas());
+        let windows = Constraint::compute_intervals(&single, iv(0.0, 24.0));
+        assert_eq!(windows.as_slice(), &[iv(0.0, 24.0)]);
+
+        let pair = single.clone().with_amount(2);
+        let windows = Constraint::compute_intervals(&pair, iv(3.0, 20.0));
+        assert_eq!(windows.as_slice(), &[iv(3.0, 6.0), iv(18.0, 20.0)]);
+
+        let triple = single.with_amount(3);
+        assert!(Constraint::compute_intervals(&triple, iv(0.0, 24.0)).is_empty());
+    }
+
+    fn plan(tasks: &[(&str, f64, f64)]) -> HashMap<Id, Schedule<Second>> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in tasks {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        HashMap::from([("r1".to_string(), s)])
+    }
+
+    #[test]
+    fn dynamic_windows_subtract_usage() {
+        let c = ResourceConstraint::from_types(["antenna"])
+            .with_capacity(antennas())
+            .with_consumers([("a", 1), ("b", 2)]);
+        let resources = plan(&[("a", 2.0, 8.0), ("x", 8.0, 9.0), ("b", 20.0, 22.0)]);
+
+        assert_eq!(
+            c.usage(&resources),
+            vec![(iv(2.0, 8.0), 1), (iv(20.0, 22.0), 2)]
+        );
+        // One unit is left at night while `a` runs; none during its daytime
+        // tail or while `b` holds both.
+        assert_eq!(
+            c.available(iv(0.0, 24.0), &resources).as_slice(),
+            &[iv(0.0, 6.0), iv(8.0, 20.0), iv(22.0, 24.0)]
+        );
+        assert!(c.is_satisfied(&resources));
+        assert!(!c.is_satisfied(&plan(&[("b", 5.0, 7.0)])));
+
+        let schedule = resources["r1"].clone();
+        let space = crate::solution_space::SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &space).with_target_id("a");
+        let windows = DynamicConstraint::compute_intervals(&c, iv(0.0, 24.0), "ignored", &ctx);
+        assert_eq!(windows.as_slice(), &[iv(0.0, 20.0), iv(22.0, 24.0)]);
+    }
+
+    #[test]
+    fn without_a_profile_the_pool_is_unlimited() {
+        let c = ResourceConstraint::from_ids(["LST1"]).with_consumer("a", 5);
+        let resources = plan(&[("a", 0.0, 10.0)]);
+        assert!(c.is_satisfied(&resources));
+        assert_eq!(
+            c.available(iv(0.0, 10.0), &resources).as_slice(),
+            &[iv(0.0, 10.0)]
+        );
+        assert_eq!(c.to_string(), r#"Resource(ids={"LST1"})"#);
+    }
 }
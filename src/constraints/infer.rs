@@ -0,0 +1,352 @@
+//! Draft constraint inference from historical schedules.
+//!
+//! Bootstrapping constraint models for an existing operation starts from what
+//! it actually did. [`ConstraintInference`] looks at a set of executed
+//! schedules, groups placements by task family and reports, per family:
+//!
+//! - the **envelope** — earliest start to latest end ever observed;
+//! - optionally a **recurring window** — the shortest phase range of a period
+//!   (e.g. one day) that covers every placement, such as "only ever ran
+//!   between 02:00 and 05:00".
+//!
+//! Each [`InferredWindow`] converts into a draft
+//! [`ConstraintExpr`] of [`IntervalConstraint`] leaves for review. The output
+//! describes past behaviour only; it is a starting point, not a model.
+//!
+//! # Recurring windows
+//!
+//! Phases are measured from axis zero modulo the period and treated as
+//! circular, so a window may wrap (22:00–02:00). The recurring window is the
+//! complement of the largest phase gap no placement touches. It is only
+//! reported if it covers at most `max_phase_fraction` of the period (half by
+//! default); wider windows say little beyond "any time".
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use super::{ConstraintExpr, IntervalConstraint};
+use crate::schedule::Schedule;
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::{Quantity, Unit};
+
+/// A window inferred for one task family.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferredWindow<U: Unit> {
+    pub family: String,
+    /// Number of placements observed.
+    pub support: usize,
+    /// Earliest start to latest end of all placements.
+    pub envelope: Interval<U>,
+    /// Phase range within the period covering every placement, as offsets
+    /// from the period start. `end` may exceed the period when the window
+    /// wraps.
+    pub recurring: Option<Interval<U>>,
+    /// Period the recurring window refers to.
+    pub period: Option<Quantity<U>>,
+}
+
+impl<U: Unit + Send + Sync> InferredWindow<U> {
+    /// Draft constraint tree over `horizon`.
+    ///
+    /// With a recurring window this is the union of its occurrences inside
+    /// `horizon`; otherwise a single leaf for the envelope.
+    pub fn to_constraint(&self, horizon: Interval<U>) -> ConstraintExpr<IntervalConstraint<U>> {
+        let (Some(phase), Some(period)) = (self.recurring, self.period) else {
+            return ConstraintExpr::leaf(IntervalConstraint::new(self.envelope));
+        };
+        let p = period.value();
+        let first = (horizon.start().value() / p).floor() - 1.0;
+        let last = (horizon.end().value() / p).ceil();
+        let mut leaves = Vec::new();
+        let mut k = first;
+        while k <= last {
+            let occurrence = Interval::new(
+                Quantity::new(k * p + phase.start().value()),
+                Quantity::new(k * p + phase.end().value()),
+            );
+            if let Some(clipped) = occurrence.intersection(&horizon) {
+                if !clipped.is_empty() {
+                    leaves.push(ConstraintExpr::leaf(IntervalConstraint::new(clipped)));
+                }
+            }
+            k += 1.0;
+        }
+        ConstraintExpr::union(leaves)
+    }
+}
+
+impl<U: Unit> fmt::Display for InferredWindow<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} placements within {}",
+            self.family, self.support, self.envelope
+        )?;
+        if let (Some(phase), Some(period)) = (self.recurring, self.period) {
+            write!(
+                f,
+                ", always at phase {}–{} of {}",
+                phase.start().value(),
+                phase.end().value(),
+                period.value()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Infers draft windows per task family from executed schedules.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::ConstraintInference;
+/// use virolai::schedule::Schedule;
+/// use virolai::solution_space::Interval;
+/// use qtty::{Hour, Quantity};
+///
+/// // Three nights of a calibration that always ran between 02:00 and 05:00.
+/// let mut nights = Vec::new();
+/// for day in 0..3 {
+///     let base = 24.0 * day as f64;
+///     let mut s = Schedule::<Hour>::new();
+///     s.add(format!("calib-{day}"), Interval::from_f64(base + 2.0, base + 5.0)).unwrap();
+///     nights.push(s);
+/// }
+///
+/// let inferred = ConstraintInference::new()
+///     .with_period(Quantity::new(24.0))
+///     .infer(&nights, |id| id.split('-').next().unwrap().to_string());
+///
+/// assert_eq!(inferred[0].family, "calib");
+/// assert_eq!(inferred[0].recurring, Some(Interval::from_f64(2.0, 5.0)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConstraintInference<U: Unit> {
+    period: Option<Quantity<U>>,
+    min_support: usize,
+    max_phase_fraction: f64,
+}
+
+impl<U: Unit> Default for ConstraintInference<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit> ConstraintInference<U> {
+    /// Creates an inference without a period, requiring 2 placements per
+    /// family.
+    pub fn new() -> Self {
+        Self {
+            period: None,
+            min_support: 2,
+            max_phase_fraction: 0.5,
+        }
+    }
+
+    /// Looks for recurring windows with this period (e.g. one day).
+    pub fn with_period(mut self, period: Quantity<U>) -> Self {
+        self.period = (period.value() > 0.0).then_some(period);
+        self
+    }
+
+    /// Families with fewer placements are not reported.
+    pub fn with_min_support(mut self, placements: usize) -> Self {
+        self.min_support = placements.max(1);
+        self
+    }
+
+    /// Widest recurring window reported, as a fraction of the period.
+    pub fn with_max_phase_fraction(mut self, fraction: f64) -> Self {
+        self.max_phase_fraction = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Infers one window per family, sorted by family name.
+    ///
+    /// `family_of` maps a task ID to its family; use the identity to infer
+    /// per task. Milestones take part like any other placement.
+    pub fn infer<'a>(
+        &self,
+        schedules: impl IntoIterator<Item = &'a Schedule<U>>,
+        family_of: impl Fn(&str) -> String,
+    ) -> Vec<InferredWindow<U>>
+    where
+        U: 'a,
+    {
+        let mut families: BTreeMap<String, Vec<Interval<U>>> = BTreeMap::new();
+        for schedule in schedules {
+            for (id, interval) in schedule.iter() {
+                families.entry(family_of(&id)).or_default().push(interval);
+            }
+        }
+
+        families
+            .into_iter()
+            .filter(|(_, placements)| placements.len() >= self.min_support)
+            .map(|(family, placements)| {
+                let start = placements
+                    .iter()
+                    .map(|iv| iv.start().value())
+                    .fold(f64::INFINITY, f64::min);
+                let end = placements
+                    .iter()
+                    .map(|iv| iv.end().value())
+                    .fold(f64::NEG_INFINITY, f64::max);
+                let recurring = self.period.and_then(|period| {
+                    recurring_window(&placements, period.value()).filter(|w| {
+                        w.duration().value() <= self.max_phase_fraction * period.value()
+                    })
+                });
+                InferredWindow {
+                    family,
+                    support: placements.len(),
+                    envelope: Interval::new(Quantity::new(start), Quantity::new(end)),
+                    recurring,
+                    period: recurring.and(self.period),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Shortest circular phase range covering every placement.
+fn recurring_window<U: Unit>(placements: &[Interval<U>], period: f64) -> Option<Interval<U>> {
+    let mut arcs = Vec::new();
+    for iv in placements {
+        let len = iv.duration().value();
+        if len >= period {
+            return None;
+        }
+        let start = iv.start().value().rem_euclid(period);
+        let end = start + len;
+        if end > period {
+            arcs.push((start, period));
+            arcs.push((0.0, end - period));
+        } else {
+            arcs.push((start, end));
+        }
+    }
+    let covered: IntervalSet<U> = arcs
+        .into_iter()
+        .map(|(a, b)| Interval::new(Quantity::new(a), Quantity::new(b)))
+        .collect();
+    let covered = covered.into_inner();
+    let (first, last) = (covered.first()?, covered.last()?);
+
+    // Largest uncovered gap, including the one wrapping past the period end.
+    let mut gap_start = last.end().value();
+    let mut gap_len = first.start().value() + period - last.end().value();
+    for pair in covered.windows(2) {
+        let len = pair[1].start().value() - pair[0].end().value();
+        if len > gap_len {
+            gap_start = pair[0].end().value();
+            gap_len = len;
+        }
+    }
+    let start = (gap_start + gap_len).rem_euclid(period);
+    Some(Interval::new(
+        Quantity::new(start),
+        Quantity::new(start + period - gap_len),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn schedule(entries: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in entries {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    fn family(id: &str) -> String {
+        id.split('#').next().unwrap().to_string()
+    }
+
+    #[test]
+    fn envelope_and_support_per_family() {
+        let runs = [
+            schedule(&[("a#1", 10.0, 20.0), ("b#1", 30.0, 40.0)]),
+            schedule(&[("a#2", 50.0, 60.0)]),
+        ];
+        let inferred = ConstraintInference::new().infer(&runs, family);
+        assert_eq!(inferred.len(), 1, "b has a single placement");
+        assert_eq!(inferred[0].family, "a");
+        assert_eq!(inferred[0].support, 2);
+        assert_eq!(inferred[0].envelope, iv(10.0, 60.0));
+        assert_eq!(inferred[0].recurring, None);
+        assert_eq!(
+            ConstraintInference::new()
+                .with_min_support(1)
+                .infer(&runs, family)
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn recurring_window_found_and_wraps() {
+        // Period 100: placements at phases 90–95 and 5–12 wrap around 0.
+        let runs = [schedule(&[
+            ("n#1", 90.0, 95.0),
+            ("n#2", 105.0, 112.0),
+            ("n#3", 292.0, 298.0),
+        ])];
+        let inferred = ConstraintInference::new()
+            .with_period(Quantity::new(100.0))
+            .infer(&runs, family);
+        assert_eq!(inferred[0].recurring, Some(iv(90.0, 112.0)));
+        assert!(inferred[0].to_string().contains("phase 90–112 of 100"));
+    }
+
+    #[test]
+    fn wide_spread_is_not_recurring() {
+        let runs = [schedule(&[("x#1", 0.0, 10.0), ("x#2", 150.0, 160.0)])];
+        let inferred = ConstraintInference::new()
+            .with_period(Quantity::new(100.0))
+            .with_max_phase_fraction(0.3)
+            .infer(&runs, family);
+        assert_eq!(inferred[0].recurring, None);
+        assert_eq!(inferred[0].period, None);
+    }
+
+    #[test]
+    fn draft_constraint_repeats_window() {
+        use crate::constraints::Constraint;
+
+        let runs = [schedule(&[("c#1", 20.0, 30.0), ("c#2", 125.0, 135.0)])];
+        let w = &ConstraintInference::new()
+            .with_period(Quantity::new(100.0))
+            .infer(&runs, family)[0];
+        assert_eq!(w.recurring, Some(iv(20.0, 35.0)));
+
+        let tree = w.to_constraint(iv(0.0, 250.0));
+        let windows: Vec<_> = match &tree {
+            ConstraintExpr::Union { children, .. } => children
+                .iter()
+                .map(|c| match c {
+                    ConstraintExpr::Leaf(leaf) => leaf.compute_intervals(iv(0.0, 250.0))[0],
+                    _ => unreachable!(),
+                })
+                .collect(),
+            _ => panic!("expected a union"),
+        };
+        assert_eq!(
+            windows,
+            vec![iv(20.0, 35.0), iv(120.0, 135.0), iv(220.0, 235.0)]
+        );
+
+        let plain = InferredWindow {
+            recurring: None,
+            ..w.clone()
+        };
+        assert!(plain.to_constraint(iv(0.0, 250.0)).is_leaf());
+    }
+}
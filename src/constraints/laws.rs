@@ -0,0 +1,438 @@
+//! Algebraic law checker for user-provided constraints.
+//!
+//! Set operations ([`compute_intersection`](super::operations::compute_intersection),
+//! [`compute_union`](super::operations::compute_union), …) and the schedulers
+//! rely on every [`Constraint`] and [`DynamicConstraint`] honouring the same
+//! contract. [`LawChecker`] verifies that contract against a concrete
+//! implementation so it can be exercised from a downstream test suite:
+//!
+//! | Law             | Meaning                                                        |
+//! |-----------------|----------------------------------------------------------------|
+//! | Canonical       | Result is sorted by start with no overlapping intervals        |
+//! | Within range    | Every returned interval lies inside the query range            |
+//! | Deterministic   | Two evaluations with identical inputs return identical results |
+//! | Monotone        | Shrinking the query range never adds feasible time             |
+//!
+//! Monotonicity is probed on a set of sub-ranges obtained by splitting the
+//! query range into equal slices (see [`LawChecker::with_subdivisions`]): for
+//! every sub-range `s`, `f(s) ⊆ f(range) ∩ s` must hold.
+//!
+//! # Example
+//!
+//! ```
+//! use virolai::constraints::laws::LawChecker;
+//! use virolai::constraints::IntervalConstraint;
+//! use virolai::solution_space::Interval;
+//! use qtty::Second;
+//!
+//! let constraint = IntervalConstraint::new(Interval::<Second>::from_f64(10.0, 50.0));
+//! LawChecker::new(Interval::from_f64(0.0, 100.0)).assert_holds(&constraint);
+//! ```
+
+use std::fmt;
+
+use super::hard::dynamic::{DynamicConstraint, SchedulingContext};
+use super::hard::static_::constraint::Constraint;
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::{Quantity, Unit};
+
+/// A single contract violation detected by [`LawChecker`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LawViolation<U: Unit> {
+    /// The result is not sorted or contains overlapping intervals.
+    NotCanonical {
+        range: Interval<U>,
+        result: IntervalSet<U>,
+    },
+    /// An interval of the result extends outside the query range.
+    OutOfRange {
+        range: Interval<U>,
+        interval: Interval<U>,
+    },
+    /// Two evaluations with identical inputs disagreed.
+    NonDeterministic {
+        range: Interval<U>,
+        first: IntervalSet<U>,
+        second: IntervalSet<U>,
+    },
+    /// Evaluating over `sub_range` produced time not feasible over `range`.
+    NotMonotone {
+        range: Interval<U>,
+        sub_range: Interval<U>,
+        extra: IntervalSet<U>,
+    },
+}
+
+impl<U: Unit> fmt::Display for LawViolation<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotCanonical { range, result } => {
+                write!(f, "result over {range} is not canonical: {result}")
+            }
+            Self::OutOfRange { range, interval } => {
+                write!(f, "interval {interval} lies outside query range {range}")
+            }
+            Self::NonDeterministic {
+                range,
+                first,
+                second,
+            } => write!(
+                f,
+                "non-deterministic result over {range}: {first} vs {second}"
+            ),
+            Self::NotMonotone {
+                range,
+                sub_range,
+                extra,
+            } => write!(
+                f,
+                "shrinking {range} to {sub_range} added feasible time {extra}"
+            ),
+        }
+    }
+}
+
+/// Verifies the algebraic contract of constraint implementations.
+///
+/// Cheap to construct; intended to be called from `#[test]` functions with a
+/// range representative of production use.
+#[derive(Debug, Clone, Copy)]
+pub struct LawChecker<U: Unit> {
+    range: Interval<U>,
+    subdivisions: usize,
+}
+
+impl<U: Unit> LawChecker<U> {
+    /// Creates a checker probing `range`, split into 4 slices for the
+    /// monotonicity law.
+    pub fn new(range: Interval<U>) -> Self {
+        Self {
+            range,
+            subdivisions: 4,
+        }
+    }
+
+    /// Sets the number of equal slices used as shrunk sub-ranges.
+    ///
+    /// Adjacent slice pairs are probed as well, so `n` slices yield
+    /// `2n - 1` sub-ranges. Values below 1 are clamped to 1.
+    pub fn with_subdivisions(mut self, subdivisions: usize) -> Self {
+        self.subdivisions = subdivisions.max(1);
+        self
+    }
+
+    /// Returns the query range probed by this checker.
+    pub fn range(&self) -> Interval<U> {
+        self.range
+    }
+
+    /// Checks all laws for a static constraint and returns every violation found.
+    pub fn check<C>(&self, constraint: &C) -> Vec<LawViolation<U>>
+    where
+        C: Constraint<U> + ?Sized,
+    {
+        self.check_with(|range| constraint.compute_intervals(range))
+    }
+
+    /// Checks all laws for a dynamic constraint evaluated against a fixed
+    /// reference task and scheduling context.
+    pub fn check_dynamic<C>(
+        &self,
+        constraint: &C,
+        ref_task_id: &str,
+        ctx: &SchedulingContext<U>,
+    ) -> Vec<LawViolation<U>>
+    where
+        C: DynamicConstraint<U> + ?Sized,
+    {
+        self.check_with(|range| constraint.compute_intervals(range, ref_task_id, ctx))
+    }
+
+    /// Panics with a description of every violation if any law fails.
+    pub fn assert_holds<C>(&self, constraint: &C)
+    where
+        C: Constraint<U> + ?Sized,
+    {
+        report(&constraint.stringify(), self.check(constraint));
+    }
+
+    /// Panics with a description of every violation if any law fails for the
+    /// dynamic constraint.
+    pub fn assert_dynamic_holds<C>(
+        &self,
+        constraint: &C,
+        ref_task_id: &str,
+        ctx: &SchedulingContext<U>,
+    ) where
+        C: DynamicConstraint<U> + ?Sized,
+    {
+        report(
+            &constraint.stringify(),
+            self.check_dynamic(constraint, ref_task_id, ctx),
+        );
+    }
+
+    /// Runs every law against an arbitrary evaluation function.
+    fn check_with<F>(&self, eval: F) -> Vec<LawViolation<U>>
+    where
+        F: Fn(Interval<U>) -> IntervalSet<U>,
+    {
+        let mut violations = Vec::new();
+
+        let full = eval(self.range);
+        check_shape(self.range, &full, &mut violations);
+
+        let again = eval(self.range);
+        if again != full {
+            violations.push(LawViolation::NonDeterministic {
+                range: self.range,
+                first: full.clone(),
+                second: again,
+            });
+        }
+
+        for sub_range in self.sub_ranges() {
+            let shrunk = eval(sub_range);
+            check_shape(sub_range, &shrunk, &mut violations);
+
+            // f(sub) must be contained in f(range) ∩ sub.
+            let allowed = full.intersection(&IntervalSet::from(sub_range));
+            let extra = difference(&shrunk, &allowed, sub_range);
+            if !extra.is_empty() {
+                violations.push(LawViolation::NotMonotone {
+                    range: self.range,
+                    sub_range,
+                    extra,
+                });
+            }
+        }
+
+        violations
+    }
+
+    /// Equal slices of the range plus each pair of adjacent slices.
+    fn sub_ranges(&self) -> Vec<Interval<U>> {
+        let n = self.subdivisions;
+        let start = self.range.start().value();
+        let width = self.range.duration().value() / n as f64;
+        let edge = |i: usize| {
+            if i == n {
+                self.range.end()
+            } else {
+                Quantity::new(start + width * i as f64)
+            }
+        };
+
+        let singles = (0..n).map(|i| Interval::new(edge(i), edge(i + 1)));
+        let pairs = (0..n.saturating_sub(1)).map(|i| Interval::new(edge(i), edge(i + 2)));
+        singles.chain(pairs).collect()
+    }
+}
+
+/// Checks the canonical and within-range laws for one result.
+fn check_shape<U: Unit>(
+    range: Interval<U>,
+    result: &IntervalSet<U>,
+    violations: &mut Vec<LawViolation<U>>,
+) {
+    // `IntervalSet` upholds this by construction except when built through
+    // `from_sorted_unchecked` in release builds, so the check is repeated here
+    // independently of the debug-only `operations::assertions` helpers.
+    let canonical = result
+        .windows(2)
+        .all(|w| w[0].end().value() <= w[1].start().value());
+    if !canonical {
+        violations.push(LawViolation::NotCanonical {
+            range,
+            result: result.clone(),
+        });
+    }
+
+    for interval in result.iter() {
+        if interval.start().value() < range.start().value()
+            || interval.end().value() > range.end().value()
+        {
+            violations.push(LawViolation::OutOfRange {
+                range,
+                interval: *interval,
+            });
+        }
+    }
+}
+
+/// Returns `a \ b` restricted to `bounds`.
+fn difference<U: Unit>(
+    a: &IntervalSet<U>,
+    b: &IntervalSet<U>,
+    bounds: Interval<U>,
+) -> IntervalSet<U> {
+    a.intersection(&b.complement(bounds))
+}
+
+fn report<U: Unit>(name: &str, violations: Vec<LawViolation<U>>) {
+    if violations.is_empty() {
+        return;
+    }
+    let details = violations
+        .iter()
+        .map(|v| format!("  - {v}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    panic!(
+        "constraint {name} violates {} law(s):\n{details}",
+        violations.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::{ConstraintExpr, DynConstraintKind, IntervalConstraint};
+    use crate::schedule::Schedule;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::iv;
+    use qtty::Second;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Ignores the query range entirely.
+    #[derive(Debug)]
+    struct FixedWindow;
+
+    impl Constraint<Second> for FixedWindow {
+        fn compute_intervals(&self, _range: Interval<Second>) -> IntervalSet<Second> {
+            IntervalSet::from(iv(0.0, 1000.0))
+        }
+
+        fn stringify(&self) -> String {
+            "FixedWindow".to_string()
+        }
+    }
+
+    /// Returns a different window every call.
+    #[derive(Debug, Default)]
+    struct Drifting(AtomicUsize);
+
+    impl Constraint<Second> for Drifting {
+        fn compute_intervals(&self, range: Interval<Second>) -> IntervalSet<Second> {
+            let n = self.0.fetch_add(1, Ordering::Relaxed) as f64;
+            let start = (range.start().value() + n).min(range.end().value());
+            IntervalSet::from(Interval::from_f64(start, range.end().value()))
+        }
+
+        fn stringify(&self) -> String {
+            "Drifting".to_string()
+        }
+    }
+
+    /// Feasible only in the second half of whatever range it is given.
+    #[derive(Debug)]
+    struct SecondHalf;
+
+    impl Constraint<Second> for SecondHalf {
+        fn compute_intervals(&self, range: Interval<Second>) -> IntervalSet<Second> {
+            let mid = (range.start().value() + range.end().value()) / 2.0;
+            IntervalSet::from(Interval::from_f64(mid, range.end().value()))
+        }
+
+        fn stringify(&self) -> String {
+            "SecondHalf".to_string()
+        }
+    }
+
+    #[test]
+    fn interval_constraint_satisfies_laws() {
+        let c = IntervalConstraint::new(iv(10.0, 50.0));
+        let checker = LawChecker::new(iv(0.0, 100.0));
+        assert!(checker.check(&c).is_empty());
+        checker.assert_holds(&c);
+    }
+
+    #[test]
+    fn constraint_tree_satisfies_laws() {
+        let tree = ConstraintExpr::intersection(vec![
+            ConstraintExpr::leaf(IntervalConstraint::new(iv(0.0, 80.0))),
+            !ConstraintExpr::leaf(IntervalConstraint::new(iv(20.0, 40.0))),
+        ]);
+        LawChecker::new(iv(0.0, 100.0))
+            .with_subdivisions(7)
+            .assert_holds(&tree);
+    }
+
+    #[test]
+    fn out_of_range_detected() {
+        let violations = LawChecker::new(iv(0.0, 100.0)).check(&FixedWindow);
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, LawViolation::OutOfRange { .. })));
+    }
+
+    #[test]
+    fn non_determinism_detected() {
+        let violations = LawChecker::new(iv(0.0, 100.0)).check(&Drifting::default());
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, LawViolation::NonDeterministic { .. })));
+    }
+
+    #[test]
+    fn non_monotone_detected() {
+        // Over [0, 100) the result is [50, 100); over [0, 25) it is [12.5, 25),
+        // which was not feasible over the full range.
+        let violations = LawChecker::new(iv(0.0, 100.0)).check(&SecondHalf);
+        assert!(violations
+            .iter()
+            .any(|v| matches!(v, LawViolation::NotMonotone { .. })));
+    }
+
+    #[test]
+    #[should_panic(expected = "violates")]
+    fn assert_holds_panics_on_violation() {
+        LawChecker::new(iv(0.0, 100.0)).assert_holds(&FixedWindow);
+    }
+
+    #[test]
+    fn sub_ranges_cover_slices_and_pairs() {
+        let checker = LawChecker::new(iv(0.0, 90.0)).with_subdivisions(3);
+        let subs = checker.sub_ranges();
+        assert_eq!(subs.len(), 5);
+        assert_eq!(subs[0], iv(0.0, 30.0));
+        assert_eq!(subs[2], iv(60.0, 90.0));
+        assert_eq!(subs[4], iv(30.0, 90.0));
+    }
+
+    #[test]
+    fn dynamic_kinds_satisfy_laws() {
+        let mut schedule = Schedule::new();
+        schedule.add("ref", iv(10.0, 30.0)).unwrap();
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        let checker = LawChecker::new(iv(0.0, 100.0));
+
+        for kind in [
+            DynConstraintKind::Dependence,
+            DynConstraintKind::Consecutive,
+            DynConstraintKind::Exclusive,
+            DynConstraintKind::simultaneous(0.0),
+            DynConstraintKind::simultaneous(15.0),
+            DynConstraintKind::start_to_start(5.0),
+            DynConstraintKind::finish_to_finish(-5.0),
+            DynConstraintKind::start_to_finish(40.0),
+            DynConstraintKind::max_wait(25.0),
+            DynConstraintKind::chained(25.0),
+            DynConstraintKind::Disjoint,
+        ] {
+            checker.assert_dynamic_holds(&kind, "ref", &ctx);
+        }
+    }
+
+    #[test]
+    fn violation_display_mentions_ranges() {
+        let v = LawViolation::OutOfRange {
+            range: iv(0.0, 10.0),
+            interval: iv(5.0, 20.0),
+        };
+        let s = v.to_string();
+        assert!(s.contains("outside"));
+        assert!(s.contains("20.000"));
+    }
+}
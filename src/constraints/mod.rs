@@ -1,19 +1,28 @@
+#[cfg(feature = "dsl")]
+pub mod dsl;
 pub mod error;
 pub mod hard;
+pub mod infer;
+pub mod laws;
 pub mod node;
 pub mod operations;
 pub mod soft;
 
 pub use error::ConstraintError;
 pub use hard::Constraint;
+pub use hard::FnConstraint;
 pub use hard::IntervalConstraint;
-pub use hard::ResourceConstraint;
+pub use hard::PeriodicConstraint;
+pub use hard::{CapacityProfile, ResourceConstraint};
+pub use hard::{Relaxable, RelaxationLadder};
+pub use infer::{ConstraintInference, InferredWindow};
 pub use node::ConstraintExpr;
 
 // Re-export dynamic constraint types at the `constraints` level.
 pub use hard::{
-    CoalitionConstraint, DynConstraintKind, DynamicConstraint, DynamicConstraintIndex,
-    SchedulingContext,
+    CardinalityViolation, CoalitionConstraint, ConsumableBudget, ContiguousGroup,
+    DynConstraintKind, DynamicConstraint, DynamicConstraintIndex, FnDynamicConstraint,
+    MaxParallelism, PowerEnvelope, SchedulingContext,
 };
 
 use qtty::{Quantity, Unit};
@@ -49,6 +49,7 @@ use serde::{Deserialize, Serialize};
 /// ```
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
 pub enum ConstraintExpr<C> {
     /// Logical NOT of a subtree.
@@ -364,11 +365,14 @@ where
                 child.compute_intervals(range).into_inner(),
                 range,
             ),
-            ConstraintExpr::Intersection { children, .. } => children
-                .iter()
-                .map(|c| c.compute_intervals(range))
-                .reduce(|acc, v| super::operations::compute_intersection(&acc, &v))
-                .unwrap_or_default(),
+            ConstraintExpr::Intersection { children, .. } => {
+                super::operations::compute_intersection_many(
+                    &children
+                        .iter()
+                        .map(|c| c.compute_intervals(range))
+                        .collect::<Vec<_>>(),
+                )
+            }
             ConstraintExpr::Union { children, .. } => children
                 .iter()
                 .map(|c| c.compute_intervals(range))
@@ -425,11 +429,14 @@ where
                     .into_inner(),
                 range,
             ),
-            ConstraintExpr::Intersection { children, .. } => children
-                .iter()
-                .map(|c| c.compute_intervals(range, ref_task_id, ctx))
-                .reduce(|acc, v| super::operations::compute_intersection(&acc, &v))
-                .unwrap_or_default(),
+            ConstraintExpr::Intersection { children, .. } => {
+                super::operations::compute_intersection_many(
+                    &children
+                        .iter()
+                        .map(|c| c.compute_intervals(range, ref_task_id, ctx))
+                        .collect::<Vec<_>>(),
+                )
+            }
             ConstraintExpr::Union { children, .. } => children
                 .iter()
                 .map(|c| c.compute_intervals(range, ref_task_id, ctx))
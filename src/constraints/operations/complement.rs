@@ -3,6 +3,8 @@ use crate::solution_space::IntervalSet;
 use qtty::Unit;
 
 /// Returns the complement of a canonical interval set within `[start, end]`.
+///
+/// Parts of the set outside `[start, end]` are ignored.
 pub fn compute_complement<U: Unit>(
     canonical: Vec<Interval<U>>,
     interval: Interval<U>,
@@ -20,10 +22,13 @@ pub fn compute_complement<U: Unit>(
     let mut result = Vec::with_capacity(canonical.len() + 1);
     let mut cursor = interval.start();
     for iv in canonical {
+        if iv.start() >= interval.end() {
+            break;
+        }
         if iv.start() > cursor {
             result.push(Interval::new(cursor, iv.start()));
         }
-        cursor = iv.end();
+        cursor = cursor.max(iv.end());
     }
 
     if cursor < interval.end() {
@@ -103,4 +108,14 @@ mod tests {
         // But Interval::new panics if start > end, so we test start == end
         assert_eq!(result.len(), 1);
     }
+
+    #[test]
+    fn complement_ignores_intervals_outside_bounds() {
+        let canonical = vec![iv(0.0, 10.0), iv(20.0, 30.0), iv(90.0, 120.0)];
+        let result = compute_complement(canonical, iv(25.0, 100.0));
+        assert_eq!(result.as_slice(), &[iv(30.0, 90.0)]);
+
+        let result = compute_complement(vec![iv(0.0, 10.0), iv(50.0, 60.0)], iv(20.0, 40.0));
+        assert_eq!(result.as_slice(), &[iv(20.0, 40.0)]);
+    }
 }
@@ -1,5 +1,5 @@
 use crate::solution_space::Interval;
-use crate::solution_space::IntervalSet;
+use crate::solution_space::{IntervalPool, IntervalSet};
 use qtty::Unit;
 
 /// Computes the intersection of two sorted interval sets.
@@ -13,15 +13,58 @@ use qtty::Unit;
 ///
 /// A vector of intervals representing the intersection, sorted and non-overlapping.
 pub fn compute_intersection<U: Unit>(a: &[Interval<U>], b: &[Interval<U>]) -> IntervalSet<U> {
-    // assert a and b are canonical (debug-only)
-    debug_assert!(super::assertions::is_canonical(a));
-    debug_assert!(super::assertions::is_canonical(b));
-
     if a.is_empty() || b.is_empty() {
         return IntervalSet::new();
     }
-
     let mut result = Vec::with_capacity(a.len().min(b.len()));
+    intersect_into(a, b, &mut result);
+    IntervalSet::from_sorted_unchecked(result)
+}
+
+/// [`compute_intersection`] writing into a buffer taken from `pool`.
+pub fn compute_intersection_pooled<U: Unit>(
+    a: &[Interval<U>],
+    b: &[Interval<U>],
+    pool: &mut IntervalPool<U>,
+) -> IntervalSet<U> {
+    let mut result = pool.take();
+    intersect_into(a, b, &mut result);
+    IntervalSet::from_sorted_unchecked(result)
+}
+
+/// Computes the intersection of any number of sorted interval sets in a
+/// single pass.
+///
+/// Equivalent to folding [`compute_intersection`] over `sets`, but walks all
+/// inputs together with one cursor each instead of materialising every
+/// partial result. Each step takes the latest current start and the earliest
+/// current end across the inputs, emits them when they form a non-empty
+/// interval, and advances every input whose current interval ends there.
+///
+/// Returns an empty set when `sets` is empty.
+pub fn compute_intersection_many<U: Unit, S: AsRef<[Interval<U>]>>(sets: &[S]) -> IntervalSet<U> {
+    let capacity = sets.iter().map(|s| s.as_ref().len()).min().unwrap_or(0);
+    let mut result = Vec::with_capacity(capacity);
+    intersect_many_into(sets, &mut result);
+    IntervalSet::from_sorted_unchecked(result)
+}
+
+/// [`compute_intersection_many`] writing into a buffer taken from `pool`.
+pub fn compute_intersection_many_pooled<U: Unit, S: AsRef<[Interval<U>]>>(
+    sets: &[S],
+    pool: &mut IntervalPool<U>,
+) -> IntervalSet<U> {
+    let mut result = pool.take();
+    intersect_many_into(sets, &mut result);
+    IntervalSet::from_sorted_unchecked(result)
+}
+
+/// Appends `a ∩ b` to `out`.
+fn intersect_into<U: Unit>(a: &[Interval<U>], b: &[Interval<U>], out: &mut Vec<Interval<U>>) {
+    // assert a and b are canonical (debug-only)
+    debug_assert!(super::assertions::is_canonical(a));
+    debug_assert!(super::assertions::is_canonical(b));
+
     let mut i = 0usize;
     let mut j = 0usize;
 
@@ -30,7 +73,7 @@ pub fn compute_intersection<U: Unit>(a: &[Interval<U>], b: &[Interval<U>]) -> In
         let ib = &b[j];
 
         if ia.overlaps(ib) {
-            result.push(Interval::new(
+            out.push(Interval::new(
                 crate::constraints::quantity_max(ia.start(), ib.start()),
                 crate::constraints::quantity_min(ia.end(), ib.end()),
             ));
@@ -45,8 +88,47 @@ pub fn compute_intersection<U: Unit>(a: &[Interval<U>], b: &[Interval<U>]) -> In
             }
         }
     }
+}
+
+/// Appends the intersection of all `sets` to `out`.
+fn intersect_many_into<U: Unit, S: AsRef<[Interval<U>]>>(sets: &[S], out: &mut Vec<Interval<U>>) {
+    match sets {
+        [] => return,
+        [only] => return out.extend_from_slice(only.as_ref()),
+        [a, b] => return intersect_into(a.as_ref(), b.as_ref(), out),
+        _ => {}
+    }
+    let lists: Vec<&[Interval<U>]> = sets.iter().map(AsRef::as_ref).collect();
+    debug_assert!(lists.iter().all(|l| super::assertions::is_canonical(l)));
 
-    IntervalSet::from_sorted_unchecked(result)
+    let mut cursors = vec![0usize; lists.len()];
+
+    while lists.iter().zip(&cursors).all(|(l, &c)| c < l.len()) {
+        let (lo, hi) = lists
+            .iter()
+            .zip(&cursors)
+            .fold((f64::NEG_INFINITY, f64::INFINITY), |(lo, hi), (l, &c)| {
+                (lo.max(l[c].start().value()), hi.min(l[c].end().value()))
+            });
+        // A single instant survives only as a degenerate interval meeting
+        // open ones, matching `Interval::overlaps`.
+        let keep = lo < hi
+            || (lo == hi
+                && lists
+                    .iter()
+                    .zip(&cursors)
+                    .filter(|(l, &c)| l[c].start().value() == lo || l[c].end().value() == hi)
+                    .count()
+                    == 1);
+        if keep {
+            out.push(Interval::from_f64(lo, hi));
+        }
+        for (l, c) in lists.iter().zip(cursors.iter_mut()) {
+            if l[*c].end().value() <= hi {
+                *c += 1;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -132,4 +214,56 @@ mod tests {
         let result = compute_intersection(&a, &b);
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn intersection_many_matches_pairwise_fold() {
+        let sets = [
+            vec![iv(0.0, 30.0), iv(40.0, 90.0)],
+            vec![iv(10.0, 45.0), iv(50.0, 50.0), iv(60.0, 100.0)],
+            vec![iv(5.0, 20.0), iv(25.0, 70.0), iv(80.0, 95.0)],
+            vec![iv(0.0, 100.0)],
+        ];
+        let folded = sets[1..]
+            .iter()
+            .fold(IntervalSet::from(sets[0].clone()), |acc, v| {
+                compute_intersection(&acc, v)
+            });
+        let batched = compute_intersection_many(&sets);
+        assert_eq!(batched, folded);
+        assert_eq!(
+            batched,
+            vec![
+                iv(10.0, 20.0),
+                iv(25.0, 30.0),
+                iv(40.0, 45.0),
+                iv(50.0, 50.0),
+                iv(60.0, 70.0),
+                iv(80.0, 90.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn intersection_many_edge_cases() {
+        let none: [Vec<Interval<Second>>; 0] = [];
+        assert!(compute_intersection_many(&none).is_empty());
+        assert_eq!(
+            compute_intersection_many(&[vec![iv(0.0, 10.0)]]),
+            vec![iv(0.0, 10.0)]
+        );
+        let instant = [
+            vec![iv(50.0, 50.0)],
+            vec![iv(50.0, 50.0)],
+            vec![iv(0.0, 100.0)],
+        ];
+        assert!(compute_intersection_many(&instant).is_empty());
+        let with_empty = [vec![iv(0.0, 10.0)], vec![], vec![iv(0.0, 10.0)]];
+        assert!(compute_intersection_many(&with_empty).is_empty());
+        let touching = [
+            vec![iv(0.0, 50.0)],
+            vec![iv(50.0, 100.0)],
+            vec![iv(0.0, 100.0)],
+        ];
+        assert!(compute_intersection_many(&touching).is_empty());
+    }
 }
@@ -0,0 +1,86 @@
+//! Conversions between occupancy windows and start-time windows.
+//!
+//! A task of duration `d` may *start* at `t` exactly when `[t, t + d)` fits
+//! inside one of its windows. Shrinking every window by `d` from the right —
+//! the Minkowski difference with `[0, d]` — turns "where the task may exist"
+//! into "where it may start"; growing start windows by `d` goes back.
+//!
+//! Start-time sets are **closed** on both ends: the last valid start `e - d`
+//! of a window `[s, e)` is included. A window exactly `d` long therefore
+//! yields the single start `[s, s]`.
+
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::{Quantity, Unit};
+
+/// Returns the valid start times for a task of `duration` in `windows`.
+///
+/// Each window `[s, e)` with `e - s >= duration` becomes `[s, e - duration]`;
+/// shorter windows are dropped.
+///
+/// # Panics
+///
+/// If `duration` is negative or NaN.
+pub fn compute_start_times<U: Unit>(
+    windows: &[Interval<U>],
+    duration: Quantity<U>,
+) -> IntervalSet<U> {
+    assert!(duration.value() >= 0.0, "duration must be non-negative");
+    debug_assert!(super::assertions::is_canonical(windows));
+
+    let starts = windows
+        .iter()
+        .filter(|w| w.duration().value() >= duration.value())
+        .map(|w| Interval::new(w.start(), w.end() - duration))
+        .collect();
+    IntervalSet::from_sorted_unchecked(starts)
+}
+
+/// Returns the time a task of `duration` may occupy when started anywhere
+/// in `starts`.
+///
+/// Inverse of [`compute_start_times`]: each start window `[a, b]` becomes
+/// `[a, b + duration)`, merging windows that come to overlap.
+///
+/// # Panics
+///
+/// If `duration` is negative or NaN.
+pub fn compute_occupancy<U: Unit>(starts: &[Interval<U>], duration: Quantity<U>) -> IntervalSet<U> {
+    assert!(duration.value() >= 0.0, "duration must be non-negative");
+    starts
+        .iter()
+        .map(|s| Interval::new(s.start(), s.end() + duration))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q};
+
+    #[test]
+    fn start_times_shrink_from_the_right() {
+        let windows = [iv(0.0, 10.0), iv(20.0, 25.0), iv(30.0, 33.0)];
+        let starts = compute_start_times(&windows, q(5.0));
+        assert_eq!(starts, vec![iv(0.0, 5.0), iv(20.0, 20.0)]);
+        assert_eq!(compute_start_times(&windows, q(0.0)), windows.to_vec());
+    }
+
+    #[test]
+    fn occupancy_inverts_start_times() {
+        let windows = IntervalSet::from(vec![iv(0.0, 10.0), iv(20.0, 25.0)]);
+        let starts = compute_start_times(&windows, q(5.0));
+        assert_eq!(compute_occupancy(&starts, q(5.0)), windows);
+    }
+
+    #[test]
+    fn occupancy_merges_windows_that_meet() {
+        let starts = [iv(0.0, 2.0), iv(5.0, 6.0)];
+        assert_eq!(compute_occupancy(&starts, q(3.0)), vec![iv(0.0, 9.0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "duration must be non-negative")]
+    fn negative_duration_panics() {
+        compute_start_times(&[iv(0.0, 10.0)], q(-1.0));
+    }
+}
@@ -1,9 +1,14 @@
 mod complement;
 mod intersection;
+mod minkowski;
 mod union;
 
 pub use complement::compute_complement;
-pub use intersection::compute_intersection;
+pub use intersection::{
+    compute_intersection, compute_intersection_many, compute_intersection_many_pooled,
+    compute_intersection_pooled,
+};
+pub use minkowski::{compute_occupancy, compute_start_times};
 pub use union::compute_union;
 
 #[cfg(debug_assertions)]
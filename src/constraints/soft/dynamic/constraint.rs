@@ -0,0 +1,32 @@
+//! Core trait for scoring placements against the partial schedule.
+//!
+//! This module defines the **Soft + Dynamic** constraint interface: like
+//! [`SoftConstraint`](crate::constraints::soft::static_::SoftConstraint) it
+//! never rejects a placement, but its score may depend on what has already
+//! been placed, read through a [`SchedulingContext`].
+
+use crate::constraints::SchedulingContext;
+use crate::solution_space::Interval;
+use qtty::Unit;
+use std::fmt::Debug;
+
+/// Scores how desirable a placement is, given the partial schedule.
+///
+/// # Contract
+///
+/// Implementations should:
+/// - Return a score in `[0, 1]`, higher meaning more desirable
+/// - Be deterministic for identical inputs and context
+/// - Only read the context, never assume the target is already placed
+pub trait DynamicSoftConstraint<U: Unit>: Send + Sync + Debug {
+    /// Scores placing the context's target over `placement`.
+    fn score(&self, placement: Interval<U>, ctx: &SchedulingContext<U>) -> f64;
+
+    /// Returns a string representation of this constraint.
+    fn stringify(&self) -> String;
+
+    /// Prints this constraint to stdout.
+    fn print(&self) {
+        println!("{}", self.stringify());
+    }
+}
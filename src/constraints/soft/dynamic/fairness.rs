@@ -0,0 +1,246 @@
+//! Preference for owners that are behind their share of time.
+//!
+//! Ranking by priority alone lets the owner with the highest-priority tasks
+//! take the whole horizon. [`FairShare`] gives each owner an allocated
+//! share of the scheduled time and rewards placing a task whose owner has
+//! received less than its share so far, so a score-aware ranking or search
+//! balances time between owners.
+//!
+//! Usage counts the scheduled time of every tagged entry in the context's
+//! schedule other than the target.
+
+use super::constraint::DynamicSoftConstraint;
+use crate::constraints::SchedulingContext;
+use crate::scheduling_block::OwnedTask;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::Unit;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// Scores placements by how far the target's owner is below its share.
+///
+/// With `used` the owner's fraction of the tagged scheduled time and
+/// `share` its allocation, the score is `1 - used / share`, clamped to
+/// `[0, 1]`: `1` for an owner with nothing placed yet, `0` once it has its
+/// share or more. An untagged target, or a schedule with no tagged entries,
+/// scores `1`.
+///
+/// Allocations are relative weights, normalised over the owners seen in the
+/// tags; owners without an explicit weight get `1`, so by default time is
+/// split evenly.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::soft::dynamic::{DynamicSoftConstraint, FairShare};
+/// use virolai::constraints::SchedulingContext;
+/// use virolai::schedule::Schedule;
+/// use virolai::solution_space::{Interval, SolutionSpace};
+/// use qtty::Second;
+///
+/// let fair = FairShare::<Second>::new()
+///     .with_owner("a1", "alice")
+///     .with_owner("a2", "alice")
+///     .with_owner("b1", "bob");
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add("a1", Interval::from_f64(0.0, 10.0)).unwrap();
+/// let ss = SolutionSpace::new();
+/// let next = Interval::from_f64(10.0, 20.0);
+///
+/// let alice = SchedulingContext::new(&schedule, &ss).with_target_id("a2");
+/// let bob = SchedulingContext::new(&schedule, &ss).with_target_id("b1");
+/// assert_eq!(fair.score(next, &alice), 0.0);
+/// assert_eq!(fair.score(next, &bob), 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FairShare<U: Unit> {
+    owners: HashMap<Id, String>,
+    weights: HashMap<String, f64>,
+    _unit: PhantomData<U>,
+}
+
+impl<U: Unit> Default for FairShare<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit> FairShare<U> {
+    /// Creates a fair-share term with no owners.
+    pub fn new() -> Self {
+        Self {
+            owners: HashMap::new(),
+            weights: HashMap::new(),
+            _unit: PhantomData,
+        }
+    }
+
+    /// Creates a fair-share term using each task's [`OwnedTask::owner`].
+    pub fn from_tasks<'a, T, I>(tasks: I) -> Self
+    where
+        T: OwnedTask + 'a,
+        I: IntoIterator<Item = (&'a str, &'a T)>,
+    {
+        let mut fair = Self::new();
+        for (id, task) in tasks {
+            fair.set_owner(id, task.owner());
+        }
+        fair
+    }
+
+    /// Sets the owner of task `id`.
+    pub fn with_owner(mut self, id: impl Into<Id>, owner: impl Into<String>) -> Self {
+        self.set_owner(id, owner);
+        self
+    }
+
+    /// Sets the owner of task `id`.
+    pub fn set_owner(&mut self, id: impl Into<Id>, owner: impl Into<String>) {
+        self.owners.insert(id.into(), owner.into());
+    }
+
+    /// Sets the relative allocation of `owner`, floored at `0`.
+    pub fn with_share(mut self, owner: impl Into<String>, weight: f64) -> Self {
+        self.weights.insert(owner.into(), weight.max(0.0));
+        self
+    }
+
+    /// Owner of task `id`, if tagged.
+    pub fn owner(&self, id: &str) -> Option<&str> {
+        self.owners.get(id).map(String::as_str)
+    }
+
+    /// Fraction of the time `owner` is allocated, in `[0, 1]`.
+    pub fn share(&self, owner: &str) -> f64 {
+        let weight = |o: &str| self.weights.get(o).copied().unwrap_or(1.0);
+        let mut seen: Vec<&str> = self.owners.values().map(String::as_str).collect();
+        seen.push(owner);
+        seen.sort_unstable();
+        seen.dedup();
+        let total: f64 = seen.iter().map(|o| weight(o)).sum();
+        if total > 0.0 {
+            weight(owner) / total
+        } else {
+            0.0
+        }
+    }
+}
+
+impl<U: Unit + Send + Sync> DynamicSoftConstraint<U> for FairShare<U> {
+    fn score(&self, _placement: Interval<U>, ctx: &SchedulingContext<U>) -> f64 {
+        let Some(owner) = ctx.target_id.and_then(|id| self.owner(id)) else {
+            return 1.0;
+        };
+        let (mut mine, mut total) = (0.0, 0.0);
+        for (id, interval) in ctx.schedule.iter() {
+            if Some(id.as_str()) == ctx.target_id {
+                continue;
+            }
+            let Some(entry_owner) = self.owner(&id) else {
+                continue;
+            };
+            let duration = interval.duration().value();
+            total += duration;
+            if entry_owner == owner {
+                mine += duration;
+            }
+        }
+        if total <= 0.0 {
+            return 1.0;
+        }
+        let share = self.share(owner);
+        if share <= 0.0 {
+            return 0.0;
+        }
+        (1.0 - (mine / total) / share).clamp(0.0, 1.0)
+    }
+
+    fn stringify(&self) -> String {
+        let mut owners: Vec<&str> = self.owners.values().map(String::as_str).collect();
+        owners.sort_unstable();
+        owners.dedup();
+        format!("FairShare({} owners)", owners.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::Schedule;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn fair() -> FairShare<Second> {
+        FairShare::new()
+            .with_owner("a1", "alice")
+            .with_owner("a2", "alice")
+            .with_owner("b1", "bob")
+            .with_owner("b2", "bob")
+    }
+
+    fn schedule(entries: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in entries {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    #[test]
+    fn scores_the_deficit_against_the_share() {
+        // alice has 30 of 40 tagged seconds: 75% against a 50% share.
+        let s = schedule(&[("a1", 0.0, 30.0), ("b1", 30.0, 40.0)]);
+        let ss = SolutionSpace::new();
+        let alice = SchedulingContext::new(&s, &ss).with_target_id("a2");
+        let bob = SchedulingContext::new(&s, &ss).with_target_id("b2");
+        assert_eq!(fair().score(iv(40.0, 50.0), &alice), 0.0);
+        assert_eq!(fair().score(iv(40.0, 50.0), &bob), 0.5);
+    }
+
+    #[test]
+    fn shares_are_relative_weights() {
+        let f = fair().with_share("alice", 3.0);
+        assert_eq!(f.share("alice"), 0.75);
+        assert_eq!(f.share("bob"), 0.25);
+        // An owner not yet seen joins with weight 1.
+        assert_eq!(fair().share("carol"), 1.0 / 3.0);
+
+        let s = schedule(&[("a1", 0.0, 30.0), ("b1", 30.0, 40.0)]);
+        let ss = SolutionSpace::new();
+        let alice = SchedulingContext::new(&s, &ss).with_target_id("a2");
+        assert_eq!(f.score(iv(40.0, 50.0), &alice), 0.0);
+    }
+
+    #[test]
+    fn untagged_and_first_placements_are_neutral() {
+        let ss = SolutionSpace::new();
+        let empty = Schedule::new();
+        let ctx = SchedulingContext::new(&empty, &ss).with_target_id("a1");
+        assert_eq!(fair().score(iv(0.0, 10.0), &ctx), 1.0);
+
+        let s = schedule(&[("x", 0.0, 10.0)]);
+        let untagged_entries = SchedulingContext::new(&s, &ss).with_target_id("a1");
+        assert_eq!(fair().score(iv(10.0, 20.0), &untagged_entries), 1.0);
+        let untagged_target = SchedulingContext::new(&s, &ss).with_target_id("y");
+        assert_eq!(fair().score(iv(10.0, 20.0), &untagged_target), 1.0);
+    }
+
+    #[test]
+    fn from_tasks_uses_owners() {
+        #[derive(Debug)]
+        struct Proposal(&'static str);
+        impl OwnedTask for Proposal {
+            fn owner(&self) -> &str {
+                self.0
+            }
+        }
+        let (a, b) = (Proposal("alice"), Proposal("bob"));
+        let f = FairShare::<Second>::from_tasks([("a", &a), ("b", &b)]);
+        assert_eq!(f.owner("a"), Some("alice"));
+        assert_eq!(f.owner("b"), Some("bob"));
+        assert_eq!(f.owner("c"), None);
+    }
+}
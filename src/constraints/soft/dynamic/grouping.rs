@@ -0,0 +1,233 @@
+//! Preference for batching tasks that share an instrument mode.
+//!
+//! Switching an instrument between configurations — a filter wheel, a
+//! spectrograph grating, a detector readout mode — is legal but wasteful:
+//! it costs calibration time and wear that the hard constraints never see.
+//! [`ModeGrouping`] rewards placing a task right after one in the same
+//! mode, while the configuration is still **warm**, so ranking or search
+//! by score groups same-mode tasks together instead of thrashing.
+//!
+//! The previous entry is the latest one in the context's schedule that ends
+//! by the start of the placement.
+
+use super::constraint::DynamicSoftConstraint;
+use crate::constraints::SchedulingContext;
+use crate::scheduling_block::SetupTask;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::{Quantity, Unit};
+use std::collections::HashMap;
+
+/// Scores placements by whether they continue the previous entry's mode.
+///
+/// | Situation                                         | Score           |
+/// |---------------------------------------------------|-----------------|
+/// | Target has no mode, or nothing was placed before  | `1`             |
+/// | Previous entry in the same mode, still warm       | `1`             |
+/// | Previous entry in another mode, untagged, or cold | `switch_score`  |
+///
+/// A mode stays warm for `warm_for` after its last entry ends; without a
+/// limit it never goes cold.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::soft::dynamic::{DynamicSoftConstraint, ModeGrouping};
+/// use virolai::constraints::SchedulingContext;
+/// use virolai::schedule::Schedule;
+/// use virolai::solution_space::{Interval, SolutionSpace};
+/// use qtty::Second;
+///
+/// let grouping = ModeGrouping::<Second>::new()
+///     .with_mode("r-band-1", "r")
+///     .with_mode("r-band-2", "r")
+///     .with_mode("spectrum", "grism");
+///
+/// let mut schedule = Schedule::new();
+/// schedule.add("r-band-1", Interval::from_f64(0.0, 10.0)).unwrap();
+/// let ss = SolutionSpace::new();
+/// let next = Interval::from_f64(10.0, 20.0);
+///
+/// let same = SchedulingContext::new(&schedule, &ss).with_target_id("r-band-2");
+/// let other = SchedulingContext::new(&schedule, &ss).with_target_id("spectrum");
+/// assert_eq!(grouping.score(next, &same), 1.0);
+/// assert_eq!(grouping.score(next, &other), 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ModeGrouping<U: Unit> {
+    modes: HashMap<Id, String>,
+    warm_for: Option<Quantity<U>>,
+    switch_score: f64,
+}
+
+impl<U: Unit> Default for ModeGrouping<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit> ModeGrouping<U> {
+    /// Creates a grouping with no modes, no warm limit and a switch score
+    /// of `0`.
+    pub fn new() -> Self {
+        Self {
+            modes: HashMap::new(),
+            warm_for: None,
+            switch_score: 0.0,
+        }
+    }
+
+    /// Creates a grouping using each task's [`SetupTask::setup_class`] as
+    /// its mode.
+    pub fn from_tasks<'a, T, I>(tasks: I) -> Self
+    where
+        T: SetupTask + 'a,
+        I: IntoIterator<Item = (&'a str, &'a T)>,
+    {
+        let mut grouping = Self::new();
+        for (id, task) in tasks {
+            grouping.set_mode(id, task.setup_class());
+        }
+        grouping
+    }
+
+    /// Sets the mode of task `id`.
+    pub fn with_mode(mut self, id: impl Into<Id>, mode: impl Into<String>) -> Self {
+        self.set_mode(id, mode);
+        self
+    }
+
+    /// Sets the mode of task `id`.
+    pub fn set_mode(&mut self, id: impl Into<Id>, mode: impl Into<String>) {
+        self.modes.insert(id.into(), mode.into());
+    }
+
+    /// Lets a mode go cold once `warm_for` has passed since its last entry.
+    pub fn with_warm_for(mut self, warm_for: Quantity<U>) -> Self {
+        self.warm_for = Some(warm_for);
+        self
+    }
+
+    /// Sets the score of a placement that changes mode, clamped to `[0, 1]`.
+    pub fn with_switch_score(mut self, score: f64) -> Self {
+        self.switch_score = score.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Mode of task `id`, if tagged.
+    pub fn mode(&self, id: &str) -> Option<&str> {
+        self.modes.get(id).map(String::as_str)
+    }
+}
+
+impl<U: Unit + Send + Sync> DynamicSoftConstraint<U> for ModeGrouping<U> {
+    fn score(&self, placement: Interval<U>, ctx: &SchedulingContext<U>) -> f64 {
+        let Some(mode) = ctx.target_id.and_then(|id| self.mode(id)) else {
+            return 1.0;
+        };
+        let start = placement.start().value();
+        let previous = ctx
+            .schedule
+            .iter()
+            .filter(|(id, interval)| {
+                Some(id.as_str()) != ctx.target_id && interval.end().value() <= start
+            })
+            .max_by(|(_, a), (_, b)| a.end().value().total_cmp(&b.end().value()));
+        let Some((previous, interval)) = previous else {
+            return 1.0;
+        };
+
+        let warm = self
+            .warm_for
+            .is_none_or(|w| start - interval.end().value() <= w.value());
+        if warm && self.mode(&previous) == Some(mode) {
+            1.0
+        } else {
+            self.switch_score
+        }
+    }
+
+    fn stringify(&self) -> String {
+        match self.warm_for {
+            Some(w) => format!("ModeGrouping(warm for {})", w.value()),
+            None => "ModeGrouping".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::Schedule;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    fn grouping() -> ModeGrouping<Second> {
+        ModeGrouping::new()
+            .with_mode("r1", "r")
+            .with_mode("r2", "r")
+            .with_mode("g1", "g")
+    }
+
+    fn schedule(entries: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in entries {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    #[test]
+    fn rewards_continuing_the_previous_mode() {
+        let s = schedule(&[("g1", 0.0, 10.0), ("r1", 10.0, 20.0)]);
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&s, &ss).with_target_id("r2");
+        assert_eq!(grouping().score(iv(20.0, 30.0), &ctx), 1.0);
+        // Before "r1", the previous entry is "g1".
+        let s = schedule(&[("g1", 0.0, 10.0), ("r1", 30.0, 40.0)]);
+        let ctx = SchedulingContext::new(&s, &ss).with_target_id("r2");
+        assert_eq!(grouping().score(iv(10.0, 20.0), &ctx), 0.0);
+    }
+
+    #[test]
+    fn untagged_and_first_placements_are_neutral() {
+        let ss = SolutionSpace::new();
+        let empty = Schedule::new();
+        let ctx = SchedulingContext::new(&empty, &ss).with_target_id("r1");
+        assert_eq!(grouping().score(iv(0.0, 10.0), &ctx), 1.0);
+
+        let s = schedule(&[("x", 0.0, 10.0)]);
+        let untagged_target = SchedulingContext::new(&s, &ss).with_target_id("y");
+        assert_eq!(grouping().score(iv(10.0, 20.0), &untagged_target), 1.0);
+        let untagged_previous = SchedulingContext::new(&s, &ss).with_target_id("r1");
+        let g = grouping().with_switch_score(0.25);
+        assert_eq!(g.score(iv(10.0, 20.0), &untagged_previous), 0.25);
+    }
+
+    #[test]
+    fn modes_go_cold() {
+        let s = schedule(&[("r1", 0.0, 10.0)]);
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&s, &ss).with_target_id("r2");
+        let g = grouping().with_warm_for(q(5.0));
+        assert_eq!(g.score(iv(15.0, 20.0), &ctx), 1.0);
+        assert_eq!(g.score(iv(16.0, 20.0), &ctx), 0.0);
+    }
+
+    #[test]
+    fn from_tasks_uses_setup_classes() {
+        #[derive(Debug)]
+        struct Obs(&'static str);
+        impl SetupTask for Obs {
+            fn setup_class(&self) -> &str {
+                self.0
+            }
+        }
+        let (a, b) = (Obs("r"), Obs("g"));
+        let g = ModeGrouping::<Second>::from_tasks([("a", &a), ("b", &b)]);
+        assert_eq!(g.mode("a"), Some("r"));
+        assert_eq!(g.mode("b"), Some("g"));
+        assert_eq!(g.mode("c"), None);
+    }
+}
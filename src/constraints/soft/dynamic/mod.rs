@@ -3,4 +3,15 @@
 //! Preference-based scoring constraints whose evaluation depends on
 //! runtime state (e.g., load balancing, fairness across schedule windows).
 //!
-//! Not yet implemented — module reserved for future growth.
+//! The [`DynamicSoftConstraint`] trait and the built-in [`ModeGrouping`],
+//! [`FairShare`] and [`DeadlinePenalty`] live here.
+
+pub mod constraint;
+pub mod fairness;
+pub mod grouping;
+pub mod tardiness;
+
+pub use constraint::DynamicSoftConstraint;
+pub use fairness::FairShare;
+pub use grouping::ModeGrouping;
+pub use tardiness::{DeadlinePenalty, PenaltyCurve};
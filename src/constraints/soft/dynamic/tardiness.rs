@@ -0,0 +1,195 @@
+//! Penalty for finishing tasks after their due time.
+//!
+//! Many tasks can run anywhere in the horizon but lose value the later they
+//! finish past a due time. [`DeadlinePenalty`] charges nothing up to each
+//! task's due time and a growing cost afterwards, so a score-aware ranking
+//! or an [`Objective`](crate::constraints::soft::Objective) trades lateness
+//! off against its other terms instead of treating the due time as hard.
+
+use super::constraint::DynamicSoftConstraint;
+use crate::constraints::SchedulingContext;
+use crate::schedule::Schedule;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::{Quantity, Unit};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// How the cost of a late task grows with its tardiness.
+#[derive(Clone)]
+pub enum PenaltyCurve<U: Unit> {
+    /// Rises linearly from `0` at the due time to `1` once the task is
+    /// `ramp` late.
+    Linear { ramp: Quantity<U> },
+    /// Maps the tardiness to a cost, clamped to `[0, 1]`.
+    Custom(Arc<dyn Fn(Quantity<U>) -> f64 + Send + Sync>),
+}
+
+impl<U: Unit> PenaltyCurve<U> {
+    /// Cost of finishing `tardiness` late, in `[0, 1]`.
+    pub fn cost(&self, tardiness: Quantity<U>) -> f64 {
+        if tardiness.value() <= 0.0 {
+            return 0.0;
+        }
+        let cost = match self {
+            Self::Linear { ramp } if ramp.value() > 0.0 => tardiness.value() / ramp.value(),
+            Self::Linear { .. } => 1.0,
+            Self::Custom(f) => f(tardiness),
+        };
+        if cost.is_nan() {
+            1.0
+        } else {
+            cost.clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl<U: Unit> fmt::Debug for PenaltyCurve<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Linear { ramp } => f.debug_struct("Linear").field("ramp", ramp).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Scores placements by how late they finish past the task's due time.
+///
+/// The score is `1 - cost`, with the [`PenaltyCurve`] cost of the
+/// tardiness `max(0, end - due)`. Tasks without a due time score `1`.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::soft::dynamic::{DeadlinePenalty, DynamicSoftConstraint};
+/// use virolai::constraints::SchedulingContext;
+/// use virolai::schedule::Schedule;
+/// use virolai::solution_space::{Interval, SolutionSpace};
+/// use qtty::{Quantity, Second};
+///
+/// let penalty = DeadlinePenalty::linear(Quantity::<Second>::new(20.0))
+///     .with_due("report", Quantity::new(50.0));
+///
+/// let (schedule, ss) = (Schedule::new(), SolutionSpace::new());
+/// let ctx = SchedulingContext::new(&schedule, &ss).with_target_id("report");
+/// assert_eq!(penalty.score(Interval::from_f64(30.0, 50.0), &ctx), 1.0);
+/// assert_eq!(penalty.score(Interval::from_f64(40.0, 60.0), &ctx), 0.5);
+/// assert_eq!(penalty.score(Interval::from_f64(80.0, 100.0), &ctx), 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeadlinePenalty<U: Unit> {
+    due: HashMap<Id, Quantity<U>>,
+    curve: PenaltyCurve<U>,
+}
+
+impl<U: Unit> DeadlinePenalty<U> {
+    /// Creates a penalty with no due times and the given curve.
+    pub fn new(curve: PenaltyCurve<U>) -> Self {
+        Self {
+            due: HashMap::new(),
+            curve,
+        }
+    }
+
+    /// Creates a penalty whose cost reaches `1` once a task is `ramp` late.
+    pub fn linear(ramp: Quantity<U>) -> Self {
+        Self::new(PenaltyCurve::Linear { ramp })
+    }
+
+    /// Creates a penalty whose cost is `curve(tardiness)`.
+    pub fn with_curve(curve: impl Fn(Quantity<U>) -> f64 + Send + Sync + 'static) -> Self {
+        Self::new(PenaltyCurve::Custom(Arc::new(curve)))
+    }
+
+    /// Sets the due time of task `id`.
+    pub fn with_due(mut self, id: impl Into<Id>, due: Quantity<U>) -> Self {
+        self.set_due(id, due);
+        self
+    }
+
+    /// Sets the due time of task `id`.
+    pub fn set_due(&mut self, id: impl Into<Id>, due: Quantity<U>) {
+        self.due.insert(id.into(), due);
+    }
+
+    /// Due time of task `id`, if any.
+    pub fn due(&self, id: &str) -> Option<Quantity<U>> {
+        self.due.get(id).copied()
+    }
+
+    /// Cost, in `[0, 1]`, of placing task `id` over `placement`.
+    pub fn cost(&self, id: &str, placement: Interval<U>) -> f64 {
+        self.due(id)
+            .map_or(0.0, |due| self.curve.cost(placement.end() - due))
+    }
+
+    /// Summed cost of the entries of `schedule`.
+    pub fn total_cost(&self, schedule: &Schedule<U>) -> f64 {
+        schedule
+            .iter()
+            .map(|(id, interval)| self.cost(&id, interval))
+            .sum()
+    }
+}
+
+impl<U: Unit + Send + Sync> DynamicSoftConstraint<U> for DeadlinePenalty<U> {
+    fn score(&self, placement: Interval<U>, ctx: &SchedulingContext<U>) -> f64 {
+        ctx.target_id
+            .map_or(1.0, |id| 1.0 - self.cost(id, placement))
+    }
+
+    fn stringify(&self) -> String {
+        match &self.curve {
+            PenaltyCurve::Linear { ramp } => {
+                format!("DeadlinePenalty(linear over {})", ramp.value())
+            }
+            PenaltyCurve::Custom(_) => "DeadlinePenalty(custom)".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    #[test]
+    fn linear_cost_ramps_after_the_due_time() {
+        let p = DeadlinePenalty::linear(q(10.0)).with_due("a", q(20.0));
+        assert_eq!(p.cost("a", iv(0.0, 20.0)), 0.0);
+        assert_eq!(p.cost("a", iv(5.0, 25.0)), 0.5);
+        assert_eq!(p.cost("a", iv(50.0, 70.0)), 1.0);
+        assert_eq!(p.cost("b", iv(50.0, 70.0)), 0.0);
+        // A zero ramp is a step at the due time.
+        let step = DeadlinePenalty::linear(q(0.0)).with_due("a", q(20.0));
+        assert_eq!(step.cost("a", iv(0.0, 21.0)), 1.0);
+    }
+
+    #[test]
+    fn custom_curves_are_clamped() {
+        let p = DeadlinePenalty::<Second>::with_curve(|late| (late.value() / 10.0).powi(2))
+            .with_due("a", q(0.0));
+        assert_eq!(p.cost("a", iv(0.0, 5.0)), 0.25);
+        assert_eq!(p.cost("a", iv(0.0, 50.0)), 1.0);
+    }
+
+    #[test]
+    fn scores_and_totals_follow_the_cost() {
+        let p = DeadlinePenalty::linear(q(10.0))
+            .with_due("a", q(10.0))
+            .with_due("b", q(10.0));
+        let mut s = Schedule::new();
+        s.add("a", iv(0.0, 10.0)).unwrap();
+        s.add("b", iv(10.0, 15.0)).unwrap();
+        assert_eq!(p.total_cost(&s), 0.5);
+
+        let ss = SolutionSpace::new();
+        let ctx = SchedulingContext::new(&s, &ss).with_target_id("b");
+        assert_eq!(p.score(iv(10.0, 15.0), &ctx), 0.5);
+        let anonymous = SchedulingContext::new(&s, &ss);
+        assert_eq!(p.score(iv(10.0, 15.0), &anonymous), 1.0);
+    }
+}
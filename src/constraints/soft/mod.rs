@@ -3,7 +3,12 @@
 //! Subdivided by data lifetime:
 //! - [`static_`] — parameters fixed before the scheduling loop.
 //! - [`dynamic`] — evaluated at runtime against mutable state.
+//!
+//! [`objective`] combines both kinds into one weighted score.
 
 pub mod dynamic;
+pub mod objective;
 #[allow(non_snake_case)]
 pub mod static_;
+
+pub use objective::{Objective, ObjectiveBuilder};
@@ -0,0 +1,301 @@
+//! Weighted-sum objectives over soft constraints.
+//!
+//! An [`ObjectiveBuilder`] collects static and dynamic soft constraints,
+//! each with a weight, into one [`Objective`]. A placement's objective
+//! score is the weighted mean of the term scores, each clamped to `[0, 1]`
+//! (non-finite scores count as `0`), so the total is in `[0, 1]` whatever
+//! the weights and however loosely a term honours the soft-constraint
+//! contract.
+//!
+//! The same objective ranks candidates in
+//! [`ESTScheduler::schedule_objective`](crate::algorithms::est::ESTScheduler::schedule_objective)
+//! and scores whole schedules through [`Objective::score_schedule`], the
+//! target a metaheuristic maximises.
+
+use super::dynamic::DynamicSoftConstraint;
+use super::static_::SoftConstraint;
+use crate::constraints::SchedulingContext;
+use crate::schedule::Schedule;
+use crate::solution_space::{Interval, SolutionSpace};
+use qtty::Unit;
+
+/// One weighted term of an [`Objective`].
+#[derive(Debug)]
+enum Term<U: Unit> {
+    Static(Box<dyn SoftConstraint<U>>),
+    Dynamic(Box<dyn DynamicSoftConstraint<U>>),
+}
+
+impl<U: Unit> Term<U> {
+    fn score(&self, placement: Interval<U>, ctx: &SchedulingContext<U>) -> f64 {
+        let raw = match self {
+            Self::Static(c) => c.score(placement),
+            Self::Dynamic(c) => c.score(placement, ctx),
+        };
+        if raw.is_finite() {
+            raw.clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn stringify(&self) -> String {
+        match self {
+            Self::Static(c) => c.stringify(),
+            Self::Dynamic(c) => c.stringify(),
+        }
+    }
+}
+
+/// Collects weighted soft constraints into an [`Objective`].
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::soft::dynamic::ModeGrouping;
+/// use virolai::constraints::soft::static_::CostConstraint;
+/// use virolai::constraints::soft::ObjectiveBuilder;
+/// use virolai::constraints::SchedulingContext;
+/// use virolai::schedule::Schedule;
+/// use virolai::scheduling_block::CostProfile;
+/// use virolai::solution_space::{Interval, SolutionSpace};
+/// use qtty::Second;
+///
+/// let peak = CostProfile::<Second>::new(1.0).with_rate(Interval::from_f64(0.0, 50.0), 3.0);
+/// let objective = ObjectiveBuilder::new()
+///     .with_static(CostConstraint::new(peak), 3.0)
+///     .with_dynamic(ModeGrouping::new().with_mode("a", "r"), 1.0)
+///     .build();
+///
+/// let (schedule, ss) = (Schedule::new(), SolutionSpace::new());
+/// let ctx = SchedulingContext::new(&schedule, &ss).with_target_id("a");
+/// assert_eq!(objective.score(Interval::from_f64(60.0, 70.0), &ctx), 1.0);
+/// assert_eq!(objective.score(Interval::from_f64(0.0, 10.0), &ctx), 0.25);
+/// ```
+#[derive(Debug)]
+pub struct ObjectiveBuilder<U: Unit> {
+    terms: Vec<(Term<U>, f64)>,
+}
+
+impl<U: Unit> Default for ObjectiveBuilder<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit> ObjectiveBuilder<U> {
+    /// Creates a builder with no terms.
+    pub fn new() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    /// Adds a static soft constraint with `weight`.
+    ///
+    /// # Panics
+    ///
+    /// If `weight` is negative or not finite.
+    pub fn with_static(
+        mut self,
+        constraint: impl SoftConstraint<U> + 'static,
+        weight: f64,
+    ) -> Self {
+        check_weight(weight);
+        self.terms
+            .push((Term::Static(Box::new(constraint)), weight));
+        self
+    }
+
+    /// Adds a dynamic soft constraint with `weight`.
+    ///
+    /// # Panics
+    ///
+    /// If `weight` is negative or not finite.
+    pub fn with_dynamic(
+        mut self,
+        constraint: impl DynamicSoftConstraint<U> + 'static,
+        weight: f64,
+    ) -> Self {
+        check_weight(weight);
+        self.terms
+            .push((Term::Dynamic(Box::new(constraint)), weight));
+        self
+    }
+
+    /// Finishes the objective.
+    pub fn build(self) -> Objective<U> {
+        let total_weight = self.terms.iter().map(|(_, w)| w).sum();
+        Objective {
+            terms: self.terms,
+            total_weight,
+        }
+    }
+}
+
+fn check_weight(weight: f64) {
+    assert!(
+        weight.is_finite() && weight >= 0.0,
+        "objective weight must be finite and non-negative, got {weight}"
+    );
+}
+
+/// Weighted mean of soft-constraint scores. Built by [`ObjectiveBuilder`].
+#[derive(Debug)]
+pub struct Objective<U: Unit> {
+    terms: Vec<(Term<U>, f64)>,
+    total_weight: f64,
+}
+
+impl<U: Unit> Objective<U> {
+    /// Number of terms.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// `true` if the objective has no terms.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Scores placing the context's target over `placement`, in `[0, 1]`.
+    ///
+    /// An objective without terms, or whose weights are all zero, scores
+    /// `1` everywhere.
+    pub fn score(&self, placement: Interval<U>, ctx: &SchedulingContext<U>) -> f64 {
+        if self.total_weight <= 0.0 {
+            return 1.0;
+        }
+        let weighted: f64 = self
+            .terms
+            .iter()
+            .map(|(term, weight)| weight * term.score(placement, ctx))
+            .sum();
+        weighted / self.total_weight
+    }
+
+    /// Normalised score of every term, in the order they were added.
+    pub fn breakdown(&self, placement: Interval<U>, ctx: &SchedulingContext<U>) -> Vec<f64> {
+        self.terms
+            .iter()
+            .map(|(term, _)| term.score(placement, ctx))
+            .collect()
+    }
+
+    /// Mean score of the entries of `schedule`, in `[0, 1]`.
+    ///
+    /// Entries are replayed in start order, each scored against the entries
+    /// before it, as the scheduling loop saw them. An empty schedule scores
+    /// `0`.
+    pub fn score_schedule(&self, schedule: &Schedule<U>, solution_space: &SolutionSpace<U>) -> f64 {
+        let mut placed = Schedule::new();
+        let mut total = 0.0;
+        for (id, interval) in schedule.iter() {
+            let ctx = SchedulingContext::new(&placed, solution_space).with_target_id(&id);
+            total += self.score(interval, &ctx);
+            let _ = placed.add(id.clone(), interval);
+        }
+        if schedule.is_empty() {
+            0.0
+        } else {
+            total / schedule.len() as f64
+        }
+    }
+}
+
+impl<U: Unit + Send + Sync> DynamicSoftConstraint<U> for Objective<U> {
+    fn score(&self, placement: Interval<U>, ctx: &SchedulingContext<U>) -> f64 {
+        Objective::score(self, placement, ctx)
+    }
+
+    fn stringify(&self) -> String {
+        let terms: Vec<_> = self
+            .terms
+            .iter()
+            .map(|(term, weight)| format!("{weight}×{}", term.stringify()))
+            .collect();
+        format!("Objective({})", terms.join(" + "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    /// Scores `value` everywhere.
+    #[derive(Debug)]
+    struct Constant(f64);
+
+    impl SoftConstraint<Second> for Constant {
+        fn score(&self, _placement: Interval<Second>) -> f64 {
+            self.0
+        }
+
+        fn stringify(&self) -> String {
+            format!("Constant({})", self.0)
+        }
+    }
+
+    /// Scores `1` while nothing is placed, `0` afterwards.
+    #[derive(Debug)]
+    struct First;
+
+    impl DynamicSoftConstraint<Second> for First {
+        fn score(&self, _placement: Interval<Second>, ctx: &SchedulingContext<Second>) -> f64 {
+            if ctx.schedule.is_empty() {
+                1.0
+            } else {
+                0.0
+            }
+        }
+
+        fn stringify(&self) -> String {
+            "First".into()
+        }
+    }
+
+    #[test]
+    fn terms_are_clamped_and_weighted() {
+        let objective = ObjectiveBuilder::new()
+            .with_static(Constant(5.0), 1.0)
+            .with_static(Constant(f64::NAN), 1.0)
+            .with_static(Constant(0.5), 2.0)
+            .build();
+        let (schedule, ss) = (Schedule::new(), SolutionSpace::new());
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        assert_eq!(objective.breakdown(iv(0.0, 1.0), &ctx), vec![1.0, 0.0, 0.5]);
+        assert_eq!(objective.score(iv(0.0, 1.0), &ctx), 0.5);
+        assert_eq!(
+            objective.stringify(),
+            "Objective(1×Constant(5) + 1×Constant(NaN) + 2×Constant(0.5))"
+        );
+    }
+
+    #[test]
+    fn empty_objective_is_neutral() {
+        let objective = ObjectiveBuilder::<Second>::new()
+            .with_static(Constant(0.0), 0.0)
+            .build();
+        let (schedule, ss) = (Schedule::new(), SolutionSpace::new());
+        let ctx = SchedulingContext::new(&schedule, &ss);
+        assert_eq!(objective.score(iv(0.0, 1.0), &ctx), 1.0);
+    }
+
+    #[test]
+    fn schedules_are_scored_in_start_order() {
+        let objective = ObjectiveBuilder::new().with_dynamic(First, 1.0).build();
+        let mut schedule = Schedule::new();
+        schedule.add("b", iv(10.0, 20.0)).unwrap();
+        schedule.add("a", iv(0.0, 10.0)).unwrap();
+        let ss = SolutionSpace::new();
+        // Only the earliest entry sees an empty schedule.
+        assert_eq!(objective.score_schedule(&schedule, &ss), 0.5);
+        assert_eq!(objective.score_schedule(&Schedule::new(), &ss), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "objective weight")]
+    fn negative_weights_are_rejected() {
+        let _ = ObjectiveBuilder::<Second>::new().with_static(Constant(1.0), -1.0);
+    }
+}
@@ -0,0 +1,75 @@
+//! Core trait for scoring candidate placements.
+//!
+//! This module defines the **Soft + Static** constraint interface:
+//! constraints that never reject a placement but grade it, using data fixed
+//! before the scheduling loop begins.
+
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::{Quantity, Unit};
+use std::fmt::Debug;
+
+/// Scores how desirable a placement is.
+///
+/// # Contract
+///
+/// Implementations should:
+/// - Return a score in `[0, 1]`, higher meaning more desirable
+/// - Be deterministic for identical inputs
+pub trait SoftConstraint<U: Unit>: Send + Sync + Debug {
+    /// Scores placing a task over `placement`.
+    fn score(&self, placement: Interval<U>) -> f64;
+
+    /// Returns a string representation of this constraint.
+    fn stringify(&self) -> String;
+
+    /// Prints this constraint to stdout.
+    fn print(&self) {
+        println!("{}", self.stringify());
+    }
+
+    /// Returns the best-scoring placement of length `size` inside `feasible`.
+    ///
+    /// Candidate starts are the start of each feasible window plus the points
+    /// returned by [`breakpoints`](Self::breakpoints) within it, so a piecewise
+    /// constant score is searched exhaustively. Ties keep the earliest start.
+    fn best_placement(
+        &self,
+        feasible: &IntervalSet<U>,
+        size: Quantity<U>,
+    ) -> Option<(Interval<U>, f64)> {
+        let mut best: Option<(Interval<U>, f64)> = None;
+        for window in feasible.iter() {
+            let latest = window.end() - size;
+            if latest < window.start() {
+                continue;
+            }
+            let mut starts = vec![window.start(), latest];
+            for p in self.breakpoints(*window) {
+                // Align either the start or the end of the placement with p.
+                for s in [p, p - size] {
+                    if s >= window.start() && s <= latest {
+                        starts.push(s);
+                    }
+                }
+            }
+            starts.sort_by(|a, b| a.value().total_cmp(&b.value()));
+            for start in starts {
+                let placement = Interval::new(start, start + size);
+                let value = self.score(placement);
+                if best.is_none_or(|(_, b)| value > b) {
+                    best = Some((placement, value));
+                }
+            }
+        }
+        best
+    }
+
+    /// Points inside `window` where the score may change.
+    ///
+    /// Used by [`best_placement`](Self::best_placement). The default returns
+    /// none, which only probes window edges.
+    fn breakpoints(&self, window: Interval<U>) -> Vec<Quantity<U>> {
+        let _ = window;
+        Vec::new()
+    }
+}
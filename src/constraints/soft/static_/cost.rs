@@ -0,0 +1,165 @@
+//! Preference for cheap placements from a cost profile or price curve.
+//!
+//! [`CostConstraint`] is the cost-minimisation term of an objective: it
+//! grades a placement by where its rate sits between the cheapest and the
+//! dearest rate of a [`CostProfile`], so ranking or search by score steers
+//! tasks towards off-peak slots without forbidding peak ones.
+//! [`PriceCurveConstraint`] does the same with the time-weighted mean price
+//! of a [`PriceCurve`] over the whole placement. A hard cap on
+//! total spend is [`ESTScheduler::schedule_budgeted`](crate::algorithms::est::ESTScheduler::schedule_budgeted).
+
+use super::constraint::SoftConstraint;
+use crate::scheduling_block::{CostProfile, PriceCurve};
+use crate::solution_space::Interval;
+use qtty::{Quantity, Unit};
+
+/// Scores placements by how cheap their rate is.
+///
+/// The score is `1` at the profile's lowest rate and `0` at its highest,
+/// linear in between; a flat profile scores `1` everywhere.
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::soft::static_::{CostConstraint, SoftConstraint};
+/// use virolai::scheduling_block::CostProfile;
+/// use virolai::solution_space::{Interval, IntervalSet};
+/// use qtty::{Quantity, Second};
+///
+/// let peak = CostProfile::<Second>::new(1.0).with_rate(Interval::from_f64(0.0, 50.0), 4.0);
+/// let c = CostConstraint::new(peak);
+/// let feasible = IntervalSet::from(Interval::from_f64(0.0, 100.0));
+/// let (best, score) = c.best_placement(&feasible, Quantity::new(10.0)).unwrap();
+/// assert_eq!(best, Interval::from_f64(50.0, 60.0));
+/// assert_eq!(score, 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CostConstraint<U: Unit> {
+    profile: CostProfile<U>,
+}
+
+impl<U: Unit> CostConstraint<U> {
+    /// Creates a soft constraint from a cost profile.
+    pub fn new(profile: CostProfile<U>) -> Self {
+        Self { profile }
+    }
+
+    /// Returns the underlying profile.
+    pub fn profile(&self) -> &CostProfile<U> {
+        &self.profile
+    }
+}
+
+impl<U: Unit + Send + Sync> SoftConstraint<U> for CostConstraint<U> {
+    fn score(&self, placement: Interval<U>) -> f64 {
+        let (min, max) = (self.profile.min_rate(), self.profile.max_rate());
+        if max <= min {
+            return 1.0;
+        }
+        (max - self.profile.rate_at(placement.start())) / (max - min)
+    }
+
+    fn stringify(&self) -> String {
+        format!(
+            "Cost(rate {}–{})",
+            self.profile.min_rate(),
+            self.profile.max_rate()
+        )
+    }
+
+    fn breakpoints(&self, window: Interval<U>) -> Vec<Quantity<U>> {
+        self.profile.rate_changes(window)
+    }
+}
+
+/// Scores placements by the mean price they pay on a [`PriceCurve`].
+///
+/// The score is `1` at the curve's lowest price and `0` at its highest,
+/// linear in the time-weighted mean price in between; a flat curve scores
+/// `1` everywhere.
+#[derive(Debug, Clone)]
+pub struct PriceCurveConstraint<U: Unit> {
+    curve: PriceCurve<U>,
+}
+
+impl<U: Unit> PriceCurveConstraint<U> {
+    /// Creates a soft constraint from a price curve.
+    pub fn new(curve: PriceCurve<U>) -> Self {
+        Self { curve }
+    }
+
+    /// Returns the underlying curve.
+    pub fn curve(&self) -> &PriceCurve<U> {
+        &self.curve
+    }
+}
+
+impl<U: Unit + Send + Sync> SoftConstraint<U> for PriceCurveConstraint<U> {
+    fn score(&self, placement: Interval<U>) -> f64 {
+        let (min, max) = (self.curve.min_price(), self.curve.max_price());
+        if max <= min {
+            return 1.0;
+        }
+        (max - self.curve.mean(placement)) / (max - min)
+    }
+
+    fn stringify(&self) -> String {
+        format!(
+            "PriceCurve(price {}–{})",
+            self.curve.min_price(),
+            self.curve.max_price()
+        )
+    }
+
+    fn breakpoints(&self, window: Interval<U>) -> Vec<Quantity<U>> {
+        self.curve.breakpoints(window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution_space::IntervalSet;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    #[test]
+    fn score_is_linear_in_rate() {
+        let p = CostProfile::<Second>::new(2.0)
+            .with_rate(iv(0.0, 10.0), 1.0)
+            .with_rate(iv(10.0, 20.0), 5.0);
+        let c = CostConstraint::new(p);
+        assert_eq!(c.score(iv(0.0, 5.0)), 1.0);
+        assert_eq!(c.score(iv(12.0, 15.0)), 0.0);
+        assert_eq!(c.score(iv(30.0, 35.0)), 0.75);
+        assert_eq!(
+            CostConstraint::new(CostProfile::<Second>::new(3.0)).score(iv(0.0, 1.0)),
+            1.0
+        );
+    }
+
+    #[test]
+    fn best_placement_finds_off_peak_phase() {
+        let p = CostProfile::<Second>::new(1.0)
+            .with_period(q(100.0))
+            .with_rate(iv(0.0, 70.0), 3.0);
+        let c = CostConstraint::new(p);
+        let feasible = IntervalSet::from(iv(120.0, 300.0));
+        let (best, score) = c.best_placement(&feasible, q(20.0)).unwrap();
+        assert_eq!(best, iv(170.0, 190.0));
+        assert_eq!(score, 1.0);
+    }
+
+    #[test]
+    fn price_curve_prefers_cheapest_span() {
+        let curve =
+            PriceCurve::<Second>::from_bins(q(0.0), q(10.0), [5.0, 1.0, 2.0, 8.0]).with_before(8.0);
+        let c = PriceCurveConstraint::new(curve);
+        // Mean over [10, 25) is (10·1 + 5·2) / 15.
+        assert!((c.score(iv(10.0, 25.0)) - (8.0 - 20.0 / 15.0) / 8.0).abs() < 1e-12);
+
+        let feasible = IntervalSet::from(iv(0.0, 40.0));
+        let (best, _) = c.best_placement(&feasible, q(15.0)).unwrap();
+        assert_eq!(best, iv(10.0, 25.0));
+    }
+}
@@ -3,4 +3,16 @@
 //! Preference-based scoring constraints whose parameters are fixed
 //! before the scheduling loop (e.g., preferred time windows, priority weights).
 //!
-//! Not yet implemented — module reserved for future growth.
+//! The [`SoftConstraint`] trait, the built-in [`ProbabilityProfileConstraint`]
+//! and [`PreferenceCurve`], and the cost terms [`CostConstraint`] and
+//! [`PriceCurveConstraint`] live here.
+
+pub mod constraint;
+pub mod cost;
+pub mod preference;
+pub mod probability;
+
+pub use constraint::SoftConstraint;
+pub use cost::{CostConstraint, PriceCurveConstraint};
+pub use preference::PreferenceCurve;
+pub use probability::{ProbabilityProfile, ProbabilityProfileConstraint, ProfileAggregate};
@@ -0,0 +1,174 @@
+//! Preference curves over the start time.
+//!
+//! Many preferences are a simple shape in time: an astronomer wants a
+//! target "as close to transit as possible", an operator prefers mornings
+//! tapering off towards noon. [`PreferenceCurve`] expresses these as a
+//! score of the placement's start, without a custom [`SoftConstraint`] impl:
+//!
+//! | Curve            | Score of start `t`                                     |
+//! |------------------|--------------------------------------------------------|
+//! | `Gaussian`       | `exp(-(t - center)² / 2σ²)`, `1` at the center         |
+//! | `PiecewiseLinear`| Linear between `points`, flat beyond the first and last |
+
+use super::constraint::SoftConstraint;
+use crate::solution_space::Interval;
+use qtty::{Quantity, Unit};
+
+/// Score of a placement as a function of its start.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreferenceCurve<U: Unit> {
+    /// Bell curve peaking at `center`. A non-positive `sigma` scores `1` at
+    /// the center and `0` elsewhere.
+    Gaussian {
+        center: Quantity<U>,
+        sigma: Quantity<U>,
+    },
+    /// Linear interpolation between `(time, score)` points sorted by time.
+    /// Scores are clamped to `[0, 1]`; no points score `0` everywhere.
+    PiecewiseLinear { points: Vec<(Quantity<U>, f64)> },
+}
+
+impl<U: Unit> PreferenceCurve<U> {
+    /// Bell curve peaking at `center` with standard deviation `sigma`.
+    ///
+    /// # Panics
+    ///
+    /// If `sigma` is not strictly positive.
+    pub fn gaussian(center: Quantity<U>, sigma: Quantity<U>) -> Self {
+        assert!(sigma.value() > 0.0, "gaussian sigma must be positive");
+        Self::Gaussian { center, sigma }
+    }
+
+    /// Linear interpolation between `points`, in any order.
+    ///
+    /// # Panics
+    ///
+    /// If `points` is empty.
+    pub fn piecewise_linear(mut points: Vec<(Quantity<U>, f64)>) -> Self {
+        assert!(!points.is_empty(), "piecewise-linear curve needs a point");
+        points.sort_by(|(a, _), (b, _)| a.value().total_cmp(&b.value()));
+        Self::PiecewiseLinear { points }
+    }
+
+    /// Score of starting at `t`, in `[0, 1]`.
+    pub fn value_at(&self, t: Quantity<U>) -> f64 {
+        let t = t.value();
+        let score = match self {
+            Self::Gaussian { center, sigma } => {
+                let (c, s) = (center.value(), sigma.value());
+                if s > 0.0 {
+                    (-(t - c).powi(2) / (2.0 * s * s)).exp()
+                } else if t == c {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Self::PiecewiseLinear { points } => interpolate(points, t),
+        };
+        if score.is_nan() {
+            0.0
+        } else {
+            score.clamp(0.0, 1.0)
+        }
+    }
+}
+
+fn interpolate<U: Unit>(points: &[(Quantity<U>, f64)], t: f64) -> f64 {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return 0.0;
+    };
+    if t <= first.0.value() {
+        return first.1;
+    }
+    if t >= last.0.value() {
+        return last.1;
+    }
+    let i = points.partition_point(|(p, _)| p.value() <= t);
+    let ((t0, s0), (t1, s1)) = (points[i - 1], points[i]);
+    let span = t1.value() - t0.value();
+    if span <= 0.0 {
+        return s1;
+    }
+    s0 + (s1 - s0) * (t - t0.value()) / span
+}
+
+impl<U: Unit + Send + Sync> SoftConstraint<U> for PreferenceCurve<U> {
+    fn score(&self, placement: Interval<U>) -> f64 {
+        self.value_at(placement.start())
+    }
+
+    fn stringify(&self) -> String {
+        match self {
+            Self::Gaussian { center, sigma } => {
+                format!("Gaussian(center {}, σ {})", center.value(), sigma.value())
+            }
+            Self::PiecewiseLinear { points } => {
+                format!("PiecewiseLinear({} points)", points.len())
+            }
+        }
+    }
+
+    /// The peak or the vertices inside `window`: the score is unimodal or
+    /// linear between them, so the best start is one of these or an edge.
+    fn breakpoints(&self, window: Interval<U>) -> Vec<Quantity<U>> {
+        let inside = |t: &Quantity<U>| window.start() <= *t && *t <= window.end();
+        match self {
+            Self::Gaussian { center, .. } => [*center].into_iter().filter(inside).collect(),
+            Self::PiecewiseLinear { points } => {
+                points.iter().map(|(t, _)| *t).filter(inside).collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution_space::IntervalSet;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    #[test]
+    fn gaussian_peaks_at_the_center() {
+        let c = PreferenceCurve::<Second>::gaussian(q(50.0), q(10.0));
+        assert_eq!(c.value_at(q(50.0)), 1.0);
+        assert!((c.value_at(q(60.0)) - (-0.5f64).exp()).abs() < 1e-12);
+        assert_eq!(c.value_at(q(40.0)), c.value_at(q(60.0)));
+
+        let feasible = IntervalSet::from(iv(0.0, 100.0));
+        let (best, score) = c.best_placement(&feasible, q(10.0)).unwrap();
+        assert_eq!(best, iv(50.0, 60.0));
+        assert_eq!(score, 1.0);
+    }
+
+    #[test]
+    fn piecewise_linear_interpolates_and_holds_ends() {
+        let c = PreferenceCurve::<Second>::piecewise_linear(vec![
+            (q(20.0), 0.0),
+            (q(0.0), 1.0),
+            (q(40.0), 2.0),
+        ]);
+        assert_eq!(c.value_at(q(-5.0)), 1.0);
+        assert_eq!(c.value_at(q(10.0)), 0.5);
+        assert_eq!(c.value_at(q(25.0)), 0.5);
+        // Scores above 1 are clamped.
+        assert_eq!(c.value_at(q(100.0)), 1.0);
+
+        let feasible = IntervalSet::from(iv(5.0, 30.0));
+        let (best, _) = c.best_placement(&feasible, q(5.0)).unwrap();
+        assert_eq!(best, iv(5.0, 10.0));
+    }
+
+    #[test]
+    fn degenerate_curves_do_not_panic() {
+        let flat = PreferenceCurve::<Second>::PiecewiseLinear { points: vec![] };
+        assert_eq!(flat.value_at(q(1.0)), 0.0);
+        let spike = PreferenceCurve::<Second>::Gaussian {
+            center: q(5.0),
+            sigma: q(0.0),
+        };
+        assert_eq!(spike.value_at(q(5.0)), 1.0);
+        assert_eq!(spike.value_at(q(6.0)), 0.0);
+    }
+}
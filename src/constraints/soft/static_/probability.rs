@@ -0,0 +1,321 @@
+//! Window scoring from a probability profile over the horizon.
+//!
+//! A [`ProbabilityProfile`] is a piecewise-constant series of probabilities,
+//! one per fixed-width bin — for example the historical clear-sky probability
+//! per hour of night, or the availability rate of a shared instrument per
+//! shift. [`ProbabilityProfileConstraint`] turns it into a soft score so the
+//! scheduler can prefer statistically reliable times without forbidding
+//! anything.
+//!
+//! # Aggregation
+//!
+//! A placement usually spans several bins. [`ProfileAggregate`] selects how
+//! the covered bins, weighted by their overlap with the placement, are
+//! reduced to one score:
+//!
+//! | Aggregate       | Score                                                    |
+//! |-----------------|----------------------------------------------------------|
+//! | `Mean`          | Overlap-weighted mean probability                        |
+//! | `Min`           | Worst covered bin                                        |
+//! | `Quantile(q)`   | Overlap-weighted `q`-quantile (e.g. `0.1` = pessimistic) |
+
+use std::fmt;
+
+use super::constraint::SoftConstraint;
+use crate::solution_space::Interval;
+use qtty::{Quantity, Unit};
+
+/// Piecewise-constant probability series over a time axis.
+///
+/// Bin `i` covers `[origin + i·width, origin + (i+1)·width)`. Time outside
+/// the bins has probability [`outside`](Self::with_outside) (0 by default).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbabilityProfile<U: Unit> {
+    origin: Quantity<U>,
+    bin_width: Quantity<U>,
+    probabilities: Vec<f64>,
+    outside: f64,
+}
+
+impl<U: Unit> ProbabilityProfile<U> {
+    /// Creates a profile from consecutive bins starting at `origin`.
+    ///
+    /// Probabilities are clamped to `[0, 1]`; NaN is treated as 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bin_width` is not strictly positive.
+    pub fn new(origin: Quantity<U>, bin_width: Quantity<U>, probabilities: Vec<f64>) -> Self {
+        assert!(
+            bin_width.value() > 0.0,
+            "probability profile bin width must be positive"
+        );
+        Self {
+            origin,
+            bin_width,
+            probabilities: probabilities.into_iter().map(clamp_probability).collect(),
+            outside: 0.0,
+        }
+    }
+
+    /// Sets the probability assumed outside the profile's bins.
+    pub fn with_outside(mut self, probability: f64) -> Self {
+        self.outside = clamp_probability(probability);
+        self
+    }
+
+    /// Returns the time span covered by the bins.
+    pub fn span(&self) -> Interval<U> {
+        Interval::new(self.origin, self.bin_edge(self.probabilities.len()))
+    }
+
+    /// Returns the probability at time `t`.
+    pub fn at(&self, t: Quantity<U>) -> f64 {
+        let offset = (t.value() - self.origin.value()) / self.bin_width.value();
+        if offset < 0.0 || !offset.is_finite() {
+            return self.outside;
+        }
+        self.probabilities
+            .get(offset.floor() as usize)
+            .copied()
+            .unwrap_or(self.outside)
+    }
+
+    /// Returns `(probability, overlap)` pairs for every bin touched by
+    /// `placement`, including the outside regions.
+    fn weighted_bins(&self, placement: Interval<U>) -> Vec<(f64, f64)> {
+        let (start, end) = (placement.start().value(), placement.end().value());
+        if end <= start {
+            return vec![(self.at(placement.start()), 1.0)];
+        }
+
+        let span = self.span();
+        let (lo, hi) = (span.start().value(), span.end().value());
+        let mut out = Vec::new();
+
+        let before = end.min(lo) - start;
+        if before > 0.0 {
+            out.push((self.outside, before));
+        }
+        let after = end - start.max(hi);
+        if after > 0.0 {
+            out.push((self.outside, after));
+        }
+
+        let width = self.bin_width.value();
+        let first = ((start.max(lo) - lo) / width).floor() as usize;
+        for (i, &p) in self.probabilities.iter().enumerate().skip(first) {
+            let (b0, b1) = (lo + width * i as f64, lo + width * (i + 1) as f64);
+            if b0 >= end {
+                break;
+            }
+            let overlap = b1.min(end) - b0.max(start);
+            if overlap > 0.0 {
+                out.push((p, overlap));
+            }
+        }
+        out
+    }
+
+    fn bin_edge(&self, i: usize) -> Quantity<U> {
+        Quantity::new(self.origin.value() + self.bin_width.value() * i as f64)
+    }
+}
+
+fn clamp_probability(p: f64) -> f64 {
+    if p.is_nan() {
+        0.0
+    } else {
+        p.clamp(0.0, 1.0)
+    }
+}
+
+/// How the bins covered by a placement are reduced to one score.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProfileAggregate {
+    /// Overlap-weighted mean probability.
+    Mean,
+    /// Lowest probability of any covered bin.
+    Min,
+    /// Overlap-weighted quantile; the value is clamped to `[0, 1]`.
+    Quantile(f64),
+}
+
+impl fmt::Display for ProfileAggregate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mean => write!(f, "mean"),
+            Self::Min => write!(f, "min"),
+            Self::Quantile(q) => write!(f, "q{q:.2}"),
+        }
+    }
+}
+
+/// Soft constraint scoring placements against a [`ProbabilityProfile`].
+///
+/// # Example
+///
+/// ```
+/// use virolai::constraints::soft::static_::{
+///     ProbabilityProfile, ProbabilityProfileConstraint, ProfileAggregate, SoftConstraint,
+/// };
+/// use virolai::solution_space::Interval;
+/// use qtty::{Quantity, Second};
+///
+/// // Hourly clear-sky probability for a 4-hour night.
+/// let profile = ProbabilityProfile::<Second>::new(
+///     Quantity::new(0.0),
+///     Quantity::new(3600.0),
+///     vec![0.4, 0.9, 0.8, 0.3],
+/// );
+/// let score = ProbabilityProfileConstraint::new(profile, ProfileAggregate::Mean);
+/// assert!(score.score(Interval::from_f64(3600.0, 7200.0)) > 0.85);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProbabilityProfileConstraint<U: Unit> {
+    profile: ProbabilityProfile<U>,
+    aggregate: ProfileAggregate,
+}
+
+impl<U: Unit> ProbabilityProfileConstraint<U> {
+    /// Creates a soft constraint from a profile and aggregation rule.
+    pub fn new(profile: ProbabilityProfile<U>, aggregate: ProfileAggregate) -> Self {
+        Self { profile, aggregate }
+    }
+
+    /// Returns the underlying profile.
+    pub fn profile(&self) -> &ProbabilityProfile<U> {
+        &self.profile
+    }
+
+    /// Returns the aggregation rule.
+    pub fn aggregate(&self) -> ProfileAggregate {
+        self.aggregate
+    }
+}
+
+impl<U: Unit + Send + Sync> SoftConstraint<U> for ProbabilityProfileConstraint<U> {
+    fn score(&self, placement: Interval<U>) -> f64 {
+        let mut bins = self.profile.weighted_bins(placement);
+        match self.aggregate {
+            ProfileAggregate::Mean => {
+                let total: f64 = bins.iter().map(|(_, w)| w).sum();
+                bins.iter().map(|(p, w)| p * w).sum::<f64>() / total
+            }
+            ProfileAggregate::Min => bins.iter().map(|(p, _)| *p).fold(1.0, f64::min),
+            ProfileAggregate::Quantile(q) => {
+                let q = clamp_probability(q);
+                bins.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let total: f64 = bins.iter().map(|(_, w)| w).sum();
+                let mut acc = 0.0;
+                for (p, w) in &bins {
+                    acc += w;
+                    if acc >= q * total {
+                        return *p;
+                    }
+                }
+                bins.last().map_or(self.profile.outside, |(p, _)| *p)
+            }
+        }
+    }
+
+    fn stringify(&self) -> String {
+        format!(
+            "ProbabilityProfile({} bins, {})",
+            self.profile.probabilities.len(),
+            self.aggregate
+        )
+    }
+
+    fn breakpoints(&self, window: Interval<U>) -> Vec<Quantity<U>> {
+        (0..=self.profile.probabilities.len())
+            .map(|i| self.profile.bin_edge(i))
+            .filter(|t| window.contains(*t))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution_space::IntervalSet;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    fn night() -> ProbabilityProfile<Second> {
+        ProbabilityProfile::new(q(0.0), q(10.0), vec![0.2, 0.9, 0.8, 0.1])
+    }
+
+    #[test]
+    fn at_looks_up_bins() {
+        let p = night();
+        assert_eq!(p.at(q(0.0)), 0.2);
+        assert_eq!(p.at(q(15.0)), 0.9);
+        assert_eq!(p.at(q(39.9)), 0.1);
+        assert_eq!(p.at(q(40.0)), 0.0);
+        assert_eq!(p.at(q(-1.0)), 0.0);
+        assert_eq!(p.clone().with_outside(0.5).at(q(100.0)), 0.5);
+    }
+
+    #[test]
+    fn probabilities_are_clamped() {
+        let p = ProbabilityProfile::<Second>::new(q(0.0), q(1.0), vec![-1.0, 2.0, f64::NAN]);
+        assert_eq!(p.at(q(0.5)), 0.0);
+        assert_eq!(p.at(q(1.5)), 1.0);
+        assert_eq!(p.at(q(2.5)), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "bin width")]
+    fn zero_bin_width_panics() {
+        ProbabilityProfile::<Second>::new(q(0.0), q(0.0), vec![]);
+    }
+
+    #[test]
+    fn mean_is_overlap_weighted() {
+        let c = ProbabilityProfileConstraint::new(night(), ProfileAggregate::Mean);
+        // 5 s at 0.9 + 10 s at 0.8 → (4.5 + 8) / 15
+        let s = c.score(iv(15.0, 30.0));
+        assert!((s - 12.5 / 15.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mean_counts_outside_time() {
+        let c = ProbabilityProfileConstraint::new(night(), ProfileAggregate::Mean);
+        // 10 s at 0.1, 10 s outside at 0.0
+        assert!((c.score(iv(30.0, 50.0)) - 0.05).abs() < 1e-12);
+    }
+
+    #[test]
+    fn min_and_quantile() {
+        let p = night();
+        let min = ProbabilityProfileConstraint::new(p.clone(), ProfileAggregate::Min);
+        assert_eq!(min.score(iv(5.0, 25.0)), 0.2);
+
+        let median = ProbabilityProfileConstraint::new(p, ProfileAggregate::Quantile(0.5));
+        // bins: 0.2 ×5, 0.9 ×10, 0.8 ×5 → sorted 0.2(5) 0.8(5) 0.9(10); half of 20 = 10 → 0.8
+        assert_eq!(median.score(iv(5.0, 25.0)), 0.8);
+    }
+
+    #[test]
+    fn best_placement_prefers_reliable_bins() {
+        let c = ProbabilityProfileConstraint::new(night(), ProfileAggregate::Mean);
+        let feasible = IntervalSet::from(vec![iv(0.0, 40.0)]);
+        let (placement, score) = c.best_placement(&feasible, q(10.0)).unwrap();
+        assert_eq!(placement, iv(10.0, 20.0));
+        assert_eq!(score, 0.9);
+    }
+
+    #[test]
+    fn best_placement_none_when_nothing_fits() {
+        let c = ProbabilityProfileConstraint::new(night(), ProfileAggregate::Mean);
+        let feasible = IntervalSet::from(vec![iv(0.0, 5.0)]);
+        assert!(c.best_placement(&feasible, q(10.0)).is_none());
+    }
+
+    #[test]
+    fn stringify_mentions_aggregate() {
+        let c = ProbabilityProfileConstraint::new(night(), ProfileAggregate::Quantile(0.1));
+        assert_eq!(c.stringify(), "ProbabilityProfile(4 bins, q0.10)");
+    }
+}
@@ -0,0 +1,299 @@
+//! Minimal CSV reader/writer shared by the `io::csv` modules.
+//!
+//! Handles exactly what the exchange formats need: three columns per record,
+//! an optional header line, blank lines, and RFC 4180 quoting of the ID field
+//! (`"a,b"`, `"say ""hi"""`), including quoted fields that span lines. A
+//! record is numbered by the line it starts on.
+
+use std::io::{BufRead, Write};
+
+use thiserror::Error;
+
+use crate::constraints::ConstraintError;
+use crate::schedule::errors::ScheduleError;
+use crate::Id;
+
+/// Errors produced while reading or writing CSV.
+#[derive(Debug, Error)]
+pub enum CsvError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("line {line}: expected 3 fields, found {found}")]
+    FieldCount { line: usize, found: usize },
+
+    #[error("line {line}: unterminated quoted field")]
+    UnterminatedQuote { line: usize },
+
+    #[error("line {line}: invalid number {value:?}")]
+    InvalidNumber { line: usize, value: String },
+
+    #[error("line {line}: start {start} is after end {end}")]
+    InvertedInterval { line: usize, start: f64, end: f64 },
+
+    #[error("line {line}: {source}")]
+    Schedule { line: usize, source: ScheduleError },
+
+    #[error("line {line}: {source}")]
+    Bound {
+        line: usize,
+        source: ConstraintError,
+    },
+}
+
+/// One parsed `id,start,end` record.
+pub(crate) struct Row {
+    /// 1-based line number in the input.
+    pub line: usize,
+    pub id: Id,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Reads all records, skipping blank lines and a leading line equal to `header`.
+pub(crate) fn read_rows<R: BufRead>(mut reader: R, header: &str) -> Result<Vec<Row>, CsvError> {
+    let mut rows = Vec::new();
+    let mut seen_content = false;
+    // A record whose quoted field is still open, and the line it started on.
+    let mut pending: Option<(usize, String)> = None;
+    let mut line_no = 0;
+    loop {
+        // Unlike `lines()`, keeps a `\r` before the `\n`: inside quotes it
+        // belongs to the field.
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_no += 1;
+        if line.ends_with('\n') {
+            line.pop();
+        }
+        let (start, record) = match pending.take() {
+            Some((start, mut record)) => {
+                record.push('\n');
+                record.push_str(&line);
+                (start, record)
+            }
+            None => {
+                let text = line.trim_end_matches('\r');
+                if text.trim().is_empty() {
+                    continue;
+                }
+                let first = !seen_content;
+                seen_content = true;
+                if first && is_header(text, header) {
+                    continue;
+                }
+                (line_no, line)
+            }
+        };
+
+        // A line ending outside quotes is never part of a field.
+        let fields = match split_fields(record.trim_end_matches('\r'), start) {
+            Err(CsvError::UnterminatedQuote { .. }) => {
+                pending = Some((start, record));
+                continue;
+            }
+            fields => fields?,
+        };
+        rows.push(parse_row(&fields, start)?);
+    }
+    if let Some((line, _)) = pending {
+        return Err(CsvError::UnterminatedQuote { line });
+    }
+    Ok(rows)
+}
+
+fn parse_row(fields: &[String], line: usize) -> Result<Row, CsvError> {
+    if fields.len() != 3 {
+        return Err(CsvError::FieldCount {
+            line,
+            found: fields.len(),
+        });
+    }
+    let start = parse_number(&fields[1], line)?;
+    let end = parse_number(&fields[2], line)?;
+    if start > end {
+        return Err(CsvError::InvertedInterval { line, start, end });
+    }
+    Ok(Row {
+        line,
+        id: fields[0].clone(),
+        start,
+        end,
+    })
+}
+
+/// Writes the header line.
+pub(crate) fn write_header<W: Write>(writer: &mut W, header: &str) -> Result<(), CsvError> {
+    writeln!(writer, "{header}")?;
+    Ok(())
+}
+
+/// Writes one record. Numbers use Rust's shortest round-trip formatting.
+pub(crate) fn write_row<W: Write>(
+    writer: &mut W,
+    id: &str,
+    start: f64,
+    end: f64,
+) -> Result<(), CsvError> {
+    write_row_with(writer, id, start, end, &[])
+}
+
+/// Writes one record followed by `extra` fields, which must not need
+/// quoting.
+pub(crate) fn write_row_with<W: Write>(
+    writer: &mut W,
+    id: &str,
+    start: f64,
+    end: f64,
+    extra: &[String],
+) -> Result<(), CsvError> {
+    write!(writer, "{},{start},{end}", quote(id))?;
+    for field in extra {
+        write!(writer, ",{field}")?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn is_header(text: &str, header: &str) -> bool {
+    let mut got = text.split(',').map(str::trim);
+    let mut want = header.split(',');
+    loop {
+        match (got.next(), want.next()) {
+            (None, None) => return true,
+            (Some(g), Some(w)) if g.eq_ignore_ascii_case(w) => {}
+            _ => return false,
+        }
+    }
+}
+
+fn parse_number(field: &str, line: usize) -> Result<f64, CsvError> {
+    match field.trim().parse::<f64>() {
+        Ok(v) if v.is_finite() => Ok(v),
+        _ => Err(CsvError::InvalidNumber {
+            line,
+            value: field.to_owned(),
+        }),
+    }
+}
+
+fn split_fields(text: &str, line: usize) -> Result<Vec<String>, CsvError> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            ',' => {
+                fields.push(finish(&mut field, quoted));
+                quoted = false;
+            }
+            '"' if !quoted && field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+                in_quotes = true;
+            }
+            // Anything between a closing quote and the separator is dropped.
+            _ if quoted => {}
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(CsvError::UnterminatedQuote { line });
+    }
+    fields.push(finish(&mut field, quoted));
+    Ok(fields)
+}
+
+/// Quoted fields are kept verbatim; bare fields are trimmed.
+fn finish(field: &mut String, quoted: bool) -> String {
+    let value = std::mem::take(field);
+    if quoted {
+        value
+    } else {
+        value.trim().to_owned()
+    }
+}
+
+fn quote(id: &str) -> String {
+    if id.contains([',', '"', '\n', '\r']) || id.trim() != id {
+        format!("\"{}\"", id.replace('"', "\"\""))
+    } else {
+        id.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(input: &str) -> Result<Vec<Row>, CsvError> {
+        read_rows(input.as_bytes(), "task_id,start,end")
+    }
+
+    #[test]
+    fn skips_header_and_blank_lines() {
+        let r = rows("task_id, start, end\n\na,0,10\r\nb,10,20\n").unwrap();
+        assert_eq!(r.len(), 2);
+        assert_eq!(r[1].id, "b");
+        assert_eq!(r[1].line, 4);
+        assert_eq!((r[1].start, r[1].end), (10.0, 20.0));
+    }
+
+    #[test]
+    fn quoted_ids_round_trip() {
+        for id in ["plain", "a,b", "say \"hi\"", " padded ", "a\nb", "a\r\nb"] {
+            let mut out = Vec::new();
+            write_row(&mut out, id, 1.5, 2.0).unwrap();
+            let r = rows(std::str::from_utf8(&out).unwrap()).unwrap();
+            assert_eq!(r[0].id, id);
+            assert_eq!((r[0].start, r[0].end), (1.5, 2.0));
+        }
+    }
+
+    #[test]
+    fn reports_malformed_rows() {
+        assert!(matches!(
+            rows("a,1\n"),
+            Err(CsvError::FieldCount { line: 1, found: 2 })
+        ));
+        assert!(matches!(
+            rows("a,x,2\n"),
+            Err(CsvError::InvalidNumber { line: 1, .. })
+        ));
+        assert!(matches!(
+            rows("a,NaN,2\n"),
+            Err(CsvError::InvalidNumber { .. })
+        ));
+        assert!(matches!(
+            rows("a,0,inf\n"),
+            Err(CsvError::InvalidNumber { line: 1, .. })
+        ));
+        assert!(matches!(
+            rows("\"a,1,2\n"),
+            Err(CsvError::UnterminatedQuote { line: 1 })
+        ));
+        assert!(matches!(
+            rows("b,0,1\n\"a\n\n1,2\n"),
+            Err(CsvError::UnterminatedQuote { line: 2 })
+        ));
+        assert!(matches!(
+            rows("a,5,2\n"),
+            Err(CsvError::InvertedInterval { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,424 @@
+//! Exact decimal time arithmetic (`decimal` feature).
+//!
+//! Planning runs on `f64`: fast, and precise enough to place tasks. Billing
+//! is different — summing hundreds of float durations and multiplying by a
+//! rate drifts by fractions of a cent, and invoices must add up exactly.
+//!
+//! [`Decimal`] is a fixed-point number with nine fractional digits stored in
+//! an `i128`, so addition and subtraction are exact and multiplication rounds
+//! once, half to even. Values cross over from the planning representation
+//! with [`Decimal::from_f64`], which rounds the float to the nearest
+//! nanounit, and go back with [`Decimal::to_f64`].
+//!
+//! [`DecimalInterval`] and the `Schedule::exact_*` methods apply this to
+//! placements: each duration is rounded once, then summed exactly.
+//!
+//! ```
+//! use virolai::decimal::Decimal;
+//!
+//! let hours: Decimal = "1.1".parse().unwrap();
+//! let rate: Decimal = "33.33".parse().unwrap();
+//! let total = [hours; 3].into_iter().sum::<Decimal>() * rate;
+//! assert_eq!(total.to_string(), "109.989");
+//! assert_eq!(format!("{total:.2}"), "109.99");
+//! ```
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::schedule::Schedule;
+use crate::solution_space::Interval;
+use crate::TaskKey;
+use qtty::{Quantity, Unit};
+
+/// Number of fractional digits kept by [`Decimal`].
+pub const DECIMAL_DIGITS: u32 = 9;
+
+const SCALE: i128 = 10i128.pow(DECIMAL_DIGITS);
+
+/// Largest magnitude accepted from `f64`; keeps products of typical
+/// duration × rate pairs inside `i128`.
+const F64_LIMIT: f64 = 1e18;
+
+/// Error parsing or converting a [`Decimal`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum DecimalError {
+    #[error("invalid decimal literal '{0}'")]
+    Invalid(String),
+    #[error("'{0}' has more than {DECIMAL_DIGITS} fractional digits")]
+    TooPrecise(String),
+    #[error("value {0} is not finite or out of range")]
+    OutOfRange(f64),
+}
+
+/// Fixed-point decimal with nine fractional digits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decimal(i128);
+
+impl Decimal {
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(SCALE);
+
+    /// Creates `units` whole units.
+    pub const fn from_int(units: i64) -> Self {
+        Self(units as i128 * SCALE)
+    }
+
+    /// Creates a value from its raw representation in units of 10⁻⁹.
+    pub const fn from_raw(raw: i128) -> Self {
+        Self(raw)
+    }
+
+    /// Raw representation in units of 10⁻⁹.
+    pub const fn raw(self) -> i128 {
+        self.0
+    }
+
+    /// Converts from `f64`, rounding to the nearest 10⁻⁹ (half to even, on
+    /// the float's exact binary value).
+    pub fn from_f64(value: f64) -> Result<Self, DecimalError> {
+        if !value.is_finite() || value.abs() >= F64_LIMIT {
+            return Err(DecimalError::OutOfRange(value));
+        }
+        // `{:.9}` rounds the exact binary value, avoiding `value * 1e9` error.
+        format!("{value:.prec$}", prec = DECIMAL_DIGITS as usize)
+            .parse()
+            .map_err(|_| DecimalError::OutOfRange(value))
+    }
+
+    /// Nearest `f64`.
+    pub fn to_f64(self) -> f64 {
+        let int = (self.0 / SCALE) as f64;
+        let frac = (self.0 % SCALE) as f64 / SCALE as f64;
+        int + frac
+    }
+
+    /// Rounds to `digits` fractional digits, half to even.
+    pub fn round_dp(self, digits: u32) -> Self {
+        if digits >= DECIMAL_DIGITS {
+            return self;
+        }
+        let step = 10i128.pow(DECIMAL_DIGITS - digits);
+        Self(div_round_half_even(self.0, step) * step)
+    }
+
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// Product rounded half to even, or `None` on overflow.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        self.0
+            .checked_mul(rhs.0)
+            .map(|p| Self(div_round_half_even(p, SCALE)))
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Converts from a quantity's `f64` value.
+    pub fn from_quantity<U: Unit>(q: Quantity<U>) -> Result<Self, DecimalError> {
+        Self::from_f64(q.value())
+    }
+
+    /// Converts to a quantity (nearest `f64`).
+    pub fn to_quantity<U: Unit>(self) -> Quantity<U> {
+        Quantity::new(self.to_f64())
+    }
+}
+
+/// `n / d` rounded to the nearest integer, ties to even (`d > 0`).
+fn div_round_half_even(n: i128, d: i128) -> i128 {
+    let q = n.div_euclid(d);
+    let r = n.rem_euclid(d);
+    match (2 * r).cmp(&d) {
+        std::cmp::Ordering::Less => q,
+        std::cmp::Ordering::Greater => q + 1,
+        std::cmp::Ordering::Equal => q + (q & 1),
+    }
+}
+
+impl Add for Decimal {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Decimal {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Decimal {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics on overflow, like integer multiplication in debug builds.
+    fn mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs)
+            .expect("decimal multiplication overflow")
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(units: i64) -> Self {
+        Self::from_int(units)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = DecimalError;
+
+    /// Parses `[-+]digits[.digits]` exactly; at most nine fractional digits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DecimalError::Invalid(s.to_string());
+        let (negative, body) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (int, frac) = body.split_once('.').unwrap_or((body, ""));
+        let digits = |d: &str| d.bytes().all(|b| b.is_ascii_digit());
+        if int.is_empty() && frac.is_empty() || !digits(int) || !digits(frac) {
+            return Err(invalid());
+        }
+        if frac.len() > DECIMAL_DIGITS as usize {
+            return Err(DecimalError::TooPrecise(s.to_string()));
+        }
+        let int: i128 = if int.is_empty() {
+            0
+        } else {
+            int.parse().map_err(|_| invalid())?
+        };
+        let frac_raw: i128 = if frac.is_empty() {
+            0
+        } else {
+            frac.parse::<i128>().map_err(|_| invalid())?
+                * 10i128.pow(DECIMAL_DIGITS - frac.len() as u32)
+        };
+        let raw = int
+            .checked_mul(SCALE)
+            .and_then(|v| v.checked_add(frac_raw))
+            .ok_or_else(invalid)?;
+        Ok(Self(if negative { -raw } else { raw }))
+    }
+}
+
+impl fmt::Display for Decimal {
+    /// Prints all significant digits, or exactly `precision` digits (rounded
+    /// half to even) when one is given, e.g. `{:.2}` for cents.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match f.precision() {
+            Some(p) => self.round_dp(p as u32),
+            None => *self,
+        };
+        let sign = if value.0 < 0 { "-" } else { "" };
+        let abs = value.0.unsigned_abs();
+        let int = abs / SCALE as u128;
+        let frac = format!("{:09}", abs % SCALE as u128);
+        let frac = match f.precision() {
+            Some(p) if p as u32 >= DECIMAL_DIGITS => {
+                format!("{frac}{}", "0".repeat(p - DECIMAL_DIGITS as usize))
+            }
+            Some(p) => frac[..p].to_string(),
+            None => frac.trim_end_matches('0').to_string(),
+        };
+        if frac.is_empty() {
+            write!(f, "{sign}{int}")
+        } else {
+            write!(f, "{sign}{int}.{frac}")
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Decimal {
+    /// Serialized as a string to keep every digit.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Decimal {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Half-open interval `[start, end)` with exact decimal bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DecimalInterval {
+    start: Decimal,
+    end: Decimal,
+}
+
+impl DecimalInterval {
+    /// Creates `[start, end)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start > end`.
+    pub fn new(start: Decimal, end: Decimal) -> Self {
+        assert!(start <= end, "Interval start must be <= end");
+        Self { start, end }
+    }
+
+    /// Converts a planning interval, rounding each bound to 10⁻⁹.
+    pub fn from_interval<U: Unit>(interval: &Interval<U>) -> Result<Self, DecimalError> {
+        Ok(Self::new(
+            Decimal::from_quantity(interval.start())?,
+            Decimal::from_quantity(interval.end())?,
+        ))
+    }
+
+    /// Converts back to a planning interval (nearest `f64` bounds).
+    pub fn to_interval<U: Unit>(&self) -> Interval<U> {
+        Interval::new(self.start.to_quantity(), self.end.to_quantity())
+    }
+
+    pub fn start(&self) -> Decimal {
+        self.start
+    }
+
+    pub fn end(&self) -> Decimal {
+        self.end
+    }
+
+    pub fn duration(&self) -> Decimal {
+        self.end - self.start
+    }
+}
+
+impl<U: Unit, I: TaskKey> Schedule<U, I> {
+    /// Exact duration of every entry, in start order.
+    ///
+    /// Each bound is rounded to 10⁻⁹ once; the duration is the exact
+    /// difference of the rounded bounds.
+    pub fn exact_durations(&self) -> Result<Vec<(I, Decimal)>, DecimalError> {
+        self.iter()
+            .map(|(id, interval)| Ok((id, DecimalInterval::from_interval(&interval)?.duration())))
+            .collect()
+    }
+
+    /// Exact sum of [`exact_durations`](Self::exact_durations).
+    pub fn exact_total_duration(&self) -> Result<Decimal, DecimalError> {
+        Ok(self.exact_durations()?.into_iter().map(|(_, d)| d).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+
+    fn d(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_roundtrip() {
+        for s in ["0", "12.5", "-0.000000001", "123456789.123456789", "3"] {
+            assert_eq!(d(s).to_string(), s);
+        }
+        assert_eq!(d("+.5").to_string(), "0.5");
+        assert_eq!(d("1.50").to_string(), "1.5");
+        assert_eq!(
+            "1.0000000001".parse::<Decimal>(),
+            Err(DecimalError::TooPrecise("1.0000000001".into()))
+        );
+        assert!("1.2.3".parse::<Decimal>().is_err());
+        assert!("".parse::<Decimal>().is_err());
+        assert!("-".parse::<Decimal>().is_err());
+    }
+
+    #[test]
+    fn sums_are_exact_where_floats_drift() {
+        let float: f64 = std::iter::repeat_n(0.1, 10).sum();
+        assert_ne!(float, 1.0);
+        let exact: Decimal = std::iter::repeat_n(d("0.1"), 10).sum();
+        assert_eq!(exact, Decimal::ONE);
+    }
+
+    #[test]
+    fn from_f64_rounds_to_nanounits() {
+        assert_eq!(Decimal::from_f64(0.1).unwrap(), d("0.1"));
+        assert_eq!(Decimal::from_f64(-2.5).unwrap(), d("-2.5"));
+        assert_eq!(Decimal::from_f64(1e-10).unwrap(), Decimal::ZERO);
+        assert!(Decimal::from_f64(f64::NAN).is_err());
+        assert!(Decimal::from_f64(1e30).is_err());
+        assert_eq!(d("1234.5").to_f64(), 1234.5);
+    }
+
+    #[test]
+    fn multiplication_and_rounding_half_even() {
+        assert_eq!(d("1.5") * d("2"), d("3"));
+        assert_eq!(d("0.000000001") * d("0.5"), Decimal::ZERO);
+        assert_eq!(d("0.000000003") * d("0.5"), d("0.000000002"));
+        assert_eq!(d("2.345").round_dp(2), d("2.34"));
+        assert_eq!(d("2.355").round_dp(2), d("2.36"));
+        assert_eq!(d("-2.355").round_dp(2), d("-2.36"));
+        assert_eq!(format!("{:.2}", d("7")), "7.00");
+        assert_eq!(format!("{:.11}", d("7.1")), "7.10000000000");
+        assert!(Decimal::from_raw(i128::MAX).checked_mul(d("2")).is_none());
+    }
+
+    #[test]
+    fn schedule_durations_are_exact() {
+        let mut s = Schedule::new();
+        s.add("a", iv(0.1, 0.2)).unwrap();
+        s.add("b", iv(0.2, 0.3)).unwrap();
+        s.add("c", iv(0.7, 1.0)).unwrap();
+        let durations = s.exact_durations().unwrap();
+        assert_eq!(durations[0], ("a".to_string(), d("0.1")));
+        assert_eq!(s.exact_total_duration().unwrap(), d("0.5"));
+
+        let interval = DecimalInterval::from_interval(&iv(1.25, 3.5)).unwrap();
+        assert_eq!(interval.duration(), d("2.25"));
+        assert_eq!(interval.to_interval::<qtty::Second>(), iv(1.25, 3.5));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_as_string() {
+        let json = serde_json::to_string(&d("12.34")).unwrap();
+        assert_eq!(json, "\"12.34\"");
+        assert_eq!(serde_json::from_str::<Decimal>(&json).unwrap(), d("12.34"));
+    }
+}
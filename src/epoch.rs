@@ -0,0 +1,544 @@
+//! Epoch layer: mapping the relative scheduling axis to absolute time.
+//!
+//! Schedulers work on a relative time axis (`Quantity<U>` offsets from an
+//! arbitrary origin). An [`Epoch`] pins that origin to an absolute instant so
+//! any relative value can also be rendered as a UTC timestamp.
+//!
+//! [`Epoch::render`] wraps a value so its `Display` output shows both axes
+//! side by side — the internal relative quantity first, followed by the
+//! corresponding absolute timestamp in brackets:
+//!
+//! ```text
+//! 3600.000 s (2024-03-01T01:00:00.000Z)
+//! [0.000, 600.000] (2024-03-01T00:00:00.000Z → 2024-03-01T00:10:00.000Z)
+//! ```
+//!
+//! Supported values:
+//!
+//! | Kind        | Types                                                          |
+//! |-------------|----------------------------------------------------------------|
+//! | Values      | [`Quantity`], [`Interval`], [`IntervalSet`]                    |
+//! | Reports     | [`Schedule`], [`ScheduleStats`], [`ScheduleDiff`]              |
+//! | Diagnostics | [`Violation`], [`LawViolation`], [`RankingSnapshot`]           |
+//! | Errors      | [`ConstraintError`], [`CsvError`]                              |
+//!
+//! Errors keep their usual message and append the absolute form of the
+//! bounds they carry; bounds that are not finite render as `-`. The CSV,
+//! Gantt and Arrow exporters take an [`Epoch`] to add absolute columns next
+//! to the relative ones.
+//!
+//! Absolute times are UTC without leap seconds (POSIX time), which matches
+//! what most data sources and calendar tools expect.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::algorithms::est::RankingSnapshot;
+use crate::constraints::laws::LawViolation;
+use crate::constraints::ConstraintError;
+use crate::schedule::io::csv::CsvError;
+use crate::schedule::{Schedule, ScheduleDiff, ScheduleStats, Violation};
+use crate::solution_space::{Interval, IntervalSet};
+use qtty::time::Time;
+use qtty::{Quantity, Unit};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// An absolute instant, stored as seconds since 1970-01-01T00:00:00Z.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AbsoluteTime(f64);
+
+impl AbsoluteTime {
+    /// The Unix epoch, 1970-01-01T00:00:00Z.
+    pub const UNIX_EPOCH: Self = Self(0.0);
+
+    /// Creates an instant from seconds since the Unix epoch.
+    pub const fn from_unix_seconds(seconds: f64) -> Self {
+        Self(seconds)
+    }
+
+    /// Creates an instant from a UTC calendar date and time of day.
+    ///
+    /// Fields are not range-checked; out-of-range values roll over
+    /// (e.g. hour 24 is midnight of the next day).
+    pub fn from_utc(year: i64, month: u32, day: u32, hour: u32, minute: u32, second: f64) -> Self {
+        let days = days_from_civil(year, month, day) as f64;
+        Self(days * SECONDS_PER_DAY + hour as f64 * 3600.0 + minute as f64 * 60.0 + second)
+    }
+
+    /// Returns seconds since the Unix epoch.
+    pub const fn unix_seconds(self) -> f64 {
+        self.0
+    }
+
+    /// Splits the instant into UTC `(year, month, day, hour, minute, second)`.
+    pub fn to_utc(self) -> (i64, u32, u32, u32, u32, f64) {
+        let days = (self.0 / SECONDS_PER_DAY).floor();
+        let secs_of_day = self.0 - days * SECONDS_PER_DAY;
+        let (y, m, d) = civil_from_days(days as i64);
+        let hour = (secs_of_day / 3600.0).floor() as u32;
+        let minute = ((secs_of_day - hour as f64 * 3600.0) / 60.0).floor() as u32;
+        let second = secs_of_day - hour as f64 * 3600.0 - minute as f64 * 60.0;
+        (y, m, d, hour, minute, second)
+    }
+}
+
+impl fmt::Display for AbsoluteTime {
+    /// Formats as ISO-8601 UTC with millisecond precision.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Round to whole milliseconds first so 59.9996 s does not print as 60.000.
+        let millis = (self.0 * 1000.0).round() / 1000.0;
+        let (y, mo, d, h, mi, s) = AbsoluteTime(millis).to_utc();
+        write!(f, "{y:04}-{mo:02}-{d:02}T{h:02}:{mi:02}:{s:06.3}Z")
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (H. Hinnant's algorithm).
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = m as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Inverse of [`days_from_civil`].
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+/// Anchors the relative time axis `U` to an absolute instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Epoch<U: Unit<Dim = Time>> {
+    origin: AbsoluteTime,
+    _unit: PhantomData<U>,
+}
+
+impl<U: Unit<Dim = Time>> Epoch<U> {
+    /// Creates an epoch whose relative zero is `origin`.
+    pub const fn new(origin: AbsoluteTime) -> Self {
+        Self {
+            origin,
+            _unit: PhantomData,
+        }
+    }
+
+    /// Returns the absolute instant of relative zero.
+    pub const fn origin(&self) -> AbsoluteTime {
+        self.origin
+    }
+
+    /// Converts a relative axis value to an absolute instant.
+    pub fn to_absolute(&self, t: Quantity<U>) -> AbsoluteTime {
+        AbsoluteTime(self.origin.0 + t.value() * U::RATIO)
+    }
+
+    /// Converts an absolute instant to a relative axis value.
+    pub fn to_relative(&self, t: AbsoluteTime) -> Quantity<U> {
+        Quantity::new((t.0 - self.origin.0) / U::RATIO)
+    }
+
+    /// Absolute timestamp of the raw axis value `t`, or `-` if it is not
+    /// finite.
+    fn absolute_or_dash(&self, t: f64) -> String {
+        if t.is_finite() {
+            self.to_absolute(Quantity::new(t)).to_string()
+        } else {
+            "-".to_owned()
+        }
+    }
+
+    /// Converts a relative interval to its absolute `(start, end)`.
+    pub fn interval_to_absolute(&self, interval: Interval<U>) -> (AbsoluteTime, AbsoluteTime) {
+        (
+            self.to_absolute(interval.start()),
+            self.to_absolute(interval.end()),
+        )
+    }
+
+    /// Wraps `value` so that it displays on both the relative and absolute axes.
+    pub fn render<'a, T: ?Sized>(&'a self, value: &'a T) -> WithEpoch<'a, T, U> {
+        WithEpoch { value, epoch: self }
+    }
+}
+
+/// A value paired with an [`Epoch`] for dual-axis display.
+///
+/// Created by [`Epoch::render`].
+#[derive(Debug, Clone, Copy)]
+pub struct WithEpoch<'a, T: ?Sized, U: Unit<Dim = Time>> {
+    value: &'a T,
+    epoch: &'a Epoch<U>,
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, Quantity<U>, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:.3} {} ({})",
+            self.value.value(),
+            U::SYMBOL,
+            self.epoch.to_absolute(*self.value)
+        )
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, Interval<U>, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (start, end) = self.epoch.interval_to_absolute(*self.value);
+        write!(f, "{} ({start} → {end})", self.value)
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, IntervalSet<U>, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, interval) in self.value.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", self.epoch.render(interval))?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, Schedule<U>, U> {
+    /// One line per task in start order: `id: [start, end] (abs → abs)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (id, interval)) in self.value.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{id}: {}", self.epoch.render(&interval))?;
+        }
+        Ok(())
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, ScheduleStats<U>, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "horizon:      {}",
+            self.epoch.render(&self.value.horizon)
+        )?;
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, ScheduleDiff<U>, U> {
+    /// Same layout as the plain diff, with absolute times on every interval.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let diff = self.value;
+        writeln!(
+            f,
+            "{} added, {} removed, {} moved, {} unchanged",
+            diff.added.len(),
+            diff.removed.len(),
+            diff.moved.len(),
+            diff.unchanged
+        )?;
+        for (id, iv) in &diff.added {
+            writeln!(f, "+ {id} {}", self.epoch.render(iv))?;
+        }
+        for (id, iv) in &diff.removed {
+            writeln!(f, "- {id} {}", self.epoch.render(iv))?;
+        }
+        for m in &diff.moved {
+            writeln!(
+                f,
+                "~ {} {} -> {}",
+                m.id,
+                self.epoch.render(&m.old),
+                self.epoch.render(&m.new)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, Violation<U>, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let epoch = self.epoch;
+        match self.value {
+            Violation::OutsideWindows { task_id, placement } => write!(
+                f,
+                "Task {task_id} at {} lies outside its windows",
+                epoch.render(placement)
+            ),
+            Violation::DynamicEdge {
+                source_id,
+                target_id,
+                constraint,
+                placement,
+            } => write!(
+                f,
+                "Task {target_id} at {} violates {constraint} from {source_id}",
+                epoch.render(placement)
+            ),
+            Violation::Blackout {
+                task_id,
+                placement,
+                label,
+            } => write!(
+                f,
+                "Task {task_id} at {} falls in blackout {label}",
+                epoch.render(placement)
+            ),
+            // The other violations carry no time.
+            other => write!(f, "{other}"),
+        }
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, LawViolation<U>, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let epoch = self.epoch;
+        match self.value {
+            LawViolation::NotCanonical { range, result } => write!(
+                f,
+                "result over {} is not canonical: {}",
+                epoch.render(range),
+                epoch.render(result)
+            ),
+            LawViolation::OutOfRange { range, interval } => write!(
+                f,
+                "interval {} lies outside query range {}",
+                epoch.render(interval),
+                epoch.render(range)
+            ),
+            LawViolation::NonDeterministic {
+                range,
+                first,
+                second,
+            } => write!(
+                f,
+                "non-deterministic result over {}: {} vs {}",
+                epoch.render(range),
+                epoch.render(first),
+                epoch.render(second)
+            ),
+            LawViolation::NotMonotone {
+                range,
+                sub_range,
+                extra,
+            } => write!(
+                f,
+                "shrinking {} to {} added feasible time {}",
+                epoch.render(range),
+                epoch.render(sub_range),
+                epoch.render(extra)
+            ),
+        }
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, RankingSnapshot<U>, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let snapshot = self.value;
+        let winner = snapshot.winner();
+        write!(
+            f,
+            "#{} @ {}: {}",
+            snapshot.iteration,
+            self.epoch.render(&snapshot.cursor),
+            winner.task_id
+        )?;
+        match &snapshot.placed {
+            Some(iv) => writeln!(f, " -> {}", self.epoch.render(iv))?,
+            None => writeln!(f, " (not placed)")?,
+        }
+        for runner in snapshot.runners_up() {
+            let reason = runner.beaten_because.expect("runners-up carry a reason");
+            writeln!(f, "  vs {}: {reason}", runner.task_id)?;
+        }
+        Ok(())
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, ConstraintError, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)?;
+        if let ConstraintError::InvalidBound { start, end } = *self.value {
+            write!(
+                f,
+                " ({} → {})",
+                self.epoch.absolute_or_dash(start),
+                self.epoch.absolute_or_dash(end)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<U: Unit<Dim = Time>> fmt::Display for WithEpoch<'_, CsvError, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            CsvError::InvertedInterval { start, end, .. } => write!(
+                f,
+                "{} ({} → {})",
+                self.value,
+                self.epoch.absolute_or_dash(*start),
+                self.epoch.absolute_or_dash(*end)
+            ),
+            CsvError::Bound { line, source } => {
+                write!(f, "line {line}: {}", self.epoch.render(source))
+            }
+            other => write!(f, "{other}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q};
+    use qtty::{Day, Second};
+
+    fn march_first() -> AbsoluteTime {
+        AbsoluteTime::from_utc(2024, 3, 1, 0, 0, 0.0)
+    }
+
+    #[test]
+    fn civil_round_trip() {
+        for days in [-719_468, -1, 0, 1, 11_016, 19_783, 60_000] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days, "{y}-{m}-{d}");
+        }
+    }
+
+    #[test]
+    fn known_dates() {
+        assert_eq!(march_first().unix_seconds(), 1_709_251_200.0);
+        assert_eq!(
+            AbsoluteTime::UNIX_EPOCH.to_string(),
+            "1970-01-01T00:00:00.000Z"
+        );
+        // 2024 is a leap year.
+        let feb29 = AbsoluteTime::from_utc(2024, 2, 29, 23, 59, 59.5);
+        assert_eq!(feb29.to_string(), "2024-02-29T23:59:59.500Z");
+        assert_eq!(
+            AbsoluteTime::from_unix_seconds(-1.0).to_string(),
+            "1969-12-31T23:59:59.000Z"
+        );
+    }
+
+    #[test]
+    fn display_rounds_without_sixty_seconds() {
+        let t = AbsoluteTime::from_unix_seconds(59.9996);
+        assert_eq!(t.to_string(), "1970-01-01T00:01:00.000Z");
+    }
+
+    #[test]
+    fn relative_absolute_round_trip() {
+        let epoch = Epoch::<Second>::new(march_first());
+        let abs = epoch.to_absolute(q(3600.0));
+        assert_eq!(abs.to_string(), "2024-03-01T01:00:00.000Z");
+        assert_eq!(epoch.to_relative(abs), q(3600.0));
+    }
+
+    #[test]
+    fn non_second_axis_uses_unit_ratio() {
+        let epoch = Epoch::<Day>::new(march_first());
+        let abs = epoch.to_absolute(Quantity::new(1.5));
+        assert_eq!(abs.to_string(), "2024-03-02T12:00:00.000Z");
+    }
+
+    #[test]
+    fn render_quantity_and_interval() {
+        let epoch = Epoch::<Second>::new(march_first());
+        assert_eq!(
+            epoch.render(&q(60.0)).to_string(),
+            "60.000 s (2024-03-01T00:01:00.000Z)"
+        );
+        assert_eq!(
+            epoch.render(&iv(0.0, 600.0)).to_string(),
+            "[0.000, 600.000] (2024-03-01T00:00:00.000Z → 2024-03-01T00:10:00.000Z)"
+        );
+    }
+
+    #[test]
+    fn render_schedule_lists_tasks() {
+        let epoch = Epoch::<Second>::new(march_first());
+        let mut s = Schedule::new();
+        s.add("b", iv(60.0, 120.0)).unwrap();
+        s.add("a", iv(0.0, 60.0)).unwrap();
+        let text = epoch.render(&s).to_string();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("a: [0.000, 60.000] (2024-03-01T00:00:00.000Z"));
+        assert!(lines[1].starts_with("b: "));
+    }
+
+    #[test]
+    fn render_stats_includes_absolute_horizon() {
+        let epoch = Epoch::<Second>::new(march_first());
+        let stats = ScheduleStats::from_schedule(&Schedule::new(), iv(0.0, 3600.0));
+        let text = epoch.render(&stats).to_string();
+        assert!(text.starts_with("horizon:      [0.000, 3600.000] (2024-03-01T00:00:00.000Z"));
+        assert!(text.contains("utilization:"));
+    }
+
+    #[test]
+    fn render_diff_and_violations() {
+        let epoch = Epoch::<Second>::new(march_first());
+        let mut old = Schedule::new();
+        old.add("a", iv(0.0, 60.0)).unwrap();
+        let mut new = Schedule::new();
+        new.add("a", iv(60.0, 120.0)).unwrap();
+        let text = epoch.render(&old.diff(&new)).to_string();
+        assert!(text.contains(
+            "~ a [0.000, 60.000] (2024-03-01T00:00:00.000Z → 2024-03-01T00:01:00.000Z) -> [60.000"
+        ));
+
+        let violation = Violation::OutsideWindows {
+            task_id: "a".into(),
+            placement: iv(60.0, 120.0),
+        };
+        assert_eq!(
+            epoch.render(&violation).to_string(),
+            "Task a at [60.000, 120.000] (2024-03-01T00:01:00.000Z → 2024-03-01T00:02:00.000Z) \
+             lies outside its windows"
+        );
+        let untimed = Violation::<Second>::Overlap {
+            first: "a".into(),
+            second: "b".into(),
+        };
+        assert_eq!(epoch.render(&untimed).to_string(), untimed.to_string());
+    }
+
+    #[test]
+    fn render_errors_appends_absolute_bounds() {
+        let epoch = Epoch::<Second>::new(march_first());
+        let err = ConstraintError::InvalidBound {
+            start: 60.0,
+            end: f64::INFINITY,
+        };
+        let text = epoch.render(&err).to_string();
+        assert!(text.starts_with(&err.to_string()));
+        assert!(text.ends_with("(2024-03-01T00:01:00.000Z → -)"));
+
+        let err = CsvError::InvertedInterval {
+            line: 3,
+            start: 120.0,
+            end: 60.0,
+        };
+        assert!(epoch
+            .render(&err)
+            .to_string()
+            .ends_with("(2024-03-01T00:02:00.000Z → 2024-03-01T00:01:00.000Z)"));
+    }
+}
@@ -0,0 +1,269 @@
+//! C bindings (`capi` feature).
+//!
+//! The crate builds as a `cdylib`; link against it and include
+//! `include/virolai.h`. A run is three calls:
+//!
+//! ```c
+//! VirolaiProblem *problem = virolai_problem_from_json(json);
+//! if (!problem) { fprintf(stderr, "%s\n", virolai_last_error()); return 1; }
+//! char *schedule = virolai_schedule(problem, 1);
+//! /* ... read the schedule JSON ... */
+//! virolai_string_free(schedule);
+//! virolai_problem_free(problem);
+//! ```
+//!
+//! Problems are [`ProblemDocument`]s of [`TaskSpec`]s and
+//! [`DynConstraintKind`] edges, as for the [`wasm`](crate::wasm) bindings,
+//! and schedules come back in the [`Schedule`](crate::schedule::Schedule)
+//! JSON format. Functions that can fail return `NULL` and leave a message for
+//! [`virolai_last_error`]; panics are caught and reported the same way.
+//!
+//! The header is generated with
+//! `cbindgen --config cbindgen.toml --output include/virolai.h src/ffi.rs`.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use qtty::Second;
+
+use crate::algorithms::ESTScheduler;
+use crate::constraints::DynConstraintKind;
+use crate::scheduling_block::import::{ImportError, ProblemDocument, TaskSpec};
+use crate::scheduling_block::SchedulingBlock;
+use crate::solution_space::{Interval, SolutionSpace};
+
+/// A loaded problem: a block and the horizon to schedule it in.
+pub struct VirolaiProblem {
+    blocks: [SchedulingBlock<TaskSpec<Second>, Second, DynConstraintKind>; 1],
+    horizon: Interval<Second>,
+    solution_space: SolutionSpace<Second>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<Vec<u8>>) {
+    // Interior NULs cannot cross into C; drop them.
+    let mut bytes = message.into();
+    bytes.retain(|&b| b != 0);
+    let message = CString::new(bytes).expect("NUL bytes removed");
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+/// Runs `f`, turning errors and panics into `None` plus a last error.
+fn guarded<R>(f: impl FnOnce() -> Result<R, String>) -> Option<R> {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(value)) => Some(value),
+        Ok(Err(message)) => {
+            set_last_error(message);
+            None
+        }
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".into());
+            set_last_error(format!("panic: {message}"));
+            None
+        }
+    }
+}
+
+/// Message of the last failure on this thread, or `NULL` if none.
+///
+/// The string is owned by the library and valid until the next failing call
+/// on the same thread; do not free it.
+#[no_mangle]
+pub extern "C" fn virolai_last_error() -> *const c_char {
+    LAST_ERROR.with(|slot| slot.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Loads a problem from a NUL-terminated UTF-8 JSON string.
+///
+/// Returns `NULL` on malformed input. Free the problem with
+/// [`virolai_problem_free`].
+///
+/// # Safety
+///
+/// `json` must be `NULL` or point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn virolai_problem_from_json(json: *const c_char) -> *mut VirolaiProblem {
+    if json.is_null() {
+        set_last_error("json is NULL");
+        return ptr::null_mut();
+    }
+    // SAFETY: non-null and NUL-terminated per the contract above.
+    let json = unsafe { CStr::from_ptr(json) };
+    guarded(|| {
+        let json = json.to_str().map_err(|e| e.to_string())?;
+        load(json).map_err(|e| e.to_string())
+    })
+    .map_or(ptr::null_mut(), |problem| Box::into_raw(Box::new(problem)))
+}
+
+fn load(json: &str) -> Result<VirolaiProblem, ImportError> {
+    let problem = ProblemDocument::<Second, TaskSpec<Second>, DynConstraintKind>::from_json(json)?;
+    let horizon = problem.horizon;
+    let blocks = [problem.into_block()?];
+    let solution_space = SolutionSpace::try_populate(&blocks, horizon)?;
+    Ok(VirolaiProblem {
+        blocks,
+        horizon,
+        solution_space,
+    })
+}
+
+/// Number of tasks in `problem`, or 0 if it is `NULL`.
+///
+/// # Safety
+///
+/// `problem` must be `NULL` or returned by [`virolai_problem_from_json`] and
+/// not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn virolai_problem_task_count(problem: *const VirolaiProblem) -> usize {
+    // SAFETY: valid or NULL per the contract above.
+    unsafe { problem.as_ref() }.map_or(0, |p| p.blocks[0].task_count())
+}
+
+/// Schedules `problem` with the EST scheduler, prerequisites first, and
+/// returns the schedule as a JSON string.
+///
+/// `endangered_threshold` is the scheduler's threshold; 1 is the default.
+/// Returns `NULL` if `problem` is `NULL` or its dependency graph has a cycle.
+/// Free the string with [`virolai_string_free`].
+///
+/// # Safety
+///
+/// `problem` must be `NULL` or returned by [`virolai_problem_from_json`] and
+/// not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn virolai_schedule(
+    problem: *const VirolaiProblem,
+    endangered_threshold: u32,
+) -> *mut c_char {
+    // SAFETY: valid or NULL per the contract above.
+    let Some(problem) = (unsafe { problem.as_ref() }) else {
+        set_last_error("problem is NULL");
+        return ptr::null_mut();
+    };
+    guarded(|| {
+        let schedule = ESTScheduler::new(endangered_threshold)
+            .schedule_layered(&problem.blocks, &problem.solution_space, problem.horizon)
+            .map_err(|e| e.to_string())?;
+        let json = serde_json::to_string(&schedule).map_err(|e| e.to_string())?;
+        CString::new(json).map_err(|e| e.to_string())
+    })
+    .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Frees a problem. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `problem` must be `NULL` or returned by [`virolai_problem_from_json`] and
+/// not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn virolai_problem_free(problem: *mut VirolaiProblem) {
+    if !problem.is_null() {
+        // SAFETY: allocated by `Box::into_raw` and not freed yet.
+        drop(unsafe { Box::from_raw(problem) });
+    }
+}
+
+/// Frees a string returned by the library. `NULL` is ignored.
+///
+/// # Safety
+///
+/// `s` must be `NULL` or returned by [`virolai_schedule`] and not yet
+/// freed.
+#[no_mangle]
+pub unsafe extern "C" fn virolai_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: allocated by `CString::into_raw` and not freed yet.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn c_string(value: &Value) -> CString {
+        CString::new(value.to_string()).unwrap()
+    }
+
+    fn last_error() -> String {
+        let error = virolai_last_error();
+        assert!(!error.is_null());
+        unsafe { CStr::from_ptr(error) }
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn loads_schedules_and_frees() {
+        let json = c_string(&json!({
+            "version": 1,
+            "horizon": { "start": 0.0, "end": 100.0 },
+            "tasks": [
+                { "id": "b", "name": "science", "size": 20.0 },
+                { "id": "a", "name": "calib", "size": 5.0 }
+            ],
+            "edges": [ { "from": "a", "to": "b", "kind": "consecutive" } ]
+        }));
+        unsafe {
+            let problem = virolai_problem_from_json(json.as_ptr());
+            assert!(!problem.is_null());
+            assert_eq!(virolai_problem_task_count(problem), 2);
+
+            let schedule = virolai_schedule(problem, 1);
+            assert!(!schedule.is_null());
+            let out: Value = serde_json::from_slice(CStr::from_ptr(schedule).to_bytes()).unwrap();
+            assert_eq!(out[0]["task"], "a");
+            assert_eq!(out[1]["interval"], json!({ "start": 5.0, "end": 25.0 }));
+
+            virolai_string_free(schedule);
+            virolai_problem_free(problem);
+        }
+    }
+
+    #[test]
+    fn failures_return_null_with_a_message() {
+        unsafe {
+            assert!(virolai_problem_from_json(ptr::null()).is_null());
+            assert_eq!(last_error(), "json is NULL");
+
+            let bad = c_string(
+                &json!({ "version": 9, "horizon": { "start": 0.0, "end": 1.0 }, "tasks": [] }),
+            );
+            assert!(virolai_problem_from_json(bad.as_ptr()).is_null());
+            assert!(last_error().contains("version 9"));
+
+            assert!(virolai_schedule(ptr::null(), 1).is_null());
+            assert_eq!(virolai_problem_task_count(ptr::null()), 0);
+            virolai_problem_free(ptr::null_mut());
+            virolai_string_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn header_declares_every_export() {
+        let header = include_str!("../include/virolai.h");
+        for symbol in [
+            "virolai_last_error",
+            "virolai_problem_from_json",
+            "virolai_problem_task_count",
+            "virolai_schedule",
+            "virolai_problem_free",
+            "virolai_string_free",
+        ] {
+            assert!(header.contains(&format!("{symbol}(")), "{symbol} missing");
+        }
+    }
+}
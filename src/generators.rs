@@ -0,0 +1,390 @@
+//! Synthetic problems for benchmarks.
+//!
+//! [`ProblemGenerator`] draws a scheduling block from a seed, so the same
+//! seed and settings give the same problem on every platform and release.
+//! Four knobs shape it:
+//!
+//! - **size** — the number of tasks and the range their sizes are drawn from;
+//! - **window density** — the share of the horizon each task's windows cover;
+//! - **dependency depth** — the number of layers linked by `Dependence`
+//!   edges, each task after the first layer depending on one task of the
+//!   layer before;
+//! - **priorities** — a [`PriorityDistribution`].
+//!
+//! # Example
+//!
+//! ```
+//! use qtty::Second;
+//! use virolai::algorithms::{ESTScheduler, SchedulingAlgorithm};
+//! use virolai::generators::{PriorityDistribution, ProblemGenerator};
+//!
+//! let problem = ProblemGenerator::new(42)
+//!     .with_tasks(200)
+//!     .with_window_density(0.3)
+//!     .with_dependency_depth(4)
+//!     .with_priorities(PriorityDistribution::Uniform { min: 0, max: 10 })
+//!     .generate::<Second>();
+//!
+//! let solution_space = problem.solution_space();
+//! let schedule = ESTScheduler::default().schedule(
+//!     std::slice::from_ref(&problem.block),
+//!     &solution_space,
+//!     problem.horizon,
+//! );
+//! assert!(schedule.len() <= 200);
+//! ```
+
+use qtty::{Quantity, Unit};
+
+use crate::constraints::{ConstraintExpr, DynConstraintKind, IntervalConstraint};
+use crate::rng::{derive_seed, SplitMix64};
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{Interval, SolutionSpace};
+
+/// How task priorities are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityDistribution {
+    /// Every task gets this priority.
+    Constant(i32),
+    /// Uniform over `min..=max`.
+    Uniform { min: i32, max: i32 },
+    /// Over `0..=max`, each level about half as likely as the one below, so
+    /// few tasks are urgent.
+    Skewed { max: i32 },
+}
+
+impl PriorityDistribution {
+    fn sample(&self, rng: &mut SplitMix64) -> i32 {
+        match *self {
+            PriorityDistribution::Constant(priority) => priority,
+            PriorityDistribution::Uniform { min, max } => {
+                let span = (max as i64 - min as i64 + 1) as f64;
+                (min as i64 + (rng.next_f64() * span) as i64).min(max as i64) as i32
+            }
+            PriorityDistribution::Skewed { max } => {
+                let mut priority = 0;
+                while priority < max && rng.next_f64() < 0.5 {
+                    priority += 1;
+                }
+                priority
+            }
+        }
+    }
+}
+
+/// Task of a generated problem.
+#[derive(Debug, Clone)]
+pub struct SyntheticTask<U: Unit + Send + Sync> {
+    pub(crate) name: String,
+    pub(crate) size: Quantity<U>,
+    pub(crate) priority: i32,
+    pub(crate) constraints: Option<ConstraintExpr<IntervalConstraint<U>>>,
+}
+
+impl<U: Unit + Send + Sync> Task<U> for SyntheticTask<U> {
+    type SizeUnit = U;
+    type ConstraintLeaf = IntervalConstraint<U>;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn size(&self) -> Quantity<U> {
+        self.size
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn constraints(&self) -> Option<&ConstraintExpr<IntervalConstraint<U>>> {
+        self.constraints.as_ref()
+    }
+}
+
+/// A generated block and the horizon it was generated for.
+#[derive(Debug)]
+pub struct GeneratedProblem<U: Unit + Send + Sync> {
+    pub block: SchedulingBlock<SyntheticTask<U>, U, DynConstraintKind>,
+    pub horizon: Interval<U>,
+}
+
+impl<U: Unit + Send + Sync> GeneratedProblem<U> {
+    /// Windows of every task within the horizon.
+    pub fn solution_space(&self) -> SolutionSpace<U> {
+        SolutionSpace::populate(std::slice::from_ref(&self.block), self.horizon)
+    }
+}
+
+/// Seeded generator of synthetic scheduling blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProblemGenerator {
+    seed: u64,
+    tasks: usize,
+    horizon: f64,
+    min_size: f64,
+    max_size: f64,
+    window_density: f64,
+    windows_per_task: usize,
+    dependency_depth: usize,
+    priorities: PriorityDistribution,
+}
+
+impl ProblemGenerator {
+    /// 100 tasks of size 5–20 over `[0, 1000)`, each with 3 windows covering
+    /// half of it, no dependencies and priorities uniform over `0..=10`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            tasks: 100,
+            horizon: 1000.0,
+            min_size: 5.0,
+            max_size: 20.0,
+            window_density: 0.5,
+            windows_per_task: 3,
+            dependency_depth: 1,
+            priorities: PriorityDistribution::Uniform { min: 0, max: 10 },
+        }
+    }
+
+    /// Number of tasks.
+    pub fn with_tasks(mut self, tasks: usize) -> Self {
+        self.tasks = tasks;
+        self
+    }
+
+    /// Horizon `[0, length)`.
+    ///
+    /// # Panics
+    ///
+    /// If `length` is not finite and positive.
+    pub fn with_horizon(mut self, length: f64) -> Self {
+        assert!(
+            length.is_finite() && length > 0.0,
+            "horizon length must be finite and positive, got {length}"
+        );
+        self.horizon = length;
+        self
+    }
+
+    /// Task sizes, drawn uniformly from `[min, max]`.
+    ///
+    /// # Panics
+    ///
+    /// If `min` is negative or `max < min`.
+    pub fn with_sizes(mut self, min: f64, max: f64) -> Self {
+        assert!(
+            min >= 0.0 && max >= min && max.is_finite(),
+            "sizes need 0 <= min <= max, got [{min}, {max}]"
+        );
+        self.min_size = min;
+        self.max_size = max;
+        self
+    }
+
+    /// Share of the horizon each task's windows cover, in `(0, 1]`, spread
+    /// over `windows` windows. At 1 tasks are unconstrained.
+    ///
+    /// Windows are never shorter than their task, and overlapping windows
+    /// merge, so the realised share can differ.
+    ///
+    /// # Panics
+    ///
+    /// If `density` is outside `(0, 1]`.
+    pub fn with_window_density(mut self, density: f64) -> Self {
+        assert!(
+            density > 0.0 && density <= 1.0,
+            "window density must be in (0, 1], got {density}"
+        );
+        self.window_density = density;
+        self
+    }
+
+    /// Number of windows per constrained task (at least one).
+    pub fn with_windows_per_task(mut self, windows: usize) -> Self {
+        self.windows_per_task = windows.max(1);
+        self
+    }
+
+    /// Number of dependency layers (at least one). With `depth` layers the
+    /// longest `Dependence` chain has `min(depth, tasks)` tasks.
+    pub fn with_dependency_depth(mut self, depth: usize) -> Self {
+        self.dependency_depth = depth.max(1);
+        self
+    }
+
+    /// How priorities are drawn.
+    pub fn with_priorities(mut self, priorities: PriorityDistribution) -> Self {
+        self.priorities = priorities;
+        self
+    }
+
+    /// Generates the problem. Task IDs are `t0`, `t1`, … in layer order.
+    pub fn generate<U: Unit + Send + Sync>(&self) -> GeneratedProblem<U> {
+        let mut block = SchedulingBlock::new();
+        let mut nodes = Vec::with_capacity(self.tasks);
+        let mut layer_starts = Vec::new();
+        for i in 0..self.tasks {
+            let layer = self.layer_of(i);
+            if layer == layer_starts.len() {
+                layer_starts.push(i);
+            }
+            let mut rng = SplitMix64::new(derive_seed(self.seed, i as u64));
+            let size = self.min_size + rng.next_f64() * (self.max_size - self.min_size);
+            let task = SyntheticTask {
+                name: format!("task {i}"),
+                size: Quantity::new(size),
+                priority: self.priorities.sample(&mut rng),
+                constraints: self.windows(size, &mut rng),
+            };
+            let id = block
+                .add_task_with_id(task, Some(format!("t{i}")))
+                .expect("generated IDs are unique");
+            let node = block.node_of(&id).expect("task was just added");
+
+            if layer > 0 {
+                let (first, end) = (layer_starts[layer - 1], layer_starts[layer]);
+                let parent = first + ((rng.next_f64() * (end - first) as f64) as usize);
+                block
+                    .add_dependency(
+                        nodes[parent.min(end - 1)],
+                        node,
+                        DynConstraintKind::Dependence,
+                    )
+                    .expect("edges only point to later layers");
+            }
+            nodes.push(node);
+        }
+        GeneratedProblem {
+            block,
+            horizon: Interval::from_f64(0.0, self.horizon),
+        }
+    }
+
+    /// Layer of task `index`; consecutive tasks are at most one layer apart.
+    fn layer_of(&self, index: usize) -> usize {
+        index * self.dependency_depth.min(self.tasks) / self.tasks.max(1)
+    }
+
+    fn windows<U: Unit + Send + Sync>(
+        &self,
+        size: f64,
+        rng: &mut SplitMix64,
+    ) -> Option<ConstraintExpr<IntervalConstraint<U>>> {
+        if self.window_density >= 1.0 {
+            return None;
+        }
+        let width = (self.window_density * self.horizon / self.windows_per_task as f64)
+            .max(size)
+            .min(self.horizon);
+        let leaves = (0..self.windows_per_task)
+            .map(|_| {
+                let end = (rng.next_f64() * (self.horizon - width) + width).min(self.horizon);
+                let mut start = (end - width).max(0.0);
+                // Keep rounding from leaving the window short of its task.
+                while end - start < size && start > 0.0 {
+                    start = start.next_down().max(0.0);
+                }
+                ConstraintExpr::leaf(IntervalConstraint::new(Interval::from_f64(start, end)))
+            })
+            .collect();
+        Some(ConstraintExpr::union(leaves))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::Constraint;
+    use qtty::Second;
+
+    #[test]
+    fn same_seed_same_problem() {
+        let generator = ProblemGenerator::new(7)
+            .with_tasks(50)
+            .with_dependency_depth(3);
+        let (a, b) = (
+            generator.generate::<Second>(),
+            generator.generate::<Second>(),
+        );
+        let ids = |p: &GeneratedProblem<Second>| {
+            p.block
+                .tasks()
+                .map(|(id, t)| (id.to_owned(), (t.size().value().to_bits(), t.priority())))
+                .collect::<std::collections::BTreeMap<_, _>>()
+        };
+        assert_eq!(ids(&a), ids(&b));
+        let (sa, sb) = (a.solution_space(), b.solution_space());
+        assert!(a
+            .block
+            .tasks()
+            .all(|(id, _)| sa.get_intervals(id) == sb.get_intervals(id)));
+        let other = ProblemGenerator::new(8).with_tasks(50).generate::<Second>();
+        assert_ne!(ids(&a), ids(&other));
+    }
+
+    #[test]
+    fn windows_fit_their_tasks_within_the_horizon() {
+        let problem = ProblemGenerator::new(1)
+            .with_tasks(40)
+            .with_horizon(500.0)
+            .with_sizes(10.0, 30.0)
+            .with_window_density(0.1)
+            .with_windows_per_task(2)
+            .generate::<Second>();
+        assert_eq!(problem.block.task_count(), 40);
+        for (_, task) in problem.block.tasks() {
+            let size = task.size().value();
+            assert!((10.0..=30.0).contains(&size));
+            let windows = task
+                .constraints()
+                .unwrap()
+                .compute_intervals(problem.horizon);
+            assert!(windows.iter().all(|w| w.end().value() <= 500.0));
+            assert!(windows.iter().any(|w| w.duration().value() >= size));
+        }
+        let open = ProblemGenerator::new(1)
+            .with_window_density(1.0)
+            .generate::<Second>();
+        assert!(open.block.tasks().all(|(_, t)| t.constraints().is_none()));
+    }
+
+    #[test]
+    fn depth_sets_the_longest_dependency_chain() {
+        let problem = ProblemGenerator::new(3)
+            .with_tasks(30)
+            .with_dependency_depth(5)
+            .generate::<Second>();
+        let layers = problem
+            .block
+            .topological_layers_by(|kind| *kind == DynConstraintKind::Dependence)
+            .unwrap();
+        assert_eq!(layers.len(), 5);
+        // Every task after the first layer has exactly one parent.
+        assert_eq!(problem.block.graph().edge_count(), 30 - layers[0].len());
+    }
+
+    #[test]
+    fn priorities_follow_the_distribution() {
+        let priorities = |dist| {
+            ProblemGenerator::new(5)
+                .with_tasks(400)
+                .with_priorities(dist)
+                .generate::<Second>()
+                .block
+                .tasks()
+                .map(|(_, t)| t.priority())
+                .collect::<Vec<_>>()
+        };
+        assert!(priorities(PriorityDistribution::Constant(4))
+            .iter()
+            .all(|&p| p == 4));
+        let uniform = priorities(PriorityDistribution::Uniform { min: -2, max: 2 });
+        assert!(uniform.iter().all(|p| (-2..=2).contains(p)));
+        assert!((-2..=2).all(|p| uniform.contains(&p)));
+        let skewed = priorities(PriorityDistribution::Skewed { max: 6 });
+        let low = skewed.iter().filter(|&&p| p == 0).count();
+        let high = skewed.iter().filter(|&&p| p >= 3).count();
+        assert!(low > high && skewed.iter().all(|p| (0..=6).contains(p)));
+    }
+}
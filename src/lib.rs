@@ -2,24 +2,82 @@
 //!
 //! A constraint-based task scheduling library supporting dependency graphs,
 //! solution spaces, and prescheduling utilities.
+//!
+//! # Stability
+//!
+//! [`prelude`] re-exports the supported surface: tasks, constraints,
+//! solution spaces, schedulers, schedules and their reports. Downstream
+//! crates that stick to it can upgrade without churn.
+//!
+//! Everything the crate's own entry points build on — the CLI, the HTTP
+//! server, the C API and the wasm bindings, including the [`registry`] they
+//! resolve scheduler names through — is held to the same standard.
+//!
+//! An item is experimental, compiled only with the `unstable` feature and
+//! liable to change in any release, when it goes beyond the greedy,
+//! one-decision-at-a-time placement every stable scheduler makes, or when it
+//! maps an outside problem format whose translation is still being settled:
+//!
+//! - `adapters` - Job-shop and timetabling adapters
+//! - `ESTScheduler::schedule_preemptive` - Evicts tasks already placed
+//! - `ESTScheduler::with_lookahead` - Simulates placements before deciding
+//! - the RL environment, policies and training - enabling the `rl` or
+//!   `rl-nn` feature also enables `unstable`
 
+#[cfg(feature = "unstable")]
+pub mod adapters;
 pub mod algorithms;
+pub mod analysis;
 pub mod constraints;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+pub mod epoch;
+#[cfg(feature = "capi")]
+pub mod ffi;
+pub mod generators;
+pub mod prelude;
+pub mod registry;
 pub mod resource;
 pub mod schedule;
 pub mod scheduling_block;
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod solution_space;
+#[cfg(any(feature = "proptest", feature = "arbitrary"))]
+pub mod testing;
+pub mod ticks;
 pub mod units;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub(crate) mod clock;
+pub(crate) mod csv;
+pub(crate) mod rng;
 
 #[cfg(test)]
 pub(crate) mod test_utils;
 
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+
 // Re-export unit conversion traits for ergonomic use
 pub use units::{convert, SameDim};
 
 /// Identifier type used for tasks, resources, and scheduling artifacts.
 pub type Id = String;
 
+/// Key type accepted wherever a task is looked up by identity.
+///
+/// [`Schedule`](schedule::Schedule), [`SolutionSpace`](solution_space::SolutionSpace)
+/// and [`DynamicConstraintIndex`](constraints::DynamicConstraintIndex) take it
+/// as a type parameter defaulting to [`Id`], so integrators can key them by
+/// UUIDs or integers directly. Blanket-implemented for every eligible type.
+pub trait TaskKey: Clone + Eq + Hash + Debug + Display {}
+
+impl<K: Clone + Eq + Hash + Debug + Display> TaskKey for K {}
+
 /// Generates a new unique identifier (UUID v4).
 pub fn generate_id() -> Id {
     uuid::Uuid::new_v4().to_string()
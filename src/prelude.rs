@@ -0,0 +1,34 @@
+//! The supported, stable surface of the crate.
+//!
+//! ```
+//! use virolai::prelude::*;
+//! ```
+//!
+//! brings in everything needed to model tasks, constrain them, build a
+//! solution space, run a scheduler, and inspect the result. Items are only
+//! removed from or changed in the prelude with a breaking version bump; the
+//! rest of the public API may be reorganised between minor versions.
+//!
+//! Experimental items (see [the crate docs](crate#stability)) are not
+//! re-exported here and are only compiled with the `unstable` feature.
+
+// Tasks and scheduling blocks.
+pub use crate::scheduling_block::{SchedulingBlock, SchedulingError, Task};
+pub use crate::Id;
+
+// Constraints.
+pub use crate::constraints::{
+    Constraint, ConstraintExpr, DynConstraintKind, DynamicConstraint, IntervalConstraint,
+};
+
+// Solution space.
+pub use crate::solution_space::{Interval, IntervalSet, SolutionSpace};
+
+// Schedulers.
+pub use crate::algorithms::{
+    ESTScheduler, MultiResourceAlgorithm, SchedulerResult, SchedulingAlgorithm, TaskOutcome,
+    TieBreak,
+};
+
+// Schedules and reports.
+pub use crate::schedule::{validate, Schedule, ScheduleStats, Violation};
@@ -0,0 +1,581 @@
+//! Name-keyed registry of constraint kinds, metrics, exporters and
+//! schedulers.
+//!
+//! Front ends that pick components by name — a command line, a problem-file
+//! loader — look them up in a [`Registry`] instead of matching on a closed
+//! set of names. [`Registry::with_builtins`] holds what this crate ships;
+//! downstream crates add their own with the `register_*` methods or bundle
+//! them as a [`Plugin`]:
+//!
+//! ```
+//! use virolai::registry::{Plugin, Registry, RegistryError};
+//! use virolai::scheduling_block::Task;
+//! use qtty::{Quantity, Second};
+//!
+//! #[derive(Debug, Clone)]
+//! struct Job;
+//!
+//! impl Task<Second> for Job {
+//!     type SizeUnit = Second;
+//!     type ConstraintLeaf = virolai::constraints::IntervalConstraint<Second>;
+//!     fn name(&self) -> &str { "job" }
+//!     fn size(&self) -> Quantity<Second> { Quantity::new(60.0) }
+//! }
+//!
+//! struct TaskCount;
+//!
+//! impl Plugin<Job, Second> for TaskCount {
+//!     fn name(&self) -> &str {
+//!         "task-count"
+//!     }
+//!
+//!     fn register(&self, registry: &mut Registry<Job, Second>) -> Result<(), RegistryError> {
+//!         registry.register_metric("task_count", |schedule, _| schedule.len() as f64)
+//!     }
+//! }
+//!
+//! let mut registry = Registry::<Job, Second>::with_builtins();
+//! registry.install(&TaskCount).unwrap();
+//! assert!(registry.metric_names().contains(&"task_count"));
+//! assert!(registry.scheduler("est", "threshold=2").is_ok());
+//! ```
+//!
+//! # Built-ins
+//!
+//! | Kind        | Names                                                  |
+//! |-------------|--------------------------------------------------------|
+//! | constraint  | `interval` (parameters `"start,end"`)                  |
+//! | metric      | `utilization`, `busy_time`, `idle_time`, `makespan`    |
+//! | exporter    | `csv`, `gantt` (with `serde`)                          |
+//! | scheduler   | `est`, `est-layered` (parameters `threshold=N,seed=S`, |
+//! |             | both optional)                                         |
+//!
+//! Built-ins are provided for the crate's own edge data,
+//! [`DynConstraintKind`]. Names are unique per kind; registering a taken
+//! name fails rather than silently replacing a built-in.
+//!
+//! Schedulers come back as `Box<dyn SchedulingAlgorithm>`, which is itself a
+//! [`SchedulingAlgorithm`], so a scheduler chosen by name can be wrapped by
+//! the drivers in [`algorithms`](crate::algorithms) like a concrete one.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use petgraph::{Directed, EdgeType};
+use qtty::{Quantity, Unit};
+use thiserror::Error;
+
+use crate::algorithms::est::LayeredScheduler;
+use crate::algorithms::{ESTScheduler, SchedulingAlgorithm, TieBreak};
+use crate::constraints::{Constraint, DynConstraintKind, IntervalConstraint};
+use crate::schedule::{Schedule, ScheduleStats};
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::Interval;
+
+/// Which table of a [`Registry`] a name belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryKind {
+    Constraint,
+    Metric,
+    Exporter,
+    Scheduler,
+}
+
+impl fmt::Display for RegistryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Constraint => "constraint",
+            Self::Metric => "metric",
+            Self::Exporter => "exporter",
+            Self::Scheduler => "scheduler",
+        })
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    #[error("A {kind} named {name:?} is already registered")]
+    Duplicate { kind: RegistryKind, name: String },
+
+    #[error("No {kind} named {name:?} is registered")]
+    Unknown { kind: RegistryKind, name: String },
+
+    #[error("Invalid parameters for {kind} {name:?}: {reason}")]
+    InvalidParameters {
+        kind: RegistryKind,
+        name: String,
+        reason: String,
+    },
+}
+
+/// Builds a constraint from its parameter text.
+pub type ConstraintFactory<U> =
+    Box<dyn Fn(&str) -> Result<Box<dyn Constraint<U>>, String> + Send + Sync>;
+
+/// Scores a schedule over a horizon.
+pub type MetricFn<U> = Box<dyn Fn(&Schedule<U>, Interval<U>) -> f64 + Send + Sync>;
+
+/// Renders a schedule, given the blocks it was built from.
+pub type ExporterFn<T, U, D, E> =
+    Box<dyn Fn(&Schedule<U>, &[SchedulingBlock<T, U, D, E>]) -> String + Send + Sync>;
+
+/// Creates a scheduler from its parameter text.
+pub type SchedulerFactory<T, U, D, E> =
+    Box<dyn Fn(&str) -> Result<Box<dyn SchedulingAlgorithm<T, U, D, E>>, String> + Send + Sync>;
+
+/// A bundle of registrations shipped by a downstream crate.
+pub trait Plugin<T, U, D = DynConstraintKind, E = Directed>
+where
+    T: Task<U>,
+    U: Unit,
+    E: EdgeType,
+{
+    fn name(&self) -> &str;
+
+    /// Adds this plugin's components to `registry`.
+    fn register(&self, registry: &mut Registry<T, U, D, E>) -> Result<(), RegistryError>;
+}
+
+/// Components available by name, per kind.
+pub struct Registry<T, U, D = DynConstraintKind, E = Directed>
+where
+    T: Task<U>,
+    U: Unit,
+    E: EdgeType,
+{
+    constraints: BTreeMap<String, ConstraintFactory<U>>,
+    metrics: BTreeMap<String, MetricFn<U>>,
+    exporters: BTreeMap<String, ExporterFn<T, U, D, E>>,
+    schedulers: BTreeMap<String, SchedulerFactory<T, U, D, E>>,
+    plugins: Vec<String>,
+}
+
+impl<T, U, D, E> Default for Registry<T, U, D, E>
+where
+    T: Task<U>,
+    U: Unit,
+    E: EdgeType,
+{
+    fn default() -> Self {
+        Self {
+            constraints: BTreeMap::new(),
+            metrics: BTreeMap::new(),
+            exporters: BTreeMap::new(),
+            schedulers: BTreeMap::new(),
+            plugins: Vec::new(),
+        }
+    }
+}
+
+impl<T, U, D, E> fmt::Debug for Registry<T, U, D, E>
+where
+    T: Task<U>,
+    U: Unit,
+    E: EdgeType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field("constraints", &self.constraint_names())
+            .field("metrics", &self.metric_names())
+            .field("exporters", &self.exporter_names())
+            .field("schedulers", &self.scheduler_names())
+            .field("plugins", &self.plugins)
+            .finish()
+    }
+}
+
+/// Inserts `value` under `name` unless the name is taken.
+fn insert<V>(
+    table: &mut BTreeMap<String, V>,
+    kind: RegistryKind,
+    name: impl Into<String>,
+    value: V,
+) -> Result<(), RegistryError> {
+    let name = name.into();
+    if table.contains_key(&name) {
+        return Err(RegistryError::Duplicate { kind, name });
+    }
+    table.insert(name, value);
+    Ok(())
+}
+
+/// Looks `name` up in `table`.
+fn lookup<'a, V>(
+    table: &'a BTreeMap<String, V>,
+    kind: RegistryKind,
+    name: &str,
+) -> Result<&'a V, RegistryError> {
+    table.get(name).ok_or_else(|| RegistryError::Unknown {
+        kind,
+        name: name.to_string(),
+    })
+}
+
+impl<T, U, D, E> Registry<T, U, D, E>
+where
+    T: Task<U>,
+    U: Unit,
+    E: EdgeType,
+{
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `plugin`'s components. The plugin's name is recorded only
+    /// if every registration succeeds; earlier ones are kept either way.
+    pub fn install(&mut self, plugin: &dyn Plugin<T, U, D, E>) -> Result<(), RegistryError> {
+        plugin.register(self)?;
+        self.plugins.push(plugin.name().to_string());
+        Ok(())
+    }
+
+    /// Names of the installed plugins, in installation order.
+    pub fn plugins(&self) -> &[String] {
+        &self.plugins
+    }
+
+    pub fn register_constraint(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&str) -> Result<Box<dyn Constraint<U>>, String> + Send + Sync + 'static,
+    ) -> Result<(), RegistryError> {
+        insert(
+            &mut self.constraints,
+            RegistryKind::Constraint,
+            name,
+            Box::new(factory),
+        )
+    }
+
+    pub fn register_metric(
+        &mut self,
+        name: impl Into<String>,
+        metric: impl Fn(&Schedule<U>, Interval<U>) -> f64 + Send + Sync + 'static,
+    ) -> Result<(), RegistryError> {
+        insert(
+            &mut self.metrics,
+            RegistryKind::Metric,
+            name,
+            Box::new(metric),
+        )
+    }
+
+    pub fn register_exporter(
+        &mut self,
+        name: impl Into<String>,
+        exporter: impl Fn(&Schedule<U>, &[SchedulingBlock<T, U, D, E>]) -> String
+            + Send
+            + Sync
+            + 'static,
+    ) -> Result<(), RegistryError> {
+        insert(
+            &mut self.exporters,
+            RegistryKind::Exporter,
+            name,
+            Box::new(exporter),
+        )
+    }
+
+    pub fn register_scheduler(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn(&str) -> Result<Box<dyn SchedulingAlgorithm<T, U, D, E>>, String>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Result<(), RegistryError> {
+        insert(
+            &mut self.schedulers,
+            RegistryKind::Scheduler,
+            name,
+            Box::new(factory),
+        )
+    }
+
+    /// Builds the constraint `name` from `params`.
+    pub fn constraint(
+        &self,
+        name: &str,
+        params: &str,
+    ) -> Result<Box<dyn Constraint<U>>, RegistryError> {
+        let factory = lookup(&self.constraints, RegistryKind::Constraint, name)?;
+        factory(params).map_err(|reason| RegistryError::InvalidParameters {
+            kind: RegistryKind::Constraint,
+            name: name.to_string(),
+            reason,
+        })
+    }
+
+    /// Evaluates the metric `name`.
+    pub fn metric(
+        &self,
+        name: &str,
+        schedule: &Schedule<U>,
+        horizon: Interval<U>,
+    ) -> Result<f64, RegistryError> {
+        lookup(&self.metrics, RegistryKind::Metric, name).map(|m| m(schedule, horizon))
+    }
+
+    /// Runs the exporter `name`.
+    pub fn export(
+        &self,
+        name: &str,
+        schedule: &Schedule<U>,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+    ) -> Result<String, RegistryError> {
+        lookup(&self.exporters, RegistryKind::Exporter, name).map(|x| x(schedule, blocks))
+    }
+
+    /// Creates the scheduler `name` from `params`.
+    pub fn scheduler(
+        &self,
+        name: &str,
+        params: &str,
+    ) -> Result<Box<dyn SchedulingAlgorithm<T, U, D, E>>, RegistryError> {
+        let factory = lookup(&self.schedulers, RegistryKind::Scheduler, name)?;
+        factory(params).map_err(|reason| RegistryError::InvalidParameters {
+            kind: RegistryKind::Scheduler,
+            name: name.to_string(),
+            reason,
+        })
+    }
+
+    /// Registered constraint names, sorted.
+    pub fn constraint_names(&self) -> Vec<&str> {
+        self.constraints.keys().map(String::as_str).collect()
+    }
+
+    /// Registered metric names, sorted.
+    pub fn metric_names(&self) -> Vec<&str> {
+        self.metrics.keys().map(String::as_str).collect()
+    }
+
+    /// Registered exporter names, sorted.
+    pub fn exporter_names(&self) -> Vec<&str> {
+        self.exporters.keys().map(String::as_str).collect()
+    }
+
+    /// Registered scheduler names, sorted.
+    pub fn scheduler_names(&self) -> Vec<&str> {
+        self.schedulers.keys().map(String::as_str).collect()
+    }
+}
+
+impl<T, U, E> Registry<T, U, DynConstraintKind, E>
+where
+    T: Task<U> + Clone,
+    U: Unit + Send + Sync,
+    E: EdgeType,
+{
+    /// A registry holding the components this crate ships.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.add_builtins();
+        registry
+    }
+
+    fn add_builtins(&mut self) {
+        let taken = "built-in names are unique";
+        self.register_constraint("interval", |params| {
+            let (start, end) = params
+                .split_once(',')
+                .ok_or_else(|| format!("expected \"start,end\", got {params:?}"))?;
+            let parse = |s: &str| s.trim().parse::<f64>().map_err(|e| e.to_string());
+            let interval = Interval::new(Quantity::new(parse(start)?), Quantity::new(parse(end)?));
+            Ok(Box::new(IntervalConstraint::new(interval)) as Box<dyn Constraint<U>>)
+        })
+        .expect(taken);
+
+        self.register_metric("utilization", |s, h| {
+            ScheduleStats::from_schedule(s, h).utilization
+        })
+        .expect(taken);
+        self.register_metric("busy_time", |s, h| {
+            ScheduleStats::from_schedule(s, h).busy_time.value()
+        })
+        .expect(taken);
+        self.register_metric("idle_time", |s, h| {
+            ScheduleStats::from_schedule(s, h).idle_time.value()
+        })
+        .expect(taken);
+        self.register_metric("makespan", |s, h| {
+            ScheduleStats::from_schedule(s, h)
+                .makespan
+                .map_or(0.0, |m| m.value())
+        })
+        .expect(taken);
+
+        self.register_exporter("csv", |s, _| crate::schedule::io::csv::to_string(s))
+            .expect(taken);
+        #[cfg(feature = "serde")]
+        self.register_exporter("gantt", |s, blocks| s.to_gantt_json(blocks))
+            .expect(taken);
+
+        self.register_scheduler("est", |params| {
+            let est = est_from_params(params)?;
+            Ok(Box::new(est) as Box<dyn SchedulingAlgorithm<T, U, DynConstraintKind, E>>)
+        })
+        .expect(taken);
+        self.register_scheduler("est-layered", |params| {
+            let layered = LayeredScheduler::new(est_from_params(params)?);
+            Ok(Box::new(layered) as Box<dyn SchedulingAlgorithm<T, U, DynConstraintKind, E>>)
+        })
+        .expect(taken);
+    }
+}
+
+/// Builds an [`ESTScheduler`] from `"threshold=N,seed=S"`.
+fn est_from_params(params: &str) -> Result<ESTScheduler, String> {
+    let mut threshold = 1;
+    let mut tie_break = TieBreak::Deterministic;
+    for (key, value) in key_values(params)? {
+        let invalid = |e: std::num::ParseIntError| format!("{key}: {e}");
+        match key {
+            "threshold" => threshold = value.parse().map_err(invalid)?,
+            "seed" => tie_break = TieBreak::Random(value.parse().map_err(invalid)?),
+            _ => return Err(format!("unknown parameter {key:?}")),
+        }
+    }
+    Ok(ESTScheduler::new(threshold).with_tie_break(tie_break))
+}
+
+/// Splits `"k1=v1,k2=v2"` into trimmed pairs. Blank text has no pairs.
+fn key_values(params: &str) -> Result<Vec<(&str, &str)>, String> {
+    params
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            pair.split_once('=')
+                .map(|(k, v)| (k.trim(), v.trim()))
+                .ok_or_else(|| format!("expected \"key=value\", got {pair:?}"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::RollingHorizonScheduler;
+    use crate::solution_space::SolutionSpace;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    type TestRegistry = Registry<TestTask, Second>;
+
+    #[test]
+    fn builtins_are_listed_and_sorted() {
+        let registry = TestRegistry::with_builtins();
+        assert_eq!(registry.constraint_names(), vec!["interval"]);
+        assert_eq!(
+            registry.metric_names(),
+            vec!["busy_time", "idle_time", "makespan", "utilization"]
+        );
+        assert!(registry.exporter_names().contains(&"csv"));
+        assert_eq!(registry.scheduler_names(), vec!["est", "est-layered"]);
+        assert!(TestRegistry::new().scheduler_names().is_empty());
+    }
+
+    #[test]
+    fn lookups_dispatch_by_name() {
+        let registry = TestRegistry::with_builtins();
+        let window = registry.constraint("interval", "10, 20").unwrap();
+        let set = window.compute_intervals(iv(0.0, 100.0));
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            vec![iv(10.0, 20.0)]
+        );
+
+        let mut schedule = Schedule::new();
+        schedule.add("a", iv(0.0, 25.0)).unwrap();
+        let utilization = registry.metric("utilization", &schedule, iv(0.0, 100.0));
+        assert_eq!(utilization, Ok(0.25));
+
+        let mut block = SchedulingBlock::new();
+        block
+            .add_task_with_id(TestTask::new("a", 10.0), Some("a".into()))
+            .unwrap();
+        let mut ss = SolutionSpace::new();
+        ss.add_interval("a", iv(0.0, 100.0));
+        let blocks = [block];
+        let est = registry.scheduler("est", "").unwrap();
+        let result = est.schedule(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(result.get_interval("a"), Some(iv(0.0, 10.0)));
+        let layered = registry.scheduler("est-layered", "seed=3").unwrap();
+        let result = layered.schedule(&blocks, &ss, iv(0.0, 100.0));
+        assert_eq!(result.get_interval("a"), Some(iv(0.0, 10.0)));
+    }
+
+    #[test]
+    fn errors_name_the_kind() {
+        let mut registry = TestRegistry::with_builtins();
+        assert_eq!(
+            registry.register_metric("makespan", |_, _| 0.0),
+            Err(RegistryError::Duplicate {
+                kind: RegistryKind::Metric,
+                name: "makespan".into()
+            })
+        );
+        let err = registry.scheduler("tabu", "").err().unwrap();
+        assert_eq!(err.to_string(), "No scheduler named \"tabu\" is registered");
+        assert!(matches!(
+            registry.constraint("interval", "nope"),
+            Err(RegistryError::InvalidParameters { .. })
+        ));
+    }
+
+    #[test]
+    fn schedulers_are_configured_from_parameters() {
+        let registry = TestRegistry::with_builtins();
+        assert!(registry.scheduler("est", "threshold=3, seed=7").is_ok());
+        let err = registry.scheduler("est", "threshold=x").err().unwrap();
+        assert!(matches!(
+            err,
+            RegistryError::InvalidParameters {
+                kind: RegistryKind::Scheduler,
+                ..
+            }
+        ));
+        assert!(registry.scheduler("est", "depth=2").is_err());
+
+        // A boxed scheduler composes with the generic drivers.
+        let mut block = SchedulingBlock::new();
+        block
+            .add_task_with_id(TestTask::new("a", 10.0), Some("a".into()))
+            .unwrap();
+        let mut ss = SolutionSpace::new();
+        ss.add_interval("a", iv(0.0, 100.0));
+        let est = registry.scheduler("est", "").unwrap();
+        let rolling = RollingHorizonScheduler::new(est, Quantity::new(50.0), Quantity::new(25.0));
+        let result = rolling.schedule(&[block], &ss, iv(0.0, 100.0));
+        assert_eq!(result.get_interval("a"), Some(iv(0.0, 10.0)));
+    }
+
+    struct Echo;
+
+    impl Plugin<TestTask, Second> for Echo {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn register(&self, registry: &mut TestRegistry) -> Result<(), RegistryError> {
+            registry.register_exporter("len", |s, _| s.len().to_string())?;
+            registry.register_metric("count", |s, _| s.len() as f64)
+        }
+    }
+
+    #[test]
+    fn plugins_extend_the_registry() {
+        let mut registry = TestRegistry::with_builtins();
+        registry.install(&Echo).unwrap();
+        assert_eq!(registry.plugins(), &["echo".to_string()]);
+        assert_eq!(
+            registry.export("len", &Schedule::new(), &[]),
+            Ok("0".to_string())
+        );
+        // A second install collides on the first name and is not recorded.
+        assert!(registry.install(&Echo).is_err());
+        assert_eq!(registry.plugins().len(), 1);
+    }
+}
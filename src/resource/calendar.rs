@@ -0,0 +1,241 @@
+//! Per-resource availability calendars.
+//!
+//! A [`ResourceCalendar`] evaluates each resource's own constraint tree once
+//! over the horizon and keeps the resulting availability windows keyed by
+//! resource ID. Task feasibility is then intersected with the calendar of
+//! whichever resource the task is assigned to, at the time the assignment is
+//! known — task constraint trees never need to embed resource availability,
+//! and re-assigning a task only changes which calendar is applied.
+//!
+//! ```text
+//!   task windows (SolutionSpace)      calendar            assignment
+//!   "obs-1" → {[0,50), [70,100)}      "LST"   → {[10,80)}  "obs-1" → "LST"
+//!                       │                  │
+//!                       └──────── ∩ ───────┘
+//!                                 ▼
+//!   "obs-1" → {[10,50), [70,80)}
+//! ```
+
+use std::collections::HashMap;
+
+use super::Resource;
+use crate::solution_space::{Interval, IntervalSet, SolutionSpace};
+use crate::Id;
+use qtty::Unit;
+
+/// Availability windows for a set of resources over a fixed horizon.
+#[derive(Debug, Clone)]
+pub struct ResourceCalendar<U: Unit> {
+    horizon: Interval<U>,
+    availability: HashMap<Id, IntervalSet<U>>,
+}
+
+impl<U: Unit> ResourceCalendar<U> {
+    /// Creates an empty calendar over `horizon`.
+    pub fn new(horizon: Interval<U>) -> Self {
+        Self {
+            horizon,
+            availability: HashMap::new(),
+        }
+    }
+
+    /// Builds a calendar from resources, evaluating each constraint tree once.
+    pub fn from_resources<'r, R, I>(resources: I, horizon: Interval<U>) -> Self
+    where
+        R: Resource<U> + 'r,
+        I: IntoIterator<Item = &'r R>,
+    {
+        let mut calendar = Self::new(horizon);
+        for resource in resources {
+            calendar.add_resource(resource);
+        }
+        calendar
+    }
+
+    /// Evaluates `resource`'s constraints over the horizon and stores the result
+    /// under its [`resource_id`](Resource::resource_id), replacing any previous entry.
+    pub fn add_resource<R: Resource<U>>(&mut self, resource: &R) {
+        let windows = resource.compute_availability(self.horizon);
+        self.availability
+            .insert(resource.resource_id().to_owned(), windows);
+    }
+
+    /// Stores precomputed availability for `resource_id`, clipped to the horizon.
+    pub fn set_availability(&mut self, resource_id: impl Into<Id>, windows: IntervalSet<U>) {
+        let clipped = windows.intersection(&IntervalSet::from(self.horizon));
+        self.availability.insert(resource_id.into(), clipped);
+    }
+
+    /// Returns the availability windows of `resource_id`, if known.
+    pub fn availability(&self, resource_id: &str) -> Option<&IntervalSet<U>> {
+        self.availability.get(resource_id)
+    }
+
+    /// Returns the calendar's horizon.
+    pub fn horizon(&self) -> Interval<U> {
+        self.horizon
+    }
+
+    /// Returns the IDs of all resources in the calendar.
+    pub fn resource_ids(&self) -> impl Iterator<Item = &str> + '_ {
+        self.availability.keys().map(|k| k.as_str())
+    }
+
+    /// Intersects `windows` with the calendar of `resource_id`.
+    ///
+    /// Returns an empty set for unknown resources: a task cannot use a
+    /// resource whose availability was never declared.
+    pub fn restrict(&self, resource_id: &str, windows: &IntervalSet<U>) -> IntervalSet<U> {
+        self.availability
+            .get(resource_id)
+            .map(|cal| windows.intersection(cal))
+            .unwrap_or_default()
+    }
+
+    /// Returns a solution space in which every assigned task's windows are
+    /// intersected with its resource's calendar.
+    ///
+    /// Tasks with no entry in `assignments` keep their windows unchanged.
+    pub fn apply(
+        &self,
+        space: &SolutionSpace<U>,
+        assignments: &HashMap<Id, Id>,
+    ) -> SolutionSpace<U> {
+        let mut out = SolutionSpace::with_capacity(space.count());
+        for id in space.ids() {
+            let windows = space.get_intervals(id).expect("id listed by space");
+            let restricted = match assignments.get(id) {
+                Some(resource_id) => self.restrict(resource_id, windows),
+                None => windows.clone(),
+            };
+            out.set_intervals(id, restricted.into_inner());
+        }
+        out
+    }
+
+    /// Returns one solution space per resource, each with every task's windows
+    /// intersected with that resource's calendar.
+    ///
+    /// The result plugs directly into
+    /// [`MultiResourceAlgorithm::schedule_multi`](crate::algorithms::MultiResourceAlgorithm::schedule_multi).
+    pub fn resource_spaces(&self, space: &SolutionSpace<U>) -> HashMap<Id, SolutionSpace<U>> {
+        self.availability
+            .iter()
+            .map(|(resource_id, calendar)| {
+                let mut per_resource = SolutionSpace::with_capacity(space.count());
+                for id in space.ids() {
+                    let windows = space.get_intervals(id).expect("id listed by space");
+                    per_resource.set_intervals(id, windows.intersection(calendar).into_inner());
+                }
+                (resource_id.clone(), per_resource)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::{ConstraintExpr, IntervalConstraint};
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    #[derive(Debug)]
+    struct Telescope {
+        id: &'static str,
+        constraints: Option<ConstraintExpr<IntervalConstraint<Second>>>,
+    }
+
+    impl Resource<Second> for Telescope {
+        type ConstraintLeaf = IntervalConstraint<Second>;
+
+        fn name(&self) -> &str {
+            self.id
+        }
+
+        fn constraints(&self) -> Option<&ConstraintExpr<Self::ConstraintLeaf>> {
+            self.constraints.as_ref()
+        }
+    }
+
+    fn telescopes() -> Vec<Telescope> {
+        vec![
+            Telescope {
+                id: "lst",
+                constraints: Some(ConstraintExpr::leaf(IntervalConstraint::new(iv(
+                    10.0, 80.0,
+                )))),
+            },
+            Telescope {
+                id: "magic",
+                constraints: None,
+            },
+        ]
+    }
+
+    fn task_space() -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("obs-1", vec![iv(0.0, 50.0), iv(70.0, 100.0)]);
+        ss.set_intervals("obs-2", vec![iv(0.0, 20.0)]);
+        ss
+    }
+
+    #[test]
+    fn from_resources_evaluates_trees() {
+        let cal = ResourceCalendar::from_resources(&telescopes(), iv(0.0, 100.0));
+        assert_eq!(cal.availability("lst").unwrap(), &vec![iv(10.0, 80.0)]);
+        assert_eq!(cal.availability("magic").unwrap(), &vec![iv(0.0, 100.0)]);
+        assert!(cal.availability("other").is_none());
+    }
+
+    #[test]
+    fn apply_intersects_with_assigned_calendar() {
+        let cal = ResourceCalendar::from_resources(&telescopes(), iv(0.0, 100.0));
+        let assignments = HashMap::from([("obs-1".to_string(), "lst".to_string())]);
+        let out = cal.apply(&task_space(), &assignments);
+
+        assert_eq!(
+            out.get_intervals("obs-1").unwrap(),
+            &vec![iv(10.0, 50.0), iv(70.0, 80.0)]
+        );
+        // Unassigned task keeps its own windows.
+        assert_eq!(out.get_intervals("obs-2").unwrap(), &vec![iv(0.0, 20.0)]);
+    }
+
+    #[test]
+    fn reassignment_changes_only_the_calendar() {
+        let cal = ResourceCalendar::from_resources(&telescopes(), iv(0.0, 100.0));
+        let on_magic = HashMap::from([("obs-1".to_string(), "magic".to_string())]);
+        let out = cal.apply(&task_space(), &on_magic);
+        assert_eq!(
+            out.get_intervals("obs-1").unwrap(),
+            &vec![iv(0.0, 50.0), iv(70.0, 100.0)]
+        );
+    }
+
+    #[test]
+    fn unknown_resource_blocks_task() {
+        let cal = ResourceCalendar::from_resources(&telescopes(), iv(0.0, 100.0));
+        let assignments = HashMap::from([("obs-2".to_string(), "missing".to_string())]);
+        let out = cal.apply(&task_space(), &assignments);
+        assert!(out.get_intervals("obs-2").unwrap().is_empty());
+    }
+
+    #[test]
+    fn resource_spaces_cover_every_resource() {
+        let cal = ResourceCalendar::from_resources(&telescopes(), iv(0.0, 100.0));
+        let spaces = cal.resource_spaces(&task_space());
+        assert_eq!(spaces.len(), 2);
+        assert_eq!(
+            spaces["lst"].get_intervals("obs-2").unwrap(),
+            &vec![iv(10.0, 20.0)]
+        );
+    }
+
+    #[test]
+    fn set_availability_clips_to_horizon() {
+        let mut cal = ResourceCalendar::new(iv(0.0, 50.0));
+        cal.set_availability("r", IntervalSet::from(vec![iv(40.0, 90.0)]));
+        assert_eq!(cal.availability("r").unwrap(), &vec![iv(40.0, 50.0)]);
+    }
+}
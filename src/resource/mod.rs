@@ -9,7 +9,12 @@
 //! Instead of duplicating shared constraints (like "astronomical night") on every task,
 //! we compute them once at the resource level and intersect task windows with the
 //! resource's availability windows.
+//!
+//! [`ResourceCalendar`] keeps those windows per resource and applies the right
+//! one to each task according to its current resource assignment.
 
+pub mod calendar;
 mod traits;
 
+pub use calendar::ResourceCalendar;
 pub use traits::Resource;
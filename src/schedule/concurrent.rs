@@ -0,0 +1,332 @@
+//! Schedules that let up to K tasks run at once.
+//!
+//! A [`Schedule`] rejects any overlap, which models a single machine. A
+//! multi-feed instrument or a shop with several interchangeable crews can
+//! run a few tasks side by side without caring which feed or crew takes
+//! which. [`ConcurrentSchedule`] keeps one timeline with a capacity `K` and
+//! only raises [`ScheduleError::OverlapsExisting`] when a placement would
+//! make more than `K` tasks run at the same instant.
+//!
+//! Milestones occupy no time and never count against the capacity.
+//! [`into_lanes`](ConcurrentSchedule::into_lanes) splits the timeline into at
+//! most `K` ordinary schedules for code that expects one schedule per
+//! resource, such as [`validate_exclusions`](super::validate_exclusions).
+//!
+//! ```
+//! use virolai::schedule::ConcurrentSchedule;
+//! use virolai::solution_space::Interval;
+//! use qtty::Second;
+//!
+//! let mut feeds = ConcurrentSchedule::<Second>::new(2);
+//! feeds.add("a", Interval::from_f64(0.0, 10.0)).unwrap();
+//! feeds.add("b", Interval::from_f64(5.0, 15.0)).unwrap();
+//! assert!(feeds.add("c", Interval::from_f64(8.0, 12.0)).is_err());
+//! feeds.add("c", Interval::from_f64(10.0, 20.0)).unwrap();
+//! assert_eq!(feeds.peak_load(), 2);
+//! ```
+
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+use super::entry_key::{Entry, F64Key, SlotKey};
+use super::errors::ScheduleError;
+use super::Schedule;
+use crate::solution_space::Interval;
+use crate::{Id, TaskKey};
+use qtty::{Quantity, Unit};
+
+/// A timeline on which at most `capacity` tasks overlap.
+///
+/// Entries are kept sorted by start time, as in [`Schedule`].
+#[derive(Debug, Clone)]
+pub struct ConcurrentSchedule<U: Unit, I: TaskKey = Id> {
+    capacity: usize,
+    by_start: BTreeMap<SlotKey, Entry<U, I>>,
+    start_by_id: HashMap<I, SlotKey>,
+    next_seq: u64,
+}
+
+impl<U: Unit> ConcurrentSchedule<U> {
+    /// Creates an empty schedule keyed by [`Id`] that runs up to `capacity`
+    /// tasks at once.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_capacity(capacity)
+    }
+}
+
+impl<U: Unit, I: TaskKey> ConcurrentSchedule<U, I> {
+    /// Creates an empty schedule for any key type.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero.
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity > 0, "schedule capacity must be at least one");
+        Self {
+            capacity,
+            by_start: BTreeMap::new(),
+            start_by_id: HashMap::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// Maximum number of tasks that may overlap.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_start.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_start.is_empty()
+    }
+
+    /// Returns true if task id exists.
+    pub fn contains_task<Q>(&self, id: &Q) -> bool
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.start_by_id.contains_key(id)
+    }
+
+    /// Gets the interval for a task id (if present).
+    pub fn get_interval<Q>(&self, id: &Q) -> Option<Interval<U>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let start = self.start_by_id.get(id)?;
+        self.by_start.get(start).map(|e| e.interval)
+    }
+
+    /// Inserts a task with its interval.
+    ///
+    /// Requires:
+    /// - `id` not already present
+    /// - interval times not NaN
+    /// - fewer than `capacity` tasks running at every instant of the interval
+    ///
+    /// On overload, `existing_id` names one of the tasks already running
+    /// where the capacity would be exceeded.
+    pub fn add(&mut self, id: impl Into<I>, interval: Interval<U>) -> Result<(), ScheduleError> {
+        let id: I = id.into();
+        if self.contains_task(&id) {
+            return Err(ScheduleError::DuplicateTaskId(id.to_string()));
+        }
+        let key = SlotKey {
+            start: Self::key(interval.start())?,
+            end: Self::key(interval.end())?,
+            seq: self.next_seq,
+        };
+
+        if let Some(existing) = self.overload(interval) {
+            return Err(ScheduleError::OverlapsExisting {
+                new_id: id.to_string(),
+                existing_id: existing.to_string(),
+            });
+        }
+
+        self.next_seq += 1;
+        self.by_start.insert(
+            key,
+            Entry {
+                id: id.clone(),
+                interval,
+            },
+        );
+        self.start_by_id.insert(id, key);
+        Ok(())
+    }
+
+    /// Removes a task by id. Returns its interval if it existed.
+    pub fn remove<Q>(&mut self, id: &Q) -> Option<Interval<U>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let key = self.start_by_id.remove(id)?;
+        self.by_start.remove(&key).map(|e| e.interval)
+    }
+
+    /// Tasks that overlap `query`, in start time order.
+    ///
+    /// Milestones neither conflict nor are conflicted with.
+    pub fn conflicts_vec(
+        &self,
+        query: Interval<U>,
+    ) -> Result<Vec<(I, Interval<U>)>, ScheduleError> {
+        Self::key(query.start())?;
+        Self::key(query.end())?;
+        Ok(self
+            .overlapping(query)
+            .map(|e| (e.id.clone(), e.interval))
+            .collect())
+    }
+
+    /// Returns `true` if a task could be placed on `query` without
+    /// exceeding the capacity.
+    pub fn has_room(&self, query: Interval<U>) -> Result<bool, ScheduleError> {
+        Self::key(query.start())?;
+        Self::key(query.end())?;
+        Ok(self.overload(query).is_none())
+    }
+
+    /// Number of tasks running at `pos`.
+    pub fn load_at(&self, pos: Quantity<U>) -> usize {
+        self.by_start
+            .values()
+            .take_while(|e| e.interval.start().value() <= pos.value())
+            .filter(|e| e.interval.contains(pos))
+            .count()
+    }
+
+    /// Largest number of tasks running at the same instant.
+    pub fn peak_load(&self) -> usize {
+        let mut running: Vec<f64> = Vec::new();
+        let mut peak = 0;
+        for e in self.by_start.values().filter(|e| !e.interval.is_empty()) {
+            let start = e.interval.start().value();
+            running.retain(|&end| end > start);
+            running.push(e.interval.end().value());
+            peak = peak.max(running.len());
+        }
+        peak
+    }
+
+    /// Returns an iterator over all scheduled tasks in start time order.
+    pub fn iter(&self) -> impl Iterator<Item = (I, Interval<U>)> + '_ {
+        self.by_start.values().map(|e| (e.id.clone(), e.interval))
+    }
+
+    /// Splits the timeline into at most `capacity` schedules without overlaps.
+    ///
+    /// Tasks are dealt in start order to the first lane that is free, which
+    /// never needs more lanes than the [`peak_load`](Self::peak_load).
+    /// Milestones go to the first lane.
+    pub fn into_lanes(self) -> Vec<Schedule<U, I>> {
+        let mut lanes: Vec<Schedule<U, I>> = Vec::new();
+        for e in self.by_start.into_values() {
+            let lane = lanes
+                .iter()
+                .position(|lane| lane.is_free(e.interval).unwrap_or(false));
+            let lane = match lane {
+                Some(lane) => &mut lanes[lane],
+                None => {
+                    lanes.push(Schedule::default());
+                    lanes.last_mut().expect("a lane was just pushed")
+                }
+            };
+            lane.add(e.id, e.interval)
+                .expect("a free lane accepts the interval");
+        }
+        lanes
+    }
+
+    /// A task running where `interval` would exceed the capacity, if any.
+    fn overload(&self, interval: Interval<U>) -> Option<&I> {
+        if interval.is_empty() {
+            return None;
+        }
+        let mut running: Vec<&Entry<U, I>> = Vec::new();
+        for e in self.overlapping(interval) {
+            let start = e.interval.start().value();
+            running.retain(|r| r.interval.end().value() > start);
+            running.push(e);
+            if running.len() >= self.capacity {
+                return Some(&e.id);
+            }
+        }
+        None
+    }
+
+    /// Entries that occupy time and overlap `query`, in start order.
+    fn overlapping(&self, query: Interval<U>) -> impl Iterator<Item = &Entry<U, I>> + '_ {
+        let end = query.end().value();
+        self.by_start
+            .values()
+            .take_while(move |e| e.interval.start().value() < end)
+            .filter(move |e| {
+                !query.is_empty() && !e.interval.is_empty() && e.interval.overlaps(&query)
+            })
+    }
+
+    fn key(q: Quantity<U>) -> Result<F64Key, ScheduleError> {
+        let v = q.value();
+        if v.is_nan() {
+            Err(ScheduleError::NaNTime)
+        } else {
+            Ok(F64Key(v))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    #[test]
+    fn overlaps_are_accepted_up_to_capacity() {
+        let mut s = ConcurrentSchedule::<Second>::new(2);
+        s.add("a", iv(0.0, 10.0)).unwrap();
+        s.add("b", iv(0.0, 5.0)).unwrap();
+        s.add("c", iv(5.0, 15.0)).unwrap();
+        assert_eq!(
+            s.add("d", iv(8.0, 9.0)),
+            Err(ScheduleError::OverlapsExisting {
+                new_id: "d".into(),
+                existing_id: "c".into()
+            })
+        );
+        // Milestones never count against the capacity.
+        s.add("tick", iv(8.0, 8.0)).unwrap();
+        s.add("d", iv(10.0, 20.0)).unwrap();
+
+        assert_eq!(s.peak_load(), 2);
+        assert_eq!(s.load_at(Quantity::new(12.0)), 2);
+        assert!(!s.has_room(iv(12.0, 13.0)).unwrap());
+        assert!(s.has_room(iv(15.0, 30.0)).unwrap());
+        assert_eq!(s.conflicts_vec(iv(9.0, 11.0)).unwrap().len(), 3);
+        assert_eq!(
+            s.add("a", iv(50.0, 60.0)),
+            Err(ScheduleError::DuplicateTaskId("a".into()))
+        );
+    }
+
+    #[test]
+    fn capacity_one_behaves_like_a_schedule() {
+        let mut s = ConcurrentSchedule::<Second>::new(1);
+        s.add("a", iv(0.0, 10.0)).unwrap();
+        s.add("b", iv(10.0, 20.0)).unwrap();
+        assert!(s.add("c", iv(5.0, 6.0)).is_err());
+        assert_eq!(s.remove("a"), Some(iv(0.0, 10.0)));
+        s.add("c", iv(5.0, 6.0)).unwrap();
+        assert_eq!(s.get_interval("c"), Some(iv(5.0, 6.0)));
+    }
+
+    #[test]
+    fn lanes_never_exceed_the_peak() {
+        let mut s = ConcurrentSchedule::<Second>::new(3);
+        for (id, a, b) in [
+            ("a", 0.0, 10.0),
+            ("b", 12.0, 20.0),
+            ("c", 2.0, 14.0),
+            ("d", 10.0, 12.0),
+        ] {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        assert_eq!(s.peak_load(), 2);
+        let lanes = s.into_lanes();
+        assert_eq!(lanes.len(), 2);
+        assert_eq!(lanes.iter().map(Schedule::len).sum::<usize>(), 4);
+    }
+}
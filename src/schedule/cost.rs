@@ -0,0 +1,104 @@
+//! Post-hoc cost of a schedule.
+//!
+//! [`Schedule::cost_report`] prices every entry with a caller-supplied cost
+//! function — typically [`PriceCurve::integrate`](crate::scheduling_block::PriceCurve::integrate)
+//! or a task's [`CostedTask::cost`](crate::scheduling_block::CostedTask::cost)
+//! — and totals the result.
+
+use std::fmt;
+
+use super::Schedule;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::Unit;
+
+/// Cost of every entry of a schedule, in start order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostReport {
+    pub entries: Vec<(Id, f64)>,
+    pub total: f64,
+}
+
+impl CostReport {
+    /// Cost of `task_id`, if it is in the report.
+    pub fn cost_of(&self, task_id: &str) -> Option<f64> {
+        self.entries
+            .iter()
+            .find(|(id, _)| id == task_id)
+            .map(|&(_, c)| c)
+    }
+
+    /// The `n` most expensive entries, dearest first.
+    pub fn most_expensive(&self, n: usize) -> Vec<(Id, f64)> {
+        let mut sorted = self.entries.clone();
+        sorted.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        sorted.truncate(n);
+        sorted
+    }
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Cost report ({} entries)", self.entries.len())?;
+        for (id, cost) in &self.entries {
+            writeln!(f, "  {id}: {cost:.2}")?;
+        }
+        write!(f, "  total: {:.2}", self.total)
+    }
+}
+
+impl<U: Unit> Schedule<U> {
+    /// Prices every entry with `cost(task_id, interval)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use virolai::schedule::Schedule;
+    /// use virolai::scheduling_block::PriceCurve;
+    /// use virolai::solution_space::Interval;
+    /// use qtty::{Hour, Quantity};
+    ///
+    /// let prices = PriceCurve::<Hour>::from_bins(Quantity::new(0.0), Quantity::new(1.0), [40.0, 25.0]);
+    /// let mut s = Schedule::new();
+    /// s.add("wash", Interval::from_f64(0.5, 1.5)).unwrap();
+    ///
+    /// let report = s.cost_report(|_, iv| prices.integrate(iv));
+    /// assert_eq!(report.total, 32.5);
+    /// ```
+    pub fn cost_report(&self, cost: impl Fn(&str, Interval<U>) -> f64) -> CostReport {
+        let entries: Vec<(Id, f64)> = self
+            .iter()
+            .map(|(id, interval)| {
+                let c = cost(&id, interval);
+                (id, c)
+            })
+            .collect();
+        let total = entries.iter().map(|(_, c)| c).sum();
+        CostReport { entries, total }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduling_block::PriceCurve;
+    use crate::test_utils::{iv, q};
+
+    #[test]
+    fn report_integrates_each_entry() {
+        let prices = PriceCurve::from_bins(q(0.0), q(10.0), [2.0, 5.0]);
+        let mut s = Schedule::new();
+        s.add("b", iv(15.0, 25.0)).unwrap();
+        s.add("a", iv(0.0, 12.0)).unwrap();
+        let report = s.cost_report(|_, interval| prices.integrate(interval));
+
+        assert_eq!(
+            report.entries,
+            vec![("a".to_string(), 30.0), ("b".to_string(), 25.0)]
+        );
+        assert_eq!(report.total, 55.0);
+        assert_eq!(report.cost_of("b"), Some(25.0));
+        assert_eq!(report.most_expensive(1), vec![("a".to_string(), 30.0)]);
+        assert!(report.to_string().ends_with("total: 55.00"));
+    }
+}
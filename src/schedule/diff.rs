@@ -0,0 +1,198 @@
+//! Differences between two versions of a schedule.
+//!
+//! [`Schedule::diff`] compares a baseline plan against a new one and reports,
+//! per task ID, whether it was added, removed, or moved to a different
+//! interval. Tasks present in both with an identical interval are counted as
+//! unchanged but not listed.
+
+use std::fmt;
+
+use super::Schedule;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::Unit;
+
+/// A task present in both schedules at different intervals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovedTask<U: Unit> {
+    pub id: Id,
+    pub old: Interval<U>,
+    pub new: Interval<U>,
+}
+
+impl<U: Unit> MovedTask<U> {
+    /// Signed shift of the start time (`new.start - old.start`).
+    pub fn start_shift(&self) -> f64 {
+        self.new.start().value() - self.old.start().value()
+    }
+}
+
+/// Result of [`Schedule::diff`].
+///
+/// Each list is sorted by start time (the new interval for `added` and
+/// `moved`, the old interval for `removed`), ties broken by task ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleDiff<U: Unit> {
+    /// Tasks only in the new schedule.
+    pub added: Vec<(Id, Interval<U>)>,
+    /// Tasks only in the baseline schedule.
+    pub removed: Vec<(Id, Interval<U>)>,
+    /// Tasks in both schedules whose interval changed.
+    pub moved: Vec<MovedTask<U>>,
+    /// Number of tasks in both schedules at the same interval.
+    pub unchanged: usize,
+}
+
+impl<U: Unit> ScheduleDiff<U> {
+    /// Returns `true` if the two schedules are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+
+    /// Total number of changed tasks.
+    pub fn change_count(&self) -> usize {
+        self.added.len() + self.removed.len() + self.moved.len()
+    }
+}
+
+impl<U: Unit> Schedule<U> {
+    /// Compares `self` (the baseline) against `other` (the new version).
+    ///
+    /// # Complexity
+    ///
+    /// O((n + m) log(n + m)) for baseline size n and new size m.
+    pub fn diff(&self, other: &Schedule<U>) -> ScheduleDiff<U> {
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        let mut moved = Vec::new();
+        let mut unchanged = 0;
+
+        for (id, old) in self.iter() {
+            match other.get_interval(&id) {
+                None => removed.push((id, old)),
+                Some(new) if new == old => unchanged += 1,
+                Some(new) => moved.push(MovedTask { id, old, new }),
+            }
+        }
+        for (id, new) in other.iter() {
+            if !self.contains_task(&id) {
+                added.push((id, new));
+            }
+        }
+
+        fn key<U: Unit>(iv: &Interval<U>, id: &str) -> (f64, String) {
+            (iv.start().value(), id.to_owned())
+        }
+        let cmp = |a: &(f64, String), b: &(f64, String)| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1));
+        added.sort_by(|a, b| cmp(&key(&a.1, &a.0), &key(&b.1, &b.0)));
+        removed.sort_by(|a, b| cmp(&key(&a.1, &a.0), &key(&b.1, &b.0)));
+        moved.sort_by(|a, b| cmp(&key(&a.new, &a.id), &key(&b.new, &b.id)));
+
+        ScheduleDiff {
+            added,
+            removed,
+            moved,
+            unchanged,
+        }
+    }
+}
+
+impl<U: Unit> fmt::Display for ScheduleDiff<U> {
+    /// Operator-facing summary, one line per change:
+    ///
+    /// ```text
+    /// + obs-7 [10.000, 20.000]
+    /// - obs-2 [30.000, 40.000]
+    /// ~ obs-4 [0.000, 5.000] -> [5.000, 10.000]
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} added, {} removed, {} moved, {} unchanged",
+            self.added.len(),
+            self.removed.len(),
+            self.moved.len(),
+            self.unchanged
+        )?;
+        for (id, iv) in &self.added {
+            writeln!(f, "+ {id} {iv}")?;
+        }
+        for (id, iv) in &self.removed {
+            writeln!(f, "- {id} {iv}")?;
+        }
+        for m in &self.moved {
+            writeln!(f, "~ {} {} -> {}", m.id, m.old, m.new)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn schedule(entries: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in entries {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    #[test]
+    fn identical_schedules_have_empty_diff() {
+        let s = schedule(&[("a", 0.0, 10.0), ("b", 20.0, 30.0)]);
+        let d = s.diff(&s);
+        assert!(d.is_empty());
+        assert_eq!(d.unchanged, 2);
+    }
+
+    #[test]
+    fn reports_added_removed_and_moved() {
+        let old = schedule(&[("a", 0.0, 10.0), ("b", 20.0, 30.0), ("c", 40.0, 50.0)]);
+        let new = schedule(&[("a", 0.0, 10.0), ("b", 25.0, 35.0), ("d", 60.0, 70.0)]);
+        let d = old.diff(&new);
+
+        assert_eq!(d.added, vec![("d".to_string(), iv(60.0, 70.0))]);
+        assert_eq!(d.removed, vec![("c".to_string(), iv(40.0, 50.0))]);
+        assert_eq!(d.moved.len(), 1);
+        assert_eq!(d.moved[0].id, "b");
+        assert_eq!(d.moved[0].old, iv(20.0, 30.0));
+        assert_eq!(d.moved[0].new, iv(25.0, 35.0));
+        assert_eq!(d.moved[0].start_shift(), 5.0);
+        assert_eq!(d.unchanged, 1);
+        assert_eq!(d.change_count(), 3);
+    }
+
+    #[test]
+    fn lists_are_time_ordered() {
+        let old = Schedule::<Second>::new();
+        let new = schedule(&[("z", 0.0, 10.0), ("a", 20.0, 30.0)]);
+        let d = old.diff(&new);
+        assert_eq!(d.added[0].0, "z");
+        assert_eq!(d.added[1].0, "a");
+    }
+
+    #[test]
+    fn diff_is_antisymmetric() {
+        let old = schedule(&[("a", 0.0, 10.0)]);
+        let new = schedule(&[("b", 0.0, 10.0)]);
+        let forward = old.diff(&new);
+        let backward = new.diff(&old);
+        assert_eq!(forward.added, backward.removed);
+        assert_eq!(forward.removed, backward.added);
+    }
+
+    #[test]
+    fn display_summarizes_changes() {
+        let old = schedule(&[("a", 0.0, 5.0), ("b", 30.0, 40.0)]);
+        let new = schedule(&[("a", 5.0, 10.0), ("c", 10.0, 20.0)]);
+        let text = old.diff(&new).to_string();
+        assert!(text.starts_with("1 added, 1 removed, 1 moved, 0 unchanged"));
+        assert!(text.contains("+ c [10.000, 20.000]"));
+        assert!(text.contains("- b [30.000, 40.000]"));
+        assert!(text.contains("~ a [0.000, 5.000] -> [5.000, 10.000]"));
+    }
+}
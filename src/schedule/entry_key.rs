@@ -1,5 +1,5 @@
 use crate::solution_space::Interval;
-use crate::Id;
+use crate::{Id, TaskKey};
 
 /// A total-order key for `f64` using IEEE-754 total order (`total_cmp`).
 /// This lets us use `f64`-backed times as `BTreeMap` keys.
@@ -29,16 +29,48 @@ impl PartialOrd for F64Key {
     }
 }
 
+/// Position of an entry in the schedule's ordered index.
+///
+/// Entries sort by start, then end — so a milestone at `t` sits after the task
+/// ending at `t` and before the task starting there — then by insertion
+/// sequence, which keeps milestones that share an instant apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct SlotKey {
+    pub(crate) start: F64Key,
+    pub(crate) end: F64Key,
+    pub(crate) seq: u64,
+}
+
+impl SlotKey {
+    /// Smallest key starting at `start`.
+    pub(crate) fn first_at(start: F64Key) -> Self {
+        Self {
+            start,
+            end: F64Key(f64::NEG_INFINITY),
+            seq: 0,
+        }
+    }
+
+    /// Largest key starting at `start`.
+    pub(crate) fn last_at(start: F64Key) -> Self {
+        Self {
+            start,
+            end: F64Key(f64::INFINITY),
+            seq: u64::MAX,
+        }
+    }
+}
+
 /// An entry in the schedule, mapping a task ID to its interval.
 #[derive(Debug, Clone, PartialEq)]
-pub struct Entry<U: qtty::Unit> {
-    pub(crate) id: Id,
+pub struct Entry<U: qtty::Unit, I: TaskKey = Id> {
+    pub(crate) id: I,
     pub(crate) interval: Interval<U>,
 }
 
-impl<U: qtty::Unit> Entry<U> {
+impl<U: qtty::Unit, I: TaskKey> Entry<U, I> {
     /// Creates a new schedule entry.
-    pub fn new(id: impl Into<Id>, interval: Interval<U>) -> Self {
+    pub fn new(id: impl Into<I>, interval: Interval<U>) -> Self {
         Self {
             id: id.into(),
             interval,
@@ -46,7 +78,7 @@ impl<U: qtty::Unit> Entry<U> {
     }
 
     /// Returns the task ID.
-    pub fn id(&self) -> &str {
+    pub fn id(&self) -> &I {
         &self.id
     }
 
@@ -83,10 +115,26 @@ mod tests {
         assert_eq!(a.partial_cmp(&b), Some(std::cmp::Ordering::Less));
     }
 
+    #[test]
+    fn slot_key_orders_milestone_between_neighbours() {
+        let key = |start, end, seq| SlotKey {
+            start: F64Key(start),
+            end: F64Key(end),
+            seq,
+        };
+        let ending = key(0.0, 10.0, 0);
+        let milestone = key(10.0, 10.0, 1);
+        let twin = key(10.0, 10.0, 2);
+        let starting = key(10.0, 20.0, 3);
+        assert!(ending < milestone && milestone < twin && twin < starting);
+        assert!(SlotKey::first_at(F64Key(10.0)) < milestone);
+        assert!(SlotKey::last_at(F64Key(10.0)) > starting);
+    }
+
     #[test]
     fn entry_new_and_accessors() {
         let interval = Interval::<Second>::from_f64(10.0, 50.0);
-        let entry = Entry::new("task-1", interval);
+        let entry: Entry<Second> = Entry::new("task-1", interval);
         assert_eq!(entry.id(), "task-1");
         assert_eq!(entry.interval().start().value(), 10.0);
         assert_eq!(entry.interval().end().value(), 50.0);
@@ -95,7 +143,7 @@ mod tests {
     #[test]
     fn entry_custom_id() {
         let interval = Interval::<Second>::from_f64(0.0, 100.0);
-        let entry = Entry::new("my-custom-id".to_string(), interval);
+        let entry: Entry<Second> = Entry::new("my-custom-id".to_string(), interval);
         assert_eq!(entry.id(), "my-custom-id");
     }
 }
@@ -0,0 +1,263 @@
+//! Partial-order view of a schedule.
+//!
+//! A fixed-time schedule breaks as soon as one task runs late. Executors that
+//! can dispatch tasks themselves cope better with a *partial-order schedule*:
+//! the order in which tasks must run plus, for each task, the range of start
+//! times that keeps every other task feasible.
+//!
+//! [`Schedule::partial_order`] derives one by keeping the schedule's sequence
+//! and each task's window, then propagating:
+//!
+//! ```text
+//! earliest_start[i] = max(window[i].start, earliest_start[i-1] + duration[i-1])
+//! latest_start[i]   = min(window[i].end, latest_start[i+1]) - duration[i]
+//! ```
+//!
+//! A task whose placement lies outside its windows, or that has none, is
+//! pinned to its current start. Starting every task anywhere inside its
+//! envelope, in the given order, yields a schedule valid against the same
+//! windows.
+
+use std::collections::HashMap;
+
+use super::Schedule;
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+/// Range of start times a task may take without disturbing the others.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExecutionEnvelope<U: Unit> {
+    pub earliest_start: Quantity<U>,
+    pub latest_start: Quantity<U>,
+    pub duration: Quantity<U>,
+}
+
+impl<U: Unit> ExecutionEnvelope<U> {
+    /// How far the start may move, `latest_start - earliest_start`.
+    pub fn slack(&self) -> Quantity<U> {
+        self.latest_start - self.earliest_start
+    }
+
+    /// Whether starting at `start` stays inside the envelope.
+    pub fn admits(&self, start: Quantity<U>) -> bool {
+        self.earliest_start.value() <= start.value() && start.value() <= self.latest_start.value()
+    }
+
+    /// Earliest start to latest end.
+    pub fn span(&self) -> Interval<U> {
+        Interval::new(self.earliest_start, self.latest_start + self.duration)
+    }
+}
+
+/// Ordering relations plus a start envelope per task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartialOrderSchedule<U: Unit> {
+    /// Tasks in execution order.
+    order: Vec<Id>,
+    envelopes: HashMap<Id, ExecutionEnvelope<U>>,
+    /// `(before, after)` pairs whose envelopes would otherwise let them
+    /// overlap.
+    orderings: Vec<(Id, Id)>,
+}
+
+impl<U: Unit> PartialOrderSchedule<U> {
+    /// Tasks in execution order.
+    pub fn order(&self) -> &[Id] {
+        &self.order
+    }
+
+    pub fn envelope(&self, task_id: &str) -> Option<&ExecutionEnvelope<U>> {
+        self.envelopes.get(task_id)
+    }
+
+    /// Binding ordering relations: `before` must end before `after` starts.
+    /// Consecutive tasks whose envelopes cannot overlap are left out.
+    pub fn orderings(&self) -> &[(Id, Id)] {
+        &self.orderings
+    }
+
+    /// Sum of all slacks.
+    pub fn total_slack(&self) -> Quantity<U> {
+        self.envelopes
+            .values()
+            .fold(Quantity::new(0.0), |acc, e| acc + e.slack())
+    }
+
+    /// Whether `schedule` places exactly these tasks, each inside its
+    /// envelope, and respects every ordering.
+    pub fn admits(&self, schedule: &Schedule<U>) -> bool {
+        if schedule.len() != self.order.len() {
+            return false;
+        }
+        let inside = self.order.iter().all(|id| {
+            schedule
+                .get_interval(id.as_str())
+                .is_some_and(|iv| self.envelopes[id].admits(iv.start()))
+        });
+        inside
+            && self.orderings.iter().all(|(before, after)| {
+                let (b, a) = (
+                    schedule.get_interval(before.as_str()),
+                    schedule.get_interval(after.as_str()),
+                );
+                matches!((b, a), (Some(b), Some(a)) if b.end().value() <= a.start().value())
+            })
+    }
+}
+
+impl<U: Unit> Schedule<U> {
+    /// Derives the partial-order schedule of this schedule against the
+    /// windows of `solution_space`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use virolai::schedule::Schedule;
+    /// use virolai::solution_space::{Interval, SolutionSpace};
+    /// use qtty::Second;
+    ///
+    /// let mut ss = SolutionSpace::<Second>::new();
+    /// ss.add_interval("a", Interval::from_f64(0.0, 50.0));
+    /// ss.add_interval("b", Interval::from_f64(0.0, 50.0));
+    ///
+    /// let mut s = Schedule::new();
+    /// s.add("a", Interval::from_f64(5.0, 15.0)).unwrap();
+    /// s.add("b", Interval::from_f64(20.0, 30.0)).unwrap();
+    ///
+    /// let pos = s.partial_order(&ss);
+    /// let b = pos.envelope("b").unwrap();
+    /// assert_eq!((b.earliest_start.value(), b.latest_start.value()), (10.0, 40.0));
+    /// assert_eq!(pos.orderings(), &[("a".to_string(), "b".to_string())]);
+    /// ```
+    pub fn partial_order(&self, solution_space: &SolutionSpace<U>) -> PartialOrderSchedule<U> {
+        let placed: Vec<(Id, Interval<U>)> = self.iter().collect();
+        // Window holding each placement; the placement itself when none does.
+        let windows: Vec<Interval<U>> = placed
+            .iter()
+            .map(|(id, p)| {
+                solution_space
+                    .get_intervals(id.as_str())
+                    .and_then(|set| {
+                        set.iter()
+                            .find(|w| w.start() <= p.start() && p.end() <= w.end())
+                            .copied()
+                    })
+                    .unwrap_or(*p)
+            })
+            .collect();
+
+        let n = placed.len();
+        let mut earliest = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut es = windows[i].start().value();
+            if i > 0 {
+                let prev = &placed[i - 1].1;
+                es = es.max(earliest[i - 1] + prev.duration().value());
+            }
+            earliest.push(es);
+        }
+        let mut latest = vec![0.0; n];
+        for i in (0..n).rev() {
+            let mut end = windows[i].end().value();
+            if i + 1 < n {
+                end = end.min(latest[i + 1]);
+            }
+            latest[i] = end - placed[i].1.duration().value();
+        }
+
+        let mut envelopes = HashMap::with_capacity(n);
+        let mut orderings = Vec::new();
+        for (i, (id, p)) in placed.iter().enumerate() {
+            envelopes.insert(
+                id.clone(),
+                ExecutionEnvelope {
+                    earliest_start: Quantity::new(earliest[i]),
+                    latest_start: Quantity::new(latest[i]),
+                    duration: p.duration(),
+                },
+            );
+            if i > 0 {
+                let (prev_id, prev) = &placed[i - 1];
+                let prev_latest_end = latest[i - 1] + prev.duration().value();
+                if prev_latest_end > earliest[i] {
+                    orderings.push((prev_id.clone(), id.clone()));
+                }
+            }
+        }
+
+        PartialOrderSchedule {
+            order: placed.into_iter().map(|(id, _)| id).collect(),
+            envelopes,
+            orderings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn space(entries: &[(&str, f64, f64)]) -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for &(id, a, b) in entries {
+            ss.add_interval(id, iv(a, b));
+        }
+        ss
+    }
+
+    fn schedule(entries: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in entries {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    fn bounds(pos: &PartialOrderSchedule<Second>, id: &str) -> (f64, f64) {
+        let e = pos.envelope(id).unwrap();
+        (e.earliest_start.value(), e.latest_start.value())
+    }
+
+    #[test]
+    fn chain_propagates_both_ways() {
+        let ss = space(&[("a", 0.0, 100.0), ("b", 0.0, 100.0), ("c", 0.0, 60.0)]);
+        let s = schedule(&[("a", 0.0, 10.0), ("b", 20.0, 30.0), ("c", 40.0, 50.0)]);
+        let pos = s.partial_order(&ss);
+        assert_eq!(bounds(&pos, "a"), (0.0, 30.0));
+        assert_eq!(bounds(&pos, "b"), (10.0, 40.0));
+        assert_eq!(bounds(&pos, "c"), (20.0, 50.0));
+        assert_eq!(pos.total_slack().value(), 90.0);
+        assert_eq!(pos.orderings().len(), 2);
+        assert!(pos.admits(&s));
+    }
+
+    #[test]
+    fn separated_windows_need_no_ordering() {
+        let ss = space(&[("a", 0.0, 20.0), ("b", 30.0, 50.0)]);
+        let s = schedule(&[("a", 5.0, 15.0), ("b", 30.0, 40.0)]);
+        let pos = s.partial_order(&ss);
+        assert!(pos.orderings().is_empty());
+        assert_eq!(pos.order(), &["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn placement_outside_windows_is_pinned() {
+        let ss = space(&[("a", 0.0, 10.0)]);
+        let s = schedule(&[("a", 20.0, 25.0)]);
+        let pos = s.partial_order(&ss);
+        assert_eq!(bounds(&pos, "a"), (20.0, 20.0));
+        assert_eq!(pos.envelope("a").unwrap().span(), iv(20.0, 25.0));
+    }
+
+    #[test]
+    fn admits_rejects_swapped_order() {
+        let ss = space(&[("a", 0.0, 100.0), ("b", 0.0, 100.0)]);
+        let pos = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 20.0)]).partial_order(&ss);
+        assert!(pos.admits(&schedule(&[("a", 5.0, 15.0), ("b", 50.0, 60.0)])));
+        assert!(!pos.admits(&schedule(&[("b", 20.0, 30.0), ("a", 40.0, 50.0)])));
+        assert!(!pos.admits(&schedule(&[("a", 0.0, 10.0)])));
+    }
+}
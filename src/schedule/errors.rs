@@ -12,6 +12,10 @@ pub enum ScheduleError {
     OverlapsExisting { new_id: Id, existing_id: Id },
     /// Task ID was not found in the schedule
     TaskNotFound(Id),
+    /// Resource ID is not part of the resource pool
+    UnknownResource(Id),
+    /// Task may not run on the requested resource
+    IncompatibleResource { task_id: Id, resource_id: Id },
 }
 
 impl fmt::Display for ScheduleError {
@@ -32,6 +36,15 @@ impl fmt::Display for ScheduleError {
             ScheduleError::TaskNotFound(id) => {
                 write!(f, "Task ID {id} not found in schedule")
             }
+            ScheduleError::UnknownResource(id) => {
+                write!(f, "Resource {id} is not part of the pool")
+            }
+            ScheduleError::IncompatibleResource {
+                task_id,
+                resource_id,
+            } => {
+                write!(f, "Task {task_id} cannot run on resource {resource_id}")
+            }
         }
     }
 }
@@ -0,0 +1,270 @@
+//! Apache Arrow export.
+//!
+//! Builds in-memory [`RecordBatch`]es from schedules and their reports, so
+//! an embedding application can hand results to DataFusion, Polars or any
+//! other Arrow consumer without serializing them first.
+//!
+//! | Function              | Rows                     | Columns                                   |
+//! |-----------------------|--------------------------|-------------------------------------------|
+//! | [`schedule_batch`]    | one per entry, by start  | `task_id`, `start`, `end`                 |
+//! | [`schedule_batch_with_epoch`] | as above         | plus `start_utc`, `end_utc`               |
+//! | [`metrics_batch`]     | one                      | the fields of [`ScheduleStats`]           |
+//! | [`cost_report_batch`] | one per entry            | `task_id`, `cost`                         |
+//! | [`run_log_batch`]     | one per run, in order    | `run_id`, `runtime_s`, `utilization`, `requested`, `skipped` |
+//!
+//! Times are axis values in the schedule's unit, as in the other exporters.
+//! The `_utc` columns are millisecond UTC timestamps under an [`Epoch`].
+//!
+//! ```ignore
+//! use virolai::schedule::export::arrow::schedule_batch;
+//!
+//! let batch = schedule_batch(&schedule);
+//! ctx.register_batch("schedule", batch)?; // DataFusion
+//! ```
+
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, Float64Array, StringArray, TimestampMillisecondArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use qtty::time::Time;
+use qtty::Unit;
+
+use crate::epoch::{AbsoluteTime, Epoch};
+use crate::schedule::{CostReport, RunLog, Schedule, ScheduleStats};
+
+pub use arrow_array::RecordBatch;
+
+/// Schema of [`schedule_batch`].
+pub fn schedule_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("task_id", DataType::Utf8, false),
+        Field::new("start", DataType::Float64, false),
+        Field::new("end", DataType::Float64, false),
+    ])
+}
+
+/// One row per entry of `schedule`, in start order.
+pub fn schedule_batch<U: Unit>(schedule: &Schedule<U>) -> RecordBatch {
+    let entries: Vec<_> = schedule.iter().collect();
+    batch(
+        schedule_schema(),
+        vec![
+            Arc::new(StringArray::from_iter_values(
+                entries.iter().map(|(id, _)| id.as_str()),
+            )),
+            Arc::new(Float64Array::from_iter_values(
+                entries.iter().map(|(_, iv)| iv.start().value()),
+            )),
+            Arc::new(Float64Array::from_iter_values(
+                entries.iter().map(|(_, iv)| iv.end().value()),
+            )),
+        ],
+    )
+}
+
+/// Schema of [`schedule_batch_with_epoch`].
+pub fn schedule_with_epoch_schema() -> Schema {
+    let utc = DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()));
+    let mut fields: Vec<Field> = schedule_schema()
+        .fields()
+        .iter()
+        .map(|f| f.as_ref().clone())
+        .collect();
+    fields.push(Field::new("start_utc", utc.clone(), false));
+    fields.push(Field::new("end_utc", utc, false));
+    Schema::new(fields)
+}
+
+/// [`schedule_batch`] with the absolute start and end of each entry under
+/// `epoch`.
+pub fn schedule_batch_with_epoch<U: Unit<Dim = Time>>(
+    schedule: &Schedule<U>,
+    epoch: &Epoch<U>,
+) -> RecordBatch {
+    let plain = schedule_batch(schedule);
+    let absolute: Vec<_> = schedule
+        .intervals()
+        .map(|iv| epoch.interval_to_absolute(iv))
+        .collect();
+    let millis = |t: AbsoluteTime| (t.unix_seconds() * 1000.0).round() as i64;
+    let timestamps = |times: Vec<i64>| {
+        Arc::new(TimestampMillisecondArray::from(times).with_timezone("UTC")) as ArrayRef
+    };
+    let mut columns = plain.columns().to_vec();
+    columns.push(timestamps(
+        absolute.iter().map(|&(s, _)| millis(s)).collect(),
+    ));
+    columns.push(timestamps(
+        absolute.iter().map(|&(_, e)| millis(e)).collect(),
+    ));
+    batch(schedule_with_epoch_schema(), columns)
+}
+
+/// Schema of [`metrics_batch`]. Only `makespan` is nullable.
+pub fn metrics_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("horizon_start", DataType::Float64, false),
+        Field::new("horizon_end", DataType::Float64, false),
+        Field::new("scheduled_count", DataType::UInt64, false),
+        Field::new("requested_count", DataType::UInt64, false),
+        Field::new("busy_time", DataType::Float64, false),
+        Field::new("idle_time", DataType::Float64, false),
+        Field::new("utilization", DataType::Float64, false),
+        Field::new("makespan", DataType::Float64, true),
+        Field::new("priority_weighted_completion", DataType::Float64, false),
+    ])
+}
+
+/// A single row holding `stats`.
+pub fn metrics_batch<U: Unit>(stats: &ScheduleStats<U>) -> RecordBatch {
+    let f = |v: f64| Arc::new(Float64Array::from(vec![v])) as ArrayRef;
+    let n = |v: usize| Arc::new(UInt64Array::from(vec![v as u64])) as ArrayRef;
+    batch(
+        metrics_schema(),
+        vec![
+            f(stats.horizon.start().value()),
+            f(stats.horizon.end().value()),
+            n(stats.scheduled_count),
+            n(stats.requested_count),
+            f(stats.busy_time.value()),
+            f(stats.idle_time.value()),
+            f(stats.utilization),
+            Arc::new(Float64Array::from(vec![stats.makespan.map(|m| m.value())])),
+            f(stats.priority_weighted_completion),
+        ],
+    )
+}
+
+/// Schema of [`cost_report_batch`].
+pub fn cost_report_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("task_id", DataType::Utf8, false),
+        Field::new("cost", DataType::Float64, false),
+    ])
+}
+
+/// One row per entry of `report`, in report order.
+pub fn cost_report_batch(report: &CostReport) -> RecordBatch {
+    batch(
+        cost_report_schema(),
+        vec![
+            Arc::new(StringArray::from_iter_values(
+                report.entries.iter().map(|(id, _)| id.as_str()),
+            )),
+            Arc::new(Float64Array::from_iter_values(
+                report.entries.iter().map(|&(_, c)| c),
+            )),
+        ],
+    )
+}
+
+/// Schema of [`run_log_batch`].
+pub fn run_log_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("run_id", DataType::Utf8, false),
+        Field::new("runtime_s", DataType::Float64, false),
+        Field::new("utilization", DataType::Float64, false),
+        Field::new("requested", DataType::UInt64, false),
+        Field::new("skipped", DataType::UInt64, false),
+    ])
+}
+
+/// One row per run of `log`, in run order; `skipped` totals every reason.
+pub fn run_log_batch(log: &RunLog) -> RecordBatch {
+    let records = log.records();
+    batch(
+        run_log_schema(),
+        vec![
+            Arc::new(StringArray::from_iter_values(
+                records.iter().map(|r| r.run_id.as_str()),
+            )),
+            Arc::new(Float64Array::from_iter_values(
+                records.iter().map(|r| r.runtime.as_secs_f64()),
+            )),
+            Arc::new(Float64Array::from_iter_values(
+                records.iter().map(|r| r.utilization),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                records.iter().map(|r| r.requested as u64),
+            )),
+            Arc::new(UInt64Array::from_iter_values(
+                records.iter().map(|r| r.skipped_count() as u64),
+            )),
+        ],
+    )
+}
+
+fn batch(schema: Schema, columns: Vec<ArrayRef>) -> RecordBatch {
+    RecordBatch::try_new(Arc::new(schema), columns).expect("columns match their schema")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::RunRecord;
+    use crate::test_utils::iv;
+    use arrow_array::Array;
+    use qtty::Second;
+    use std::time::Duration;
+
+    fn schedule() -> Schedule<Second> {
+        let mut s = Schedule::new();
+        s.add("b", iv(10.0, 20.0)).unwrap();
+        s.add("a", iv(0.0, 5.0)).unwrap();
+        s
+    }
+
+    fn column<'a, A: 'static>(batch: &'a RecordBatch, name: &str) -> &'a A {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<A>()
+            .unwrap()
+    }
+
+    #[test]
+    fn schedule_rows_in_start_order() {
+        let batch = schedule_batch(&schedule());
+        assert_eq!(batch.num_rows(), 2);
+        let ids = column::<StringArray>(&batch, "task_id");
+        assert_eq!((ids.value(0), ids.value(1)), ("a", "b"));
+        assert_eq!(column::<Float64Array>(&batch, "end").value(1), 20.0);
+    }
+
+    #[test]
+    fn epoch_adds_timestamp_columns() {
+        let epoch = Epoch::new(AbsoluteTime::from_unix_seconds(1_000.0));
+        let batch = schedule_batch_with_epoch(&schedule(), &epoch);
+        assert_eq!(batch.num_columns(), 5);
+        let end = column::<TimestampMillisecondArray>(&batch, "end_utc");
+        assert_eq!(end.value(1), 1_020_000);
+        assert_eq!(column::<Float64Array>(&batch, "end").value(1), 20.0);
+    }
+
+    #[test]
+    fn metrics_single_row_with_nullable_makespan() {
+        let stats = ScheduleStats::from_schedule(&schedule(), iv(0.0, 100.0));
+        let batch = metrics_batch(&stats);
+        assert_eq!(batch.num_rows(), 1);
+        assert_eq!(column::<Float64Array>(&batch, "busy_time").value(0), 15.0);
+        assert_eq!(column::<UInt64Array>(&batch, "scheduled_count").value(0), 2);
+
+        let empty = ScheduleStats::from_schedule(&Schedule::<Second>::new(), iv(0.0, 1.0));
+        assert!(column::<Float64Array>(&metrics_batch(&empty), "makespan").is_null(0));
+    }
+
+    #[test]
+    fn reports_as_batches() {
+        let costs = schedule().cost_report(|_, iv| iv.duration().value() * 2.0);
+        let batch = cost_report_batch(&costs);
+        assert_eq!(column::<Float64Array>(&batch, "cost").value(0), 10.0);
+
+        let mut log = RunLog::new();
+        log.push(RunRecord::new("n0", Duration::from_millis(1500), 0.5, 10).with_skips("x", 3));
+        let batch = run_log_batch(&log);
+        assert_eq!(column::<StringArray>(&batch, "run_id").value(0), "n0");
+        assert_eq!(column::<Float64Array>(&batch, "runtime_s").value(0), 1.5);
+        assert_eq!(column::<UInt64Array>(&batch, "skipped").value(0), 3);
+    }
+}
@@ -0,0 +1,259 @@
+//! Gantt-oriented export.
+//!
+//! [`Schedule::to_gantt`] joins a schedule with the [`SchedulingBlock`]s it
+//! was built from and produces a flat, widget-friendly [`GanttChart`]. With the
+//! `serde` feature, `Schedule::to_gantt_json` serializes it directly.
+//!
+//! # Format (version 1)
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "tasks": [
+//!     {
+//!       "id": "obs-1",
+//!       "name": "Crab Nebula",
+//!       "start": 0.0,
+//!       "end": 1800.0,
+//!       "priority": 5,
+//!       "dependencies": ["calib-1"],
+//!       "milestone": false
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! - `start`/`end` are axis values in the schedule's unit, half-open.
+//! - `tasks` are sorted by `start`, then `id`.
+//! - `dependencies` lists the IDs of the task's direct predecessors in its
+//!   block graph, sorted; it may reference tasks that were not scheduled.
+//! - Tasks scheduled but absent from every block use their ID as `name`,
+//!   priority `0` and no dependencies.
+//! - `milestone` is `true` for zero-duration entries (`start == end`); it
+//!   defaults to `false` when reading documents that predate it.
+//! - `start_utc`/`end_utc` are the ISO-8601 UTC timestamps of `start` and
+//!   `end`, present only in charts built by
+//!   [`Schedule::to_gantt_with_epoch`].
+//!
+//! Fields are only ever added within a version; any rename, removal or change
+//! of meaning bumps [`GANTT_FORMAT_VERSION`].
+
+use crate::epoch::Epoch;
+use crate::schedule::Schedule;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::Id;
+use qtty::time::Time;
+use qtty::{Quantity, Unit};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Current version of the Gantt export format.
+pub const GANTT_FORMAT_VERSION: u32 = 1;
+
+/// One bar of a Gantt chart.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GanttTask {
+    pub id: Id,
+    pub name: String,
+    pub start: f64,
+    pub end: f64,
+    pub priority: i32,
+    pub dependencies: Vec<Id>,
+    /// Zero-duration entry (`start == end`), drawn as a marker.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub milestone: bool,
+    /// Absolute `start`, when the chart was built with an epoch.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub start_utc: Option<String>,
+    /// Absolute `end`, when the chart was built with an epoch.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub end_utc: Option<String>,
+}
+
+/// Versioned Gantt chart document.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GanttChart {
+    pub version: u32,
+    pub tasks: Vec<GanttTask>,
+}
+
+impl<U: Unit> Schedule<U> {
+    /// Builds a [`GanttChart`] for this schedule, resolving names, priorities
+    /// and dependencies from `blocks`.
+    pub fn to_gantt<T, D, E>(&self, blocks: &[SchedulingBlock<T, U, D, E>]) -> GanttChart
+    where
+        T: Task<U>,
+        E: petgraph::EdgeType,
+    {
+        let tasks = self
+            .iter()
+            .map(|(id, interval)| {
+                let found = blocks
+                    .iter()
+                    .find_map(|b| b.node_of(&id).map(|node| (b, node)));
+                let (name, priority, dependencies) = match found {
+                    Some((block, node)) => {
+                        let task = block.task_by_id(&id).expect("node resolved from id");
+                        let mut deps: Vec<Id> = block
+                            .predecessors(node)
+                            .into_iter()
+                            .filter_map(|p| block.id_of(p).map(str::to_owned))
+                            .collect();
+                        deps.sort();
+                        (task.name().to_owned(), task.priority(), deps)
+                    }
+                    None => (id.clone(), 0, Vec::new()),
+                };
+                GanttTask {
+                    id,
+                    name,
+                    start: interval.start().value(),
+                    end: interval.end().value(),
+                    priority,
+                    dependencies,
+                    milestone: interval.is_empty(),
+                    start_utc: None,
+                    end_utc: None,
+                }
+            })
+            .collect();
+
+        GanttChart {
+            version: GANTT_FORMAT_VERSION,
+            tasks,
+        }
+    }
+
+    /// Serializes [`to_gantt`](Self::to_gantt) as a JSON string.
+    #[cfg(feature = "serde")]
+    pub fn to_gantt_json<T, D, E>(&self, blocks: &[SchedulingBlock<T, U, D, E>]) -> String
+    where
+        T: Task<U>,
+        E: petgraph::EdgeType,
+    {
+        serde_json::to_string(&self.to_gantt(blocks)).expect("Gantt chart is always valid JSON")
+    }
+}
+
+impl<U: Unit<Dim = Time>> Schedule<U> {
+    /// Like [`to_gantt`](Self::to_gantt), with the absolute start and end of
+    /// every bar under `epoch`.
+    pub fn to_gantt_with_epoch<T, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        epoch: &Epoch<U>,
+    ) -> GanttChart
+    where
+        T: Task<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut chart = self.to_gantt(blocks);
+        for bar in &mut chart.tasks {
+            bar.start_utc = Some(epoch.to_absolute(Quantity::new(bar.start)).to_string());
+            bar.end_utc = Some(epoch.to_absolute(Quantity::new(bar.end)).to_string());
+        }
+        chart
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    fn fixture() -> (Schedule<Second>, Vec<SchedulingBlock<TestTask, Second>>) {
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        for (id, prio) in [("calib", 1), ("obs", 5), ("late", 2)] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0).with_priority(prio), Some(id.into()))
+                .unwrap();
+        }
+        let calib = block.node_of("calib").unwrap();
+        let obs = block.node_of("obs").unwrap();
+        let late = block.node_of("late").unwrap();
+        block.add_dependency(calib, obs, ()).unwrap();
+        block.add_dependency(calib, late, ()).unwrap();
+        block.add_dependency(obs, late, ()).unwrap();
+
+        let mut s = Schedule::new();
+        s.add("obs", iv(10.0, 20.0)).unwrap();
+        s.add("calib", iv(0.0, 10.0)).unwrap();
+        s.add("late", iv(20.0, 30.0)).unwrap();
+        s.add("ad-hoc", iv(40.0, 45.0)).unwrap();
+        s.add("review", iv(30.0, 30.0)).unwrap();
+        (s, vec![block])
+    }
+
+    #[test]
+    fn gantt_resolves_block_metadata() {
+        let (s, blocks) = fixture();
+        let chart = s.to_gantt(&blocks);
+        assert_eq!(chart.version, GANTT_FORMAT_VERSION);
+
+        let ids: Vec<_> = chart.tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["calib", "obs", "late", "review", "ad-hoc"]);
+
+        assert_eq!(chart.tasks[1].priority, 5);
+        assert_eq!(chart.tasks[1].dependencies, vec!["calib"]);
+        assert_eq!(chart.tasks[2].dependencies, vec!["calib", "obs"]);
+        assert_eq!(chart.tasks[2].start, 20.0);
+        assert_eq!(chart.tasks[2].end, 30.0);
+    }
+
+    #[test]
+    fn gantt_unknown_task_defaults() {
+        let (s, blocks) = fixture();
+        let chart = s.to_gantt(&blocks);
+        let adhoc = &chart.tasks[4];
+        assert_eq!(adhoc.name, "ad-hoc");
+        assert_eq!(adhoc.priority, 0);
+        assert!(adhoc.dependencies.is_empty());
+        assert!(!adhoc.milestone);
+        assert!(chart.tasks[3].milestone);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn gantt_json_schema() {
+        let (s, blocks) = fixture();
+        let json: serde_json::Value = serde_json::from_str(&s.to_gantt_json(&blocks)).unwrap();
+        assert_eq!(json["version"], 1);
+        let obs = &json["tasks"][1];
+        assert_eq!(obs["id"], "obs");
+        assert_eq!(obs["name"], "obs");
+        assert_eq!(obs["start"], 10.0);
+        assert_eq!(obs["end"], 20.0);
+        assert_eq!(obs["priority"], 5);
+        assert_eq!(obs["dependencies"], serde_json::json!(["calib"]));
+        assert!(obs.get("start_utc").is_none());
+    }
+
+    #[test]
+    fn gantt_with_epoch_adds_absolute_times() {
+        use crate::epoch::AbsoluteTime;
+
+        let (s, blocks) = fixture();
+        let epoch = Epoch::new(AbsoluteTime::from_utc(2024, 3, 1, 0, 0, 0.0));
+        let chart = s.to_gantt_with_epoch(&blocks, &epoch);
+        assert_eq!(chart.tasks[1].start, 10.0);
+        assert_eq!(
+            chart.tasks[1].start_utc.as_deref(),
+            Some("2024-03-01T00:00:10.000Z")
+        );
+        assert_eq!(
+            chart.tasks[1].end_utc.as_deref(),
+            Some("2024-03-01T00:00:20.000Z")
+        );
+        assert!(s.to_gantt(&blocks).tasks[1].start_utc.is_none());
+    }
+}
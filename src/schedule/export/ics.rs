@@ -0,0 +1,177 @@
+//! iCalendar (RFC 5545) export.
+//!
+//! Renders each scheduled task as a `VEVENT` whose `UID` is the task ID, with
+//! `DTSTART`/`DTEND` mapped to UTC through an [`Epoch`]. The output can be
+//! imported into any calendar tool.
+//!
+//! ```ignore
+//! use virolai::epoch::{AbsoluteTime, Epoch};
+//! use virolai::schedule::export::ics::{to_ics, IcsOptions};
+//!
+//! let epoch = Epoch::<Second>::new(AbsoluteTime::from_utc(2024, 3, 1, 20, 0, 0.0));
+//! std::fs::write("night.ics", to_ics(&schedule, &epoch, &IcsOptions::default()))?;
+//! ```
+
+use crate::epoch::{AbsoluteTime, Epoch};
+use crate::schedule::Schedule;
+use qtty::time::Time;
+use qtty::Unit;
+
+/// Calendar-level settings for [`to_ics`].
+#[derive(Debug, Clone)]
+pub struct IcsOptions {
+    /// `PRODID` of the calendar.
+    pub prod_id: String,
+    /// Optional `X-WR-CALNAME` shown by most calendar clients.
+    pub calendar_name: Option<String>,
+    /// `DTSTAMP` of every event. Defaults to the epoch origin so that the
+    /// same schedule always exports to the same bytes.
+    pub dtstamp: Option<AbsoluteTime>,
+}
+
+impl Default for IcsOptions {
+    fn default() -> Self {
+        Self {
+            prod_id: "-//virolai//schedule export//EN".to_string(),
+            calendar_name: None,
+            dtstamp: None,
+        }
+    }
+}
+
+/// Renders `schedule` as an iCalendar document.
+///
+/// Events appear in start-time order. Lines are CRLF-terminated and folded
+/// at 75 octets as required by RFC 5545; text values are escaped.
+pub fn to_ics<U: Unit<Dim = Time>>(
+    schedule: &Schedule<U>,
+    epoch: &Epoch<U>,
+    options: &IcsOptions,
+) -> String {
+    let mut out = String::new();
+    let dtstamp = format_utc(options.dtstamp.unwrap_or(epoch.origin()));
+
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(
+        &mut out,
+        &format!("PRODID:{}", escape_text(&options.prod_id)),
+    );
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    if let Some(name) = &options.calendar_name {
+        push_line(&mut out, &format!("X-WR-CALNAME:{}", escape_text(name)));
+    }
+
+    for (id, interval) in schedule.iter() {
+        let (start, end) = epoch.interval_to_absolute(interval);
+        let id = escape_text(&id);
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{id}"));
+        push_line(&mut out, &format!("DTSTAMP:{dtstamp}"));
+        push_line(&mut out, &format!("DTSTART:{}", format_utc(start)));
+        push_line(&mut out, &format!("DTEND:{}", format_utc(end)));
+        push_line(&mut out, &format!("SUMMARY:{id}"));
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Formats an instant as an RFC 5545 UTC DATE-TIME (`19970714T173000Z`),
+/// rounded to the nearest second.
+fn format_utc(t: AbsoluteTime) -> String {
+    let rounded = AbsoluteTime::from_unix_seconds(t.unix_seconds().round());
+    let (y, mo, d, h, mi, s) = rounded.to_utc();
+    format!("{y:04}{mo:02}{d:02}T{h:02}{mi:02}{:02}Z", s.round() as u32)
+}
+
+/// Escapes a TEXT value (RFC 5545 §3.3.11).
+fn escape_text(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Appends a content line, folding it at 75 octets (RFC 5545 §3.1).
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += len;
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn epoch() -> Epoch<Second> {
+        Epoch::new(AbsoluteTime::from_utc(2024, 3, 1, 20, 0, 0.0))
+    }
+
+    #[test]
+    fn renders_events_with_uids() {
+        let mut s = Schedule::new();
+        s.add("obs-2", iv(3600.0, 5400.0)).unwrap();
+        s.add("obs-1", iv(0.0, 1800.0)).unwrap();
+
+        let ics = to_ics(&s, &epoch(), &IcsOptions::default());
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+
+        let first = ics.find("UID:obs-1").unwrap();
+        let second = ics.find("UID:obs-2").unwrap();
+        assert!(first < second);
+        assert!(ics.contains("DTSTART:20240301T200000Z\r\nDTEND:20240301T203000Z"));
+        assert!(ics.contains("DTSTART:20240301T210000Z\r\nDTEND:20240301T213000Z"));
+        assert!(ics.contains("DTSTAMP:20240301T200000Z"));
+    }
+
+    #[test]
+    fn calendar_name_and_escaping() {
+        let mut s = Schedule::new();
+        s.add("a,b;c", iv(0.0, 60.0)).unwrap();
+        let options = IcsOptions {
+            calendar_name: Some("Night 1".to_string()),
+            ..IcsOptions::default()
+        };
+        let ics = to_ics(&s, &epoch(), &options);
+        assert!(ics.contains("X-WR-CALNAME:Night 1\r\n"));
+        assert!(ics.contains("UID:a\\,b\\;c\r\n"));
+    }
+
+    #[test]
+    fn long_lines_are_folded() {
+        let mut out = String::new();
+        push_line(&mut out, &"x".repeat(100));
+        let lines: Vec<&str> = out.split("\r\n").collect();
+        assert_eq!(lines[0].len(), 75);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(lines[1].len(), 26);
+    }
+
+    #[test]
+    fn format_utc_rounds_to_seconds() {
+        let t = AbsoluteTime::from_utc(2024, 12, 31, 23, 59, 59.6);
+        assert_eq!(format_utc(t), "20250101T000000Z");
+    }
+}
@@ -0,0 +1,15 @@
+//! Exporters that render a [`Schedule`](super::Schedule) for external tools.
+//!
+//! Formats that need extra machinery live behind their own cargo feature:
+//!
+//! | Module    | Feature | Output                                  |
+//! |-----------|---------|-----------------------------------------|
+//! | [`gantt`] | —       | Versioned Gantt chart (JSON with `serde`) |
+//! | `ics`     | `ics`   | iCalendar (RFC 5545) `VEVENT`s          |
+//! | `arrow`   | `arrow` | Apache Arrow `RecordBatch`es            |
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod gantt;
+#[cfg(feature = "ics")]
+pub mod ics;
@@ -0,0 +1,218 @@
+//! Explicit idle entries for uncovered time.
+//!
+//! Gaps in a [`Schedule`] are implicit: nothing is placed there. Exporters
+//! and executors that expect a fully tiled timeline — a command generator
+//! that must say what a resource does at every instant — need those gaps as
+//! entries. [`Schedule::fill_idle`] adds one idle entry per gap of the
+//! horizon; [`ResourcePool::fill_idle`] does so for every resource.
+//!
+//! Idle entries are ordinary schedule entries whose IDs start with
+//! [`IdleFill::prefix`]; [`IdleFill::is_idle`] tells them apart. Gaps shorter
+//! than [`IdleFill::min_length`] stay uncovered.
+
+use super::{ResourcePool, Schedule};
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::{Quantity, Unit};
+
+/// How [`Schedule::fill_idle`] names and filters idle entries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdleFill<U: Unit> {
+    prefix: String,
+    min_length: Quantity<U>,
+}
+
+impl<U: Unit> Default for IdleFill<U> {
+    /// Prefix `"idle-"`, every non-empty gap filled.
+    fn default() -> Self {
+        Self {
+            prefix: "idle-".to_string(),
+            min_length: Quantity::new(0.0),
+        }
+    }
+}
+
+impl<U: Unit> IdleFill<U> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID prefix of idle entries.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Leaves gaps shorter than `min_length` uncovered.
+    pub fn with_min_length(mut self, min_length: Quantity<U>) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn min_length(&self) -> Quantity<U> {
+        self.min_length
+    }
+
+    /// Whether `task_id` names an idle entry.
+    pub fn is_idle(&self, task_id: &str) -> bool {
+        task_id.starts_with(&self.prefix)
+    }
+}
+
+impl<U: Unit> Schedule<U> {
+    /// Uncovered stretches of `horizon`, in order. Milestones cover nothing.
+    pub fn gaps(&self, horizon: Interval<U>) -> Vec<Interval<U>> {
+        let mut gaps = Vec::new();
+        let mut cursor = horizon.start();
+        for interval in self.intervals().filter(|i| !i.is_empty()) {
+            if interval.start() > cursor {
+                let end = interval.start().min(horizon.end());
+                if end > cursor {
+                    gaps.push(Interval::new(cursor, end));
+                }
+            }
+            cursor = cursor.max(interval.end());
+        }
+        if cursor < horizon.end() {
+            gaps.push(Interval::new(cursor, horizon.end()));
+        }
+        gaps
+    }
+
+    /// Adds an idle entry `"<prefix><n>"` for every gap of `horizon` at least
+    /// `fill.min_length()` long and returns the new IDs. `n` counts from 0
+    /// and skips IDs already in the schedule.
+    pub fn fill_idle(&mut self, horizon: Interval<U>, fill: &IdleFill<U>) -> Vec<Id> {
+        self.idle_entries(horizon, fill, &fill.prefix)
+            .into_iter()
+            .map(|(id, gap)| {
+                self.add(id.clone(), gap).expect("gaps are free");
+                id
+            })
+            .collect()
+    }
+
+    /// Idle entries `"<stem><n>"` for the gaps of `horizon`.
+    fn idle_entries(
+        &self,
+        horizon: Interval<U>,
+        fill: &IdleFill<U>,
+        stem: &str,
+    ) -> Vec<(Id, Interval<U>)> {
+        let mut n = 0;
+        self.gaps(horizon)
+            .into_iter()
+            .filter(|gap| gap.duration() >= fill.min_length)
+            .map(|gap| {
+                let id = loop {
+                    let candidate = format!("{stem}{n}");
+                    n += 1;
+                    if !self.contains_task(candidate.as_str()) {
+                        break candidate;
+                    }
+                };
+                (id, gap)
+            })
+            .collect()
+    }
+}
+
+impl<U: Unit> ResourcePool<U> {
+    /// [`Schedule::fill_idle`] on every resource. Entries are named
+    /// `"<prefix><resource>-<n>"`; the result lists them per resource,
+    /// sorted by resource ID.
+    pub fn fill_idle(&mut self, horizon: Interval<U>, fill: &IdleFill<U>) -> Vec<(Id, Vec<Id>)> {
+        let resources: Vec<Id> = self.resource_ids().into_iter().map(str::to_owned).collect();
+        resources
+            .into_iter()
+            .map(|resource| {
+                let stem = format!("{}{resource}-", fill.prefix);
+                let entries = self.schedules()[&resource].idle_entries(horizon, fill, &stem);
+                let ids = entries
+                    .into_iter()
+                    .map(|(id, gap)| {
+                        self.add(id.clone(), &resource, gap).expect("gaps are free");
+                        id
+                    })
+                    .collect();
+                (resource, ids)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    fn schedule(entries: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in entries {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    #[test]
+    fn gaps_cover_horizon_complement() {
+        let s = schedule(&[("a", 10.0, 20.0), ("m", 30.0, 30.0), ("b", 40.0, 120.0)]);
+        assert_eq!(s.gaps(iv(0.0, 100.0)), vec![iv(0.0, 10.0), iv(20.0, 40.0)]);
+        assert_eq!(s.gaps(iv(15.0, 35.0)), vec![iv(20.0, 35.0)]);
+        assert_eq!(
+            Schedule::<Second>::new().gaps(iv(0.0, 5.0)),
+            vec![iv(0.0, 5.0)]
+        );
+    }
+
+    #[test]
+    fn fill_idle_tiles_the_horizon() {
+        let mut s = schedule(&[("a", 10.0, 20.0), ("b", 22.0, 30.0)]);
+        let fill = IdleFill::new().with_min_length(q(5.0));
+        let added = s.fill_idle(iv(0.0, 50.0), &fill);
+        assert_eq!(added, vec!["idle-0".to_string(), "idle-1".to_string()]);
+        assert_eq!(s.get_interval("idle-0"), Some(iv(0.0, 10.0)));
+        assert_eq!(s.get_interval("idle-1"), Some(iv(30.0, 50.0)));
+        // The 2-long gap is below the minimum.
+        assert_eq!(s.gaps(iv(0.0, 50.0)), vec![iv(20.0, 22.0)]);
+        assert!(fill.is_idle("idle-1"));
+        assert!(!fill.is_idle("a"));
+    }
+
+    #[test]
+    fn fill_idle_skips_taken_ids() {
+        let mut s = schedule(&[("park0", 10.0, 20.0)]);
+        let fill = IdleFill::new().with_prefix("park");
+        let added = s.fill_idle(iv(0.0, 20.0), &fill);
+        assert_eq!(added, vec!["park1".to_string()]);
+    }
+
+    #[test]
+    fn pool_fill_names_entries_per_resource() {
+        let mut pool = ResourcePool::<Second>::new()
+            .with_resource("r1")
+            .with_resource("r2");
+        pool.add("a", "r1", iv(0.0, 10.0)).unwrap();
+        let added = pool.fill_idle(iv(0.0, 20.0), &IdleFill::new());
+        assert_eq!(
+            added,
+            vec![
+                ("r1".to_string(), vec!["idle-r1-0".to_string()]),
+                ("r2".to_string(), vec!["idle-r2-0".to_string()]),
+            ]
+        );
+        assert_eq!(
+            pool.schedule("r1").unwrap().get_interval("idle-r1-0"),
+            Some(iv(10.0, 20.0))
+        );
+        assert_eq!(
+            pool.schedule("r2").unwrap().get_interval("idle-r2-0"),
+            Some(iv(0.0, 20.0))
+        );
+    }
+}
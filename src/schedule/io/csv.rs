@@ -0,0 +1,153 @@
+//! CSV rows of `task_id,start,end`.
+//!
+//! ```text
+//! task_id,start,end
+//! obs-1,0,1800
+//! "cal,b",1800,2100
+//! ```
+//!
+//! Times are axis values in the schedule's unit. The header is written by
+//! [`write()`] and optional on [`read()`]; blank lines are ignored and IDs
+//! containing commas or quotes are quoted as in RFC 4180.
+//!
+//! [`write_with_epoch`] adds `start_utc` and `end_utc` columns with the
+//! absolute timestamps of each entry, for people and tools that read the
+//! file directly. Those files are an export only: [`read()`] takes the
+//! three-column form.
+
+use std::io::{BufRead, Write};
+
+use crate::csv::{read_rows, write_header, write_row, write_row_with};
+use crate::epoch::Epoch;
+use crate::schedule::Schedule;
+use crate::solution_space::Interval;
+use qtty::time::Time;
+use qtty::Unit;
+
+pub use crate::csv::CsvError;
+
+/// Header line written by [`write()`].
+pub const HEADER: &str = "task_id,start,end";
+
+/// Header line written by [`write_with_epoch`].
+pub const HEADER_WITH_EPOCH: &str = "task_id,start,end,start_utc,end_utc";
+
+/// Reads a schedule. Fails on malformed rows, duplicate IDs or overlaps.
+pub fn read<U: Unit, R: BufRead>(reader: R) -> Result<Schedule<U>, CsvError> {
+    let mut schedule = Schedule::new();
+    for row in read_rows(reader, HEADER)? {
+        schedule
+            .add(row.id, Interval::from_f64(row.start, row.end))
+            .map_err(|source| CsvError::Schedule {
+                line: row.line,
+                source,
+            })?;
+    }
+    Ok(schedule)
+}
+
+/// Writes `schedule` with a header, one row per task in start order.
+pub fn write<U: Unit, W: Write>(schedule: &Schedule<U>, mut writer: W) -> Result<(), CsvError> {
+    write_header(&mut writer, HEADER)?;
+    for (id, interval) in schedule.iter() {
+        write_row(
+            &mut writer,
+            &id,
+            interval.start().value(),
+            interval.end().value(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Renders `schedule` as a CSV string.
+pub fn to_string<U: Unit>(schedule: &Schedule<U>) -> String {
+    let mut out = Vec::new();
+    write(schedule, &mut out).expect("writing to a Vec cannot fail");
+    String::from_utf8(out).expect("CSV output is UTF-8")
+}
+
+/// Like [`write()`], with the absolute start and end of each entry under
+/// `epoch` as two more columns.
+pub fn write_with_epoch<U: Unit<Dim = Time>, W: Write>(
+    schedule: &Schedule<U>,
+    epoch: &Epoch<U>,
+    mut writer: W,
+) -> Result<(), CsvError> {
+    write_header(&mut writer, HEADER_WITH_EPOCH)?;
+    for (id, interval) in schedule.iter() {
+        let (start, end) = epoch.interval_to_absolute(interval);
+        write_row_with(
+            &mut writer,
+            &id,
+            interval.start().value(),
+            interval.end().value(),
+            &[start.to_string(), end.to_string()],
+        )?;
+    }
+    Ok(())
+}
+
+/// Renders `schedule` with absolute columns as a CSV string.
+pub fn to_string_with_epoch<U: Unit<Dim = Time>>(
+    schedule: &Schedule<U>,
+    epoch: &Epoch<U>,
+) -> String {
+    let mut out = Vec::new();
+    write_with_epoch(schedule, epoch, &mut out).expect("writing to a Vec cannot fail");
+    String::from_utf8(out).expect("CSV output is UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::errors::ScheduleError;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    #[test]
+    fn round_trip() {
+        let mut s = Schedule::<Second>::new();
+        s.add("b", iv(10.0, 20.5)).unwrap();
+        s.add("a,1", iv(0.0, 10.0)).unwrap();
+
+        let text = to_string(&s);
+        assert_eq!(text, "task_id,start,end\n\"a,1\",0,10\nb,10,20.5\n");
+
+        let back: Schedule<Second> = read(text.as_bytes()).unwrap();
+        assert!(s.diff(&back).is_empty());
+    }
+
+    #[test]
+    fn header_is_optional() {
+        let s: Schedule<Second> = read("a,0,5\nb,5,7\n".as_bytes()).unwrap();
+        assert_eq!(s.len(), 2);
+        assert_eq!(s.get_interval("b"), Some(iv(5.0, 7.0)));
+    }
+
+    #[test]
+    fn overlap_reports_line() {
+        let err = read::<Second, _>("task_id,start,end\na,0,10\nb,5,15\n".as_bytes()).unwrap_err();
+        assert!(matches!(
+            err,
+            CsvError::Schedule {
+                line: 3,
+                source: ScheduleError::OverlapsExisting { .. }
+            }
+        ));
+    }
+
+    #[test]
+    fn epoch_adds_absolute_columns() {
+        use crate::epoch::AbsoluteTime;
+
+        let mut s = Schedule::<Second>::new();
+        s.add("a,1", iv(0.0, 90.0)).unwrap();
+        let epoch = Epoch::new(AbsoluteTime::from_utc(2024, 3, 1, 0, 0, 0.0));
+        assert_eq!(
+            to_string_with_epoch(&s, &epoch),
+            "task_id,start,end,start_utc,end_utc\n\
+             \"a,1\",0,90,2024-03-01T00:00:00.000Z,2024-03-01T00:01:30.000Z\n"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+//! Reading and writing schedules in exchange formats.
+
+pub mod csv;
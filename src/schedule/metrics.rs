@@ -0,0 +1,232 @@
+//! Utilization and KPI metrics for a finished schedule.
+//!
+//! [`ScheduleStats`] gathers the numbers most consumers of a [`Schedule`]
+//! need — utilization, idle time, makespan, completion counts and
+//! priority-weighted completion — in one pass, so they are computed the same
+//! way everywhere.
+
+use std::fmt;
+
+use super::Schedule;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::Interval;
+use qtty::{Quantity, Unit};
+
+/// Summary metrics of a schedule over a horizon.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleStats<U: Unit> {
+    /// Horizon the metrics refer to.
+    pub horizon: Interval<U>,
+    /// Number of tasks present in the schedule.
+    pub scheduled_count: usize,
+    /// Number of tasks that were requested (equals `scheduled_count` when the
+    /// stats were computed without blocks).
+    pub requested_count: usize,
+    /// Scheduled time inside the horizon.
+    pub busy_time: Quantity<U>,
+    /// Horizon time not covered by any scheduled task.
+    pub idle_time: Quantity<U>,
+    /// `busy_time / horizon.duration()`, in `[0, 1]` (0 for an empty horizon).
+    pub utilization: f64,
+    /// Time from the horizon start to the end of the last task (`None` if empty).
+    pub makespan: Option<Quantity<U>>,
+    /// Fraction of requested priority that was scheduled, in `[0, 1]`.
+    ///
+    /// Each task weighs `max(priority, 0)`. If the total requested weight is
+    /// zero, this falls back to `scheduled_count / requested_count`.
+    pub priority_weighted_completion: f64,
+}
+
+impl<U: Unit> ScheduleStats<U> {
+    /// Computes metrics for `schedule` alone, treating every scheduled task as
+    /// requested and weighing them equally.
+    pub fn from_schedule(schedule: &Schedule<U>, horizon: Interval<U>) -> Self {
+        let n = schedule.len();
+        Self::build(schedule, horizon, n, 0.0, 0.0)
+    }
+
+    /// Computes metrics for `schedule` against the tasks requested in `blocks`.
+    ///
+    /// Scheduled IDs that do not belong to any block still count toward busy
+    /// time but not toward completion.
+    pub fn compute<T, D, E>(
+        schedule: &Schedule<U>,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        horizon: Interval<U>,
+    ) -> Self
+    where
+        T: Task<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut requested = 0;
+        let mut requested_weight = 0.0;
+        let mut scheduled_weight = 0.0;
+        for (id, task) in blocks.iter().flat_map(|b| b.tasks()) {
+            let weight = task.priority().max(0) as f64;
+            requested += 1;
+            requested_weight += weight;
+            if schedule.contains_task(id) {
+                scheduled_weight += weight;
+            }
+        }
+        Self::build(
+            schedule,
+            horizon,
+            requested,
+            requested_weight,
+            scheduled_weight,
+        )
+    }
+
+    fn build(
+        schedule: &Schedule<U>,
+        horizon: Interval<U>,
+        requested_count: usize,
+        requested_weight: f64,
+        scheduled_weight: f64,
+    ) -> Self {
+        let busy: f64 = schedule
+            .intervals()
+            .filter_map(|i| i.intersection(&horizon))
+            .map(|i| i.duration().value())
+            .sum();
+        let total = horizon.duration().value();
+        let scheduled_count = schedule.len();
+
+        let completion = if requested_weight > 0.0 {
+            scheduled_weight / requested_weight
+        } else if requested_count > 0 {
+            scheduled_count.min(requested_count) as f64 / requested_count as f64
+        } else {
+            1.0
+        };
+
+        Self {
+            horizon,
+            scheduled_count,
+            requested_count,
+            busy_time: Quantity::new(busy),
+            idle_time: Quantity::new((total - busy).max(0.0)),
+            utilization: if total > 0.0 { busy / total } else { 0.0 },
+            makespan: schedule.latest_end().map(|end| end - horizon.start()),
+            priority_weighted_completion: completion,
+        }
+    }
+
+    /// Number of requested tasks that were not scheduled.
+    pub fn unscheduled_count(&self) -> usize {
+        self.requested_count.saturating_sub(self.scheduled_count)
+    }
+
+    /// `scheduled_count / requested_count` (1 when nothing was requested).
+    pub fn completion_ratio(&self) -> f64 {
+        if self.requested_count == 0 {
+            1.0
+        } else {
+            self.scheduled_count as f64 / self.requested_count as f64
+        }
+    }
+}
+
+impl<U: Unit> fmt::Display for ScheduleStats<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "scheduled:    {}/{}",
+            self.scheduled_count, self.requested_count
+        )?;
+        writeln!(f, "utilization:  {:.1}%", self.utilization * 100.0)?;
+        writeln!(f, "busy:         {:.3}", self.busy_time.value())?;
+        writeln!(f, "idle:         {:.3}", self.idle_time.value())?;
+        match self.makespan {
+            Some(m) => writeln!(f, "makespan:     {:.3}", m.value())?,
+            None => writeln!(f, "makespan:     -")?,
+        }
+        write!(
+            f,
+            "weighted:     {:.1}%",
+            self.priority_weighted_completion * 100.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    fn sample() -> Schedule<Second> {
+        let mut s = Schedule::new();
+        s.add("a", iv(0.0, 20.0)).unwrap();
+        s.add("b", iv(50.0, 80.0)).unwrap();
+        s
+    }
+
+    #[test]
+    fn from_schedule_basic_numbers() {
+        let stats = ScheduleStats::from_schedule(&sample(), iv(0.0, 100.0));
+        assert_eq!(stats.scheduled_count, 2);
+        assert_eq!(stats.requested_count, 2);
+        assert_eq!(stats.busy_time.value(), 50.0);
+        assert_eq!(stats.idle_time.value(), 50.0);
+        assert_eq!(stats.utilization, 0.5);
+        assert_eq!(stats.makespan.unwrap().value(), 80.0);
+        assert_eq!(stats.priority_weighted_completion, 1.0);
+    }
+
+    #[test]
+    fn busy_time_clipped_to_horizon() {
+        let stats = ScheduleStats::from_schedule(&sample(), iv(10.0, 60.0));
+        assert_eq!(stats.busy_time.value(), 20.0);
+        assert_eq!(stats.idle_time.value(), 30.0);
+        assert_eq!(stats.makespan.unwrap().value(), 70.0);
+    }
+
+    #[test]
+    fn empty_schedule() {
+        let stats = ScheduleStats::from_schedule(&Schedule::<Second>::new(), iv(0.0, 10.0));
+        assert_eq!(stats.utilization, 0.0);
+        assert_eq!(stats.idle_time.value(), 10.0);
+        assert!(stats.makespan.is_none());
+        assert_eq!(stats.completion_ratio(), 1.0);
+    }
+
+    #[test]
+    fn compute_counts_requested_and_weights_priority() {
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        for (id, prio) in [("a", 3), ("b", 1), ("c", 4)] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0).with_priority(prio), Some(id.into()))
+                .unwrap();
+        }
+
+        let stats = ScheduleStats::compute(&sample(), &[block], iv(0.0, 100.0));
+        assert_eq!(stats.requested_count, 3);
+        assert_eq!(stats.unscheduled_count(), 1);
+        assert!((stats.completion_ratio() - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(stats.priority_weighted_completion, 0.5);
+    }
+
+    #[test]
+    fn zero_priorities_fall_back_to_counts() {
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        for id in ["a", "x"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let mut s = Schedule::new();
+        s.add("a", iv(0.0, 10.0)).unwrap();
+
+        let stats = ScheduleStats::compute(&s, &[block], iv(0.0, 100.0));
+        assert_eq!(stats.priority_weighted_completion, 0.5);
+    }
+
+    #[test]
+    fn display_lists_fields() {
+        let text = ScheduleStats::from_schedule(&sample(), iv(0.0, 100.0)).to_string();
+        assert!(text.contains("scheduled:    2/2"));
+        assert!(text.contains("utilization:  50.0%"));
+    }
+}
@@ -1,23 +1,69 @@
 use crate::solution_space::Interval;
-use crate::Id;
+use crate::{Id, TaskKey};
 use qtty::Quantity;
+use std::borrow::Borrow;
 use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+pub mod concurrent;
+pub mod cost;
+pub mod diff;
 pub mod entry_key;
+pub mod envelope;
 pub mod errors;
+pub mod export;
+pub mod idle;
+pub mod io;
+pub mod metrics;
+pub mod pool;
+pub mod runs;
+pub mod stn;
+pub mod transaction;
+pub mod validate;
 use entry_key::*;
 use errors::*;
 
+pub use concurrent::ConcurrentSchedule;
+pub use cost::CostReport;
+pub use diff::{MovedTask, ScheduleDiff};
+pub use envelope::{ExecutionEnvelope, PartialOrderSchedule};
+pub use idle::IdleFill;
+pub use metrics::ScheduleStats;
+pub use pool::ResourcePool;
+pub use runs::{AnomalyMetric, RollingStats, RunAnomaly, RunLog, RunRecord, RunReport};
+pub use stn::{SimpleTemporalNetwork, StnDistances, StnError};
+pub use transaction::{Changeset, Edit, ScheduleHistory, ScheduleTransaction};
+pub use validate::{validate, validate_exclusions, validate_with_blackouts, Violation};
+
 #[cfg(test)]
 mod tests;
 
 /// Schedule of non-overlapping tasks sorted by start time.
 ///
+/// For a timeline where up to K tasks may overlap, see [`ConcurrentSchedule`].
+///
 /// A `Schedule` maintains a collection of non-overlapping intervals (tasks) indexed by task ID,
 /// providing efficient operations for insertion, removal, and conflict detection.
 ///
 /// # Internal Structure
-/// - `by_start`: `BTreeMap` from start time to task entry
-/// - `start_by_id`: `HashMap` from task ID to start time
+/// - `by_start`: `BTreeMap` from slot key (start, end, insertion sequence) to task entry
+/// - `start_by_id`: `HashMap` from task ID to slot key
+///
+/// # Milestones
+/// An empty interval `[t, t)` places a milestone. Milestones occupy no time:
+/// they never conflict with other entries (several may share `t`, and one may
+/// sit inside a running task), [`task_at`](Self::task_at) never returns them,
+/// and they are ordered after the task ending at `t` and before the task
+/// starting at `t`.
+///
+/// Task IDs are of type `I` ([`Id`] unless specified; see [`TaskKey`]).
+/// With the `serde` feature, only `Schedule<U>` with the default [`Id`] keys
+/// is serializable; schedules with other keys convert their IDs first.
+///
+/// # Generations
+/// Every successful mutation (`add`, `remove`, `clear`) bumps the schedule's
+/// [`generation`](Self::generation) and stamps the affected IDs with it, so a
+/// cache keyed on a task's placement can check [`changed_at`](Self::changed_at)
+/// instead of comparing intervals.
 ///
 /// # Complexity
 /// - `add`: O(log n) with O(1) neighbor overlap checks
@@ -59,28 +105,37 @@ mod tests;
 /// assert_eq!(removed.unwrap().start().value(), 15.0);
 /// ```
 #[derive(Debug, Clone)]
-pub struct Schedule<U: qtty::Unit> {
-    by_start: BTreeMap<F64Key, Entry<U>>,
-    start_by_id: HashMap<Id, F64Key>,
+pub struct Schedule<U: qtty::Unit, I: TaskKey = Id> {
+    by_start: BTreeMap<SlotKey, Entry<U, I>>,
+    start_by_id: HashMap<I, SlotKey>,
+    next_seq: u64,
+    generation: u64,
+    /// Generation of the last mutation touching each ID.
+    changed: HashMap<I, u64>,
 }
 
-impl<U: qtty::Unit> Default for Schedule<U> {
+impl<U: qtty::Unit, I: TaskKey> Default for Schedule<U, I> {
     fn default() -> Self {
         Self {
             by_start: BTreeMap::new(),
             start_by_id: HashMap::new(),
+            next_seq: 0,
+            generation: 0,
+            changed: HashMap::new(),
         }
     }
 }
 
 impl<U: qtty::Unit> Schedule<U> {
+    /// Creates an empty schedule keyed by [`Id`].
+    ///
+    /// For other key types use [`Schedule::default`].
     pub fn new() -> Self {
-        Self {
-            by_start: BTreeMap::new(),
-            start_by_id: HashMap::new(),
-        }
+        Self::default()
     }
+}
 
+impl<U: qtty::Unit, I: TaskKey> Schedule<U, I> {
     pub fn len(&self) -> usize {
         self.by_start.len()
     }
@@ -107,12 +162,20 @@ impl<U: qtty::Unit> Schedule<U> {
     }
 
     /// Returns true if task id exists.
-    pub fn contains_task(&self, id: &str) -> bool {
+    pub fn contains_task<Q>(&self, id: &Q) -> bool
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.start_by_id.contains_key(id)
     }
 
     /// Gets the interval for a task id (if present).
-    pub fn get_interval(&self, id: &str) -> Option<Interval<U>> {
+    pub fn get_interval<Q>(&self, id: &Q) -> Option<Interval<U>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         let start = self.start_by_id.get(id)?;
         self.by_start.get(start).map(|e| e.interval)
     }
@@ -126,58 +189,96 @@ impl<U: qtty::Unit> Schedule<U> {
     ///
     /// Efficiency: only predecessor + successor checks are needed because the schedule
     /// is maintained as non-overlapping and sorted by start time.
-    pub fn add(&mut self, id: impl Into<Id>, interval: Interval<U>) -> Result<(), ScheduleError> {
-        let id: Id = id.into();
+    pub fn add(&mut self, id: impl Into<I>, interval: Interval<U>) -> Result<(), ScheduleError> {
+        let id: I = id.into();
         if self.contains_task(&id) {
-            return Err(ScheduleError::DuplicateTaskId(id));
+            return Err(ScheduleError::DuplicateTaskId(id.to_string()));
         }
 
         let start_k = Self::key(interval.start())?;
         let end_k = Self::key(interval.end())?;
 
-        // Optional sanity: if your Interval::new already enforces start <= end,
-        // you don't need this. Still, it helps if callers bypass it somehow.
-        if start_k > end_k {
-            // Interval::new would panic; here we just fail fast with NaNTime? not right.
-            // Keep behavior aligned with Interval::new: assume it's valid.
-        }
+        let key = SlotKey {
+            start: start_k,
+            end: end_k,
+            seq: self.next_seq,
+        };
 
-        // Check predecessor (latest interval with start <= new.start).
-        if let Some((_k, prev)) = self.by_start.range(..=start_k).next_back() {
-            if prev.interval.overlaps(&interval) {
-                return Err(ScheduleError::OverlapsExisting {
-                    new_id: id,
-                    existing_id: prev.id.clone(),
-                });
+        // Milestones never collide, and are skipped when looking for the
+        // occupied neighbours of a task.
+        if !interval.is_empty() {
+            // Check predecessor (latest occupied interval sorting before the new one).
+            if let Some(prev) = Self::occupied(self.by_start.range(..key).rev()).next() {
+                if prev.interval.overlaps(&interval) {
+                    return Err(ScheduleError::OverlapsExisting {
+                        new_id: id.to_string(),
+                        existing_id: prev.id.to_string(),
+                    });
+                }
             }
-        }
 
-        // Check successor (earliest interval with start >= new.start).
-        if let Some((_k, next)) = self.by_start.range(start_k..).next() {
-            if next.interval.overlaps(&interval) {
-                return Err(ScheduleError::OverlapsExisting {
-                    new_id: id,
-                    existing_id: next.id.clone(),
-                });
+            // Check successor (earliest occupied interval sorting after the new one).
+            if let Some(next) = Self::occupied(self.by_start.range(key..)).next() {
+                if next.interval.overlaps(&interval) {
+                    return Err(ScheduleError::OverlapsExisting {
+                        new_id: id.to_string(),
+                        existing_id: next.id.to_string(),
+                    });
+                }
             }
         }
 
+        self.next_seq += 1;
         self.by_start.insert(
-            start_k,
+            key,
             Entry {
                 id: id.clone(),
                 interval,
             },
         );
-        self.start_by_id.insert(id, start_k);
+        self.start_by_id.insert(id.clone(), key);
+        self.touch(id);
         Ok(())
     }
 
     /// Removes a task by id. Returns its interval if it existed.
-    pub fn remove(&mut self, id: &str) -> Option<Interval<U>> {
-        let start_k = self.start_by_id.remove(id)?;
+    pub fn remove<Q>(&mut self, id: &Q) -> Option<Interval<U>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(id).map(|(_, interval)| interval)
+    }
+
+    /// Removes a task by id, returning the owned key with its interval.
+    pub(crate) fn remove_entry<Q>(&mut self, id: &Q) -> Option<(I, Interval<U>)>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (_, start_k) = self.start_by_id.remove_entry(id)?;
         let entry = self.by_start.remove(&start_k)?;
-        Some(entry.interval)
+        self.touch(entry.id.clone());
+        Some((entry.id, entry.interval))
+    }
+
+    /// Number of mutations applied so far; `0` for a fresh schedule.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Generation at which `id` was last added or removed; `0` if never.
+    pub fn changed_at<Q>(&self, id: &Q) -> u64
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.changed.get(id).copied().unwrap_or(0)
+    }
+
+    fn touch(&mut self, id: I) {
+        self.generation += 1;
+        self.changed.insert(id, self.generation);
     }
 
     /// Returns true if `query` overlaps any scheduled task.
@@ -192,10 +293,12 @@ impl<U: qtty::Unit> Schedule<U> {
     /// Note: uses `Interval::overlaps` exactly as you defined it (inclusive endpoints).
     /// If you want “back-to-back tasks are OK”, change Interval semantics to half-open
     /// and update `overlaps` accordingly.
+    ///
+    /// Milestones neither conflict nor are conflicted with.
     pub fn conflicts<'a>(
         &'a self,
         query: Interval<U>,
-    ) -> Result<impl Iterator<Item = (Id, Interval<U>)> + 'a, ScheduleError> {
+    ) -> Result<impl Iterator<Item = (I, Interval<U>)> + 'a, ScheduleError> {
         let q_start = query.start().value();
         let q_end = query.end().value();
 
@@ -205,28 +308,25 @@ impl<U: qtty::Unit> Schedule<U> {
         // Determine where to start scanning:
         // - the predecessor of q_start (it might start before q_start but still overlap)
         // - otherwise the first start >= q_start
-        let start_from = if let Some((k, prev)) = self.by_start.range(..=q_start_k).next_back() {
-            if prev.interval.overlaps(&query) {
-                Some(*k)
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let start_from = self
+            .by_start
+            .range(..=SlotKey::last_at(q_start_k))
+            .rev()
+            .find(|(_k, e)| !e.interval.is_empty())
+            .filter(|(_k, prev)| prev.interval.overlaps(&query))
+            .map(|(k, _e)| *k);
 
-        let range_start = match start_from {
-            Some(k) => k,
-            None => q_start_k,
-        };
+        let range_start = start_from.unwrap_or(SlotKey::first_at(q_start_k));
 
         // Scan all intervals whose start <= q_end and filter by overlap.
         // We can't stop at q_end because we need inclusive range behavior.
         let iter = self
             .by_start
             .range(range_start..)
-            .take_while(move |(k, _e)| k.0 <= q_end)
-            .filter(move |(_k, e)| e.interval.overlaps(&query))
+            .take_while(move |(k, _e)| k.start.0 <= q_end)
+            .filter(move |(_k, e)| {
+                !query.is_empty() && !e.interval.is_empty() && e.interval.overlaps(&query)
+            })
             .map(|(_k, e)| (e.id.clone(), e.interval));
 
         Ok(iter)
@@ -236,7 +336,7 @@ impl<U: qtty::Unit> Schedule<U> {
     pub fn conflicts_vec(
         &self,
         query: Interval<U>,
-    ) -> Result<Vec<(Id, Interval<U>)>, ScheduleError> {
+    ) -> Result<Vec<(I, Interval<U>)>, ScheduleError> {
         Ok(self.conflicts(query)?.collect())
     }
 
@@ -248,9 +348,9 @@ impl<U: qtty::Unit> Schedule<U> {
     /// (Optional) Find the task that contains `pos`, if any.
     ///
     /// Complexity: O(log n).
-    pub fn task_at(&self, pos: Quantity<U>) -> Result<Option<Id>, ScheduleError> {
-        let p = Self::key(pos)?;
-        if let Some((_k, e)) = self.by_start.range(..=p).next_back() {
+    pub fn task_at(&self, pos: Quantity<U>) -> Result<Option<I>, ScheduleError> {
+        let p = SlotKey::last_at(Self::key(pos)?);
+        if let Some(e) = Self::occupied(self.by_start.range(..=p).rev()).next() {
             if e.interval.contains(pos) {
                 return Ok(Some(e.id.clone()));
             }
@@ -261,12 +361,12 @@ impl<U: qtty::Unit> Schedule<U> {
     /// Returns an iterator over all scheduled tasks in start time order.
     ///
     /// Each item is `(id, interval)`.
-    pub fn iter(&self) -> impl Iterator<Item = (Id, Interval<U>)> + '_ {
+    pub fn iter(&self) -> impl Iterator<Item = (I, Interval<U>)> + '_ {
         self.by_start.values().map(|e| (e.id.clone(), e.interval))
     }
 
     /// Returns an iterator over all IDs.
-    pub fn ids(&self) -> impl Iterator<Item = Id> + '_ {
+    pub fn ids(&self) -> impl Iterator<Item = I> + '_ {
         self.start_by_id.keys().cloned()
     }
 
@@ -277,8 +377,14 @@ impl<U: qtty::Unit> Schedule<U> {
 
     /// Clears all tasks from the schedule.
     pub fn clear(&mut self) {
+        if self.is_empty() {
+            return;
+        }
+        self.generation += 1;
+        for (id, _) in self.start_by_id.drain() {
+            self.changed.insert(id, self.generation);
+        }
         self.by_start.clear();
-        self.start_by_id.clear();
     }
 
     /// Returns the total scheduled duration (sum of all interval durations).
@@ -298,7 +404,24 @@ impl<U: qtty::Unit> Schedule<U> {
 
     /// Returns the latest end time in the schedule, if any.
     pub fn latest_end(&self) -> Option<Quantity<U>> {
-        self.by_start.values().next_back().map(|e| e.interval.end())
+        let last = self.by_start.values().next_back()?.interval.end();
+        // A milestone placed inside the last task sorts after it.
+        let busy = Self::occupied(self.by_start.range(..).rev()).next();
+        Some(match busy {
+            Some(e) if e.interval.end().value() > last.value() => e.interval.end(),
+            _ => last,
+        })
+    }
+
+    /// Entries of `range` that occupy time, i.e. everything but milestones.
+    fn occupied<'a>(
+        range: impl Iterator<Item = (&'a SlotKey, &'a Entry<U, I>)>,
+    ) -> impl Iterator<Item = &'a Entry<U, I>>
+    where
+        U: 'a,
+        I: 'a,
+    {
+        range.map(|(_k, e)| e).filter(|e| !e.interval.is_empty())
     }
 
     /// Returns the time span from earliest start to latest end, if any tasks exist.
@@ -315,6 +438,8 @@ impl<U: qtty::Unit> Schedule<U> {
 // Schedule Serde Support
 // =============================================================================
 
+/// Serde support covers the default [`Id`] keys only: entries are written
+/// with a string `task` field.
 #[cfg(feature = "serde")]
 mod serde_impl {
     use super::*;
@@ -450,4 +575,27 @@ mod serde_impl {
             deserializer.deserialize_map(EntryVisitor(PhantomData))
         }
     }
+
+    /// Describes the written form; `task_id` is still accepted on input.
+    #[cfg(feature = "schema")]
+    impl<U: qtty::Unit> schemars::JsonSchema for Schedule<U> {
+        fn schema_name() -> std::borrow::Cow<'static, str> {
+            "Schedule".into()
+        }
+
+        fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+            let interval = generator.subschema_for::<Interval<U>>();
+            schemars::json_schema!({
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "task": { "type": "string" },
+                        "interval": interval
+                    },
+                    "required": ["task", "interval"]
+                }
+            })
+        }
+    }
 }
@@ -0,0 +1,254 @@
+//! Schedules for a set of parallel resources.
+//!
+//! A [`ResourcePool`] holds one [`Schedule`] per resource (machine, telescope,
+//! antenna) plus an optional task → compatible-resources map. Each resource's
+//! schedule keeps the usual non-overlap guarantee; tasks on different
+//! resources may run concurrently.
+//!
+//! ```
+//! use virolai::schedule::ResourcePool;
+//! use virolai::solution_space::Interval;
+//! use qtty::Second;
+//!
+//! let mut pool = ResourcePool::<Second>::new()
+//!     .with_resource("LST-1")
+//!     .with_resource("LST-2")
+//!     .with_compatibility("calib", ["LST-1"]);
+//!
+//! pool.add("obs", "LST-2", Interval::from_f64(0.0, 10.0)).unwrap();
+//! pool.add("calib", "LST-1", Interval::from_f64(0.0, 10.0)).unwrap();
+//! assert!(pool.add("calib-2", "LST-3", Interval::from_f64(0.0, 1.0)).is_err());
+//! assert_eq!(pool.resource_of("obs"), Some("LST-2"));
+//! ```
+
+use std::collections::HashMap;
+
+use super::errors::ScheduleError;
+use super::Schedule;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::Unit;
+
+/// One schedule per resource, with task-to-resource compatibility.
+#[derive(Debug, Clone)]
+pub struct ResourcePool<U: Unit> {
+    schedules: HashMap<Id, Schedule<U>>,
+    compatibility: HashMap<Id, Vec<Id>>,
+}
+
+impl<U: Unit> Default for ResourcePool<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit> From<HashMap<Id, Schedule<U>>> for ResourcePool<U> {
+    /// Wraps existing per-resource schedules, with no compatibility restrictions.
+    fn from(schedules: HashMap<Id, Schedule<U>>) -> Self {
+        Self {
+            schedules,
+            compatibility: HashMap::new(),
+        }
+    }
+}
+
+impl<U: Unit> ResourcePool<U> {
+    /// Creates a pool with no resources.
+    pub fn new() -> Self {
+        Self {
+            schedules: HashMap::new(),
+            compatibility: HashMap::new(),
+        }
+    }
+
+    /// Adds a resource with an empty schedule.
+    pub fn with_resource(mut self, resource_id: impl Into<Id>) -> Self {
+        self.add_resource(resource_id);
+        self
+    }
+
+    /// Restricts `task_id` to the given resources. Tasks without an entry may
+    /// use any resource.
+    pub fn with_compatibility(
+        mut self,
+        task_id: impl Into<Id>,
+        resources: impl IntoIterator<Item = impl Into<Id>>,
+    ) -> Self {
+        self.set_compatibility(task_id, resources);
+        self
+    }
+
+    /// Adds a resource with an empty schedule; existing resources are kept.
+    pub fn add_resource(&mut self, resource_id: impl Into<Id>) {
+        self.schedules.entry(resource_id.into()).or_default();
+    }
+
+    /// Replaces the compatible resources of `task_id`.
+    pub fn set_compatibility(
+        &mut self,
+        task_id: impl Into<Id>,
+        resources: impl IntoIterator<Item = impl Into<Id>>,
+    ) {
+        self.compatibility.insert(
+            task_id.into(),
+            resources.into_iter().map(Into::into).collect(),
+        );
+    }
+
+    /// Returns `true` if `task_id` may run on `resource_id`.
+    ///
+    /// Does not check that the resource belongs to the pool.
+    pub fn is_compatible(&self, task_id: &str, resource_id: &str) -> bool {
+        self.compatibility
+            .get(task_id)
+            .is_none_or(|allowed| allowed.iter().any(|r| r == resource_id))
+    }
+
+    /// Resources of the pool `task_id` may run on, sorted by ID.
+    pub fn compatible_resources(&self, task_id: &str) -> Vec<&str> {
+        self.resource_ids()
+            .into_iter()
+            .filter(|r| self.is_compatible(task_id, r))
+            .collect()
+    }
+
+    /// Resource IDs, sorted.
+    pub fn resource_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.schedules.keys().map(String::as_str).collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Number of resources.
+    pub fn len(&self) -> usize {
+        self.schedules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schedules.is_empty()
+    }
+
+    /// Total number of placements across all resources.
+    pub fn task_count(&self) -> usize {
+        self.schedules.values().map(Schedule::len).sum()
+    }
+
+    /// Schedule of `resource_id`, if it belongs to the pool.
+    pub fn schedule(&self, resource_id: &str) -> Option<&Schedule<U>> {
+        self.schedules.get(resource_id)
+    }
+
+    /// All schedules keyed by resource ID.
+    pub fn schedules(&self) -> &HashMap<Id, Schedule<U>> {
+        &self.schedules
+    }
+
+    pub fn into_schedules(self) -> HashMap<Id, Schedule<U>> {
+        self.schedules
+    }
+
+    /// Places `task_id` on `resource_id`.
+    ///
+    /// Fails if the resource is unknown, the task is not compatible with it,
+    /// or the resource's schedule rejects the interval (see [`Schedule::add`]).
+    /// The same task may be placed on several resources.
+    pub fn add(
+        &mut self,
+        task_id: impl Into<Id>,
+        resource_id: &str,
+        interval: Interval<U>,
+    ) -> Result<(), ScheduleError> {
+        let task_id = task_id.into();
+        if !self.is_compatible(&task_id, resource_id) {
+            return Err(ScheduleError::IncompatibleResource {
+                task_id,
+                resource_id: resource_id.to_owned(),
+            });
+        }
+        self.schedules
+            .get_mut(resource_id)
+            .ok_or_else(|| ScheduleError::UnknownResource(resource_id.to_owned()))?
+            .add(task_id, interval)
+    }
+
+    /// Removes `task_id` from `resource_id`, returning its interval.
+    pub fn remove(&mut self, task_id: &str, resource_id: &str) -> Option<Interval<U>> {
+        self.schedules.get_mut(resource_id)?.remove(task_id)
+    }
+
+    /// Every resource `task_id` is placed on, with its interval, sorted by
+    /// resource ID.
+    pub fn placements(&self, task_id: &str) -> Vec<(&str, Interval<U>)> {
+        self.resource_ids()
+            .into_iter()
+            .filter_map(|r| Some((r, self.schedules[r].get_interval(task_id)?)))
+            .collect()
+    }
+
+    /// First resource (by ID) `task_id` is placed on.
+    pub fn resource_of(&self, task_id: &str) -> Option<&str> {
+        self.placements(task_id).first().map(|&(r, _)| r)
+    }
+
+    /// Returns `true` if `task_id` is placed on any resource.
+    pub fn contains_task(&self, task_id: &str) -> bool {
+        self.schedules.values().any(|s| s.contains_task(task_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn pool() -> ResourcePool<Second> {
+        ResourcePool::new()
+            .with_resource("r2")
+            .with_resource("r1")
+            .with_compatibility("pinned", ["r2"])
+    }
+
+    #[test]
+    fn resources_are_independent() {
+        let mut p = pool();
+        p.add("a", "r1", iv(0.0, 10.0)).unwrap();
+        p.add("b", "r2", iv(0.0, 10.0)).unwrap();
+        assert!(p.add("c", "r1", iv(5.0, 15.0)).is_err());
+        assert_eq!(p.task_count(), 2);
+        assert_eq!(p.resource_ids(), vec!["r1", "r2"]);
+    }
+
+    #[test]
+    fn compatibility_and_unknown_resource() {
+        let mut p = pool();
+        assert_eq!(p.compatible_resources("pinned"), vec!["r2"]);
+        assert_eq!(p.compatible_resources("free"), vec!["r1", "r2"]);
+        assert_eq!(
+            p.add("pinned", "r1", iv(0.0, 1.0)),
+            Err(ScheduleError::IncompatibleResource {
+                task_id: "pinned".into(),
+                resource_id: "r1".into()
+            })
+        );
+        assert_eq!(
+            p.add("free", "r9", iv(0.0, 1.0)),
+            Err(ScheduleError::UnknownResource("r9".into()))
+        );
+    }
+
+    #[test]
+    fn placements_span_resources() {
+        let mut p = pool();
+        p.add("coal", "r2", iv(0.0, 10.0)).unwrap();
+        p.add("coal", "r1", iv(0.0, 10.0)).unwrap();
+        assert_eq!(
+            p.placements("coal"),
+            vec![("r1", iv(0.0, 10.0)), ("r2", iv(0.0, 10.0))]
+        );
+        assert_eq!(p.resource_of("coal"), Some("r1"));
+        assert_eq!(p.remove("coal", "r1"), Some(iv(0.0, 10.0)));
+        assert_eq!(p.resource_of("coal"), Some("r2"));
+        assert!(!p.contains_task("other"));
+    }
+}
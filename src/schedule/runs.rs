@@ -0,0 +1,526 @@
+//! Rolling statistics and anomaly flags over past scheduling runs.
+//!
+//! Each run is summarised as a [`RunRecord`] — runtime, utilisation and how
+//! many requested tasks were skipped, per reason. A [`RunLog`] keeps these
+//! records in run order and compares every run against the rolling baseline
+//! of the runs before it, so a drift in constraint data quality (e.g. a
+//! sudden jump in "no visibility" skips) shows up as a flagged run instead of
+//! an operator complaint.
+//!
+//! A metric is flagged when it deviates from the baseline mean by more than
+//! `sigma` standard deviations in the bad direction: runtime and skip rates
+//! upward, utilisation downward. When the baseline has no spread at all, any
+//! deviation in the bad direction is flagged.
+//!
+//! ```
+//! use std::time::Duration;
+//! use virolai::schedule::{RunLog, RunRecord};
+//!
+//! let mut log = RunLog::new().with_window(5);
+//! for night in 0..5 {
+//!     log.push(RunRecord::new(format!("n{night}"), Duration::from_secs(60), 0.8, 100)
+//!         .with_skips("no-visibility", 4 + night % 2));
+//! }
+//! log.push(RunRecord::new("n5", Duration::from_secs(60), 0.8, 100)
+//!     .with_skips("no-visibility", 40));
+//!
+//! let report = log.report(3.0);
+//! assert_eq!(report.anomalies.len(), 2); // total and per-reason skip rate
+//! assert!(report.anomalies.iter().all(|a| a.run_id == "n5"));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use super::ScheduleStats;
+use qtty::Unit;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Summary of one scheduling run.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RunRecord {
+    pub run_id: String,
+    /// Wall-clock time the scheduler took.
+    pub runtime: Duration,
+    /// Busy fraction of the horizon, in `[0, 1]` (see
+    /// [`ScheduleStats::utilization`]).
+    pub utilization: f64,
+    /// Number of tasks requested.
+    pub requested: usize,
+    /// Number of requested tasks left out, by reason.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub skipped: BTreeMap<String, usize>,
+}
+
+impl RunRecord {
+    /// Creates a record with no skips.
+    pub fn new(
+        run_id: impl Into<String>,
+        runtime: Duration,
+        utilization: f64,
+        requested: usize,
+    ) -> Self {
+        Self {
+            run_id: run_id.into(),
+            runtime,
+            utilization,
+            requested,
+            skipped: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a record from the [`ScheduleStats`] of the run's schedule.
+    ///
+    /// Skip reasons are not known to the stats; add them with
+    /// [`with_skips`](Self::with_skips).
+    pub fn from_stats<U: Unit>(
+        run_id: impl Into<String>,
+        runtime: Duration,
+        stats: &ScheduleStats<U>,
+    ) -> Self {
+        Self::new(run_id, runtime, stats.utilization, stats.requested_count)
+    }
+
+    /// Adds `count` tasks skipped for `reason`.
+    pub fn with_skips(mut self, reason: impl Into<String>, count: usize) -> Self {
+        *self.skipped.entry(reason.into()).or_default() += count;
+        self
+    }
+
+    /// Total number of skipped tasks.
+    pub fn skipped_count(&self) -> usize {
+        self.skipped.values().sum()
+    }
+
+    /// Fraction of requested tasks skipped (0 when nothing was requested).
+    pub fn skip_rate(&self) -> f64 {
+        self.rate(self.skipped_count())
+    }
+
+    /// Fraction of requested tasks skipped for `reason`.
+    pub fn skip_rate_for(&self, reason: &str) -> f64 {
+        self.rate(self.skipped.get(reason).copied().unwrap_or(0))
+    }
+
+    fn rate(&self, count: usize) -> f64 {
+        if self.requested == 0 {
+            0.0
+        } else {
+            count as f64 / self.requested as f64
+        }
+    }
+}
+
+/// Mean and population standard deviation of a metric.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spread {
+    pub mean: f64,
+    pub std: f64,
+}
+
+impl Spread {
+    fn of(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Self {
+                mean: 0.0,
+                std: 0.0,
+            };
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        Self {
+            mean,
+            std: var.sqrt(),
+        }
+    }
+
+    /// Deviation of `value` from the mean in standard deviations (infinite
+    /// when the spread is zero and `value` differs from the mean).
+    pub fn z_score(&self, value: f64) -> f64 {
+        let d = value - self.mean;
+        if self.std > 0.0 {
+            d / self.std
+        } else if d == 0.0 {
+            0.0
+        } else {
+            d.signum() * f64::INFINITY
+        }
+    }
+}
+
+/// Statistics over a window of runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollingStats {
+    /// Number of runs in the window.
+    pub runs: usize,
+    pub median_runtime: Duration,
+    pub median_utilization: f64,
+    pub runtime_secs: Spread,
+    pub utilization: Spread,
+    pub skip_rate: Spread,
+    /// Skip rate per reason; a run without a reason counts as zero for it.
+    pub skip_rates: BTreeMap<String, Spread>,
+}
+
+impl RollingStats {
+    /// Computes statistics over `records`; `None` if empty.
+    pub fn compute(records: &[RunRecord]) -> Option<Self> {
+        if records.is_empty() {
+            return None;
+        }
+        let runtimes: Vec<f64> = records.iter().map(|r| r.runtime.as_secs_f64()).collect();
+        let utilizations: Vec<f64> = records.iter().map(|r| r.utilization).collect();
+        let skip_rates: Vec<f64> = records.iter().map(RunRecord::skip_rate).collect();
+
+        let reasons: std::collections::BTreeSet<&String> =
+            records.iter().flat_map(|r| r.skipped.keys()).collect();
+        let per_reason = reasons
+            .into_iter()
+            .map(|reason| {
+                let rates: Vec<f64> = records.iter().map(|r| r.skip_rate_for(reason)).collect();
+                (reason.clone(), Spread::of(&rates))
+            })
+            .collect();
+
+        Some(Self {
+            runs: records.len(),
+            median_runtime: Duration::from_secs_f64(median(&runtimes)),
+            median_utilization: median(&utilizations),
+            runtime_secs: Spread::of(&runtimes),
+            utilization: Spread::of(&utilizations),
+            skip_rate: Spread::of(&skip_rates),
+            skip_rates: per_reason,
+        })
+    }
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Metric on which a run was flagged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnomalyMetric {
+    Runtime,
+    Utilization,
+    SkipRate,
+    /// Skip rate for one reason.
+    SkipReason(String),
+}
+
+impl fmt::Display for AnomalyMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnomalyMetric::Runtime => write!(f, "runtime"),
+            AnomalyMetric::Utilization => write!(f, "utilization"),
+            AnomalyMetric::SkipRate => write!(f, "skip rate"),
+            AnomalyMetric::SkipReason(reason) => write!(f, "skip rate ({reason})"),
+        }
+    }
+}
+
+/// A run whose metric strayed from the baseline of the runs before it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunAnomaly {
+    pub run_id: String,
+    pub metric: AnomalyMetric,
+    pub value: f64,
+    pub baseline: Spread,
+    /// Signed deviation in standard deviations.
+    pub z_score: f64,
+}
+
+impl fmt::Display for RunAnomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "run {}: {} {:.3} vs baseline {:.3} ± {:.3} ({:+.1}σ)",
+            self.run_id,
+            self.metric,
+            self.value,
+            self.baseline.mean,
+            self.baseline.std,
+            self.z_score
+        )
+    }
+}
+
+/// Rolling baseline of the latest runs plus every flagged run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunReport {
+    /// Statistics over the last `window` runs (`None` if the log is empty).
+    pub latest: Option<RollingStats>,
+    pub anomalies: Vec<RunAnomaly>,
+}
+
+impl fmt::Display for RunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.latest {
+            Some(s) => {
+                writeln!(f, "runs:         {}", s.runs)?;
+                writeln!(
+                    f,
+                    "runtime:      {:.3}s (median)",
+                    s.median_runtime.as_secs_f64()
+                )?;
+                writeln!(
+                    f,
+                    "utilization:  {:.1}% (median)",
+                    s.median_utilization * 100.0
+                )?;
+                writeln!(f, "skip rate:    {:.1}% (mean)", s.skip_rate.mean * 100.0)?;
+                for (reason, spread) in &s.skip_rates {
+                    writeln!(f, "  {reason}: {:.1}%", spread.mean * 100.0)?;
+                }
+            }
+            None => writeln!(f, "runs:         0")?,
+        }
+        write!(f, "anomalies:    {}", self.anomalies.len())?;
+        for anomaly in &self.anomalies {
+            write!(f, "\n  {anomaly}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Ordered archive of run records with rolling statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunLog {
+    records: Vec<RunRecord>,
+    window: usize,
+    min_baseline: usize,
+}
+
+impl Default for RunLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunLog {
+    /// Creates an empty log with a 20-run window; runs need at least 3
+    /// earlier runs before they can be flagged.
+    pub fn new() -> Self {
+        Self {
+            records: Vec::new(),
+            window: 20,
+            min_baseline: 3,
+        }
+    }
+
+    /// Sets how many preceding runs form the baseline (at least 1).
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Sets how many preceding runs are needed before a run can be flagged.
+    pub fn with_min_baseline(mut self, runs: usize) -> Self {
+        self.min_baseline = runs.max(1);
+        self
+    }
+
+    /// Appends the latest run.
+    pub fn push(&mut self, record: RunRecord) {
+        self.records.push(record);
+    }
+
+    pub fn records(&self) -> &[RunRecord] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Statistics over the last `window` runs.
+    pub fn rolling(&self) -> Option<RollingStats> {
+        let from = self.records.len().saturating_sub(self.window);
+        RollingStats::compute(&self.records[from..])
+    }
+
+    /// Baseline for the run at `index`: the up to `window` runs before it.
+    pub fn baseline_for(&self, index: usize) -> Option<RollingStats> {
+        let end = index.min(self.records.len());
+        RollingStats::compute(&self.records[end.saturating_sub(self.window)..end])
+    }
+
+    /// Every run whose metrics deviate from its baseline by more than `sigma`
+    /// standard deviations in the bad direction, in run order.
+    pub fn anomalies(&self, sigma: f64) -> Vec<RunAnomaly> {
+        let mut out = Vec::new();
+        for (index, run) in self.records.iter().enumerate() {
+            if index < self.min_baseline {
+                continue;
+            }
+            let Some(base) = self.baseline_for(index) else {
+                continue;
+            };
+            let mut check = |metric: AnomalyMetric, value: f64, baseline: Spread, upward: bool| {
+                let z = baseline.z_score(value);
+                let bad = if upward { z > sigma } else { z < -sigma };
+                if bad {
+                    out.push(RunAnomaly {
+                        run_id: run.run_id.clone(),
+                        metric,
+                        value,
+                        baseline,
+                        z_score: z,
+                    });
+                }
+            };
+
+            check(
+                AnomalyMetric::Runtime,
+                run.runtime.as_secs_f64(),
+                base.runtime_secs,
+                true,
+            );
+            check(
+                AnomalyMetric::Utilization,
+                run.utilization,
+                base.utilization,
+                false,
+            );
+            check(
+                AnomalyMetric::SkipRate,
+                run.skip_rate(),
+                base.skip_rate,
+                true,
+            );
+            for reason in run.skipped.keys() {
+                // A reason never seen in the baseline has a zero baseline.
+                let baseline = base.skip_rates.get(reason).copied().unwrap_or(Spread {
+                    mean: 0.0,
+                    std: 0.0,
+                });
+                check(
+                    AnomalyMetric::SkipReason(reason.clone()),
+                    run.skip_rate_for(reason),
+                    baseline,
+                    true,
+                );
+            }
+        }
+        out
+    }
+
+    /// Rolling statistics of the latest runs plus all anomalies at `sigma`.
+    pub fn report(&self, sigma: f64) -> RunReport {
+        RunReport {
+            latest: self.rolling(),
+            anomalies: self.anomalies(sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: &str, secs: u64, utilization: f64, skips: &[(&str, usize)]) -> RunRecord {
+        skips.iter().fold(
+            RunRecord::new(id, Duration::from_secs(secs), utilization, 100),
+            |r, &(reason, n)| r.with_skips(reason, n),
+        )
+    }
+
+    #[test]
+    fn record_from_stats() {
+        use crate::schedule::Schedule;
+        use crate::test_utils::iv;
+
+        let mut schedule = Schedule::<qtty::Second>::new();
+        schedule.add("a", iv(0.0, 25.0)).unwrap();
+        let stats = ScheduleStats::from_schedule(&schedule, iv(0.0, 100.0));
+        let r = RunRecord::from_stats("r", Duration::from_secs(1), &stats).with_skips("weather", 1);
+        assert_eq!(r.utilization, 0.25);
+        assert_eq!(r.requested, 1);
+        assert_eq!(r.skip_rate(), 1.0);
+    }
+
+    #[test]
+    fn rolling_statistics() {
+        let records = [
+            run("a", 10, 0.5, &[("weather", 10)]),
+            run("b", 30, 0.7, &[("weather", 20), ("visibility", 10)]),
+            run("c", 20, 0.9, &[]),
+        ];
+        let s = RollingStats::compute(&records).unwrap();
+        assert_eq!(s.runs, 3);
+        assert_eq!(s.median_runtime, Duration::from_secs(20));
+        assert_eq!(s.median_utilization, 0.7);
+        assert!((s.skip_rate.mean - 0.4 / 3.0).abs() < 1e-12);
+        assert!((s.skip_rates["weather"].mean - 0.1).abs() < 1e-12);
+        assert!((s.skip_rates["visibility"].mean - 0.1 / 3.0).abs() < 1e-12);
+        assert!(RollingStats::compute(&[]).is_none());
+    }
+
+    #[test]
+    fn flags_skip_spike_and_utilization_drop() {
+        let mut log = RunLog::new().with_window(4);
+        for (i, n) in [5, 6, 5, 6].into_iter().enumerate() {
+            log.push(run(&format!("r{i}"), 60, 0.8, &[("weather", n)]));
+        }
+        log.push(run("spike", 60, 0.8, &[("weather", 30)]));
+        log.push(run("drop", 60, 0.2, &[("weather", 5)]));
+
+        let flagged: Vec<_> = log
+            .anomalies(3.0)
+            .into_iter()
+            .map(|a| (a.run_id, a.metric))
+            .collect();
+        assert_eq!(
+            flagged,
+            vec![
+                ("spike".to_string(), AnomalyMetric::SkipRate),
+                (
+                    "spike".to_string(),
+                    AnomalyMetric::SkipReason("weather".into())
+                ),
+                ("drop".to_string(), AnomalyMetric::Utilization),
+            ]
+        );
+    }
+
+    #[test]
+    fn needs_a_baseline_and_ignores_improvements() {
+        let mut log = RunLog::new().with_min_baseline(2);
+        log.push(run("a", 60, 0.5, &[]));
+        log.push(run("b", 600, 0.1, &[("weather", 50)]));
+        assert!(log.anomalies(3.0).is_empty());
+
+        log.push(run("c", 10, 0.9, &[]));
+        assert!(log.anomalies(3.0).is_empty());
+    }
+
+    #[test]
+    fn report_lists_anomalies() {
+        let mut log = RunLog::new();
+        for id in ["a", "b", "c"] {
+            log.push(run(id, 60, 0.8, &[]));
+        }
+        log.push(run("d", 60, 0.8, &[("new-reason", 1)]));
+        let report = log.report(3.0);
+        assert_eq!(report.latest.as_ref().unwrap().runs, 4);
+        let text = report.to_string();
+        assert!(text.contains("anomalies:    2"));
+        assert!(text.contains("run d: skip rate (new-reason)"));
+    }
+}
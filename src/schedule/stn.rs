@@ -0,0 +1,414 @@
+//! Simple Temporal Network export and propagation.
+//!
+//! A Simple Temporal Network (STN) is a set of timepoints and binary
+//! constraints `lo <= t_to - t_from <= hi`, stored as a *distance graph*:
+//! edge `from → to` with weight `hi` and edge `to → from` with weight `-lo`.
+//! The network is consistent iff the distance graph has no negative cycle;
+//! the shortest-path distances then give every timepoint's feasible range
+//! relative to the origin `z`, which is what a dispatcher executes against.
+//!
+//! [`Schedule::to_stn`] exports a plan as an STN: a start and an end point
+//! per task (`"<id>.start"`, `"<id>.end"`), fixed durations, the window
+//! holding each placement, and the schedule's sequence.
+//! [`SimpleTemporalNetwork::add_relations`] adds the block's temporal
+//! dynamic edges. [`SimpleTemporalNetwork::propagate`] runs Floyd–Warshall.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+use thiserror::Error;
+
+use super::Schedule;
+use crate::constraints::DynConstraintKind;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::SolutionSpace;
+use qtty::{Quantity, Unit};
+
+/// Name of the origin timepoint.
+pub const ORIGIN: &str = "z";
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum StnError {
+    #[error("Unknown timepoint: {0}")]
+    UnknownPoint(String),
+
+    #[error("Inconsistent network: negative cycle through {0}")]
+    Inconsistent(String),
+}
+
+/// A distance graph over named timepoints, in axis units `U`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleTemporalNetwork<U: Unit> {
+    points: Vec<String>,
+    index: HashMap<String, usize>,
+    /// `(from, to, w)`: `t_to - t_from <= w`.
+    edges: Vec<(usize, usize, f64)>,
+    _unit: PhantomData<U>,
+}
+
+impl<U: Unit> Default for SimpleTemporalNetwork<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit> SimpleTemporalNetwork<U> {
+    /// Creates a network holding only the origin.
+    pub fn new() -> Self {
+        Self {
+            points: vec![ORIGIN.to_string()],
+            index: HashMap::from([(ORIGIN.to_string(), 0)]),
+            edges: Vec::new(),
+            _unit: PhantomData,
+        }
+    }
+
+    /// Adds a timepoint, or returns the index of an existing one.
+    pub fn add_point(&mut self, name: impl Into<String>) -> usize {
+        let name = name.into();
+        if let Some(&i) = self.index.get(&name) {
+            return i;
+        }
+        self.points.push(name.clone());
+        self.index.insert(name, self.points.len() - 1);
+        self.points.len() - 1
+    }
+
+    /// Requires `lo <= t_to - t_from <= hi`. Use an infinite bound to leave
+    /// that side open.
+    ///
+    /// # Errors
+    ///
+    /// `UnknownPoint` if either timepoint was not added.
+    pub fn add_constraint(
+        &mut self,
+        from: &str,
+        to: &str,
+        lo: Quantity<U>,
+        hi: Quantity<U>,
+    ) -> Result<(), StnError> {
+        let f = self.point(from)?;
+        let t = self.point(to)?;
+        if hi.value().is_finite() {
+            self.edges.push((f, t, hi.value()));
+        }
+        if lo.value().is_finite() {
+            self.edges.push((t, f, -lo.value()));
+        }
+        Ok(())
+    }
+
+    fn point(&self, name: &str) -> Result<usize, StnError> {
+        self.index
+            .get(name)
+            .copied()
+            .ok_or_else(|| StnError::UnknownPoint(name.to_string()))
+    }
+
+    /// Timepoint names; the origin comes first.
+    pub fn points(&self) -> &[String] {
+        &self.points
+    }
+
+    /// Distance-graph edges as `(from, to, weight)`.
+    pub fn edges(&self) -> impl Iterator<Item = (&str, &str, Quantity<U>)> + '_ {
+        self.edges.iter().map(|&(f, t, w)| {
+            (
+                self.points[f].as_str(),
+                self.points[t].as_str(),
+                Quantity::new(w),
+            )
+        })
+    }
+
+    /// Adds the temporal relations among `block`'s dynamic edges whose two
+    /// tasks both have timepoints in the network. `Dependence`, `Exclusive`
+    /// and `Simultaneous` are not simple temporal constraints and are
+    /// skipped.
+    pub fn add_relations<T, E>(&mut self, block: &SchedulingBlock<T, U, DynConstraintKind, E>)
+    where
+        T: Task<U>,
+        E: petgraph::EdgeType,
+    {
+        use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+        for edge in block.graph().edge_references() {
+            let (Some(a), Some(b)) = (block.id_of(edge.source()), block.id_of(edge.target()))
+            else {
+                continue;
+            };
+            let (from, to, lag, max) = match *edge.weight() {
+                DynConstraintKind::Consecutive => (end(a), start(b), 0.0, f64::INFINITY),
+                DynConstraintKind::StartToStart { lag } => {
+                    (start(a), start(b), lag.value(), f64::INFINITY)
+                }
+                DynConstraintKind::FinishToFinish { lag } => {
+                    (end(a), end(b), lag.value(), f64::INFINITY)
+                }
+                DynConstraintKind::StartToFinish { lag } => {
+                    (start(a), end(b), lag.value(), f64::INFINITY)
+                }
+                DynConstraintKind::MaxWait { max_wait } => {
+                    (end(a), start(b), 0.0, max_wait.value())
+                }
+                // The network cannot express "nothing in between"; only the
+                // gap bound is kept.
+                DynConstraintKind::Chained { max_gap } => (end(a), start(b), 0.0, max_gap.value()),
+                _ => continue,
+            };
+            // Edges touching tasks outside the network are skipped.
+            let _ = self.add_constraint(&from, &to, Quantity::new(lag), Quantity::new(max));
+        }
+    }
+
+    /// All-pairs shortest paths (Floyd–Warshall, O(n³)).
+    ///
+    /// # Errors
+    ///
+    /// `Inconsistent` naming a timepoint on a negative cycle.
+    pub fn propagate(&self) -> Result<StnDistances<U>, StnError> {
+        let n = self.points.len();
+        let mut d = vec![vec![f64::INFINITY; n]; n];
+        for (i, row) in d.iter_mut().enumerate() {
+            row[i] = 0.0;
+        }
+        for &(f, t, w) in &self.edges {
+            d[f][t] = d[f][t].min(w);
+        }
+        for k in 0..n {
+            for i in 0..n {
+                if d[i][k] == f64::INFINITY {
+                    continue;
+                }
+                for j in 0..n {
+                    let via = d[i][k] + d[k][j];
+                    if via < d[i][j] {
+                        d[i][j] = via;
+                    }
+                }
+            }
+        }
+        if let Some(i) = (0..n).find(|&i| d[i][i] < 0.0) {
+            return Err(StnError::Inconsistent(self.points[i].clone()));
+        }
+        Ok(StnDistances {
+            index: self.index.clone(),
+            distances: d,
+            _unit: PhantomData,
+        })
+    }
+
+    /// Whether the network admits at least one assignment.
+    pub fn is_consistent(&self) -> bool {
+        self.propagate().is_ok()
+    }
+}
+
+/// Writes one `from to weight` line per distance-graph edge.
+impl<U: Unit> fmt::Display for SimpleTemporalNetwork<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &(from, to, w) in &self.edges {
+            writeln!(f, "{} {} {}", self.points[from], self.points[to], w)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shortest-path distances of a consistent network.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StnDistances<U: Unit> {
+    index: HashMap<String, usize>,
+    distances: Vec<Vec<f64>>,
+    _unit: PhantomData<U>,
+}
+
+impl<U: Unit> StnDistances<U> {
+    /// Tightest upper bound on `t_to - t_from`; infinite when unbounded.
+    pub fn distance(&self, from: &str, to: &str) -> Option<Quantity<U>> {
+        let f = *self.index.get(from)?;
+        let t = *self.index.get(to)?;
+        Some(Quantity::new(self.distances[f][t]))
+    }
+
+    /// Feasible range `[earliest, latest]` of `point` relative to the origin.
+    pub fn window(&self, point: &str) -> Option<(Quantity<U>, Quantity<U>)> {
+        let p = *self.index.get(point)?;
+        Some((
+            Quantity::new(-self.distances[p][0]),
+            Quantity::new(self.distances[0][p]),
+        ))
+    }
+
+    /// Every timepoint at its earliest time — always a consistent assignment.
+    pub fn earliest(&self) -> HashMap<String, Quantity<U>> {
+        self.index
+            .iter()
+            .map(|(name, &p)| (name.clone(), Quantity::new(-self.distances[p][0])))
+            .collect()
+    }
+}
+
+fn start(id: &str) -> String {
+    format!("{id}.start")
+}
+
+fn end(id: &str) -> String {
+    format!("{id}.end")
+}
+
+impl<U: Unit> Schedule<U> {
+    /// Exports the schedule as a Simple Temporal Network.
+    ///
+    /// Each task gets a start and an end point with `end - start` fixed to
+    /// its duration and its start bound by the window in `solution_space`
+    /// that holds its placement (pinned when none does). Consecutive entries
+    /// keep their order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use virolai::schedule::Schedule;
+    /// use virolai::solution_space::{Interval, SolutionSpace};
+    /// use qtty::Second;
+    ///
+    /// let mut ss = SolutionSpace::<Second>::new();
+    /// ss.add_interval("a", Interval::from_f64(0.0, 40.0));
+    /// let mut s = Schedule::new();
+    /// s.add("a", Interval::from_f64(5.0, 15.0)).unwrap();
+    ///
+    /// let dist = s.to_stn(&ss).propagate().unwrap();
+    /// let (lo, hi) = dist.window("a.start").unwrap();
+    /// assert_eq!((lo.value(), hi.value()), (0.0, 30.0));
+    /// ```
+    pub fn to_stn(&self, solution_space: &SolutionSpace<U>) -> SimpleTemporalNetwork<U> {
+        let mut stn = SimpleTemporalNetwork::new();
+        let mut previous_end: Option<String> = None;
+        for (id, placement) in self.iter() {
+            let (s, e) = (start(&id), end(&id));
+            stn.add_point(s.clone());
+            stn.add_point(e.clone());
+
+            let window = solution_space
+                .get_intervals(id.as_str())
+                .and_then(|set| {
+                    set.iter()
+                        .find(|w| w.start() <= placement.start() && placement.end() <= w.end())
+                        .copied()
+                })
+                .unwrap_or(placement);
+            let duration = placement.duration();
+            let constraints = [
+                (ORIGIN, s.as_str(), window.start(), window.end() - duration),
+                (s.as_str(), e.as_str(), duration, duration),
+            ];
+            for (from, to, lo, hi) in constraints {
+                stn.add_constraint(from, to, lo, hi)
+                    .expect("points were just added");
+            }
+            if let Some(prev) = previous_end.replace(e) {
+                stn.add_constraint(&prev, &s, Quantity::new(0.0), Quantity::new(f64::INFINITY))
+                    .expect("points were just added");
+            }
+        }
+        stn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    fn window(d: &StnDistances<Second>, point: &str) -> (f64, f64) {
+        let (lo, hi) = d.window(point).unwrap();
+        (lo.value(), hi.value())
+    }
+
+    #[test]
+    fn propagation_tightens_windows() {
+        let mut stn = SimpleTemporalNetwork::<Second>::new();
+        stn.add_point("a");
+        stn.add_point("b");
+        stn.add_constraint(ORIGIN, "a", q(0.0), q(10.0)).unwrap();
+        stn.add_constraint("a", "b", q(5.0), q(8.0)).unwrap();
+        stn.add_constraint(ORIGIN, "b", q(0.0), q(12.0)).unwrap();
+
+        let d = stn.propagate().unwrap();
+        assert_eq!(window(&d, "a"), (0.0, 7.0));
+        assert_eq!(window(&d, "b"), (5.0, 12.0));
+        assert_eq!(d.distance("a", "b").unwrap().value(), 8.0);
+        let earliest = d.earliest();
+        assert_eq!(earliest["b"].value(), 5.0);
+    }
+
+    #[test]
+    fn negative_cycle_is_inconsistent() {
+        let mut stn = SimpleTemporalNetwork::<Second>::new();
+        stn.add_point("a");
+        stn.add_point("b");
+        stn.add_constraint("a", "b", q(10.0), q(20.0)).unwrap();
+        stn.add_constraint("b", "a", q(0.0), q(f64::INFINITY))
+            .unwrap();
+        assert!(!stn.is_consistent());
+        assert!(matches!(stn.propagate(), Err(StnError::Inconsistent(_))));
+    }
+
+    #[test]
+    fn unknown_point_is_rejected() {
+        let mut stn = SimpleTemporalNetwork::<Second>::new();
+        assert_eq!(
+            stn.add_constraint(ORIGIN, "x", q(0.0), q(1.0)),
+            Err(StnError::UnknownPoint("x".into()))
+        );
+    }
+
+    #[test]
+    fn schedule_export_keeps_order_and_windows() {
+        let mut ss = SolutionSpace::<Second>::new();
+        ss.add_interval("a", iv(0.0, 100.0));
+        ss.add_interval("b", iv(0.0, 30.0));
+        let mut s = Schedule::new();
+        s.add("a", iv(0.0, 10.0)).unwrap();
+        s.add("b", iv(10.0, 20.0)).unwrap();
+
+        let stn = s.to_stn(&ss);
+        assert_eq!(stn.points().len(), 5);
+        let d = stn.propagate().unwrap();
+        // b must start by 20, so a must end by 20.
+        assert_eq!(window(&d, "a.start"), (0.0, 10.0));
+        assert_eq!(window(&d, "b.start"), (10.0, 20.0));
+        assert_eq!(window(&d, "b.end"), (20.0, 30.0));
+    }
+
+    #[test]
+    fn block_relations_are_added() {
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        for id in ["a", "b"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let (a, b) = (block.node_of("a").unwrap(), block.node_of("b").unwrap());
+        block
+            .add_dependency(a, b, DynConstraintKind::start_to_start(25.0))
+            .unwrap();
+
+        let mut ss = SolutionSpace::<Second>::new();
+        ss.add_interval("a", iv(0.0, 100.0));
+        ss.add_interval("b", iv(0.0, 100.0));
+        let mut s = Schedule::new();
+        s.add("a", iv(0.0, 10.0)).unwrap();
+        s.add("b", iv(30.0, 40.0)).unwrap();
+
+        let mut stn = s.to_stn(&ss);
+        let before = stn.edges().count();
+        stn.add_relations(&block);
+        assert_eq!(stn.edges().count(), before + 1);
+        let d = stn.propagate().unwrap();
+        assert_eq!(window(&d, "b.start"), (25.0, 90.0));
+        assert!(stn.to_string().contains("b.start a.start -25"));
+    }
+}
@@ -470,6 +470,59 @@ mod edge_cases {
     }
 }
 
+#[cfg(test)]
+mod milestones {
+    use super::*;
+
+    #[test]
+    fn test_milestone_between_abutting_tasks() {
+        let mut schedule = TestSchedule::new();
+        schedule.add("a", iv(0.0, 10.0)).unwrap();
+        schedule.add("b", iv(10.0, 20.0)).unwrap();
+        schedule.add("m", iv(10.0, 10.0)).unwrap();
+
+        let ids: Vec<_> = schedule.iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, vec!["a", "m", "b"]);
+        assert_eq!(schedule.get_interval("b"), Some(iv(10.0, 20.0)));
+    }
+
+    #[test]
+    fn test_milestones_share_an_instant() {
+        let mut schedule = TestSchedule::new();
+        schedule.add("m1", iv(5.0, 5.0)).unwrap();
+        schedule.add("m2", iv(5.0, 5.0)).unwrap();
+
+        assert_eq!(schedule.len(), 2);
+        assert_eq!(schedule.remove("m1"), Some(iv(5.0, 5.0)));
+        assert_eq!(schedule.get_interval("m2"), Some(iv(5.0, 5.0)));
+    }
+
+    #[test]
+    fn test_milestone_inside_task_is_transparent() {
+        let mut schedule = TestSchedule::new();
+        schedule.add("a", iv(0.0, 10.0)).unwrap();
+        schedule.add("m", iv(5.0, 5.0)).unwrap();
+
+        // Overlap detection still sees `a` behind the milestone.
+        assert!(schedule.add("b", iv(6.0, 8.0)).is_err());
+        assert_eq!(schedule.task_at(q(7.0)).unwrap(), Some("a".to_string()));
+        assert_eq!(schedule.conflicts_vec(iv(6.0, 8.0)).unwrap().len(), 1);
+        assert!(schedule.is_free(iv(5.0, 5.0)).unwrap());
+        assert_eq!(schedule.latest_end(), Some(q(10.0)));
+    }
+
+    #[test]
+    fn test_milestone_is_last_entry() {
+        let mut schedule = TestSchedule::new();
+        schedule.add("a", iv(0.0, 10.0)).unwrap();
+        schedule.add("m", iv(12.0, 12.0)).unwrap();
+
+        assert_eq!(schedule.latest_end(), Some(q(12.0)));
+        assert_eq!(schedule.total_duration(), q(10.0));
+        assert_eq!(schedule.task_at(q(12.0)).unwrap(), None);
+    }
+}
+
 #[cfg(test)]
 mod nan_handling {
     use super::*;
@@ -506,6 +559,62 @@ mod nan_handling {
     }
 }
 
+// =============================================================================
+// Non-default keys and change tracking
+// =============================================================================
+
+mod custom_keys {
+    use super::*;
+
+    #[test]
+    fn integer_keys() {
+        let mut schedule: Schedule<Second, u64> = Schedule::default();
+        schedule.add(7u64, iv(0.0, 10.0)).unwrap();
+        schedule.add(3u64, iv(10.0, 20.0)).unwrap();
+
+        assert!(schedule.contains_task(&7));
+        assert_eq!(schedule.get_interval(&3), Some(iv(10.0, 20.0)));
+        assert_eq!(schedule.task_at(q(5.0)).unwrap(), Some(7));
+        assert_eq!(
+            schedule.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+            vec![7, 3]
+        );
+        assert_eq!(
+            schedule.add(7u64, iv(30.0, 40.0)),
+            Err(ScheduleError::DuplicateTaskId("7".to_string()))
+        );
+        assert_eq!(schedule.remove(&7), Some(iv(0.0, 10.0)));
+    }
+}
+
+mod generations {
+    use super::*;
+
+    #[test]
+    fn mutations_stamp_touched_ids() {
+        let mut schedule = TestSchedule::new();
+        assert_eq!(schedule.generation(), 0);
+        schedule.add("a", iv(0.0, 10.0)).unwrap();
+        schedule.add("b", iv(10.0, 20.0)).unwrap();
+        assert_eq!(schedule.changed_at("a"), 1);
+        assert_eq!(schedule.changed_at("b"), 2);
+        assert_eq!(schedule.changed_at("c"), 0);
+
+        // Rejected additions leave everything untouched.
+        assert!(schedule.add("c", iv(5.0, 15.0)).is_err());
+        assert_eq!(schedule.generation(), 2);
+
+        schedule.remove("a");
+        assert_eq!(schedule.changed_at("a"), 3);
+        assert_eq!(schedule.changed_at("b"), 2);
+
+        schedule.clear();
+        assert_eq!(schedule.generation(), 4);
+        assert_eq!(schedule.changed_at("a"), 3);
+        assert_eq!(schedule.changed_at("b"), 4);
+    }
+}
+
 // =============================================================================
 // Serde serialization tests
 // =============================================================================
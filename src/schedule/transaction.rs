@@ -0,0 +1,405 @@
+//! Transactional editing with undo/redo.
+//!
+//! [`Schedule::begin`] opens a [`ScheduleTransaction`] that applies `add` and
+//! `remove` calls to the schedule immediately while logging them. Committing
+//! yields a [`Changeset`]; rolling back (explicitly or by dropping the
+//! transaction) replays the log in reverse, so trying an edit never requires
+//! cloning the schedule.
+//!
+//! A [`ScheduleHistory`] keeps committed changesets on an undo stack:
+//!
+//! ```
+//! use virolai::schedule::{Schedule, ScheduleHistory};
+//! use virolai::solution_space::Interval;
+//! use qtty::Second;
+//!
+//! let mut schedule = Schedule::<Second>::new();
+//! let mut history = ScheduleHistory::new();
+//!
+//! let mut tx = schedule.begin();
+//! tx.add("a", Interval::from_f64(0.0, 10.0)).unwrap();
+//! tx.add("b", Interval::from_f64(10.0, 20.0)).unwrap();
+//! history.push(tx.commit());
+//!
+//! history.undo(&mut schedule).unwrap();
+//! assert!(schedule.is_empty());
+//! history.redo(&mut schedule).unwrap();
+//! assert_eq!(schedule.len(), 2);
+//! ```
+
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+use super::errors::ScheduleError;
+use super::Schedule;
+use crate::solution_space::Interval;
+use crate::{Id, TaskKey};
+use qtty::Unit;
+
+/// One logged schedule edit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Edit<U: Unit, I: TaskKey = Id> {
+    /// `id` was inserted at `interval`.
+    Add { id: I, interval: Interval<U> },
+    /// `id` was removed from `interval`.
+    Remove { id: I, interval: Interval<U> },
+}
+
+impl<U: Unit, I: TaskKey> Edit<U, I> {
+    /// Applies this edit to `schedule`.
+    fn apply(&self, schedule: &mut Schedule<U, I>) -> Result<(), ScheduleError> {
+        match self {
+            Edit::Add { id, interval } => schedule.add(id.clone(), *interval),
+            Edit::Remove { id, .. } => schedule
+                .remove(id)
+                .map(|_| ())
+                .ok_or_else(|| ScheduleError::TaskNotFound(id.to_string())),
+        }
+    }
+
+    /// The edit that undoes this one.
+    fn inverse(&self) -> Self {
+        match self {
+            Edit::Add { id, interval } => Edit::Remove {
+                id: id.clone(),
+                interval: *interval,
+            },
+            Edit::Remove { id, interval } => Edit::Add {
+                id: id.clone(),
+                interval: *interval,
+            },
+        }
+    }
+}
+
+/// Edits made by a committed transaction, in the order they were applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Changeset<U: Unit, I: TaskKey = Id> {
+    edits: Vec<Edit<U, I>>,
+}
+
+impl<U: Unit, I: TaskKey> Changeset<U, I> {
+    pub fn edits(&self) -> &[Edit<U, I>] {
+        &self.edits
+    }
+
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Re-applies the edits to `schedule`, all or nothing.
+    pub fn apply(&self, schedule: &mut Schedule<U, I>) -> Result<(), ScheduleError> {
+        let mut tx = schedule.begin();
+        for edit in &self.edits {
+            tx.apply(edit.clone())?;
+        }
+        tx.commit();
+        Ok(())
+    }
+
+    /// Undoes the edits on `schedule`, all or nothing.
+    ///
+    /// Fails if `schedule` was changed since in a way that conflicts with the
+    /// edits (e.g. an added task was already removed).
+    pub fn revert(&self, schedule: &mut Schedule<U, I>) -> Result<(), ScheduleError> {
+        let mut tx = schedule.begin();
+        for edit in self.edits.iter().rev() {
+            tx.apply(edit.inverse())?;
+        }
+        tx.commit();
+        Ok(())
+    }
+}
+
+/// Pending edits on a borrowed schedule.
+///
+/// Edits take effect immediately, so the schedule can be inspected through
+/// [`schedule`](Self::schedule) mid-transaction. A failed `add` changes
+/// nothing and leaves the transaction open. Dropping the transaction without
+/// calling [`commit`](Self::commit) rolls it back.
+#[derive(Debug)]
+pub struct ScheduleTransaction<'a, U: Unit, I: TaskKey = Id> {
+    schedule: &'a mut Schedule<U, I>,
+    log: Vec<Edit<U, I>>,
+}
+
+impl<U: Unit, I: TaskKey> Schedule<U, I> {
+    /// Starts a transaction on this schedule.
+    pub fn begin(&mut self) -> ScheduleTransaction<'_, U, I> {
+        ScheduleTransaction {
+            schedule: self,
+            log: Vec::new(),
+        }
+    }
+}
+
+impl<U: Unit, I: TaskKey> ScheduleTransaction<'_, U, I> {
+    /// Inserts a task; see [`Schedule::add`].
+    pub fn add(&mut self, id: impl Into<I>, interval: Interval<U>) -> Result<(), ScheduleError> {
+        self.apply(Edit::Add {
+            id: id.into(),
+            interval,
+        })
+    }
+
+    /// Removes a task; see [`Schedule::remove`].
+    pub fn remove<Q>(&mut self, id: &Q) -> Option<Interval<U>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let (id, interval) = self.schedule.remove_entry(id)?;
+        self.log.push(Edit::Remove { id, interval });
+        Some(interval)
+    }
+
+    /// The schedule with the pending edits applied.
+    pub fn schedule(&self) -> &Schedule<U, I> {
+        self.schedule
+    }
+
+    /// Edits applied so far.
+    pub fn edits(&self) -> &[Edit<U, I>] {
+        &self.log
+    }
+
+    /// Keeps the edits and returns them.
+    pub fn commit(mut self) -> Changeset<U, I> {
+        Changeset {
+            edits: std::mem::take(&mut self.log),
+        }
+    }
+
+    /// Undoes every edit made in this transaction.
+    pub fn rollback(self) {
+        // Dropping does the work.
+    }
+
+    fn apply(&mut self, edit: Edit<U, I>) -> Result<(), ScheduleError> {
+        edit.apply(self.schedule)?;
+        self.log.push(edit);
+        Ok(())
+    }
+}
+
+impl<U: Unit, I: TaskKey> Drop for ScheduleTransaction<'_, U, I> {
+    fn drop(&mut self) {
+        for edit in self.log.drain(..).rev() {
+            edit.inverse()
+                .apply(self.schedule)
+                .expect("inverse of a logged edit always applies");
+        }
+    }
+}
+
+/// Undo/redo stacks of committed [`Changeset`]s.
+#[derive(Debug, Clone)]
+pub struct ScheduleHistory<U: Unit, I: TaskKey = Id> {
+    undo: Vec<Changeset<U, I>>,
+    redo: Vec<Changeset<U, I>>,
+    limit: Option<usize>,
+}
+
+impl<U: Unit, I: TaskKey> Default for ScheduleHistory<U, I> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            limit: None,
+        }
+    }
+}
+
+impl<U: Unit> ScheduleHistory<U> {
+    /// Creates an empty, unbounded history keyed by [`Id`].
+    ///
+    /// For other key types use [`ScheduleHistory::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<U: Unit, I: TaskKey> ScheduleHistory<U, I> {
+    /// Keeps at most `limit` undo steps, dropping the oldest.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self.trim();
+        self
+    }
+
+    /// Records a committed changeset and clears the redo stack.
+    ///
+    /// Empty changesets are ignored.
+    pub fn push(&mut self, changeset: Changeset<U, I>) {
+        if changeset.is_empty() {
+            return;
+        }
+        self.undo.push(changeset);
+        self.redo.clear();
+        self.trim();
+    }
+
+    /// Reverts the latest changeset. Returns `Ok(false)` if there is nothing
+    /// to undo; on error the schedule and both stacks are unchanged.
+    pub fn undo(&mut self, schedule: &mut Schedule<U, I>) -> Result<bool, ScheduleError> {
+        let Some(changeset) = self.undo.last() else {
+            return Ok(false);
+        };
+        changeset.revert(schedule)?;
+        let changeset = self.undo.pop().expect("checked above");
+        self.redo.push(changeset);
+        Ok(true)
+    }
+
+    /// Re-applies the latest undone changeset. Returns `Ok(false)` if there is
+    /// nothing to redo; on error the schedule and both stacks are unchanged.
+    pub fn redo(&mut self, schedule: &mut Schedule<U, I>) -> Result<bool, ScheduleError> {
+        let Some(changeset) = self.redo.last() else {
+            return Ok(false);
+        };
+        changeset.apply(schedule)?;
+        let changeset = self.redo.pop().expect("checked above");
+        self.undo.push(changeset);
+        Ok(true)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forgets both stacks.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    fn trim(&mut self) {
+        if let Some(limit) = self.limit {
+            let excess = self.undo.len().saturating_sub(limit);
+            self.undo.drain(..excess);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn base() -> Schedule<Second> {
+        let mut s = Schedule::new();
+        s.add("a", iv(0.0, 10.0)).unwrap();
+        s.add("b", iv(20.0, 30.0)).unwrap();
+        s
+    }
+
+    fn snapshot(s: &Schedule<Second>) -> Vec<(Id, Interval<Second>)> {
+        s.iter().collect()
+    }
+
+    #[test]
+    fn commit_keeps_edits() {
+        let mut s = base();
+        let mut tx = s.begin();
+        tx.add("c", iv(10.0, 20.0)).unwrap();
+        assert_eq!(tx.remove("a"), Some(iv(0.0, 10.0)));
+        assert!(tx.schedule().contains_task("c"));
+        let changes = tx.commit();
+
+        assert_eq!(changes.len(), 2);
+        assert!(!s.contains_task("a"));
+        assert_eq!(s.get_interval("c"), Some(iv(10.0, 20.0)));
+    }
+
+    #[test]
+    fn rollback_and_drop_restore_schedule() {
+        let mut s = base();
+        let before = snapshot(&s);
+
+        let mut tx = s.begin();
+        tx.remove("a");
+        tx.add("a", iv(40.0, 50.0)).unwrap();
+        tx.rollback();
+        assert_eq!(snapshot(&s), before);
+
+        {
+            let mut tx = s.begin();
+            tx.remove("b");
+            tx.add("c", iv(5.0, 25.0)).unwrap_err(); // overlaps `a`
+            tx.add("c", iv(15.0, 25.0)).unwrap();
+        }
+        assert_eq!(snapshot(&s), before);
+    }
+
+    #[test]
+    fn undo_redo_round_trip() {
+        let mut s = base();
+        let before = snapshot(&s);
+        let mut history = ScheduleHistory::new();
+
+        let mut tx = s.begin();
+        tx.remove("b");
+        tx.add("b", iv(40.0, 50.0)).unwrap();
+        history.push(tx.commit());
+        let after = snapshot(&s);
+
+        assert!(history.undo(&mut s).unwrap());
+        assert_eq!(snapshot(&s), before);
+        assert!(!history.undo(&mut s).unwrap());
+
+        assert!(history.redo(&mut s).unwrap());
+        assert_eq!(snapshot(&s), after);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn push_clears_redo_and_respects_limit() {
+        let mut s = Schedule::<Second>::new();
+        let mut history = ScheduleHistory::new().with_limit(2);
+        for (i, id) in ["a", "b", "c"].into_iter().enumerate() {
+            let mut tx = s.begin();
+            tx.add(id, iv(i as f64 * 10.0, i as f64 * 10.0 + 5.0))
+                .unwrap();
+            history.push(tx.commit());
+        }
+        history.push(s.begin().commit()); // empty, ignored
+
+        assert!(history.undo(&mut s).unwrap());
+        assert!(history.undo(&mut s).unwrap());
+        assert!(!history.undo(&mut s).unwrap()); // "a" fell off
+        assert_eq!(s.ids().collect::<Vec<_>>(), vec!["a"]);
+
+        assert!(history.can_redo());
+        let mut tx = s.begin();
+        tx.add("d", iv(50.0, 60.0)).unwrap();
+        history.push(tx.commit());
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn failed_undo_leaves_state_untouched() {
+        let mut s = Schedule::<Second>::new();
+        let mut history = ScheduleHistory::new();
+        let mut tx = s.begin();
+        tx.add("a", iv(0.0, 10.0)).unwrap();
+        tx.add("b", iv(10.0, 20.0)).unwrap();
+        history.push(tx.commit());
+
+        s.remove("a"); // edited outside the history
+        assert_eq!(
+            history.undo(&mut s),
+            Err(ScheduleError::TaskNotFound("a".into()))
+        );
+        assert!(s.contains_task("b"));
+        assert!(history.can_undo());
+    }
+}
@@ -0,0 +1,540 @@
+//! Independent feasibility check of a finished schedule.
+//!
+//! [`validate`] re-derives every rule a schedule must satisfy from the
+//! [`SchedulingBlock`] and [`SolutionSpace`] it claims to implement, without
+//! trusting whoever produced it. It is meant for schedules loaded from outside
+//! (files, other planners, manual edits) and reports every problem it finds
+//! instead of stopping at the first. [`validate_with_blackouts`] also
+//! enforces a global [`BlackoutSet`]. [`validate_exclusions`] checks the
+//! block's mutual-exclusion groups across several resource schedules.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::Schedule;
+use crate::constraints::{DynamicConstraint, DynamicConstraintIndex, SchedulingContext};
+use crate::scheduling_block::{AlternativeStatus, SchedulingBlock, Task};
+use crate::solution_space::{BlackoutSet, Interval, SolutionSpace};
+use crate::Id;
+use qtty::Unit;
+
+/// A rule broken by a schedule.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation<U: Unit> {
+    /// The scheduled task does not belong to the block.
+    UnknownTask { task_id: Id },
+    /// The placement is not contained in any of the task's static windows.
+    OutsideWindows { task_id: Id, placement: Interval<U> },
+    /// Two placements share time.
+    Overlap { first: Id, second: Id },
+    /// A dynamic edge `source → target` is not satisfied by the placement of
+    /// `target`.
+    DynamicEdge {
+        source_id: Id,
+        target_id: Id,
+        constraint: String,
+        placement: Interval<U>,
+    },
+    /// An alternative group has no member placed although it is required,
+    /// or more than one. `placed` lists the placed members in start order.
+    Alternatives { group: String, placed: Vec<Id> },
+    /// Two members of a mutual-exclusion group share time, `first` starting
+    /// no later than `second`.
+    Exclusion {
+        group: String,
+        first: Id,
+        second: Id,
+    },
+    /// Foreign tasks sit between the placed members of a contiguous group;
+    /// `intruders` lists them in time order.
+    Contiguity { group: String, intruders: Vec<Id> },
+    /// The placement runs into the global blackout named `label`.
+    Blackout {
+        task_id: Id,
+        placement: Interval<U>,
+        label: String,
+    },
+}
+
+impl<U: Unit> fmt::Display for Violation<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::UnknownTask { task_id } => {
+                write!(f, "Task {task_id} is not part of the block")
+            }
+            Violation::OutsideWindows { task_id, placement } => {
+                write!(f, "Task {task_id} at {placement} lies outside its windows")
+            }
+            Violation::Overlap { first, second } => {
+                write!(f, "Task {first} overlaps with task {second}")
+            }
+            Violation::DynamicEdge {
+                source_id,
+                target_id,
+                constraint,
+                placement,
+            } => write!(
+                f,
+                "Task {target_id} at {placement} violates {constraint} from {source_id}"
+            ),
+            Violation::Alternatives { group, placed } if placed.is_empty() => {
+                write!(f, "Alternative group {group} has no member placed")
+            }
+            Violation::Alternatives { group, placed } => write!(
+                f,
+                "Alternative group {group} places {} members: {}",
+                placed.len(),
+                placed.join(", ")
+            ),
+            Violation::Exclusion {
+                group,
+                first,
+                second,
+            } => write!(
+                f,
+                "Tasks {first} and {second} of exclusion group {group} overlap"
+            ),
+            Violation::Contiguity { group, intruders } => write!(
+                f,
+                "Contiguous group {group} is interrupted by {}",
+                intruders.join(", ")
+            ),
+            Violation::Blackout {
+                task_id,
+                placement,
+                label,
+            } => write!(f, "Task {task_id} at {placement} falls in blackout {label}"),
+        }
+    }
+}
+
+/// Checks `schedule` against `block` and `solution_space`.
+///
+/// For every placed task, in start order:
+/// - it must belong to `block`;
+/// - its interval must fit inside one of its windows in `solution_space`
+///   (a task with no entry has no windows), as decided by
+///   [`SolutionSpace::can_place`];
+/// - it must not overlap the previous placement (milestones are exempt);
+/// - every incoming dynamic edge must admit the whole interval, evaluated
+///   against the complete schedule.
+///
+/// Then every [alternative group](crate::scheduling_block::AlternativeGroup)
+/// of `block` must be satisfied, no two members of an
+/// [exclusion group](crate::scheduling_block::ExclusionGroup) may overlap,
+/// and no foreign task may sit inside a
+/// [contiguous group](crate::constraints::ContiguousGroup).
+///
+/// An empty result means the schedule is valid.
+pub fn validate<T, U, D, E>(
+    schedule: &Schedule<U>,
+    block: &SchedulingBlock<T, U, D, E>,
+    solution_space: &SolutionSpace<U>,
+) -> Vec<Violation<U>>
+where
+    T: Task<U>,
+    U: Unit,
+    D: DynamicConstraint<U>,
+    E: petgraph::EdgeType,
+{
+    validate_with_blackouts(schedule, block, solution_space, &BlackoutSet::new())
+}
+
+/// [`validate`], additionally reporting a [`Violation::Blackout`] for every
+/// blackout a placement runs into.
+///
+/// The blackouts are checked on their own, so a schedule validated against
+/// a solution space they were never [applied](BlackoutSet::apply) to is
+/// still caught.
+pub fn validate_with_blackouts<T, U, D, E>(
+    schedule: &Schedule<U>,
+    block: &SchedulingBlock<T, U, D, E>,
+    solution_space: &SolutionSpace<U>,
+    blackouts: &BlackoutSet<U>,
+) -> Vec<Violation<U>>
+where
+    T: Task<U>,
+    U: Unit,
+    D: DynamicConstraint<U>,
+    E: petgraph::EdgeType,
+{
+    let index = DynamicConstraintIndex::from_blocks(std::slice::from_ref(block));
+    let mut violations = Vec::new();
+
+    let mut last_occupied: Option<(Id, Interval<U>)> = None;
+    for (task_id, placement) in schedule.iter() {
+        if block.node_of(&task_id).is_none() {
+            violations.push(Violation::UnknownTask {
+                task_id: task_id.clone(),
+            });
+        }
+
+        if !solution_space.can_place(task_id.as_str(), placement.start(), placement.duration()) {
+            violations.push(Violation::OutsideWindows {
+                task_id: task_id.clone(),
+                placement,
+            });
+        }
+
+        for (_, label) in blackouts.conflicts(placement) {
+            violations.push(Violation::Blackout {
+                task_id: task_id.clone(),
+                placement,
+                label: label.to_owned(),
+            });
+        }
+
+        // Milestones occupy no time and cannot overlap anything.
+        if !placement.is_empty() {
+            if let Some((prev_id, prev)) = &last_occupied {
+                if prev.overlaps(&placement) {
+                    violations.push(Violation::Overlap {
+                        first: prev_id.clone(),
+                        second: task_id.clone(),
+                    });
+                }
+            }
+            last_occupied = Some((task_id.clone(), placement));
+        }
+
+        let ctx = SchedulingContext::new(schedule, solution_space)
+            .with_target_size(placement.duration())
+            .with_target_id(task_id.as_str());
+        for (source_id, constraint) in index.get_edges(task_id.as_str()).unwrap_or_default() {
+            let admitted = constraint
+                .compute_intervals(placement, source_id, &ctx)
+                .iter()
+                .any(|iv| iv.start() <= placement.start() && iv.end() >= placement.end());
+            if !admitted {
+                violations.push(Violation::DynamicEdge {
+                    source_id: source_id.clone(),
+                    target_id: task_id.clone(),
+                    constraint: constraint.stringify(),
+                    placement,
+                });
+            }
+        }
+    }
+
+    for group in block.alternatives() {
+        if group.is_satisfied(schedule) {
+            continue;
+        }
+        let placed = match group.check(schedule) {
+            AlternativeStatus::Conflict(placed) => placed,
+            _ => Vec::new(),
+        };
+        violations.push(Violation::Alternatives {
+            group: group.name().to_owned(),
+            placed,
+        });
+    }
+
+    for group in block.contiguous_groups() {
+        let intruders = group.intruders(schedule);
+        if !intruders.is_empty() {
+            violations.push(Violation::Contiguity {
+                group: group.name().to_owned(),
+                intruders,
+            });
+        }
+    }
+
+    violations.extend(exclusion_violations(block, [schedule]));
+    violations
+}
+
+/// Checks the mutual-exclusion groups of `block` across the schedules of
+/// every resource.
+///
+/// [`validate`] only sees one schedule, where placements cannot overlap
+/// anyway; this catches members that run at once on different resources.
+pub fn validate_exclusions<T, U, D, E>(
+    resources: &HashMap<Id, Schedule<U>>,
+    block: &SchedulingBlock<T, U, D, E>,
+) -> Vec<Violation<U>>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    exclusion_violations(block, resources.values())
+}
+
+fn exclusion_violations<'s, T, U, D, E>(
+    block: &SchedulingBlock<T, U, D, E>,
+    schedules: impl IntoIterator<Item = &'s Schedule<U>> + Clone,
+) -> Vec<Violation<U>>
+where
+    T: Task<U>,
+    U: Unit + 's,
+    E: petgraph::EdgeType,
+{
+    block
+        .exclusions()
+        .iter()
+        .flat_map(|group| {
+            group
+                .conflicts(schedules.clone())
+                .into_iter()
+                .map(|(first, second)| Violation::Exclusion {
+                    group: group.name().to_owned(),
+                    first,
+                    second,
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::DynConstraintKind;
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    type Block = SchedulingBlock<TestTask, Second, DynConstraintKind>;
+
+    /// `a → b` (Consecutive), `a → c` (Exclusive), `d` unconstrained.
+    fn block() -> Block {
+        let mut block = Block::new();
+        for id in ["a", "b", "c", "d"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let a = block.node_of("a").unwrap();
+        let b = block.node_of("b").unwrap();
+        let c = block.node_of("c").unwrap();
+        block
+            .add_dependency(a, b, DynConstraintKind::Consecutive)
+            .unwrap();
+        block
+            .add_dependency(a, c, DynConstraintKind::Exclusive)
+            .unwrap();
+        block
+    }
+
+    fn space() -> SolutionSpace<Second> {
+        let mut ss = SolutionSpace::new();
+        for id in ["a", "b", "c", "d"] {
+            ss.set_intervals(id, vec![iv(0.0, 100.0)]);
+        }
+        ss.set_intervals("d", vec![iv(50.0, 60.0)]);
+        ss
+    }
+
+    fn schedule(entries: &[(&str, f64, f64)]) -> Schedule<Second> {
+        let mut s = Schedule::new();
+        for &(id, a, b) in entries {
+            s.add(id, iv(a, b)).unwrap();
+        }
+        s
+    }
+
+    #[test]
+    fn valid_schedule_has_no_violations() {
+        let s = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 20.0), ("d", 50.0, 60.0)]);
+        assert!(validate(&s, &block(), &space()).is_empty());
+    }
+
+    #[test]
+    fn reports_window_and_unknown_task() {
+        let s = schedule(&[("d", 40.0, 50.0), ("zz", 70.0, 80.0)]);
+        let v = validate(&s, &block(), &space());
+        assert_eq!(
+            v,
+            vec![
+                Violation::OutsideWindows {
+                    task_id: "d".into(),
+                    placement: iv(40.0, 50.0)
+                },
+                Violation::UnknownTask {
+                    task_id: "zz".into()
+                },
+                Violation::OutsideWindows {
+                    task_id: "zz".into(),
+                    placement: iv(70.0, 80.0)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_each_dynamic_kind() {
+        // b before a (Consecutive broken), c alongside a (Exclusive broken).
+        let s = schedule(&[("b", 0.0, 10.0), ("a", 10.0, 20.0), ("c", 30.0, 40.0)]);
+        let v = validate(&s, &block(), &space());
+        let broken: Vec<_> = v
+            .iter()
+            .map(|v| match v {
+                Violation::DynamicEdge {
+                    source_id,
+                    target_id,
+                    ..
+                } => (source_id.as_str(), target_id.as_str()),
+                other => panic!("unexpected {other}"),
+            })
+            .collect();
+        assert_eq!(broken, vec![("a", "b"), ("a", "c")]);
+    }
+
+    #[test]
+    fn dependence_requires_source() {
+        let mut block = Block::new();
+        for id in ["a", "b"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let (a, b) = (block.node_of("a").unwrap(), block.node_of("b").unwrap());
+        block
+            .add_dependency(a, b, DynConstraintKind::Dependence)
+            .unwrap();
+
+        let s = schedule(&[("b", 0.0, 10.0)]);
+        let v = validate(&s, &block, &space());
+        assert_eq!(v.len(), 1);
+        assert!(v[0].to_string().contains("from a"));
+    }
+
+    #[test]
+    fn finish_to_finish_checks_placement_end() {
+        let mut block = Block::new();
+        for id in ["a", "b"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let (a, b) = (block.node_of("a").unwrap(), block.node_of("b").unwrap());
+        block
+            .add_dependency(a, b, DynConstraintKind::finish_to_finish(5.0))
+            .unwrap();
+
+        let s = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 20.0)]);
+        assert!(validate(&s, &block, &space()).is_empty());
+
+        let s = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 14.0)]);
+        assert_eq!(validate(&s, &block, &space()).len(), 1);
+    }
+
+    #[test]
+    fn milestone_after_consecutive_source() {
+        let mut block = Block::new();
+        for (id, size) in [("a", 10.0), ("m", 0.0)] {
+            block
+                .add_task_with_id(TestTask::new(id, size), Some(id.into()))
+                .unwrap();
+        }
+        let (a, m) = (block.node_of("a").unwrap(), block.node_of("m").unwrap());
+        block
+            .add_dependency(a, m, DynConstraintKind::Consecutive)
+            .unwrap();
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("a", vec![iv(0.0, 10.0)]);
+        ss.set_intervals("m", vec![iv(0.0, 10.0)]);
+
+        // On the closing edge of both the predecessor and the window.
+        let s = schedule(&[("a", 0.0, 10.0), ("m", 10.0, 10.0)]);
+        assert!(validate(&s, &block, &ss).is_empty());
+
+        let s = schedule(&[("a", 0.0, 10.0), ("m", 5.0, 5.0)]);
+        let v = validate(&s, &block, &ss);
+        assert_eq!(v.len(), 1);
+        assert!(matches!(v[0], Violation::DynamicEdge { .. }));
+    }
+
+    #[test]
+    fn reports_alternative_groups() {
+        use crate::scheduling_block::AlternativeGroup;
+
+        let mut block = block();
+        block
+            .add_alternatives(AlternativeGroup::new("cal", ["b", "d"]))
+            .unwrap();
+
+        let none = schedule(&[("a", 0.0, 10.0)]);
+        assert_eq!(
+            validate(&none, &block, &space()),
+            vec![Violation::Alternatives {
+                group: "cal".into(),
+                placed: vec![]
+            }]
+        );
+
+        let both = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 20.0), ("d", 50.0, 60.0)]);
+        let v = validate(&both, &block, &space());
+        assert!(matches!(v[0], Violation::DynamicEdge { .. }));
+        assert_eq!(
+            v[1].to_string(),
+            "Alternative group cal places 2 members: b, d"
+        );
+        assert_eq!(v.len(), 2);
+    }
+
+    #[test]
+    fn reports_contiguous_groups() {
+        use crate::constraints::ContiguousGroup;
+
+        let mut block = block();
+        block
+            .add_contiguous(ContiguousGroup::new("scan").with_members(["a", "b"]))
+            .unwrap();
+
+        let s = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 20.0), ("d", 50.0, 60.0)]);
+        assert!(validate(&s, &block, &space()).is_empty());
+
+        let mut ss = space();
+        ss.set_intervals("b", vec![iv(0.0, 100.0)]);
+        let s = schedule(&[("a", 0.0, 10.0), ("c", 10.0, 20.0), ("b", 20.0, 30.0)]);
+        let v = validate(&s, &block, &ss);
+        assert_eq!(
+            v.last().unwrap().to_string(),
+            "Contiguous group scan is interrupted by c"
+        );
+    }
+
+    #[test]
+    fn reports_exclusion_groups_across_resources() {
+        use crate::scheduling_block::ExclusionGroup;
+
+        let mut block = block();
+        block
+            .add_exclusion(ExclusionGroup::new("detector", ["b", "d"]))
+            .unwrap();
+
+        let r1 = schedule(&[("a", 0.0, 10.0), ("b", 50.0, 60.0)]);
+        let r2 = schedule(&[("d", 55.0, 65.0)]);
+        assert!(validate(&r1, &block, &space()).is_empty());
+
+        let resources = HashMap::from([("r1".to_string(), r1), ("r2".to_string(), r2)]);
+        let v = validate_exclusions(&resources, &block);
+        assert_eq!(
+            v,
+            vec![Violation::Exclusion {
+                group: "detector".into(),
+                first: "b".into(),
+                second: "d".into(),
+            }]
+        );
+        assert_eq!(
+            v[0].to_string(),
+            "Tasks b and d of exclusion group detector overlap"
+        );
+    }
+
+    #[test]
+    fn blackouts_are_enforced_without_being_applied() {
+        let blackouts = BlackoutSet::new().with_blackout(iv(55.0, 65.0), "maintenance");
+        let s = schedule(&[("a", 0.0, 10.0), ("b", 10.0, 20.0), ("d", 50.0, 60.0)]);
+        assert!(validate(&s, &block(), &space()).is_empty());
+        assert_eq!(
+            validate_with_blackouts(&s, &block(), &space(), &blackouts),
+            vec![Violation::Blackout {
+                task_id: "d".into(),
+                placement: iv(50.0, 60.0),
+                label: "maintenance".into(),
+            }]
+        );
+    }
+}
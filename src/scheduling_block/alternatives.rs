@@ -0,0 +1,162 @@
+//! Groups of mutually exclusive alternative tasks.
+//!
+//! Some requirements can be met in several ways — two calibrator options,
+//! the same exposure on either of two instruments — of which exactly one
+//! should run. [`SchedulingBlock::add_alternatives`](super::SchedulingBlock::add_alternatives)
+//! records such a group on the block and adds an
+//! [`Exclusive`](crate::constraints::DynConstraintKind::Exclusive) edge between
+//! every pair of members, from the member earlier in topological order to the
+//! later one. Once one alternative is placed the others become unschedulable:
+//! the edges rule out the later members, and the
+//! [EST scheduler](crate::algorithms::est) checks the group itself, so an
+//! earlier member is ruled out too when a later one is placed first.
+//!
+//! The edges only rule out a second choice; whether one was made at all is a
+//! group-level question answered by [`AlternativeGroup::check`] and reported
+//! by [`validate`](crate::schedule::validate()).
+
+use std::fmt;
+
+use crate::schedule::Schedule;
+use crate::Id;
+use qtty::Unit;
+
+/// A named set of tasks of which one should be scheduled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlternativeGroup {
+    name: String,
+    members: Vec<Id>,
+    required: bool,
+}
+
+/// Outcome of an [`AlternativeGroup`] in a schedule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlternativeStatus {
+    /// Exactly one member is placed.
+    Chosen(Id),
+    /// No member is placed.
+    Unchosen,
+    /// Several members are placed, in start order.
+    Conflict(Vec<Id>),
+}
+
+impl AlternativeGroup {
+    /// Creates a required group: exactly one member must be placed.
+    pub fn new(name: impl Into<String>, members: impl IntoIterator<Item = impl Into<Id>>) -> Self {
+        Self {
+            name: name.into(),
+            members: members.into_iter().map(Into::into).collect(),
+            required: true,
+        }
+    }
+
+    /// Whether placing none of the members is a violation. When `false` the
+    /// group only asks for at most one.
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Members in topological order.
+    pub fn members(&self) -> &[Id] {
+        &self.members
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    pub fn contains(&self, task_id: &str) -> bool {
+        self.members.iter().any(|m| m == task_id)
+    }
+
+    pub(crate) fn set_members(&mut self, members: Vec<Id>) {
+        self.members = members;
+    }
+
+    pub(crate) fn remove_member(&mut self, task_id: &str) {
+        self.members.retain(|m| m != task_id);
+    }
+
+    /// Which members `schedule` placed.
+    pub fn check<U: Unit>(&self, schedule: &Schedule<U>) -> AlternativeStatus {
+        let placed: Vec<Id> = schedule
+            .iter()
+            .map(|(id, _)| id)
+            .filter(|id| self.contains(id))
+            .collect();
+        match placed.len() {
+            0 => AlternativeStatus::Unchosen,
+            1 => AlternativeStatus::Chosen(placed.into_iter().next().unwrap()),
+            _ => AlternativeStatus::Conflict(placed),
+        }
+    }
+
+    /// Whether `schedule` satisfies the group.
+    pub fn is_satisfied<U: Unit>(&self, schedule: &Schedule<U>) -> bool {
+        match self.check(schedule) {
+            AlternativeStatus::Chosen(_) => true,
+            AlternativeStatus::Unchosen => !self.required,
+            AlternativeStatus::Conflict(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for AlternativeGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bound = if self.required { "exactly" } else { "at most" };
+        write!(
+            f,
+            "{}: {bound} one of [{}]",
+            self.name,
+            self.members.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn group() -> AlternativeGroup {
+        AlternativeGroup::new("calibrator", ["lamp", "sky"])
+    }
+
+    #[test]
+    fn status_by_placed_members() {
+        let mut s = Schedule::<Second>::new();
+        s.add("other", iv(0.0, 5.0)).unwrap();
+        assert_eq!(group().check(&s), AlternativeStatus::Unchosen);
+        assert!(!group().is_satisfied(&s));
+        assert!(group().with_required(false).is_satisfied(&s));
+
+        s.add("sky", iv(20.0, 30.0)).unwrap();
+        assert_eq!(group().check(&s), AlternativeStatus::Chosen("sky".into()));
+        assert!(group().is_satisfied(&s));
+
+        s.add("lamp", iv(10.0, 20.0)).unwrap();
+        assert_eq!(
+            group().check(&s),
+            AlternativeStatus::Conflict(vec!["lamp".into(), "sky".into()])
+        );
+        assert!(!group().with_required(false).is_satisfied(&s));
+    }
+
+    #[test]
+    fn display_lists_members() {
+        assert_eq!(
+            group().to_string(),
+            "calibrator: exactly one of [lamp, sky]"
+        );
+        assert_eq!(
+            group().with_required(false).to_string(),
+            "calibrator: at most one of [lamp, sky]"
+        );
+    }
+}
@@ -1,11 +1,15 @@
+use super::alternatives::AlternativeGroup;
 use super::error::SchedulingError;
+use super::exclusion::ExclusionGroup;
 use super::task::Task;
+use crate::constraints::ContiguousGroup;
 use crate::Id;
-use petgraph::algo::{has_path_connecting, toposort};
+use petgraph::algo::{has_path_connecting, tarjan_scc, toposort};
 use petgraph::stable_graph::StableGraph;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
 use petgraph::{Directed, Direction, EdgeType};
 use qtty::{Quantity, Second, Unit};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::Display;
 
 /// DAG-based task scheduler with dependency tracking.
@@ -36,6 +40,12 @@ where
     id_by_node: HashMap<petgraph::graph::NodeIndex, Id>,
     /// Maps ID → node index for reverse lookup.
     node_by_id: HashMap<Id, petgraph::graph::NodeIndex>,
+    /// Groups registered through [`add_alternatives`](Self::add_alternatives).
+    alternatives: Vec<AlternativeGroup>,
+    /// Groups registered through [`add_exclusion`](Self::add_exclusion).
+    exclusions: Vec<ExclusionGroup>,
+    /// Groups registered through [`add_contiguous`](Self::add_contiguous).
+    contiguous: Vec<ContiguousGroup>,
     _phantom: std::marker::PhantomData<U>,
 }
 
@@ -50,6 +60,9 @@ where
             graph: StableGraph::default(),
             id_by_node: HashMap::new(),
             node_by_id: HashMap::new(),
+            alternatives: Vec::new(),
+            exclusions: Vec::new(),
+            contiguous: Vec::new(),
             _phantom: std::marker::PhantomData,
         }
     }
@@ -66,6 +79,9 @@ where
             graph: StableGraph::default(),
             id_by_node: HashMap::new(),
             node_by_id: HashMap::new(),
+            alternatives: Vec::new(),
+            exclusions: Vec::new(),
+            contiguous: Vec::new(),
             _phantom: std::marker::PhantomData,
         }
     }
@@ -157,6 +173,14 @@ where
         Ok(())
     }
 
+    /// Adds edge `from` → `to` between two task IDs without the cycle check,
+    /// for tests of graphs that reached a block some other way.
+    #[cfg(test)]
+    pub(crate) fn add_edge_unchecked(&mut self, from: &str, to: &str, dep: D) {
+        let (from, to) = (self.node_by_id[from], self.node_by_id[to]);
+        self.graph.add_edge(from, to, dep);
+    }
+
     /// Returns task nodes in topological order.
     ///
     /// # Errors
@@ -167,6 +191,131 @@ where
         toposort(&self.graph, None).map_err(|_| SchedulingError::GraphContainsCycle)
     }
 
+    /// Groups tasks into layers: layer 0 holds tasks without predecessors,
+    /// layer `k` tasks whose predecessors all sit in earlier layers, at
+    /// least one in layer `k - 1`. Each layer is sorted by node index.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GraphContainsCycle` if the graph has a cycle.
+    pub fn topological_layers(
+        &self,
+    ) -> Result<Vec<Vec<petgraph::graph::NodeIndex>>, SchedulingError> {
+        self.topological_layers_by(|_| true)
+    }
+
+    /// [`topological_layers`](Self::topological_layers) following only the
+    /// edges whose data satisfies `follow`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GraphContainsCycle` if the followed edges form a cycle.
+    pub fn topological_layers_by(
+        &self,
+        follow: impl Fn(&D) -> bool,
+    ) -> Result<Vec<Vec<petgraph::graph::NodeIndex>>, SchedulingError> {
+        let edges: Vec<_> = self
+            .graph
+            .edge_references()
+            .filter(|e| follow(e.weight()))
+            .map(|e| (e.source(), e.target()))
+            .collect();
+        let mut in_degree: HashMap<_, usize> = self.graph.node_indices().map(|n| (n, 0)).collect();
+        for &(from, to) in &edges {
+            *in_degree.get_mut(&to).expect("edge endpoints exist") += 1;
+            if !E::is_directed() {
+                *in_degree.get_mut(&from).expect("edge endpoints exist") += 1;
+            }
+        }
+
+        let mut layers = Vec::new();
+        let mut current: Vec<_> = in_degree
+            .iter()
+            .filter(|&(_, &d)| d == 0)
+            .map(|(&n, _)| n)
+            .collect();
+        let mut placed = 0;
+        while !current.is_empty() {
+            current.sort();
+            placed += current.len();
+            let mut next = Vec::new();
+            for &node in &current {
+                for &(_, to) in edges.iter().filter(|&&(from, _)| from == node) {
+                    let d = in_degree.get_mut(&to).expect("edge endpoints exist");
+                    *d -= 1;
+                    if *d == 0 {
+                        next.push(to);
+                    }
+                }
+            }
+            layers.push(std::mem::replace(&mut current, next));
+        }
+
+        if placed != self.graph.node_count() {
+            return Err(SchedulingError::GraphContainsCycle);
+        }
+        Ok(layers)
+    }
+
+    /// Returns the edges of one cycle per strongly connected component that
+    /// has one, each as `(from, to)` ID pairs with the last edge closing the
+    /// loop, e.g. `[(a, b), (b, a)]`.
+    ///
+    /// [`add_dependency`](Self::add_dependency) rejects cycles, so this is a
+    /// diagnostic for graphs that reached a block some other way. Undirected
+    /// graphs have no dependency direction and always return an empty list.
+    pub fn find_cycles(&self) -> Vec<Vec<(Id, Id)>> {
+        if !E::is_directed() {
+            return Vec::new();
+        }
+        let mut cycles = Vec::new();
+        for scc in tarjan_scc(&self.graph) {
+            let start = scc[0];
+            let is_cycle = scc.len() > 1 || self.graph.find_edge(start, start).is_some();
+            if !is_cycle {
+                continue;
+            }
+            let members: HashSet<_> = scc.iter().copied().collect();
+            // Breadth-first search back to `start` within the component.
+            let mut parent = HashMap::new();
+            let mut queue = VecDeque::from([start]);
+            let mut closing = None;
+            while let Some(node) = queue.pop_front() {
+                if self.graph.find_edge(node, start).is_some() {
+                    closing = Some(node);
+                    break;
+                }
+                for next in self.graph.neighbors_directed(node, Direction::Outgoing) {
+                    if members.contains(&next) && next != start && !parent.contains_key(&next) {
+                        parent.insert(next, node);
+                        queue.push_back(next);
+                    }
+                }
+            }
+            let Some(last) = closing else { continue };
+            let mut path = vec![last];
+            while let Some(&p) = parent.get(path.last().unwrap()) {
+                path.push(p);
+            }
+            path.reverse();
+            let id = |n| self.id_by_node[&n].clone();
+            let mut edges: Vec<_> = path.windows(2).map(|w| (id(w[0]), id(w[1]))).collect();
+            edges.push((id(last), id(start)));
+            cycles.push(edges);
+        }
+        cycles.sort();
+        cycles
+    }
+
+    /// Fails with [`SchedulingError::Cycle`] naming the first cycle found by
+    /// [`find_cycles`](Self::find_cycles).
+    pub fn ensure_acyclic(&self) -> Result<(), SchedulingError> {
+        match self.find_cycles().into_iter().next() {
+            Some(cycle) => Err(SchedulingError::Cycle(cycle)),
+            None => Ok(()),
+        }
+    }
+
     /// Returns tasks with no predecessors (entry points).
     pub fn roots(&self) -> Vec<petgraph::graph::NodeIndex> {
         self.graph
@@ -213,9 +362,55 @@ where
     pub fn remove_task(&mut self, id: &str) -> Option<T> {
         let node = self.node_by_id.remove(id)?;
         self.id_by_node.remove(&node);
+        for group in &mut self.alternatives {
+            group.remove_member(id);
+        }
+        for group in &mut self.exclusions {
+            group.remove_member(id);
+        }
+        for group in &mut self.contiguous {
+            group.remove_member(id);
+        }
         self.graph.remove_node(node)
     }
 
+    /// Alternative groups of this block.
+    pub fn alternatives(&self) -> &[AlternativeGroup] {
+        &self.alternatives
+    }
+
+    /// Mutual-exclusion groups of this block.
+    pub fn exclusions(&self) -> &[ExclusionGroup] {
+        &self.exclusions
+    }
+
+    /// Contiguous groups of this block.
+    pub fn contiguous_groups(&self) -> &[ContiguousGroup] {
+        &self.contiguous
+    }
+
+    /// Registers `group`, whose scheduled members must form one block with
+    /// no foreign task between them.
+    ///
+    /// No edges are added: the constraint concerns every task of the run,
+    /// members or not, so the scheduler evaluates the group itself.
+    ///
+    /// # Errors
+    ///
+    /// - `UnknownId` if a member is not in the block; nothing is added
+    pub fn add_contiguous(&mut self, group: ContiguousGroup) -> Result<(), SchedulingError> {
+        let mut missing: Vec<&str> = group
+            .members()
+            .filter(|m| !self.node_by_id.contains_key(*m))
+            .collect();
+        missing.sort_unstable();
+        if let Some(missing) = missing.first() {
+            return Err(SchedulingError::UnknownId((*missing).to_owned()));
+        }
+        self.contiguous.push(group);
+        Ok(())
+    }
+
     pub fn get_task(&self, node: petgraph::graph::NodeIndex) -> Option<&T> {
         self.graph.node_weight(node)
     }
@@ -240,6 +435,85 @@ where
     }
 }
 
+impl<T, U, D, E> SchedulingBlock<T, U, D, E>
+where
+    T: Task<U>,
+    U: Unit,
+    D: From<crate::constraints::DynConstraintKind>,
+    E: EdgeType,
+{
+    /// Registers `group` and adds an `Exclusive` edge between each pair of
+    /// its members, directed along the topological order so the graph stays
+    /// acyclic. The stored group lists its members in that order.
+    ///
+    /// # Errors
+    ///
+    /// - `UnknownId` if a member is not in the block; nothing is added
+    /// - `GraphContainsCycle` if the graph is not acyclic
+    pub fn add_alternatives(&mut self, mut group: AlternativeGroup) -> Result<(), SchedulingError> {
+        let members = self.connect_group(
+            group.members(),
+            crate::constraints::DynConstraintKind::Exclusive,
+        )?;
+        group.set_members(members);
+        self.alternatives.push(group);
+        Ok(())
+    }
+
+    /// Registers `group` and adds a `Disjoint` edge between each pair of its
+    /// members, directed along the topological order so the graph stays
+    /// acyclic. The stored group lists its members in that order.
+    ///
+    /// # Errors
+    ///
+    /// - `UnknownId` if a member is not in the block; nothing is added
+    /// - `GraphContainsCycle` if the graph is not acyclic
+    pub fn add_exclusion(&mut self, mut group: ExclusionGroup) -> Result<(), SchedulingError> {
+        let members = self.connect_group(
+            group.members(),
+            crate::constraints::DynConstraintKind::Disjoint,
+        )?;
+        group.set_members(members);
+        self.exclusions.push(group);
+        Ok(())
+    }
+
+    /// Adds a `kind` edge between each pair of `members` along the
+    /// topological order and returns the members in that order.
+    fn connect_group(
+        &mut self,
+        members: &[Id],
+        kind: crate::constraints::DynConstraintKind,
+    ) -> Result<Vec<Id>, SchedulingError> {
+        if let Some(missing) = members
+            .iter()
+            .find(|m| !self.node_by_id.contains_key(m.as_str()))
+        {
+            return Err(SchedulingError::UnknownId(missing.clone()));
+        }
+
+        let position: HashMap<_, _> = self
+            .topo_order()?
+            .into_iter()
+            .enumerate()
+            .map(|(i, node)| (node, i))
+            .collect();
+        let mut nodes: Vec<_> = members
+            .iter()
+            .map(|m| self.node_by_id[m.as_str()])
+            .collect();
+        nodes.sort_by_key(|n| position[n]);
+        nodes.dedup();
+
+        for (i, &from) in nodes.iter().enumerate() {
+            for &to in &nodes[i + 1..] {
+                self.graph.add_edge(from, to, kind.into());
+            }
+        }
+        Ok(nodes.iter().map(|n| self.id_by_node[n].clone()).collect())
+    }
+}
+
 // Dynamic constraint evaluation methods.
 //
 // Available only when edge data `D` implements `DynamicConstraint<U>`.
@@ -280,7 +554,7 @@ where
             return None;
         }
 
-        let result = incoming
+        let results: Vec<_> = incoming
             .iter()
             .filter_map(|&pred_node| {
                 let pred_id = self.id_by_node.get(&pred_node)?;
@@ -289,9 +563,10 @@ where
                 let constraint = self.graph.edge_weight(edge_idx)?;
                 Some(constraint.compute_intervals(range, pred_id, ctx))
             })
-            .reduce(|acc, v| crate::constraints::operations::compute_intersection(&acc, &v));
+            .collect();
 
-        result
+        (!results.is_empty())
+            .then(|| crate::constraints::operations::compute_intersection_many(&results))
     }
 }
 
@@ -646,6 +921,104 @@ mod tests {
         assert_eq!(result, Err(SchedulingError::CycleDetected));
     }
 
+    // ── Alternatives ──────────────────────────────────────────────────
+
+    #[test]
+    fn add_alternatives_links_members_in_topo_order() {
+        use crate::constraints::DynConstraintKind;
+
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        for id in ["a", "b", "c"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let (nb, nc) = (block.node_of("b").unwrap(), block.node_of("c").unwrap());
+        block
+            .add_dependency(nc, nb, DynConstraintKind::Dependence)
+            .unwrap();
+
+        block
+            .add_alternatives(AlternativeGroup::new("g", ["b", "c", "a"]))
+            .unwrap();
+        assert_eq!(block.dependency_count(), 4);
+        let members = block.alternatives()[0].members();
+        let pos = |id: &str| members.iter().position(|m| m == id).unwrap();
+        assert!(pos("c") < pos("b"));
+        assert!(block.topo_order().is_ok());
+
+        block.remove_task("a");
+        assert!(!block.alternatives()[0].contains("a"));
+    }
+
+    #[test]
+    fn add_alternatives_unknown_member() {
+        use crate::constraints::DynConstraintKind;
+
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        block
+            .add_task_with_id(TestTask::new("a", 10.0), Some("a".into()))
+            .unwrap();
+        let result = block.add_alternatives(AlternativeGroup::new("g", ["a", "zz"]));
+        assert_eq!(result, Err(SchedulingError::UnknownId("zz".into())));
+        assert_eq!(block.dependency_count(), 0);
+        assert!(block.alternatives().is_empty());
+    }
+
+    #[test]
+    fn add_contiguous_checks_members() {
+        use crate::constraints::ContiguousGroup;
+
+        let mut block: SchedulingBlock<TestTask> = SchedulingBlock::new();
+        for id in ["m1", "m2"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let result =
+            block.add_contiguous(ContiguousGroup::new("mosaic").with_members(["m1", "zz"]));
+        assert_eq!(result, Err(SchedulingError::UnknownId("zz".into())));
+        assert!(block.contiguous_groups().is_empty());
+
+        let mosaic = ContiguousGroup::new("mosaic").with_members(["m1", "m2"]);
+        block.add_contiguous(mosaic).unwrap();
+        assert_eq!(block.dependency_count(), 0);
+        block.remove_task("m2");
+        assert!(!block.contiguous_groups()[0].contains("m2"));
+    }
+
+    #[test]
+    fn add_exclusion_generates_disjoint_edges() {
+        use crate::constraints::DynConstraintKind;
+        use crate::scheduling_block::ExclusionGroup;
+
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        for id in ["a", "b", "c"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        block
+            .add_exclusion(ExclusionGroup::new("crew", ["a", "b", "c"]))
+            .unwrap();
+        assert_eq!(block.dependency_count(), 3);
+        assert!(block
+            .graph()
+            .edge_weights()
+            .all(|kind| *kind == DynConstraintKind::Disjoint));
+        assert_eq!(block.exclusions()[0].members().len(), 3);
+
+        let result = block.add_exclusion(ExclusionGroup::new("g", ["a", "zz"]));
+        assert_eq!(result, Err(SchedulingError::UnknownId("zz".into())));
+        assert_eq!(block.exclusions().len(), 1);
+
+        block.remove_task("b");
+        assert!(!block.exclusions()[0].contains("b"));
+    }
+
     // ── Topological order ─────────────────────────────────────────────
 
     #[test]
@@ -682,6 +1055,92 @@ mod tests {
         assert!(order.is_empty());
     }
 
+    // ── Topological layers ────────────────────────────────────────────
+
+    #[test]
+    fn topological_layers_group_by_depth() {
+        let mut block: SchedulingBlock<TestTask, Second, bool> = SchedulingBlock::new();
+        for id in ["a", "b", "c", "d"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        let n = |id| block.node_of(id).unwrap();
+        let (a, b, c, d) = (n("a"), n("b"), n("c"), n("d"));
+        block.add_dependency(a, b, true).unwrap();
+        block.add_dependency(b, c, true).unwrap();
+        block.add_dependency(a, c, true).unwrap();
+        block.add_dependency(d, c, false).unwrap();
+
+        assert_eq!(
+            block.topological_layers().unwrap(),
+            vec![vec![a, d], vec![b], vec![c]]
+        );
+        // Ignoring d → c does not move c: it still waits for b.
+        assert_eq!(
+            block.topological_layers_by(|&hard| hard).unwrap(),
+            vec![vec![a, d], vec![b], vec![c]]
+        );
+        assert_eq!(
+            block.topological_layers_by(|_| false).unwrap(),
+            vec![vec![a, b, c, d]]
+        );
+    }
+
+    #[test]
+    fn topological_layers_reject_cycles() {
+        let block = cyclic_block(&[("a", "b"), ("b", "a")]);
+        assert_eq!(
+            block.topological_layers(),
+            Err(SchedulingError::GraphContainsCycle)
+        );
+    }
+
+    // ── Cycle diagnostics ─────────────────────────────────────────────
+
+    /// Builds a graph with a cycle behind the block's back; `add_dependency`
+    /// would refuse it.
+    fn cyclic_block(edges: &[(&str, &str)]) -> SchedulingBlock<TestTask> {
+        let mut block: SchedulingBlock<TestTask> = SchedulingBlock::new();
+        for id in ["a", "b", "c", "d"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0), Some(id.into()))
+                .unwrap();
+        }
+        for &(from, to) in edges {
+            block.add_edge_unchecked(from, to, ());
+        }
+        block
+    }
+
+    #[test]
+    fn find_cycles_on_dag_is_empty() {
+        let block = cyclic_block(&[("a", "b"), ("b", "c"), ("a", "c")]);
+        assert!(block.find_cycles().is_empty());
+        assert!(block.ensure_acyclic().is_ok());
+    }
+
+    #[test]
+    fn find_cycles_reports_each_component() {
+        let block = cyclic_block(&[("a", "b"), ("b", "c"), ("c", "a"), ("d", "d")]);
+        let cycles = block.find_cycles();
+        assert_eq!(cycles.len(), 2);
+        let three = cycles.iter().find(|c| c.len() == 3).unwrap();
+        for (i, (_, to)) in three.iter().enumerate() {
+            assert_eq!(to, &three[(i + 1) % 3].0);
+        }
+        assert!(cycles.contains(&vec![("d".to_string(), "d".to_string())]));
+    }
+
+    #[test]
+    fn ensure_acyclic_names_the_cycle() {
+        let block = cyclic_block(&[("a", "b"), ("b", "a")]);
+        let err = block.ensure_acyclic().unwrap_err();
+        assert!(matches!(err, SchedulingError::Cycle(ref edges) if edges.len() == 2));
+        let msg = err.to_string();
+        assert!(msg == "Dependency cycle: a -> b -> a" || msg == "Dependency cycle: b -> a -> b");
+    }
+
     // ── Roots and leaves ──────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,235 @@
+//! Hierarchical tasks built from child tasks.
+//!
+//! Observation programs are trees rather than flat lists: a programme holds
+//! observing blocks, which hold individual exposures. A [`CompositeTask`]
+//! names such a parent. Its children are task IDs of a
+//! [`SchedulingBlock`](super::SchedulingBlock) or nested composites; only the
+//! leaves are scheduled. The parent has no interval of its own — it is
+//! derived as the envelope of its children, and only exists once every leaf
+//! is placed.
+//!
+//! # Example
+//!
+//! ```
+//! use qtty::Second;
+//! use virolai::schedule::Schedule;
+//! use virolai::scheduling_block::CompositeTask;
+//! use virolai::solution_space::Interval;
+//!
+//! let programme = CompositeTask::new("programme")
+//!     .with_task("flat")
+//!     .with_composite(CompositeTask::new("science").with_task("exp-1").with_task("exp-2"));
+//!
+//! let mut schedule = Schedule::<Second>::new();
+//! schedule.add("flat", Interval::from_f64(0.0, 5.0)).unwrap();
+//! schedule.add("exp-1", Interval::from_f64(10.0, 20.0)).unwrap();
+//! assert_eq!(programme.envelope(&schedule), None);
+//! assert_eq!(programme.missing(&schedule), vec!["exp-2".to_string()]);
+//!
+//! schedule.add("exp-2", Interval::from_f64(20.0, 30.0)).unwrap();
+//! assert_eq!(programme.envelope(&schedule), Some(Interval::from_f64(0.0, 30.0)));
+//! ```
+
+use crate::schedule::Schedule;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::Unit;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A child of a [`CompositeTask`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum CompositeChild {
+    /// A schedulable task, by ID.
+    Task(Id),
+    /// A nested composite.
+    Composite(CompositeTask),
+}
+
+/// A parent task whose children must all be scheduled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CompositeTask {
+    name: String,
+    children: Vec<CompositeChild>,
+}
+
+impl CompositeTask {
+    /// Creates a composite with no children.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Adds the task `id` as a child.
+    pub fn with_task(mut self, id: impl Into<Id>) -> Self {
+        self.children.push(CompositeChild::Task(id.into()));
+        self
+    }
+
+    /// Adds a nested composite as a child.
+    pub fn with_composite(mut self, composite: CompositeTask) -> Self {
+        self.children.push(CompositeChild::Composite(composite));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Direct children, in insertion order.
+    pub fn children(&self) -> &[CompositeChild] {
+        &self.children
+    }
+
+    /// Task IDs of every leaf, depth-first.
+    pub fn leaves(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        self.collect_leaves(&mut out);
+        out
+    }
+
+    fn collect_leaves<'a>(&'a self, out: &mut Vec<&'a str>) {
+        for child in &self.children {
+            match child {
+                CompositeChild::Task(id) => out.push(id),
+                CompositeChild::Composite(c) => c.collect_leaves(out),
+            }
+        }
+    }
+
+    /// Whether `id` is a leaf anywhere below this composite.
+    pub fn contains(&self, id: &str) -> bool {
+        self.children.iter().any(|child| match child {
+            CompositeChild::Task(t) => t == id,
+            CompositeChild::Composite(c) => c.contains(id),
+        })
+    }
+
+    /// Levels below this one; a composite of plain tasks has depth 1.
+    pub fn depth(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(|child| match child {
+                CompositeChild::Task(_) => 0,
+                CompositeChild::Composite(c) => c.depth(),
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Finds the composite named `name`, this one included.
+    pub fn find(&self, name: &str) -> Option<&CompositeTask> {
+        if self.name == name {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| match child {
+            CompositeChild::Task(_) => None,
+            CompositeChild::Composite(c) => c.find(name),
+        })
+    }
+
+    /// Leaves not placed in `schedule`, depth-first.
+    pub fn missing<U: Unit>(&self, schedule: &Schedule<U>) -> Vec<Id> {
+        self.leaves()
+            .into_iter()
+            .filter(|id| !schedule.contains_task(*id))
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// Whether every leaf is placed in `schedule`.
+    pub fn is_complete<U: Unit>(&self, schedule: &Schedule<U>) -> bool {
+        self.leaves().iter().all(|id| schedule.contains_task(*id))
+    }
+
+    /// Earliest start to latest end of the leaves in `schedule`.
+    ///
+    /// `None` unless every leaf is placed, or if the composite has no leaves.
+    pub fn envelope<U: Unit>(&self, schedule: &Schedule<U>) -> Option<Interval<U>> {
+        let mut envelope: Option<Interval<U>> = None;
+        for id in self.leaves() {
+            let iv = schedule.get_interval(id)?;
+            envelope = Some(match envelope {
+                None => iv,
+                Some(e) => Interval::new(
+                    if iv.start().value() < e.start().value() {
+                        iv.start()
+                    } else {
+                        e.start()
+                    },
+                    if iv.end().value() > e.end().value() {
+                        iv.end()
+                    } else {
+                        e.end()
+                    },
+                ),
+            });
+        }
+        envelope
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn programme() -> CompositeTask {
+        CompositeTask::new("programme")
+            .with_task("a")
+            .with_composite(
+                CompositeTask::new("block")
+                    .with_task("b")
+                    .with_composite(CompositeTask::new("inner").with_task("c")),
+            )
+            .with_task("d")
+    }
+
+    #[test]
+    fn leaves_are_depth_first() {
+        let p = programme();
+        assert_eq!(p.leaves(), vec!["a", "b", "c", "d"]);
+        assert_eq!(p.depth(), 3);
+        assert!(p.contains("c"));
+        assert!(!p.contains("block"));
+        assert_eq!(p.find("inner").unwrap().leaves(), vec!["c"]);
+        assert!(p.find("nope").is_none());
+    }
+
+    #[test]
+    fn envelope_spans_all_leaves() {
+        let p = programme();
+        let mut schedule = Schedule::<Second>::new();
+        schedule.add("b", iv(10.0, 20.0)).unwrap();
+        schedule.add("a", iv(30.0, 40.0)).unwrap();
+        schedule.add("c", iv(0.0, 5.0)).unwrap();
+        assert!(!p.is_complete(&schedule));
+        assert_eq!(p.missing(&schedule), vec!["d".to_string()]);
+        assert_eq!(p.envelope(&schedule), None);
+        assert_eq!(
+            p.find("block").unwrap().envelope(&schedule),
+            Some(iv(0.0, 20.0))
+        );
+
+        schedule.add("d", iv(50.0, 55.0)).unwrap();
+        assert!(p.is_complete(&schedule));
+        assert_eq!(p.envelope(&schedule), Some(iv(0.0, 55.0)));
+    }
+
+    #[test]
+    fn empty_composite_has_no_envelope() {
+        let schedule = Schedule::<Second>::new();
+        let empty = CompositeTask::new("empty");
+        assert_eq!(empty.envelope(&schedule), None);
+        assert!(empty.is_complete(&schedule));
+        assert_eq!(empty.depth(), 1);
+    }
+}
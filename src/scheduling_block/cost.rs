@@ -0,0 +1,386 @@
+//! Optional execution-cost extension for priced tasks.
+//!
+//! Cloud jobs, energy-market loads and rented instruments are scheduled
+//! against prices, not just time. Tasks that implement [`CostedTask`] report
+//! what a placement would cost; the EST scheduler can then keep the total
+//! under a budget, and [`CostConstraint`](crate::constraints::soft::static_::CostConstraint)
+//! turns the same prices into a soft preference for cheap slots.
+//!
+//! [`CostProfile`] is a ready-made cost function: a fixed charge plus a rate
+//! per axis unit, where the rate may change inside given windows — absolute,
+//! or repeating with a period (time-of-day tariffs). The rate in force when
+//! the task **starts** applies to its whole run, the usual rule for jobs
+//! priced at submission; [`CostProfile::integrated_cost`] instead bills every
+//! part of the run at the rate then in force.
+//!
+//! [`PriceCurve`] is a step function of price over the axis, such as hourly
+//! energy-market prices. [`PriceCurve::integrate`] gives the exact cost of a
+//! half-open interval, splitting partial bins at their boundaries.
+//!
+//! # Example
+//!
+//! ```
+//! use qtty::{Hour, Quantity};
+//! use virolai::scheduling_block::CostProfile;
+//! use virolai::solution_space::Interval;
+//!
+//! // 10/h, 25/h at peak (08:00–20:00) every day, plus 5 to start a job.
+//! let price = CostProfile::<Hour>::new(10.0)
+//!     .with_fixed(5.0)
+//!     .with_period(Quantity::new(24.0))
+//!     .with_rate(Interval::from_f64(8.0, 20.0), 25.0);
+//!
+//! assert_eq!(price.cost(Interval::from_f64(2.0, 4.0)), 25.0);
+//! assert_eq!(price.cost(Interval::from_f64(33.0, 35.0)), 55.0);
+//! ```
+
+use qtty::{Quantity, Unit};
+
+use super::Task;
+use crate::solution_space::Interval;
+
+/// A task whose execution has a cost that may depend on when it runs.
+pub trait CostedTask<U: Unit>: Task<U> {
+    /// Cost of running this task over `placement`.
+    fn cost(&self, placement: Interval<U>) -> f64;
+}
+
+/// Fixed charge plus a possibly time-dependent rate per axis unit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostProfile<U: Unit> {
+    fixed: f64,
+    rate: f64,
+    period: Option<Quantity<U>>,
+    /// Rate overrides; the first window containing the phase wins.
+    rates: Vec<(Interval<U>, f64)>,
+}
+
+impl<U: Unit> CostProfile<U> {
+    /// Creates a profile charging `rate` per axis unit at all times.
+    pub fn new(rate: f64) -> Self {
+        Self {
+            fixed: 0.0,
+            rate,
+            period: None,
+            rates: Vec::new(),
+        }
+    }
+
+    /// Adds a charge paid once per placement.
+    pub fn with_fixed(mut self, cost: f64) -> Self {
+        self.fixed = cost;
+        self
+    }
+
+    /// Makes rate windows repeat with this period; windows are then phases
+    /// in `[0, period)`.
+    pub fn with_period(mut self, period: Quantity<U>) -> Self {
+        self.period = (period.value() > 0.0).then_some(period);
+        self
+    }
+
+    /// Charges `rate` instead of the base rate inside `window`.
+    pub fn with_rate(mut self, window: Interval<U>, rate: f64) -> Self {
+        self.rates.push((window, rate));
+        self
+    }
+
+    /// Rate in force at `t`.
+    pub fn rate_at(&self, t: Quantity<U>) -> f64 {
+        let phase = match self.period {
+            Some(p) => Quantity::new(t.value().rem_euclid(p.value())),
+            None => t,
+        };
+        self.rates
+            .iter()
+            .find(|(window, _)| window.contains(phase))
+            .map_or(self.rate, |&(_, rate)| rate)
+    }
+
+    /// Lowest rate the profile can charge.
+    pub fn min_rate(&self) -> f64 {
+        self.rates.iter().map(|&(_, r)| r).fold(self.rate, f64::min)
+    }
+
+    /// Highest rate the profile can charge.
+    pub fn max_rate(&self) -> f64 {
+        self.rates.iter().map(|&(_, r)| r).fold(self.rate, f64::max)
+    }
+
+    /// Charge paid once per placement.
+    pub fn fixed(&self) -> f64 {
+        self.fixed
+    }
+
+    /// Cost of a placement: the fixed charge plus its duration at the rate
+    /// in force at its start.
+    pub fn cost(&self, placement: Interval<U>) -> f64 {
+        self.fixed + self.rate_at(placement.start()) * placement.duration().value()
+    }
+
+    /// Cost of a placement billed as it runs: the fixed charge plus the
+    /// integral of the rate over the placement.
+    pub fn integrated_cost(&self, placement: Interval<U>) -> f64 {
+        let mut cost = self.fixed;
+        let mut from = placement.start();
+        for to in self
+            .rate_changes(placement)
+            .into_iter()
+            .chain(std::iter::once(placement.end()))
+        {
+            cost += self.rate_at(from) * (to - from).value();
+            from = to;
+        }
+        cost
+    }
+
+    /// Points inside `window` where the rate may change.
+    pub fn rate_changes(&self, window: Interval<U>) -> Vec<Quantity<U>> {
+        let edges = self
+            .rates
+            .iter()
+            .flat_map(|(w, _)| [w.start().value(), w.end().value()]);
+        let mut points: Vec<Quantity<U>> = match self.period {
+            None => edges.map(Quantity::new).collect(),
+            Some(p) => {
+                let p = p.value();
+                let first = (window.start().value() / p).floor() as i64;
+                let last = (window.end().value() / p).floor() as i64;
+                edges
+                    .flat_map(|e| (first..=last).map(move |k| Quantity::new(k as f64 * p + e)))
+                    .collect()
+            }
+        };
+        points.retain(|&t| window.contains(t));
+        points.sort_by(|a, b| a.value().total_cmp(&b.value()));
+        points.dedup();
+        points
+    }
+}
+
+/// Piecewise-constant price over the axis.
+///
+/// Each step `(t, price)` sets the price from `t` until the next step; the
+/// last step lasts forever. Before the first step the price is
+/// [`before`](Self::with_before) (0 by default).
+///
+/// # Example
+///
+/// ```
+/// use qtty::{Hour, Quantity};
+/// use virolai::scheduling_block::PriceCurve;
+/// use virolai::solution_space::Interval;
+///
+/// // Hourly prices from 00:00; nothing is sold after 03:00.
+/// let prices = PriceCurve::<Hour>::from_bins(Quantity::new(0.0), Quantity::new(1.0), [40.0, 25.0, 60.0]);
+///
+/// // 00:30–02:15 → 0.5 h × 40 + 1 h × 25 + 0.25 h × 60
+/// assert_eq!(prices.integrate(Interval::from_f64(0.5, 2.25)), 60.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceCurve<U: Unit> {
+    /// Sorted by time; ties keep the last price given.
+    steps: Vec<(Quantity<U>, f64)>,
+    before: f64,
+}
+
+impl<U: Unit> PriceCurve<U> {
+    /// Creates a curve from `(time, price)` steps, in any order.
+    pub fn new(steps: impl IntoIterator<Item = (Quantity<U>, f64)>) -> Self {
+        let mut steps: Vec<_> = steps.into_iter().collect();
+        steps.sort_by(|a, b| a.0.value().total_cmp(&b.0.value()));
+        steps.dedup_by(|later, earlier| {
+            let same = later.0 == earlier.0;
+            if same {
+                earlier.1 = later.1;
+            }
+            same
+        });
+        Self { steps, before: 0.0 }
+    }
+
+    /// Creates a curve from consecutive bins of equal `width` starting at
+    /// `origin`; the price is 0 outside the bins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not strictly positive.
+    pub fn from_bins(
+        origin: Quantity<U>,
+        width: Quantity<U>,
+        prices: impl IntoIterator<Item = f64>,
+    ) -> Self {
+        assert!(width.value() > 0.0, "price bin width must be positive");
+        let mut steps: Vec<_> = prices
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| (Quantity::new(origin.value() + i as f64 * width.value()), p))
+            .collect();
+        let end = origin.value() + steps.len() as f64 * width.value();
+        steps.push((Quantity::new(end), 0.0));
+        Self::new(steps)
+    }
+
+    /// Sets the price before the first step.
+    pub fn with_before(mut self, price: f64) -> Self {
+        self.before = price;
+        self
+    }
+
+    /// Price in force at `t`.
+    pub fn price_at(&self, t: Quantity<U>) -> f64 {
+        let i = self.steps.partition_point(|(s, _)| s.value() <= t.value());
+        if i == 0 {
+            self.before
+        } else {
+            self.steps[i - 1].1
+        }
+    }
+
+    /// Exact integral of the price over `interval`.
+    ///
+    /// A step at `interval.start()` applies; one at `interval.end()` does
+    /// not. An empty interval costs nothing.
+    pub fn integrate(&self, interval: Interval<U>) -> f64 {
+        let mut total = 0.0;
+        let mut from = interval.start();
+        for to in self
+            .breakpoints(interval)
+            .into_iter()
+            .chain(std::iter::once(interval.end()))
+        {
+            total += self.price_at(from) * (to - from).value();
+            from = to;
+        }
+        total
+    }
+
+    /// Time-weighted mean price over `interval`; the price at its start if
+    /// it is empty.
+    pub fn mean(&self, interval: Interval<U>) -> f64 {
+        let d = interval.duration().value();
+        if d > 0.0 {
+            self.integrate(interval) / d
+        } else {
+            self.price_at(interval.start())
+        }
+    }
+
+    /// Lowest price anywhere on the axis.
+    pub fn min_price(&self) -> f64 {
+        self.steps
+            .iter()
+            .map(|&(_, p)| p)
+            .fold(self.before, f64::min)
+    }
+
+    /// Highest price anywhere on the axis.
+    pub fn max_price(&self) -> f64 {
+        self.steps
+            .iter()
+            .map(|&(_, p)| p)
+            .fold(self.before, f64::max)
+    }
+
+    /// Step times strictly inside `window`, in order.
+    pub fn breakpoints(&self, window: Interval<U>) -> Vec<Quantity<U>> {
+        self.steps
+            .iter()
+            .map(|&(t, _)| t)
+            .filter(|t| window.start() < *t && *t < window.end())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q};
+    use qtty::Second;
+
+    fn tariff() -> CostProfile<Second> {
+        CostProfile::new(1.0)
+            .with_period(q(100.0))
+            .with_rate(iv(20.0, 40.0), 3.0)
+    }
+
+    #[test]
+    fn rate_follows_periodic_windows() {
+        let p = tariff();
+        assert_eq!(p.rate_at(q(10.0)), 1.0);
+        assert_eq!(p.rate_at(q(20.0)), 3.0);
+        assert_eq!(p.rate_at(q(40.0)), 1.0);
+        assert_eq!(p.rate_at(q(125.0)), 3.0);
+        assert_eq!(p.rate_at(q(-75.0)), 3.0);
+        assert_eq!((p.min_rate(), p.max_rate()), (1.0, 3.0));
+    }
+
+    #[test]
+    fn cost_uses_start_rate() {
+        let p = tariff().with_fixed(2.0);
+        assert_eq!(p.cost(iv(0.0, 10.0)), 12.0);
+        assert_eq!(p.cost(iv(30.0, 50.0)), 62.0);
+        assert_eq!(p.cost(iv(5.0, 5.0)), 2.0);
+    }
+
+    #[test]
+    fn rate_changes_repeat_inside_window() {
+        let p = tariff();
+        let points: Vec<f64> = p
+            .rate_changes(iv(30.0, 230.0))
+            .iter()
+            .map(|t| t.value())
+            .collect();
+        assert_eq!(points, vec![40.0, 120.0, 140.0, 220.0]);
+
+        let absolute = CostProfile::<Second>::new(1.0).with_rate(iv(20.0, 40.0), 3.0);
+        assert_eq!(absolute.rate_changes(iv(0.0, 30.0)), vec![q(20.0)]);
+    }
+
+    #[test]
+    fn integrated_cost_splits_at_rate_changes() {
+        let p = tariff().with_fixed(2.0);
+        // 10 s at 1, 20 s at 3, 10 s at 1
+        assert_eq!(p.integrated_cost(iv(10.0, 50.0)), 2.0 + 10.0 + 60.0 + 10.0);
+        // Wraps into the next period.
+        assert_eq!(p.integrated_cost(iv(90.0, 130.0)), 2.0 + 30.0 + 30.0);
+        assert_eq!(p.integrated_cost(iv(25.0, 25.0)), 2.0);
+    }
+
+    // ── PriceCurve ────────────────────────────────────────────────────
+
+    fn hourly() -> PriceCurve<Second> {
+        PriceCurve::from_bins(q(0.0), q(10.0), [4.0, 1.0, 6.0])
+    }
+
+    #[test]
+    fn price_steps_are_half_open() {
+        let c = hourly();
+        assert_eq!(c.price_at(q(-1.0)), 0.0);
+        assert_eq!(c.price_at(q(0.0)), 4.0);
+        assert_eq!(c.price_at(q(10.0)), 1.0);
+        assert_eq!(c.price_at(q(29.9)), 6.0);
+        assert_eq!(c.price_at(q(30.0)), 0.0);
+        assert_eq!(c.clone().with_before(9.0).price_at(q(-1.0)), 9.0);
+        assert_eq!((c.min_price(), c.max_price()), (0.0, 6.0));
+    }
+
+    #[test]
+    fn integrate_handles_partial_bins_and_edges() {
+        let c = hourly();
+        assert_eq!(c.integrate(iv(0.0, 10.0)), 40.0);
+        assert_eq!(c.integrate(iv(5.0, 25.0)), 20.0 + 10.0 + 30.0);
+        assert_eq!(c.integrate(iv(-10.0, 5.0)), 20.0);
+        assert_eq!(c.integrate(iv(25.0, 40.0)), 30.0);
+        assert_eq!(c.integrate(iv(12.0, 12.0)), 0.0);
+        assert_eq!(c.mean(iv(5.0, 25.0)), 3.0);
+        assert_eq!(c.mean(iv(12.0, 12.0)), 1.0);
+    }
+
+    #[test]
+    fn later_duplicate_step_wins() {
+        let c = PriceCurve::<Second>::new([(q(10.0), 2.0), (q(0.0), 1.0), (q(10.0), 5.0)]);
+        assert_eq!(c.price_at(q(10.0)), 5.0);
+        assert_eq!(c.breakpoints(iv(0.0, 20.0)), vec![q(10.0)]);
+    }
+}
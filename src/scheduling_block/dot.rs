@@ -0,0 +1,170 @@
+//! Graphviz DOT export of a scheduling block.
+//!
+//! [`SchedulingBlock::to_dot`] renders every task as a node labelled with its
+//! name, size and priority, and every dependency as an edge labelled with its
+//! edge data — for [`DynConstraintKind`](crate::constraints::DynConstraintKind)
+//! edges, the kind. Pipe the output through `dot -Tsvg` to inspect large
+//! dependency structures before scheduling.
+//!
+//! ```
+//! use virolai::constraints::DynConstraintKind;
+//! use virolai::scheduling_block::{SchedulingBlock, Task};
+//! use qtty::{Quantity, Second};
+//!
+//! #[derive(Debug, Clone)]
+//! struct Job(&'static str);
+//!
+//! impl Task<Second> for Job {
+//!     type SizeUnit = Second;
+//!     type ConstraintLeaf = virolai::constraints::IntervalConstraint<Second>;
+//!     fn name(&self) -> &str { self.0 }
+//!     fn size(&self) -> Quantity<Second> { Quantity::new(60.0) }
+//! }
+//!
+//! let mut block = SchedulingBlock::<Job, Second, DynConstraintKind>::new();
+//! let a = block.add_task_with_id(Job("flat"), Some("a".into())).unwrap();
+//! let b = block.add_task_with_id(Job("science"), Some("b".into())).unwrap();
+//! let (na, nb) = (block.node_of(&a).unwrap(), block.node_of(&b).unwrap());
+//! block.add_dependency(na, nb, DynConstraintKind::Consecutive).unwrap();
+//!
+//! let dot = block.to_dot();
+//! assert!(dot.starts_with("digraph"));
+//! assert!(dot.contains("\"a\" -> \"b\" [label=\"Consecutive\"];"));
+//! ```
+
+use std::fmt::{Display, Write};
+
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+use petgraph::EdgeType;
+use qtty::{Quantity, Unit};
+
+use super::{SchedulingBlock, Task};
+
+impl<T, U, D, E> SchedulingBlock<T, U, D, E>
+where
+    T: Task<U>,
+    U: Unit,
+    D: Display,
+    E: EdgeType,
+    Quantity<U>: Display,
+{
+    /// Renders the block as a Graphviz `digraph` (`graph` when undirected).
+    ///
+    /// Nodes are keyed by task ID and listed in insertion order, followed by
+    /// the edges.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_with(|d| d.to_string())
+    }
+}
+
+impl<T, U, D, E> SchedulingBlock<T, U, D, E>
+where
+    T: Task<U>,
+    U: Unit,
+    E: EdgeType,
+    Quantity<U>: Display,
+{
+    /// [`to_dot`](Self::to_dot) with edge labels from `label`; an empty
+    /// label leaves the edge unlabelled.
+    pub fn to_dot_with(&self, label: impl Fn(&D) -> String) -> String {
+        let (kind, arrow) = if E::is_directed() {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        let graph = self.graph();
+        let mut out = format!("{kind} scheduling_block {{\n  node [shape=box];\n");
+
+        let mut nodes: Vec<_> = graph.node_indices().collect();
+        nodes.sort();
+        for node in nodes {
+            let (Some(id), Some(task)) = (self.id_of(node), self.get_task(node)) else {
+                continue;
+            };
+            let text = format!(
+                "{}\nsize: {}\npriority: {}",
+                task.name(),
+                task.size_on_axis(),
+                task.priority()
+            );
+            let _ = writeln!(out, "  {} [label={}];", quote(id), quote(&text));
+        }
+
+        for edge in graph.edge_references() {
+            let (Some(from), Some(to)) = (self.id_of(edge.source()), self.id_of(edge.target()))
+            else {
+                continue;
+            };
+            let text = label(edge.weight());
+            let _ = write!(out, "  {} {arrow} {}", quote(from), quote(to));
+            if !text.is_empty() {
+                let _ = write!(out, " [label={}]", quote(&text));
+            }
+            out.push_str(";\n");
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Quotes a DOT ID, escaping `"` and `\`, and newlines as `\n`.
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            _ => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::DynConstraintKind;
+    use crate::test_utils::TestTask;
+    use qtty::Second;
+
+    #[test]
+    fn renders_nodes_and_labelled_edges() {
+        let mut block: SchedulingBlock<TestTask, Second, DynConstraintKind> =
+            SchedulingBlock::new();
+        for id in ["a", "b"] {
+            block
+                .add_task_with_id(TestTask::new(id, 10.0).with_priority(3), Some(id.into()))
+                .unwrap();
+        }
+        let (a, b) = (block.node_of("a").unwrap(), block.node_of("b").unwrap());
+        block
+            .add_dependency(a, b, DynConstraintKind::start_to_start(5.0))
+            .unwrap();
+
+        let dot = block.to_dot();
+        assert!(dot.starts_with("digraph scheduling_block {\n"));
+        assert!(dot.contains("\"a\" [label=\"a\\nsize: 10"));
+        assert!(dot.contains("\\npriority: 3\"];"));
+        assert!(dot.contains("  \"a\" -> \"b\" [label=\"StartToStart(lag=5)\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn unit_edges_with_empty_labels() {
+        let mut block: SchedulingBlock<TestTask> = SchedulingBlock::new();
+        for id in ["x\"y", "z"] {
+            block
+                .add_task_with_id(TestTask::new(id, 1.0), Some(id.into()))
+                .unwrap();
+        }
+        let (a, b) = (block.node_of("x\"y").unwrap(), block.node_of("z").unwrap());
+        block.add_dependency(a, b, ()).unwrap();
+
+        let dot = block.to_dot_with(|_| String::new());
+        assert!(dot.contains("  \"x\\\"y\" -> \"z\";\n"));
+    }
+}
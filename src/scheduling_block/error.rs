@@ -17,6 +17,21 @@ pub enum SchedulingError {
 
     #[error("Task ID already exists: {0}")]
     DuplicateId(String),
+
+    #[error("Unknown task ID: {0}")]
+    UnknownId(String),
+
+    #[error("Dependency cycle: {}", format_cycle(.0))]
+    Cycle(Vec<(String, String)>),
+}
+
+/// Renders `[(a, b), (b, a)]` as `a -> b -> a`.
+fn format_cycle(edges: &[(String, String)]) -> String {
+    let mut out: Vec<&str> = edges.iter().map(|(from, _)| from.as_str()).collect();
+    if let Some((_, to)) = edges.last() {
+        out.push(to);
+    }
+    out.join(" -> ")
 }
 
 #[cfg(test)]
@@ -63,6 +78,16 @@ mod tests {
         assert_eq!(e.to_string(), "Task ID already exists: my-task");
     }
 
+    #[test]
+    fn cycle_display() {
+        let e = SchedulingError::Cycle(vec![
+            ("a".into(), "b".into()),
+            ("b".into(), "c".into()),
+            ("c".into(), "a".into()),
+        ]);
+        assert_eq!(e.to_string(), "Dependency cycle: a -> b -> c -> a");
+    }
+
     #[test]
     fn error_equality() {
         assert_eq!(
@@ -0,0 +1,140 @@
+//! Groups of tasks that may never run at the same time.
+//!
+//! A single schedule already keeps its tasks apart, but tasks on different
+//! resources may overlap — unless they share something the resources do
+//! not model, such as a detector or a crew. [`SchedulingBlock::add_exclusion`](super::SchedulingBlock::add_exclusion)
+//! records such a group on the block and adds a
+//! [`Disjoint`](crate::constraints::DynConstraintKind::Disjoint) edge between
+//! every pair of members, from the member earlier in topological order to the
+//! later one.
+//!
+//! The edges alone only constrain the later member of each pair. The EST
+//! scheduler also evaluates the group itself, so once a member is placed the
+//! others avoid it on every resource, whichever comes first in topological
+//! order. Plans built by other means are checked by
+//! [`ExclusionGroup::conflicts`] and [`validate`](crate::schedule::validate()).
+
+use std::fmt;
+
+use crate::schedule::Schedule;
+use crate::solution_space::Interval;
+use crate::Id;
+use qtty::Unit;
+
+/// A named set of tasks no two of which may overlap in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExclusionGroup {
+    name: String,
+    members: Vec<Id>,
+}
+
+impl ExclusionGroup {
+    pub fn new(name: impl Into<String>, members: impl IntoIterator<Item = impl Into<Id>>) -> Self {
+        Self {
+            name: name.into(),
+            members: members.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Members in topological order.
+    pub fn members(&self) -> &[Id] {
+        &self.members
+    }
+
+    pub fn contains(&self, task_id: &str) -> bool {
+        self.members.iter().any(|m| m == task_id)
+    }
+
+    pub(crate) fn set_members(&mut self, members: Vec<Id>) {
+        self.members = members;
+    }
+
+    pub(crate) fn remove_member(&mut self, task_id: &str) {
+        self.members.retain(|m| m != task_id);
+    }
+
+    /// Pairs of distinct members that overlap across `schedules`, each pair
+    /// once and ordered by start.
+    pub fn conflicts<'s, U: Unit + 's>(
+        &self,
+        schedules: impl IntoIterator<Item = &'s Schedule<U>>,
+    ) -> Vec<(Id, Id)> {
+        let mut placed: Vec<(Id, Interval<U>)> = schedules
+            .into_iter()
+            .flat_map(|schedule| schedule.iter())
+            .filter(|(id, placement)| !placement.is_empty() && self.contains(id))
+            .collect();
+        placed.sort_by(|a, b| a.1.start().value().total_cmp(&b.1.start().value()));
+
+        let mut conflicts = Vec::new();
+        for (i, (first, a)) in placed.iter().enumerate() {
+            for (second, b) in &placed[i + 1..] {
+                if b.start() >= a.end() {
+                    break;
+                }
+                let pair = (first.clone(), second.clone());
+                if first != second && !conflicts.contains(&pair) {
+                    conflicts.push(pair);
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Whether no two members overlap across `schedules`.
+    pub fn is_satisfied<'s, U: Unit + 's>(
+        &self,
+        schedules: impl IntoIterator<Item = &'s Schedule<U>>,
+    ) -> bool {
+        self.conflicts(schedules).is_empty()
+    }
+}
+
+impl fmt::Display for ExclusionGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: none of [{}] at once",
+            self.name,
+            self.members.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn group() -> ExclusionGroup {
+        ExclusionGroup::new("detector", ["a", "b", "c"])
+    }
+
+    #[test]
+    fn conflicts_span_resources() {
+        let mut r1 = Schedule::<Second>::new();
+        r1.add("a", iv(0.0, 10.0)).unwrap();
+        r1.add("b", iv(10.0, 20.0)).unwrap();
+        let mut r2 = Schedule::<Second>::new();
+        r2.add("c", iv(5.0, 12.0)).unwrap();
+        r2.add("other", iv(0.0, 5.0)).unwrap();
+        r2.add("a", iv(15.0, 15.0)).unwrap();
+
+        assert!(group().is_satisfied([&r1]));
+        assert_eq!(
+            group().conflicts([&r1, &r2]),
+            vec![("a".into(), "c".into()), ("c".into(), "b".into())]
+        );
+        assert!(!group().is_satisfied([&r1, &r2]));
+    }
+
+    #[test]
+    fn display_lists_members() {
+        assert_eq!(group().to_string(), "detector: none of [a, b, c] at once");
+    }
+}
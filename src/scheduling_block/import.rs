@@ -0,0 +1,406 @@
+//! JSON import of scheduling blocks.
+//!
+//! Problems authored outside Rust — by a proposal tool, a notebook, another
+//! service — are described as a [`BlockDocument`] and turned into a
+//! [`SchedulingBlock`] with [`SchedulingBlock::from_json`] or
+//! [`SchedulingBlock::from_document`]. [`TaskSpec`] is a ready-made task type
+//! for such documents; any `T: Deserialize` works as well.
+//!
+//! # Format (version 1)
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "tasks": [
+//!     {
+//!       "id": "calib",
+//!       "name": "Flat field",
+//!       "size": 600.0,
+//!       "priority": 2
+//!     },
+//!     {
+//!       "id": "obs",
+//!       "name": "Crab Nebula",
+//!       "size": 1800.0,
+//!       "constraints": {
+//!         "type": "union",
+//!         "children": [
+//!           { "start": 0.0, "end": 3600.0 },
+//!           { "start": 7200.0, "end": 10800.0 }
+//!         ]
+//!       }
+//!     }
+//!   ],
+//!   "edges": [
+//!     { "from": "calib", "to": "obs", "kind": "consecutive" }
+//!   ]
+//! }
+//! ```
+//!
+//! - Each task entry is `id` plus the fields of the task type. For
+//!   [`TaskSpec`], `size` is in the axis unit, `priority` defaults to `0` and
+//!   `constraints` is an optional [`ConstraintExpr`] tree whose leaves are
+//!   `{"start", "end"}` windows.
+//! - `edges` are added in order with [`SchedulingBlock::add_dependency`];
+//!   `kind` is the edge data (for [`DynConstraintKind`](crate::constraints::DynConstraintKind),
+//!   e.g. `"dependence"` or `{"start_to_start": {"lag": 60.0}}`). `edges`
+//!   may be omitted.
+//!
+//! A [`ProblemDocument`] is a block document with a `"horizon"` object
+//! (`{"start", "end"}`) next to `"version"`, for tools that take a whole
+//! problem in one file.
+//!
+//! Fields are only ever added within a version; any rename, removal or change
+//! of meaning bumps [`BLOCK_FORMAT_VERSION`].
+
+use petgraph::EdgeType;
+use qtty::{Quantity, Unit};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{SchedulingBlock, SchedulingError, Task};
+use crate::constraints::{Constraint, ConstraintError, ConstraintExpr, IntervalConstraint};
+use crate::solution_space::Interval;
+use crate::Id;
+
+/// Current version of the block document format.
+pub const BLOCK_FORMAT_VERSION: u32 = 1;
+
+/// Why a document could not be turned into a block.
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("Malformed document: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unsupported document version {0} (expected {BLOCK_FORMAT_VERSION})")]
+    UnsupportedVersion(u32),
+
+    #[error("Edge {from} -> {to} references unknown task {missing}")]
+    UnknownTask { from: Id, to: Id, missing: Id },
+
+    #[error(transparent)]
+    Scheduling(#[from] SchedulingError),
+
+    #[error(transparent)]
+    Bound(#[from] ConstraintError),
+}
+
+/// Versioned description of a scheduling block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BlockDocument<T, D> {
+    pub version: u32,
+    pub tasks: Vec<TaskEntry<T>>,
+    #[serde(default = "Vec::<EdgeEntry<D>>::new")]
+    pub edges: Vec<EdgeEntry<D>>,
+}
+
+/// A task and the ID it is registered under.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TaskEntry<T> {
+    pub id: Id,
+    #[serde(flatten)]
+    pub task: T,
+}
+
+/// A typed edge between two task IDs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EdgeEntry<D> {
+    pub from: Id,
+    pub to: Id,
+    pub kind: D,
+}
+
+/// A [`BlockDocument`] and the horizon to schedule it in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(bound(
+    serialize = "T: Serialize, D: Serialize",
+    deserialize = "T: Deserialize<'de>, D: Deserialize<'de>"
+))]
+#[cfg_attr(
+    feature = "schema",
+    schemars(bound = "T: schemars::JsonSchema, D: schemars::JsonSchema")
+)]
+pub struct ProblemDocument<U: Unit, T, D> {
+    pub horizon: Interval<U>,
+    #[serde(flatten)]
+    pub block: BlockDocument<T, D>,
+}
+
+impl<U: Unit, T, D> ProblemDocument<U, T, D> {
+    /// Parses a problem from JSON.
+    pub fn from_json(json: &str) -> Result<Self, ImportError>
+    where
+        T: DeserializeOwned,
+        D: DeserializeOwned,
+    {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Builds the block; [`horizon`](Self::horizon) is left to the caller.
+    pub fn into_block<E: EdgeType>(self) -> Result<SchedulingBlock<T, U, D, E>, ImportError>
+    where
+        T: Task<U>,
+    {
+        SchedulingBlock::from_document(self.block)
+    }
+}
+
+/// Plain task for imported problems: a name, a size in the axis unit, a
+/// priority and an optional constraint tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(bound(serialize = "C: Serialize", deserialize = "C: Deserialize<'de>"))]
+#[cfg_attr(feature = "schema", schemars(bound = "C: schemars::JsonSchema"))]
+pub struct TaskSpec<U: Unit, C = IntervalConstraint<U>> {
+    pub name: String,
+    #[cfg_attr(feature = "schema", schemars(with = "f64"))]
+    pub size: Quantity<U>,
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<ConstraintExpr<C>>,
+}
+
+impl<U: Unit, C> TaskSpec<U, C> {
+    pub fn new(name: impl Into<String>, size: Quantity<U>) -> Self {
+        Self {
+            name: name.into(),
+            size,
+            priority: 0,
+            constraints: None,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_constraints(mut self, constraints: ConstraintExpr<C>) -> Self {
+        self.constraints = Some(constraints);
+        self
+    }
+}
+
+impl<U, C> Task<U> for TaskSpec<U, C>
+where
+    U: Unit + Send + Sync,
+    C: Constraint<U> + 'static,
+{
+    type SizeUnit = U;
+    type ConstraintLeaf = C;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn size(&self) -> Quantity<U> {
+        self.size
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn constraints(&self) -> Option<&ConstraintExpr<C>> {
+        self.constraints.as_ref()
+    }
+}
+
+impl<T, U, D, E> SchedulingBlock<T, U, D, E>
+where
+    T: Task<U>,
+    U: Unit,
+    E: EdgeType,
+{
+    /// Builds a block from a JSON [`BlockDocument`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use virolai::constraints::DynConstraintKind;
+    /// use virolai::scheduling_block::import::TaskSpec;
+    /// use virolai::scheduling_block::SchedulingBlock;
+    /// use qtty::Second;
+    ///
+    /// let json = r#"{
+    ///     "version": 1,
+    ///     "tasks": [
+    ///         { "id": "a", "name": "calib", "size": 60.0 },
+    ///         { "id": "b", "name": "science", "size": 600.0,
+    ///           "constraints": { "start": 0.0, "end": 3600.0 } }
+    ///     ],
+    ///     "edges": [ { "from": "a", "to": "b", "kind": "consecutive" } ]
+    /// }"#;
+    ///
+    /// let block: SchedulingBlock<TaskSpec<Second>, Second, DynConstraintKind> =
+    ///     SchedulingBlock::from_json(json).unwrap();
+    /// assert_eq!(block.task_count(), 2);
+    /// assert_eq!(block.task_by_id("b").unwrap().name, "science");
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, ImportError>
+    where
+        T: DeserializeOwned,
+        D: DeserializeOwned,
+    {
+        Self::from_document(serde_json::from_str(json)?)
+    }
+
+    /// Builds a block from `document`: tasks first, in order, then edges.
+    pub fn from_document(document: BlockDocument<T, D>) -> Result<Self, ImportError> {
+        if document.version != BLOCK_FORMAT_VERSION {
+            return Err(ImportError::UnsupportedVersion(document.version));
+        }
+        let mut block = Self::new();
+        for entry in document.tasks {
+            block.add_task_with_id(entry.task, Some(entry.id))?;
+        }
+        for edge in document.edges {
+            let node = |id: &Id| {
+                block.node_of(id).ok_or_else(|| ImportError::UnknownTask {
+                    from: edge.from.clone(),
+                    to: edge.to.clone(),
+                    missing: id.clone(),
+                })
+            };
+            let (from, to) = (node(&edge.from)?, node(&edge.to)?);
+            block.add_dependency(from, to, edge.kind)?;
+        }
+        Ok(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::DynConstraintKind;
+    use crate::solution_space::Interval;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    type Block = SchedulingBlock<TaskSpec<Second>, Second, DynConstraintKind>;
+
+    fn document(edges: &str) -> String {
+        format!(
+            r#"{{
+                "version": 1,
+                "tasks": [
+                    {{ "id": "a", "name": "calib", "size": 60.0, "priority": 3 }},
+                    {{ "id": "b", "name": "obs", "size": 600.0,
+                       "constraints": {{
+                           "type": "intersection",
+                           "children": [
+                               {{ "start": 0.0, "end": 1000.0 }},
+                               {{ "type": "not", "child": {{ "start": 100.0, "end": 200.0 }} }}
+                           ]
+                       }} }}
+                ],
+                "edges": {edges}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn loads_tasks_edges_and_constraint_trees() {
+        let json = document(
+            r#"[ { "from": "a", "to": "b", "kind": { "start_to_start": { "lag": 5.0 } } } ]"#,
+        );
+        let block = Block::from_json(&json).unwrap();
+
+        let a = block.task_by_id("a").unwrap();
+        assert_eq!((a.name(), a.priority()), ("calib", 3));
+        let b = block.task_by_id("b").unwrap();
+        assert_eq!(b.size().value(), 600.0);
+        let windows = b.constraints().unwrap().compute_intervals(iv(0.0, 2000.0));
+        let windows: Vec<Interval<Second>> = windows.iter().copied().collect();
+        assert_eq!(windows, vec![iv(0.0, 100.0), iv(200.0, 1000.0)]);
+
+        let (na, nb) = (block.node_of("a").unwrap(), block.node_of("b").unwrap());
+        let edge = block.graph().find_edge(na, nb).unwrap();
+        assert_eq!(block.graph()[edge], DynConstraintKind::start_to_start(5.0));
+    }
+
+    #[test]
+    fn edges_are_optional() {
+        let json = r#"{ "version": 1, "tasks": [ { "id": "x", "name": "x", "size": 1.0 } ] }"#;
+        let block = Block::from_json(json).unwrap();
+        assert_eq!(block.task_count(), 1);
+        assert_eq!(block.graph().edge_count(), 0);
+    }
+
+    #[test]
+    fn rejects_bad_documents() {
+        let unknown = document(r#"[ { "from": "a", "to": "zz", "kind": "dependence" } ]"#);
+        assert!(matches!(
+            Block::from_json(&unknown),
+            Err(ImportError::UnknownTask { missing, .. }) if missing == "zz"
+        ));
+
+        let cyclic = document(
+            r#"[ { "from": "a", "to": "b", "kind": "dependence" },
+                 { "from": "b", "to": "a", "kind": "dependence" } ]"#,
+        );
+        assert!(matches!(
+            Block::from_json(&cyclic),
+            Err(ImportError::Scheduling(SchedulingError::CycleDetected))
+        ));
+
+        let future = document("[]").replacen("\"version\": 1", "\"version\": 2", 1);
+        assert!(matches!(
+            Block::from_json(&future),
+            Err(ImportError::UnsupportedVersion(2))
+        ));
+
+        assert!(matches!(
+            Block::from_json("{ \"version\": 1 }"),
+            Err(ImportError::Json(_))
+        ));
+    }
+
+    #[test]
+    fn document_round_trips() {
+        let doc: BlockDocument<TaskSpec<Second>, DynConstraintKind> = BlockDocument {
+            version: BLOCK_FORMAT_VERSION,
+            tasks: vec![TaskEntry {
+                id: "a".into(),
+                task: TaskSpec::new("calib", Quantity::new(60.0))
+                    .with_priority(1)
+                    .with_constraints(ConstraintExpr::leaf(IntervalConstraint::new(iv(0.0, 10.0)))),
+            }],
+            edges: Vec::new(),
+        };
+        let json = serde_json::to_string(&doc).unwrap();
+        let block = Block::from_json(&json).unwrap();
+        let a = block.task_by_id("a").unwrap();
+        assert_eq!((a.name.as_str(), a.priority), ("calib", 1));
+        assert!(a.constraints.is_some());
+    }
+
+    #[test]
+    fn problem_carries_the_horizon() {
+        let json = document("[]").replacen(
+            "\"version\": 1,",
+            "\"version\": 1, \"horizon\": { \"start\": 0.0, \"end\": 5000.0 },",
+            1,
+        );
+        let problem =
+            ProblemDocument::<Second, TaskSpec<Second>, DynConstraintKind>::from_json(&json)
+                .unwrap();
+        assert_eq!(problem.horizon, iv(0.0, 5000.0));
+        let block: Block = problem.into_block().unwrap();
+        assert_eq!(block.task_count(), 2);
+
+        // A plain block document has no horizon.
+        assert!(matches!(
+            ProblemDocument::<Second, TaskSpec<Second>, DynConstraintKind>::from_json(&document(
+                "[]"
+            )),
+            Err(ImportError::Json(_))
+        ));
+    }
+}
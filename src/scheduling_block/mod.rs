@@ -1,13 +1,32 @@
+pub mod alternatives;
+pub mod composite;
+pub mod cost;
 pub mod error;
+pub mod exclusion;
+#[cfg(feature = "serde")]
+pub mod import;
+pub mod owner;
+pub mod setup;
 pub mod spatial;
+pub mod splittable;
 pub mod task;
+pub mod uncertain;
 
 mod block;
+mod dot;
 pub use block::SchedulingBlock;
 
+pub use alternatives::{AlternativeGroup, AlternativeStatus};
+pub use composite::{CompositeChild, CompositeTask};
+pub use cost::{CostProfile, CostedTask, PriceCurve};
 pub use error::SchedulingError;
-pub use spatial::SpatialTask;
+pub use exclusion::ExclusionGroup;
+pub use owner::OwnedTask;
+pub use setup::{SetupMatrix, SetupTask};
+pub use spatial::{SpatialTask, TransitionModel};
+pub use splittable::SplittableTask;
 pub use task::Task;
+pub use uncertain::{DurationDistribution, UncertainTask};
 
 // Re-export from the dedicated `resource` module for backward compatibility.
 #[deprecated(note = "Use `virolai::resource::Resource` instead")]
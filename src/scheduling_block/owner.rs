@@ -0,0 +1,14 @@
+//! Optional ownership extension for tasks shared between stakeholders.
+//!
+//! Telescope programmes, shared machines and compute clusters serve several
+//! owners — principal investigators, teams, customers — each entitled to a
+//! share of the time. Tasks that implement [`OwnedTask`] name their owner so
+//! fairness terms such as
+//! [`FairShare`](crate::constraints::soft::dynamic::FairShare) can group
+//! them.
+
+/// A task that belongs to an owner.
+pub trait OwnedTask {
+    /// Owner the task's time is charged to.
+    fn owner(&self) -> &str;
+}
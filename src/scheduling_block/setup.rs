@@ -0,0 +1,105 @@
+//! Optional sequence-dependent setup extension for classed tasks.
+//!
+//! Some domains pay a changeover whenever consecutive tasks differ in kind —
+//! retooling a machine, cleaning a line between products — and the cost
+//! depends on both the outgoing and the incoming kind. A constant
+//! [`Task::gap_after`](super::Task::gap_after) cannot express that.
+//!
+//! Tasks that implement [`SetupTask`] name their class; a [`SetupMatrix`]
+//! gives the setup time for each ordered pair of classes. Schedulers that
+//! know the previously placed task delay the next one by the matching entry.
+//!
+//! # Example
+//!
+//! ```
+//! use qtty::{Quantity, Second};
+//! use virolai::scheduling_block::SetupMatrix;
+//!
+//! let matrix = SetupMatrix::<Second>::new(Quantity::new(30.0))
+//!     .with_setup("paint-white", "paint-black", Quantity::new(5.0))
+//!     .with_setup("paint-black", "paint-white", Quantity::new(60.0));
+//!
+//! assert_eq!(matrix.setup("paint-white", "paint-black").value(), 5.0);
+//! assert_eq!(matrix.setup("paint-black", "paint-white").value(), 60.0);
+//! assert_eq!(matrix.setup("paint-black", "drill").value(), 30.0);
+//! assert_eq!(matrix.setup("drill", "drill").value(), 0.0);
+//! ```
+
+use std::collections::HashMap;
+
+use qtty::{Quantity, Unit};
+
+/// A task that belongs to a setup class.
+pub trait SetupTask {
+    /// Class used to look up setup times.
+    fn setup_class(&self) -> &str;
+}
+
+/// Setup time between task classes, in axis units `U`.
+///
+/// Lookups fall back, in order, to the explicit `(from, to)` entry, zero when
+/// `from == to`, then the default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetupMatrix<U: Unit> {
+    default: Quantity<U>,
+    /// `from` class → `to` class → setup.
+    entries: HashMap<String, HashMap<String, Quantity<U>>>,
+}
+
+impl<U: Unit> Default for SetupMatrix<U> {
+    fn default() -> Self {
+        Self::new(Quantity::new(0.0))
+    }
+}
+
+impl<U: Unit> SetupMatrix<U> {
+    /// Creates a matrix charging `default` between any two different classes.
+    pub fn new(default: Quantity<U>) -> Self {
+        Self {
+            default,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Sets the setup time from class `from` to class `to`.
+    pub fn with_setup(
+        mut self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+        setup: Quantity<U>,
+    ) -> Self {
+        self.set(from, to, setup);
+        self
+    }
+
+    /// Sets the setup time from class `from` to class `to`.
+    pub fn set(&mut self, from: impl Into<String>, to: impl Into<String>, setup: Quantity<U>) {
+        self.entries
+            .entry(from.into())
+            .or_default()
+            .insert(to.into(), setup);
+    }
+
+    /// Setup time when a task of class `to` follows one of class `from`.
+    pub fn setup(&self, from: &str, to: &str) -> Quantity<U> {
+        match self.entries.get(from).and_then(|row| row.get(to)) {
+            Some(&setup) => setup,
+            None if from == to => Quantity::new(0.0),
+            None => self.default,
+        }
+    }
+
+    /// Setup time charged between different classes without an entry.
+    pub fn default_setup(&self) -> Quantity<U> {
+        self.default
+    }
+
+    /// Number of explicit entries.
+    pub fn len(&self) -> usize {
+        self.entries.values().map(HashMap::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -25,9 +25,16 @@
 //!     }
 //! }
 //! ```
+//!
+//! A [`TransitionModel`] turns two positions into the time needed to move
+//! between them (slew, travel, reconfiguration). Schedulers that know the
+//! previously placed task can then delay the next one by the actual
+//! transition instead of a fixed [`Task::gap_after`](super::Task::gap_after).
 
 use std::fmt::Debug;
 
+use qtty::{Quantity, Unit};
+
 /// A task that has an associated spatial position.
 ///
 /// `C` is the coordinate type, chosen by the domain (e.g., `ICRS` for astronomy).
@@ -39,3 +46,34 @@ pub trait SpatialTask<C: Debug> {
     /// Returns a reference to this task's spatial position.
     fn position(&self) -> &C;
 }
+
+/// Time needed to move from one position to another, in axis units `U`.
+///
+/// Implemented for any `Fn(&C, &C) -> Quantity<U>`, so a closure can serve
+/// as a model. Implementations should return a non-negative duration; the
+/// transition from a position to itself is usually zero.
+///
+/// # Example
+///
+/// ```
+/// use qtty::{Quantity, Second};
+/// use virolai::scheduling_block::TransitionModel;
+///
+/// // One second per degree along a single axis.
+/// let slew = |a: &f64, b: &f64| Quantity::<Second>::new((a - b).abs());
+/// assert_eq!(slew.transition_time(&10.0, &40.0).value(), 30.0);
+/// ```
+pub trait TransitionModel<C, U: Unit>: Send + Sync {
+    /// Time to move from `from` to `to`.
+    fn transition_time(&self, from: &C, to: &C) -> Quantity<U>;
+}
+
+impl<C, U, F> TransitionModel<C, U> for F
+where
+    U: Unit,
+    F: Fn(&C, &C) -> Quantity<U> + Send + Sync,
+{
+    fn transition_time(&self, from: &C, to: &C) -> Quantity<U> {
+        self(from, to)
+    }
+}
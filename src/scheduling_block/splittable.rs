@@ -0,0 +1,47 @@
+//! Optional preemption extension for tasks that may run in several pieces.
+//!
+//! Core scheduling places every task as one contiguous interval. A task that
+//! implements [`SplittableTask`] additionally tells
+//! [`algorithms::split`](crate::algorithms::split) how it may be cut when no
+//! single window is long enough — e.g. a long integration that must span a
+//! meridian gap.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use qtty::{Quantity, Second};
+//! use virolai::scheduling_block::SplittableTask;
+//!
+//! impl SplittableTask<Second> for Integration {
+//!     fn min_chunk(&self) -> Quantity<Second> {
+//!         Quantity::new(600.0)
+//!     }
+//!
+//!     fn max_splits(&self) -> usize {
+//!         2
+//!     }
+//! }
+//! ```
+
+use qtty::{Quantity, Unit};
+
+use super::Task;
+
+/// A task that may be preempted and resumed.
+///
+/// Splitting is a last resort: a splittable task is still placed as a single
+/// interval whenever one fits.
+///
+/// # Invariants
+///
+/// - Every piece lasts at least [`min_chunk`](SplittableTask::min_chunk)
+/// - A task is cut at most [`max_splits`](SplittableTask::max_splits) times,
+///   i.e. placed as at most `max_splits + 1` pieces
+/// - The pieces add up to [`size_on_axis`](Task::size_on_axis)
+pub trait SplittableTask<A: Unit>: Task<A> {
+    /// Shortest piece worth running, in axis units.
+    fn min_chunk(&self) -> Quantity<A>;
+
+    /// Maximum number of cuts. Zero disables splitting.
+    fn max_splits(&self) -> usize;
+}
@@ -19,7 +19,8 @@ use qtty::{Quantity, Unit};
 ///
 /// # Invariants
 ///
-/// - `size()` must return a positive quantity
+/// - `size()` must return a non-negative quantity; zero marks a milestone
+///   (see [`is_milestone`](Task::is_milestone))
 /// - `name()` should uniquely identify the task within a scheduling context
 /// - `priority()` defaults to 0; higher values indicate greater importance
 /// - `constraints()` returns `None` if the task is unconstrained
@@ -72,6 +73,17 @@ pub trait Task<A: Unit>: Send + Sync + Debug + 'static {
         self.size().to::<A>()
     }
 
+    /// Returns true if this task has zero size.
+    ///
+    /// A milestone occupies no time: it is placed as an empty interval
+    /// `[t, t)`, never overlaps other tasks, and may sit on the closing edge of
+    /// a window or share an instant with other milestones. It still takes part
+    /// in dynamic edges — after a `Consecutive` predecessor it may be placed
+    /// exactly at that predecessor's end.
+    fn is_milestone(&self) -> bool {
+        self.size_on_axis().value() == 0.0
+    }
+
     fn priority(&self) -> i32 {
         0
     }
@@ -0,0 +1,262 @@
+//! Optional duration-uncertainty extension.
+//!
+//! Observations overrun, jobs straggle, crews are late: the size a task
+//! declares is a nominal value, not a promise. Tasks that implement
+//! [`UncertainTask`] describe how long they may actually take with a
+//! [`DurationDistribution`], so the EST scheduler can plan with a quantile
+//! or keep protective buffers, and report the risk of each placement
+//! overrunning into the next.
+//!
+//! # Example
+//!
+//! ```
+//! use qtty::{Quantity, Second};
+//! use virolai::scheduling_block::DurationDistribution;
+//!
+//! // Usually 60 s, but 1 run in 20 takes more than 90 s.
+//! let d = DurationDistribution::<Second>::from_p95(Quantity::new(60.0), Quantity::new(90.0));
+//! assert!((d.quantile(0.95).value() - 90.0).abs() < 1e-6);
+//! assert!((d.exceedance(Quantity::new(90.0)) - 0.05).abs() < 1e-6);
+//! ```
+
+use qtty::{Quantity, Unit};
+
+use super::Task;
+use crate::rng::SplitMix64;
+
+/// Standard-normal quantile of 0.95.
+const Z_95: f64 = 1.644_853_626_951_472_2;
+
+/// A task whose actual duration is uncertain.
+pub trait UncertainTask<U: Unit>: Task<U> {
+    /// Distribution of the actual duration, on the axis.
+    fn duration_distribution(&self) -> DurationDistribution<U>;
+}
+
+/// Distribution of a task's actual duration.
+///
+/// Durations are never negative: quantiles are clamped at zero.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DurationDistribution<U: Unit> {
+    /// Always exactly this long.
+    Fixed(Quantity<U>),
+    /// Normally distributed.
+    Normal {
+        mean: Quantity<U>,
+        std_dev: Quantity<U>,
+    },
+    /// Equally likely observed durations, ascending.
+    Empirical(Vec<Quantity<U>>),
+}
+
+impl<U: Unit> DurationDistribution<U> {
+    /// A duration known exactly.
+    pub fn fixed(duration: Quantity<U>) -> Self {
+        Self::Fixed(duration)
+    }
+
+    /// A normal distribution.
+    ///
+    /// # Panics
+    ///
+    /// If `mean` or `std_dev` is not finite, or `std_dev` is negative.
+    pub fn normal(mean: Quantity<U>, std_dev: Quantity<U>) -> Self {
+        assert!(
+            mean.value().is_finite() && std_dev.value().is_finite() && std_dev.value() >= 0.0,
+            "normal duration needs a finite mean and a finite, non-negative deviation"
+        );
+        Self::Normal { mean, std_dev }
+    }
+
+    /// The normal distribution centred on `nominal` whose 95th percentile is
+    /// `p95`.
+    ///
+    /// # Panics
+    ///
+    /// If either value is not finite or `p95 < nominal`.
+    pub fn from_p95(nominal: Quantity<U>, p95: Quantity<U>) -> Self {
+        assert!(
+            p95.value() >= nominal.value(),
+            "p95 duration {} is below the nominal {}",
+            p95.value(),
+            nominal.value()
+        );
+        Self::normal(
+            nominal,
+            Quantity::new((p95.value() - nominal.value()) / Z_95),
+        )
+    }
+
+    /// The empirical distribution of `samples`.
+    ///
+    /// # Panics
+    ///
+    /// If `samples` is empty or holds a non-finite value.
+    pub fn empirical(samples: impl IntoIterator<Item = Quantity<U>>) -> Self {
+        let mut samples: Vec<_> = samples.into_iter().collect();
+        assert!(
+            !samples.is_empty() && samples.iter().all(|s| s.value().is_finite()),
+            "empirical duration needs at least one finite sample"
+        );
+        samples.sort_by(|a, b| a.value().total_cmp(&b.value()));
+        Self::Empirical(samples)
+    }
+
+    /// Median duration.
+    pub fn median(&self) -> Quantity<U> {
+        self.quantile(0.5)
+    }
+
+    /// Duration not exceeded with probability `p` (clamped to `[0, 1]`).
+    pub fn quantile(&self, p: f64) -> Quantity<U> {
+        let p = if p.is_nan() { 0.5 } else { p.clamp(0.0, 1.0) };
+        let value = match self {
+            Self::Fixed(d) => d.value(),
+            Self::Normal { mean, std_dev } => {
+                mean.value() + std_dev.value() * inverse_normal_cdf(p.clamp(1e-12, 1.0 - 1e-12))
+            }
+            Self::Empirical(samples) => {
+                // Nearest rank.
+                let rank = (p * samples.len() as f64).ceil() as usize;
+                samples[rank.clamp(1, samples.len()) - 1].value()
+            }
+        };
+        Quantity::new(value.max(0.0))
+    }
+
+    /// Draws a duration by inverse-transform sampling.
+    pub(crate) fn sample(&self, rng: &mut SplitMix64) -> Quantity<U> {
+        self.quantile(rng.next_f64())
+    }
+
+    /// Probability that the duration exceeds `duration`.
+    pub fn exceedance(&self, duration: Quantity<U>) -> f64 {
+        let d = duration.value();
+        match self {
+            Self::Fixed(fixed) => f64::from(u8::from(fixed.value() > d)),
+            Self::Normal { mean, std_dev } if std_dev.value() == 0.0 => {
+                f64::from(u8::from(mean.value() > d))
+            }
+            Self::Normal { mean, std_dev } => {
+                1.0 - normal_cdf((d - mean.value()) / std_dev.value())
+            }
+            Self::Empirical(samples) => {
+                let longer = samples.len() - samples.partition_point(|s| s.value() <= d);
+                longer as f64 / samples.len() as f64
+            }
+        }
+    }
+}
+
+/// Standard-normal CDF (Abramowitz & Stegun 7.1.26, error below 1.5e-7).
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+/// Standard-normal quantile for `p` in `(0, 1)` (Acklam, relative error
+/// below 1.2e-9).
+fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::q;
+    use qtty::Second;
+
+    #[test]
+    fn normal_quantiles_and_exceedance_agree() {
+        let d = DurationDistribution::<Second>::normal(q(100.0), q(10.0));
+        assert!((d.median().value() - 100.0).abs() < 1e-9);
+        for p in [0.1, 0.5, 0.8, 0.99] {
+            let x = d.quantile(p);
+            assert!((d.exceedance(x) - (1.0 - p)).abs() < 1e-6, "p = {p}");
+        }
+        // Quantiles never go negative.
+        assert_eq!(
+            DurationDistribution::<Second>::normal(q(1.0), q(10.0)).quantile(0.01),
+            q(0.0)
+        );
+    }
+
+    #[test]
+    fn empirical_uses_nearest_rank() {
+        let d = DurationDistribution::<Second>::empirical([q(30.0), q(10.0), q(20.0), q(40.0)]);
+        assert_eq!(d.quantile(0.0), q(10.0));
+        assert_eq!(d.quantile(0.5), q(20.0));
+        assert_eq!(d.quantile(0.51), q(30.0));
+        assert_eq!(d.quantile(1.0), q(40.0));
+        assert_eq!(d.exceedance(q(20.0)), 0.5);
+        assert_eq!(d.exceedance(q(40.0)), 0.0);
+    }
+
+    #[test]
+    fn fixed_durations_are_certain() {
+        let d = DurationDistribution::<Second>::fixed(q(5.0));
+        assert_eq!(d.quantile(0.99), q(5.0));
+        assert_eq!(d.exceedance(q(5.0)), 0.0);
+        assert_eq!(d.exceedance(q(4.9)), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "below the nominal")]
+    fn p95_below_nominal_is_rejected() {
+        let _ = DurationDistribution::<Second>::from_p95(q(10.0), q(5.0));
+    }
+}
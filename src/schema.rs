@@ -0,0 +1,98 @@
+//! JSON Schemas for problem and schedule files (`schema` feature).
+//!
+//! Producers of [`BlockDocument`]s and schedules outside Rust can validate
+//! their payloads against these schemas before submitting them. The schemas
+//! follow the serde representation exactly: interval bounds and sizes are
+//! plain numbers in the axis unit, so they do not depend on the unit type.
+//!
+//! Any task or edge type deriving [`JsonSchema`] plugs into
+//! [`block_schema`]; [`TaskSpec`](crate::scheduling_block::import::TaskSpec)
+//! and [`DynConstraintKind`](crate::constraints::DynConstraintKind) already
+//! do.
+//!
+//! # Example
+//!
+//! ```
+//! use virolai::constraints::DynConstraintKind;
+//! use virolai::scheduling_block::import::TaskSpec;
+//! use qtty::Second;
+//!
+//! let schema = virolai::schema::block_schema::<TaskSpec<Second>, DynConstraintKind>();
+//! let json = serde_json::to_value(&schema).unwrap();
+//! assert_eq!(json["required"], serde_json::json!(["version", "tasks"]));
+//! ```
+
+use crate::constraints::ConstraintExpr;
+use crate::schedule::Schedule;
+use crate::scheduling_block::import::BlockDocument;
+
+pub use schemars::{JsonSchema, Schema};
+
+/// Schema of a [`BlockDocument`] with tasks `T` and edges `D`.
+pub fn block_schema<T: JsonSchema, D: JsonSchema>() -> Schema {
+    schemars::schema_for!(BlockDocument<T, D>)
+}
+
+/// Schema of a constraint tree with leaves `C`.
+pub fn constraint_schema<C: JsonSchema>() -> Schema {
+    schemars::schema_for!(ConstraintExpr<C>)
+}
+
+/// Schema of a serialized [`Schedule`].
+pub fn schedule_schema() -> Schema {
+    // The written form does not depend on the unit.
+    schemars::schema_for!(Schedule<qtty::Second>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::{DynConstraintKind, IntervalConstraint, PeriodicConstraint};
+    use crate::scheduling_block::import::TaskSpec;
+    use qtty::Second;
+    use serde_json::{json, Value};
+
+    fn to_json(schema: Schema) -> Value {
+        serde_json::to_value(schema).unwrap()
+    }
+
+    #[test]
+    fn block_schema_describes_tasks_and_edges() {
+        let schema = to_json(block_schema::<TaskSpec<Second>, DynConstraintKind>());
+        let text = schema.to_string();
+        for field in [
+            "version",
+            "tasks",
+            "edges",
+            "size",
+            "priority",
+            "constraints",
+        ] {
+            assert!(text.contains(&format!("\"{field}\"")), "{field} missing");
+        }
+        assert!(text.contains("\"consecutive\""));
+        assert!(text.contains("\"start_to_start\""));
+    }
+
+    #[test]
+    fn constraint_schema_tags_combinators() {
+        let schema = to_json(constraint_schema::<IntervalConstraint<Second>>());
+        let text = schema.to_string();
+        for tag in ["\"not\"", "\"intersection\"", "\"union\""] {
+            assert!(text.contains(tag), "{tag} missing");
+        }
+        let periodic = to_json(constraint_schema::<PeriodicConstraint<Second>>()).to_string();
+        assert!(periodic.contains("\"period\"") && periodic.contains("\"width\""));
+    }
+
+    #[test]
+    fn schedule_schema_is_an_array_of_entries() {
+        let schema = to_json(schedule_schema());
+        assert_eq!(schema["type"], json!("array"));
+        assert_eq!(schema["items"]["required"], json!(["task", "interval"]));
+        assert_eq!(
+            schema["$defs"]["Interval"]["required"],
+            json!(["start", "end"])
+        );
+    }
+}
@@ -0,0 +1,453 @@
+//! Scheduling service (`server` feature).
+//!
+//! [`SchedulerService`] runs submitted problems on a pool of worker threads,
+//! so clients share one scheduler instead of each linking the crate.
+//! [`serve`] exposes it over HTTP:
+//!
+//! | Method   | Path                | Response                                          |
+//! |----------|---------------------|---------------------------------------------------|
+//! | `POST`   | `/jobs`             | `202` and `{"id"}`; `400` for a malformed problem  |
+//! | `GET`    | `/jobs/{id}`        | `{"id", "status"}`, plus `"error"` if it failed    |
+//! | `GET`    | `/jobs/{id}/result` | `200` and the schedule once done, `409` before     |
+//! | `DELETE` | `/jobs/{id}`        | Cancels a pending job or discards a finished one   |
+//!
+//! Problems are [`ProblemDocument`]s of [`TaskSpec`]s and
+//! [`DynConstraintKind`] edges, as for the [`ffi`](crate::ffi) bindings, and
+//! are scheduled with [`ESTScheduler::schedule_limited`]. Results are the
+//! schedule in the [`Schedule`](crate::schedule::Schedule) JSON format.
+//! Statuses are `queued`, `running`, `done`, `failed` and `cancelled`.
+//!
+//! Each run carries its job's cancel flag in its [`ExecutionLimit`], so
+//! cancelling a running job stops it at its next placement attempt and
+//! frees the worker. Finished jobs are kept until deleted.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::net::ToSocketAddrs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Condvar, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use qtty::Second;
+use serde_json::{json, Value};
+
+use crate::algorithms::est::ExecutionLimit;
+use crate::algorithms::ESTScheduler;
+use crate::constraints::DynConstraintKind;
+use crate::scheduling_block::import::{ImportError, ProblemDocument, TaskSpec};
+use crate::scheduling_block::SchedulingBlock;
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+
+type Block = SchedulingBlock<TaskSpec<Second>, Second, DynConstraintKind>;
+
+/// Where a job is in its life cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    /// The run failed with this message.
+    Failed(String),
+    Cancelled,
+}
+
+impl JobStatus {
+    /// `true` once the job will not change any more.
+    pub fn is_finished(&self) -> bool {
+        !matches!(self, JobStatus::Queued | JobStatus::Running)
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Done => "done",
+            JobStatus::Failed(_) => "failed",
+            JobStatus::Cancelled => "cancelled",
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Job {
+    status: JobStatus,
+    /// Schedule JSON, once done.
+    result: Option<String>,
+    /// Set on cancellation; stops the run.
+    cancel: Arc<AtomicBool>,
+}
+
+#[derive(Debug, Default)]
+struct Jobs {
+    jobs: Mutex<HashMap<Id, Job>>,
+    changed: Condvar,
+}
+
+impl Jobs {
+    fn lock(&self) -> MutexGuard<'_, HashMap<Id, Job>> {
+        // A worker never panics while holding the lock.
+        self.jobs.lock().expect("job table poisoned")
+    }
+}
+
+/// Scheduler running submitted problems on a pool of worker threads.
+///
+/// Dropping the service lets the workers finish the queued jobs, then joins
+/// them.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use virolai::server::{JobStatus, SchedulerService};
+///
+/// let service = SchedulerService::new(2);
+/// let id = service
+///     .submit(r#"{
+///         "version": 1,
+///         "horizon": { "start": 0.0, "end": 100.0 },
+///         "tasks": [ { "id": "a", "name": "calib", "size": 10.0 } ]
+///     }"#)
+///     .unwrap();
+///
+/// assert_eq!(service.wait(&id, Duration::from_secs(5)), Some(JobStatus::Done));
+/// let schedule = service.result(&id).unwrap();
+/// assert!(schedule.contains("\"task\":\"a\""));
+/// ```
+pub struct SchedulerService {
+    jobs: Arc<Jobs>,
+    queue: Option<mpsc::Sender<(Id, Block, Interval<Second>)>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl SchedulerService {
+    /// Starts `workers` worker threads (at least one).
+    pub fn new(workers: usize) -> Self {
+        let jobs = Arc::new(Jobs::default());
+        let (queue, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..workers.max(1))
+            .map(|_| {
+                let (jobs, receiver) = (Arc::clone(&jobs), Arc::clone(&receiver));
+                std::thread::spawn(move || work(&jobs, &receiver))
+            })
+            .collect();
+        Self {
+            jobs,
+            queue: Some(queue),
+            workers,
+        }
+    }
+
+    /// Parses `problem` and queues it; returns the job ID.
+    pub fn submit(&self, problem: &str) -> Result<Id, ImportError> {
+        let problem =
+            ProblemDocument::<Second, TaskSpec<Second>, DynConstraintKind>::from_json(problem)?;
+        let horizon = problem.horizon;
+        let block = problem.into_block()?;
+
+        let id = crate::generate_id();
+        self.jobs.lock().insert(
+            id.clone(),
+            Job {
+                status: JobStatus::Queued,
+                result: None,
+                cancel: Arc::default(),
+            },
+        );
+        self.queue
+            .as_ref()
+            .expect("queue is open until drop")
+            .send((id.clone(), block, horizon))
+            .expect("workers outlive the queue");
+        Ok(id)
+    }
+
+    /// Status of job `id`, if known.
+    pub fn status(&self, id: &str) -> Option<JobStatus> {
+        self.jobs.lock().get(id).map(|job| job.status.clone())
+    }
+
+    /// Schedule JSON of job `id`, if it is done.
+    pub fn result(&self, id: &str) -> Option<String> {
+        self.jobs.lock().get(id).and_then(|job| job.result.clone())
+    }
+
+    /// Cancels job `id` if it is queued or running; returns whether it was.
+    pub fn cancel(&self, id: &str) -> bool {
+        let mut jobs = self.jobs.lock();
+        let Some(job) = jobs.get_mut(id).filter(|job| !job.status.is_finished()) else {
+            return false;
+        };
+        job.status = JobStatus::Cancelled;
+        job.cancel.store(true, Ordering::Relaxed);
+        drop(jobs);
+        self.jobs.changed.notify_all();
+        true
+    }
+
+    /// Forgets finished job `id`; returns whether it was.
+    pub fn remove(&self, id: &str) -> bool {
+        let mut jobs = self.jobs.lock();
+        if jobs.get(id).is_some_and(|job| job.status.is_finished()) {
+            jobs.remove(id);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Blocks until job `id` is finished or `timeout` has passed, then
+    /// returns its status.
+    pub fn wait(&self, id: &str, timeout: Duration) -> Option<JobStatus> {
+        let deadline = Instant::now() + timeout;
+        let mut jobs = self.jobs.lock();
+        loop {
+            let status = jobs.get(id)?.status.clone();
+            let left = deadline.saturating_duration_since(Instant::now());
+            if status.is_finished() || left.is_zero() {
+                return Some(status);
+            }
+            jobs = self
+                .jobs
+                .changed
+                .wait_timeout(jobs, left)
+                .expect("job table poisoned")
+                .0;
+        }
+    }
+}
+
+impl Drop for SchedulerService {
+    fn drop(&mut self) {
+        self.queue = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn work(jobs: &Jobs, receiver: &Mutex<mpsc::Receiver<(Id, Block, Interval<Second>)>>) {
+    loop {
+        let next = receiver.lock().expect("queue poisoned").recv();
+        let Ok((id, block, horizon)) = next else {
+            return;
+        };
+        let cancel = match jobs.lock().get_mut(&id) {
+            Some(job) if job.status == JobStatus::Queued => {
+                job.status = JobStatus::Running;
+                Arc::clone(&job.cancel)
+            }
+            _ => continue,
+        };
+        jobs.changed.notify_all();
+
+        let outcome = catch_unwind(AssertUnwindSafe(|| run(block, horizon, cancel)))
+            .unwrap_or_else(|_| Err("scheduler panicked".into()));
+        if let Some(job) = jobs
+            .lock()
+            .get_mut(&id)
+            .filter(|job| job.status == JobStatus::Running)
+        {
+            match outcome {
+                Ok(schedule) => {
+                    job.status = JobStatus::Done;
+                    job.result = Some(schedule);
+                }
+                Err(message) => job.status = JobStatus::Failed(message),
+            }
+        }
+        jobs.changed.notify_all();
+    }
+}
+
+fn run(block: Block, horizon: Interval<Second>, cancel: Arc<AtomicBool>) -> Result<String, String> {
+    let blocks = [block];
+    blocks[0].ensure_acyclic().map_err(|e| e.to_string())?;
+    let solution_space =
+        SolutionSpace::try_populate(&blocks, horizon).map_err(|e| e.to_string())?;
+    let limit = ExecutionLimit::unlimited().with_cancel(cancel);
+    let result = ESTScheduler::default().schedule_limited(&blocks, &solution_space, horizon, limit);
+    if result.cancelled {
+        return Err("cancelled".into());
+    }
+    serde_json::to_string(&result.schedule).map_err(|e| e.to_string())
+}
+
+/// Answers one request: status code and JSON body.
+fn respond(service: &SchedulerService, method: &str, path: &str, body: &str) -> (u16, String) {
+    let error = |code, message: String| (code, json!({ "error": message }).to_string());
+    let segments: Vec<&str> = path
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    match (method, segments.as_slice()) {
+        ("POST", ["jobs"]) => match service.submit(body) {
+            Ok(id) => (202, json!({ "id": id }).to_string()),
+            Err(e) => error(400, e.to_string()),
+        },
+        ("GET", ["jobs", id]) => match service.status(id) {
+            Some(status) => {
+                let mut reply = json!({ "id": id, "status": status.to_string() });
+                if let JobStatus::Failed(message) = status {
+                    reply["error"] = Value::from(message);
+                }
+                (200, reply.to_string())
+            }
+            None => error(404, format!("unknown job {id}")),
+        },
+        ("GET", ["jobs", id, "result"]) => match (service.result(id), service.status(id)) {
+            (Some(schedule), _) => (200, schedule),
+            (None, Some(status)) => error(409, format!("job {id} is {status}")),
+            (None, None) => error(404, format!("unknown job {id}")),
+        },
+        ("DELETE", ["jobs", id]) => {
+            if service.cancel(id) {
+                (200, json!({ "id": id, "status": "cancelled" }).to_string())
+            } else if service.remove(id) {
+                (200, json!({ "id": id, "status": "removed" }).to_string())
+            } else {
+                error(404, format!("unknown job {id}"))
+            }
+        }
+        _ => error(404, format!("no route for {method} {path}")),
+    }
+}
+
+/// Serves `service` over HTTP on `addr` until the listener fails.
+///
+/// Requests are answered on the calling thread; scheduling happens on the
+/// service's workers.
+pub fn serve(service: &SchedulerService, addr: impl ToSocketAddrs) -> io::Result<()> {
+    let server = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+    let content_type = tiny_http::Header::from_bytes("Content-Type", "application/json")
+        .expect("static header is valid");
+    for mut request in server.incoming_requests() {
+        let mut body = String::new();
+        let (code, reply) = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => respond(service, request.method().as_str(), request.url(), &body),
+            Err(e) => (400, json!({ "error": e.to_string() }).to_string()),
+        };
+        let response = tiny_http::Response::from_string(reply)
+            .with_status_code(code)
+            .with_header(content_type.clone());
+        // A client that hung up is not the server's problem.
+        let _ = request.respond(response);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    fn problem(edges: &str) -> String {
+        format!(
+            r#"{{
+                "version": 1,
+                "horizon": {{ "start": 0.0, "end": 100.0 }},
+                "tasks": [
+                    {{ "id": "a", "name": "calib", "size": 10.0 }},
+                    {{ "id": "b", "name": "science", "size": 20.0 }}
+                ],
+                "edges": {edges}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn runs_jobs_on_the_pool() {
+        let service = SchedulerService::new(2);
+        let ids: Vec<Id> = (0..4)
+            .map(|_| service.submit(&problem("[]")).unwrap())
+            .collect();
+        for id in &ids {
+            assert_eq!(service.wait(id, TIMEOUT), Some(JobStatus::Done));
+            let schedule: Value = serde_json::from_str(&service.result(id).unwrap()).unwrap();
+            assert_eq!(schedule.as_array().unwrap().len(), 2);
+        }
+        assert!(!service.cancel(&ids[0]));
+        assert!(service.remove(&ids[0]));
+        assert_eq!(service.status(&ids[0]), None);
+    }
+
+    #[test]
+    fn malformed_problems_are_rejected_at_submission() {
+        let service = SchedulerService::new(1);
+        let cyclic = problem(
+            r#"[ { "from": "a", "to": "b", "kind": "dependence" },
+                 { "from": "b", "to": "a", "kind": "dependence" } ]"#,
+        );
+        assert!(service.submit(&cyclic).is_err());
+        assert!(service.submit("{}").is_err());
+    }
+
+    #[test]
+    fn cancelled_jobs_keep_no_result() {
+        let service = SchedulerService::new(1);
+        let id = service.submit(&problem("[]")).unwrap();
+        if service.cancel(&id) {
+            assert_eq!(service.wait(&id, TIMEOUT), Some(JobStatus::Cancelled));
+            assert_eq!(service.result(&id), None);
+        } else {
+            // The worker was faster.
+            assert_eq!(service.status(&id), Some(JobStatus::Done));
+        }
+        assert!(!service.cancel("nope"));
+    }
+
+    #[test]
+    fn cancel_flag_stops_the_run() {
+        let document = ProblemDocument::<Second, TaskSpec<Second>, DynConstraintKind>::from_json(
+            &problem("[]"),
+        )
+        .unwrap();
+        let horizon = document.horizon;
+        let block = document.into_block().unwrap();
+        let flag = Arc::new(AtomicBool::new(true));
+        assert_eq!(run(block.clone(), horizon, flag), Err("cancelled".into()));
+        assert!(run(block, horizon, Arc::default()).is_ok());
+    }
+
+    #[test]
+    fn routes_requests() {
+        let service = SchedulerService::new(1);
+        let (code, body) = respond(&service, "POST", "/jobs", &problem("[]"));
+        assert_eq!(code, 202);
+        let id = serde_json::from_str::<Value>(&body).unwrap()["id"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+        service.wait(&id, TIMEOUT);
+
+        let (code, body) = respond(&service, "GET", &format!("/jobs/{id}"), "");
+        assert_eq!(code, 200);
+        assert_eq!(
+            serde_json::from_str::<Value>(&body).unwrap()["status"],
+            "done"
+        );
+        let (code, body) = respond(&service, "GET", &format!("/jobs/{id}/result"), "");
+        assert_eq!(code, 200);
+        assert!(body.starts_with('['));
+        assert_eq!(
+            respond(&service, "DELETE", &format!("/jobs/{id}"), "").0,
+            200
+        );
+
+        assert_eq!(respond(&service, "GET", &format!("/jobs/{id}"), "").0, 404);
+        assert_eq!(respond(&service, "POST", "/jobs", "{").0, 400);
+        assert_eq!(respond(&service, "PUT", "/jobs", "").0, 404);
+    }
+}
@@ -0,0 +1,169 @@
+//! Global blackout periods.
+//!
+//! Maintenance slots and facility downtime apply to every task alike.
+//! Rather than repeating them in each task's constraint tree, collect them
+//! once in a [`BlackoutSet`]: [`apply`](BlackoutSet::apply) removes them from
+//! every window of a [`SolutionSpace`], and
+//! [`validate_with_blackouts`](crate::schedule::validate_with_blackouts)
+//! checks them again on schedules produced elsewhere.
+
+use super::{Interval, IntervalSet, SolutionSpace};
+use qtty::Unit;
+
+/// Labelled intervals no task may overlap.
+///
+/// # Example
+///
+/// ```
+/// use virolai::solution_space::{BlackoutSet, Interval, SolutionSpace};
+/// use qtty::Second;
+///
+/// let blackouts = BlackoutSet::<Second>::new()
+///     .with_blackout(Interval::from_f64(40.0, 50.0), "maintenance");
+///
+/// let mut ss = SolutionSpace::new();
+/// ss.add_interval("a", Interval::from_f64(0.0, 100.0));
+/// blackouts.apply(&mut ss);
+/// assert_eq!(
+///     ss.get_intervals("a").unwrap().as_slice(),
+///     &[Interval::from_f64(0.0, 40.0), Interval::from_f64(50.0, 100.0)]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlackoutSet<U: Unit> {
+    /// Blackouts in insertion order, with their labels.
+    entries: Vec<(Interval<U>, String)>,
+    /// Union of every blackout.
+    union: IntervalSet<U>,
+}
+
+impl<U: Unit> Default for BlackoutSet<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<U: Unit> BlackoutSet<U> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            union: IntervalSet::new(),
+        }
+    }
+
+    /// Adds the blackout `interval`, named `label`.
+    pub fn with_blackout(mut self, interval: Interval<U>, label: impl Into<String>) -> Self {
+        self.add(interval, label);
+        self
+    }
+
+    /// Adds the blackout `interval`, named `label`.
+    pub fn add(&mut self, interval: Interval<U>, label: impl Into<String>) {
+        self.entries.push((interval, label.into()));
+        self.union.push(interval);
+    }
+
+    /// Number of blackouts added.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// `true` if no blackout was added.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Blackouts with their labels, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (Interval<U>, &str)> + '_ {
+        self.entries
+            .iter()
+            .map(|(interval, label)| (*interval, label.as_str()))
+    }
+
+    /// Union of every blackout.
+    pub fn intervals(&self) -> &IntervalSet<U> {
+        &self.union
+    }
+
+    /// Blackouts a task placed at `placement` would run into.
+    ///
+    /// A milestone runs into a blackout containing its instant.
+    pub fn conflicts(
+        &self,
+        placement: Interval<U>,
+    ) -> impl Iterator<Item = (Interval<U>, &str)> + '_ {
+        self.iter().filter(move |(blackout, _)| {
+            if placement.is_empty() {
+                blackout.contains(placement.start())
+            } else {
+                blackout.overlaps(&placement)
+            }
+        })
+    }
+
+    /// `windows` without the blackouts.
+    pub fn subtract(&self, windows: &IntervalSet<U>) -> IntervalSet<U> {
+        let (Some(first), Some(last)) = (windows.first(), windows.last()) else {
+            return windows.clone();
+        };
+        if self.union.is_empty() {
+            return windows.clone();
+        }
+        let bounds = Interval::new(first.start(), last.end());
+        windows.intersection(&self.union.complement(bounds))
+    }
+
+    /// Removes the blackouts from the windows of every task in `solution_space`.
+    pub fn apply(&self, solution_space: &mut SolutionSpace<U>) {
+        if self.union.is_empty() {
+            return;
+        }
+        let ids: Vec<_> = solution_space.ids().cloned().collect();
+        for id in ids {
+            if let Some(windows) = solution_space.get_intervals(&id) {
+                let remaining = self.subtract(windows).into_inner();
+                solution_space.set_intervals(id, remaining);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn blackouts() -> BlackoutSet<Second> {
+        BlackoutSet::new()
+            .with_blackout(iv(10.0, 20.0), "maintenance")
+            .with_blackout(iv(15.0, 30.0), "downtime")
+    }
+
+    #[test]
+    fn apply_subtracts_from_every_task() {
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("a", vec![iv(0.0, 50.0)]);
+        ss.set_intervals("b", vec![iv(12.0, 18.0), iv(40.0, 60.0)]);
+        blackouts().apply(&mut ss);
+        assert_eq!(
+            ss.get_intervals("a").unwrap().as_slice(),
+            &[iv(0.0, 10.0), iv(30.0, 50.0)]
+        );
+        assert_eq!(ss.get_intervals("b").unwrap().as_slice(), &[iv(40.0, 60.0)]);
+    }
+
+    #[test]
+    fn conflicts_name_the_blackouts_hit() {
+        let blackouts = blackouts();
+        let hit: Vec<_> = blackouts
+            .conflicts(iv(18.0, 25.0))
+            .map(|(_, label)| label)
+            .collect();
+        assert_eq!(hit, vec!["maintenance", "downtime"]);
+        assert_eq!(blackouts.conflicts(iv(0.0, 10.0)).count(), 0);
+        assert_eq!(blackouts.conflicts(iv(10.0, 10.0)).count(), 1);
+        assert_eq!(blackouts.intervals().as_slice(), &[iv(10.0, 30.0)]);
+    }
+}
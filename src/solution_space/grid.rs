@@ -0,0 +1,199 @@
+//! Discrete start-time grid.
+//!
+//! Many instruments only accept commands at fixed cadences (every minute,
+//! every frame). A [`TimeGrid`] describes such a cadence as an `origin` and a
+//! `step`; [`SolutionSpace::quantized`] shrinks every window so that the
+//! starts it admits for its task are exactly the grid points it contains.
+//! Because EST, deadline and flexibility are all read off the windows, they
+//! round the same way once the space is quantized.
+
+use super::{Interval, SolutionSpace};
+use crate::scheduling_block::{SchedulingBlock, Task};
+use qtty::{Quantity, Unit};
+
+/// Relative slack for treating a value as on the grid despite rounding error.
+const GRID_EPSILON: f64 = 1e-9;
+
+/// Start times `origin + k · step` for every integer `k`.
+///
+/// # Example
+///
+/// ```
+/// use virolai::solution_space::{Interval, TimeGrid};
+/// use qtty::{Quantity, Second};
+///
+/// let minutes = TimeGrid::new(Quantity::<Second>::new(0.0), Quantity::new(60.0));
+/// assert_eq!(minutes.snap_up(Quantity::new(61.0)).value(), 120.0);
+/// assert_eq!(minutes.snap_down(Quantity::new(119.0)).value(), 60.0);
+///
+/// // A 30 s task in [10, 150) can only start at 60 or 120.
+/// let window = minutes.quantize(Interval::from_f64(10.0, 150.0), Quantity::new(30.0));
+/// assert_eq!(window, Some(Interval::from_f64(60.0, 150.0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeGrid<U: Unit> {
+    origin: Quantity<U>,
+    step: Quantity<U>,
+}
+
+impl<U: Unit> TimeGrid<U> {
+    /// Creates a grid through `origin` with spacing `step`.
+    ///
+    /// # Panics
+    ///
+    /// If `origin` is not finite or `step` is not finite and positive.
+    pub fn new(origin: Quantity<U>, step: Quantity<U>) -> Self {
+        assert!(
+            origin.value().is_finite(),
+            "grid origin must be finite, got {}",
+            origin.value()
+        );
+        assert!(
+            step.value().is_finite() && step.value() > 0.0,
+            "grid step must be finite and positive, got {}",
+            step.value()
+        );
+        Self { origin, step }
+    }
+
+    /// A grid point.
+    pub fn origin(&self) -> Quantity<U> {
+        self.origin
+    }
+
+    /// Spacing between grid points.
+    pub fn step(&self) -> Quantity<U> {
+        self.step
+    }
+
+    /// Earliest grid point at or after `t`.
+    pub fn snap_up(&self, t: Quantity<U>) -> Quantity<U> {
+        self.point((self.steps(t) - GRID_EPSILON).ceil())
+    }
+
+    /// Latest grid point at or before `t`.
+    pub fn snap_down(&self, t: Quantity<U>) -> Quantity<U> {
+        self.point((self.steps(t) + GRID_EPSILON).floor())
+    }
+
+    /// `true` if `t` is a grid point, up to rounding error.
+    pub fn is_aligned(&self, t: Quantity<U>) -> bool {
+        let steps = self.steps(t);
+        (steps - steps.round()).abs() <= GRID_EPSILON
+    }
+
+    /// Part of `window` a task of `size` can occupy when it starts on the
+    /// grid: from the first grid start to the last one plus `size`.
+    ///
+    /// Returns `None` if no grid start fits.
+    pub fn quantize(&self, window: Interval<U>, size: Quantity<U>) -> Option<Interval<U>> {
+        let first = self.snap_up(window.start());
+        let last = self.snap_down(window.end() - size);
+        (first <= last).then(|| Interval::new(first, last + size))
+    }
+
+    fn steps(&self, t: Quantity<U>) -> f64 {
+        (t.value() - self.origin.value()) / self.step.value()
+    }
+
+    fn point(&self, steps: f64) -> Quantity<U> {
+        Quantity::new(self.origin.value() + steps * self.step.value())
+    }
+}
+
+impl<U: Unit> SolutionSpace<U> {
+    /// Copy restricted to `range` with every window of a task of `blocks`
+    /// [quantized](TimeGrid::quantize) for that task's size.
+    ///
+    /// Windows that admit no grid start are dropped; entries of IDs not in
+    /// `blocks` are left out.
+    pub fn quantized<T, D, E>(
+        &self,
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        grid: &TimeGrid<U>,
+        range: Interval<U>,
+    ) -> Self
+    where
+        T: Task<U>,
+        E: petgraph::EdgeType,
+    {
+        let mut quantized = Self::new();
+        for (id, task) in blocks.iter().flat_map(|block| block.tasks()) {
+            let Some(intervals) = self.get_intervals(id) else {
+                continue;
+            };
+            let size = task.size_on_axis();
+            let windows = intervals
+                .iter()
+                .filter_map(|w| w.intersection(&range))
+                .filter_map(|w| grid.quantize(w, size))
+                .collect();
+            quantized.set_intervals(id, windows);
+        }
+        quantized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    fn grid() -> TimeGrid<Second> {
+        TimeGrid::new(q(5.0), q(10.0))
+    }
+
+    #[test]
+    fn snapping_is_tolerant_of_rounding_error() {
+        let grid = grid();
+        assert_eq!(grid.snap_up(q(5.0)), q(5.0));
+        assert_eq!(grid.snap_up(q(5.1)), q(15.0));
+        assert_eq!(grid.snap_up(q(-7.0)), q(-5.0));
+        assert_eq!(grid.snap_down(q(14.9)), q(5.0));
+        assert_eq!(grid.snap_up(q(15.0 + 1e-12)), q(15.0));
+        assert!(grid.is_aligned(q(25.0)));
+        assert!(!grid.is_aligned(q(20.0)));
+    }
+
+    #[test]
+    fn quantize_keeps_only_grid_starts() {
+        let grid = grid();
+        assert_eq!(grid.quantize(iv(0.0, 40.0), q(10.0)), Some(iv(5.0, 35.0)));
+        assert_eq!(grid.quantize(iv(6.0, 24.0), q(10.0)), None);
+        // Milestones may sit on the window's end.
+        assert_eq!(grid.quantize(iv(6.0, 15.0), q(0.0)), Some(iv(15.0, 15.0)));
+    }
+
+    #[test]
+    fn quantized_space_follows_task_sizes() {
+        let mut block = SchedulingBlock::<TestTask, Second>::new();
+        block
+            .add_task_with_id(TestTask::new("a", 10.0), Some("a".into()))
+            .unwrap();
+        block
+            .add_task_with_id(TestTask::new("b", 20.0), Some("b".into()))
+            .unwrap();
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("a", vec![iv(0.0, 30.0), iv(40.0, 48.0)]);
+        ss.set_intervals("b", vec![iv(0.0, 30.0)]);
+        ss.set_intervals("ghost", vec![iv(0.0, 30.0)]);
+
+        let quantized = ss.quantized(&[block], &grid(), iv(0.0, 100.0));
+        assert_eq!(
+            quantized.get_intervals("a").unwrap().as_slice(),
+            &[iv(5.0, 25.0)]
+        );
+        assert_eq!(
+            quantized.get_intervals("b").unwrap().as_slice(),
+            &[iv(5.0, 25.0)]
+        );
+        assert!(quantized.get_intervals("ghost").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "grid step")]
+    fn zero_step_is_rejected() {
+        let _ = TimeGrid::new(q(0.0), q(0.0));
+    }
+}
@@ -0,0 +1,289 @@
+//! Time-binned feasibility heatmap of a problem before scheduling.
+//!
+//! A [`Heatmap`] cuts the horizon into bins and reports, per bin, how many
+//! tasks could run there and how much work competes for the time. Plotted
+//! over the horizon it is a "pressure map": bins where demand exceeds
+//! capacity will leave tasks out, whatever the algorithm does.
+//!
+//! # Semantics (version 1)
+//!
+//! - Bins are half-open, `[origin + k·width, origin + (k + 1)·width)`, from
+//!   the horizon start; the last bin is clipped to the horizon end.
+//! - A task's **usable** windows are its solution-space windows, clipped to
+//!   the horizon, that are long enough to hold it.
+//! - A task is **feasible** in a bin if some placement inside a usable
+//!   window overlaps the bin for a positive length. A milestone is feasible
+//!   in a bin whose half-open span contains a point of a usable window.
+//! - A task's **demand** is its size spread uniformly over its usable time:
+//!   a bin receives `size × overlap / usable length`. Summed over bins it
+//!   equals the size of every task that fits somewhere.
+//! - **Capacity** is the bin length times the number of resources (1 by
+//!   default).
+//!
+//! Any change to these rules bumps [`HEATMAP_FORMAT_VERSION`].
+//!
+//! # Example
+//!
+//! ```
+//! use virolai::constraints::IntervalConstraint;
+//! use virolai::scheduling_block::{SchedulingBlock, Task};
+//! use virolai::solution_space::{Heatmap, Interval, SolutionSpace};
+//! use qtty::{Quantity, Second};
+//!
+//! #[derive(Debug, Clone)]
+//! struct Job(&'static str);
+//!
+//! impl Task<Second> for Job {
+//!     type SizeUnit = Second;
+//!     type ConstraintLeaf = IntervalConstraint<Second>;
+//!     fn name(&self) -> &str { self.0 }
+//!     fn size(&self) -> Quantity<Second> { Quantity::new(10.0) }
+//! }
+//!
+//! let mut block = SchedulingBlock::<Job, Second>::new();
+//! block.add_task_with_id(Job("a"), Some("a".into())).unwrap();
+//! let mut ss = SolutionSpace::new();
+//! ss.add_interval("a", Interval::from_f64(0.0, 20.0));
+//!
+//! let map = Heatmap::new(&[block], &ss, Interval::from_f64(0.0, 40.0), Quantity::new(10.0));
+//! assert_eq!(map.bins.len(), 4);
+//! assert_eq!(map.bins[0].feasible, 1);
+//! assert_eq!(map.bins[0].demand, 5.0);
+//! assert_eq!(map.bins[2].feasible, 0);
+//! ```
+
+use std::io::Write;
+
+use super::{Interval, SolutionSpace};
+use crate::scheduling_block::{SchedulingBlock, Task};
+use qtty::{Quantity, Unit};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Current version of the heatmap semantics and export format.
+pub const HEATMAP_FORMAT_VERSION: u32 = 1;
+
+/// Header line written by [`Heatmap::write_csv`].
+pub const HEATMAP_CSV_HEADER: &str = "bin_start,bin_end,feasible,demand,capacity";
+
+/// One bin of a [`Heatmap`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HeatmapBin {
+    pub start: f64,
+    pub end: f64,
+    /// Tasks that could run at least partly inside the bin.
+    pub feasible: usize,
+    /// Work expected in the bin, in axis units.
+    pub demand: f64,
+    /// Time available in the bin, in axis units.
+    pub capacity: f64,
+}
+
+impl HeatmapBin {
+    /// Demand over capacity; above `1` the bin is oversubscribed.
+    pub fn pressure(&self) -> f64 {
+        if self.capacity > 0.0 {
+            self.demand / self.capacity
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Versioned, time-binned feasibility heatmap.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Heatmap {
+    pub version: u32,
+    pub bins: Vec<HeatmapBin>,
+}
+
+impl Heatmap {
+    /// Bins `horizon` into `bin_width` steps and measures every task of
+    /// `blocks` against its windows in `solution_space`.
+    ///
+    /// # Panics
+    ///
+    /// If `bin_width` is not positive.
+    pub fn new<T, U, D, E>(
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        solution_space: &SolutionSpace<U>,
+        horizon: Interval<U>,
+        bin_width: Quantity<U>,
+    ) -> Self
+    where
+        T: Task<U>,
+        U: Unit,
+        E: petgraph::EdgeType,
+    {
+        let width = bin_width.value();
+        assert!(width > 0.0, "heatmap bin width must be positive");
+        let (origin, end) = (horizon.start().value(), horizon.end().value());
+
+        let mut bins = Vec::new();
+        let mut start = origin;
+        while start < end {
+            let bin_end = (start + width).min(end);
+            bins.push(HeatmapBin {
+                start,
+                end: bin_end,
+                feasible: 0,
+                demand: 0.0,
+                capacity: bin_end - start,
+            });
+            start = origin + width * bins.len() as f64;
+        }
+
+        for (id, task) in blocks.iter().flat_map(|block| block.tasks()) {
+            let size = task.size_on_axis().value();
+            let usable: Vec<(f64, f64)> = solution_space
+                .get_intervals(id)
+                .map(|set| set.as_slice())
+                .unwrap_or_default()
+                .iter()
+                .map(|w| (w.start().value().max(origin), w.end().value().min(end)))
+                .filter(|(a, b)| b - a >= size)
+                .collect();
+            let total: f64 = usable.iter().map(|(a, b)| b - a).sum();
+
+            for bin in &mut bins {
+                let mut feasible = false;
+                let mut overlap = 0.0;
+                for &(a, b) in &usable {
+                    feasible |= if size > 0.0 {
+                        a < bin.end && b > bin.start
+                    } else {
+                        a < bin.end && b >= bin.start
+                    };
+                    overlap += (b.min(bin.end) - a.max(bin.start)).max(0.0);
+                }
+                if feasible {
+                    bin.feasible += 1;
+                }
+                if total > 0.0 {
+                    bin.demand += size * overlap / total;
+                }
+            }
+        }
+
+        Self {
+            version: HEATMAP_FORMAT_VERSION,
+            bins,
+        }
+    }
+
+    /// Scales every bin's capacity to `resources` parallel resources.
+    pub fn with_resources(mut self, resources: usize) -> Self {
+        for bin in &mut self.bins {
+            bin.capacity = (bin.end - bin.start) * resources as f64;
+        }
+        self
+    }
+
+    /// The bins whose demand exceeds their capacity.
+    pub fn oversubscribed(&self) -> impl Iterator<Item = &HeatmapBin> + '_ {
+        self.bins.iter().filter(|bin| bin.pressure() > 1.0)
+    }
+
+    /// Writes the bins as CSV with a [`HEATMAP_CSV_HEADER`] line.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "{HEATMAP_CSV_HEADER}")?;
+        for bin in &self.bins {
+            writeln!(
+                writer,
+                "{},{},{},{},{}",
+                bin.start, bin.end, bin.feasible, bin.demand, bin.capacity
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Renders the bins as a CSV string.
+    pub fn to_csv(&self) -> String {
+        let mut out = Vec::new();
+        self.write_csv(&mut out)
+            .expect("writing to a Vec cannot fail");
+        String::from_utf8(out).expect("CSV output is UTF-8")
+    }
+
+    /// Serializes the heatmap as a JSON string.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("heatmap is always valid JSON")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{iv, q, TestTask};
+    use qtty::Second;
+
+    fn fixture() -> (
+        Vec<SchedulingBlock<TestTask, Second>>,
+        SolutionSpace<Second>,
+    ) {
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        for (id, size) in [("a", 10.0), ("b", 10.0), ("m", 0.0), ("big", 50.0)] {
+            block
+                .add_task_with_id(TestTask::new(id, size), Some(id.into()))
+                .unwrap();
+        }
+        let mut ss = SolutionSpace::new();
+        ss.set_intervals("a", vec![iv(0.0, 20.0)]);
+        ss.set_intervals("b", vec![iv(10.0, 20.0), iv(30.0, 35.0)]);
+        ss.set_intervals("m", vec![iv(20.0, 20.0)]);
+        ss.set_intervals("big", vec![iv(0.0, 30.0)]);
+        (vec![block], ss)
+    }
+
+    #[test]
+    fn bins_are_half_open_and_clipped() {
+        let (blocks, ss) = fixture();
+        let map = Heatmap::new(&blocks, &ss, iv(0.0, 35.0), q(10.0));
+        let edges: Vec<_> = map.bins.iter().map(|b| (b.start, b.end)).collect();
+        assert_eq!(
+            edges,
+            vec![(0.0, 10.0), (10.0, 20.0), (20.0, 30.0), (30.0, 35.0)]
+        );
+        assert_eq!(map.bins[3].capacity, 5.0);
+    }
+
+    #[test]
+    fn counts_feasible_tasks_and_spreads_demand() {
+        let (blocks, ss) = fixture();
+        let map = Heatmap::new(&blocks, &ss, iv(0.0, 40.0), q(10.0));
+        let feasible: Vec<_> = map.bins.iter().map(|b| b.feasible).collect();
+        // "b"'s second window is too short; "big" fits nowhere; the milestone
+        // at t = 20 belongs to the bin starting there.
+        assert_eq!(feasible, vec![1, 2, 1, 0]);
+        let demand: Vec<_> = map.bins.iter().map(|b| b.demand).collect();
+        assert_eq!(demand, vec![5.0, 15.0, 0.0, 0.0]);
+        assert_eq!(map.oversubscribed().count(), 1);
+        assert_eq!(map.bins[1].pressure(), 1.5);
+        assert_eq!(map.clone().with_resources(2).bins[1].pressure(), 0.75);
+    }
+
+    #[test]
+    fn csv_export() {
+        let (blocks, ss) = fixture();
+        let map = Heatmap::new(&blocks, &ss, iv(0.0, 20.0), q(10.0));
+        assert_eq!(
+            map.to_csv(),
+            "bin_start,bin_end,feasible,demand,capacity\n0,10,1,5,10\n10,20,2,15,10\n"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_export() {
+        let (blocks, ss) = fixture();
+        let map = Heatmap::new(&blocks, &ss, iv(0.0, 20.0), q(10.0));
+        let json: serde_json::Value = serde_json::from_str(&map.to_json()).unwrap();
+        assert_eq!(json["version"], 1);
+        assert_eq!(json["bins"][1]["feasible"], 2);
+        assert_eq!(json["bins"][1]["demand"], 15.0);
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::fmt::Display;
 
+use crate::constraints::ConstraintError;
 use qtty::{Quantity, Unit};
 
 /// Continuous range `[start, end)` where a task may be scheduled.
@@ -18,9 +19,11 @@ pub struct Interval<U: Unit> {
 impl<U: Unit> Interval<U> {
     /// Creates interval `[start, end)`.
     ///
+    /// Use [`try_new`](Self::try_new) for bounds read from external data.
+    ///
     /// # Panics
     ///
-    /// Panics if `start > end`.
+    /// Panics if `start > end` or either bound is NaN.
     pub const fn new(start: Quantity<U>, end: Quantity<U>) -> Self {
         assert!(
             start.value() <= end.value(),
@@ -29,6 +32,29 @@ impl<U: Unit> Interval<U> {
         Self { start, end }
     }
 
+    /// Creates interval `[start, end)`, rejecting bounds that are NaN or
+    /// infinite or out of order.
+    ///
+    /// # Errors
+    ///
+    /// [`ConstraintError::InvalidBound`] with the offending bounds.
+    pub fn try_new(start: Quantity<U>, end: Quantity<U>) -> Result<Self, ConstraintError> {
+        let (s, e) = (start.value(), end.value());
+        if s.is_finite() && e.is_finite() && s <= e {
+            Ok(Self { start, end })
+        } else {
+            Err(ConstraintError::InvalidBound { start: s, end: e })
+        }
+    }
+
+    /// Returns an error unless both bounds are finite.
+    ///
+    /// Intervals built with [`new`](Self::new) may still hold infinite
+    /// bounds; ingestion points call this to keep them out of stored data.
+    pub fn validate(&self) -> Result<(), ConstraintError> {
+        Self::try_new(self.start, self.end).map(|_| ())
+    }
+
     pub const fn from_f64(start: f64, end: f64) -> Self {
         Self::new(Quantity::<U>::new(start), Quantity::<U>::new(end))
     }
@@ -45,6 +71,14 @@ impl<U: Unit> Interval<U> {
         self.end - self.start
     }
 
+    /// Returns true if the interval has zero width (`start == end`).
+    ///
+    /// An empty interval contains no position; it is how a milestone's
+    /// placement is represented.
+    pub const fn is_empty(&self) -> bool {
+        self.start.value() == self.end.value()
+    }
+
     /// Converts this interval to another unit of the same dimension.
     ///
     /// # Example
@@ -97,8 +131,13 @@ impl<U: Unit> Interval<U> {
     }
 
     /// Returns true if task of `size` fits starting at `start_position`.
+    ///
+    /// A zero-size task (milestone) fits anywhere in `[start, end]`: an instant
+    /// on the closing edge still belongs to the interval, so a milestone can
+    /// mark the end of work that fills it.
     pub fn can_fit(&self, start_position: Quantity<U>, size: Quantity<U>) -> bool {
-        self.contains(start_position) && (start_position + size).value() <= self.end.value()
+        self.start.value() <= start_position.value()
+            && (start_position + size).value() <= self.end.value()
     }
 }
 
@@ -139,6 +178,12 @@ impl<'de, U: Unit> serde::Deserialize<'de> for Interval<U> {
         }
 
         let raw = Raw::deserialize(deserializer)?;
+        if raw.start.partial_cmp(&raw.end).is_none_or(|o| o.is_gt()) {
+            return Err(serde::de::Error::custom(format!(
+                "interval start {} is after end {}",
+                raw.start, raw.end
+            )));
+        }
         Ok(Self::new(
             Quantity::<U>::new(raw.start),
             Quantity::<U>::new(raw.end),
@@ -146,6 +191,24 @@ impl<'de, U: Unit> serde::Deserialize<'de> for Interval<U> {
     }
 }
 
+#[cfg(feature = "schema")]
+impl<U: Unit> schemars::JsonSchema for Interval<U> {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Interval".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "object",
+            "properties": {
+                "start": { "type": "number" },
+                "end": { "type": "number" }
+            },
+            "required": ["start", "end"]
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,6 +222,37 @@ mod tests {
         assert_eq!(interval.end().value(), 100.0);
     }
 
+    #[test]
+    fn test_try_new_rejects_non_finite_and_reversed() {
+        let q = Quantity::<Second>::new;
+        assert!(Interval::try_new(q(0.0), q(10.0)).is_ok());
+        assert!(Interval::try_new(q(5.0), q(5.0)).is_ok());
+        for (start, end) in [
+            (f64::NAN, 10.0),
+            (0.0, f64::NAN),
+            (0.0, f64::INFINITY),
+            (f64::NEG_INFINITY, 0.0),
+            (10.0, 0.0),
+        ] {
+            assert!(
+                matches!(
+                    Interval::try_new(q(start), q(end)),
+                    Err(ConstraintError::InvalidBound { .. })
+                ),
+                "[{start}, {end})"
+            );
+        }
+        assert!(Interval::<Second>::from_f64(0.0, f64::INFINITY)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Interval start must be <= end")]
+    fn test_new_panics_on_nan() {
+        Interval::<Second>::from_f64(f64::NAN, 1.0);
+    }
+
     #[test]
     fn test_interval_to_conversion() {
         let interval_sec = Interval::new(
@@ -283,6 +377,22 @@ mod tests {
         assert!(!interval.can_fit(Quantity::<Second>::new(1.0), Quantity::<Second>::new(100.0)));
     }
 
+    #[test]
+    fn test_zero_width_is_empty() {
+        assert!(Interval::<Second>::from_f64(5.0, 5.0).is_empty());
+        assert!(!Interval::<Second>::from_f64(5.0, 6.0).is_empty());
+    }
+
+    #[test]
+    fn test_can_fit_milestone_on_closed_edges() {
+        let interval = Interval::<Second>::from_f64(10.0, 20.0);
+        let zero = Quantity::<Second>::new(0.0);
+        assert!(interval.can_fit(Quantity::new(10.0), zero));
+        assert!(interval.can_fit(Quantity::new(20.0), zero));
+        assert!(!interval.can_fit(Quantity::new(20.5), zero));
+        assert!(!interval.can_fit(Quantity::new(9.5), zero));
+    }
+
     #[test]
     fn test_contains_boundary() {
         let interval = Interval::new(Quantity::<Second>::new(10.0), Quantity::<Second>::new(20.0));
@@ -303,4 +413,12 @@ mod tests {
         assert_eq!(restored.start().value(), 10.0);
         assert_eq!(restored.end().value(), 50.0);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_reversed_bounds() {
+        let err =
+            serde_json::from_str::<Interval<Second>>(r#"{"start": 5.0, "end": 1.0}"#).unwrap_err();
+        assert!(err.to_string().contains("after end"));
+    }
 }
@@ -12,7 +12,7 @@ use std::fmt::Display;
 use std::ops::{Deref, Index, RangeFull};
 
 use super::interval::Interval;
-use qtty::Unit;
+use qtty::{Quantity, Unit};
 
 /// A sorted, non-overlapping set of half-open intervals.
 ///
@@ -172,6 +172,25 @@ impl<U: Unit> IntervalSet<U> {
     pub fn as_slice(&self) -> &[Interval<U>] {
         &self.0
     }
+
+    /// Returns the contiguous run of intervals that overlap `range`.
+    ///
+    /// An interval is included when `end > range.start` and
+    /// `start < range.end`. Because the set is canonical, both starts and ends
+    /// are sorted, so the run is located with two binary searches.
+    ///
+    /// # Complexity
+    ///
+    /// O(log n).
+    pub fn query_overlapping(&self, range: Interval<U>) -> &[Interval<U>] {
+        let lo = self
+            .0
+            .partition_point(|i| i.end().value() <= range.start().value());
+        let hi = self
+            .0
+            .partition_point(|i| i.start().value() < range.end().value());
+        &self.0[lo..hi.max(lo)]
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────
@@ -189,6 +208,45 @@ impl<U: Unit> IntervalSet<U> {
         crate::constraints::operations::compute_intersection(&self.0, &other.0)
     }
 
+    /// Returns the intersection of `self` with every set in `others`.
+    ///
+    /// Walks all inputs in one pass instead of intersecting pairwise, so no
+    /// intermediate sets are built. Prefer it over chained
+    /// [`intersection`](Self::intersection) calls when combining several sets.
+    pub fn intersect_many<S: AsRef<[Interval<U>]>>(&self, others: &[S]) -> IntervalSet<U> {
+        let mut lists = Vec::with_capacity(others.len() + 1);
+        lists.push(self.as_slice());
+        lists.extend(others.iter().map(AsRef::as_ref));
+        crate::constraints::operations::compute_intersection_many(&lists)
+    }
+
+    /// Returns the intervals overlapping `bounds`, each clipped to `bounds`.
+    ///
+    /// Locates the overlapping run with [`query_overlapping`](Self::query_overlapping)
+    /// and clamps every interval in it with plain `max`/`min`, which never
+    /// fails for that run and so needs no per-interval `Option`.
+    pub fn clipped(
+        &self,
+        bounds: Interval<U>,
+    ) -> impl DoubleEndedIterator<Item = Interval<U>> + ExactSizeIterator + '_ {
+        let (lo, hi) = (bounds.start().value(), bounds.end().value());
+        self.query_overlapping(bounds)
+            .iter()
+            .map(move |i| Interval::from_f64(i.start().value().max(lo), i.end().value().min(hi)))
+    }
+
+    /// Returns the valid start times for a task of `duration` in these
+    /// windows; see [`compute_start_times`](crate::constraints::operations::compute_start_times).
+    pub fn start_times(&self, duration: Quantity<U>) -> IntervalSet<U> {
+        crate::constraints::operations::compute_start_times(&self.0, duration)
+    }
+
+    /// Returns the time a task of `duration` may occupy when started within
+    /// these start windows; the inverse of [`start_times`](Self::start_times).
+    pub fn occupancy(&self, duration: Quantity<U>) -> IntervalSet<U> {
+        crate::constraints::operations::compute_occupancy(&self.0, duration)
+    }
+
     /// Returns the complement of `self` within `bounds`.
     pub fn complement(&self, bounds: Interval<U>) -> IntervalSet<U> {
         crate::constraints::operations::compute_complement(self.0.clone(), bounds)
@@ -394,6 +452,17 @@ impl<'de, U: Unit> serde::Deserialize<'de> for IntervalSet<U> {
     }
 }
 
+#[cfg(feature = "schema")]
+impl<U: Unit> schemars::JsonSchema for IntervalSet<U> {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "IntervalSet".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        Vec::<Interval<U>>::json_schema(generator)
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────
 // Tests
 // ─────────────────────────────────────────────────────────────────────
@@ -650,6 +719,61 @@ mod tests {
         assert_eq!(c[2], iv(80.0, 100.0));
     }
 
+    #[test]
+    fn intersect_many_combines_all_sets() {
+        let windows = IntervalSet::from(vec![iv(0.0, 40.0), iv(60.0, 100.0)]);
+        let horizon = IntervalSet::from(iv(10.0, 90.0));
+        let dynamic = IntervalSet::from(vec![iv(30.0, 70.0)]);
+        let i = windows.intersect_many(&[horizon.clone(), dynamic.clone()]);
+        assert_eq!(i, vec![iv(30.0, 40.0), iv(60.0, 70.0)]);
+        assert_eq!(i, windows.intersection(&horizon).intersection(&dynamic));
+        assert_eq!(windows.intersect_many::<IntervalSet<Second>>(&[]), windows);
+    }
+
+    #[test]
+    fn clipped_clamps_the_overlapping_run() {
+        let set = IntervalSet::from(vec![iv(0.0, 20.0), iv(30.0, 50.0), iv(70.0, 90.0)]);
+        let clipped: Vec<_> = set.clipped(iv(10.0, 80.0)).collect();
+        assert_eq!(
+            clipped,
+            vec![iv(10.0, 20.0), iv(30.0, 50.0), iv(70.0, 80.0)]
+        );
+        assert_eq!(set.clipped(iv(55.0, 65.0)).len(), 0);
+        assert_eq!(set.clipped(iv(20.0, 30.0)).count(), 0);
+    }
+
+    // ── query_overlapping ─────────────────────────────────────────────
+
+    #[test]
+    fn query_overlapping_returns_relevant_run() {
+        let set = IntervalSet::from(vec![
+            iv(0.0, 10.0),
+            iv(20.0, 30.0),
+            iv(40.0, 50.0),
+            iv(60.0, 70.0),
+        ]);
+        assert_eq!(
+            set.query_overlapping(iv(25.0, 45.0)),
+            &[iv(20.0, 30.0), iv(40.0, 50.0)]
+        );
+        assert_eq!(set.query_overlapping(iv(0.0, 100.0)).len(), 4);
+    }
+
+    #[test]
+    fn query_overlapping_half_open_boundaries() {
+        let set = IntervalSet::from(vec![iv(0.0, 10.0), iv(20.0, 30.0)]);
+        // Touching at an endpoint is not an overlap.
+        assert!(set.query_overlapping(iv(10.0, 20.0)).is_empty());
+        assert_eq!(set.query_overlapping(iv(9.0, 20.0)), &[iv(0.0, 10.0)]);
+        assert!(set.query_overlapping(iv(30.0, 40.0)).is_empty());
+    }
+
+    #[test]
+    fn query_overlapping_empty_set() {
+        let set = IntervalSet::<Second>::new();
+        assert!(set.query_overlapping(iv(0.0, 10.0)).is_empty());
+    }
+
     // ── Display ───────────────────────────────────────────────────────
 
     #[test]
@@ -0,0 +1,97 @@
+//! CSV rows of `task_id,window_start,window_end`.
+//!
+//! ```text
+//! task_id,window_start,window_end
+//! obs-1,0,3600
+//! obs-1,7200,10800
+//! ```
+//!
+//! A task with several windows has one row per window; rows for the same task
+//! need not be adjacent, and overlapping windows are merged on read. Tasks
+//! with no windows cannot be represented and are omitted on write.
+
+use std::io::{BufRead, Write};
+
+use crate::csv::{read_rows, write_header, write_row};
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::Id;
+use qtty::Unit;
+
+pub use crate::csv::CsvError;
+
+/// Header line written by [`write()`].
+pub const HEADER: &str = "task_id,window_start,window_end";
+
+/// Reads a solution space.
+pub fn read<U: Unit, R: BufRead>(reader: R) -> Result<SolutionSpace<U>, CsvError> {
+    let mut space = SolutionSpace::new();
+    for row in read_rows(reader, HEADER)? {
+        space
+            .try_add_interval(row.id, Interval::from_f64(row.start, row.end))
+            .map_err(|source| CsvError::Bound {
+                line: row.line,
+                source,
+            })?;
+    }
+    Ok(space)
+}
+
+/// Writes `space` with a header, sorted by task ID then window start.
+pub fn write<U: Unit, W: Write>(space: &SolutionSpace<U>, mut writer: W) -> Result<(), CsvError> {
+    write_header(&mut writer, HEADER)?;
+    let mut ids: Vec<&Id> = space.ids().collect();
+    ids.sort_unstable();
+    for id in ids {
+        let windows = space.get_intervals(id).expect("id listed by space");
+        for window in windows.as_slice() {
+            write_row(
+                &mut writer,
+                id,
+                window.start().value(),
+                window.end().value(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Renders `space` as a CSV string.
+pub fn to_string<U: Unit>(space: &SolutionSpace<U>) -> String {
+    let mut out = Vec::new();
+    write(space, &mut out).expect("writing to a Vec cannot fail");
+    String::from_utf8(out).expect("CSV output is UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    #[test]
+    fn round_trip_is_sorted() {
+        let mut ss = SolutionSpace::<Second>::new();
+        ss.set_intervals("b", vec![iv(50.0, 60.0), iv(0.0, 10.0)]);
+        ss.set_intervals("a", vec![iv(5.0, 6.0)]);
+
+        let text = to_string(&ss);
+        assert_eq!(
+            text,
+            "task_id,window_start,window_end\na,5,6\nb,0,10\nb,50,60\n"
+        );
+
+        let back: SolutionSpace<Second> = read(text.as_bytes()).unwrap();
+        assert_eq!(
+            back.get_intervals("b").unwrap(),
+            &vec![iv(0.0, 10.0), iv(50.0, 60.0)]
+        );
+        assert_eq!(back.count(), 2);
+    }
+
+    #[test]
+    fn scattered_rows_are_merged() {
+        let input = "x,0,10\ny,0,1\nx,5,20\n";
+        let ss: SolutionSpace<Second> = read(input.as_bytes()).unwrap();
+        assert_eq!(ss.get_intervals("x").unwrap(), &vec![iv(0.0, 20.0)]);
+    }
+}
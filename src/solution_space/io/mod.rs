@@ -0,0 +1,3 @@
+//! Reading and writing solution spaces in exchange formats.
+
+pub mod csv;
@@ -0,0 +1,172 @@
+//! Solution space computed on first access.
+//!
+//! [`SolutionSpace::populate`] evaluates every task's constraint tree up
+//! front, so startup time grows with the total task count even when most
+//! tasks are never looked at — a rolling horizon that only reaches the first
+//! windows, a what-if query about a handful of tasks. [`LazySolutionSpace`]
+//! evaluates a task's constraints the first time its intervals are asked
+//! for and caches the result; construction does no constraint work at all.
+//!
+//! The cache is a plain [`SolutionSpace`], so the tasks computed so far can
+//! be handed to any scheduler through [`cached`](LazySolutionSpace::cached)
+//! after [`ensure`](LazySolutionSpace::ensure)-ing the ones it will need.
+
+use super::populate::task_windows;
+use super::{Interval, IntervalSet, SolutionSpace};
+use crate::scheduling_block::{SchedulingBlock, Task};
+use qtty::Unit;
+
+/// A [`SolutionSpace`] over `blocks` whose entries are computed on demand.
+///
+/// Entries are identical to those [`SolutionSpace::populate`] produces for
+/// the same blocks and range.
+///
+/// # Example
+///
+/// ```
+/// use virolai::solution_space::{Interval, LazySolutionSpace};
+/// use virolai::scheduling_block::{SchedulingBlock, Task};
+/// use virolai::constraints::IntervalConstraint;
+/// use qtty::{Quantity, Second};
+///
+/// #[derive(Debug)]
+/// struct Job;
+///
+/// impl Task<Second> for Job {
+///     type SizeUnit = Second;
+///     type ConstraintLeaf = IntervalConstraint<Second>;
+///     fn name(&self) -> &str { "job" }
+///     fn size(&self) -> Quantity<Second> { Quantity::new(10.0) }
+/// }
+///
+/// let mut block = SchedulingBlock::<Job, Second>::new();
+/// let first = block.add_task(Job);
+/// block.add_task(Job);
+///
+/// let blocks = [block];
+/// let mut lazy = LazySolutionSpace::new(&blocks, Interval::from_f64(0.0, 100.0));
+/// assert_eq!(lazy.computed(), 0);
+/// assert_eq!(lazy.get_intervals(&first).unwrap().len(), 1);
+/// assert_eq!(lazy.computed(), 1);
+/// ```
+#[derive(Debug)]
+pub struct LazySolutionSpace<'a, T, U, D = (), E = petgraph::Directed>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    blocks: &'a [SchedulingBlock<T, U, D, E>],
+    range: Interval<U>,
+    cache: SolutionSpace<U>,
+}
+
+impl<'a, T, U, D, E> LazySolutionSpace<'a, T, U, D, E>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    /// Creates an empty cache over the tasks of `blocks` within `range`.
+    pub fn new(blocks: &'a [SchedulingBlock<T, U, D, E>], range: Interval<U>) -> Self {
+        Self {
+            blocks,
+            range,
+            cache: SolutionSpace::new(),
+        }
+    }
+
+    /// Range the intervals are computed within.
+    pub fn range(&self) -> Interval<U> {
+        self.range
+    }
+
+    /// Intervals of task `id`, computed and cached on first access.
+    ///
+    /// Returns `None` if no block holds the task.
+    pub fn get_intervals(&mut self, id: &str) -> Option<&IntervalSet<U>> {
+        if self.cache.get_intervals(id).is_none() {
+            let task = self.blocks.iter().find_map(|b| b.task_by_id(id))?;
+            self.cache.set_intervals(id, task_windows(task, self.range));
+        }
+        self.cache.get_intervals(id)
+    }
+
+    /// Computes the intervals of every task in `ids` not cached yet.
+    /// Unknown IDs are skipped.
+    pub fn ensure<'i>(&mut self, ids: impl IntoIterator<Item = &'i str>) {
+        for id in ids {
+            let _ = self.get_intervals(id);
+        }
+    }
+
+    /// `true` if the intervals of `id` have been computed.
+    pub fn is_computed(&self, id: &str) -> bool {
+        self.cache.get_intervals(id).is_some()
+    }
+
+    /// Number of tasks computed so far.
+    pub fn computed(&self) -> usize {
+        self.cache.count()
+    }
+
+    /// The tasks computed so far, as a regular solution space.
+    pub fn cached(&self) -> &SolutionSpace<U> {
+        &self.cache
+    }
+
+    /// Computes every remaining task and returns the full solution space.
+    pub fn into_solution_space(mut self) -> SolutionSpace<U> {
+        let blocks = self.blocks;
+        self.ensure(blocks.iter().flat_map(|b| b.tasks().map(|(id, _)| id)));
+        self.cache
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::{ConstraintExpr, IntervalConstraint};
+    use crate::test_utils::{iv, TestTask};
+    use qtty::Second;
+
+    fn blocks() -> Vec<SchedulingBlock<TestTask, Second>> {
+        let mut block = SchedulingBlock::new();
+        let windowed = TestTask::new("w", 10.0).with_constraints(ConstraintExpr::union(vec![
+            ConstraintExpr::leaf(IntervalConstraint::new(iv(0.0, 5.0))),
+            ConstraintExpr::leaf(IntervalConstraint::new(iv(20.0, 40.0))),
+        ]));
+        block.add_task_with_id(windowed, Some("w".into())).unwrap();
+        block
+            .add_task_with_id(TestTask::new("free", 10.0), Some("free".into()))
+            .unwrap();
+        vec![block]
+    }
+
+    #[test]
+    fn computes_on_first_access_only() {
+        let blocks = blocks();
+        let mut lazy = LazySolutionSpace::new(&blocks, iv(0.0, 100.0));
+        assert!(!lazy.is_computed("w"));
+        assert_eq!(
+            lazy.get_intervals("w").unwrap().as_slice(),
+            &[iv(20.0, 40.0)]
+        );
+        assert!(lazy.is_computed("w"));
+        assert!(!lazy.is_computed("free"));
+        assert!(lazy.get_intervals("ghost").is_none());
+        assert_eq!(lazy.computed(), 1);
+        assert!(lazy.cached().get_intervals("free").is_none());
+    }
+
+    #[test]
+    fn matches_eager_population() {
+        let blocks = blocks();
+        let eager = SolutionSpace::populate(&blocks, iv(0.0, 100.0));
+        let lazy = LazySolutionSpace::new(&blocks, iv(0.0, 100.0)).into_solution_space();
+        assert_eq!(lazy.count(), eager.count());
+        for id in ["w", "free"] {
+            assert_eq!(lazy.get_intervals(id), eager.get_intervals(id));
+        }
+    }
+}
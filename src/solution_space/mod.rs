@@ -4,12 +4,24 @@
 //! feasible positions. Can be used for both tasks and resources (instruments).
 //! Users populate it with intervals computed from constraints.
 
+pub mod io;
+
+mod blackout;
+mod grid;
+mod heatmap;
 mod interval;
 mod interval_set;
+mod lazy;
+mod pool;
 mod populate;
 mod space;
 
+pub use blackout::BlackoutSet;
+pub use grid::TimeGrid;
+pub use heatmap::{Heatmap, HeatmapBin, HEATMAP_CSV_HEADER, HEATMAP_FORMAT_VERSION};
 pub use interval::Interval;
 pub use interval_set::IntervalSet;
+pub use lazy::LazySolutionSpace;
+pub use pool::{IntervalPool, PoolStats};
 pub use populate::collect_intervals;
 pub use space::SolutionSpace;
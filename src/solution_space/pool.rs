@@ -0,0 +1,140 @@
+//! Recycled buffers for short-lived interval sets.
+//!
+//! Dynamic-constraint evaluation builds many [`IntervalSet`]s that are
+//! dropped within the same scheduling iteration. [`IntervalPool`] keeps the
+//! backing `Vec`s of sets handed back with [`recycle`](IntervalPool::recycle)
+//! and hands them out again from [`take`](IntervalPool::take), so a loop that
+//! returns its temporaries at the end of each iteration stops allocating once
+//! the pool has warmed up.
+
+use super::interval::Interval;
+use super::interval_set::IntervalSet;
+use qtty::Unit;
+
+/// A free list of interval buffers.
+///
+/// Buffers are returned empty but keep their capacity. At most
+/// [`max_retained`](Self::with_max_retained) buffers are kept; further
+/// recycled sets are dropped, so one unusually wide iteration cannot pin
+/// memory for the rest of the run.
+#[derive(Debug)]
+pub struct IntervalPool<U: Unit> {
+    free: Vec<Vec<Interval<U>>>,
+    max_retained: usize,
+    stats: PoolStats,
+}
+
+/// Counters reported by [`IntervalPool::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Buffers served from the free list.
+    pub reused: usize,
+    /// Buffers allocated because the free list was empty.
+    pub allocated: usize,
+}
+
+impl<U: Unit> IntervalPool<U> {
+    /// Buffers retained by a pool created with [`new`](Self::new).
+    pub const DEFAULT_MAX_RETAINED: usize = 64;
+
+    /// Creates an empty pool.
+    pub fn new() -> Self {
+        Self {
+            free: Vec::new(),
+            max_retained: Self::DEFAULT_MAX_RETAINED,
+            stats: PoolStats::default(),
+        }
+    }
+
+    /// Sets the number of idle buffers the pool keeps.
+    pub fn with_max_retained(mut self, max_retained: usize) -> Self {
+        self.max_retained = max_retained;
+        self.free.truncate(max_retained);
+        self
+    }
+
+    /// Returns an empty buffer, reusing a recycled one when available.
+    pub fn take(&mut self) -> Vec<Interval<U>> {
+        match self.free.pop() {
+            Some(buffer) => {
+                self.stats.reused += 1;
+                buffer
+            }
+            None => {
+                self.stats.allocated += 1;
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns a copy of `set` backed by a pooled buffer.
+    pub fn copy_of(&mut self, set: &IntervalSet<U>) -> IntervalSet<U> {
+        let mut buffer = self.take();
+        buffer.extend_from_slice(set);
+        IntervalSet::from_sorted_unchecked(buffer)
+    }
+
+    /// Hands the buffer behind `set` back to the pool.
+    pub fn recycle(&mut self, set: IntervalSet<U>) {
+        let mut buffer = set.into_inner();
+        if self.free.len() < self.max_retained && buffer.capacity() > 0 {
+            buffer.clear();
+            self.free.push(buffer);
+        }
+    }
+
+    /// Number of idle buffers currently held.
+    pub fn retained(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Buffers reused and allocated since the pool was created.
+    pub fn stats(&self) -> PoolStats {
+        self.stats
+    }
+}
+
+impl<U: Unit> Default for IntervalPool<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    #[test]
+    fn recycled_buffers_are_reused() {
+        let mut pool = IntervalPool::<Second>::new();
+        let set = IntervalSet::from(vec![iv(0.0, 10.0), iv(20.0, 30.0)]);
+        let copy = pool.copy_of(&set);
+        assert_eq!(copy, set);
+        pool.recycle(copy);
+        assert_eq!(pool.retained(), 1);
+
+        let buffer = pool.take();
+        assert!(buffer.is_empty());
+        assert!(buffer.capacity() >= 2);
+        assert_eq!(
+            pool.stats(),
+            PoolStats {
+                reused: 1,
+                allocated: 1
+            }
+        );
+    }
+
+    #[test]
+    fn retention_is_capped() {
+        let mut pool = IntervalPool::<Second>::new().with_max_retained(1);
+        pool.recycle(IntervalSet::from(iv(0.0, 1.0)));
+        pool.recycle(IntervalSet::from(iv(0.0, 1.0)));
+        assert_eq!(pool.retained(), 1);
+        // Buffers without capacity are not worth keeping.
+        pool.recycle(IntervalSet::new());
+        assert_eq!(pool.retained(), 1);
+    }
+}
@@ -1,7 +1,7 @@
 //! Solution space population utilities.
 
 use super::Interval;
-use crate::constraints::Constraint;
+use crate::constraints::{Constraint, ConstraintError, Relaxable};
 use crate::scheduling_block::{SchedulingBlock, Task};
 use crate::Id;
 use qtty::{Quantity, Unit};
@@ -35,6 +35,26 @@ where
     intervals
 }
 
+/// Windows of `task` within `range`: its constraint intervals long enough
+/// to hold it, or the whole range if it is unconstrained.
+pub(crate) fn task_windows<T, U>(task: &T, range: Interval<U>) -> Vec<Interval<U>>
+where
+    T: Task<U>,
+    U: Unit,
+{
+    // Use size_on_axis() to get duration in axis units
+    let task_size = task.size_on_axis();
+    task.constraints().map_or_else(
+        || vec![range],
+        |ct| {
+            ct.compute_intervals(range)
+                .into_iter()
+                .filter(|i| i.duration().value() >= task_size.value())
+                .collect::<Vec<_>>()
+        },
+    )
+}
+
 impl<U: Unit> super::SolutionSpace<U> {
     /// Populates a solution space from multiple scheduling blocks.
     ///
@@ -70,6 +90,10 @@ impl<U: Unit> super::SolutionSpace<U> {
     /// );
     /// let solution_space = SolutionSpace::populate(&[block], range);
     /// ```
+    #[cfg_attr(
+        feature = "trace",
+        tracing::instrument(level = "debug", skip_all, fields(blocks = blocks.len(), range = %range))
+    )]
     pub fn populate<T, D, E>(
         blocks: &[crate::scheduling_block::SchedulingBlock<T, U, D, E>],
         range: Interval<U>,
@@ -77,22 +101,70 @@ impl<U: Unit> super::SolutionSpace<U> {
     where
         T: crate::scheduling_block::Task<U>,
         E: petgraph::EdgeType,
+    {
+        Self::from_hashmap(windows_by_id(blocks, range))
+    }
+
+    /// Like [`populate`](Self::populate), but rejects windows with
+    /// non-finite bounds, as produced by an unbounded `range` or corrupted
+    /// constraint data. Entry points that read problems from outside the
+    /// process populate through this.
+    ///
+    /// # Errors
+    ///
+    /// [`ConstraintError::InvalidBound`] with the bounds of an offending
+    /// window.
+    pub fn try_populate<T, D, E>(
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        range: Interval<U>,
+    ) -> Result<Self, ConstraintError>
+    where
+        T: Task<U>,
+        E: petgraph::EdgeType,
+    {
+        Self::try_from_hashmap(windows_by_id(blocks, range))
+    }
+
+    /// Populates a solution space with every task's constraints evaluated at
+    /// relaxation `level`.
+    ///
+    /// Identical to [`populate`](Self::populate) at level 0. See
+    /// [`Relaxable`](crate::constraints::Relaxable) for how levels map onto
+    /// a constraint tree.
+    #[cfg_attr(
+        feature = "trace",
+        tracing::instrument(level = "debug", skip_all, fields(blocks = blocks.len(), range = %range))
+    )]
+    pub fn populate_at_level<T, D, E>(
+        blocks: &[SchedulingBlock<T, U, D, E>],
+        range: Interval<U>,
+        level: usize,
+    ) -> Self
+    where
+        T: Task<U>,
+        T::ConstraintLeaf: Relaxable<U>,
+        E: petgraph::EdgeType,
     {
         let map = blocks
             .iter()
             .flat_map(|block| block.tasks())
             .map(|(id, task)| {
-                // Use size_on_axis() to get duration in axis units
                 let task_size = task.size_on_axis();
                 let intervals = task.constraints().map_or_else(
                     || vec![range],
                     |ct| {
-                        ct.compute_intervals(range)
+                        ct.compute_intervals_at(range, level)
                             .into_iter()
                             .filter(|i| i.duration().value() >= task_size.value())
                             .collect::<Vec<_>>()
                     },
                 );
+                #[cfg(feature = "trace")]
+                tracing::trace!(
+                    task = id,
+                    windows = intervals.len(),
+                    "constraints evaluated"
+                );
                 (id.to_owned(), intervals)
             })
             .collect::<HashMap<Id, Vec<Interval<U>>>>();
@@ -101,6 +173,32 @@ impl<U: Unit> super::SolutionSpace<U> {
     }
 }
 
+/// Every task's windows within `range`, keyed by task ID.
+fn windows_by_id<T, U, D, E>(
+    blocks: &[SchedulingBlock<T, U, D, E>],
+    range: Interval<U>,
+) -> HashMap<Id, Vec<Interval<U>>>
+where
+    T: Task<U>,
+    U: Unit,
+    E: petgraph::EdgeType,
+{
+    blocks
+        .iter()
+        .flat_map(|block| block.tasks())
+        .map(|(id, task)| {
+            let intervals = task_windows(task, range);
+            #[cfg(feature = "trace")]
+            tracing::trace!(
+                task = id,
+                windows = intervals.len(),
+                "constraints evaluated"
+            );
+            (id.to_owned(), intervals)
+        })
+        .collect::<HashMap<Id, Vec<Interval<U>>>>()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -171,4 +269,69 @@ mod tests {
         assert!(space.get_intervals(&id1).is_some());
         assert!(space.get_intervals(&id2).is_some());
     }
+
+    #[test]
+    fn try_populate_rejects_an_unbounded_range() {
+        let mut block: SchedulingBlock<TestTask, Second> = SchedulingBlock::new();
+        block.add_task(TestTask::new("a", 10.0));
+        let blocks = [block];
+
+        let bounded = Interval::from_f64(0.0, 100.0);
+        assert!(super::super::SolutionSpace::try_populate(&blocks, bounded).is_ok());
+        let unbounded = Interval::from_f64(0.0, f64::INFINITY);
+        assert!(matches!(
+            super::super::SolutionSpace::try_populate(&blocks, unbounded),
+            Err(ConstraintError::InvalidBound { .. })
+        ));
+    }
+
+    #[test]
+    fn populate_at_level_uses_relaxed_windows() {
+        use crate::constraints::RelaxationLadder;
+        use crate::solution_space::SolutionSpace;
+
+        #[derive(Debug)]
+        struct LadderTask;
+
+        impl Task<Second> for LadderTask {
+            type SizeUnit = Second;
+            type ConstraintLeaf = RelaxationLadder<IntervalConstraint<Second>>;
+
+            fn name(&self) -> &str {
+                "ladder"
+            }
+
+            fn size(&self) -> Quantity<Second> {
+                Quantity::new(10.0)
+            }
+
+            fn constraints(&self) -> Option<&ConstraintExpr<Self::ConstraintLeaf>> {
+                static TREE: std::sync::OnceLock<
+                    ConstraintExpr<RelaxationLadder<IntervalConstraint<Second>>>,
+                > = std::sync::OnceLock::new();
+                Some(TREE.get_or_init(|| {
+                    ConstraintExpr::leaf(
+                        RelaxationLadder::new(IntervalConstraint::new(Interval::from_f64(
+                            0.0, 5.0,
+                        )))
+                        .then(IntervalConstraint::new(Interval::from_f64(0.0, 20.0))),
+                    )
+                }))
+            }
+        }
+
+        let mut block: SchedulingBlock<LadderTask, Second> = SchedulingBlock::new();
+        let id = block.add_task(LadderTask);
+        let range = Interval::from_f64(0.0, 100.0);
+        let blocks = [block];
+
+        let nominal = SolutionSpace::populate_at_level(&blocks, range, 0);
+        assert!(nominal.get_intervals(&id).unwrap().is_empty());
+
+        let relaxed = SolutionSpace::populate_at_level(&blocks, range, 1);
+        assert_eq!(
+            relaxed.get_intervals(&id).unwrap()[0],
+            Interval::from_f64(0.0, 20.0)
+        );
+    }
 }
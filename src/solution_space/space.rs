@@ -3,12 +3,15 @@
 //! The [`SolutionSpace`] acts as a lookup table that schedulers query to find
 //! feasible positions. Users populate it with intervals computed from constraints.
 
+use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::hash::Hash;
 
 use super::interval::Interval;
 use super::interval_set::IntervalSet;
-use crate::Id;
+use crate::constraints::ConstraintError;
+use crate::{Id, TaskKey};
 use qtty::{Quantity, Unit};
 
 /// Collection of valid intervals where tasks may be scheduled.
@@ -18,11 +21,12 @@ use qtty::{Quantity, Unit};
 ///
 /// # Design
 ///
-/// - Uses task IDs (`String`) as stable keys, avoiding lifetime issues
+/// - Uses task IDs (any [`TaskKey`], [`Id`] by default) as stable keys,
+///   avoiding lifetime issues
 /// - Tasks without constraints get a single interval spanning [start, end]
 /// - Each task maintains its own sorted, non-overlapping interval list
-#[derive(Debug)]
-pub struct SolutionSpace<U: Unit>(HashMap<Id, IntervalSet<U>>);
+#[derive(Debug, Clone)]
+pub struct SolutionSpace<U: Unit, I: TaskKey = Id>(HashMap<I, IntervalSet<U>>);
 
 /// Binary search to find interval containing a position in sorted list.
 fn find_interval_containing_sorted<U: Unit>(
@@ -34,15 +38,27 @@ fn find_interval_containing_sorted<U: Unit>(
 }
 
 impl<U: Unit> SolutionSpace<U> {
+    /// Creates an empty solution space keyed by [`Id`].
+    ///
+    /// For other key types use [`SolutionSpace::default`].
     pub fn new() -> Self {
-        Self(HashMap::new())
+        Self::default()
     }
 
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(HashMap::with_capacity(capacity))
+    }
+}
+
+impl<U: Unit, I: TaskKey> SolutionSpace<U, I> {
     /// Creates a [`SolutionSpace`] from a pre-built map.
     ///
     /// Each vector is **normalized** (sorted by start, overlapping intervals
     /// merged) so that all binary-search queries remain correct.
-    pub fn from_hashmap(map: HashMap<Id, Vec<Interval<U>>>) -> Self {
+    ///
+    /// Bounds are not checked; use [`try_from_hashmap`](Self::try_from_hashmap)
+    /// for untrusted input.
+    pub fn from_hashmap(map: HashMap<I, Vec<Interval<U>>>) -> Self {
         let canonical = map
             .into_iter()
             .map(|(id, intervals)| (id, IntervalSet::from(intervals)))
@@ -50,41 +66,117 @@ impl<U: Unit> SolutionSpace<U> {
         Self(canonical)
     }
 
-    pub fn with_capacity(capacity: usize) -> Self {
-        Self(HashMap::with_capacity(capacity))
+    /// Like [`from_hashmap`](Self::from_hashmap), but rejects non-finite
+    /// bounds.
+    ///
+    /// # Errors
+    ///
+    /// [`ConstraintError::InvalidBound`] with the bounds of an offending
+    /// interval.
+    pub fn try_from_hashmap(map: HashMap<I, Vec<Interval<U>>>) -> Result<Self, ConstraintError> {
+        map.values().flatten().try_for_each(Interval::validate)?;
+        Ok(Self::from_hashmap(map))
     }
 
     /// Adds an interval for a specific ID.
     ///
     /// The stored set is kept canonical (sorted, overlaps merged) after
     /// insertion so that binary-search queries remain correct.
-    pub fn add_interval(&mut self, id: impl Into<Id>, interval: Interval<U>) {
+    pub fn add_interval(&mut self, id: impl Into<I>, interval: Interval<U>) {
         self.0.entry(id.into()).or_default().push(interval);
     }
 
+    /// Like [`add_interval`](Self::add_interval), but rejects non-finite
+    /// bounds. The space is left unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// [`ConstraintError::InvalidBound`] with the interval's bounds.
+    pub fn try_add_interval(
+        &mut self,
+        id: impl Into<I>,
+        interval: Interval<U>,
+    ) -> Result<(), ConstraintError> {
+        interval.validate()?;
+        self.add_interval(id, interval);
+        Ok(())
+    }
+
     /// Adds multiple intervals for a specific ID.
     ///
     /// The stored set is kept canonical (sorted, overlaps merged) after
     /// insertion so that binary-search queries remain correct.
-    pub fn add_intervals(&mut self, id: impl Into<Id>, intervals: Vec<Interval<U>>) {
+    pub fn add_intervals(&mut self, id: impl Into<I>, intervals: Vec<Interval<U>>) {
         self.0.entry(id.into()).or_default().extend(intervals);
     }
 
+    /// Like [`add_intervals`](Self::add_intervals), but rejects non-finite
+    /// bounds. The space is left unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// [`ConstraintError::InvalidBound`] with the first offending interval's
+    /// bounds.
+    pub fn try_add_intervals(
+        &mut self,
+        id: impl Into<I>,
+        intervals: Vec<Interval<U>>,
+    ) -> Result<(), ConstraintError> {
+        intervals.iter().try_for_each(Interval::validate)?;
+        self.add_intervals(id, intervals);
+        Ok(())
+    }
+
     /// Sets the intervals for a specific ID, replacing any existing intervals.
     ///
     /// The supplied list is normalized (sorted, overlaps merged) before storage.
-    pub fn set_intervals(&mut self, id: impl Into<Id>, intervals: Vec<Interval<U>>) {
+    pub fn set_intervals(&mut self, id: impl Into<I>, intervals: Vec<Interval<U>>) {
         self.0.insert(id.into(), IntervalSet::from(intervals));
     }
 
+    /// Like [`set_intervals`](Self::set_intervals), but rejects non-finite
+    /// bounds. The space is left unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// [`ConstraintError::InvalidBound`] with the offending interval's bounds.
+    pub fn try_set_intervals(
+        &mut self,
+        id: impl Into<I>,
+        intervals: Vec<Interval<U>>,
+    ) -> Result<(), ConstraintError> {
+        intervals.iter().try_for_each(Interval::validate)?;
+        self.set_intervals(id, intervals);
+        Ok(())
+    }
+
     /// Returns intervals for a specific ID.
-    pub fn get_intervals(&self, id: &str) -> Option<&IntervalSet<U>> {
+    pub fn get_intervals<Q>(&self, id: &Q) -> Option<&IntervalSet<U>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.0.get(id)
     }
 
+    /// Returns the intervals for `id` that overlap `range`.
+    ///
+    /// Located by binary search, so callers that only care about a window
+    /// never touch the windows outside it. Unknown IDs yield an empty slice.
+    pub fn query_overlapping<Q>(&self, id: &Q, range: Interval<U>) -> &[Interval<U>]
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0
+            .get(id)
+            .map(|set| set.query_overlapping(range))
+            .unwrap_or_default()
+    }
+
     /// Returns IDs that have intervals defined.
-    pub fn ids(&self) -> impl Iterator<Item = &str> + '_ {
-        self.0.keys().map(|k| k.as_str())
+    pub fn ids(&self) -> impl Iterator<Item = &I> + '_ {
+        self.0.keys()
     }
 
     /// Returns total number of entries in the solution space.
@@ -98,7 +190,11 @@ impl<U: Unit> SolutionSpace<U> {
     }
 
     /// Removes all intervals for a specific ID.
-    pub fn remove(&mut self, id: &str) -> bool {
+    pub fn remove<Q>(&mut self, id: &Q) -> bool
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.0.remove(id).is_some()
     }
 
@@ -111,7 +207,11 @@ impl<U: Unit> SolutionSpace<U> {
     }
 
     /// Returns true if the specified ID has any interval containing `position` (O(log m) binary search).
-    pub fn contains_position_for(&self, id: &str, position: Quantity<U>) -> bool {
+    pub fn contains_position_for<Q>(&self, id: &Q, position: Quantity<U>) -> bool
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.0
             .get(id)
             .map(|set| find_interval_containing_sorted(set, position).is_some())
@@ -126,11 +226,19 @@ impl<U: Unit> SolutionSpace<U> {
     }
 
     /// Returns true if the specified ID can fit at `position` with given `size` (O(log m) binary search).
-    pub fn can_place(&self, id: &str, position: Quantity<U>, size: Quantity<U>) -> bool {
+    pub fn can_place<Q>(&self, id: &Q, position: Quantity<U>, size: Quantity<U>) -> bool
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.0
             .get(id)
             .map(|set| {
-                find_interval_containing_sorted(set, position)
+                // First window not ending before `position`; unlike
+                // `find_interval_containing_sorted` this keeps a window that
+                // ends exactly there, where a milestone may still be placed.
+                let idx = set.partition_point(|i| i.end().value() < position.value());
+                set.get(idx)
                     .map(|i| i.can_fit(position, size))
                     .unwrap_or(false)
             })
@@ -147,7 +255,11 @@ impl<U: Unit> SolutionSpace<U> {
     }
 
     /// Returns sum of all interval durations for a specific ID.
-    pub fn capacity(&self, id: &str) -> Quantity<U> {
+    pub fn capacity<Q>(&self, id: &Q) -> Quantity<U>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.0
             .get(id)
             .map(|intervals| {
@@ -168,8 +280,26 @@ impl<U: Unit> SolutionSpace<U> {
             .fold(Quantity::new(0.0), |acc, dur| acc + dur)
     }
 
+    /// Returns the valid start times of a task of `size` for a specific ID.
+    ///
+    /// See [`IntervalSet::start_times`]. Unknown IDs yield an empty set.
+    pub fn start_times<Q>(&self, id: &Q, size: Quantity<U>) -> IntervalSet<U>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.0
+            .get(id)
+            .map(|intervals| intervals.start_times(size))
+            .unwrap_or_default()
+    }
+
     /// Returns start of the first interval with capacity ≥ `size` for a specific ID.
-    pub fn find_earliest_fit_for(&self, id: &str, size: Quantity<U>) -> Option<Quantity<U>> {
+    pub fn find_earliest_fit_for<Q>(&self, id: &Q, size: Quantity<U>) -> Option<Quantity<U>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.0.get(id).and_then(|intervals| {
             intervals
                 .iter()
@@ -189,11 +319,15 @@ impl<U: Unit> SolutionSpace<U> {
     }
 
     /// Returns the first interval containing `position` for a specific ID (O(log m) binary search).
-    pub fn find_interval_containing_for(
+    pub fn find_interval_containing_for<Q>(
         &self,
-        id: &str,
+        id: &Q,
         position: Quantity<U>,
-    ) -> Option<&Interval<U>> {
+    ) -> Option<&Interval<U>>
+    where
+        I: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.0
             .get(id)
             .and_then(|set| find_interval_containing_sorted(set, position))
@@ -207,13 +341,13 @@ impl<U: Unit> SolutionSpace<U> {
     }
 }
 
-impl<U: Unit> Default for SolutionSpace<U> {
+impl<U: Unit, I: TaskKey> Default for SolutionSpace<U, I> {
     fn default() -> Self {
-        Self::new()
+        Self(HashMap::new())
     }
 }
 
-impl<U: Unit> Display for SolutionSpace<U> {
+impl<U: Unit, I: TaskKey> Display for SolutionSpace<U, I> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "SolutionSpace {{")?;
         writeln!(f, "  Entries: {}", self.count())?;
@@ -284,6 +418,66 @@ mod tests {
         assert_eq!(space.interval_count(), 0);
     }
 
+    #[test]
+    fn test_try_set_intervals_rejects_infinite_bounds() {
+        let mut space: SolutionSpace<Second> = SolutionSpace::new();
+        space
+            .try_set_intervals("a", vec![Interval::from_f64(0.0, 10.0)])
+            .unwrap();
+        let err = space
+            .try_set_intervals(
+                "a",
+                vec![
+                    Interval::from_f64(20.0, 30.0),
+                    Interval::from_f64(40.0, f64::INFINITY),
+                ],
+            )
+            .unwrap_err();
+        assert!(matches!(err, ConstraintError::InvalidBound { start, .. } if start == 40.0));
+        assert_eq!(space.get_intervals("a").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_try_add_and_from_hashmap_reject_infinite_bounds() {
+        let unbounded = Interval::from_f64(f64::NEG_INFINITY, 0.0);
+        let mut space: SolutionSpace<Second> = SolutionSpace::new();
+        assert!(space.try_add_interval("a", unbounded).is_err());
+        assert!(space
+            .try_add_intervals("a", vec![Interval::from_f64(0.0, 1.0), unbounded])
+            .is_err());
+        assert!(space.is_empty());
+        space
+            .try_add_interval("a", Interval::from_f64(0.0, 1.0))
+            .unwrap();
+        assert_eq!(space.interval_count(), 1);
+
+        let map = HashMap::from([("a".to_string(), vec![unbounded])]);
+        assert!(SolutionSpace::try_from_hashmap(map.clone()).is_err());
+        // The unchecked constructors store the bounds as given.
+        assert_eq!(SolutionSpace::from_hashmap(map).interval_count(), 1);
+        space.set_intervals("b", vec![unbounded]);
+        assert_eq!(space.interval_count(), 2);
+    }
+
+    #[test]
+    fn test_start_times_per_task() {
+        let mut space: SolutionSpace<Second> = SolutionSpace::new();
+        space.set_intervals(
+            "a",
+            vec![
+                Interval::from_f64(0.0, 10.0),
+                Interval::from_f64(20.0, 22.0),
+            ],
+        );
+        let starts = space.start_times("a", Quantity::new(4.0));
+        assert_eq!(starts, vec![Interval::from_f64(0.0, 6.0)]);
+        assert_eq!(
+            starts.first().map(|s| s.start()),
+            space.find_earliest_fit_for("a", Quantity::new(4.0))
+        );
+        assert!(space.start_times("missing", Quantity::new(1.0)).is_empty());
+    }
+
     #[test]
     fn test_populate_empty_blocks() {
         let blocks: Vec<SchedulingBlock<TestTask, Second>> = vec![];
@@ -762,4 +956,35 @@ mod tests {
         assert_eq!(intervals[0].start().value(), 0.0);
         assert_eq!(intervals[0].end().value(), 100.0);
     }
+
+    #[test]
+    fn test_query_overlapping() {
+        let mut space = SolutionSpace::<Second>::new();
+        space.set_intervals(
+            "task1",
+            (0..1000)
+                .map(|i| Interval::from_f64(i as f64 * 10.0, i as f64 * 10.0 + 5.0))
+                .collect(),
+        );
+
+        let hits = space.query_overlapping("task1", Interval::from_f64(42.0, 71.0));
+        assert_eq!(hits.len(), 4);
+        assert_eq!(hits[0].start().value(), 40.0);
+        assert_eq!(hits[3].start().value(), 70.0);
+        assert!(space
+            .query_overlapping("missing", Interval::from_f64(0.0, 10.0))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_custom_key_type() {
+        let mut space: SolutionSpace<Second, u32> = SolutionSpace::default();
+        space.set_intervals(1u32, vec![Interval::from_f64(0.0, 10.0)]);
+        space.add_interval(1u32, Interval::from_f64(5.0, 20.0));
+
+        assert_eq!(space.get_intervals(&1).unwrap().len(), 1);
+        assert_eq!(space.capacity(&1).value(), 20.0);
+        assert!(space.can_place(&1, Quantity::new(0.0), Quantity::new(15.0)));
+        assert_eq!(space.ids().copied().collect::<Vec<_>>(), vec![1]);
+    }
 }
@@ -0,0 +1,189 @@
+//! `Arbitrary` impls for the core types.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use petgraph::EdgeType;
+use qtty::{Quantity, Unit};
+
+use crate::constraints::{ConstraintExpr, DynConstraintKind, IntervalConstraint};
+use crate::generators::SyntheticTask;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{Interval, IntervalSet};
+
+/// Deepest nesting of combinators below the root of a constraint tree.
+const MAX_DEPTH: u32 = 3;
+/// Most tasks in a block.
+const MAX_TASKS: usize = 8;
+
+/// A bound in `[-1e6, 1e6]`, in steps of 0.01.
+fn coordinate(u: &mut Unstructured<'_>) -> Result<f64> {
+    Ok(f64::from(u.int_in_range(-100_000_000..=100_000_000)?) / 100.0)
+}
+
+/// A length in `[0, 1e4]`, in steps of 0.01.
+fn length(u: &mut Unstructured<'_>) -> Result<f64> {
+    Ok(f64::from(u.int_in_range(0..=1_000_000)?) / 100.0)
+}
+
+impl<'a, U: Unit> Arbitrary<'a> for Interval<U> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (a, b) = (coordinate(u)?, coordinate(u)?);
+        Ok(Interval::from_f64(a.min(b), a.max(b)))
+    }
+}
+
+impl<'a, U: Unit> Arbitrary<'a> for IntervalSet<U> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let count = u.int_in_range(0..=MAX_TASKS)?;
+        let intervals = (0..count)
+            .map(|_| Interval::arbitrary(u))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(IntervalSet::from(intervals))
+    }
+}
+
+impl<'a, U: Unit + Send + Sync> Arbitrary<'a> for IntervalConstraint<U> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(IntervalConstraint::new(Interval::arbitrary(u)?))
+    }
+}
+
+impl<'a, C: Arbitrary<'a>> Arbitrary<'a> for ConstraintExpr<C> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        expr(u, MAX_DEPTH)
+    }
+}
+
+fn expr<'a, C: Arbitrary<'a>>(u: &mut Unstructured<'a>, depth: u32) -> Result<ConstraintExpr<C>> {
+    let kind = if depth == 0 {
+        0
+    } else {
+        u.int_in_range(0..=3)?
+    };
+    let children = |u: &mut Unstructured<'a>| {
+        let count = u.int_in_range(1..=3)?;
+        (0..count)
+            .map(|_| expr(u, depth - 1))
+            .collect::<Result<Vec<_>>>()
+    };
+    Ok(match kind {
+        0 => ConstraintExpr::leaf(C::arbitrary(u)?),
+        1 => ConstraintExpr::not(expr(u, depth - 1)?),
+        2 => ConstraintExpr::intersection(children(u)?),
+        _ => ConstraintExpr::union(children(u)?),
+    })
+}
+
+impl<'a> Arbitrary<'a> for DynConstraintKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=9)? {
+            0 => DynConstraintKind::Dependence,
+            1 => DynConstraintKind::Consecutive,
+            2 => DynConstraintKind::Exclusive,
+            3 => DynConstraintKind::simultaneous(length(u)?),
+            4 => DynConstraintKind::start_to_start(coordinate(u)? / 100.0),
+            5 => DynConstraintKind::finish_to_finish(coordinate(u)? / 100.0),
+            6 => DynConstraintKind::start_to_finish(coordinate(u)? / 100.0),
+            7 => DynConstraintKind::Disjoint,
+            8 => DynConstraintKind::chained(length(u)?),
+            _ => DynConstraintKind::max_wait(length(u)?),
+        })
+    }
+}
+
+impl<'a, U: Unit + Send + Sync> Arbitrary<'a> for SyntheticTask<U> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(SyntheticTask {
+            name: String::arbitrary(u)?,
+            size: Quantity::new(length(u)?),
+            priority: u.int_in_range(-100..=100)?,
+            constraints: Option::arbitrary(u)?,
+        })
+    }
+}
+
+impl<'a, T, U, D, E> Arbitrary<'a> for SchedulingBlock<T, U, D, E>
+where
+    T: Task<U> + Arbitrary<'a>,
+    U: Unit,
+    D: Arbitrary<'a>,
+    E: EdgeType,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut block = SchedulingBlock::new();
+        let mut nodes = Vec::new();
+        for i in 0..u.int_in_range(0..=MAX_TASKS)? {
+            let id = block
+                .add_task_with_id(T::arbitrary(u)?, Some(format!("t{i}")))
+                .expect("IDs are unique");
+            nodes.push(block.node_of(&id).expect("task was just added"));
+        }
+        for (i, &from) in nodes.iter().enumerate() {
+            for &to in &nodes[i + 1..] {
+                if u.ratio(1, 4)? {
+                    block
+                        .add_dependency(from, to, D::arbitrary(u)?)
+                        .expect("edges run forward, so no cycle");
+                }
+            }
+        }
+        Ok(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::laws::LawChecker;
+    use crate::rng::SplitMix64;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    /// Raw fuzzer input of `len` bytes drawn from `seed`.
+    fn bytes(seed: u64, len: usize) -> Vec<u8> {
+        let mut rng = SplitMix64::new(seed);
+        (0..len).map(|_| rng.next_u64() as u8).collect()
+    }
+
+    #[test]
+    fn values_respect_invariants() {
+        for seed in 0..200 {
+            let data = bytes(seed, 512);
+            let mut u = Unstructured::new(&data);
+            let interval = Interval::<Second>::arbitrary(&mut u).unwrap();
+            assert!(interval.start() <= interval.end());
+            assert!(interval.start().value().abs() <= 1e6);
+
+            let set = IntervalSet::<Second>::arbitrary(&mut u).unwrap();
+            assert!(set.as_slice().windows(2).all(|w| w[0].end() < w[1].start()));
+
+            let expr = ConstraintExpr::<IntervalConstraint<Second>>::arbitrary(&mut u).unwrap();
+            assert!(LawChecker::new(iv(-2e6, 2e6)).check(&expr).is_empty());
+        }
+    }
+
+    #[test]
+    fn blocks_are_small_and_acyclic() {
+        let mut largest = 0;
+        for seed in 0..200 {
+            let data = bytes(seed, 4096);
+            let block =
+                SchedulingBlock::<SyntheticTask<Second>, Second, DynConstraintKind>::arbitrary(
+                    &mut Unstructured::new(&data),
+                )
+                .unwrap();
+            assert!(block.task_count() <= MAX_TASKS);
+            assert!(block.topological_layers_by(|_| true).is_ok());
+            assert!(block.tasks().all(|(_, t)| t.size().value() >= 0.0));
+            largest = largest.max(block.graph().edge_count());
+        }
+        assert!(largest > 0);
+    }
+
+    #[test]
+    fn empty_input_still_builds() {
+        let mut u = Unstructured::new(&[]);
+        let block =
+            SchedulingBlock::<SyntheticTask<Second>, Second, ()>::arbitrary(&mut u).unwrap();
+        assert_eq!(block.task_count(), 0);
+    }
+}
@@ -0,0 +1,27 @@
+//! Property-testing and fuzzing support.
+//!
+//! With the `proptest` feature, [`strategies`] builds `proptest` strategies
+//! for intervals, interval sets, constraint trees and small scheduling
+//! blocks. With the `arbitrary` feature, the same types implement
+//! [`Arbitrary`](::arbitrary::Arbitrary), for `cargo fuzz` targets.
+//!
+//! Generated values respect the crate's invariants — finite bounds, start
+//! before end, acyclic blocks — so a failing property points at the code
+//! under test. Pair them with [`LawChecker`](crate::constraints::laws::LawChecker)
+//! to check a custom constraint against the contract the schedulers rely on.
+//!
+//! `Arbitrary` is implemented for [`Interval`](crate::solution_space::Interval),
+//! [`IntervalSet`](crate::solution_space::IntervalSet),
+//! [`IntervalConstraint`](crate::constraints::IntervalConstraint),
+//! [`ConstraintExpr`](crate::constraints::ConstraintExpr) (at most
+//! four levels deep),
+//! [`DynConstraintKind`](crate::constraints::DynConstraintKind),
+//! [`SyntheticTask`](crate::generators::SyntheticTask) and
+//! [`SchedulingBlock`](crate::scheduling_block::SchedulingBlock) (at most
+//! eight tasks, edges from earlier to later tasks only). Bounds lie in
+//! `[-1e6, 1e6]`.
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "proptest")]
+pub mod strategies;
@@ -0,0 +1,197 @@
+//! `proptest` strategies for the core types.
+//!
+//! # Example
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use qtty::Second;
+//! use virolai::constraints::laws::LawChecker;
+//! use virolai::solution_space::Interval;
+//! use virolai::testing::strategies;
+//!
+//! proptest!(|(expr in strategies::constraint_expr(
+//!     strategies::interval_constraint::<Second>(0.0..100.0),
+//!     3,
+//! ))| {
+//!     prop_assert!(LawChecker::new(Interval::from_f64(0.0, 100.0)).check(&expr).is_empty());
+//! });
+//! ```
+
+use std::fmt::Debug;
+use std::ops::Range;
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::Index;
+use qtty::{Quantity, Unit};
+
+use crate::constraints::{ConstraintExpr, DynConstraintKind, IntervalConstraint};
+use crate::generators::SyntheticTask;
+use crate::scheduling_block::{SchedulingBlock, Task};
+use crate::solution_space::{Interval, IntervalSet};
+
+/// Intervals with both bounds in `bounds`.
+pub fn interval<U: Unit>(bounds: Range<f64>) -> impl Strategy<Value = Interval<U>> {
+    (bounds.clone(), bounds).prop_map(|(a, b)| Interval::from_f64(a.min(b), a.max(b)))
+}
+
+/// Sets of up to `max_intervals` intervals with bounds in `bounds`, merged
+/// into canonical form.
+pub fn interval_set<U: Unit>(
+    bounds: Range<f64>,
+    max_intervals: usize,
+) -> impl Strategy<Value = IntervalSet<U>> {
+    vec(interval(bounds), 0..=max_intervals).prop_map(IntervalSet::from)
+}
+
+/// Window constraints with bounds in `bounds`.
+pub fn interval_constraint<U: Unit + Send + Sync>(
+    bounds: Range<f64>,
+) -> impl Strategy<Value = IntervalConstraint<U>> {
+    interval(bounds).prop_map(IntervalConstraint::new)
+}
+
+/// Constraint trees over `leaf`, with up to `depth` levels of combinators.
+pub fn constraint_expr<C, S>(leaf: S, depth: u32) -> impl Strategy<Value = ConstraintExpr<C>>
+where
+    C: Clone + Debug + 'static,
+    S: Strategy<Value = C> + 'static,
+{
+    leaf.prop_map(ConstraintExpr::leaf)
+        .prop_recursive(depth, 64, 3, |inner| {
+            prop_oneof![
+                inner.clone().prop_map(ConstraintExpr::not),
+                vec(inner.clone(), 1..=3).prop_map(ConstraintExpr::intersection),
+                vec(inner, 1..=3).prop_map(ConstraintExpr::union),
+            ]
+        })
+}
+
+/// Any edge kind, with lags and bounds in `[-100, 100]`.
+pub fn dyn_constraint_kind() -> impl Strategy<Value = DynConstraintKind> {
+    prop_oneof![
+        Just(DynConstraintKind::Dependence),
+        Just(DynConstraintKind::Consecutive),
+        Just(DynConstraintKind::Exclusive),
+        Just(DynConstraintKind::Disjoint),
+        (0.0..100.0).prop_map(DynConstraintKind::simultaneous),
+        (-100.0..100.0).prop_map(DynConstraintKind::start_to_start),
+        (-100.0..100.0).prop_map(DynConstraintKind::finish_to_finish),
+        (-100.0..100.0).prop_map(DynConstraintKind::start_to_finish),
+        (0.0..100.0).prop_map(DynConstraintKind::max_wait),
+        (0.0..100.0).prop_map(DynConstraintKind::chained),
+    ]
+}
+
+/// Tasks with sizes in `sizes`, priorities in `0..=10` and, half of the
+/// time, a constraint tree of windows in `bounds`.
+pub fn synthetic_task<U: Unit + Send + Sync>(
+    sizes: Range<f64>,
+    bounds: Range<f64>,
+) -> impl Strategy<Value = SyntheticTask<U>> {
+    (
+        sizes,
+        0..=10,
+        proptest::option::of(constraint_expr(interval_constraint(bounds), 2)),
+    )
+        .prop_map(|(size, priority, constraints)| SyntheticTask {
+            name: "synthetic".into(),
+            size: Quantity::new(size),
+            priority,
+            constraints,
+        })
+}
+
+/// Blocks of 1 to `max_tasks` tasks with IDs `t0`, `t1`, … and up to
+/// `2 · max_tasks` edges, each from an earlier task to a later one, so the
+/// graph is acyclic.
+///
+/// # Panics
+///
+/// If `max_tasks` is 0.
+pub fn small_block<T, U, D>(
+    task: impl Strategy<Value = T>,
+    edge: impl Strategy<Value = D>,
+    max_tasks: usize,
+) -> impl Strategy<Value = SchedulingBlock<T, U, D>>
+where
+    T: Task<U>,
+    U: Unit,
+    D: Debug,
+{
+    assert!(max_tasks > 0, "blocks need room for at least one task");
+    (
+        vec(task, 1..=max_tasks),
+        vec((any::<Index>(), any::<Index>(), edge), 0..=2 * max_tasks),
+    )
+        .prop_map(|(tasks, edges)| {
+            let mut block = SchedulingBlock::new();
+            let nodes: Vec<_> = tasks
+                .into_iter()
+                .enumerate()
+                .map(|(i, task)| {
+                    let id = block
+                        .add_task_with_id(task, Some(format!("t{i}")))
+                        .expect("IDs are unique");
+                    block.node_of(&id).expect("task was just added")
+                })
+                .collect();
+            for (a, b, kind) in edges {
+                let (a, b) = (a.index(nodes.len()), b.index(nodes.len()));
+                if a != b {
+                    let (from, to) = (nodes[a.min(b)], nodes[a.max(b)]);
+                    block
+                        .add_dependency(from, to, kind)
+                        .expect("edges run forward, so no cycle");
+                }
+            }
+            block
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::laws::LawChecker;
+    use crate::constraints::Constraint;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    proptest! {
+        #[test]
+        fn intervals_and_sets_are_well_formed(
+            interval in interval::<Second>(-50.0..50.0),
+            set in interval_set::<Second>(0.0..100.0, 6),
+        ) {
+            prop_assert!(interval.start() <= interval.end());
+            prop_assert!(interval.start().value() >= -50.0 && interval.end().value() < 50.0);
+            prop_assert!(set.as_slice().windows(2).all(|w| w[0].end() < w[1].start()));
+        }
+
+        #[test]
+        fn constraint_trees_obey_the_laws(
+            expr in constraint_expr(interval_constraint::<Second>(0.0..100.0), 3),
+        ) {
+            prop_assert!(LawChecker::new(iv(0.0, 100.0)).check(&expr).is_empty());
+        }
+
+        #[test]
+        fn blocks_are_acyclic_with_known_ids(
+            block in small_block::<_, Second, _>(
+                synthetic_task(1.0..10.0, 0.0..100.0),
+                dyn_constraint_kind(),
+                6,
+            ),
+        ) {
+            prop_assert!((1..=6).contains(&block.task_count()));
+            prop_assert!(block.topological_layers_by(|_| true).is_ok());
+            for (id, task) in block.tasks() {
+                prop_assert!(id.starts_with('t'));
+                if let Some(tree) = task.constraints() {
+                    let windows = tree.compute_intervals(iv(0.0, 100.0));
+                    prop_assert!(windows.iter().all(|w| w.end().value() <= 100.0));
+                }
+            }
+        }
+    }
+}
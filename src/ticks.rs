@@ -0,0 +1,299 @@
+//! Integer tick axis for exact schedule arithmetic.
+//!
+//! Placements on a floating-point axis accumulate rounding error: a chain of
+//! ten thousand 0.1 s tasks does not end at exactly 1000 s, and over long
+//! horizons such drift can open or close gaps that decide whether a task
+//! fits. [`Tick`] is a unit whose values are whole numbers. Every integer up
+//! to 2⁵³ is exact in `f64`, so adding, subtracting and comparing tick
+//! quantities never rounds, and `Schedule<Tick>`, `SolutionSpace<Tick>` and
+//! the EST metrics work on it unchanged — with exact results.
+//!
+//! [`TickAxis`] maps a continuous axis onto ticks of a fixed resolution and
+//! back. Inputs are snapped once on the way in, conservatively: windows
+//! shrink to whole ticks inside them and durations round up, so a schedule
+//! found in ticks is feasible on the original axis. Results come back with
+//! a single multiplication per bound, so nothing accumulates.
+//!
+//! ```
+//! use qtty::{Quantity, Second};
+//! use virolai::solution_space::Interval;
+//! use virolai::ticks::TickAxis;
+//!
+//! let axis = TickAxis::new(Quantity::<Second>::new(0.1));
+//! let window = axis
+//!     .window_to_ticks(Interval::from_f64(0.05, 100.0))
+//!     .unwrap()
+//!     .unwrap();
+//! assert_eq!(window.tick_bounds(), (1, 1000));
+//! assert_eq!(axis.duration_to_ticks(Quantity::new(0.25)).unwrap().value(), 3.0);
+//! ```
+
+use crate::schedule::Schedule;
+use crate::solution_space::{Interval, SolutionSpace};
+use crate::TaskKey;
+use qtty::{Dimensionless, Quantity, Unit};
+
+/// One step of an integer time axis.
+///
+/// `Tick` is dimensionless so it cannot be mixed with time units by
+/// accident; cross between them with a [`TickAxis`].
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Tick;
+
+impl Unit for Tick {
+    const RATIO: f64 = 1.0;
+    type Dim = Dimensionless;
+    const SYMBOL: &'static str = "tick";
+}
+
+/// A quantity measured in ticks.
+pub type Ticks = Quantity<Tick>;
+
+/// Largest tick magnitude represented exactly.
+pub const MAX_TICK: i64 = 1 << 53;
+
+/// Relative distance to the nearest integer below which a scaled value is
+/// treated as that integer, absorbing the error of the division itself.
+const SNAP_TOLERANCE: f64 = 1e-9;
+
+/// Error converting a value onto a [`TickAxis`].
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TickError {
+    #[error("value {0} is not finite")]
+    NotFinite(f64),
+    #[error("value {0} is beyond ±2^53 ticks")]
+    OutOfRange(f64),
+}
+
+/// Which way [`TickAxis`] rounds values that fall between ticks.
+#[derive(Clone, Copy)]
+enum Snap {
+    Nearest,
+    Up,
+    Down,
+}
+
+/// A mapping between a continuous axis in `U` and integer ticks.
+///
+/// Tick `n` sits at `origin + n · resolution`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TickAxis<U: Unit> {
+    origin: Quantity<U>,
+    resolution: Quantity<U>,
+}
+
+impl<U: Unit> TickAxis<U> {
+    /// Creates an axis with ticks `resolution` apart, starting at zero.
+    ///
+    /// # Panics
+    ///
+    /// If `resolution` is not positive and finite.
+    pub fn new(resolution: Quantity<U>) -> Self {
+        assert!(
+            resolution.value() > 0.0 && resolution.value().is_finite(),
+            "tick resolution must be positive and finite"
+        );
+        Self {
+            origin: Quantity::new(0.0),
+            resolution,
+        }
+    }
+
+    /// Places tick zero at `origin`.
+    pub fn with_origin(mut self, origin: Quantity<U>) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn origin(&self) -> Quantity<U> {
+        self.origin
+    }
+
+    pub fn resolution(&self) -> Quantity<U> {
+        self.resolution
+    }
+
+    /// Converts an instant to the nearest tick.
+    pub fn to_ticks(&self, t: Quantity<U>) -> Result<Ticks, TickError> {
+        self.scale(t - self.origin, Snap::Nearest)
+    }
+
+    /// Converts a duration to ticks, rounding up so a task never shrinks.
+    pub fn duration_to_ticks(&self, d: Quantity<U>) -> Result<Ticks, TickError> {
+        self.scale(d, Snap::Up)
+    }
+
+    /// Converts a window to the whole ticks it contains.
+    ///
+    /// The start rounds up and the end rounds down. Returns `Ok(None)` when
+    /// no tick-aligned interval of positive length remains.
+    pub fn window_to_ticks(&self, w: Interval<U>) -> Result<Option<Interval<Tick>>, TickError> {
+        let start = self.scale(w.start() - self.origin, Snap::Up)?;
+        let end = self.scale(w.end() - self.origin, Snap::Down)?;
+        Ok((start.value() < end.value()).then(|| Interval::new(start, end)))
+    }
+
+    /// Converts a tick back to the continuous axis.
+    pub fn from_ticks(&self, t: Ticks) -> Quantity<U> {
+        self.origin + self.resolution * t.value()
+    }
+
+    /// Converts a tick interval back to the continuous axis.
+    pub fn interval_from_ticks(&self, i: Interval<Tick>) -> Interval<U> {
+        Interval::new(self.from_ticks(i.start()), self.from_ticks(i.end()))
+    }
+
+    /// Converts every window of `space` with
+    /// [`window_to_ticks`](Self::window_to_ticks), dropping those too short
+    /// to hold a tick.
+    pub fn solution_space_to_ticks<I: TaskKey>(
+        &self,
+        space: &SolutionSpace<U, I>,
+    ) -> Result<SolutionSpace<Tick, I>, TickError> {
+        let mut ticks = SolutionSpace::default();
+        for id in space.ids() {
+            let mut windows = Vec::new();
+            for &w in space.get_intervals(id).into_iter().flatten() {
+                windows.extend(self.window_to_ticks(w)?);
+            }
+            ticks.set_intervals(id.clone(), windows);
+        }
+        Ok(ticks)
+    }
+
+    /// Converts every placement of `schedule` back to the continuous axis.
+    pub fn schedule_from_ticks<I: TaskKey>(&self, schedule: &Schedule<Tick, I>) -> Schedule<U, I> {
+        let mut out = Schedule::default();
+        for (id, interval) in schedule.iter() {
+            out.add(id, self.interval_from_ticks(interval))
+                .expect("an affine map keeps placements disjoint");
+        }
+        out
+    }
+
+    fn scale(&self, q: Quantity<U>, snap: Snap) -> Result<Ticks, TickError> {
+        let x = q.value() / self.resolution.value();
+        if !x.is_finite() {
+            return Err(TickError::NotFinite(q.value()));
+        }
+        let nearest = x.round();
+        let n = if (x - nearest).abs() <= SNAP_TOLERANCE * nearest.abs().max(1.0) {
+            nearest
+        } else {
+            match snap {
+                Snap::Nearest => nearest,
+                Snap::Up => x.ceil(),
+                Snap::Down => x.floor(),
+            }
+        };
+        if n.abs() > MAX_TICK as f64 {
+            return Err(TickError::OutOfRange(q.value()));
+        }
+        Ok(Quantity::new(n))
+    }
+}
+
+impl Interval<Tick> {
+    /// Creates a tick interval from integer bounds.
+    pub fn ticks(start: i64, end: i64) -> Self {
+        Self::from_f64(start as f64, end as f64)
+    }
+
+    /// Returns the bounds as integers.
+    pub fn tick_bounds(&self) -> (i64, i64) {
+        (self.start().value() as i64, self.end().value() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{ESTScheduler, SchedulingAlgorithm};
+    use crate::generators::SyntheticTask;
+    use crate::scheduling_block::SchedulingBlock;
+    use crate::test_utils::iv;
+    use qtty::Second;
+
+    fn axis() -> TickAxis<Second> {
+        TickAxis::new(Quantity::new(0.1))
+    }
+
+    #[test]
+    fn conversions_snap_conservatively() {
+        let axis = axis().with_origin(Quantity::new(10.0));
+        assert_eq!(axis.to_ticks(Quantity::new(10.3)).unwrap().value(), 3.0);
+        assert_eq!(axis.to_ticks(Quantity::new(10.26)).unwrap().value(), 3.0);
+        assert_eq!(
+            axis.duration_to_ticks(Quantity::new(0.3)).unwrap().value(),
+            3.0
+        );
+        assert_eq!(
+            axis.duration_to_ticks(Quantity::new(0.31)).unwrap().value(),
+            4.0
+        );
+
+        let w = axis.window_to_ticks(iv(10.05, 10.95)).unwrap().unwrap();
+        assert_eq!(w.tick_bounds(), (1, 9));
+        assert!(axis.window_to_ticks(iv(10.01, 10.09)).unwrap().is_none());
+        assert_eq!(axis.interval_from_ticks(w), iv(10.1, 10.9));
+    }
+
+    #[test]
+    fn rejects_values_off_the_axis() {
+        assert_eq!(
+            axis()
+                .to_ticks(Quantity::new(f64::NAN))
+                .map_err(|e| e.to_string()),
+            Err("value NaN is not finite".into())
+        );
+        assert!(matches!(
+            axis().to_ticks(Quantity::new(1e18)),
+            Err(TickError::OutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn long_chains_stay_exact() {
+        let mut float = Quantity::<Second>::new(0.0);
+        let mut ticks = Ticks::new(0.0);
+        let step = axis().duration_to_ticks(Quantity::new(0.1)).unwrap();
+        for _ in 0..10_000 {
+            float += Quantity::new(0.1);
+            ticks += step;
+        }
+        assert_ne!(float.value(), 1000.0);
+        assert_eq!(ticks.value(), 10_000.0);
+        assert_eq!(axis().from_ticks(ticks).value(), 1000.0);
+    }
+
+    #[test]
+    fn est_schedules_on_the_tick_axis() {
+        let axis = axis();
+        let mut block: SchedulingBlock<SyntheticTask<Tick>, Tick> = SchedulingBlock::new();
+        for (i, size) in [0.25, 0.1, 0.3].into_iter().enumerate() {
+            let task = SyntheticTask {
+                name: format!("t{i}"),
+                size: axis.duration_to_ticks(Quantity::new(size)).unwrap(),
+                priority: 0,
+                constraints: None,
+            };
+            block.add_task_with_id(task, Some(format!("t{i}"))).unwrap();
+        }
+        let mut space = SolutionSpace::<Second>::new();
+        for i in 0..3 {
+            space.set_intervals(format!("t{i}"), vec![iv(0.05, 100.0)]);
+        }
+        let space = axis.solution_space_to_ticks(&space).unwrap();
+        let horizon = axis.window_to_ticks(iv(0.0, 100.0)).unwrap().unwrap();
+
+        let schedule = ESTScheduler::default().schedule(&[block], &space, horizon);
+        assert_eq!(schedule.len(), 3);
+        for (_, placed) in schedule.iter() {
+            let (start, end) = placed.tick_bounds();
+            assert_eq!(placed, Interval::ticks(start, end));
+        }
+        let seconds = axis.schedule_from_ticks(&schedule);
+        assert!(seconds.iter().all(|(_, i)| i.start().value() >= 0.1));
+        assert_eq!(seconds.len(), 3);
+    }
+}
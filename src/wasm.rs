@@ -0,0 +1,88 @@
+//! Browser bindings (`wasm` feature).
+//!
+//! Build for the web with `wasm-pack build --target web --features wasm`.
+//! The package exports one function, `schedule(problem)`, which takes a
+//! [`ProblemDocument`] as a JSON string — a block document of
+//! [`TaskSpec`]s and [`DynConstraintKind`] edges plus a `"horizon"` — and
+//! returns the schedule as a JSON string in the
+//! [`Schedule`](crate::schedule::Schedule) format:
+//!
+//! ```js
+//! import init, { schedule } from "./pkg/virolai.js";
+//!
+//! await init();
+//! const placements = JSON.parse(schedule(JSON.stringify(problem)));
+//! ```
+//!
+//! Tasks are placed by [`ESTScheduler::schedule_layered`], so
+//! `dependence` and `consecutive` edges order them. Numbers are read on the
+//! axis as they are; the bindings use seconds as its unit. Malformed
+//! problems throw an `Error` with the [`ImportError`] message.
+
+use qtty::Second;
+use wasm_bindgen::prelude::*;
+
+use crate::algorithms::ESTScheduler;
+use crate::constraints::DynConstraintKind;
+use crate::scheduling_block::import::{ImportError, ProblemDocument, TaskSpec};
+use crate::scheduling_block::SchedulingBlock;
+use crate::solution_space::SolutionSpace;
+
+type Problem = ProblemDocument<Second, TaskSpec<Second>, DynConstraintKind>;
+
+/// Schedules the JSON `problem` and returns the schedule as JSON.
+#[wasm_bindgen]
+pub fn schedule(problem: &str) -> Result<String, JsError> {
+    schedule_json(problem).map_err(|e| JsError::new(&e.to_string()))
+}
+
+fn schedule_json(problem: &str) -> Result<String, ImportError> {
+    let problem = Problem::from_json(problem)?;
+    let horizon = problem.horizon;
+    let block: SchedulingBlock<_, Second, _> = problem.into_block()?;
+    let blocks = [block];
+    let solution_space = SolutionSpace::try_populate(&blocks, horizon)?;
+    let schedule = ESTScheduler::default().schedule_layered(&blocks, &solution_space, horizon)?;
+    Ok(serde_json::to_string(&schedule)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn schedules_a_problem_round_trip() {
+        let problem = json!({
+            "version": 1,
+            "horizon": { "start": 0.0, "end": 100.0 },
+            "tasks": [
+                { "id": "obs", "name": "science", "size": 30.0, "priority": 5 },
+                { "id": "cal", "name": "calib", "size": 10.0,
+                  "constraints": { "start": 0.0, "end": 50.0 } }
+            ],
+            "edges": [ { "from": "cal", "to": "obs", "kind": "dependence" } ]
+        });
+        let out: Value =
+            serde_json::from_str(&schedule_json(&problem.to_string()).unwrap()).unwrap();
+        assert_eq!(
+            out,
+            json!([
+                { "task": "cal", "interval": { "start": 0.0, "end": 10.0 } },
+                { "task": "obs", "interval": { "start": 10.0, "end": 40.0 } }
+            ])
+        );
+    }
+
+    #[test]
+    fn malformed_problems_are_errors() {
+        let reversed = r#"{ "version": 1, "horizon": { "start": 9.0, "end": 1.0 }, "tasks": [] }"#;
+        assert!(matches!(schedule_json(reversed), Err(ImportError::Json(_))));
+        assert!(matches!(
+            schedule_json(
+                r#"{ "version": 7, "horizon": { "start": 0.0, "end": 1.0 }, "tasks": [] }"#
+            ),
+            Err(ImportError::UnsupportedVersion(7))
+        ));
+    }
+}